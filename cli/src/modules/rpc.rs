@@ -83,12 +83,13 @@ impl Rpc {
             // }
             RpcApiOps::GetMempoolEntries => {
                 // TODO
-                let result = rpc
-                    .get_mempool_entries_call(
-                        None,
-                        GetMempoolEntriesRequest { include_orphan_pool: true, filter_transaction_pool: true },
-                    )
-                    .await?;
+                let mut request = GetMempoolEntriesRequest::new(true, true);
+                if !argv.is_empty() {
+                    let limit = argv.remove(0).parse::<u32>()?;
+                    let cursor = if argv.is_empty() { None } else { Some(argv.remove(0)) };
+                    request = request.with_page(cursor, limit).with_order(RpcMempoolEntriesOrder::Feerate).with_compact(true);
+                }
+                let result = rpc.get_mempool_entries_call(None, request).await?;
                 self.println(&ctx, result);
             }
             RpcApiOps::GetConnectedPeerInfo => {
@@ -142,6 +143,16 @@ impl Rpc {
                     .await?;
                 self.println(&ctx, result);
             }
+            RpcApiOps::GetVirtualChainChunk => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Missing startHash argument"));
+                };
+                let start_hash = RpcHash::from_hex(argv.remove(0).as_str())?;
+                let max_chain_blocks = if argv.is_empty() { 10 } else { argv.remove(0).parse::<u32>()? };
+                let result =
+                    rpc.get_virtual_chain_chunk_call(None, GetVirtualChainChunkRequest { start_hash, max_chain_blocks }).await?;
+                self.println(&ctx, result);
+            }
             // RpcApiOps::GetBlocks => {
             //     let result = rpc.get_blocks_call(GetBlocksRequest {  }).await?;
             //     self.println(&ctx, result);
@@ -266,6 +277,11 @@ impl Rpc {
                 let result = rpc.get_fee_estimate_experimental_call(None, GetFeeEstimateExperimentalRequest { verbose }).await?;
                 self.println(&ctx, result);
             }
+            RpcApiOps::GetFeeEstimateHistory => {
+                let max_blocks = if argv.is_empty() { 100 } else { argv.remove(0).parse::<u32>()? };
+                let result = rpc.get_fee_estimate_history_call(None, GetFeeEstimateHistoryRequest { max_blocks }).await?;
+                self.println(&ctx, result);
+            }
             RpcApiOps::GetCurrentBlockColor => {
                 if argv.is_empty() {
                     return Err(Error::custom("Missing block hash argument"));
@@ -290,6 +306,34 @@ impl Rpc {
 
                 self.println(&ctx, result);
             }
+            RpcApiOps::GetTransaction => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Missing transaction id argument"));
+                }
+                let transaction_id = argv.remove(0);
+                let transaction_id = RpcHash::from_hex(transaction_id.as_str())?;
+                let result = rpc.get_transaction_call(None, GetTransactionRequest { transaction_id }).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetTransactionStatus => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Missing transaction id argument"));
+                }
+                let transaction_id = argv.remove(0);
+                let transaction_id = RpcHash::from_hex(transaction_id.as_str())?;
+                let result = rpc.get_transaction_status_call(None, GetTransactionStatusRequest { transaction_id }).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetAddressHistory => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Missing address argument"));
+                }
+                let address = Address::try_from(argv.remove(0).as_str())?;
+                let limit = if argv.is_empty() { 100 } else { argv.remove(0).parse::<u32>()? };
+                let cursor = if argv.is_empty() { None } else { Some(argv.remove(0)) };
+                let result = rpc.get_address_history_call(None, GetAddressHistoryRequest { address, cursor, limit }).await?;
+                self.println(&ctx, result);
+            }
             _ => {
                 tprintln!(ctx, "rpc method exists but is not supported by the cli: '{op_str}'\r\n");
                 return Ok(());
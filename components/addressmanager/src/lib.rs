@@ -16,7 +16,7 @@ use itertools::{
 use vecno_consensus_core::config::Config;
 use vecno_core::{debug, info, task::tick::TickService, time::unix_now, warn};
 use vecno_database::prelude::{CachePolicy, StoreResultExtensions, DB};
-use vecno_utils::networking::IpAddress;
+use vecno_utils::networking::{IpAddress, OnionAddress, PeerAddress};
 use local_ip_address::list_afinet_netifas;
 use parking_lot::Mutex;
 use stores::banned_address_store::{BannedAddressesStore, BannedAddressesStoreReader, ConnectionBanTimestamp, DbBannedAddressesStore};
@@ -241,13 +241,17 @@ impl AddressManager {
         }
     }
 
-    pub fn best_local_address(&mut self) -> Option<NetAddress> {
+    pub fn best_local_address(&mut self) -> Option<PeerAddress> {
+        // A published onion address is advertised in place of the IP addresses, so that the node does not expose them
+        if let Some(onion_address) = self.config.onion_address {
+            return Some(onion_address.into());
+        }
         if self.local_net_addresses.is_empty() {
             None
         } else {
             // TODO: Add logic for finding the best as a function of a peer remote address.
             // for now, returning the first one
-            Some(self.local_net_addresses[0])
+            Some(self.local_net_addresses[0].into())
         }
     }
 
@@ -257,6 +261,12 @@ impl AddressManager {
             return;
         }
 
+        // Onion addresses can only be added along with their public key, see `add_onion_address`
+        if address.ip.is_onion() {
+            debug!("[Address manager] skipping onion address {} without public key", address.ip);
+            return;
+        }
+
         if self.address_store.has(address) {
             return;
         }
@@ -265,6 +275,27 @@ impl AddressManager {
         self.address_store.set(address, 1);
     }
 
+    /// Adds an onion service address, stored under the address standing for it (see [`OnionAddress::net_address`])
+    pub fn add_onion_address(&mut self, address: OnionAddress) {
+        if self.config.onion_address == Some(address) || self.address_store.has(address.net_address()) {
+            return;
+        }
+
+        self.address_store.set_onion(address, 1);
+    }
+
+    pub fn add_peer_address(&mut self, address: PeerAddress) {
+        match address {
+            PeerAddress::Ip(address) => self.add_address(address),
+            PeerAddress::Onion(address) => self.add_onion_address(address),
+        }
+    }
+
+    /// Returns the onion address the given address stands for, if any
+    pub fn onion_address(&self, address: NetAddress) -> Option<OnionAddress> {
+        self.address_store.get_onion(address)
+    }
+
     pub fn mark_connection_failure(&mut self, address: NetAddress) {
         if !self.address_store.has(address) {
             return;
@@ -290,10 +321,23 @@ impl AddressManager {
         self.address_store.iterate_addresses()
     }
 
-    pub fn iterate_prioritized_random_addresses(&self, exceptions: HashSet<NetAddress>) -> impl ExactSizeIterator<Item = NetAddress> {
+    /// Iterates the addresses, resolving the ones standing for onion addresses
+    pub fn iterate_peer_addresses(&self) -> impl Iterator<Item = PeerAddress> + '_ {
+        self.address_store.iterate_peer_addresses()
+    }
+
+    pub fn iterate_prioritized_random_addresses(
+        &self,
+        mut exceptions: HashSet<NetAddress>,
+    ) -> impl ExactSizeIterator<Item = NetAddress> {
+        // Onion addresses are reachable through a proxy only
+        if self.config.proxy.is_none() {
+            exceptions.extend(self.address_store.iterate_addresses().filter(|address| address.ip.is_onion()));
+        }
         self.address_store.iterate_prioritized_random_addresses(exceptions)
     }
 
+    /// Bans the given IP until the ban duration of the config has elapsed
     pub fn ban(&mut self, ip: IpAddress) {
         self.banned_address_store.set(ip.into(), ConnectionBanTimestamp(unix_now())).unwrap();
         self.address_store.remove_by_ip(ip.into());
@@ -304,10 +348,9 @@ impl AddressManager {
     }
 
     pub fn is_banned(&mut self, ip: IpAddress) -> bool {
-        const MAX_BANNED_TIME: u64 = 24 * 60 * 60 * 1000;
         match self.banned_address_store.get(ip.into()).unwrap_option() {
             Some(timestamp) => {
-                if unix_now() - timestamp.0 > MAX_BANNED_TIME {
+                if unix_now().saturating_sub(timestamp.0) >= self.config.ban_duration.as_millis() as u64 {
                     self.unban(ip);
                     false
                 } else {
@@ -338,7 +381,7 @@ mod address_store_with_cache {
 
     use itertools::Itertools;
     use vecno_database::prelude::{CachePolicy, DB};
-    use vecno_utils::networking::PrefixBucket;
+    use vecno_utils::networking::{OnionAddress, PeerAddress, PrefixBucket};
     use rand::{
         distributions::{WeightedError, WeightedIndex},
         prelude::Distribution,
@@ -347,6 +390,7 @@ mod address_store_with_cache {
     use crate::{
         stores::{
             address_store::{AddressesStore, DbAddressesStore, Entry},
+            onion_address_store::{DbOnionAddressesStore, OnionAddressesStore, OnionPublicKey},
             AddressKey,
         },
         NetAddress, MAX_ADDRESSES, MAX_CONNECTION_FAILED_COUNT,
@@ -355,18 +399,31 @@ mod address_store_with_cache {
     pub struct Store {
         db_store: DbAddressesStore,
         addresses: HashMap<AddressKey, Entry>,
+        onion_db_store: DbOnionAddressesStore,
+        /// Public keys of the onion services some of the addresses stand for
+        onion_public_keys: HashMap<AddressKey, OnionPublicKey>,
     }
 
     impl Store {
         fn new(db: Arc<DB>) -> Self {
             // We manage the cache ourselves on this level, so we disable the inner builtin cache
-            let db_store = DbAddressesStore::new(db, CachePolicy::Empty);
+            let db_store = DbAddressesStore::new(db.clone(), CachePolicy::Empty);
             let mut addresses = HashMap::new();
             for (key, entry) in db_store.iterator().map(|res| res.unwrap()) {
                 addresses.insert(key, entry);
             }
 
-            Self { db_store, addresses }
+            let mut onion_db_store = DbOnionAddressesStore::new(db, CachePolicy::Empty);
+            let mut onion_public_keys = HashMap::new();
+            for (key, public_key) in onion_db_store.iterator().map(|res| res.unwrap()).collect_vec() {
+                if addresses.contains_key(&key) {
+                    onion_public_keys.insert(key, public_key);
+                } else {
+                    onion_db_store.remove(key).unwrap();
+                }
+            }
+
+            Self { db_store, addresses, onion_db_store, onion_public_keys }
         }
 
         pub fn has(&mut self, address: NetAddress) -> bool {
@@ -383,6 +440,17 @@ mod address_store_with_cache {
             self.keep_limit();
         }
 
+        pub fn set_onion(&mut self, address: OnionAddress, connection_failed_count: u64) {
+            let public_key = OnionPublicKey(*address.public_key());
+            self.onion_db_store.set(address.net_address().into(), public_key).unwrap();
+            self.onion_public_keys.insert(address.net_address().into(), public_key);
+            self.set(address.net_address(), connection_failed_count);
+        }
+
+        pub fn get_onion(&self, address: NetAddress) -> Option<OnionAddress> {
+            self.onion_public_keys.get(&address.into()).map(|public_key| OnionAddress::new(public_key.0, address.port))
+        }
+
         fn keep_limit(&mut self) {
             while self.addresses.len() > MAX_ADDRESSES {
                 let to_remove =
@@ -401,13 +469,23 @@ mod address_store_with_cache {
 
         fn remove_by_key(&mut self, key: AddressKey) {
             self.addresses.remove(&key);
-            self.db_store.remove(key).unwrap()
+            self.db_store.remove(key).unwrap();
+            if self.onion_public_keys.remove(&key).is_some() {
+                self.onion_db_store.remove(key).unwrap();
+            }
         }
 
         pub fn iterate_addresses(&self) -> impl Iterator<Item = NetAddress> + '_ {
             self.addresses.values().map(|entry| entry.address)
         }
 
+        pub fn iterate_peer_addresses(&self) -> impl Iterator<Item = PeerAddress> + '_ {
+            self.addresses.iter().map(|(key, entry)| match self.onion_public_keys.get(key) {
+                Some(public_key) => OnionAddress::new(public_key.0, entry.address.port).into(),
+                None => entry.address.into(),
+            })
+        }
+
         /// This iterator functions as the node's ip routing selection algo.
         /// It first adjusts in respect to the number of connection failures of each ip address,
         /// whereby each connection failure (up to [`MAX_CONNECTION_FAILED_COUNT`]) reduces an ip's selection weight by a factor of 64,
@@ -514,14 +592,17 @@ mod address_store_with_cache {
         use std::str::FromStr;
 
         use super::*;
-        use address_manager::AddressManager;
+        use address_manager::{AddressManager, BannedAddressesStore, ConnectionBanTimestamp};
         use vecno_consensus_core::config::{params::SIMNET_PARAMS, Config};
-        use vecno_core::task::tick::TickService;
+        use vecno_core::{task::tick::TickService, time::unix_now};
         use vecno_database::create_temp_db;
         use vecno_database::prelude::ConnBuilder;
         use vecno_utils::networking::IpAddress;
         use rv::{dist::Uniform, misc::ks_test as one_way_ks_test, traits::Cdf};
-        use std::net::{IpAddr, Ipv6Addr};
+        use std::{
+            net::{IpAddr, Ipv6Addr},
+            time::Duration,
+        };
 
         #[test]
         fn test_weighted_iterator() {
@@ -535,6 +616,71 @@ mod address_store_with_cache {
             assert_eq!(iter.count(), 0);
         }
 
+        #[test]
+        fn test_onion_addresses() {
+            let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+            let onion = OnionAddress::from_str("2gzyxa5ihm7nsggfxnu52rck2vv4rvmdlkiu3zzui5du4xyclen53wid.onion:7111").unwrap();
+            let ip = NetAddress::from_str("1.2.3.4:7111").unwrap();
+            let config = Config { disable_upnp: true, ..Config::new(SIMNET_PARAMS) };
+            let (am, _) = AddressManager::new(Arc::new(config.clone()), db.clone(), Arc::new(TickService::default()));
+            let mut am = am.lock();
+
+            // Onion addresses are stored under the address standing for them, which cannot be added on its own
+            am.add_address(onion.net_address());
+            assert!(am.get_all_addresses().is_empty());
+            am.add_peer_address(onion.into());
+            am.add_peer_address(ip.into());
+            assert_eq!(am.onion_address(onion.net_address()), Some(onion));
+            assert_eq!(am.onion_address(ip), None);
+            assert_eq!(am.iterate_peer_addresses().collect::<HashSet<_>>(), HashSet::from([PeerAddress::from(onion), ip.into()]));
+
+            // Onion addresses are not connected to without a proxy
+            assert_eq!(am.iterate_prioritized_random_addresses(HashSet::new()).collect_vec(), vec![ip]);
+            drop(am);
+            let config = Config { proxy: Some(NetAddress::from_str("127.0.0.1:9050").unwrap()), ..config };
+            let (am, _) = AddressManager::new(Arc::new(config), db, Arc::new(TickService::default()));
+            let mut am = am.lock();
+            assert_eq!(am.iterate_prioritized_random_addresses(HashSet::new()).len(), 2);
+
+            // The public key is dropped along with the address
+            for _ in 0..=MAX_CONNECTION_FAILED_COUNT {
+                am.mark_connection_failure(onion.net_address());
+            }
+            assert_eq!(am.onion_address(onion.net_address()), None);
+            assert_eq!(am.iterate_peer_addresses().collect_vec(), vec![PeerAddress::from(ip)]);
+        }
+
+        #[test]
+        fn test_ban_expiry() {
+            let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+            let address = NetAddress::from_str("1.2.3.4:7111").unwrap();
+            let stale = IpAddress::from_str("5.6.7.8").unwrap();
+            let config = Config { disable_upnp: true, ..Config::new(SIMNET_PARAMS) };
+            let (am, _) = AddressManager::new(Arc::new(config.clone()), db.clone(), Arc::new(TickService::default()));
+            let mut am = am.lock();
+
+            // A banned IP loses its addresses and stays banned until the ban duration has elapsed
+            am.add_address(address);
+            am.ban(address.ip);
+            assert!(am.is_banned(address.ip));
+            assert!(am.get_all_addresses().is_empty());
+
+            // Bans are stored by the time they were issued at, so the ones stored by earlier versions expire as before
+            let issued = unix_now() - config.ban_duration.as_millis() as u64 - 1000;
+            am.banned_address_store.set(stale.into(), ConnectionBanTimestamp(issued)).unwrap();
+            assert_eq!(am.get_all_banned_addresses().len(), 2);
+            assert!(!am.is_banned(stale));
+            assert_eq!(am.get_all_banned_addresses(), vec![address.ip]);
+            drop(am);
+
+            // The ban expires once the configured duration has elapsed, and is then removed
+            let config = Config { ban_duration: Duration::ZERO, ..config };
+            let (am, _) = AddressManager::new(Arc::new(config), db, Arc::new(TickService::default()));
+            let mut am = am.lock();
+            assert!(!am.is_banned(address.ip));
+            assert!(am.get_all_banned_addresses().is_empty());
+        }
+
         #[test]
         fn test_network_distribution_weighting() {
             vecno_core::log::try_init_logger("info");
@@ -38,7 +38,7 @@ pub const ADDRESS_KEY_SIZE: usize = IPV6_LEN + PORT_LEN;
 
 // TODO: This pattern is used a lot. Think of some macro or any other way to generalize it.
 #[derive(Eq, Hash, PartialEq, Debug, Copy, Clone)]
-struct DbAddressKey([u8; ADDRESS_KEY_SIZE]);
+pub(super) struct DbAddressKey(pub(super) [u8; ADDRESS_KEY_SIZE]);
 
 impl AsRef<[u8]> for DbAddressKey {
     fn as_ref(&self) -> &[u8] {
@@ -0,0 +1,72 @@
+use vecno_database::{
+    prelude::DB,
+    prelude::{CachePolicy, StoreError, StoreResult},
+    prelude::{CachedDbAccess, DirectDbWriter},
+    registry::DatabaseStorePrefixes,
+};
+use vecno_utils::mem_size::MemSizeEstimator;
+use serde::{Deserialize, Serialize};
+use std::{error::Error, sync::Arc};
+
+use super::{
+    address_store::{DbAddressKey, ADDRESS_KEY_SIZE},
+    AddressKey,
+};
+
+/// Public key of the onion service an address of the address store stands for
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct OnionPublicKey(pub [u8; 32]);
+
+impl MemSizeEstimator for OnionPublicKey {}
+
+pub trait OnionAddressesStoreReader {
+    #[allow(dead_code)]
+    fn get(&self, key: AddressKey) -> Result<OnionPublicKey, StoreError>;
+}
+
+pub trait OnionAddressesStore: OnionAddressesStoreReader {
+    fn set(&mut self, key: AddressKey, public_key: OnionPublicKey) -> StoreResult<()>;
+    fn remove(&mut self, key: AddressKey) -> StoreResult<()>;
+}
+
+#[derive(Clone)]
+pub struct DbOnionAddressesStore {
+    db: Arc<DB>,
+    access: CachedDbAccess<DbAddressKey, OnionPublicKey>,
+}
+
+impl DbOnionAddressesStore {
+    pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
+        Self { db: Arc::clone(&db), access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::OnionAddresses.into()) }
+    }
+
+    pub fn iterator(&self) -> impl Iterator<Item = Result<(AddressKey, OnionPublicKey), Box<dyn Error>>> + '_ {
+        self.access.iterator().map(|iter_result| match iter_result {
+            Ok((key_bytes, public_key)) => match <[u8; ADDRESS_KEY_SIZE]>::try_from(&key_bytes[..]) {
+                Ok(address_key_slice) => {
+                    let addr_key = DbAddressKey(address_key_slice);
+                    let address: AddressKey = addr_key.into();
+                    Ok((address, public_key))
+                }
+                Err(e) => Err(e.into()),
+            },
+            Err(e) => Err(e),
+        })
+    }
+}
+
+impl OnionAddressesStoreReader for DbOnionAddressesStore {
+    fn get(&self, key: AddressKey) -> Result<OnionPublicKey, StoreError> {
+        self.access.read(key.into())
+    }
+}
+
+impl OnionAddressesStore for DbOnionAddressesStore {
+    fn set(&mut self, key: AddressKey, public_key: OnionPublicKey) -> StoreResult<()> {
+        self.access.write(DirectDbWriter::new(&self.db), key.into(), public_key)
+    }
+
+    fn remove(&mut self, key: AddressKey) -> StoreResult<()> {
+        self.access.delete(DirectDbWriter::new(&self.db), key.into())
+    }
+}
@@ -131,7 +131,7 @@ impl ConnectionManager {
 
             if !is_connected && request.next_attempt <= SystemTime::now() {
                 debug!("Connecting to peer request {}", address);
-                match self.p2p_adaptor.connect_peer(address.to_string()).await {
+                match self.p2p_adaptor.connect_peer(self.dial_address(address)).await {
                     Err(err) => {
                         debug!("Failed connecting to peer request: {}, {}", address, err);
                         if request.is_permanent {
@@ -186,10 +186,10 @@ impl ConnectionManager {
                     connecting = false;
                     break;
                 };
-                let socket_addr = SocketAddr::new(net_addr.ip.into(), net_addr.port).to_string();
-                debug!("Connecting to {}", &socket_addr);
+                let dial_addr = self.dial_address(net_addr.into());
+                debug!("Connecting to {}", &dial_addr);
                 addrs_to_connect.push(net_addr);
-                jobs.push(self.p2p_adaptor.connect_peer(socket_addr.clone()));
+                jobs.push(self.p2p_adaptor.connect_peer(dial_addr));
             }
 
             if progressing && !jobs.is_empty() {
@@ -231,6 +231,14 @@ impl ConnectionManager {
         }
     }
 
+    /// Returns the address to connect to for the given address, i.e. the onion address it stands for if any
+    fn dial_address(&self, address: SocketAddr) -> String {
+        match self.address_manager.lock().onion_address(address.into()) {
+            Some(onion_address) => onion_address.to_string(),
+            None => address.to_string(),
+        }
+    }
+
     async fn handle_inbound_connections(self: &Arc<Self>, peer_by_address: &HashMap<SocketAddr, Peer>) {
         let active_inbound = peer_by_address.values().filter(|peer| !peer.is_outbound()).collect_vec();
         let active_inbound_len = active_inbound.len();
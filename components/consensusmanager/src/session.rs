@@ -324,6 +324,13 @@ impl ConsensusSessionOwned {
         self.clone().spawn_blocking(move |c| c.get_populated_transaction(txid, accepting_block_daa_score)).await
     }
 
+    pub async fn async_get_populated_accepted_transactions(
+        &self,
+        chain_block: Hash,
+    ) -> Result<Vec<SignableTransaction>, UtxoInquirerError> {
+        self.clone().spawn_blocking(move |c| c.get_populated_accepted_transactions(chain_block)).await
+    }
+
     /// Returns the antipast of block `hash` from the POV of `context`, i.e. `antipast(hash) ∩ past(context)`.
     /// Since this might be an expensive operation for deep blocks, we allow the caller to specify a limit
     /// `max_traversal_allowed` on the maximum amount of blocks to traverse for obtaining the answer
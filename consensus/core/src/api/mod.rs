@@ -180,6 +180,11 @@ pub trait ConsensusApi: Send + Sync {
         unimplemented!()
     }
 
+    /// Returns the fully populated transactions accepted by the selected chain block `chain_block`, in acceptance data order.
+    fn get_populated_accepted_transactions(&self, chain_block: Hash) -> Result<Vec<SignableTransaction>, UtxoInquirerError> {
+        unimplemented!()
+    }
+
     fn get_virtual_parents(&self) -> BlockHashSet {
         unimplemented!()
     }
@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 use workflow_serializer::prelude::*;
 
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct BlockCount {
     pub header_count: u64,
@@ -3,14 +3,14 @@ pub mod constants;
 pub mod genesis;
 pub mod params;
 
-use vecno_utils::networking::{ContextualNetAddress, NetAddress};
+use vecno_utils::networking::{ContextualNetAddress, NetAddress, OnionAddress};
 
 #[cfg(feature = "devnet-prealloc")]
 use crate::utxo::utxo_collection::UtxoCollection;
 #[cfg(feature = "devnet-prealloc")]
 use std::sync::Arc;
 
-use std::ops::Deref;
+use std::{ops::Deref, time::Duration};
 
 use {
     constants::perf::{PerfParams, PERF_PARAMS},
@@ -42,6 +42,12 @@ pub struct Config {
     /// Enable the UTXO index
     pub utxoindex: bool,
 
+    /// Enable the transaction index
+    pub txindex: bool,
+
+    /// Enable the address history index
+    pub addressindex: bool,
+
     /// Enable RPC commands which affect the state of the node
     pub unsafe_rpc: bool,
 
@@ -66,6 +72,21 @@ pub struct Config {
 
     pub disable_upnp: bool,
 
+    /// Whether transactions go through a stem phase, being relayed to a single peer before getting diffused to all
+    pub tx_stem_relay: bool,
+
+    /// The misbehavior score at which a peer gets banned
+    pub ban_threshold: u32,
+
+    /// How long misbehaving peers are banned for
+    pub ban_duration: Duration,
+
+    /// The SOCKS5 proxy outbound P2P connections are routed through, required to connect to onion addresses
+    pub proxy: Option<NetAddress>,
+
+    /// The onion address of the node, advertised to peers in place of its IP addresses
+    pub onion_address: Option<OnionAddress>,
+
     /// A scale factor to apply to memory allocation bounds
     pub ram_scale: f64,
 
@@ -86,6 +107,8 @@ impl Config {
             is_archival: false,
             enable_sanity_checks: false,
             utxoindex: false,
+            txindex: false,
+            addressindex: false,
             unsafe_rpc: false,
             enable_unsynced_mining: false,
             enable_mainnet_mining: false,
@@ -97,6 +120,11 @@ impl Config {
             #[cfg(feature = "devnet-prealloc")]
             initial_utxo_set: Default::default(),
             disable_upnp: false,
+            tx_stem_relay: false,
+            ban_threshold: 100,
+            ban_duration: Duration::from_secs(24 * 60 * 60),
+            proxy: None,
+            onion_address: None,
             ram_scale: 1.0,
             retention_period_days: None,
         }
@@ -17,6 +17,51 @@ pub enum ConfigError {
     #[error("Configuration: --max-tracked-addresses cannot be set above {0}")]
     MaxTrackedAddressesTooHigh(usize),
 
+    #[error("Configuration: --mine requires a --miningaddr")]
+    MissingMiningAddress,
+
+    #[error("Configuration: --miningaddr is invalid: {0}")]
+    InvalidMiningAddress(String),
+
+    #[error("Configuration: --mining-threads cannot be set to 0")]
+    MiningThreadsZero,
+
+    #[error("Configuration: --stratumlisten requires a --stratum-payaddr")]
+    MissingStratumPayAddress,
+
+    #[error("Configuration: --stratum-payaddr is invalid: {0}")]
+    InvalidStratumPayAddress(String),
+
+    #[error("Configuration: --stratum-difficulty must be a positive number")]
+    InvalidStratumDifficulty,
+
+    #[error("Configuration: [mempool-policy] is invalid: {0}")]
+    InvalidMempoolPolicy(String),
+
+    #[error("Configuration: --rpc-tls-cert and --rpc-tls-key must be set together")]
+    MissingRpcTlsCertOrKey,
+
+    #[error("Configuration: the RPC TLS certificate or key cannot be read: {0}")]
+    InvalidRpcTlsIdentity(String),
+
+    #[error("Configuration: [[rpc-credentials]] entries must have a non-empty token and at least one permission")]
+    InvalidRpcCredential,
+
+    #[error("Configuration: --p2p-allow-peer {0} is not a valid identity key")]
+    InvalidP2pAllowedPeer(String),
+
+    #[error("Configuration: the P2P node identity {0} cannot be loaded or created: {1}")]
+    InvalidP2pIdentity(String, String),
+
+    #[error("Configuration: --banthreshold cannot be set to 0")]
+    BanThresholdZero,
+
+    #[error("Configuration: --banduration cannot be set to 0")]
+    BanDurationZero,
+
+    #[error("Configuration: --onion-address {0} is invalid: {1}")]
+    InvalidOnionAddress(String, String),
+
     #[cfg(feature = "devnet-prealloc")]
     #[error("Cannot preallocate UTXOs on any network except devnet")]
     PreallocUtxosOnNonDevnet,
@@ -25,6 +25,7 @@ pub enum NetworkTypeError {
 
 /// @category Consensus
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq, Eq, Hash, Ord, PartialOrd)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 #[wasm_bindgen]
 pub enum NetworkType {
@@ -58,6 +59,14 @@ impl NetworkType {
         }
     }
 
+    pub fn default_http_rpc_port(&self) -> u16 {
+        match self {
+            NetworkType::Mainnet => 10110,
+            NetworkType::Testnet => 10210,
+            NetworkType::Simnet => 10310,
+        }
+    }
+
     pub fn iter() -> impl Iterator<Item = Self> {
         static NETWORK_TYPES: [NetworkType; 3] =
             [NetworkType::Mainnet, NetworkType::Testnet, NetworkType::Simnet];
@@ -261,6 +270,12 @@ impl Display for NetworkId {
     }
 }
 
+#[cfg(feature = "schemars")]
+vecno_utils::json_schema_impl_display_string!(
+    NetworkId,
+    "Network type optionally suffixed by a testnet suffix, e.g. `mainnet` or `testnet-11`"
+);
+
 impl Serialize for NetworkId {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -21,6 +21,8 @@ impl Debug for SubnetworkId {
 
 serde_impl_ser_fixed_bytes_ref!(SubnetworkId, SUBNETWORK_ID_SIZE);
 serde_impl_deser_fixed_bytes_ref!(SubnetworkId, SUBNETWORK_ID_SIZE);
+#[cfg(feature = "schemars")]
+vecno_utils::json_schema_impl_hex_string!(SubnetworkId, Some(SUBNETWORK_ID_SIZE));
 
 impl AsRef<[u8; SUBNETWORK_ID_SIZE]> for SubnetworkId {
     fn as_ref(&self) -> &[u8; SUBNETWORK_ID_SIZE] {
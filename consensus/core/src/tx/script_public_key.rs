@@ -78,6 +78,10 @@ struct ScriptPublicKeyInternal<'a> {
     script: &'a [u8],
 }
 
+// Serialized as the big endian version followed by the script, hex encoded
+#[cfg(feature = "schemars")]
+vecno_utils::json_schema_impl_hex_string!(ScriptPublicKey, None);
+
 impl Serialize for ScriptPublicKey {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -25,6 +25,8 @@ pub enum UtxoInquirerError {
     MissingUtxoDiffForChainBlock(Hash),
     #[error("Transaction {0} acceptance data must also be in the same block in this case")]
     MissingOtherTransactionAcceptanceData(Hash),
+    #[error("Did not find output index {0} in transaction {1}")]
+    MissingOutputIndexOfTransaction(u32, Hash),
     #[error("Did not find index for hash {0}")]
     MissingIndexForHash(Hash),
     #[error("Did not find tip data")]
@@ -7,7 +7,10 @@ use vecno_notify::{
     notification::Notification as NotificationTrait,
     subscription::{
         context::SubscriptionContext,
-        single::{OverallSubscription, UtxosChangedSubscription, VirtualChainChangedSubscription},
+        single::{
+            BlockAddedSubscription, OverallSubscription, TransactionStatusChangedSubscription, UtxosChangedSubscription,
+            VirtualChainChangedSubscription,
+        },
         Subscription,
     },
 };
@@ -53,6 +56,13 @@ impl NotificationTrait for Notification {
         }
     }
 
+    fn apply_block_added_subscription(&self, subscription: &BlockAddedSubscription, _context: &SubscriptionContext) -> Option<Self> {
+        match subscription.active() {
+            true => Some(self.clone()),
+            false => None,
+        }
+    }
+
     fn apply_virtual_chain_changed_subscription(
         &self,
         subscription: &VirtualChainChangedSubscription,
@@ -87,6 +97,14 @@ impl NotificationTrait for Notification {
         Some(self.clone())
     }
 
+    fn apply_transaction_status_changed_subscription(
+        &self,
+        _subscription: &TransactionStatusChangedSubscription,
+        _context: &SubscriptionContext,
+    ) -> Option<Self> {
+        Some(self.clone())
+    }
+
     fn event_type(&self) -> EventType {
         self.into()
     }
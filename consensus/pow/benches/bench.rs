@@ -1,7 +1,7 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
-use vecno_hashes::Hash;
-use vecno_pow::{matrix::Matrix, xoshiro::XoShiRo256PlusPlus};
+use vecno_hashes::{Hash, PowHash};
+use vecno_pow::{mem_hash::mem_hash, mem_hash_batch, xoshiro::XoShiRo256PlusPlus};
 
 // Big enough to make the cache not very useful
 const ITERS: usize = 1024;
@@ -9,24 +9,37 @@ const ITERS: usize = 1024;
 fn bench_pow(c: &mut Criterion) {
     let mut gen = XoShiRo256PlusPlus::new(Hash::from_bytes([42; 32]));
     let gen_hash = |gen: &mut XoShiRo256PlusPlus| Hash::from_le_u64([gen.u64(), gen.u64(), gen.u64(), gen.u64()]);
-    let matrices: Vec<_> = (0..ITERS).map(|_| Matrix::generate(gen_hash(&mut gen))).collect();
     let hashes: Vec<_> = (0..ITERS).map(|_| gen_hash(&mut gen)).collect();
+    let timestamps: Vec<_> = (0..ITERS).map(|_| gen.u64()).collect();
+    let nonces: Vec<_> = (0..ITERS).map(|_| gen.u64()).collect();
 
-    c.bench_function("Compute Rank", |b| {
+    // Distinct headers, as hashed when validating the headers of a pruning point proof
+    c.bench_function("MemHash scalar", |b| {
         b.iter(|| {
-            for matrix in &matrices {
-                black_box(matrix.compute_rank());
+            for ((&hash, &timestamp), &nonce) in hashes.iter().zip(timestamps.iter()).zip(nonces.iter()) {
+                black_box(mem_hash(hash, timestamp, nonce));
             }
         });
     });
 
-    c.bench_function("HeavyHash", |b| {
+    c.bench_function("MemHash batch", |b| {
+        b.iter(|| black_box(mem_hash_batch::mem_hash_batch(&hashes, &timestamps, &nonces)));
+    });
+
+    // Nonces of a single header, as hashed when mining
+    let (pre_pow_hash, timestamp) = (hashes[0], timestamps[0]);
+    c.bench_function("PoW scalar", |b| {
         b.iter(|| {
-            for (matrix, &hash) in matrices.iter().zip(hashes.iter()) {
-                black_box(matrix.heavy_hash(hash));
+            let hasher = PowHash::new(pre_pow_hash, timestamp);
+            for &nonce in &nonces {
+                black_box(mem_hash(hasher.clone().finalize_with_nonce(nonce), timestamp, nonce));
             }
         });
     });
+
+    c.bench_function("PoW batch", |b| {
+        b.iter(|| black_box(mem_hash_batch::pow_hash_batch(pre_pow_hash, timestamp, &nonces)));
+    });
 }
 
 criterion_group!(benches, bench_pow);
@@ -1,12 +1,16 @@
-use std::cmp::max;
+use std::{cmp::max, sync::Arc};
 use crate::mem_hash::mem_hash;
 use vecno_consensus_core::{hashing, header::Header, BlockLevel};
-use vecno_hashes::{PowHash};
+use vecno_hashes::{Hash, PowHash};
 use vecno_math::Uint256;
 
 // public for benchmarks
 #[doc(hidden)]
 pub mod mem_hash;
+#[doc(hidden)]
+pub mod mem_hash_batch;
+#[doc(hidden)]
+pub mod xoshiro;
 #[cfg(feature = "wasm32-sdk")]
 pub mod wasm;
 #[doc(hidden)]
@@ -15,17 +19,20 @@ pub struct State {
     pub(crate) target: Uint256,
     pub(crate) hasher: PowHash,
     pub(crate) timestamp: u64,
+    pub(crate) pre_pow_hash: Hash,
 }
 
 impl State {
     #[inline]
     pub fn new(header: &Header) -> Self {
         let target = Uint256::from_compact_target_bits(header.bits);
-        let hasher = PowHash::new(hashing::header::hash_override_nonce_time(header, 0, 0), header.timestamp);
+        let pre_pow_hash = hashing::header::hash_override_nonce_time(header, 0, 0);
+        let hasher = PowHash::new(pre_pow_hash, header.timestamp);
         Self {
             target,
             hasher,
             timestamp: header.timestamp,
+            pre_pow_hash,
         }
     }
 
@@ -39,12 +46,30 @@ impl State {
         Uint256::from_le_bytes(hash.as_bytes())
     }
 
+    /// Batch equivalent of [`Self::calculate_pow`], returning the PoW value of every nonce in order.
+    ///
+    /// Nonces are hashed side by side in SIMD lanes when the CPU supports it, sharing the header part of
+    /// the pre-PoW hash. The output is identical to calling [`Self::calculate_pow`] on each nonce.
+    #[must_use]
+    pub fn calculate_pow_batch(&self, nonces: &[u64]) -> Vec<Uint256> {
+        mem_hash_batch::pow_hash_batch(self.pre_pow_hash, self.timestamp, nonces)
+            .into_iter()
+            .map(|hash| Uint256::from_le_bytes(hash.as_bytes()))
+            .collect()
+    }
+
     #[inline]
     #[must_use]
     pub fn check_pow(&self, nonce: u64) -> (bool, Uint256) {
         let pow = self.calculate_pow(nonce);
         (pow <= self.target, pow)
     }
+
+    /// Batch equivalent of [`Self::check_pow`], returning the first nonce meeting the target along with its PoW value.
+    #[must_use]
+    pub fn check_pow_batch(&self, nonces: &[u64]) -> Option<(u64, Uint256)> {
+        nonces.iter().copied().zip(self.calculate_pow_batch(nonces)).find(|(_, pow)| *pow <= self.target)
+    }
 }
 
 pub fn calc_block_level(header: &Header, max_block_level: BlockLevel) -> BlockLevel {
@@ -62,6 +87,25 @@ pub fn calc_block_level_check_pow(header: &Header, max_block_level: BlockLevel)
     (block_level, passed)
 }
 
+/// Batch equivalent of [`calc_block_level_check_pow`], hashing the headers side by side in SIMD lanes when the CPU supports it.
+pub fn calc_block_levels_check_pow(headers: &[Arc<Header>], max_block_level: BlockLevel) -> Vec<(BlockLevel, bool)> {
+    let pre_pow_hashes: Vec<Hash> = headers.iter().map(|header| hashing::header::hash_override_nonce_time(header, 0, 0)).collect();
+    let timestamps: Vec<u64> = headers.iter().map(|header| header.timestamp).collect();
+    let nonces: Vec<u64> = headers.iter().map(|header| header.nonce).collect();
+    let hashes = mem_hash_batch::pow_hash_batch_headers(&pre_pow_hashes, &timestamps, &nonces);
+    headers
+        .iter()
+        .zip(hashes)
+        .map(|(header, hash)| {
+            if header.parents_by_level.is_empty() {
+                return (max_block_level, true); // Genesis block
+            }
+            let pow = Uint256::from_le_bytes(hash.as_bytes());
+            (calc_level_from_pow(pow, max_block_level), pow <= Uint256::from_compact_target_bits(header.bits))
+        })
+        .collect()
+}
+
 pub fn calc_level_from_pow(pow: Uint256, max_block_level: BlockLevel) -> BlockLevel {
     let signed_block_level = max_block_level as i64 - pow.bits() as i64;
     max(signed_block_level, 0) as BlockLevel
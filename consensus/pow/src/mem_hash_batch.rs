@@ -0,0 +1,629 @@
+//! Lane-parallel implementation of [`mem_hash`].
+//!
+//! Every BLAKE3 invocation made by MemHash hashes at most a single 64-byte block, so it boils down to one
+//! BLAKE3 compression. This module runs those compressions for [`LANES`] independent nonces at once, one
+//! nonce per SIMD lane: AVX2 on x86_64 (selected at runtime) and NEON on aarch64. Other targets fall back
+//! to the scalar implementation.
+//! The S-box and round count derive from the nonce dependent pre-PoW hash so they cannot be shared between
+//! nonces, and the data-dependent S-box lookups remain scalar per lane. What is shared is the first block
+//! of the pre-PoW hash, which only covers the header.
+//!
+//! The output is bit-identical to the scalar implementation, which remains the reference.
+
+use crate::mem_hash::mem_hash;
+use vecno_hashes::{Hash, PowHash, VecnoHash};
+
+pub use simd::LANES;
+
+type Lanes = [u32; LANES];
+
+const IV: [u32; 8] = [0x6A09E667, 0xBB67AE85, 0x3C6EF372, 0xA54FF53A, 0x510E527F, 0x9B05688C, 0x1F83D9AB, 0x5BE0CD19];
+const MSG_PERMUTATION: [usize; 16] = [2, 6, 3, 10, 7, 0, 4, 13, 1, 11, 12, 5, 9, 14, 15, 8];
+
+const CHUNK_START: u32 = 1 << 0;
+const CHUNK_END: u32 = 1 << 1;
+const ROOT: u32 = 1 << 3;
+
+/// Number of message words occupied by the `usize` round counter hashed in the mixing loop
+const ROUND_WORDS: usize = size_of::<usize>() / size_of::<u32>();
+
+// Only AVX2 has enough vector registers to hold the compression state on x86_64, the baseline SSE2
+// is slower than the scalar implementation there.
+#[cfg(target_arch = "x86_64")]
+mod simd {
+    use std::arch::x86_64::*;
+
+    /// Number of nonces hashed side by side
+    pub const LANES: usize = 8;
+
+    pub(super) type Vector = __m256i;
+
+    #[inline(always)]
+    pub(super) unsafe fn load(lanes: &[u32; LANES]) -> Vector {
+        _mm256_loadu_si256(lanes.as_ptr() as *const __m256i)
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn store(vector: Vector) -> [u32; LANES] {
+        let mut lanes = [0; LANES];
+        _mm256_storeu_si256(lanes.as_mut_ptr() as *mut __m256i, vector);
+        lanes
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn splat(value: u32) -> Vector {
+        _mm256_set1_epi32(value as i32)
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn add(a: Vector, b: Vector) -> Vector {
+        _mm256_add_epi32(a, b)
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn xor(a: Vector, b: Vector) -> Vector {
+        _mm256_xor_si256(a, b)
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn rot16(x: Vector) -> Vector {
+        _mm256_or_si256(_mm256_srli_epi32::<16>(x), _mm256_slli_epi32::<16>(x))
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn rot12(x: Vector) -> Vector {
+        _mm256_or_si256(_mm256_srli_epi32::<12>(x), _mm256_slli_epi32::<20>(x))
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn rot8(x: Vector) -> Vector {
+        _mm256_or_si256(_mm256_srli_epi32::<8>(x), _mm256_slli_epi32::<24>(x))
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn rot7(x: Vector) -> Vector {
+        _mm256_or_si256(_mm256_srli_epi32::<7>(x), _mm256_slli_epi32::<25>(x))
+    }
+
+    pub(super) fn is_supported() -> bool {
+        std::arch::is_x86_feature_detected!("avx2")
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+mod simd {
+    use std::arch::aarch64::*;
+
+    /// Number of nonces hashed side by side
+    pub const LANES: usize = 4;
+
+    pub(super) type Vector = uint32x4_t;
+
+    #[inline(always)]
+    pub(super) unsafe fn load(lanes: &[u32; LANES]) -> Vector {
+        vld1q_u32(lanes.as_ptr())
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn store(vector: Vector) -> [u32; LANES] {
+        let mut lanes = [0; LANES];
+        vst1q_u32(lanes.as_mut_ptr(), vector);
+        lanes
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn splat(value: u32) -> Vector {
+        vdupq_n_u32(value)
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn add(a: Vector, b: Vector) -> Vector {
+        vaddq_u32(a, b)
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn xor(a: Vector, b: Vector) -> Vector {
+        veorq_u32(a, b)
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn rot16(x: Vector) -> Vector {
+        vreinterpretq_u32_u16(vrev32q_u16(vreinterpretq_u16_u32(x)))
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn rot12(x: Vector) -> Vector {
+        vsriq_n_u32::<12>(vshlq_n_u32::<20>(x), x)
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn rot8(x: Vector) -> Vector {
+        vsriq_n_u32::<8>(vshlq_n_u32::<24>(x), x)
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn rot7(x: Vector) -> Vector {
+        vsriq_n_u32::<7>(vshlq_n_u32::<25>(x), x)
+    }
+
+    /// NEON is part of the aarch64 baseline
+    pub(super) fn is_supported() -> bool {
+        true
+    }
+}
+
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+mod simd {
+    /// Number of nonces hashed side by side
+    pub const LANES: usize = 4;
+
+    pub(super) type Vector = [u32; LANES];
+
+    #[inline(always)]
+    pub(super) unsafe fn load(lanes: &[u32; LANES]) -> Vector {
+        *lanes
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn store(vector: Vector) -> [u32; LANES] {
+        vector
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn splat(value: u32) -> Vector {
+        [value; LANES]
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn add(a: Vector, b: Vector) -> Vector {
+        std::array::from_fn(|l| a[l].wrapping_add(b[l]))
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn xor(a: Vector, b: Vector) -> Vector {
+        std::array::from_fn(|l| a[l] ^ b[l])
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn rot16(x: Vector) -> Vector {
+        x.map(|word| word.rotate_right(16))
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn rot12(x: Vector) -> Vector {
+        x.map(|word| word.rotate_right(12))
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn rot8(x: Vector) -> Vector {
+        x.map(|word| word.rotate_right(8))
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn rot7(x: Vector) -> Vector {
+        x.map(|word| word.rotate_right(7))
+    }
+
+    /// Plain arrays are not vectorized by the compiler, making them slower than the scalar implementation
+    pub(super) fn is_supported() -> bool {
+        false
+    }
+}
+
+use simd::Vector;
+
+// All the lane kernels below are `unsafe` since they require `simd::is_supported()` to hold.
+
+#[inline(always)]
+unsafe fn g(state: &mut [Vector; 16], a: usize, b: usize, c: usize, d: usize, mx: Vector, my: Vector) {
+    state[a] = simd::add(simd::add(state[a], state[b]), mx);
+    state[d] = simd::rot16(simd::xor(state[d], state[a]));
+    state[c] = simd::add(state[c], state[d]);
+    state[b] = simd::rot12(simd::xor(state[b], state[c]));
+    state[a] = simd::add(simd::add(state[a], state[b]), my);
+    state[d] = simd::rot8(simd::xor(state[d], state[a]));
+    state[c] = simd::add(state[c], state[d]);
+    state[b] = simd::rot7(simd::xor(state[b], state[c]));
+}
+
+/// BLAKE3 compression of a single block with a zero counter, across all lanes. Returns the first
+/// 8 words of the output, i.e. the chaining value, or the 32-byte digest when `flags` contains `ROOT`.
+#[inline(always)]
+unsafe fn compress(cv: &[Lanes; 8], block: &[Lanes; 16], block_len: u32, flags: u32) -> [Lanes; 8] {
+    let mut state = [
+        simd::load(&cv[0]),
+        simd::load(&cv[1]),
+        simd::load(&cv[2]),
+        simd::load(&cv[3]),
+        simd::load(&cv[4]),
+        simd::load(&cv[5]),
+        simd::load(&cv[6]),
+        simd::load(&cv[7]),
+        simd::splat(IV[0]),
+        simd::splat(IV[1]),
+        simd::splat(IV[2]),
+        simd::splat(IV[3]),
+        simd::splat(0),
+        simd::splat(0),
+        simd::splat(block_len),
+        simd::splat(flags),
+    ];
+    let mut m = [simd::splat(0); 16];
+    for (word, lanes) in m.iter_mut().zip(block.iter()) {
+        *word = simd::load(lanes);
+    }
+    for round in 0..7 {
+        g(&mut state, 0, 4, 8, 12, m[0], m[1]);
+        g(&mut state, 1, 5, 9, 13, m[2], m[3]);
+        g(&mut state, 2, 6, 10, 14, m[4], m[5]);
+        g(&mut state, 3, 7, 11, 15, m[6], m[7]);
+        g(&mut state, 0, 5, 10, 15, m[8], m[9]);
+        g(&mut state, 1, 6, 11, 12, m[10], m[11]);
+        g(&mut state, 2, 7, 8, 13, m[12], m[13]);
+        g(&mut state, 3, 4, 9, 14, m[14], m[15]);
+        if round < 6 {
+            let permuted = m;
+            for (word, &index) in m.iter_mut().zip(MSG_PERMUTATION.iter()) {
+                *word = permuted[index];
+            }
+        }
+    }
+    let mut output = [[0; LANES]; 8];
+    for (i, lanes) in output.iter_mut().enumerate() {
+        *lanes = simd::store(simd::xor(state[i], state[i + 8]));
+    }
+    output
+}
+
+/// BLAKE3 hash of a message of `len <= 64` bytes, given as zero padded little endian words.
+#[inline(always)]
+unsafe fn hash_block(block: &[Lanes; 16], len: u32) -> [Lanes; 8] {
+    compress(&IV.map(|word| [word; LANES]), block, len, CHUNK_START | CHUNK_END | ROOT)
+}
+
+/// Hashes a 32-byte value, as done by every chained BLAKE3 call of MemHash.
+#[inline(always)]
+unsafe fn hash_words(words: &[Lanes; 8]) -> [Lanes; 8] {
+    let mut block = [[0; LANES]; 16];
+    block[..8].copy_from_slice(words);
+    hash_block(&block, 32)
+}
+
+/// `MemHash::bit_manipulations` expressed on little endian words: bytes 0 and 2 of each word are
+/// xor-ed with bytes 1 and 3 respectively.
+#[inline(always)]
+fn bit_manipulations(words: &mut [Lanes; 8]) {
+    for word in words.iter_mut() {
+        for value in word.iter_mut() {
+            *value ^= (*value >> 8) & 0x00FF00FF;
+        }
+    }
+}
+
+#[inline(always)]
+fn hash_to_words(hash: &Hash) -> [u32; 8] {
+    let bytes = hash.as_bytes();
+    std::array::from_fn(|i| u32::from_le_bytes(bytes[i * 4..i * 4 + 4].try_into().unwrap()))
+}
+
+#[inline(always)]
+fn words_to_hash(words: &[u32; 8]) -> Hash {
+    let mut bytes = [0u8; 32];
+    for (i, word) in words.iter().enumerate() {
+        bytes[i * 4..i * 4 + 4].copy_from_slice(&word.to_le_bytes());
+    }
+    Hash::from_bytes(bytes)
+}
+
+/// The chaining value of the first block of the PoW pre-hash, `PRE_POW_HASH || TIME || 24 zero bytes`,
+/// repeated across all lanes since it is shared by all nonces of a header.
+#[inline(always)]
+unsafe fn pow_hash_prefix_cv(pre_pow_hash: Hash, timestamp: u64) -> [Lanes; 8] {
+    let mut block = [[0; LANES]; 16];
+    for (lanes, word) in block.iter_mut().zip(hash_to_words(&pre_pow_hash)) {
+        *lanes = [word; LANES];
+    }
+    block[8] = [timestamp as u32; LANES];
+    block[9] = [(timestamp >> 32) as u32; LANES];
+    compress(&IV.map(|word| [word; LANES]), &block, 64, CHUNK_START)
+}
+
+/// Lane-parallel equivalent of `PowHash::finalize_with_nonce`, completing the second block
+/// `8 zero bytes || NONCE` of the PoW pre-hash from its shared first block chaining value.
+#[inline(always)]
+unsafe fn finalize_pow_hash(prefix_cv: &[Lanes; 8], nonces: &[u64; LANES]) -> [Lanes; 8] {
+    let mut block = [[0; LANES]; 16];
+    block[2] = nonces.map(|nonce| nonce as u32);
+    block[3] = nonces.map(|nonce| (nonce >> 32) as u32);
+    compress(prefix_cv, &block, 16, CHUNK_END | ROOT)
+}
+
+/// Runs MemHash over [`LANES`] inputs at once.
+#[inline(always)]
+unsafe fn mem_hash_lanes(input: [Lanes; 8], timestamps: &[u64; LANES], nonces: &[u64; LANES]) -> [Hash; LANES] {
+    // S-box: a chain of BLAKE3 hashes seeded by the input, contributing two bytes each
+    let mut sbox = [[0u8; 64]; LANES];
+    let mut seed = hash_words(&input);
+    for k in 0..32 {
+        for (l, sbox) in sbox.iter_mut().enumerate() {
+            let bytes = seed[0][l].to_le_bytes();
+            sbox[2 * k] = bytes[0];
+            sbox[2 * k + 1] = bytes[1];
+        }
+        // The scalar version derives one more seed after the last chunk, which is never read
+        if k < 31 {
+            seed = hash_words(&seed);
+        }
+    }
+
+    // Round count: BLAKE3(input || timestamp)
+    let mut block = [[0; LANES]; 16];
+    block[..8].copy_from_slice(&input);
+    block[8] = timestamps.map(|timestamp| timestamp as u32);
+    block[9] = timestamps.map(|timestamp| (timestamp >> 32) as u32);
+    let rounds: [usize; LANES] = hash_block(&block, 40)[0].map(|word| (word % 8 + 16) as usize);
+    let max_rounds = *rounds.iter().max().unwrap();
+
+    // First and second loops: 2 * rounds chained hashes with bit manipulations, lanes having
+    // completed their own round count are left untouched
+    let mut result = input;
+    for iteration in 0..2 * max_rounds {
+        let mut hashed = hash_words(&result);
+        bit_manipulations(&mut hashed);
+        let mask = rounds.map(|lane_rounds| if iteration < 2 * lane_rounds { u32::MAX } else { 0 });
+        for (result_word, hashed_word) in result.iter_mut().zip(hashed.iter()) {
+            for l in 0..LANES {
+                result_word[l] = (hashed_word[l] & mask[l]) | (result_word[l] & !mask[l]);
+            }
+        }
+    }
+
+    // Mixing loop
+    let mut block = [[0; LANES]; 16];
+    block[1 + ROUND_WORDS] = nonces.map(|nonce| nonce as u32);
+    block[2 + ROUND_WORDS] = nonces.map(|nonce| (nonce >> 32) as u32);
+    let block_len = (size_of::<u32>() + size_of::<usize>() + size_of::<u64>()) as u32;
+    for round in 0..max_rounds {
+        block[1] = [round as u32; LANES];
+        if ROUND_WORDS == 2 {
+            block[2] = [(round as u64 >> 32) as u32; LANES];
+        }
+        for i in 0..8 {
+            block[0] = result[i];
+            let state_word = hash_block(&block, block_len)[0];
+            let (first, current, next) = (result[0], result[i], result[(i + 1) % 8]);
+            let mut mixed = current;
+            for l in 0..LANES {
+                if round >= rounds[l] {
+                    continue;
+                }
+                // Only the first 4 bytes of the state hash xor-ed with the whole result are used
+                let mut v = state_word[l] ^ first[l];
+                v ^= current[l];
+                let b = next[l];
+                v = match (v & 0xFF) % 4 {
+                    0 => v.wrapping_add(b),
+                    1 => v.wrapping_sub(b),
+                    2 => v.rotate_left(b & 0x1F),
+                    _ => v ^ b,
+                };
+                let bytes = v.to_le_bytes();
+                let idx_base = (v as usize) % 64;
+                let sbox = &sbox[l];
+                mixed[l] = u32::from_le_bytes([
+                    sbox[(idx_base + (bytes[0] as usize)) % 64],
+                    sbox[(idx_base + (bytes[1] as usize)) % 64],
+                    sbox[(idx_base + (bytes[2] as usize)) % 64],
+                    sbox[(idx_base + (bytes[3] as usize)) % 64],
+                ]);
+            }
+            result[i] = mixed;
+        }
+    }
+
+    bit_manipulations(&mut result);
+    std::array::from_fn(|l| VecnoHash::hash(words_to_hash(&result.map(|word| word[l]))))
+}
+
+/// Transposes up to [`LANES`] hashes into lanes, repeating the last one to fill the remaining lanes.
+#[inline(always)]
+fn transpose(hashes: &[Hash]) -> [Lanes; 8] {
+    let words: Vec<[u32; 8]> = hashes.iter().map(hash_to_words).collect();
+    std::array::from_fn(|i| std::array::from_fn(|l| words[l.min(words.len() - 1)][i]))
+}
+
+#[inline(always)]
+fn fill<T: Copy>(values: &[T]) -> [T; LANES] {
+    std::array::from_fn(|l| values[l.min(values.len() - 1)])
+}
+
+#[cfg_attr(target_arch = "x86_64", target_feature(enable = "avx2"))]
+unsafe fn mem_hash_batch_lanes(input_hashes: &[Hash], timestamps: &[u64], nonces: &[u64]) -> Vec<Hash> {
+    let mut output = Vec::with_capacity(nonces.len());
+    for ((input_hashes, timestamps), nonces) in input_hashes.chunks(LANES).zip(timestamps.chunks(LANES)).zip(nonces.chunks(LANES)) {
+        let hashes = mem_hash_lanes(transpose(input_hashes), &fill(timestamps), &fill(nonces));
+        output.extend_from_slice(&hashes[..nonces.len()]);
+    }
+    output
+}
+
+#[cfg_attr(target_arch = "x86_64", target_feature(enable = "avx2"))]
+unsafe fn pow_hash_batch_lanes(pre_pow_hash: Hash, timestamp: u64, nonces: &[u64]) -> Vec<Hash> {
+    let prefix_cv = pow_hash_prefix_cv(pre_pow_hash, timestamp);
+    let mut output = Vec::with_capacity(nonces.len());
+    for nonces in nonces.chunks(LANES) {
+        let lane_nonces = fill(nonces);
+        let hashes = mem_hash_lanes(finalize_pow_hash(&prefix_cv, &lane_nonces), &[timestamp; LANES], &lane_nonces);
+        output.extend_from_slice(&hashes[..nonces.len()]);
+    }
+    output
+}
+
+/// Batch equivalent of [`mem_hash`], computing the hash of every `(input_hashes[i], timestamps[i], nonces[i])` triplet.
+pub fn mem_hash_batch(input_hashes: &[Hash], timestamps: &[u64], nonces: &[u64]) -> Vec<Hash> {
+    assert_eq!(input_hashes.len(), nonces.len(), "every input hash must be paired with a nonce");
+    assert_eq!(timestamps.len(), nonces.len(), "every input hash must be paired with a timestamp");
+    if !simd::is_supported() {
+        return input_hashes
+            .iter()
+            .zip(timestamps)
+            .zip(nonces)
+            .map(|((&hash, &timestamp), &nonce)| mem_hash(hash, timestamp, nonce))
+            .collect();
+    }
+    // SAFETY: the SIMD requirements of the lane kernels were just checked
+    unsafe { mem_hash_batch_lanes(input_hashes, timestamps, nonces) }
+}
+
+/// Computes the full PoW hash (pre-hash followed by MemHash) of every nonce of a header, which is
+/// what [`State::calculate_pow`](crate::State::calculate_pow) hashes for a single nonce.
+pub fn pow_hash_batch(pre_pow_hash: Hash, timestamp: u64, nonces: &[u64]) -> Vec<Hash> {
+    if !simd::is_supported() {
+        let hasher = PowHash::new(pre_pow_hash, timestamp);
+        return nonces.iter().map(|&nonce| mem_hash(hasher.clone().finalize_with_nonce(nonce), timestamp, nonce)).collect();
+    }
+    // SAFETY: the SIMD requirements of the lane kernels were just checked
+    unsafe { pow_hash_batch_lanes(pre_pow_hash, timestamp, nonces) }
+}
+
+/// Computes the full PoW hash of distinct headers, given as `(pre_pow_hashes[i], timestamps[i], nonces[i])` triplets.
+/// Only MemHash runs in lanes since the pre-hash of each header is unique.
+pub fn pow_hash_batch_headers(pre_pow_hashes: &[Hash], timestamps: &[u64], nonces: &[u64]) -> Vec<Hash> {
+    assert_eq!(pre_pow_hashes.len(), nonces.len(), "every pre-PoW hash must be paired with a nonce");
+    assert_eq!(timestamps.len(), nonces.len(), "every pre-PoW hash must be paired with a timestamp");
+    let input_hashes: Vec<Hash> = pre_pow_hashes
+        .iter()
+        .zip(timestamps)
+        .zip(nonces)
+        .map(|((&pre_pow_hash, &timestamp), &nonce)| PowHash::new(pre_pow_hash, timestamp).finalize_with_nonce(nonce))
+        .collect();
+    mem_hash_batch(&input_hashes, timestamps, nonces)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{calc_block_level_check_pow, calc_block_levels_check_pow, xoshiro::XoShiRo256PlusPlus};
+    use vecno_consensus_core::header::Header;
+    use std::sync::Arc;
+
+    fn gen_hash(gen: &mut XoShiRo256PlusPlus) -> Hash {
+        Hash::from_le_u64([gen.u64(), gen.u64(), gen.u64(), gen.u64()])
+    }
+
+    #[test]
+    fn test_compress_matches_blake3() {
+        if !simd::is_supported() {
+            return;
+        }
+        let mut gen = XoShiRo256PlusPlus::new(Hash::from_bytes([7; 32]));
+        for len in [16usize, 20, 24, 32, 40, 64] {
+            let messages: Vec<Vec<u8>> = (0..LANES)
+                .map(|_| (0..len.div_ceil(8)).flat_map(|_| gen.u64().to_le_bytes()).take(len).collect::<Vec<u8>>())
+                .collect();
+            let mut block = [[0; LANES]; 16];
+            for (l, message) in messages.iter().enumerate() {
+                let mut padded = [0u8; 64];
+                padded[..len].copy_from_slice(message);
+                for (i, word) in block.iter_mut().enumerate() {
+                    word[l] = u32::from_le_bytes(padded[i * 4..i * 4 + 4].try_into().unwrap());
+                }
+            }
+            // SAFETY: checked above
+            let hashes = unsafe { hash_block(&block, len as u32) };
+            for (l, message) in messages.iter().enumerate() {
+                assert_eq!(words_to_hash(&hashes.map(|word| word[l])).as_bytes(), *blake3::hash(message).as_bytes(), "length {len}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_pow_hash_prefix_matches_pow_hash() {
+        if !simd::is_supported() {
+            return;
+        }
+        let mut gen = XoShiRo256PlusPlus::new(Hash::from_bytes([11; 32]));
+        for _ in 0..64 {
+            let pre_pow_hash = gen_hash(&mut gen);
+            let timestamp = gen.u64();
+            let nonces: [u64; LANES] = std::array::from_fn(|_| gen.u64());
+            let hasher = PowHash::new(pre_pow_hash, timestamp);
+            // SAFETY: checked above
+            let hashes = unsafe { finalize_pow_hash(&pow_hash_prefix_cv(pre_pow_hash, timestamp), &nonces) };
+            for (l, &nonce) in nonces.iter().enumerate() {
+                assert_eq!(words_to_hash(&hashes.map(|word| word[l])), hasher.clone().finalize_with_nonce(nonce));
+            }
+        }
+    }
+
+    #[test]
+    fn test_mem_hash_batch_matches_scalar() {
+        let mut gen = XoShiRo256PlusPlus::new(Hash::from_bytes([42; 32]));
+        // Covers empty, partial and multiple full batches, with random and edge case timestamps and nonces
+        for count in [0, 1, LANES - 1, LANES, LANES + 1, 3 * LANES + 5] {
+            for timestamp in [Some(0), Some(u64::MAX), None] {
+                let input_hashes: Vec<Hash> = (0..count).map(|_| gen_hash(&mut gen)).collect();
+                // A missing timestamp stands for a distinct timestamp per input
+                let timestamps: Vec<u64> = (0..count).map(|_| timestamp.unwrap_or_else(|| gen.u64())).collect();
+                let nonces: Vec<u64> = (0..count).map(|i| if i % 3 == 0 { i as u64 } else { gen.u64() }).collect();
+                let expected: Vec<Hash> = input_hashes
+                    .iter()
+                    .zip(timestamps.iter())
+                    .zip(nonces.iter())
+                    .map(|((&hash, &timestamp), &nonce)| mem_hash(hash, timestamp, nonce))
+                    .collect();
+                assert_eq!(mem_hash_batch(&input_hashes, &timestamps, &nonces), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_pow_hash_batch_matches_scalar() {
+        let mut gen = XoShiRo256PlusPlus::new(Hash::from_bytes([3; 32]));
+        for _ in 0..16 {
+            let pre_pow_hash = gen_hash(&mut gen);
+            let timestamp = gen.u64();
+            let nonces: Vec<u64> = (0..2 * LANES + 3).map(|_| gen.u64()).collect();
+            let hasher = PowHash::new(pre_pow_hash, timestamp);
+            let expected: Vec<Hash> =
+                nonces.iter().map(|&nonce| mem_hash(hasher.clone().finalize_with_nonce(nonce), timestamp, nonce)).collect();
+            assert_eq!(pow_hash_batch(pre_pow_hash, timestamp, &nonces), expected);
+        }
+    }
+
+    #[test]
+    fn test_pow_hash_batch_headers_matches_scalar() {
+        let mut gen = XoShiRo256PlusPlus::new(Hash::from_bytes([5; 32]));
+        let count = 2 * LANES + 3;
+        let pre_pow_hashes: Vec<Hash> = (0..count).map(|_| gen_hash(&mut gen)).collect();
+        let timestamps: Vec<u64> = (0..count).map(|_| gen.u64()).collect();
+        let nonces: Vec<u64> = (0..count).map(|_| gen.u64()).collect();
+        let expected: Vec<Hash> = (0..count)
+            .map(|i| {
+                let pow_hash = PowHash::new(pre_pow_hashes[i], timestamps[i]).finalize_with_nonce(nonces[i]);
+                mem_hash(pow_hash, timestamps[i], nonces[i])
+            })
+            .collect();
+        assert_eq!(pow_hash_batch_headers(&pre_pow_hashes, &timestamps, &nonces), expected);
+    }
+
+    #[test]
+    fn test_calc_block_levels_check_pow_matches_scalar() {
+        let mut gen = XoShiRo256PlusPlus::new(Hash::from_bytes([9; 32]));
+        let headers: Vec<Arc<Header>> = (0..2 * LANES + 1)
+            .map(|i| {
+                let mut header = Header::from_precomputed_hash(gen_hash(&mut gen), vec![gen_hash(&mut gen)]);
+                // The first header stands for genesis, and every other header has a target easy enough to be met
+                if i == 0 {
+                    header.parents_by_level = vec![];
+                }
+                header.bits = if i % 2 == 0 { 0x207fffff } else { 0x1d00ffff };
+                header.timestamp = gen.u64();
+                header.nonce = gen.u64();
+                Arc::new(header)
+            })
+            .collect();
+        let expected: Vec<_> = headers.iter().map(|header| calc_block_level_check_pow(header, 225)).collect();
+        assert_eq!(calc_block_levels_check_pow(&headers, 225), expected);
+    }
+}
@@ -788,6 +788,12 @@ impl ConsensusApi for Consensus {
         self.virtual_processor.get_populated_transaction(txid, accepting_block_daa_score, self.get_retention_period_root())
     }
 
+    fn get_populated_accepted_transactions(&self, chain_block: Hash) -> Result<Vec<SignableTransaction>, UtxoInquirerError> {
+        // We need consistency between the utxo_diffs_store, block_transactions_store, acceptance data and headers store reads
+        let _guard = self.pruning_lock.blocking_read();
+        self.virtual_processor.get_populated_accepted_transactions(chain_block)
+    }
+
     fn get_virtual_parents(&self) -> BlockHashSet {
         self.lkg_virtual_state.load().parents.iter().copied().collect()
     }
@@ -1,8 +1,8 @@
-use std::{cmp, sync::Arc};
+use std::{cmp, collections::HashMap, sync::Arc};
 
 use vecno_consensus_core::{
     acceptance_data::AcceptanceData,
-    tx::{SignableTransaction, Transaction, UtxoEntry},
+    tx::{SignableTransaction, Transaction, TransactionId, UtxoEntry},
     utxo::{utxo_diff::ImmutableUtxoDiff, utxo_inquirer::UtxoInquirerError},
 };
 use vecno_core::{trace, warn};
@@ -51,7 +51,7 @@ impl VirtualStateProcessor {
 
         let removed_diffs = utxo_diff.removed();
 
-        populated_tx.tx.inputs.iter().enumerate().for_each(|(index, input)| {
+        for (index, input) in populated_tx.tx.inputs.iter().enumerate() {
             let filled_utxo = if let Some(utxo_entry) = removed_diffs.get(&input.previous_outpoint) {
                 Some(utxo_entry.clone().to_owned())
             } else {
@@ -62,19 +62,95 @@ impl VirtualStateProcessor {
                 // In this case, removed_diff wouldn't contain the outpoint of the created-and-immediately-spent UTXO
                 // so we use the transaction (which also has acceptance data in this block) and look at its outputs
                 let other_txid = input.previous_outpoint.transaction_id;
-                let other_tx = self.find_tx_from_acceptance_data(other_txid, &acceptance_data).unwrap();
-                let output = &other_tx.outputs[input.previous_outpoint.index as usize];
+                let other_tx = self.find_tx_from_acceptance_data(other_txid, &acceptance_data)?;
+                let output = other_tx
+                    .outputs
+                    .get(input.previous_outpoint.index as usize)
+                    .ok_or(UtxoInquirerError::MissingOutputIndexOfTransaction(input.previous_outpoint.index, other_txid))?;
                 let utxo_entry =
                     UtxoEntry::new(output.value, output.script_public_key.clone(), accepting_block_daa_score, other_tx.is_coinbase());
                 Some(utxo_entry)
             };
 
             populated_tx.entries[index] = filled_utxo;
-        });
+        }
 
         Ok(populated_tx)
     }
 
+    /// Returns the fully populated transactions accepted by the selected chain block `chain_block`, ordered as
+    /// they appear in its acceptance data.
+    ///
+    /// *Assumed to be called under the pruning read lock.*
+    pub fn get_populated_accepted_transactions(&self, chain_block: Hash) -> Result<Vec<SignableTransaction>, UtxoInquirerError> {
+        let acceptance_data = self
+            .acceptance_data_store
+            .get(chain_block)
+            .map_err(|_| UtxoInquirerError::MissingAcceptanceDataForChainBlock(chain_block))?;
+        let utxo_diff =
+            self.utxo_diffs_store.get(chain_block).map_err(|_| UtxoInquirerError::MissingUtxoDiffForChainBlock(chain_block))?;
+        let accepting_block_daa_score = self
+            .headers_store
+            .get_daa_score(chain_block)
+            .map_err(|_| UtxoInquirerError::MissingCompactHeaderForBlockHash(chain_block))?;
+
+        let mut accepted_transactions = Vec::with_capacity(acceptance_data.iter().map(|mbad| mbad.accepted_transactions.len()).sum());
+        for mbad in acceptance_data.iter() {
+            let block_txs = self
+                .block_transactions_store
+                .get(mbad.block_hash)
+                .map_err(|_| UtxoInquirerError::MissingBlockFromBlockTxStore(mbad.block_hash))?;
+            for entry in mbad.accepted_transactions.iter() {
+                let index = entry.index_within_block as usize;
+                let tx =
+                    block_txs.get(index).cloned().ok_or(UtxoInquirerError::MissingTransactionIndexOfBlock(index, mbad.block_hash))?;
+                if tx.id() != entry.transaction_id {
+                    warn!(
+                        "Expected {} to match {} when checking block_transaction_store using array index of transaction",
+                        tx.id(),
+                        entry.transaction_id
+                    );
+                    return Err(UtxoInquirerError::UnexpectedTransactionMismatch(tx.id(), entry.transaction_id));
+                }
+                accepted_transactions.push(tx);
+            }
+        }
+
+        // Outputs created and spent within the same mergeset are missing from the utxo diff, so they are resolved
+        // through the creating transaction (see `get_populated_transaction`)
+        let accepted_by_id: HashMap<TransactionId, &Transaction> = accepted_transactions.iter().map(|tx| (tx.id(), tx)).collect();
+        let removed_diffs = utxo_diff.removed();
+
+        accepted_transactions
+            .iter()
+            .map(|tx| {
+                let mut populated_tx = SignableTransaction::new(tx.clone());
+                for (index, input) in tx.inputs.iter().enumerate() {
+                    let utxo_entry = match removed_diffs.get(&input.previous_outpoint) {
+                        Some(utxo_entry) => utxo_entry.clone(),
+                        None => {
+                            let other_txid = input.previous_outpoint.transaction_id;
+                            let other_tx = accepted_by_id
+                                .get(&other_txid)
+                                .ok_or(UtxoInquirerError::MissingOtherTransactionAcceptanceData(other_txid))?;
+                            let output = other_tx.outputs.get(input.previous_outpoint.index as usize).ok_or(
+                                UtxoInquirerError::MissingOutputIndexOfTransaction(input.previous_outpoint.index, other_txid),
+                            )?;
+                            UtxoEntry::new(
+                                output.value,
+                                output.script_public_key.clone(),
+                                accepting_block_daa_score,
+                                other_tx.is_coinbase(),
+                            )
+                        }
+                    };
+                    populated_tx.entries[index] = Some(utxo_entry);
+                }
+                Ok(populated_tx)
+            })
+            .collect()
+    }
+
     /// Find the accepting chain block hash at the given DAA score by binary searching
     /// through selected chain store using indexes.
     /// This method assumes that local caller have acquired the pruning read lock to guarantee
@@ -14,7 +14,7 @@ use vecno_consensus_core::{
 use vecno_core::info;
 use vecno_database::prelude::{CachePolicy, ConnBuilder, StoreResultEmptyTuple, StoreResultExtensions};
 use vecno_hashes::Hash;
-use vecno_pow::{calc_block_level, calc_block_level_check_pow};
+use vecno_pow::{calc_block_level, calc_block_levels_check_pow};
 use vecno_utils::vec::VecExtensions;
 use parking_lot::lock_api::RwLock;
 use rocksdb::WriteBatch;
@@ -266,8 +266,9 @@ impl PruningProofManager {
             }
             let level_idx = level as usize;
             let mut selected_tip = None;
-            for (i, header) in proof[level as usize].iter().enumerate() {
-                let (header_level, pow_passes) = calc_block_level_check_pow(header, self.max_block_level);
+            // The PoW of the whole level is checked at once so the headers can be hashed side by side
+            let levels_and_pow = calc_block_levels_check_pow(&proof[level_idx], self.max_block_level);
+            for (i, (header, (header_level, pow_passes))) in proof[level_idx].iter().zip(levels_and_pow).enumerate() {
                 if header_level < level {
                     return Err(PruningImportError::PruningProofWrongBlockLevel(header.hash, header_level, level));
                 }
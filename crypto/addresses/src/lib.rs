@@ -334,6 +334,29 @@ impl TryFrom<&str> for Address {
     }
 }
 
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for Address {
+    fn schema_name() -> String {
+        "Address".to_owned()
+    }
+
+    fn json_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        schemars::schema::SchemaObject {
+            instance_type: Some(schemars::schema::InstanceType::String.into()),
+            metadata: Some(Box::new(schemars::schema::Metadata {
+                description: Some("Bech32 encoded address prefixed by its network, e.g. `vecno:...`".to_owned()),
+                ..Default::default()
+            })),
+            string: Some(Box::new(schemars::schema::StringValidation {
+                pattern: Some("^[a-z]+:[02-9ac-hj-np-z]+$".to_owned()),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
 impl Serialize for Address {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -28,6 +28,7 @@ blake3_hasher! {
     struct MerkleBranchHash => b"MerkleBranchHash\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0",
     struct MuHashElementHash => b"MuHashElement\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0",
     struct MuHashFinalizeHash => b"MuHashFinalize\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0",
+    struct ShortTransactionID => b"ShortTransactionID\0\0\0\0\0\0\0\0\0\0\0\0\0\0",
     struct PersonalMessageSigningHash => b"PersonalMessageSigningHash\0\0\0\0\0\0",
 }
 
@@ -28,6 +28,8 @@ pub struct Hash([u8; HASH_SIZE]);
 
 serde_impl_ser_fixed_bytes_ref!(Hash, HASH_SIZE);
 serde_impl_deser_fixed_bytes_ref!(Hash, HASH_SIZE);
+#[cfg(feature = "schemars")]
+vecno_utils::json_schema_impl_hex_string!(Hash, Some(HASH_SIZE));
 
 impl From<[u8; HASH_SIZE]> for Hash {
     fn from(value: [u8; HASH_SIZE]) -> Self {
@@ -17,6 +17,7 @@ pub enum Error {
 
 /// Standard classes of script payment in the blockDAG
 #[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[borsh(use_discriminant = true)]
 #[repr(u8)]
 pub enum ScriptClass {
@@ -56,11 +56,19 @@ pub enum DatabaseStorePrefixes {
     // ---- Components ----
     Addresses = 128,
     BannedAddresses = 129,
+    MempoolTransactions = 130,
+    OnionAddresses = 131,
 
     // ---- Indexes ----
     UtxoIndex = 192,
     UtxoIndexTips = 193,
     CirculatingSupply = 194,
+    TxIndexEntries = 195,
+    TxIndexAcceptedTransactions = 196,
+    TxIndexSink = 197,
+    AddressIndexHistory = 198,
+    AddressIndexChainBlockKeys = 199,
+    AddressIndexSink = 200,
 
     // ---- Separator ----
     /// Reserved as a separator
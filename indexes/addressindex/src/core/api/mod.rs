@@ -0,0 +1,84 @@
+use vecno_consensus_core::tx::ScriptPublicKey;
+use vecno_consensusmanager::spawn_blocking;
+use vecno_database::prelude::StoreResult;
+use vecno_hashes::Hash;
+use parking_lot::RwLock;
+use std::{fmt::Debug, sync::Arc};
+
+use crate::{
+    errors::AddressIndexResult,
+    model::{AddressHistoryCursor, AddressHistoryPage},
+};
+
+///Address index API targeted at retrieval calls.
+pub trait AddressIndexApi: Send + Sync + Debug {
+    /// Retrieve a page of at most `limit` history entries of `script_public_key`, in chronological order,
+    /// starting at `cursor` or at the oldest indexed entry if `None`.
+    ///
+    /// Note: Use a read lock when accessing this method
+    fn get_address_history(
+        &self,
+        script_public_key: &ScriptPublicKey,
+        cursor: Option<AddressHistoryCursor>,
+        limit: usize,
+    ) -> StoreResult<AddressHistoryPage>;
+
+    /// Retrieve the selected chain sink the address index is synced up to.
+    ///
+    /// Note: Use a read lock when accessing this method
+    fn get_sink(&self) -> StoreResult<Hash>;
+
+    /// Checks if the address index's db is synced with consensus.
+    ///
+    /// Note:
+    /// 1) Use a read lock when accessing this method
+    /// 2) due to potential sync-gaps is_synced is unreliable while consensus is actively resolving virtual states.
+    fn is_synced(&self) -> AddressIndexResult<bool>;
+
+    /// Update the address index with the given selected chain changes.
+    ///
+    /// Note: Use a write lock when accessing this method
+    fn update(
+        &mut self,
+        removed_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_block_hashes: Arc<Vec<Hash>>,
+    ) -> AddressIndexResult<()>;
+
+    /// Resync the address index from the consensus db
+    ///
+    /// Note: Use a write lock when accessing this method
+    fn resync(&mut self) -> AddressIndexResult<()>;
+}
+
+/// Async proxy for the address index
+#[derive(Debug, Clone)]
+pub struct AddressIndexProxy {
+    inner: Arc<RwLock<dyn AddressIndexApi>>,
+}
+
+impl AddressIndexProxy {
+    pub fn new(inner: Arc<RwLock<dyn AddressIndexApi>>) -> Self {
+        Self { inner }
+    }
+
+    pub async fn get_address_history(
+        self,
+        script_public_key: ScriptPublicKey,
+        cursor: Option<AddressHistoryCursor>,
+        limit: usize,
+    ) -> StoreResult<AddressHistoryPage> {
+        spawn_blocking(move || self.inner.read().get_address_history(&script_public_key, cursor, limit)).await.unwrap()
+    }
+
+    pub async fn get_sink(self) -> StoreResult<Hash> {
+        spawn_blocking(move || self.inner.read().get_sink()).await.unwrap()
+    }
+
+    pub async fn update(
+        self,
+        removed_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_block_hashes: Arc<Vec<Hash>>,
+    ) -> AddressIndexResult<()> {
+        spawn_blocking(move || self.inner.write().update(removed_chain_block_hashes, added_chain_block_hashes)).await.unwrap()
+    }
+}
@@ -0,0 +1,24 @@
+use thiserror::Error;
+
+use crate::IDENT;
+use vecno_consensus_core::{errors::consensus::ConsensusError, tx::TransactionId, utxo::utxo_inquirer::UtxoInquirerError};
+use vecno_database::prelude::StoreError;
+
+/// Errors originating from the [`AddressIndex`](crate::AddressIndex).
+#[derive(Error, Debug)]
+pub enum AddressIndexError {
+    #[error("[{IDENT}]: {0}")]
+    StoreAccessError(#[from] StoreError),
+
+    #[error("[{IDENT}]: {0}")]
+    ConsensusError(#[from] ConsensusError),
+
+    #[error("[{IDENT}]: {0}")]
+    UtxoInquirerError(#[from] UtxoInquirerError),
+
+    #[error("[{IDENT}]: the utxo entries spent by accepted transaction {0} are not populated")]
+    UnpopulatedTransaction(TransactionId),
+}
+
+/// Results originating from the [`AddressIndex`](crate::AddressIndex).
+pub type AddressIndexResult<T> = Result<T, AddressIndexError>;
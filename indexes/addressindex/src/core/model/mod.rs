@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+use std::array::TryFromSliceError;
+use vecno_consensus_core::tx::{TransactionId, TransactionIndexType, TransactionOutpoint};
+use vecno_hashes::{Hash, HASH_SIZE};
+use vecno_utils::mem_size::MemSizeEstimator;
+
+/// The way a transaction affected the balance of an address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum AddressActivityKind {
+    /// The transaction created an output paying to the address
+    Received = 0,
+    /// The transaction spent an output previously paid to the address
+    Spent = 1,
+}
+
+/// A single received or spent event in the history of an address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddressHistoryEntry {
+    /// The transaction which created or spent the output
+    pub transaction_id: TransactionId,
+    pub kind: AddressActivityKind,
+    /// The created output for [`AddressActivityKind::Received`], the spent one for [`AddressActivityKind::Spent`]
+    pub outpoint: TransactionOutpoint,
+    pub amount: u64,
+    /// The selected chain block whose mergeset accepted the transaction
+    pub accepting_block: Hash,
+    pub accepting_block_daa_score: u64,
+}
+
+impl MemSizeEstimator for AddressHistoryEntry {}
+
+/// Size of the [`AddressHistoryCursor`] in bytes.
+pub const ADDRESS_HISTORY_CURSOR_SIZE: usize =
+    size_of::<u64>() + HASH_SIZE + size_of::<AddressActivityKind>() + size_of::<TransactionIndexType>();
+
+/// Position of an entry within the history of an address.
+///
+/// Consists of 8 bytes of big endian accepting DAA score, 32 bytes of [`TransactionId`], 1 byte of
+/// [`AddressActivityKind`] and 4 bytes of big endian input or output index, so that the byte order
+/// matches the chronological order of the history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AddressHistoryCursor([u8; ADDRESS_HISTORY_CURSOR_SIZE]);
+
+impl AddressHistoryCursor {
+    pub fn new(
+        accepting_block_daa_score: u64,
+        transaction_id: TransactionId,
+        kind: AddressActivityKind,
+        index: TransactionIndexType,
+    ) -> Self {
+        let mut bytes = [0; ADDRESS_HISTORY_CURSOR_SIZE];
+        bytes[..8].copy_from_slice(&accepting_block_daa_score.to_be_bytes());
+        bytes[8..8 + HASH_SIZE].copy_from_slice(&transaction_id.as_bytes());
+        bytes[8 + HASH_SIZE] = kind as u8;
+        bytes[8 + HASH_SIZE + 1..].copy_from_slice(&index.to_be_bytes());
+        Self(bytes)
+    }
+}
+
+impl AsRef<[u8]> for AddressHistoryCursor {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl TryFrom<&[u8]> for AddressHistoryCursor {
+    type Error = TryFromSliceError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Ok(Self(bytes.try_into()?))
+    }
+}
+
+/// A page of the history of an address, in chronological order.
+#[derive(Debug, Clone, Default)]
+pub struct AddressHistoryPage {
+    pub entries: Vec<AddressHistoryEntry>,
+    /// The position of the first entry of the next page, if any
+    pub next_cursor: Option<AddressHistoryCursor>,
+}
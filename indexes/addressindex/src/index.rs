@@ -0,0 +1,180 @@
+use crate::{
+    api::AddressIndexApi,
+    errors::{AddressIndexError, AddressIndexResult},
+    model::{AddressHistoryCursor, AddressHistoryPage},
+    stores::store_manager::{AcceptedChainBlock, Store},
+    IDENT,
+};
+use vecno_consensus_core::{api::ConsensusApi, tx::ScriptPublicKey};
+use vecno_consensusmanager::{ConsensusManager, ConsensusResetHandler};
+use vecno_core::{info, trace};
+use vecno_database::prelude::{StoreError, StoreResult, DB};
+use vecno_hashes::Hash;
+use parking_lot::RwLock;
+use std::{
+    fmt::Debug,
+    sync::{Arc, Weak},
+};
+
+const RESYNC_CHUNK_SIZE: usize = 1024;
+
+/// AddressIndex indexes the received and spent events of every script public key, as accepted by the selected chain.
+/// Note: The AddressIndex struct by itself is not thread save, only correct usage of the supplied RwLock via `new` makes it so.
+/// please follow guidelines found in the comments under `addressindex::core::api::AddressIndexApi` for proper thread safety.
+pub struct AddressIndex {
+    consensus_manager: Arc<ConsensusManager>,
+    store: Store,
+}
+
+impl AddressIndex {
+    /// Creates a new [`AddressIndex`] within a [`RwLock`]
+    pub fn new(consensus_manager: Arc<ConsensusManager>, db: Arc<DB>) -> AddressIndexResult<Arc<RwLock<Self>>> {
+        let mut addressindex = Self { consensus_manager: consensus_manager.clone(), store: Store::new(db) };
+        if !addressindex.is_synced()? {
+            addressindex.resync()?;
+        }
+        let addressindex = Arc::new(RwLock::new(addressindex));
+        consensus_manager
+            .register_consensus_reset_handler(Arc::new(AddressIndexConsensusResetHandler::new(Arc::downgrade(&addressindex))));
+        Ok(addressindex)
+    }
+
+    /// Collects the populated transactions accepted by each of the given selected chain blocks.
+    fn collect_accepted_chain_blocks(
+        consensus: &dyn ConsensusApi,
+        chain_block_hashes: &[Hash],
+    ) -> AddressIndexResult<Vec<AcceptedChainBlock>> {
+        chain_block_hashes
+            .iter()
+            .map(|&hash| {
+                Ok(AcceptedChainBlock {
+                    hash,
+                    daa_score: consensus.get_header(hash)?.daa_score,
+                    transactions: consensus.get_populated_accepted_transactions(hash)?,
+                })
+            })
+            .collect()
+    }
+}
+
+impl AddressIndexApi for AddressIndex {
+    /// Retrieve a page of the history of a script public key from the addressindex db.
+    fn get_address_history(
+        &self,
+        script_public_key: &ScriptPublicKey,
+        cursor: Option<AddressHistoryCursor>,
+        limit: usize,
+    ) -> StoreResult<AddressHistoryPage> {
+        trace!("[{0}] retrieving at most {1} history entries", IDENT, limit);
+
+        self.store.get_address_history(script_public_key, cursor, limit)
+    }
+
+    /// Retrieve the stored sink of the addressindex.
+    fn get_sink(&self) -> StoreResult<Hash> {
+        trace!("[{0}] retrieving sink", IDENT);
+
+        self.store.get_sink()
+    }
+
+    /// Checks to see if the [AddressIndex] is sync'd. This is done via comparing the addressindex committed sink with the one of the consensus database.
+    ///
+    /// **Note:** Due to sync gaps between the addressindex and consensus, this function is only reliable while consensus is not processing new blocks.
+    fn is_synced(&self) -> AddressIndexResult<bool> {
+        trace!("[{0}] checking sync status...", IDENT);
+
+        let consensus = self.consensus_manager.consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        match self.store.get_sink() {
+            Ok(addressindex_sink) => {
+                let res = addressindex_sink == session.get_sink();
+                trace!("[{0}] sync status is {1}", IDENT, res);
+                Ok(res)
+            }
+            Err(StoreError::KeyNotFound(_)) => {
+                //Means addressindex sink database is empty i.e. not sync'd.
+                trace!("[{0}] sync status is {1}", IDENT, false);
+                Ok(false)
+            }
+            Err(other_store_errors) => Err(AddressIndexError::StoreAccessError(other_store_errors)),
+        }
+    }
+
+    /// Updates the [AddressIndex] with a selected chain change:
+    /// 1) Deletes the history entries added by the removed chain blocks.
+    /// 2) Records the history entries of the transactions accepted by the added chain blocks and the new sink.
+    fn update(
+        &mut self,
+        removed_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_block_hashes: Arc<Vec<Hash>>,
+    ) -> AddressIndexResult<()> {
+        trace!("[{0}] updating...", IDENT);
+        trace!("[{0}] adding {1} chain blocks", IDENT, added_chain_block_hashes.len());
+        trace!("[{0}] removing {1} chain blocks", IDENT, removed_chain_block_hashes.len());
+
+        let consensus = self.consensus_manager.consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+        let added_chain_blocks = Self::collect_accepted_chain_blocks(&*session, &added_chain_block_hashes)?;
+
+        self.store.apply_chain_changes(&removed_chain_block_hashes, &added_chain_blocks, added_chain_block_hashes.last().copied())?;
+        Ok(())
+    }
+
+    /// Deletes and reinstates the addressindex database, syncing it from scratch by walking the selected chain
+    /// from the retention period root up to the sink.
+    ///
+    /// **Notes:**
+    /// 1) Transactions accepted by the retention period root itself or earlier are not indexed.
+    /// 2) resyncing while consensus notifies of chain changes, may result in a corrupted db.
+    fn resync(&mut self) -> AddressIndexResult<()> {
+        info!("Resyncing the addressindex...");
+
+        self.store.delete_all()?;
+        let consensus = self.consensus_manager.consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        let mut low = session.get_retention_period_root();
+        loop {
+            let chain_path = session.get_virtual_chain_from_block(low, Some(RESYNC_CHUNK_SIZE))?;
+            let Some(&last) = chain_path.added.last() else {
+                break;
+            };
+            trace!("[{0}] resyncing with batch of {1} chain blocks from consensus db", IDENT, chain_path.added.len());
+            let added_chain_blocks = Self::collect_accepted_chain_blocks(&*session, &chain_path.added)?;
+            self.store.apply_chain_changes(&[], &added_chain_blocks, None)?;
+            low = last;
+        }
+
+        // Commit the sink only once the whole chain was indexed, so that an interrupted resync is restarted
+        let sink = session.get_sink();
+        trace!("[{0}] committing sink {1} from consensus db", IDENT, sink);
+        self.store.set_sink(sink)?;
+
+        Ok(())
+    }
+}
+
+impl Debug for AddressIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AddressIndex").finish()
+    }
+}
+
+struct AddressIndexConsensusResetHandler {
+    addressindex: Weak<RwLock<AddressIndex>>,
+}
+
+impl AddressIndexConsensusResetHandler {
+    fn new(addressindex: Weak<RwLock<AddressIndex>>) -> Self {
+        Self { addressindex }
+    }
+}
+
+impl ConsensusResetHandler for AddressIndexConsensusResetHandler {
+    fn handle_consensus_reset(&self) {
+        if let Some(addressindex) = self.addressindex.upgrade() {
+            addressindex.write().resync().unwrap();
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use rocksdb::WriteBatch;
+use vecno_consensus_core::BlockHasher;
+use vecno_database::{
+    prelude::{BatchDbWriter, CachePolicy, CachedDbAccess, DirectDbWriter, StoreResult, DB},
+    registry::DatabaseStorePrefixes,
+};
+use vecno_hashes::Hash;
+
+use super::history::AddressHistoryKey;
+
+/// Reader API for `AddressIndexChainBlockKeysStore`.
+///
+/// Maps a selected chain block to the keys of the history entries it added, so that
+/// they can be reverted when the block leaves the selected chain.
+pub trait AddressIndexChainBlockKeysStoreReader {
+    fn get(&self, chain_block: Hash) -> StoreResult<Vec<AddressHistoryKey>>;
+}
+
+pub trait AddressIndexChainBlockKeysStore: AddressIndexChainBlockKeysStoreReader {
+    fn delete_all(&mut self) -> StoreResult<()>;
+}
+
+/// A DB + cache implementation of `AddressIndexChainBlockKeysStore` trait
+#[derive(Clone)]
+pub struct DbAddressIndexChainBlockKeysStore {
+    db: Arc<DB>,
+    access: CachedDbAccess<Hash, Vec<AddressHistoryKey>, BlockHasher>,
+}
+
+impl DbAddressIndexChainBlockKeysStore {
+    pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
+        Self {
+            db: Arc::clone(&db),
+            access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::AddressIndexChainBlockKeys.into()),
+        }
+    }
+
+    pub fn insert_batch(&self, batch: &mut WriteBatch, chain_block: Hash, keys: Vec<AddressHistoryKey>) -> StoreResult<()> {
+        self.access.write(BatchDbWriter::new(batch), chain_block, keys)
+    }
+
+    pub fn delete_batch(&self, batch: &mut WriteBatch, chain_block: Hash) -> StoreResult<()> {
+        self.access.delete(BatchDbWriter::new(batch), chain_block)
+    }
+}
+
+impl AddressIndexChainBlockKeysStoreReader for DbAddressIndexChainBlockKeysStore {
+    fn get(&self, chain_block: Hash) -> StoreResult<Vec<AddressHistoryKey>> {
+        self.access.read(chain_block)
+    }
+}
+
+impl AddressIndexChainBlockKeysStore for DbAddressIndexChainBlockKeysStore {
+    fn delete_all(&mut self) -> StoreResult<()> {
+        self.access.delete_all(DirectDbWriter::new(&self.db))
+    }
+}
@@ -0,0 +1,129 @@
+use std::{fmt::Display, sync::Arc};
+
+use rocksdb::WriteBatch;
+use serde::{Deserialize, Serialize};
+use vecno_consensus_core::tx::ScriptPublicKey;
+use vecno_database::{
+    prelude::{BatchDbWriter, CachePolicy, CachedDbAccess, DirectDbWriter, StoreError, StoreResult, DB},
+    registry::DatabaseStorePrefixes,
+};
+
+use crate::model::{AddressHistoryCursor, AddressHistoryEntry, AddressHistoryPage, ADDRESS_HISTORY_CURSOR_SIZE};
+
+/// [`ScriptPublicKeyBucket`].
+/// Consists of 2 bytes of little endian version bytes, followed by 8 bytes of little endian script length and the script itself.
+#[derive(Eq, Hash, PartialEq, Debug, Clone)]
+struct ScriptPublicKeyBucket(Vec<u8>);
+
+impl From<&ScriptPublicKey> for ScriptPublicKeyBucket {
+    fn from(script_public_key: &ScriptPublicKey) -> Self {
+        let mut bytes: Vec<u8> = Vec::with_capacity(size_of::<u16>() + size_of::<u64>() + script_public_key.script().len());
+        bytes.extend_from_slice(&script_public_key.version().to_le_bytes());
+        bytes.extend_from_slice(&(script_public_key.script().len() as u64).to_le_bytes());
+        bytes.extend_from_slice(script_public_key.script());
+        Self(bytes)
+    }
+}
+
+impl AsRef<[u8]> for ScriptPublicKeyBucket {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+}
+
+/// Full [`AddressHistoryEntry`] access key.
+/// Consists of variable amount of bytes of [`ScriptPublicKeyBucket`], and 45 bytes of [`AddressHistoryCursor`]
+#[derive(Eq, Hash, PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct AddressHistoryKey(Vec<u8>);
+
+impl AddressHistoryKey {
+    pub fn new(script_public_key: &ScriptPublicKey, cursor: AddressHistoryCursor) -> Self {
+        Self::from_bucket(&ScriptPublicKeyBucket::from(script_public_key), cursor)
+    }
+
+    fn from_bucket(bucket: &ScriptPublicKeyBucket, cursor: AddressHistoryCursor) -> Self {
+        let mut bytes = Vec::with_capacity(bucket.as_ref().len() + ADDRESS_HISTORY_CURSOR_SIZE);
+        bytes.extend_from_slice(bucket.as_ref());
+        bytes.extend_from_slice(cursor.as_ref());
+        Self(bytes)
+    }
+}
+
+impl Display for AddressHistoryKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl AsRef<[u8]> for AddressHistoryKey {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+}
+
+/// Reader API for `AddressHistoryStore`.
+pub trait AddressHistoryStoreReader {
+    fn get_page(
+        &self,
+        script_public_key: &ScriptPublicKey,
+        cursor: Option<AddressHistoryCursor>,
+        limit: usize,
+    ) -> StoreResult<AddressHistoryPage>;
+}
+
+pub trait AddressHistoryStore: AddressHistoryStoreReader {
+    fn delete_all(&mut self) -> StoreResult<()>;
+}
+
+/// A DB + cache implementation of `AddressHistoryStore` trait
+#[derive(Clone)]
+pub struct DbAddressHistoryStore {
+    db: Arc<DB>,
+    access: CachedDbAccess<AddressHistoryKey, AddressHistoryEntry>,
+}
+
+impl DbAddressHistoryStore {
+    pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
+        Self { db: Arc::clone(&db), access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::AddressIndexHistory.into()) }
+    }
+
+    pub fn insert_batch(&self, batch: &mut WriteBatch, key: AddressHistoryKey, entry: AddressHistoryEntry) -> StoreResult<()> {
+        self.access.write(BatchDbWriter::new(batch), key, entry)
+    }
+
+    pub fn delete_batch(&self, batch: &mut WriteBatch, key: AddressHistoryKey) -> StoreResult<()> {
+        self.access.delete(BatchDbWriter::new(batch), key)
+    }
+}
+
+impl AddressHistoryStoreReader for DbAddressHistoryStore {
+    fn get_page(
+        &self,
+        script_public_key: &ScriptPublicKey,
+        cursor: Option<AddressHistoryCursor>,
+        limit: usize,
+    ) -> StoreResult<AddressHistoryPage> {
+        let bucket = ScriptPublicKeyBucket::from(script_public_key);
+        let seek_from = cursor.map(|cursor| AddressHistoryKey::from_bucket(&bucket, cursor));
+        let mut page = AddressHistoryPage::default();
+        // Fetch one extra entry in order to learn where the next page starts
+        for item in self.access.seek_iterator(Some(bucket.as_ref()), seek_from, limit.saturating_add(1), false) {
+            let (key, entry) = item.map_err(|err| StoreError::DataInconsistency(err.to_string()))?;
+            if page.entries.len() == limit {
+                page.next_cursor = Some(
+                    AddressHistoryCursor::try_from(&key[..])
+                        .map_err(|_| StoreError::DataInconsistency(format!("unexpected address history key size {}", key.len())))?,
+                );
+                break;
+            }
+            page.entries.push(entry);
+        }
+        Ok(page)
+    }
+}
+
+impl AddressHistoryStore for DbAddressHistoryStore {
+    fn delete_all(&mut self) -> StoreResult<()> {
+        self.access.delete_all(DirectDbWriter::new(&self.db))
+    }
+}
@@ -0,0 +1,244 @@
+use std::sync::Arc;
+
+use rocksdb::WriteBatch;
+use vecno_consensus_core::tx::{ScriptPublicKey, SignableTransaction, TransactionOutpoint};
+use vecno_core::trace;
+use vecno_database::prelude::{CachePolicy, StoreError, StoreResult, DB};
+use vecno_hashes::Hash;
+
+use crate::{
+    errors::{AddressIndexError, AddressIndexResult},
+    model::{AddressActivityKind, AddressHistoryCursor, AddressHistoryEntry, AddressHistoryPage},
+    stores::{
+        chain_block_keys::{
+            AddressIndexChainBlockKeysStore, AddressIndexChainBlockKeysStoreReader, DbAddressIndexChainBlockKeysStore,
+        },
+        history::{AddressHistoryKey, AddressHistoryStore, AddressHistoryStoreReader, DbAddressHistoryStore},
+        sink::{AddressIndexSinkStore, AddressIndexSinkStoreReader, DbAddressIndexSinkStore},
+    },
+    IDENT,
+};
+
+/// The populated transactions accepted by a selected chain block, along with its hash and DAA score.
+pub struct AcceptedChainBlock {
+    pub hash: Hash,
+    pub daa_score: u64,
+    pub transactions: Vec<SignableTransaction>,
+}
+
+#[derive(Clone)]
+pub struct Store {
+    db: Arc<DB>,
+    history_store: DbAddressHistoryStore,
+    chain_block_keys_store: DbAddressIndexChainBlockKeysStore,
+    sink_store: DbAddressIndexSinkStore,
+}
+
+impl Store {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self {
+            db: db.clone(),
+            history_store: DbAddressHistoryStore::new(db.clone(), CachePolicy::Empty),
+            chain_block_keys_store: DbAddressIndexChainBlockKeysStore::new(db.clone(), CachePolicy::Empty),
+            sink_store: DbAddressIndexSinkStore::new(db),
+        }
+    }
+
+    pub fn get_address_history(
+        &self,
+        script_public_key: &ScriptPublicKey,
+        cursor: Option<AddressHistoryCursor>,
+        limit: usize,
+    ) -> StoreResult<AddressHistoryPage> {
+        self.history_store.get_page(script_public_key, cursor, limit)
+    }
+
+    pub fn get_sink(&self) -> StoreResult<Hash> {
+        self.sink_store.get()
+    }
+
+    /// Atomically applies a selected chain change to the index.
+    ///
+    /// The entries added by removed chain blocks are deleted before the added chain blocks are recorded,
+    /// so that a transaction re-accepted by one of the added chain blocks keeps its new entries.
+    pub fn apply_chain_changes(
+        &mut self,
+        removed_chain_block_hashes: &[Hash],
+        added_chain_blocks: &[AcceptedChainBlock],
+        sink: Option<Hash>,
+    ) -> AddressIndexResult<()> {
+        let mut batch = WriteBatch::default();
+
+        for &removed in removed_chain_block_hashes {
+            let keys = match self.chain_block_keys_store.get(removed) {
+                Ok(keys) => keys,
+                Err(StoreError::KeyNotFound(_)) => continue,
+                Err(err) => return Err(err.into()),
+            };
+            for key in keys {
+                self.history_store.delete_batch(&mut batch, key)?;
+            }
+            self.chain_block_keys_store.delete_batch(&mut batch, removed)?;
+        }
+
+        for chain_block in added_chain_blocks {
+            let mut keys = Vec::new();
+            for populated_tx in chain_block.transactions.iter() {
+                let transaction_id = populated_tx.tx.id();
+                let mut record = |script_public_key: &ScriptPublicKey,
+                                  kind: AddressActivityKind,
+                                  index: u32,
+                                  outpoint: TransactionOutpoint,
+                                  amount: u64|
+                 -> StoreResult<()> {
+                    let key = AddressHistoryKey::new(
+                        script_public_key,
+                        AddressHistoryCursor::new(chain_block.daa_score, transaction_id, kind, index),
+                    );
+                    let entry = AddressHistoryEntry {
+                        transaction_id,
+                        kind,
+                        outpoint,
+                        amount,
+                        accepting_block: chain_block.hash,
+                        accepting_block_daa_score: chain_block.daa_score,
+                    };
+                    self.history_store.insert_batch(&mut batch, key.clone(), entry)?;
+                    keys.push(key);
+                    Ok(())
+                };
+                for (index, (input, utxo_entry)) in populated_tx.tx.inputs.iter().zip(populated_tx.entries.iter()).enumerate() {
+                    let utxo_entry = utxo_entry.as_ref().ok_or(AddressIndexError::UnpopulatedTransaction(transaction_id))?;
+                    record(
+                        &utxo_entry.script_public_key,
+                        AddressActivityKind::Spent,
+                        index as u32,
+                        input.previous_outpoint,
+                        utxo_entry.amount,
+                    )?;
+                }
+                for (index, output) in populated_tx.tx.outputs.iter().enumerate() {
+                    record(
+                        &output.script_public_key,
+                        AddressActivityKind::Received,
+                        index as u32,
+                        TransactionOutpoint::new(transaction_id, index as u32),
+                        output.value,
+                    )?;
+                }
+            }
+            self.chain_block_keys_store.insert_batch(&mut batch, chain_block.hash, keys)?;
+        }
+
+        if let Some(sink) = sink {
+            self.sink_store.set_batch(&mut batch, sink)?;
+        }
+
+        self.db.write(batch)?;
+        Ok(())
+    }
+
+    pub fn set_sink(&mut self, sink: Hash) -> StoreResult<()> {
+        self.sink_store.set(sink)
+    }
+
+    /// Resets the address index database:
+    pub fn delete_all(&mut self) -> StoreResult<()> {
+        trace!("[{0}] attempting to clear addressindex database...", IDENT);
+
+        // Clear all
+        self.sink_store.remove()?;
+        self.chain_block_keys_store.delete_all()?;
+        self.history_store.delete_all()?;
+
+        trace!("[{0}] clearing addressindex database - success!", IDENT);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vecno_consensus_core::{
+        subnets::SUBNETWORK_ID_NATIVE,
+        tx::{Transaction, TransactionInput, TransactionOutput, UtxoEntry},
+    };
+    use vecno_database::{create_temp_db, prelude::ConnBuilder};
+
+    fn script_public_key(seed: u8) -> ScriptPublicKey {
+        ScriptPublicKey::from_vec(0, vec![seed; 34])
+    }
+
+    /// Builds a transaction spending the given outputs to the given script public keys
+    fn transaction(
+        spent: &[(TransactionOutpoint, &ScriptPublicKey, u64)],
+        outputs: &[(&ScriptPublicKey, u64)],
+    ) -> SignableTransaction {
+        let inputs = spent.iter().map(|&(outpoint, _, _)| TransactionInput::new(outpoint, vec![], 0, 1)).collect();
+        let outputs = outputs.iter().map(|&(spk, amount)| TransactionOutput::new(amount, spk.clone())).collect();
+        let tx = Transaction::new(0, inputs, outputs, 0, SUBNETWORK_ID_NATIVE, 0, vec![]);
+        let entries = spent.iter().map(|&(_, spk, amount)| UtxoEntry::new(amount, spk.clone(), 0, false)).collect();
+        SignableTransaction::with_entries(tx, entries)
+    }
+
+    fn chain_block(seed: u64, daa_score: u64, transactions: Vec<SignableTransaction>) -> AcceptedChainBlock {
+        AcceptedChainBlock { hash: Hash::from_u64_word(seed), daa_score, transactions }
+    }
+
+    fn history(store: &Store, script_public_key: &ScriptPublicKey) -> Vec<(TransactionOutpoint, AddressActivityKind, u64)> {
+        let page = store.get_address_history(script_public_key, None, usize::MAX).unwrap();
+        assert!(page.next_cursor.is_none());
+        page.entries.into_iter().map(|entry| (entry.outpoint, entry.kind, entry.amount)).collect()
+    }
+
+    #[test]
+    fn test_apply_chain_changes() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let mut store = Store::new(db);
+        let (spk_a, spk_b) = (script_public_key(1), script_public_key(2));
+
+        let tx1 = transaction(&[], &[(&spk_a, 100), (&spk_b, 50)]);
+        let (tx1_out0, tx1_out1) = (TransactionOutpoint::new(tx1.id(), 0), TransactionOutpoint::new(tx1.id(), 1));
+        let tx2 = transaction(&[(tx1_out0, &spk_a, 100)], &[(&spk_b, 90)]);
+        let tx2_out0 = TransactionOutpoint::new(tx2.id(), 0);
+        let (block1, block2) = (chain_block(1, 1, vec![tx1]), chain_block(2, 2, vec![tx2]));
+        store.apply_chain_changes(&[], &[block1, block2], Some(Hash::from_u64_word(2))).unwrap();
+        assert_eq!(store.get_sink().unwrap(), Hash::from_u64_word(2));
+        assert_eq!(
+            history(&store, &spk_a),
+            vec![(tx1_out0, AddressActivityKind::Received, 100), (tx1_out0, AddressActivityKind::Spent, 100)]
+        );
+        assert_eq!(
+            history(&store, &spk_b),
+            vec![(tx1_out1, AddressActivityKind::Received, 50), (tx2_out0, AddressActivityKind::Received, 90)]
+        );
+
+        // Paging through the history of B, one entry at a time
+        let page = store.get_address_history(&spk_b, None, 1).unwrap();
+        assert_eq!(page.entries.iter().map(|entry| entry.outpoint).collect::<Vec<_>>(), vec![tx1_out1]);
+        let page = store.get_address_history(&spk_b, page.next_cursor, 1).unwrap();
+        assert_eq!(page.entries.iter().map(|entry| entry.outpoint).collect::<Vec<_>>(), vec![tx2_out0]);
+        assert!(page.next_cursor.is_none());
+
+        // A reorg replacing chain block 2 rolls back the history it added
+        let tx3 = transaction(&[(tx1_out1, &spk_b, 50)], &[(&spk_a, 40)]);
+        let tx3_out0 = TransactionOutpoint::new(tx3.id(), 0);
+        store.apply_chain_changes(&[Hash::from_u64_word(2)], &[chain_block(3, 2, vec![tx3])], Some(Hash::from_u64_word(3))).unwrap();
+        assert_eq!(
+            history(&store, &spk_a),
+            vec![(tx1_out0, AddressActivityKind::Received, 100), (tx3_out0, AddressActivityKind::Received, 40)]
+        );
+        assert_eq!(
+            history(&store, &spk_b),
+            vec![(tx1_out1, AddressActivityKind::Received, 50), (tx1_out1, AddressActivityKind::Spent, 50)]
+        );
+
+        // Transactions missing the entries they spend are rejected without altering the index
+        let unpopulated = SignableTransaction::new(transaction(&[(tx3_out0, &spk_a, 40)], &[]).tx);
+        let result = store.apply_chain_changes(&[], &[chain_block(4, 3, vec![unpopulated])], Some(Hash::from_u64_word(4)));
+        assert!(matches!(result, Err(AddressIndexError::UnpopulatedTransaction(_))));
+        assert_eq!(store.get_sink().unwrap(), Hash::from_u64_word(3));
+        assert_eq!(history(&store, &spk_a).len(), 2);
+    }
+}
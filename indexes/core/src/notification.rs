@@ -6,7 +6,10 @@ use vecno_notify::{
     notification::Notification as NotificationTrait,
     subscription::{
         context::SubscriptionContext,
-        single::{OverallSubscription, UtxosChangedSubscription, VirtualChainChangedSubscription},
+        single::{
+            BlockAddedSubscription, OverallSubscription, TransactionStatusChangedSubscription, UtxosChangedSubscription,
+            VirtualChainChangedSubscription,
+        },
         Subscription,
     },
 };
@@ -31,6 +34,13 @@ impl NotificationTrait for Notification {
         }
     }
 
+    fn apply_block_added_subscription(&self, subscription: &BlockAddedSubscription, _context: &SubscriptionContext) -> Option<Self> {
+        match subscription.active() {
+            true => Some(self.clone()),
+            false => None,
+        }
+    }
+
     fn apply_virtual_chain_changed_subscription(
         &self,
         _subscription: &VirtualChainChangedSubscription,
@@ -53,6 +63,14 @@ impl NotificationTrait for Notification {
         }
     }
 
+    fn apply_transaction_status_changed_subscription(
+        &self,
+        _subscription: &TransactionStatusChangedSubscription,
+        _context: &SubscriptionContext,
+    ) -> Option<Self> {
+        Some(self.clone())
+    }
+
     fn event_type(&self) -> EventType {
         self.into()
     }
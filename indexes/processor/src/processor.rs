@@ -3,6 +3,7 @@ use crate::{
     IDENT,
 };
 use async_trait::async_trait;
+use vecno_addressindex::api::AddressIndexProxy;
 use vecno_consensus_notify::{notification as consensus_notification, notification::Notification as ConsensusNotification};
 use vecno_core::{debug, trace};
 use vecno_index_core::notification::{Notification, PruningPointUtxoSetOverrideNotification, UtxosChangedNotification};
@@ -13,6 +14,7 @@ use vecno_notify::{
     notification::Notification as NotificationTrait,
     notifier::DynNotify,
 };
+use vecno_txindex::api::TxIndexProxy;
 use vecno_utils::triggers::SingleTrigger;
 use vecno_utxoindex::api::UtxoIndexProxy;
 use std::sync::{
@@ -21,7 +23,8 @@ use std::sync::{
 };
 
 /// Processor processes incoming consensus UtxosChanged and PruningPointUtxoSetOverride
-/// notifications submitting them to a UtxoIndex.
+/// notifications submitting them to a UtxoIndex, and VirtualChainChanged notifications
+/// submitting them to a TxIndex and an AddressIndex.
 ///
 /// It also acts as a [`Collector`], converting the incoming consensus notifications
 /// into their pending local versions and relaying them to a local notifier.
@@ -30,6 +33,12 @@ pub struct Processor {
     /// An optional UTXO indexer
     utxoindex: Option<UtxoIndexProxy>,
 
+    /// An optional transaction indexer
+    txindex: Option<TxIndexProxy>,
+
+    /// An optional address history indexer
+    addressindex: Option<AddressIndexProxy>,
+
     recv_channel: CollectorNotificationReceiver<ConsensusNotification>,
 
     /// Has this collector been started?
@@ -39,9 +48,16 @@ pub struct Processor {
 }
 
 impl Processor {
-    pub fn new(utxoindex: Option<UtxoIndexProxy>, recv_channel: CollectorNotificationReceiver<ConsensusNotification>) -> Self {
+    pub fn new(
+        utxoindex: Option<UtxoIndexProxy>,
+        txindex: Option<TxIndexProxy>,
+        addressindex: Option<AddressIndexProxy>,
+        recv_channel: CollectorNotificationReceiver<ConsensusNotification>,
+    ) -> Self {
         Self {
             utxoindex,
+            txindex,
+            addressindex,
             recv_channel,
             collect_shutdown: Arc::new(SingleTrigger::new()),
             is_started: Arc::new(AtomicBool::new(false)),
@@ -58,12 +74,13 @@ impl Processor {
 
             while let Ok(notification) = self.recv_channel.recv().await {
                 match self.process_notification(notification).await {
-                    Ok(notification) => match notifier.notify(notification) {
+                    Ok(Some(notification)) => match notifier.notify(notification) {
                         Ok(_) => (),
                         Err(err) => {
                             trace!("[Index processor] notification sender error: {err:?}");
                         }
                     },
+                    Ok(None) => (),
                     Err(err) => {
                         trace!("[Index processor] error while processing a consensus notification: {err:?}");
                     }
@@ -76,13 +93,18 @@ impl Processor {
         });
     }
 
-    async fn process_notification(self: &Arc<Self>, notification: ConsensusNotification) -> IndexResult<Notification> {
+    /// Processes a consensus notification, returning the index notification to relay, if any.
+    async fn process_notification(self: &Arc<Self>, notification: ConsensusNotification) -> IndexResult<Option<Notification>> {
         match notification {
             ConsensusNotification::UtxosChanged(utxos_changed) => {
-                Ok(Notification::UtxosChanged(self.process_utxos_changed(utxos_changed).await?))
+                Ok(Some(Notification::UtxosChanged(self.process_utxos_changed(utxos_changed).await?)))
             }
             ConsensusNotification::PruningPointUtxoSetOverride(_) => {
-                Ok(Notification::PruningPointUtxoSetOverride(PruningPointUtxoSetOverrideNotification {}))
+                Ok(Some(Notification::PruningPointUtxoSetOverride(PruningPointUtxoSetOverrideNotification {})))
+            }
+            ConsensusNotification::VirtualChainChanged(virtual_chain_changed) => {
+                self.process_virtual_chain_changed(virtual_chain_changed).await?;
+                Ok(None)
             }
             _ => Err(IndexError::NotSupported(notification.event_type())),
         }
@@ -106,6 +128,39 @@ impl Processor {
         Err(IndexError::NotSupported(EventType::UtxosChanged))
     }
 
+    async fn process_virtual_chain_changed(
+        self: &Arc<Self>,
+        notification: consensus_notification::VirtualChainChangedNotification,
+    ) -> IndexResult<()> {
+        trace!("[{IDENT}]: processing {:?}", notification);
+        if self.txindex.is_none() && self.addressindex.is_none() {
+            return Err(IndexError::NotSupported(EventType::VirtualChainChanged));
+        }
+        if let Some(txindex) = self.txindex.clone() {
+            debug!(
+                "IDXPRC, Updating the txindex with {} added and {} removed chain blocks",
+                notification.added_chain_block_hashes.len(),
+                notification.removed_chain_block_hashes.len()
+            );
+            txindex
+                .update(
+                    notification.removed_chain_block_hashes.clone(),
+                    notification.added_chain_block_hashes.clone(),
+                    notification.added_chain_blocks_acceptance_data.clone(),
+                )
+                .await?;
+        }
+        if let Some(addressindex) = self.addressindex.clone() {
+            debug!(
+                "IDXPRC, Updating the addressindex with {} added and {} removed chain blocks",
+                notification.added_chain_block_hashes.len(),
+                notification.removed_chain_block_hashes.len()
+            );
+            addressindex.update(notification.removed_chain_block_hashes, notification.added_chain_block_hashes).await?;
+        }
+        Ok(())
+    }
+
     async fn join_collecting_task(&self) -> Result<()> {
         trace!("[Index processor] joining");
         self.collect_shutdown.listener.clone().await;
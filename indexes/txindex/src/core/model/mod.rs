@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+use vecno_hashes::Hash;
+use vecno_utils::mem_size::MemSizeEstimator;
+
+/// Locates an accepted transaction within the DAG.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxIndexEntry {
+    /// The block containing the transaction
+    pub containing_block: Hash,
+    /// The selected chain block whose mergeset accepted the transaction
+    pub accepting_block: Hash,
+    /// The position of the transaction within the containing block
+    pub index_within_block: u32,
+}
+
+impl TxIndexEntry {
+    pub fn new(containing_block: Hash, accepting_block: Hash, index_within_block: u32) -> Self {
+        Self { containing_block, accepting_block, index_within_block }
+    }
+}
+
+impl MemSizeEstimator for TxIndexEntry {}
@@ -0,0 +1,160 @@
+use crate::{
+    api::TxIndexApi,
+    errors::{TxIndexError, TxIndexResult},
+    model::TxIndexEntry,
+    stores::store_manager::Store,
+    IDENT,
+};
+use vecno_consensus_core::{acceptance_data::AcceptanceData, tx::TransactionId};
+use vecno_consensusmanager::{ConsensusManager, ConsensusResetHandler};
+use vecno_core::{info, trace};
+use vecno_database::prelude::{StoreError, StoreResult, DB};
+use vecno_hashes::Hash;
+use parking_lot::RwLock;
+use std::{
+    fmt::Debug,
+    sync::{Arc, Weak},
+};
+
+const RESYNC_CHUNK_SIZE: usize = 1024;
+
+/// TxIndex indexes accepted transactions by [`TransactionId`], recording the block containing each
+/// transaction and the selected chain block which accepted it.
+/// Note: The TxIndex struct by itself is not thread save, only correct usage of the supplied RwLock via `new` makes it so.
+/// please follow guidelines found in the comments under `txindex::core::api::TxIndexApi` for proper thread safety.
+pub struct TxIndex {
+    consensus_manager: Arc<ConsensusManager>,
+    store: Store,
+}
+
+impl TxIndex {
+    /// Creates a new [`TxIndex`] within a [`RwLock`]
+    pub fn new(consensus_manager: Arc<ConsensusManager>, db: Arc<DB>) -> TxIndexResult<Arc<RwLock<Self>>> {
+        let mut txindex = Self { consensus_manager: consensus_manager.clone(), store: Store::new(db) };
+        if !txindex.is_synced()? {
+            txindex.resync()?;
+        }
+        let txindex = Arc::new(RwLock::new(txindex));
+        consensus_manager.register_consensus_reset_handler(Arc::new(TxIndexConsensusResetHandler::new(Arc::downgrade(&txindex))));
+        Ok(txindex)
+    }
+}
+
+impl TxIndexApi for TxIndex {
+    /// Retrieve the location of an accepted transaction from the txindex db.
+    fn get_transaction_entry(&self, transaction_id: TransactionId) -> StoreResult<Option<TxIndexEntry>> {
+        trace!("[{0}] retrieving entry of transaction {1}", IDENT, transaction_id);
+
+        self.store.get_entry(transaction_id)
+    }
+
+    /// Retrieve the stored sink of the txindex.
+    fn get_sink(&self) -> StoreResult<Hash> {
+        trace!("[{0}] retrieving sink", IDENT);
+
+        self.store.get_sink()
+    }
+
+    /// Checks to see if the [TxIndex] is sync'd. This is done via comparing the txindex committed sink with the one of the consensus database.
+    ///
+    /// **Note:** Due to sync gaps between the txindex and consensus, this function is only reliable while consensus is not processing new blocks.
+    fn is_synced(&self) -> TxIndexResult<bool> {
+        trace!("[{0}] checking sync status...", IDENT);
+
+        let consensus = self.consensus_manager.consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        match self.store.get_sink() {
+            Ok(txindex_sink) => {
+                let res = txindex_sink == session.get_sink();
+                trace!("[{0}] sync status is {1}", IDENT, res);
+                Ok(res)
+            }
+            Err(StoreError::KeyNotFound(_)) => {
+                //Means txindex sink database is empty i.e. not sync'd.
+                trace!("[{0}] sync status is {1}", IDENT, false);
+                Ok(false)
+            }
+            Err(other_store_errors) => Err(TxIndexError::StoreAccessError(other_store_errors)),
+        }
+    }
+
+    /// Updates the [TxIndex] with a selected chain change:
+    /// 1) Reverts the transactions accepted by the removed chain blocks.
+    /// 2) Records the transactions accepted by the added chain blocks and the new sink.
+    fn update(
+        &mut self,
+        removed_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_blocks_acceptance_data: Arc<Vec<Arc<AcceptanceData>>>,
+    ) -> TxIndexResult<()> {
+        trace!("[{0}] updating...", IDENT);
+        trace!("[{0}] adding {1} chain blocks", IDENT, added_chain_block_hashes.len());
+        trace!("[{0}] removing {1} chain blocks", IDENT, removed_chain_block_hashes.len());
+
+        self.store.apply_chain_changes(
+            &removed_chain_block_hashes,
+            &added_chain_block_hashes,
+            &added_chain_blocks_acceptance_data,
+            added_chain_block_hashes.last().copied(),
+        )?;
+        Ok(())
+    }
+
+    /// Deletes and reinstates the txindex database, syncing it from scratch by walking the selected chain
+    /// from the retention period root up to the sink.
+    ///
+    /// **Notes:**
+    /// 1) Transactions accepted by the retention period root itself or earlier are not indexed.
+    /// 2) resyncing while consensus notifies of chain changes, may result in a corrupted db.
+    fn resync(&mut self) -> TxIndexResult<()> {
+        info!("Resyncing the txindex...");
+
+        self.store.delete_all()?;
+        let consensus = self.consensus_manager.consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        let mut low = session.get_retention_period_root();
+        loop {
+            let chain_path = session.get_virtual_chain_from_block(low, Some(RESYNC_CHUNK_SIZE))?;
+            let Some(&last) = chain_path.added.last() else {
+                break;
+            };
+            trace!("[{0}] resyncing with batch of {1} chain blocks from consensus db", IDENT, chain_path.added.len());
+            let acceptance_data = session.get_blocks_acceptance_data(&chain_path.added, None)?;
+            self.store.apply_chain_changes(&[], &chain_path.added, &acceptance_data, None)?;
+            low = last;
+        }
+
+        // Commit the sink only once the whole chain was indexed, so that an interrupted resync is restarted
+        let sink = session.get_sink();
+        trace!("[{0}] committing sink {1} from consensus db", IDENT, sink);
+        self.store.set_sink(sink)?;
+
+        Ok(())
+    }
+}
+
+impl Debug for TxIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TxIndex").finish()
+    }
+}
+
+struct TxIndexConsensusResetHandler {
+    txindex: Weak<RwLock<TxIndex>>,
+}
+
+impl TxIndexConsensusResetHandler {
+    fn new(txindex: Weak<RwLock<TxIndex>>) -> Self {
+        Self { txindex }
+    }
+}
+
+impl ConsensusResetHandler for TxIndexConsensusResetHandler {
+    fn handle_consensus_reset(&self) {
+        if let Some(txindex) = self.txindex.upgrade() {
+            txindex.write().resync().unwrap();
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use rocksdb::WriteBatch;
+use vecno_consensus_core::{tx::TransactionId, BlockHasher};
+use vecno_database::{
+    prelude::{BatchDbWriter, CachePolicy, CachedDbAccess, DirectDbWriter, StoreResult, DB},
+    registry::DatabaseStorePrefixes,
+};
+use vecno_hashes::Hash;
+
+/// Reader API for `TxIndexAcceptedTransactionsStore`.
+///
+/// Maps a selected chain block to the ids of the transactions it accepted, so that
+/// index entries can be reverted when the block leaves the selected chain.
+pub trait TxIndexAcceptedTransactionsStoreReader {
+    fn get(&self, accepting_block: Hash) -> StoreResult<Vec<TransactionId>>;
+}
+
+pub trait TxIndexAcceptedTransactionsStore: TxIndexAcceptedTransactionsStoreReader {
+    fn delete_all(&mut self) -> StoreResult<()>;
+}
+
+/// A DB + cache implementation of `TxIndexAcceptedTransactionsStore` trait
+#[derive(Clone)]
+pub struct DbTxIndexAcceptedTransactionsStore {
+    db: Arc<DB>,
+    access: CachedDbAccess<Hash, Vec<TransactionId>, BlockHasher>,
+}
+
+impl DbTxIndexAcceptedTransactionsStore {
+    pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
+        Self {
+            db: Arc::clone(&db),
+            access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::TxIndexAcceptedTransactions.into()),
+        }
+    }
+
+    pub fn insert_batch(&self, batch: &mut WriteBatch, accepting_block: Hash, transaction_ids: Vec<TransactionId>) -> StoreResult<()> {
+        self.access.write(BatchDbWriter::new(batch), accepting_block, transaction_ids)
+    }
+
+    pub fn delete_batch(&self, batch: &mut WriteBatch, accepting_block: Hash) -> StoreResult<()> {
+        self.access.delete(BatchDbWriter::new(batch), accepting_block)
+    }
+}
+
+impl TxIndexAcceptedTransactionsStoreReader for DbTxIndexAcceptedTransactionsStore {
+    fn get(&self, accepting_block: Hash) -> StoreResult<Vec<TransactionId>> {
+        self.access.read(accepting_block)
+    }
+}
+
+impl TxIndexAcceptedTransactionsStore for DbTxIndexAcceptedTransactionsStore {
+    fn delete_all(&mut self) -> StoreResult<()> {
+        self.access.delete_all(DirectDbWriter::new(&self.db))
+    }
+}
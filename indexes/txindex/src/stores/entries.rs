@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use rocksdb::WriteBatch;
+use vecno_consensus_core::{tx::TransactionId, BlockHasher};
+use vecno_database::{
+    prelude::{BatchDbWriter, CachePolicy, CachedDbAccess, DirectDbWriter, StoreResult, DB},
+    registry::DatabaseStorePrefixes,
+};
+
+use crate::model::TxIndexEntry;
+
+/// Reader API for `TxIndexEntriesStore`.
+pub trait TxIndexEntriesStoreReader {
+    fn get(&self, transaction_id: TransactionId) -> StoreResult<TxIndexEntry>;
+}
+
+pub trait TxIndexEntriesStore: TxIndexEntriesStoreReader {
+    fn delete_all(&mut self) -> StoreResult<()>;
+}
+
+/// A DB + cache implementation of `TxIndexEntriesStore` trait
+#[derive(Clone)]
+pub struct DbTxIndexEntriesStore {
+    db: Arc<DB>,
+    access: CachedDbAccess<TransactionId, TxIndexEntry, BlockHasher>,
+}
+
+impl DbTxIndexEntriesStore {
+    pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
+        Self { db: Arc::clone(&db), access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::TxIndexEntries.into()) }
+    }
+
+    pub fn insert_batch(&self, batch: &mut WriteBatch, transaction_id: TransactionId, entry: TxIndexEntry) -> StoreResult<()> {
+        self.access.write(BatchDbWriter::new(batch), transaction_id, entry)
+    }
+
+    pub fn delete_batch(&self, batch: &mut WriteBatch, transaction_id: TransactionId) -> StoreResult<()> {
+        self.access.delete(BatchDbWriter::new(batch), transaction_id)
+    }
+}
+
+impl TxIndexEntriesStoreReader for DbTxIndexEntriesStore {
+    fn get(&self, transaction_id: TransactionId) -> StoreResult<TxIndexEntry> {
+        self.access.read(transaction_id)
+    }
+}
+
+impl TxIndexEntriesStore for DbTxIndexEntriesStore {
+    fn delete_all(&mut self) -> StoreResult<()> {
+        self.access.delete_all(DirectDbWriter::new(&self.db))
+    }
+}
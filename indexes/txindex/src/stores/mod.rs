@@ -0,0 +1,4 @@
+mod accepted;
+mod entries;
+mod sink;
+pub mod store_manager;
@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use rocksdb::WriteBatch;
+use vecno_database::{
+    prelude::{BatchDbWriter, CachedDbItem, DirectDbWriter, StoreError, StoreResult, DB},
+    registry::DatabaseStorePrefixes,
+};
+use vecno_hashes::Hash;
+
+/// Reader API for `TxIndexSinkStore`.
+pub trait TxIndexSinkStoreReader {
+    fn get(&self) -> StoreResult<Hash>;
+}
+
+pub trait TxIndexSinkStore: TxIndexSinkStoreReader {
+    fn set(&mut self, sink: Hash) -> StoreResult<()>;
+    fn remove(&mut self) -> Result<(), StoreError>;
+}
+
+/// A DB + cache implementation of `TxIndexSinkStore` trait
+#[derive(Clone)]
+pub struct DbTxIndexSinkStore {
+    db: Arc<DB>,
+    access: CachedDbItem<Hash>,
+}
+
+impl DbTxIndexSinkStore {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self { db: Arc::clone(&db), access: CachedDbItem::new(db.clone(), DatabaseStorePrefixes::TxIndexSink.into()) }
+    }
+
+    pub fn set_batch(&mut self, batch: &mut WriteBatch, sink: Hash) -> StoreResult<()> {
+        self.access.write(BatchDbWriter::new(batch), &sink)
+    }
+}
+
+impl TxIndexSinkStoreReader for DbTxIndexSinkStore {
+    fn get(&self) -> StoreResult<Hash> {
+        self.access.read()
+    }
+}
+
+impl TxIndexSinkStore for DbTxIndexSinkStore {
+    fn set(&mut self, sink: Hash) -> StoreResult<()> {
+        self.access.write(DirectDbWriter::new(&self.db), &sink)
+    }
+
+    fn remove(&mut self) -> Result<(), StoreError> {
+        self.access.remove(DirectDbWriter::new(&self.db))
+    }
+}
@@ -0,0 +1,187 @@
+use std::sync::Arc;
+
+use rocksdb::WriteBatch;
+use vecno_consensus_core::acceptance_data::AcceptanceData;
+use vecno_core::trace;
+use vecno_database::prelude::{CachePolicy, StoreError, StoreResult, DB};
+use vecno_hashes::Hash;
+
+use crate::{
+    model::TxIndexEntry,
+    stores::{
+        accepted::{DbTxIndexAcceptedTransactionsStore, TxIndexAcceptedTransactionsStore, TxIndexAcceptedTransactionsStoreReader},
+        entries::{DbTxIndexEntriesStore, TxIndexEntriesStore, TxIndexEntriesStoreReader},
+        sink::{DbTxIndexSinkStore, TxIndexSinkStore, TxIndexSinkStoreReader},
+    },
+    IDENT,
+};
+
+#[derive(Clone)]
+pub struct Store {
+    db: Arc<DB>,
+    entries_store: DbTxIndexEntriesStore,
+    accepted_transactions_store: DbTxIndexAcceptedTransactionsStore,
+    sink_store: DbTxIndexSinkStore,
+}
+
+impl Store {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self {
+            db: db.clone(),
+            entries_store: DbTxIndexEntriesStore::new(db.clone(), CachePolicy::Empty),
+            accepted_transactions_store: DbTxIndexAcceptedTransactionsStore::new(db.clone(), CachePolicy::Empty),
+            sink_store: DbTxIndexSinkStore::new(db),
+        }
+    }
+
+    pub fn get_entry(&self, transaction_id: Hash) -> StoreResult<Option<TxIndexEntry>> {
+        optional(self.entries_store.get(transaction_id))
+    }
+
+    pub fn get_sink(&self) -> StoreResult<Hash> {
+        self.sink_store.get()
+    }
+
+    /// Atomically applies a selected chain change to the index.
+    ///
+    /// Removed chain blocks are reverted first so that a transaction re-accepted by
+    /// one of the added chain blocks ends up pointing to its new accepting block.
+    pub fn apply_chain_changes(
+        &mut self,
+        removed_chain_block_hashes: &[Hash],
+        added_chain_block_hashes: &[Hash],
+        added_chain_blocks_acceptance_data: &[Arc<AcceptanceData>],
+        sink: Option<Hash>,
+    ) -> StoreResult<()> {
+        let mut batch = WriteBatch::default();
+
+        for &removed in removed_chain_block_hashes {
+            let Some(transaction_ids) = optional(self.accepted_transactions_store.get(removed))? else {
+                continue;
+            };
+            for transaction_id in transaction_ids {
+                if let Some(entry) = optional(self.entries_store.get(transaction_id))? {
+                    if entry.accepting_block == removed {
+                        self.entries_store.delete_batch(&mut batch, transaction_id)?;
+                    }
+                }
+            }
+            self.accepted_transactions_store.delete_batch(&mut batch, removed)?;
+        }
+
+        for (&accepting_block, acceptance_data) in added_chain_block_hashes.iter().zip(added_chain_blocks_acceptance_data.iter()) {
+            let mut transaction_ids = Vec::with_capacity(acceptance_data.iter().map(|m| m.accepted_transactions.len()).sum());
+            for mergeset_block in acceptance_data.iter() {
+                for accepted in mergeset_block.accepted_transactions.iter() {
+                    self.entries_store.insert_batch(
+                        &mut batch,
+                        accepted.transaction_id,
+                        TxIndexEntry::new(mergeset_block.block_hash, accepting_block, accepted.index_within_block),
+                    )?;
+                    transaction_ids.push(accepted.transaction_id);
+                }
+            }
+            self.accepted_transactions_store.insert_batch(&mut batch, accepting_block, transaction_ids)?;
+        }
+
+        if let Some(sink) = sink {
+            self.sink_store.set_batch(&mut batch, sink)?;
+        }
+
+        self.db.write(batch)?;
+        Ok(())
+    }
+
+    pub fn set_sink(&mut self, sink: Hash) -> StoreResult<()> {
+        self.sink_store.set(sink)
+    }
+
+    /// Resets the txindex database:
+    pub fn delete_all(&mut self) -> StoreResult<()> {
+        trace!("[{0}] attempting to clear txindex database...", IDENT);
+
+        // Clear all
+        self.sink_store.remove()?;
+        self.accepted_transactions_store.delete_all()?;
+        self.entries_store.delete_all()?;
+
+        trace!("[{0}] clearing txindex database - success!", IDENT);
+
+        Ok(())
+    }
+}
+
+/// Maps a missing key to `None`, propagating any other store error
+fn optional<T>(result: StoreResult<T>) -> StoreResult<Option<T>> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(StoreError::KeyNotFound(_)) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vecno_consensus_core::acceptance_data::{AcceptedTxEntry, MergesetBlockAcceptanceData};
+    use vecno_database::{create_temp_db, prelude::ConnBuilder};
+
+    fn acceptance_data(mergeset: &[(u64, &[u64])]) -> Arc<AcceptanceData> {
+        Arc::new(
+            mergeset
+                .iter()
+                .map(|&(block, transactions)| MergesetBlockAcceptanceData {
+                    block_hash: Hash::from_u64_word(block),
+                    accepted_transactions: transactions
+                        .iter()
+                        .enumerate()
+                        .map(|(i, &tx)| AcceptedTxEntry { transaction_id: Hash::from_u64_word(tx), index_within_block: i as u32 })
+                        .collect(),
+                })
+                .collect(),
+        )
+    }
+
+    fn entry(store: &Store, transaction: u64) -> Option<TxIndexEntry> {
+        store.get_entry(Hash::from_u64_word(transaction)).unwrap()
+    }
+
+    #[test]
+    fn test_apply_chain_changes() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let mut store = Store::new(db);
+        let (chain1, chain2, chain3) = (Hash::from_u64_word(1), Hash::from_u64_word(2), Hash::from_u64_word(3));
+
+        // Chain blocks 1 and 2 accept transactions of their mergesets
+        store
+            .apply_chain_changes(
+                &[],
+                &[chain1, chain2],
+                &[acceptance_data(&[(1, &[10, 11])]), acceptance_data(&[(2, &[12]), (4, &[13])])],
+                Some(chain2),
+            )
+            .unwrap();
+        assert_eq!(store.get_sink().unwrap(), chain2);
+        assert_eq!(entry(&store, 11), Some(TxIndexEntry::new(chain1, chain1, 1)));
+        assert_eq!(entry(&store, 13), Some(TxIndexEntry::new(Hash::from_u64_word(4), chain2, 0)));
+        assert_eq!(entry(&store, 14), None);
+
+        // A reorg replaces chain block 2 by chain block 3, which re-accepts transaction 13 only
+        store.apply_chain_changes(&[chain2], &[chain3], &[acceptance_data(&[(4, &[13])])], Some(chain3)).unwrap();
+        assert_eq!(store.get_sink().unwrap(), chain3);
+        assert_eq!(entry(&store, 10), Some(TxIndexEntry::new(chain1, chain1, 0)));
+        assert_eq!(entry(&store, 12), None);
+        assert_eq!(entry(&store, 13), Some(TxIndexEntry::new(Hash::from_u64_word(4), chain3, 0)));
+
+        // Removing chain blocks unknown to the index is a no-op
+        store.apply_chain_changes(&[Hash::from_u64_word(5)], &[], &[], None).unwrap();
+        assert_eq!(entry(&store, 13), Some(TxIndexEntry::new(Hash::from_u64_word(4), chain3, 0)));
+
+        // Removing all chain blocks empties the index
+        store.apply_chain_changes(&[chain3, chain1], &[], &[], None).unwrap();
+        assert!([10, 11, 12, 13].into_iter().all(|tx| entry(&store, tx).is_none()));
+
+        store.delete_all().unwrap();
+        assert!(matches!(store.get_sink(), Err(StoreError::KeyNotFound(_))));
+    }
+}
@@ -11,6 +11,9 @@ construct_uint!(Uint256, 4);
 construct_uint!(Uint320, 5);
 construct_uint!(Uint3072, 48);
 
+#[cfg(feature = "schemars")]
+vecno_utils::json_schema_impl_hex_string!(Uint192, Some(Uint192::BYTES));
+
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error("{0:?}")]
@@ -78,6 +78,24 @@ pub enum RuleError {
 
     #[error("Rejected tx {0} from mempool due to incomputable storage mass")]
     RejectStorageMassIncomputable(TransactionId),
+
+    #[error("package contains no transaction")]
+    RejectEmptyPackage,
+
+    #[error("package of {0} transactions is larger than the maximum allowed of {1}")]
+    RejectPackageTooLarge(usize, usize),
+
+    #[error("transaction {0} appears more than once in the package")]
+    RejectPackageDuplicate(TransactionId),
+
+    #[error("output {0} is spent by both transactions {1} and {2} of the package")]
+    RejectPackageDoubleSpend(TransactionOutpoint, TransactionId, TransactionId),
+
+    #[error("transaction {0} neither spends nor funds any other transaction of the package")]
+    RejectPackageUnrelatedTransaction(TransactionId),
+
+    #[error("package has {0} fees which is under the required amount of {1}")]
+    RejectPackageInsufficientFee(u64, u64),
 }
 
 impl From<NonStandardError> for RuleError {
@@ -132,6 +150,15 @@ pub enum NonStandardError {
 
     #[error("transaction input #{1} has {2} signature operations which is more than the allowed max amount of {3}")]
     RejectSignatureCount(TransactionId, usize, u64, u8),
+
+    #[error("transaction payload size of {1} bytes is larger than the maximum allowed size of {2} bytes")]
+    RejectPayloadSize(TransactionId, usize, usize),
+
+    #[error("transaction paying to {1} outputs has a size of {2} bytes which is larger than the maximum allowed size of {3} bytes")]
+    RejectScriptClassSize(TransactionId, String, u64, u64),
+
+    #[error("transactions paying to {1} outputs exceed the maximum allowed rate of {2} per minute")]
+    RejectScriptClassRate(TransactionId, String, u32),
 }
 
 impl NonStandardError {
@@ -148,6 +175,9 @@ impl NonStandardError {
             NonStandardError::RejectInputScriptClass(id, _) => id,
             NonStandardError::RejectInsufficientFee(id, _, _) => id,
             NonStandardError::RejectSignatureCount(id, _, _, _) => id,
+            NonStandardError::RejectPayloadSize(id, _, _) => id,
+            NonStandardError::RejectScriptClassSize(id, _, _, _) => id,
+            NonStandardError::RejectScriptClassRate(id, _, _) => id,
         }
     }
 }
@@ -1,9 +1,12 @@
 use super::errors::BuilderResult;
+use crate::model::candidate_tx::CandidateTransaction;
 use vecno_consensus_core::{
-    api::ConsensusApi,
+    api::{args::TransactionValidationArgs, ConsensusApi},
     block::{BlockTemplate, TemplateBuildMode, TemplateTransactionSelector},
     coinbase::MinerData,
-    tx::COINBASE_TRANSACTION_INDEX,
+    errors::tx::TxResult,
+    mass::ContextualMasses,
+    tx::{MutableTransaction, Transaction, COINBASE_TRANSACTION_INDEX},
 };
 use vecno_core::time::{unix_now, Stopwatch};
 
@@ -88,6 +91,21 @@ impl BlockTemplateBuilder {
         Ok(consensus.build_block_template(miner_data.clone(), selector, build_mode)?)
     }
 
+    /// Validates a caller-supplied transaction against the current virtual state so it can be selected
+    /// as a mandatory transaction of a block template, populating its fee and mass
+    pub(crate) fn validate_mandatory_transaction(
+        consensus: &dyn ConsensusApi,
+        transaction: Transaction,
+    ) -> TxResult<CandidateTransaction> {
+        let mut transaction = MutableTransaction::from_tx(transaction);
+        let non_contextual_masses = consensus.calculate_transaction_non_contextual_masses(&transaction.tx);
+        transaction.calculated_non_contextual_masses = Some(non_contextual_masses);
+        consensus.validate_mempool_transaction(&mut transaction, &TransactionValidationArgs::default())?;
+        let calculated_fee = transaction.calculated_fee.expect("fee is expected to be populated");
+        let calculated_mass = ContextualMasses::new(transaction.tx.mass()).max(non_contextual_masses);
+        Ok(CandidateTransaction { tx: transaction.tx, calculated_fee, calculated_mass })
+    }
+
     /// modify_block_template clones an existing block template, modifies it to the requested coinbase data and updates the timestamp
     pub(crate) fn modify_block_template(
         consensus: &dyn ConsensusApi,
@@ -1,6 +1,10 @@
 use vecno_core::{time::Stopwatch, trace};
+use parking_lot::Mutex;
 use rand::Rng;
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 use crate::model::candidate_tx::CandidateTransaction;
 
@@ -256,6 +260,71 @@ impl TemplateTransactionSelector for RebalancingWeightedTransactionSelector {
     }
 }
 
+/// Selects caller-supplied mandatory transactions ahead of any other, then fills the remaining block mass
+/// with a [`RebalancingWeightedTransactionSelector`] over the other candidate transactions.
+///
+/// Callers are expected to only provide mandatory transactions which fit in the block mass altogether.
+pub struct MandatoryTransactionsSelector {
+    /// Transactions selected by the first selection call
+    mandatory: Vec<CandidateTransaction>,
+    mandatory_ids: HashSet<TransactionId>,
+    is_mandatory_selected: bool,
+
+    /// Ids of the mandatory transactions rejected during the selection
+    rejections: Arc<Mutex<Vec<TransactionId>>>,
+
+    /// Selector of the transactions filling the remaining block mass
+    filler: RebalancingWeightedTransactionSelector,
+}
+
+impl MandatoryTransactionsSelector {
+    pub fn new(policy: Policy, mandatory: Vec<CandidateTransaction>, mut transactions: Vec<CandidateTransaction>) -> Self {
+        let mandatory_mass = mandatory.iter().map(|tx| tx.calculated_mass).sum::<u64>();
+        let remaining_mass = policy.max_block_mass.saturating_sub(mandatory_mass);
+        if remaining_mass == 0 {
+            // The rebalancing selector cannot value transactions against an empty block
+            transactions.clear();
+        }
+        Self {
+            mandatory_ids: mandatory.iter().map(|tx| tx.tx.id()).collect(),
+            mandatory,
+            is_mandatory_selected: false,
+            rejections: Default::default(),
+            filler: RebalancingWeightedTransactionSelector::new(Policy::new(remaining_mass.max(1)), transactions),
+        }
+    }
+
+    /// Returns a handle on the ids of the mandatory transactions rejected during the selection, which
+    /// remains readable once the selector has been consumed
+    pub fn rejections(&self) -> Arc<Mutex<Vec<TransactionId>>> {
+        self.rejections.clone()
+    }
+}
+
+impl TemplateTransactionSelector for MandatoryTransactionsSelector {
+    fn select_transactions(&mut self) -> Vec<Transaction> {
+        let mut transactions = Vec::new();
+        if !self.is_mandatory_selected {
+            self.is_mandatory_selected = true;
+            transactions.extend(self.mandatory.iter().map(|x| x.tx.as_ref().clone()));
+        }
+        transactions.extend(self.filler.select_transactions());
+        transactions
+    }
+
+    fn reject_selection(&mut self, tx_id: TransactionId) {
+        if self.mandatory_ids.contains(&tx_id) {
+            self.rejections.lock().push(tx_id);
+        } else {
+            self.filler.reject_selection(tx_id);
+        }
+    }
+
+    fn is_successful(&self) -> bool {
+        self.filler.is_successful()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -323,6 +392,37 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_mandatory_transactions_selector() {
+        let transactions = (0..100).map(|i| create_transaction(VENI_PER_VECNO * (i + 1) as u64)).collect_vec();
+        let mass = transactions[0].calculated_mass;
+        let (mandatory, others) = transactions.split_at(3);
+
+        // The block fits the mandatory transactions and 10 others
+        let policy = Policy::new(mass * 13);
+        let mut selector = MandatoryTransactionsSelector::new(policy, mandatory.to_vec(), others.to_vec());
+        let rejections = selector.rejections();
+
+        let selected_txs = selector.select_transactions();
+        assert_eq!(selected_txs.len(), 13);
+        assert!(mandatory.iter().all(|x| selected_txs.iter().any(|tx| tx.id() == x.tx.id())));
+
+        // Rejected mandatory transactions are reported and not replaced
+        selector.reject_selection(mandatory[1].tx.id());
+        assert_eq!(*rejections.lock(), vec![mandatory[1].tx.id()]);
+        assert!(selector.select_transactions().is_empty());
+
+        // Rejected fillers are replaced
+        let filler = selected_txs.iter().find(|tx| !mandatory.iter().any(|x| x.tx.id() == tx.id())).unwrap();
+        selector.reject_selection(filler.id());
+        assert_eq!(selector.select_transactions().len(), 1);
+
+        // Mandatory transactions filling the whole block leave no room for others
+        let policy = Policy::new(mass * 3);
+        let mut selector = MandatoryTransactionsSelector::new(policy, mandatory.to_vec(), others.to_vec());
+        assert_eq!(selector.select_transactions().len(), 3);
+    }
+
     fn create_transaction(value: u64) -> CandidateTransaction {
         let previous_outpoint = TransactionOutpoint::new(TransactionId::default(), 0);
         let (script_public_key, redeem_script) = op_true_script();
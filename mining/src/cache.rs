@@ -1,11 +1,28 @@
 use vecno_consensus_core::block::{BlockTemplate, VirtualStateApproxId};
 use vecno_core::time::unix_now;
+use vecno_hashes::Hash;
 use parking_lot::{Mutex, MutexGuard};
 use std::sync::Arc;
+use tokio::sync::watch;
 
 /// CACHE_LIFETIME indicates the default duration in milliseconds after which the cached data expires.
 const DEFAULT_CACHE_LIFETIME: u64 = 1_000;
 
+/// Minimal increase, in percent, of the total fees of a block template over the last published one for the
+/// new template to be considered materially different when built over the same sink
+const MATERIAL_FEE_INCREASE_PERCENT: u64 = 10;
+
+/// Identifies a block template up to its coinbase and timestamp. Consecutive templates built over the same sink
+/// share the same id unless their fees are materially higher, so miners can skip templates they already work on.
+pub type BlockTemplateId = u64;
+
+/// The sink and fees of the template which was last assigned a new id
+struct PublishedTemplate {
+    id: BlockTemplateId,
+    selected_parent_hash: Hash,
+    total_fees: u64,
+}
+
 pub(crate) struct Inner {
     /// Time, in milliseconds, at which the cache was last updated
     last_update_time: u64,
@@ -15,43 +32,84 @@ pub(crate) struct Inner {
 
     /// Duration in milliseconds after which the cached data expires
     cache_lifetime: u64,
+
+    /// The last template assigned a new id, kept across cache clears
+    published: PublishedTemplate,
+
+    /// Signals every new block template id
+    updates: Arc<watch::Sender<()>>,
 }
 
 impl Inner {
-    pub(crate) fn new(cache_lifetime: Option<u64>) -> Self {
+    pub(crate) fn new(cache_lifetime: Option<u64>, updates: Arc<watch::Sender<()>>) -> Self {
         let cache_lifetime = cache_lifetime.unwrap_or(DEFAULT_CACHE_LIFETIME);
-        Self { last_update_time: 0, block_template: None, cache_lifetime }
+        // Ids start at a random value so that they do not repeat across node restarts
+        let published = PublishedTemplate { id: rand::random(), selected_parent_hash: Default::default(), total_fees: 0 };
+        Self { last_update_time: 0, block_template: None, cache_lifetime, published, updates }
     }
 
     fn clear(&mut self) {
         self.block_template = None;
     }
 
-    pub(crate) fn get_immutable_cached_template(&self) -> Option<Arc<BlockTemplate>> {
+    pub(crate) fn get_immutable_cached_template(&self) -> Option<(Arc<BlockTemplate>, BlockTemplateId)> {
         let now = unix_now();
         // We verify that `now > last update` in order to avoid theoretic clock change bugs
         if now > self.last_update_time + self.cache_lifetime || now < self.last_update_time {
             None
         } else {
-            self.block_template.clone()
+            self.block_template.clone().map(|block_template| (block_template, self.published.id))
         }
     }
 
-    pub(crate) fn set_immutable_cached_template(&mut self, block_template: BlockTemplate) -> Arc<BlockTemplate> {
+    pub(crate) fn set_immutable_cached_template(&mut self, block_template: BlockTemplate) -> (Arc<BlockTemplate>, BlockTemplateId) {
         self.last_update_time = unix_now();
         let block_template = Arc::new(block_template);
         self.block_template = Some(block_template.clone());
-        block_template
+        let template_id = self.publish(&block_template);
+        (block_template, template_id)
+    }
+
+    /// Returns the id of a block template, assigning a new one if the template is built over a new sink
+    /// or with materially higher fees than the last template assigned a new id
+    fn publish(&mut self, block_template: &BlockTemplate) -> BlockTemplateId {
+        let total_fees = block_template.calculated_fees.iter().sum::<u64>();
+        let is_new_sink = block_template.selected_parent_hash != self.published.selected_parent_hash;
+        let is_materially_richer = total_fees > self.published.total_fees
+            && total_fees.saturating_mul(100) >= self.published.total_fees.saturating_mul(100 + MATERIAL_FEE_INCREASE_PERCENT);
+        if is_new_sink || is_materially_richer {
+            self.published = PublishedTemplate {
+                id: self.published.id.wrapping_add(1),
+                selected_parent_hash: block_template.selected_parent_hash,
+                total_fees,
+            };
+            self.updates.send_replace(());
+        }
+        self.published.id
     }
 }
 
 pub(crate) struct BlockTemplateCache {
     inner: Mutex<Inner>,
+
+    /// Signals every new block template id as well as every event which might make the cached template stale
+    updates: Arc<watch::Sender<()>>,
 }
 
 impl BlockTemplateCache {
     pub(crate) fn new(cache_lifetime: Option<u64>) -> Self {
-        Self { inner: Mutex::new(Inner::new(cache_lifetime)) }
+        let updates = Arc::new(watch::channel(()).0);
+        Self { inner: Mutex::new(Inner::new(cache_lifetime, updates.clone())), updates }
+    }
+
+    /// Subscribes to the signals of new block template ids and of events possibly making the cached template stale
+    pub(crate) fn subscribe(&self) -> watch::Receiver<()> {
+        self.updates.subscribe()
+    }
+
+    /// Signals that the cached template might be stale, typically since the virtual state changed
+    pub(crate) fn signal_stale(&self) {
+        self.updates.send_replace(());
     }
 
     #[cfg(test)]
@@ -68,3 +126,45 @@ impl BlockTemplateCache {
         guard
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vecno_consensus_core::{block::MutableBlock, coinbase::MinerData, header::Header, tx::ScriptPublicKey};
+
+    fn template(selected_parent_hash: u64, calculated_fees: Vec<u64>) -> BlockTemplate {
+        let header = Header::from_precomputed_hash(Hash::from_u64_word(selected_parent_hash), vec![]);
+        let miner_data = MinerData::new(ScriptPublicKey::from_vec(0, vec![]), vec![]);
+        let selected_parent_hash = Hash::from_u64_word(selected_parent_hash);
+        BlockTemplate::new(MutableBlock::from_header(header), miner_data, false, 0, 0, selected_parent_hash, calculated_fees)
+    }
+
+    #[test]
+    fn test_block_template_ids() {
+        let cache = BlockTemplateCache::new(None);
+        let mut updates = cache.subscribe();
+        let mut inner = cache.inner.lock();
+
+        let (_, first) = inner.set_immutable_cached_template(template(1, vec![100, 100]));
+        assert!(updates.has_changed().unwrap());
+        updates.mark_unchanged();
+
+        // Same sink, fees not materially higher
+        assert_eq!(inner.set_immutable_cached_template(template(1, vec![100, 100])).1, first);
+        assert_eq!(inner.set_immutable_cached_template(template(1, vec![100, 110])).1, first);
+        assert_eq!(inner.set_immutable_cached_template(template(1, vec![50])).1, first);
+        assert!(!updates.has_changed().unwrap());
+
+        // Same sink, fees materially higher
+        let (_, second) = inner.set_immutable_cached_template(template(1, vec![100, 120]));
+        assert_ne!(second, first);
+        assert!(updates.has_changed().unwrap());
+
+        // New sink, even with lower fees
+        let (_, third) = inner.set_immutable_cached_template(template(2, vec![]));
+        assert_ne!(third, second);
+
+        // Cached templates keep their id
+        assert_eq!(inner.get_immutable_cached_template().map(|(_, id)| id), Some(third));
+    }
+}
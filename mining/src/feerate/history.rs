@@ -0,0 +1,201 @@
+//! Rolling history of the feerates paid by the mempool transactions included in recently mined blocks,
+//! used to calibrate the mempool based [`FeerateEstimations`] against actual inclusion times.
+
+use super::{FeerateBucket, FeerateEstimations};
+use std::collections::VecDeque;
+
+/// Default number of blocks whose feerate records are kept
+pub const DEFAULT_FEERATE_HISTORY_BLOCKS: usize = 6_000;
+
+/// Default number of transaction inclusions kept for calibrating the estimations
+pub const DEFAULT_FEERATE_HISTORY_INCLUSIONS: usize = 50_000;
+
+/// Minimum number of recorded inclusions paying at least the feerate of a bucket for the history to calibrate it
+pub const MIN_CALIBRATION_INCLUSIONS: usize = 20;
+
+/// The inclusion of a mempool transaction into a block
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FeerateInclusion {
+    pub feerate: f64,
+    /// The time spent by the transaction in the mempool before being included
+    pub waited_seconds: f64,
+}
+
+impl FeerateInclusion {
+    pub fn new(feerate: f64, waited_seconds: f64) -> Self {
+        Self { feerate, waited_seconds }
+    }
+}
+
+/// Feerate statistics of the mempool transactions included by a block
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BlockFeerateRecord {
+    pub daa_score: u64,
+    /// Unix time in milliseconds at which the block was handled by the mempool
+    pub timestamp: u64,
+    /// The number of block transactions found in the mempool, the only ones the statistics are about
+    pub transaction_count: u32,
+    pub min_feerate: f64,
+    pub median_feerate: f64,
+    pub max_feerate: f64,
+    pub median_waited_seconds: f64,
+}
+
+/// Rolling history of the feerates paid by the mempool transactions included in blocks
+#[derive(Clone, Debug)]
+pub struct FeerateHistory {
+    records: VecDeque<BlockFeerateRecord>,
+    inclusions: VecDeque<FeerateInclusion>,
+    max_records: usize,
+    max_inclusions: usize,
+}
+
+impl FeerateHistory {
+    pub fn new(max_records: usize, max_inclusions: usize) -> Self {
+        Self { records: VecDeque::new(), inclusions: VecDeque::new(), max_records, max_inclusions }
+    }
+
+    /// Records the inclusions of the mempool transactions of a block, evicting the oldest records and
+    /// inclusions past the history capacity. Blocks including no mempool transaction are not recorded.
+    pub fn record_block(&mut self, daa_score: u64, timestamp: u64, mut inclusions: Vec<FeerateInclusion>) {
+        if inclusions.is_empty() {
+            return;
+        }
+        self.inclusions.extend(inclusions.iter().copied());
+        while self.inclusions.len() > self.max_inclusions {
+            self.inclusions.pop_front();
+        }
+
+        inclusions.sort_unstable_by(|a, b| a.feerate.total_cmp(&b.feerate));
+        let mut waited_seconds = inclusions.iter().map(|x| x.waited_seconds).collect::<Vec<_>>();
+        waited_seconds.sort_unstable_by(f64::total_cmp);
+        self.records.push_back(BlockFeerateRecord {
+            daa_score,
+            timestamp,
+            transaction_count: inclusions.len() as u32,
+            min_feerate: inclusions[0].feerate,
+            median_feerate: inclusions[inclusions.len() / 2].feerate,
+            max_feerate: inclusions[inclusions.len() - 1].feerate,
+            median_waited_seconds: waited_seconds[waited_seconds.len() / 2],
+        });
+        while self.records.len() > self.max_records {
+            self.records.pop_front();
+        }
+    }
+
+    /// Returns the records of at most the `count` most recent blocks, from the oldest
+    pub fn records(&self, count: usize) -> Vec<BlockFeerateRecord> {
+        self.records.iter().skip(self.records.len().saturating_sub(count)).copied().collect()
+    }
+
+    pub fn inclusion_count(&self) -> usize {
+        self.inclusions.len()
+    }
+
+    /// Returns the waiting times of the recorded inclusions paying at least `feerate`, in ascending order
+    fn waited_seconds(&self, feerate: f64) -> Vec<f64> {
+        let mut waited_seconds = self.inclusions.iter().filter(|x| x.feerate >= feerate).map(|x| x.waited_seconds).collect::<Vec<_>>();
+        waited_seconds.sort_unstable_by(f64::total_cmp);
+        waited_seconds
+    }
+
+    /// Calibrates a bucket against the history.
+    ///
+    /// The estimated time is raised to the median waiting time of the recorded inclusions paying at least the bucket feerate
+    /// if higher, and the confidence is set to the share of these inclusions which waited no longer than the estimated time.
+    /// The bucket is left untouched if too few inclusions pay as much.
+    fn calibrate_bucket(&self, bucket: &mut FeerateBucket) {
+        let waited_seconds = self.waited_seconds(bucket.feerate);
+        if waited_seconds.len() < MIN_CALIBRATION_INCLUSIONS {
+            return;
+        }
+        bucket.estimated_seconds = bucket.estimated_seconds.max(waited_seconds[waited_seconds.len() / 2]);
+        let within_estimate = waited_seconds.partition_point(|x| *x <= bucket.estimated_seconds);
+        bucket.confidence = Some(within_estimate as f64 / waited_seconds.len() as f64);
+    }
+
+    /// Calibrates all the buckets of `estimations` against the history, see [`Self::calibrate_bucket`].
+    ///
+    /// The estimated times of the lower priority buckets are kept no shorter than those of the higher priority ones.
+    pub fn calibrate(&self, estimations: &mut FeerateEstimations) {
+        let mut min_seconds = 0f64;
+        let buckets = std::iter::once(&mut estimations.priority_bucket)
+            .chain(estimations.normal_buckets.iter_mut())
+            .chain(estimations.low_buckets.iter_mut());
+        for bucket in buckets {
+            self.calibrate_bucket(bucket);
+            bucket.estimated_seconds = bucket.estimated_seconds.max(min_seconds);
+            min_seconds = bucket.estimated_seconds;
+        }
+    }
+}
+
+impl Default for FeerateHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_FEERATE_HISTORY_BLOCKS, DEFAULT_FEERATE_HISTORY_INCLUSIONS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::Itertools;
+
+    fn bucket(feerate: f64, estimated_seconds: f64) -> FeerateBucket {
+        FeerateBucket { feerate, estimated_seconds, confidence: None }
+    }
+
+    #[test]
+    fn test_feerate_history_records() {
+        let mut history = FeerateHistory::new(2, 5);
+        history.record_block(1, 1_000, vec![]);
+        assert!(history.records(10).is_empty());
+
+        history.record_block(
+            2,
+            2_000,
+            vec![FeerateInclusion::new(3.0, 1.0), FeerateInclusion::new(1.0, 5.0), FeerateInclusion::new(2.0, 2.0)],
+        );
+        let records = history.records(10);
+        assert_eq!(records.len(), 1);
+        assert_eq!((records[0].min_feerate, records[0].median_feerate, records[0].max_feerate), (1.0, 2.0, 3.0));
+        assert_eq!(records[0].median_waited_seconds, 2.0);
+        assert_eq!(records[0].transaction_count, 3);
+
+        history.record_block(3, 3_000, vec![FeerateInclusion::new(1.0, 1.0); 2]);
+        history.record_block(4, 4_000, vec![FeerateInclusion::new(1.0, 1.0)]);
+        assert_eq!(history.records(10).iter().map(|x| x.daa_score).collect_vec(), vec![3, 4], "the oldest record should be evicted");
+        assert_eq!(history.records(1).iter().map(|x| x.daa_score).collect_vec(), vec![4]);
+        assert_eq!(history.inclusion_count(), 5);
+    }
+
+    #[test]
+    fn test_feerate_history_calibration() {
+        let mut history = FeerateHistory::default();
+        let mut estimations = FeerateEstimations {
+            priority_bucket: bucket(10.0, 1.0),
+            normal_buckets: vec![bucket(5.0, 2.0)],
+            low_buckets: vec![bucket(1.0, 3.0)],
+        };
+
+        // Without enough history, the estimations are left untouched
+        history.record_block(1, 1_000, vec![FeerateInclusion::new(10.0, 0.5); MIN_CALIBRATION_INCLUSIONS - 1]);
+        history.calibrate(&mut estimations);
+        assert!(estimations.ordered_buckets().iter().all(|x| x.confidence.is_none()));
+
+        // Transactions paying 5 got included in 4 seconds where 2 were estimated
+        history.record_block(2, 2_000, vec![FeerateInclusion::new(10.0, 0.5)]);
+        history.record_block(3, 3_000, vec![FeerateInclusion::new(5.0, 4.0); MIN_CALIBRATION_INCLUSIONS * 2]);
+        history.calibrate(&mut estimations);
+        let buckets = estimations.ordered_buckets();
+        assert_eq!(buckets[0].estimated_seconds, 1.0);
+        assert_eq!(buckets[0].confidence, Some(1.0));
+        assert_eq!(buckets[1].estimated_seconds, 4.0, "the estimated time should be raised to the observed median");
+        assert_eq!(buckets[1].confidence, Some(1.0));
+        assert_eq!(buckets[2].estimated_seconds, 4.0, "a lower priority bucket should not be estimated faster");
+        for (i, j) in buckets.into_iter().tuple_windows() {
+            assert!(i.feerate >= j.feerate);
+            assert!(i.estimated_seconds <= j.estimated_seconds);
+        }
+    }
+}
@@ -5,6 +5,8 @@ use crate::block_template::selector::ALPHA;
 use itertools::Itertools;
 use std::fmt::Display;
 
+pub mod history;
+
 /// A type representing fee/mass of a transaction in `veni/gram` units.
 /// Given a feerate value recommendation, calculate the required fee by
 /// taking the transaction mass and multiplying it by feerate: `fee = feerate * mass(tx)`
@@ -14,11 +16,18 @@ pub type Feerate = f64;
 pub struct FeerateBucket {
     pub feerate: f64,
     pub estimated_seconds: f64,
+    /// The share of the recently mined transactions paying at least `feerate` which got included
+    /// within `estimated_seconds`, `None` if the fee estimation history is too thin to tell
+    pub confidence: Option<f64>,
 }
 
 impl Display for FeerateBucket {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "({:.4}, {:.4}s)", self.feerate, self.estimated_seconds)
+        write!(f, "({:.4}, {:.4}s", self.feerate, self.estimated_seconds)?;
+        if let Some(confidence) = self.confidence {
+            write!(f, ", {:.0}%", confidence * 100.0)?;
+        }
+        write!(f, ")")
     }
 }
 
@@ -146,12 +155,12 @@ impl FeerateEstimator {
                   to cover large fractions of the integral area (reflecting the position within the waiting-time distribution)
         */
         FeerateEstimations {
-            priority_bucket: FeerateBucket { feerate: high, estimated_seconds: self.feerate_to_time(high) },
+            priority_bucket: FeerateBucket { feerate: high, estimated_seconds: self.feerate_to_time(high), confidence: None },
             normal_buckets: vec![
-                FeerateBucket { feerate: normal, estimated_seconds: self.feerate_to_time(normal) },
-                FeerateBucket { feerate: mid, estimated_seconds: self.feerate_to_time(mid) },
+                FeerateBucket { feerate: normal, estimated_seconds: self.feerate_to_time(normal), confidence: None },
+                FeerateBucket { feerate: mid, estimated_seconds: self.feerate_to_time(mid), confidence: None },
             ],
-            low_buckets: vec![FeerateBucket { feerate: low, estimated_seconds: self.feerate_to_time(low) }],
+            low_buckets: vec![FeerateBucket { feerate: low, estimated_seconds: self.feerate_to_time(low), confidence: None }],
         }
     }
 }
@@ -14,6 +14,9 @@ mod manager_tests;
 pub mod mempool;
 pub mod model;
 pub mod monitor;
+pub mod persistence;
+
+pub use cache::BlockTemplateId;
 
 // Exposed for benchmarks
 pub use block_template::{policy::Policy, selector::RebalancingWeightedTransactionSelector};
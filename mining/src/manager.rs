@@ -1,24 +1,35 @@
 use crate::{
-    block_template::{builder::BlockTemplateBuilder, errors::BuilderError},
-    cache::BlockTemplateCache,
+    block_template::{builder::BlockTemplateBuilder, errors::BuilderError, selector::MandatoryTransactionsSelector},
+    cache::{BlockTemplateCache, BlockTemplateId},
     errors::MiningManagerResult,
-    feerate::{FeeEstimateVerbose, FeerateEstimations, FeerateEstimatorArgs},
+    feerate::{
+        history::{BlockFeerateRecord, FeerateHistory},
+        FeeEstimateVerbose, FeerateEstimations, FeerateEstimatorArgs,
+    },
     mempool::{
         config::Config,
-        model::tx::{MempoolTransaction, TransactionPostValidation, TransactionPreValidation, TxRemovalReason},
+        model::{
+            status_tracker::TransactionStatusTracker,
+            tx::{MempoolTransaction, TransactionPostValidation, TransactionPreValidation, TxRemovalReason},
+        },
+        policy::MempoolPolicy,
         populate_entries_and_try_validate::{
             populate_mempool_transactions_in_parallel, validate_mempool_transaction, validate_mempool_transactions_in_parallel,
         },
-        tx::{Orphan, Priority, RbfPolicy},
+        tx::{Orphan, Priority, RateLimit, RbfPolicy},
         Mempool,
     },
     model::{
+        mempool_entries::{MempoolEntriesPage, MempoolEntriesQuery},
         owner_txs::{GroupedOwnerTransactions, ScriptPublicKeySet},
+        persisted_tx::PersistedTransaction,
         topological_sort::IntoIterTopologically,
         tx_insert::TransactionInsertion,
         tx_query::TransactionQuery,
+        tx_selection::{RejectedTransaction, TransactionSelection},
+        tx_status::{TransactionStatus, TransactionStatusChange},
     },
-    MempoolCountersSnapshot, MiningCounters, P2pTxCountSample,
+    MempoolCountersSnapshot, MiningCounters, P2pTxCountSample, Policy,
 };
 use itertools::Itertools;
 use vecno_consensus_core::{
@@ -33,16 +44,25 @@ use vecno_consensus_core::{
     tx::{MutableTransaction, Transaction, TransactionId, TransactionOutput},
 };
 use vecno_consensusmanager::{spawn_blocking, ConsensusProxy};
-use vecno_core::{debug, error, info, time::Stopwatch, warn};
+use vecno_core::{
+    debug, error, info,
+    time::{unix_now, Stopwatch},
+    warn,
+};
 use vecno_mining_errors::{manager::MiningManagerError, mempool::RuleError};
-use parking_lot::RwLock;
-use std::sync::Arc;
-use tokio::sync::mpsc::UnboundedSender;
+use vecno_utils::channel::Channel;
+use parking_lot::{Mutex, RwLock};
+use std::{collections::HashSet, sync::Arc};
+use tokio::sync::{mpsc::UnboundedSender, watch};
 
 pub struct MiningManager {
     config: Arc<Config>,
     block_template_cache: BlockTemplateCache,
     mempool: RwLock<Mempool>,
+    /// Feerates paid by the mempool transactions included in the recently handled blocks
+    feerate_history: Mutex<FeerateHistory>,
+    /// Outcomes of the transactions recently gone through the mempool, shared with the mempool itself
+    transaction_statuses: Arc<TransactionStatusTracker>,
     counters: Arc<MiningCounters>,
 }
 
@@ -65,30 +85,49 @@ impl MiningManager {
         max_block_mass: u64,
         ram_scale: f64,
         cache_lifetime: Option<u64>,
+        policy: Arc<dyn MempoolPolicy>,
         counters: Arc<MiningCounters>,
     ) -> Self {
-        let config =
-            Config::build_default(target_time_per_block, relay_non_std_transactions, max_block_mass).apply_ram_scale(ram_scale);
+        let config = Config::build_default(target_time_per_block, relay_non_std_transactions, max_block_mass)
+            .apply_ram_scale(ram_scale)
+            .with_policy(policy);
         Self::with_config(config, cache_lifetime, counters)
     }
 
     pub(crate) fn with_config(config: Config, cache_lifetime: Option<u64>, counters: Arc<MiningCounters>) -> Self {
         let config = Arc::new(config);
-        let mempool = RwLock::new(Mempool::new(config.clone(), counters.clone()));
+        let mempool = Mempool::new(config.clone(), counters.clone());
+        let transaction_statuses = mempool.transaction_statuses();
         let block_template_cache = BlockTemplateCache::new(cache_lifetime);
-        Self { config, block_template_cache, mempool, counters }
+        Self {
+            config,
+            block_template_cache,
+            mempool: RwLock::new(mempool),
+            feerate_history: Default::default(),
+            transaction_statuses,
+            counters,
+        }
     }
 
     pub fn get_block_template(&self, consensus: &dyn ConsensusApi, miner_data: &MinerData) -> MiningManagerResult<BlockTemplate> {
+        self.get_block_template_with_id(consensus, miner_data).map(|(block_template, _)| block_template)
+    }
+
+    /// Returns a block template along with its [`BlockTemplateId`]
+    pub fn get_block_template_with_id(
+        &self,
+        consensus: &dyn ConsensusApi,
+        miner_data: &MinerData,
+    ) -> MiningManagerResult<(BlockTemplate, BlockTemplateId)> {
         let virtual_state_approx_id = consensus.get_virtual_state_approx_id();
         let mut cache_lock = self.block_template_cache.lock(virtual_state_approx_id);
         let immutable_template = cache_lock.get_immutable_cached_template();
 
         // We first try and use a cached template if not expired
-        if let Some(immutable_template) = immutable_template {
+        if let Some((immutable_template, template_id)) = immutable_template {
             drop(cache_lock);
             if immutable_template.miner_data == *miner_data {
-                return Ok(immutable_template.as_ref().clone());
+                return Ok((immutable_template.as_ref().clone(), template_id));
             }
             // Miner data is new -- make the minimum changes required
             // Note the call returns a modified clone of the cached block template
@@ -96,7 +135,7 @@ impl MiningManager {
 
             // No point in updating cache since we have no reason to believe this coinbase will be used more
             // than the previous one, and we want to maintain the original template caching time
-            return Ok(block_template);
+            return Ok((block_template, template_id));
         }
 
         // Rust rewrite:
@@ -118,7 +157,7 @@ impl MiningManager {
             };
             match block_template_builder.build_block_template(consensus, miner_data, selector, build_mode) {
                 Ok(block_template) => {
-                    let block_template = cache_lock.set_immutable_cached_template(block_template);
+                    let (block_template, template_id) = cache_lock.set_immutable_cached_template(block_template);
                     match attempts {
                         1 => {
                             debug!(
@@ -143,7 +182,7 @@ impl MiningManager {
                             );
                         }
                     }
-                    return Ok(block_template.as_ref().clone());
+                    return Ok((block_template.as_ref().clone(), template_id));
                 }
                 Err(BuilderError::ConsensusError(BlockRuleError::InvalidTransactionsInNewBlock(invalid_transactions))) => {
                     let mut missing_outpoint: usize = 0;
@@ -197,19 +236,123 @@ impl MiningManager {
         }
     }
 
+    /// Builds a block template honoring a caller-supplied [`TransactionSelection`]. Returns the template along with
+    /// its [`BlockTemplateId`] and the mandatory transactions which could not be included.
+    ///
+    /// Such templates are specific to their caller so they are built on every call and never cached. Their id is derived
+    /// from their transactions, through the merkle root, rather than published to the long-polling callers of regular templates.
+    pub fn get_block_template_with_selection(
+        &self,
+        consensus: &dyn ConsensusApi,
+        miner_data: &MinerData,
+        selection: &TransactionSelection,
+    ) -> MiningManagerResult<(BlockTemplate, BlockTemplateId, Vec<RejectedTransaction>)> {
+        let mut rejected_transactions = Vec::new();
+
+        // Resolve the mandatory transactions, looking up the mempool for both ids and raw transactions
+        let mut candidates = Vec::with_capacity(selection.mandatory_transaction_ids.len() + selection.mandatory_transactions.len());
+        let mut unknown_transactions = Vec::new();
+        let mempool_read = self.mempool.read();
+        for transaction_id in selection.mandatory_transaction_ids.iter() {
+            match mempool_read.get_ready_candidate_transaction(transaction_id) {
+                Some(candidate) => candidates.push(candidate),
+                None if mempool_read.has_transaction(transaction_id, TransactionQuery::TransactionsOnly) => rejected_transactions
+                    .push(RejectedTransaction::new(*transaction_id, "spends outputs of transactions which are not in the block")),
+                None => rejected_transactions.push(RejectedTransaction::new(*transaction_id, "not found in the mempool")),
+            }
+        }
+        for transaction in selection.mandatory_transactions.iter() {
+            match mempool_read.get_ready_candidate_transaction(&transaction.id()) {
+                Some(candidate) => candidates.push(candidate),
+                None => unknown_transactions.push(transaction.clone()),
+            }
+        }
+        let fillers = mempool_read.ready_candidate_transactions();
+        drop(mempool_read);
+
+        for transaction in unknown_transactions {
+            let transaction_id = transaction.id();
+            match BlockTemplateBuilder::validate_mandatory_transaction(consensus, transaction) {
+                Ok(candidate) => candidates.push(candidate),
+                Err(err) => rejected_transactions.push(RejectedTransaction::new(transaction_id, err.to_string())),
+            }
+        }
+
+        // Keep the mandatory transactions which fit in the block and do not conflict with each other
+        let max_block_mass = self.config.maximum_mass_per_block;
+        let mut mandatory = Vec::with_capacity(candidates.len());
+        let mut mandatory_ids = HashSet::with_capacity(candidates.len());
+        let mut mandatory_mass: u64 = 0;
+        let mut spent_outpoints = HashSet::new();
+        for candidate in candidates {
+            let transaction_id = candidate.tx.id();
+            if !mandatory_ids.insert(transaction_id) {
+                continue;
+            }
+            if candidate.tx.inputs.iter().any(|input| spent_outpoints.contains(&input.previous_outpoint)) {
+                rejected_transactions.push(RejectedTransaction::new(transaction_id, "double spends another mandatory transaction"));
+            } else if mandatory_mass.saturating_add(candidate.calculated_mass) > max_block_mass {
+                rejected_transactions
+                    .push(RejectedTransaction::new(transaction_id, "exceeds the block mass left by the previous ones"));
+            } else {
+                mandatory_mass += candidate.calculated_mass;
+                spent_outpoints.extend(candidate.tx.inputs.iter().map(|input| input.previous_outpoint));
+                mandatory.push(candidate);
+            }
+        }
+
+        // Fill the block with the other ready transactions, skipping the excluded ones and the ones conflicting
+        // with the mandatory transactions (which conflict with themselves)
+        let excluded_ids = selection.excluded_transaction_ids.iter().collect::<HashSet<_>>();
+        let fillers = fillers
+            .into_iter()
+            .filter(|candidate| {
+                !excluded_ids.contains(&candidate.tx.id())
+                    && !candidate.tx.inputs.iter().any(|input| spent_outpoints.contains(&input.previous_outpoint))
+            })
+            .collect();
+
+        // Unlike regular templates, the build cannot be retried after evicting the transactions invalidated by
+        // consensus from the mempool, so we build in infallible mode
+        let selector = MandatoryTransactionsSelector::new(Policy::new(max_block_mass), mandatory, fillers);
+        let rejections = selector.rejections();
+        let block_template = BlockTemplateBuilder::new().build_block_template(
+            consensus,
+            miner_data,
+            Box::new(selector),
+            TemplateBuildMode::Infallible,
+        )?;
+        rejected_transactions.extend(
+            rejections
+                .lock()
+                .iter()
+                .map(|id| RejectedTransaction::new(*id, "rejected by consensus while building the block template")),
+        );
+
+        let template_id = block_template.block.header.hash_merkle_root.to_le_u64()[0];
+        Ok((block_template, template_id, rejected_transactions))
+    }
+
     /// Dynamically builds a transaction selector based on the specific state of the ready transactions frontier
     pub(crate) fn build_selector(&self) -> Box<dyn TemplateTransactionSelector> {
         self.mempool.read().build_selector()
     }
 
-    /// Returns realtime feerate estimations based on internal mempool state
+    /// Returns realtime feerate estimations based on internal mempool state, calibrated against the feerate history
     pub(crate) fn get_realtime_feerate_estimations(&self, virtual_daa_score: u64) -> FeerateEstimations {
         let args = FeerateEstimatorArgs::new(
             self.config.network_blocks_per_second.get(virtual_daa_score),
             self.config.maximum_mass_per_block,
         );
         let estimator = self.mempool.read().build_feerate_estimator(args);
-        estimator.calc_estimations(self.config.minimum_feerate())
+        let mut estimations = estimator.calc_estimations(self.config.minimum_feerate());
+        self.feerate_history.lock().calibrate(&mut estimations);
+        estimations
+    }
+
+    /// Returns the feerate records of at most the `max_blocks` most recently handled blocks, from the oldest
+    pub(crate) fn get_feerate_history(&self, max_blocks: usize) -> Vec<BlockFeerateRecord> {
+        self.feerate_history.lock().records(max_blocks)
     }
 
     /// Returns realtime feerate estimations based on internal mempool state with additional verbose data
@@ -228,8 +371,10 @@ impl MiningManager {
         let ready_transactions_count = mempool_read.ready_transaction_count();
         let ready_transaction_total_mass = mempool_read.ready_transaction_total_mass();
         drop(mempool_read);
+        let mut estimations = estimator.calc_estimations(self.config.minimum_feerate());
+        self.feerate_history.lock().calibrate(&mut estimations);
         let mut resp = FeeEstimateVerbose {
-            estimations: estimator.calc_estimations(self.config.minimum_feerate()),
+            estimations,
             network_mass_per_second,
             mempool_ready_transactions_count: ready_transactions_count as u64,
             mempool_ready_transactions_total_mass: ready_transaction_total_mass,
@@ -261,6 +406,11 @@ impl MiningManager {
         Ok(resp)
     }
 
+    /// Subscribes to the signals of new block template ids, also raised whenever the current template might be stale
+    pub fn block_template_updates(&self) -> watch::Receiver<()> {
+        self.block_template_cache.subscribe()
+    }
+
     /// Clears the block template cache, forcing the next call to get_block_template to build a new block template.
     #[cfg(test)]
     pub(crate) fn clear_block_template(&self) {
@@ -305,15 +455,34 @@ impl MiningManager {
         orphan: Orphan,
         rbf_policy: RbfPolicy,
     ) -> MiningManagerResult<TransactionInsertion> {
+        self.validate_and_insert_transaction_with_rate_limit(consensus, transaction, priority, orphan, rbf_policy, RateLimit::Enforced)
+    }
+
+    fn validate_and_insert_transaction_with_rate_limit(
+        &self,
+        consensus: &dyn ConsensusApi,
+        transaction: MutableTransaction,
+        priority: Priority,
+        orphan: Orphan,
+        rbf_policy: RbfPolicy,
+        rate_limit: RateLimit,
+    ) -> MiningManagerResult<TransactionInsertion> {
+        let transaction_id = transaction.id();
         // read lock on mempool
-        let TransactionPreValidation { mut transaction, feerate_threshold } =
-            self.mempool.read().pre_validate_and_populate_transaction(consensus, transaction, rbf_policy)?;
+        let TransactionPreValidation { mut transaction, feerate_threshold } = self
+            .mempool
+            .read()
+            .pre_validate_and_populate_transaction(consensus, transaction, rbf_policy)
+            .inspect_err(|err| self.record_rejection(transaction_id, err))?;
         let args = TransactionValidationArgs::new(feerate_threshold);
         // no lock on mempool
         let validation_result = validate_mempool_transaction(consensus, &mut transaction, &args);
         // write lock on mempool
         let mut mempool = self.mempool.write();
-        match mempool.post_validate_and_insert_transaction(consensus, validation_result, transaction, priority, orphan, rbf_policy)? {
+        match mempool
+            .post_validate_and_insert_transaction(consensus, validation_result, transaction, priority, orphan, rbf_policy, rate_limit)
+            .inspect_err(|err| self.record_rejection(transaction_id, err))?
+        {
             TransactionPostValidation { removed, accepted: Some(accepted_transaction) } => {
                 let unorphaned_transactions = mempool.get_unorphaned_transactions_after_accepted_transaction(&accepted_transaction);
                 drop(mempool);
@@ -322,7 +491,11 @@ impl MiningManager {
                 let mut accepted_transactions = Vec::with_capacity(unorphaned_transactions.len() + 1);
                 // We include the original accepted transaction as well
                 accepted_transactions.push(accepted_transaction);
-                accepted_transactions.extend(self.validate_and_insert_unorphaned_transactions(consensus, unorphaned_transactions));
+                accepted_transactions.extend(self.validate_and_insert_unorphaned_transactions(
+                    consensus,
+                    unorphaned_transactions,
+                    rate_limit,
+                ));
                 self.counters.increase_tx_counts(1, priority);
 
                 Ok(TransactionInsertion::new(removed, accepted_transactions))
@@ -331,10 +504,53 @@ impl MiningManager {
         }
     }
 
+    /// validate_and_insert_package validates a package of related transactions and adds them all to the
+    /// mempool, or none of them.
+    ///
+    /// The transactions of a package are validated against the outputs of each other and pay the minimum
+    /// relay fee as a whole, allowing a child to pay for parents which would not be accepted on their own.
+    ///
+    /// On success, returns the package transactions in topological order, followed by the transactions that
+    /// where unorphaned following their insertion.
+    pub fn validate_and_insert_package(
+        &self,
+        consensus: &dyn ConsensusApi,
+        transactions: Vec<Transaction>,
+        priority: Priority,
+    ) -> MiningManagerResult<TransactionInsertion> {
+        let transaction_ids = transactions.iter().map(|x| x.id()).collect_vec();
+        let record_rejections = |err: &RuleError| transaction_ids.iter().for_each(|x| self.record_rejection(*x, err));
+        // read lock on mempool
+        let mut transactions =
+            self.mempool.read().pre_validate_and_populate_package(consensus, transactions).inspect_err(record_rejections)?;
+        // no lock on mempool
+        let args = TransactionValidationBatchArgs::new();
+        let validation_results = validate_mempool_transactions_in_parallel(consensus, &mut transactions, &args);
+        // write lock on mempool
+        let mut mempool = self.mempool.write();
+        let mut accepted_transactions = mempool
+            .post_validate_and_insert_package(consensus, transactions, validation_results, priority)
+            .inspect_err(record_rejections)?;
+        let unorphaned_transactions = accepted_transactions
+            .iter()
+            .flat_map(|transaction| mempool.get_unorphaned_transactions_after_accepted_transaction(transaction))
+            .collect_vec();
+        drop(mempool);
+
+        self.counters.increase_tx_counts(accepted_transactions.len() as u64, priority);
+        accepted_transactions.extend(self.validate_and_insert_unorphaned_transactions(
+            consensus,
+            unorphaned_transactions,
+            RateLimit::Enforced,
+        ));
+        Ok(TransactionInsertion::new(None, accepted_transactions))
+    }
+
     fn validate_and_insert_unorphaned_transactions(
         &self,
         consensus: &dyn ConsensusApi,
         mut incoming_transactions: Vec<MempoolTransaction>,
+        rate_limit: RateLimit,
     ) -> Vec<Arc<Transaction>> {
         // The capacity used here may be exceeded (see next comment).
         let mut accepted_transactions = Vec::with_capacity(incoming_transactions.len());
@@ -380,6 +596,7 @@ impl MiningManager {
                         priority,
                         Orphan::Forbidden,
                         rbf_policy,
+                        rate_limit,
                     ) {
                         Ok(TransactionPostValidation { removed: _, accepted: Some(accepted_transaction) }) => {
                             accepted_transactions.push(accepted_transaction.clone());
@@ -389,6 +606,7 @@ impl MiningManager {
                         Ok(TransactionPostValidation { removed: _, accepted: None }) => vec![],
                         Err(err) => {
                             debug!("Failed to unorphan transaction {0} due to rule error: {1}", orphan_id, err);
+                            self.record_rejection(orphan_id, &err);
                             vec![]
                         }
                     }
@@ -453,6 +671,7 @@ impl MiningManager {
                     }
                     Err(err) => {
                         debug!("Failed to pre validate transaction {0} due to rule error: {1}", transaction_id, err);
+                        self.record_rejection(transaction_id, &err);
                         insert_results.push(Err(MiningManagerError::MempoolError(err)));
                         None
                     }
@@ -489,6 +708,7 @@ impl MiningManager {
                     priority,
                     orphan,
                     rbf_policy,
+                    RateLimit::Enforced,
                 ) {
                     Ok(TransactionPostValidation { removed: _, accepted: Some(accepted_transaction) }) => {
                         insert_results.push(Ok(accepted_transaction.clone()));
@@ -501,6 +721,7 @@ impl MiningManager {
                     }
                     Err(err) => {
                         debug!("Failed to post validate transaction {0} due to rule error: {1}", transaction_id, err);
+                        self.record_rejection(transaction_id, &err);
                         insert_results.push(Err(MiningManagerError::MempoolError(err)));
                         vec![]
                     }
@@ -509,11 +730,20 @@ impl MiningManager {
             unorphaned_transactions.extend(txs);
         }
 
-        insert_results
-            .extend(self.validate_and_insert_unorphaned_transactions(consensus, unorphaned_transactions).into_iter().map(Ok));
+        let unorphaned_transactions =
+            self.validate_and_insert_unorphaned_transactions(consensus, unorphaned_transactions, RateLimit::Enforced);
+        insert_results.extend(unorphaned_transactions.into_iter().map(Ok));
         insert_results
     }
 
+    /// Records the rejection of a transaction, unless it is a mere duplicate of a known one
+    fn record_rejection(&self, transaction_id: TransactionId, err: &RuleError) {
+        match err {
+            RuleError::RejectAlreadyAccepted(_) | RuleError::RejectDuplicate(_) | RuleError::RejectDuplicateOrphan(_) => {}
+            err => self.transaction_statuses.update(transaction_id, TransactionStatus::Rejected { reason: err.to_string() }),
+        }
+    }
+
     fn next_transaction_chunk_upper_bound(&self, transactions: &[MutableTransaction], lower_bound: usize) -> Option<usize> {
         if lower_bound >= transactions.len() {
             return None;
@@ -544,6 +774,31 @@ impl MiningManager {
         self.mempool.read().has_transaction(transaction_id, query)
     }
 
+    /// Returns the status of a transaction, either still in the mempool or among the recent outcomes,
+    /// or `None` if unknown.
+    pub fn get_transaction_status(&self, transaction_id: &TransactionId) -> Option<TransactionStatus> {
+        let mempool = self.mempool.read();
+        if mempool.has_transaction(transaction_id, TransactionQuery::TransactionsOnly) {
+            Some(TransactionStatus::InMempool)
+        } else if mempool.has_transaction(transaction_id, TransactionQuery::OrphansOnly) {
+            Some(TransactionStatus::Orphan)
+        } else {
+            drop(mempool);
+            self.transaction_statuses.get(transaction_id)
+        }
+    }
+
+    /// Registers the unique listener of the transaction status changes, replacing any previous one.
+    ///
+    /// The changes are only sent while enabled by [`Self::set_transaction_status_notifications`].
+    pub fn register_transaction_status_listener(&self) -> Channel<TransactionStatusChange> {
+        self.transaction_statuses.register_listener()
+    }
+
+    pub fn set_transaction_status_notifications(&self, active: bool) {
+        self.transaction_statuses.set_active(active);
+    }
+
     pub fn get_all_transactions(&self, query: TransactionQuery) -> (Vec<MutableTransaction>, Vec<MutableTransaction>) {
         const TRANSACTION_CHUNK_SIZE: usize = 1000;
         // read lock on mempool by transaction chunks
@@ -567,6 +822,31 @@ impl MiningManager {
         (transactions, orphans)
     }
 
+    /// Returns the mempool transactions whose id is accepted by `filter`, only the matching ones being cloned
+    pub fn get_transactions_by_filter(
+        &self,
+        filter: impl Fn(&TransactionId) -> bool,
+        query: TransactionQuery,
+    ) -> Vec<MutableTransaction> {
+        const TRANSACTION_CHUNK_SIZE: usize = 1000;
+        let (transaction_ids, orphan_ids) = self.mempool.read().get_all_transaction_ids(query);
+        let matching_ids = transaction_ids.into_iter().chain(orphan_ids).filter(|x| filter(x)).collect::<Vec<_>>();
+        // read lock on mempool by transaction chunks
+        let mut transactions = Vec::with_capacity(matching_ids.len());
+        for chunks in matching_ids.chunks(TRANSACTION_CHUNK_SIZE) {
+            let mempool = self.mempool.read();
+            transactions.extend(chunks.iter().filter_map(|x| mempool.get_transaction(x, query)));
+        }
+        transactions
+    }
+
+    /// Returns a page of the mempool entries selected by `query`.
+    ///
+    /// The mempool is scanned under a single read lock without cloning the transactions left out of the page.
+    pub fn get_mempool_entries(&self, query: &MempoolEntriesQuery) -> MempoolEntriesPage {
+        self.mempool.read().get_entries_page(query)
+    }
+
     /// get_transactions_by_addresses returns the sending and receiving transactions for
     /// a set of addresses.
     ///
@@ -595,10 +875,21 @@ impl MiningManager {
         // problem of the internal implementation and unrelated to the caller
 
         // write lock on mempool
-        let unorphaned_transactions = self.mempool.write().handle_new_block_transactions(block_daa_score, block_transactions)?;
+        let mut mempool = self.mempool.write();
+        let blocks_per_second = self.config.network_blocks_per_second.get(block_daa_score);
+        let inclusions = mempool.collect_feerate_inclusions(block_daa_score, block_transactions, blocks_per_second);
+        let unorphaned_transactions = mempool.handle_new_block_transactions(block_daa_score, block_transactions)?;
+        drop(mempool);
+
+        // Learn from the feerates actually paid for inclusion
+        self.feerate_history.lock().record_block(block_daa_score, unix_now(), inclusions);
 
         // alternate no & write lock on mempool
-        let accepted_transactions = self.validate_and_insert_unorphaned_transactions(consensus, unorphaned_transactions);
+        let accepted_transactions =
+            self.validate_and_insert_unorphaned_transactions(consensus, unorphaned_transactions, RateLimit::Enforced);
+
+        // The virtual state most likely changed, so let block template waiters check for a new template
+        self.block_template_cache.signal_stale();
 
         Ok(accepted_transactions)
     }
@@ -619,10 +910,9 @@ impl MiningManager {
         let expired_low_priority_transactions = self.mempool.write().collect_expired_low_priority_transactions(consensus);
         for chunk in &expired_low_priority_transactions.iter().chunks(24) {
             let mut mempool = self.mempool.write();
-            chunk.into_iter().for_each(|tx| {
-                if let Err(err) = mempool.remove_transaction(tx, true, TxRemovalReason::Muted, "") {
-                    warn!("Failed to remove transaction {} from mempool: {}", tx, err);
-                }
+            chunk.into_iter().for_each(|tx| match mempool.remove_transaction(tx, true, TxRemovalReason::Muted, "") {
+                Ok(removed) => self.transaction_statuses.update_evicted(&removed, &TxRemovalReason::Expired, ""),
+                Err(err) => warn!("Failed to remove transaction {} from mempool: {}", tx, err),
             });
         }
         match expired_low_priority_transactions.len() {
@@ -779,9 +1069,14 @@ impl MiningManager {
                             transaction_id, err
                         );
                         // This call cleanly removes the invalid transaction and its redeemers.
-                        _ = mempool
-                            .remove_transaction(&transaction_id, true, TxRemovalReason::Muted, "")
-                            .inspect_err(|err| warn!("Failed to remove transaction {} from mempool: {}", transaction_id, err));
+                        match mempool.remove_transaction(&transaction_id, true, TxRemovalReason::Muted, "") {
+                            Ok(removed) => self.transaction_statuses.update_evicted(
+                                &removed,
+                                &TxRemovalReason::FailedRevalidation,
+                                format!(" error: {}", err).as_str(),
+                            ),
+                            Err(err) => warn!("Failed to remove transaction {} from mempool: {}", transaction_id, err),
+                        }
                         invalid += 1;
                     }
                 }
@@ -838,6 +1133,52 @@ impl MiningManager {
         self.mempool.read().unknown_transactions(transactions)
     }
 
+    /// Returns all the transactions of the mempool, orphans included, as they are saved on disk
+    pub fn persisted_transactions(&self) -> Vec<PersistedTransaction> {
+        self.mempool.read().persisted_transactions()
+    }
+
+    /// Revalidates transactions previously saved on disk and inserts the valid ones back into the mempool,
+    /// preserving the DAA score at which they were originally added.
+    ///
+    /// Low priority transactions which expired while the node was down are dropped without being revalidated.
+    /// Only transactions which were orphans are allowed back into the orphan pool, the other ones missing some
+    /// outpoints having most likely been mined or double spent in the meantime. The transactions were admitted
+    /// before the node restarted so they are not subject to the rate limits of the mempool policy.
+    ///
+    /// Returns the number of restored transactions.
+    pub fn restore_transactions(&self, consensus: &dyn ConsensusApi, transactions: Vec<PersistedTransaction>) -> usize {
+        let virtual_daa_score = consensus.get_virtual_daa_score();
+        let expire_interval = self.config.transaction_expire_interval_daa_score.get(virtual_daa_score);
+        let transactions = transactions
+            .into_iter()
+            .filter(|tx| tx.priority == Priority::High || virtual_daa_score <= tx.added_at_daa_score + expire_interval)
+            .collect_vec();
+
+        let mut restored = 0;
+        for PersistedTransaction { transaction, priority, added_at_daa_score, is_orphan } in transactions.topological_into_iter() {
+            let transaction_id = transaction.id();
+            let orphan = if is_orphan { Orphan::Allowed } else { Orphan::Forbidden };
+            let transaction = MutableTransaction::from_tx(transaction);
+            match self.validate_and_insert_transaction_with_rate_limit(
+                consensus,
+                transaction,
+                priority,
+                orphan,
+                RbfPolicy::Forbidden,
+                RateLimit::Skipped,
+            ) {
+                Ok(_) => {
+                    if self.mempool.write().restore_added_at_daa_score(&transaction_id, added_at_daa_score) {
+                        restored += 1;
+                    }
+                }
+                Err(err) => debug!("Dropping persisted transaction {} due to: {}", transaction_id, err),
+            }
+        }
+        restored
+    }
+
     #[cfg(test)]
     pub(crate) fn get_estimated_size(&self) -> usize {
         self.mempool.read().get_estimated_size()
@@ -859,11 +1200,41 @@ impl MiningManagerProxy {
         consensus.clone().spawn_blocking(move |c| self.inner.get_block_template(c, &miner_data)).await
     }
 
+    /// Returns a block template along with its [`BlockTemplateId`]
+    pub async fn get_block_template_with_id(
+        self,
+        consensus: &ConsensusProxy,
+        miner_data: MinerData,
+    ) -> MiningManagerResult<(BlockTemplate, BlockTemplateId)> {
+        consensus.clone().spawn_blocking(move |c| self.inner.get_block_template_with_id(c, &miner_data)).await
+    }
+
+    /// Builds a block template honoring a caller-supplied [`TransactionSelection`], returning it along with
+    /// its [`BlockTemplateId`] and the mandatory transactions which could not be included
+    pub async fn get_block_template_with_selection(
+        self,
+        consensus: &ConsensusProxy,
+        miner_data: MinerData,
+        selection: TransactionSelection,
+    ) -> MiningManagerResult<(BlockTemplate, BlockTemplateId, Vec<RejectedTransaction>)> {
+        consensus.clone().spawn_blocking(move |c| self.inner.get_block_template_with_selection(c, &miner_data, &selection)).await
+    }
+
+    /// Subscribes to the signals of new block template ids, also raised whenever the current template might be stale
+    pub fn block_template_updates(&self) -> watch::Receiver<()> {
+        self.inner.block_template_updates()
+    }
+
     /// Returns realtime feerate estimations based on internal mempool state
     pub async fn get_realtime_feerate_estimations(self, virtual_daa_score: u64) -> FeerateEstimations {
         spawn_blocking(move || self.inner.get_realtime_feerate_estimations(virtual_daa_score)).await.unwrap()
     }
 
+    /// Returns the feerate records of at most the `max_blocks` most recently handled blocks, from the oldest
+    pub async fn get_feerate_history(self, max_blocks: usize) -> Vec<BlockFeerateRecord> {
+        spawn_blocking(move || self.inner.get_feerate_history(max_blocks)).await.unwrap()
+    }
+
     /// Returns realtime feerate estimations based on internal mempool state with additional verbose data
     pub async fn get_realtime_feerate_estimations_verbose(
         self,
@@ -894,6 +1265,18 @@ impl MiningManagerProxy {
             .await
     }
 
+    /// Validates a package of related transactions and adds them all to the mempool, or none of them.
+    ///
+    /// See [`MiningManager::validate_and_insert_package`].
+    pub async fn validate_and_insert_package(
+        self,
+        consensus: &ConsensusProxy,
+        transactions: Vec<Transaction>,
+        priority: Priority,
+    ) -> MiningManagerResult<TransactionInsertion> {
+        consensus.clone().spawn_blocking(move |c| self.inner.validate_and_insert_package(c, transactions, priority)).await
+    }
+
     /// Validates a batch of transactions, handling iteratively only the independent ones, and
     /// adds those to the set of known transactions that have not yet been added to any block.
     ///
@@ -952,6 +1335,18 @@ impl MiningManagerProxy {
         spawn_blocking(move || self.inner.has_transaction(&transaction_id, query)).await.unwrap()
     }
 
+    pub async fn get_transaction_status(self, transaction_id: TransactionId) -> Option<TransactionStatus> {
+        spawn_blocking(move || self.inner.get_transaction_status(&transaction_id)).await.unwrap()
+    }
+
+    pub fn register_transaction_status_listener(&self) -> Channel<TransactionStatusChange> {
+        self.inner.register_transaction_status_listener()
+    }
+
+    pub fn set_transaction_status_notifications(&self, active: bool) {
+        self.inner.set_transaction_status_notifications(active)
+    }
+
     pub async fn transaction_count(self, query: TransactionQuery) -> usize {
         spawn_blocking(move || self.inner.transaction_count(query)).await.unwrap()
     }
@@ -960,6 +1355,20 @@ impl MiningManagerProxy {
         spawn_blocking(move || self.inner.get_all_transactions(query)).await.unwrap()
     }
 
+    /// Returns the mempool transactions whose id is accepted by `filter`
+    pub async fn get_transactions_by_filter(
+        self,
+        filter: impl Fn(&TransactionId) -> bool + Send + 'static,
+        query: TransactionQuery,
+    ) -> Vec<MutableTransaction> {
+        spawn_blocking(move || self.inner.get_transactions_by_filter(filter, query)).await.unwrap()
+    }
+
+    /// Returns a page of the mempool entries selected by `query`.
+    pub async fn get_mempool_entries(self, query: MempoolEntriesQuery) -> MempoolEntriesPage {
+        spawn_blocking(move || self.inner.get_mempool_entries(&query)).await.unwrap()
+    }
+
     /// get_transactions_by_addresses returns the sending and receiving transactions for
     /// a set of addresses.
     ///
@@ -996,6 +1405,18 @@ impl MiningManagerProxy {
         spawn_blocking(move || self.inner.unknown_transactions(transactions)).await.unwrap()
     }
 
+    /// Returns all the transactions of the mempool, orphans included, as they are saved on disk
+    pub async fn persisted_transactions(self) -> Vec<PersistedTransaction> {
+        spawn_blocking(move || self.inner.persisted_transactions()).await.unwrap()
+    }
+
+    /// Revalidates transactions previously saved on disk and inserts the valid ones back into the mempool.
+    ///
+    /// Returns the number of restored transactions.
+    pub async fn restore_transactions(self, consensus: &ConsensusProxy, transactions: Vec<PersistedTransaction>) -> usize {
+        consensus.clone().spawn_blocking(move |c| self.inner.restore_transactions(c, transactions)).await
+    }
+
     pub fn snapshot(&self) -> MempoolCountersSnapshot {
         self.inner.counters.snapshot()
     }
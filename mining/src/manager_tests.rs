@@ -8,9 +8,16 @@ mod tests {
             config::{Config, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE},
             errors::RuleError,
             model::frontier::selectors::TakeAllSelector,
+            policy::{DefaultMempoolPolicy, MempoolPolicyConfig, ScriptClassLimits},
             tx::{Orphan, Priority, RbfPolicy},
         },
-        model::{tx_insert::TransactionInsertion, tx_query::TransactionQuery},
+        model::{
+            mempool_entries::{MempoolEntriesOrder, MempoolEntriesQuery},
+            tx_insert::TransactionInsertion,
+            tx_query::TransactionQuery,
+            tx_selection::TransactionSelection,
+            tx_status::{TransactionStatus, TransactionStatusChange},
+        },
         testutils::consensus_mock::ConsensusMock,
         MiningCounters,
     };
@@ -37,7 +44,11 @@ mod tests {
         test_helpers::{create_transaction, create_transaction_with_change, op_true_script},
     };
     use vecno_utils::mem_size::MemSizeEstimator;
-    use std::{iter::once, sync::Arc};
+    use std::{
+        collections::{HashMap, HashSet},
+        iter::once,
+        sync::Arc,
+    };
     use tokio::sync::mpsc::{error::TryRecvError, unbounded_channel};
 
     const TARGET_TIME_PER_BLOCK: u64 = 1_000;
@@ -554,9 +565,9 @@ mod tests {
         const TX_COUNT: u32 = 10;
         let transactions_to_insert = (0..TX_COUNT).map(|i| create_transaction_with_utxo_entry(i, 0)).collect::<Vec<_>>();
         for transaction in transactions_to_insert.iter() {
-            let result = mining_manager.validate_and_insert_transaction(
+            let result = mining_manager.validate_and_insert_mutable_transaction(
                 consensus.as_ref(),
-                transaction.tx.as_ref().clone(),
+                transaction.clone(),
                 Priority::Low,
                 Orphan::Allowed,
                 RbfPolicy::Forbidden,
@@ -581,6 +592,9 @@ mod tests {
                 "the transaction {handled_tx_id} should not be in the mempool"
             );
         }
+        let feerate_history = mining_manager.get_feerate_history(usize::MAX);
+        assert_eq!(feerate_history.len(), 1, "the block should be recorded in the feerate history");
+        assert_eq!((feerate_history[0].daa_score, feerate_history[0].transaction_count), (2, PARTIAL_LEN as u32));
         // There are no chained/double-spends transactions, and hence it is expected that all the other
         // transactions, will still be included in the mempool.
         for handled_tx_id in rest.iter().map(|x| x.id()) {
@@ -602,6 +616,11 @@ mod tests {
                 "the transaction {handled_tx_id} should no longer be in the mempool"
             );
         }
+        let feerate_history = mining_manager.get_feerate_history(usize::MAX);
+        assert_eq!(
+            feerate_history.iter().map(|x| x.transaction_count).collect::<Vec<_>>(),
+            vec![PARTIAL_LEN as u32, TX_COUNT - PARTIAL_LEN as u32]
+        );
     }
 
     #[test]
@@ -637,6 +656,87 @@ mod tests {
         );
     }
 
+    /// test_transaction_status verifies the statuses reported and notified for the transactions going through the mempool
+    #[test]
+    fn test_transaction_status() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+        let status_changes = mining_manager.register_transaction_status_listener();
+        mining_manager.set_transaction_status_notifications(true);
+
+        let (parent_txs, child_txs) = create_arrays_of_parent_and_children_transactions(&consensus, 2);
+        let insert = |transaction: &Transaction, orphan: Orphan| {
+            mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                transaction.clone(),
+                Priority::Low,
+                orphan,
+                RbfPolicy::Forbidden,
+            )
+        };
+
+        // An orphan gets rejected when orphans are forbidden
+        assert!(insert(&child_txs[1], Orphan::Forbidden).is_err());
+        let rejection_reason = RuleError::RejectDisallowedOrphan(child_txs[1].id()).to_string();
+        assert_eq!(
+            mining_manager.get_transaction_status(&child_txs[1].id()),
+            Some(TransactionStatus::Rejected { reason: rejection_reason.clone() })
+        );
+
+        // An orphan moves to the transaction pool once its parent gets inserted
+        assert!(insert(&child_txs[0], Orphan::Allowed).is_ok());
+        assert_eq!(mining_manager.get_transaction_status(&child_txs[0].id()), Some(TransactionStatus::Orphan));
+        assert!(insert(&parent_txs[0], Orphan::Allowed).is_ok());
+        assert_eq!(mining_manager.get_transaction_status(&child_txs[0].id()), Some(TransactionStatus::InMempool));
+
+        // A transaction double spent by a block gets evicted while the block transaction is included
+        let transaction_in_the_mempool = create_transaction_with_utxo_entry(0, 0);
+        let result = mining_manager.validate_and_insert_mutable_transaction(
+            consensus.as_ref(),
+            transaction_in_the_mempool.clone(),
+            Priority::Low,
+            Orphan::Forbidden,
+            RbfPolicy::Forbidden,
+        );
+        assert!(result.is_ok());
+        let mut double_spend_transaction_in_the_block = create_transaction_with_utxo_entry(1, 0);
+        Arc::make_mut(&mut double_spend_transaction_in_the_block.tx).inputs[0].previous_outpoint =
+            transaction_in_the_mempool.tx.inputs[0].previous_outpoint;
+        let block_transactions = build_block_transactions(once(double_spend_transaction_in_the_block.tx.as_ref()));
+        assert!(mining_manager.handle_new_block_transactions(consensus.as_ref(), 2, &block_transactions).is_ok());
+        let eviction_reason = format!("double spend favouring {}", double_spend_transaction_in_the_block.id());
+        assert_eq!(
+            mining_manager.get_transaction_status(&transaction_in_the_mempool.id()),
+            Some(TransactionStatus::Evicted { reason: eviction_reason.clone() })
+        );
+        assert_eq!(
+            mining_manager.get_transaction_status(&double_spend_transaction_in_the_block.id()),
+            Some(TransactionStatus::Included { block_daa_score: 2 })
+        );
+        assert_eq!(mining_manager.get_transaction_status(&TransactionId::from_u64_word(u64::MAX)), None);
+
+        let expected_changes = vec![
+            TransactionStatusChange::new(child_txs[1].id(), TransactionStatus::Rejected { reason: rejection_reason }),
+            TransactionStatusChange::new(child_txs[0].id(), TransactionStatus::Orphan),
+            TransactionStatusChange::new(parent_txs[0].id(), TransactionStatus::InMempool),
+            TransactionStatusChange::new(child_txs[0].id(), TransactionStatus::InMempool),
+            TransactionStatusChange::new(transaction_in_the_mempool.id(), TransactionStatus::InMempool),
+            TransactionStatusChange::new(transaction_in_the_mempool.id(), TransactionStatus::Evicted { reason: eviction_reason }),
+            TransactionStatusChange::new(
+                double_spend_transaction_in_the_block.id(),
+                TransactionStatus::Included { block_daa_score: 2 },
+            ),
+        ];
+        let changes = std::iter::from_fn(|| status_changes.try_recv().ok()).collect_vec();
+        assert_eq!(changes, expected_changes);
+
+        // No change is sent once the notifications are disabled
+        mining_manager.set_transaction_status_notifications(false);
+        assert!(insert(&parent_txs[1], Orphan::Allowed).is_ok());
+        assert!(status_changes.is_empty());
+    }
+
     /// test_orphan_transactions verifies that a transaction could be a part of a new block template only if it's not an orphan.
     #[test]
     fn test_orphan_transactions() {
@@ -1175,6 +1275,350 @@ mod tests {
         assert!(validate_and_insert_mutable_transaction(&mining_manager, consensus.as_ref(), too_big_tx.clone()).is_err());
     }
 
+    /// test_block_template_with_selection verifies that a block template built with a caller-supplied selection includes
+    /// the valid mandatory transactions, skips the excluded ones and reports the mandatory ones which cannot be included.
+    #[test]
+    fn test_block_template_with_selection() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+
+        // Insert all parents and a child in the mempool, the child being not ready for a block
+        let (parent_txs, child_txs) = create_arrays_of_parent_and_children_transactions(&consensus, 3);
+        let (priority, orphan, rbf_policy) = (Priority::Low, Orphan::Forbidden, RbfPolicy::Forbidden);
+        validate_and_insert_transactions(&mining_manager, consensus.as_ref(), parent_txs.iter(), priority, orphan, rbf_policy);
+        validate_and_insert_transactions(&mining_manager, consensus.as_ref(), child_txs.iter().take(1), priority, orphan, rbf_policy);
+
+        // A transaction unknown to the mempool and another one double spending it
+        let funding_txs = create_and_add_funding_transactions(&consensus, 1);
+        let raw_tx =
+            create_funded_transaction(select_transactions(&funding_txs, &[0]), vec![0], None, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        let double_spend_tx = create_funded_transaction(
+            select_transactions(&funding_txs, &[0]),
+            vec![0],
+            None,
+            DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE * 2,
+        );
+
+        let selection = TransactionSelection::new(
+            vec![parent_txs[0].id(), child_txs[0].id(), child_txs[1].id()],
+            vec![raw_tx.clone(), double_spend_tx.clone()],
+            vec![parent_txs[1].id()],
+        );
+        let miner_data = get_miner_data(Prefix::Testnet);
+        let (_, shared_template_id) = mining_manager.get_block_template_with_id(consensus.as_ref(), &miner_data).unwrap();
+        let mut updates = mining_manager.block_template_updates();
+        updates.borrow_and_update();
+        let result = mining_manager.get_block_template_with_selection(consensus.as_ref(), &miner_data, &selection);
+        assert!(result.is_ok(), "failed at getting a block template with a transaction selection");
+        let (template, template_id, rejected_transactions) = result.unwrap();
+
+        for included in [&parent_txs[0], &parent_txs[2], &raw_tx] {
+            assert!(contained_by(included.id(), &template.block.transactions), "transaction {} should be included", included.id());
+        }
+        assert!(!contained_by(parent_txs[1].id(), &template.block.transactions), "excluded transactions should not be included");
+        assert_eq!(
+            rejected_transactions.iter().map(|x| x.transaction_id).collect::<HashSet<_>>(),
+            HashSet::from([child_txs[0].id(), child_txs[1].id(), double_spend_tx.id()]),
+            "mandatory transactions not ready, unknown or double spending should be rejected"
+        );
+
+        // The template is specific to its caller so it must not affect the template shared with other miners
+        assert!(!updates.has_changed().unwrap(), "a template with a transaction selection should not signal an update");
+        assert_eq!(mining_manager.get_block_template_with_id(consensus.as_ref(), &miner_data).unwrap().1, shared_template_id);
+        let (_, rebuilt_template_id, _) =
+            mining_manager.get_block_template_with_selection(consensus.as_ref(), &miner_data, &selection).unwrap();
+        assert_eq!(template_id, rebuilt_template_id, "rebuilding the same selection should keep the template id");
+    }
+
+    /// test_policy_rate_limit verifies that only the transactions actually inserted into the mempool consume the rate
+    /// limit allowance of the mempool policy and that restored transactions consume none.
+    #[test]
+    fn test_policy_rate_limit() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let build_mining_manager = |transactions_per_minute| {
+            let limits = ScriptClassLimits { max_transactions_per_minute: Some(transactions_per_minute), ..Default::default() };
+            let policy_config =
+                MempoolPolicyConfig { script_classes: HashMap::from([("scripthash".to_owned(), limits)]), ..Default::default() };
+            let mut config = Config::build_default(ForkedParam::new_const(TARGET_TIME_PER_BLOCK), false, MAX_BLOCK_MASS)
+                .with_policy(Arc::new(DefaultMempoolPolicy::new(policy_config).unwrap()));
+            config.maximum_transaction_count = 1;
+            MiningManager::with_config(config, None, Arc::new(MiningCounters::default()))
+        };
+        let insert = |mining_manager: &MiningManager, transaction: &Transaction| {
+            let (priority, orphan, rbf_policy) = (Priority::Low, Orphan::Forbidden, RbfPolicy::Forbidden);
+            into_mempool_result(mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                transaction.clone(),
+                priority,
+                orphan,
+                rbf_policy,
+            ))
+        };
+
+        // Unrelated transactions, the second one paying the lowest fee
+        let funding_txs = create_and_add_funding_transactions(&consensus, 4);
+        let txs = funding_txs
+            .iter()
+            .zip([2, 1, 4, 8])
+            .map(|(funding_tx, fee)| create_transaction(funding_tx, fee * DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE))
+            .collect_vec();
+
+        let mining_manager = build_mining_manager(2);
+        assert!(insert(&mining_manager, &txs[0]).is_ok());
+        assert!(matches!(insert(&mining_manager, &txs[1]), Err(RuleError::RejectMempoolIsFull)));
+        assert!(insert(&mining_manager, &txs[2]).is_ok(), "a transaction rejected for a full mempool should consume no allowance");
+        assert!(matches!(insert(&mining_manager, &txs[3]), Err(RuleError::RejectNonStandard(..))));
+
+        let restarted_mining_manager = build_mining_manager(1);
+        let restored = restarted_mining_manager.restore_transactions(consensus.as_ref(), mining_manager.persisted_transactions());
+        assert_eq!(restored, 1);
+        assert!(insert(&restarted_mining_manager, &txs[3]).is_ok(), "a restored transaction should consume no allowance");
+    }
+
+    /// test_restore_transactions verifies that persisted transactions are restored in a fresh mempool, whatever the
+    /// order they were persisted in, and that transactions which are no longer valid are dropped.
+    #[test]
+    fn test_restore_transactions() {
+        const TX_PAIRS_COUNT: usize = 5;
+
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+        let (parent_txs, child_txs) = create_arrays_of_parent_and_children_transactions(&consensus, TX_PAIRS_COUNT);
+        let (orphan, rbf_policy) = (Orphan::Forbidden, RbfPolicy::Forbidden);
+        validate_and_insert_transactions(&mining_manager, consensus.as_ref(), parent_txs.iter(), Priority::High, orphan, rbf_policy);
+        validate_and_insert_transactions(&mining_manager, consensus.as_ref(), child_txs.iter(), Priority::Low, orphan, rbf_policy);
+        let orphan_tx = create_transaction(&create_transaction_without_input(vec![500 * VENI_PER_VECNO]), 1000);
+        validate_and_insert_transactions(
+            &mining_manager,
+            consensus.as_ref(),
+            once(&orphan_tx),
+            Priority::Low,
+            Orphan::Allowed,
+            rbf_policy,
+        );
+
+        // Children are persisted ahead of their parents
+        let mut persisted_transactions = mining_manager.persisted_transactions();
+        assert_eq!(persisted_transactions.len(), TX_PAIRS_COUNT * 2 + 1);
+        persisted_transactions.sort_by_key(|tx| tx.priority == Priority::High);
+
+        // The first parent got mined while the node was down, so it is no longer valid but its child is
+        consensus.add_transaction(parent_txs[0].clone(), 1);
+
+        let counters = Arc::new(MiningCounters::default());
+        let restarted_mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+        let restored = restarted_mining_manager.restore_transactions(consensus.as_ref(), persisted_transactions);
+        assert_eq!(restored, TX_PAIRS_COUNT * 2, "all still valid transactions should be restored");
+        assert!(!restarted_mining_manager.has_transaction(&parent_txs[0].id(), TransactionQuery::All));
+        for transaction in parent_txs.iter().skip(1).chain(child_txs.iter()) {
+            let restored_transaction = restarted_mining_manager.get_transaction(&transaction.id(), TransactionQuery::TransactionsOnly);
+            assert!(restored_transaction.is_some(), "transaction {} should be restored", transaction.id());
+        }
+        assert!(restarted_mining_manager.has_transaction(&orphan_tx.id(), TransactionQuery::OrphansOnly));
+    }
+
+    /// test_package_child_pays_for_parent verifies that a parent paying no fee is accepted as part of a package whose child
+    /// pays for both, that a rejected package leaves no trace in the mempool and that replacing the parent requires
+    /// outbidding the feerate of the whole package.
+    #[test]
+    fn test_package_child_pays_for_parent() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+        let funding_txs = create_and_add_funding_transactions(&consensus, 2);
+        let (priority, orphan, rbf_policy) = (Priority::High, Orphan::Forbidden, RbfPolicy::Forbidden);
+
+        // A parent paying no fee is rejected on its own
+        let parent_tx = create_transaction(&funding_txs[0], 0);
+        let result =
+            mining_manager.validate_and_insert_transaction(consensus.as_ref(), parent_tx.clone(), priority, orphan, rbf_policy);
+        assert!(result.is_err(), "a transaction paying no fee should be rejected");
+
+        // A package not paying enough fees as a whole is rejected, leaving the mempool untouched
+        let poor_child_tx = create_transaction(&parent_tx, 0);
+        let result = mining_manager.validate_and_insert_package(consensus.as_ref(), vec![parent_tx.clone(), poor_child_tx], priority);
+        assert!(matches!(into_mempool_result(result), Err(RuleError::RejectPackageInsufficientFee(0, _))));
+        assert_transaction_count(&mining_manager, 0, "a rejected package should leave no transaction in the mempool");
+
+        // Transactions of a package must be related
+        let unrelated_tx = create_transaction(&funding_txs[1], DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        let result = mining_manager.validate_and_insert_package(consensus.as_ref(), vec![parent_tx.clone(), unrelated_tx], priority);
+        assert!(matches!(into_mempool_result(result), Err(RuleError::RejectPackageUnrelatedTransaction(_))));
+
+        // The child pays for its parent, whatever their order in the package
+        let child_tx = create_transaction(&parent_tx, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE * 4);
+        let result =
+            mining_manager.validate_and_insert_package(consensus.as_ref(), vec![child_tx.clone(), parent_tx.clone()], priority);
+        assert!(result.is_ok(), "a child paying for its parent should get the package accepted");
+        let accepted_ids = result.unwrap().accepted.iter().map(|x| x.id()).collect_vec();
+        assert_eq!(accepted_ids, vec![parent_tx.id(), child_tx.id()], "package transactions should be accepted in topological order");
+        assert_transaction_count(&mining_manager, 2, "both package transactions should be in the mempool");
+
+        // Outbidding the parent alone does not suffice to replace it
+        let rbf_policy = RbfPolicy::Allowed;
+        let replacement_tx = create_transaction(&funding_txs[0], DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        let result = mining_manager.validate_and_insert_transaction(consensus.as_ref(), replacement_tx, priority, orphan, rbf_policy);
+        assert!(matches!(into_mempool_result(result), Err(RuleError::RejectDoubleSpendInMempool(_, id)) if id == parent_tx.id()));
+
+        // Outbidding the whole package does
+        let replacement_tx = create_transaction(&funding_txs[0], DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE * 10);
+        let result = mining_manager.validate_and_insert_transaction(consensus.as_ref(), replacement_tx, priority, orphan, rbf_policy);
+        assert!(result.is_ok(), "a transaction outbidding the package feerate should replace the parent");
+        assert_transaction_count(&mining_manager, 1, "the package should be replaced");
+    }
+
+    /// test_package_eviction verifies that a package competes for room in a full mempool with the feerate of all its
+    /// transactions and that a rejected package evicts no transaction.
+    #[test]
+    fn test_package_eviction() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let mut config = Config::build_default(ForkedParam::new_const(TARGET_TIME_PER_BLOCK), false, MAX_BLOCK_MASS);
+        config.maximum_transaction_count = 3;
+        let mining_manager = MiningManager::with_config(config, None, Arc::new(MiningCounters::default()));
+        let funding_txs = create_and_add_funding_transactions(&consensus, 4);
+        let (orphan, rbf_policy) = (Orphan::Forbidden, RbfPolicy::Forbidden);
+
+        // All the transactions have the same mass, the low priority ones paying exactly the minimum relay fee
+        let mass = transaction_estimated_serialized_size(&create_transaction(&funding_txs[0], 0));
+        let minimum_fee = mass * DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE / 1000;
+        let low_priority_txs = funding_txs[1..].iter().map(|funding_tx| create_transaction(funding_tx, minimum_fee)).collect_vec();
+        for tx in low_priority_txs.iter() {
+            let result =
+                mining_manager.validate_and_insert_transaction(consensus.as_ref(), tx.clone(), Priority::Low, orphan, rbf_policy);
+            assert!(result.is_ok(), "a transaction paying the minimum relay fee should be accepted");
+        }
+        assert_transaction_count(&mining_manager, 3, "the mempool should be full");
+        let low_priority_count = || {
+            low_priority_txs.iter().filter(|tx| mining_manager.has_transaction(&tx.id(), TransactionQuery::TransactionsOnly)).count()
+        };
+
+        // The parent alone outbids the low priority transactions but the package does not pay its minimum relay fee
+        let parent_tx = create_transaction(&funding_txs[0], minimum_fee * 3 / 2);
+        let poor_child_tx = create_transaction(&parent_tx, 0);
+        let result =
+            mining_manager.validate_and_insert_package(consensus.as_ref(), vec![parent_tx.clone(), poor_child_tx], Priority::High);
+        assert!(matches!(into_mempool_result(result), Err(RuleError::RejectPackageInsufficientFee(..))));
+        assert_eq!(low_priority_count(), 3, "a rejected package should evict no transaction");
+
+        // A child paying for its parent makes room for the whole package
+        let child_tx = create_transaction(&parent_tx, minimum_fee * 3);
+        let result =
+            mining_manager.validate_and_insert_package(consensus.as_ref(), vec![parent_tx.clone(), child_tx.clone()], Priority::High);
+        assert!(result.is_ok(), "a package outbidding low priority transactions should evict them");
+        assert_transaction_count(&mining_manager, 3, "the mempool should stay full");
+        assert!(mining_manager.has_transaction(&parent_tx.id(), TransactionQuery::TransactionsOnly));
+        assert!(mining_manager.has_transaction(&child_tx.id(), TransactionQuery::TransactionsOnly));
+        assert_eq!(low_priority_count(), 1, "two low priority transactions should be evicted");
+    }
+
+    /// test_package_descendant_limit verifies that only the descendants within the maximum package transaction count
+    /// of a transaction pay for it.
+    #[test]
+    fn test_package_descendant_limit() {
+        for (maximum_package_transaction_count, expect_replacement) in [(2, true), (3, false)] {
+            let consensus = Arc::new(ConsensusMock::new());
+            let mut config = Config::build_default(ForkedParam::new_const(TARGET_TIME_PER_BLOCK), false, MAX_BLOCK_MASS);
+            config.maximum_package_transaction_count = maximum_package_transaction_count;
+            let mining_manager = MiningManager::with_config(config, None, Arc::new(MiningCounters::default()));
+            let funding_txs = create_and_add_funding_transactions(&consensus, 1);
+            let (priority, orphan) = (Priority::High, Orphan::Forbidden);
+
+            // A chain where only the grandchild pays a high fee
+            let mass = transaction_estimated_serialized_size(&create_transaction(&funding_txs[0], 0));
+            let minimum_fee = mass * DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE / 1000;
+            let parent_tx = create_transaction(&funding_txs[0], minimum_fee);
+            let child_tx = create_transaction(&parent_tx, minimum_fee);
+            let grandchild_tx = create_transaction(&child_tx, minimum_fee * 20);
+            for tx in [parent_tx, child_tx, grandchild_tx] {
+                let result =
+                    mining_manager.validate_and_insert_transaction(consensus.as_ref(), tx, priority, orphan, RbfPolicy::Forbidden);
+                assert!(result.is_ok(), "a transaction paying the minimum relay fee should be accepted");
+            }
+
+            // The replacement outbids the parent and its child but not the package of the grandchild
+            let (replacement_tx, rbf_policy) = (create_transaction(&funding_txs[0], minimum_fee * 2), RbfPolicy::Allowed);
+            let result =
+                mining_manager.validate_and_insert_transaction(consensus.as_ref(), replacement_tx, priority, orphan, rbf_policy);
+            assert_eq!(
+                result.is_ok(),
+                expect_replacement,
+                "with a package limit of {}, the grandchild should {}pay for the parent",
+                maximum_package_transaction_count,
+                if expect_replacement { "not " } else { "" }
+            );
+        }
+    }
+
+    /// test_get_mempool_entries verifies that the mempool entries are paged through in the requested order,
+    /// each of them exactly once, and that the feerate, address and compact options are applied.
+    #[test]
+    fn test_get_mempool_entries() {
+        const TX_COUNT: usize = 5;
+
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+        let funding_txs = create_and_add_funding_transactions(&consensus, TX_COUNT);
+        let transactions = funding_txs
+            .iter()
+            .enumerate()
+            .map(|(i, funding_tx)| create_transaction(funding_tx, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE * (i as u64 + 1)))
+            .collect_vec();
+        let (priority, orphan, rbf_policy) = (Priority::Low, Orphan::Allowed, RbfPolicy::Forbidden);
+        validate_and_insert_transactions(&mining_manager, consensus.as_ref(), transactions.iter(), priority, orphan, rbf_policy);
+        let orphan_tx = create_transaction(&create_transaction_without_input(vec![500 * VENI_PER_VECNO]), 1000);
+        validate_and_insert_transactions(&mining_manager, consensus.as_ref(), once(&orphan_tx), priority, orphan, rbf_policy);
+
+        let query = |order, limit, min_feerate, compact| MempoolEntriesQuery {
+            query: TransactionQuery::All,
+            order,
+            cursor: None,
+            limit,
+            min_feerate,
+            script_public_keys: Default::default(),
+            compact,
+        };
+
+        // Every entry is paged through once, the best paying entries coming first and the orphan last by feerate
+        for order in [MempoolEntriesOrder::Feerate, MempoolEntriesOrder::Arrival] {
+            let mut query = query(order, 2, None, true);
+            let mut entries = vec![];
+            loop {
+                let page = mining_manager.get_mempool_entries(&query);
+                assert!(page.entries.len() <= 2);
+                assert!(page.entries.iter().all(|x| x.transaction.is_none()), "compact entries should have no transaction");
+                entries.extend(page.entries);
+                match page.next_cursor {
+                    Some(cursor) => query.cursor = Some(cursor),
+                    None => break,
+                }
+            }
+            assert_eq!(entries.len(), TX_COUNT + 1, "{order:?}: every entry should be paged through");
+            assert_eq!(entries.iter().map(|x| x.transaction_id).unique().count(), TX_COUNT + 1);
+            if order == MempoolEntriesOrder::Feerate {
+                let expected = transactions.iter().rev().map(|x| x.id()).chain(once(orphan_tx.id())).collect_vec();
+                assert_eq!(entries.iter().map(|x| x.transaction_id).collect_vec(), expected);
+                assert!(entries.last().unwrap().is_orphan && entries.last().unwrap().fee.is_none());
+            }
+        }
+
+        // The minimum feerate filters out the lower paying entries and the orphans
+        let page = mining_manager.get_mempool_entries(&query(MempoolEntriesOrder::Feerate, TX_COUNT, None, false));
+        let third = &page.entries[2];
+        let min_feerate = third.fee.unwrap() as f64 / third.mass as f64;
+        let page = mining_manager.get_mempool_entries(&query(MempoolEntriesOrder::Feerate, usize::MAX, Some(min_feerate), false));
+        assert_eq!(page.entries.len(), 3);
+        assert!(page.next_cursor.is_none());
+        assert!(page.entries.iter().all(|x| x.transaction.as_ref().is_some_and(|tx| tx.id() == x.transaction_id)));
+
+        // Filtering by a script public key no entry pays to or spends from returns an empty page
+        let mut query = query(MempoolEntriesOrder::Arrival, usize::MAX, None, true);
+        query.script_public_keys.insert(ScriptPublicKey::from_vec(0, vec![0x51, 0x51]));
+        assert!(mining_manager.get_mempool_entries(&query).entries.is_empty());
+    }
+
     fn validate_and_insert_mutable_transaction(
         mining_manager: &MiningManager,
         consensus: &dyn ConsensusApi,
@@ -167,8 +167,6 @@ impl Mempool {
     /// context of this function is one whose referenced public key script is of a
     /// standard form and, for pay-to-script-hash, does not have more than
     /// maxStandardP2SHSigOps signature operations.
-    /// In addition, makes sure that the transaction's fee is above the minimum for acceptance
-    /// into the mempool and relay.
     pub(crate) fn check_transaction_standard_in_context(&self, transaction: &MutableTransaction) -> NonStandardResult<()> {
         let transaction_id = transaction.id();
         let contextual_mass = transaction.tx.mass();
@@ -197,22 +195,30 @@ impl Mempool {
                     }
                 }
             }
-
-            // TODO: For now, until wallets adapt, we only require minimum fee as function of compute mass (but the fee/mass ratio will
-            // use the max over all masses and will affect tx selection to block template)
-            let minimum_fee =
-                self.minimum_required_transaction_relay_fee(transaction.calculated_non_contextual_masses.unwrap().compute_mass);
-            if transaction.calculated_fee.unwrap() < minimum_fee {
-                return Err(NonStandardError::RejectInsufficientFee(transaction_id, transaction.calculated_fee.unwrap(), minimum_fee));
-            }
         }
 
         Ok(())
     }
 
+    /// check_transaction_relay_fee makes sure that the transaction's fee is above the minimum
+    /// for acceptance into the mempool and relay.
+    ///
+    /// Transactions submitted as part of a package are exempt from this check, the package
+    /// paying for its transactions as a whole.
+    pub(crate) fn check_transaction_relay_fee(&self, transaction: &MutableTransaction) -> NonStandardResult<()> {
+        // TODO: For now, until wallets adapt, we only require minimum fee as function of compute mass (but the fee/mass ratio will
+        // use the max over all masses and will affect tx selection to block template)
+        let minimum_fee =
+            self.minimum_required_transaction_relay_fee(transaction.calculated_non_contextual_masses.unwrap().compute_mass);
+        if transaction.calculated_fee.unwrap() < minimum_fee {
+            return Err(NonStandardError::RejectInsufficientFee(transaction.id(), transaction.calculated_fee.unwrap(), minimum_fee));
+        }
+        Ok(())
+    }
+
     /// minimum_required_transaction_relay_fee returns the minimum transaction fee required
     /// for a transaction with the passed mass to be accepted into the mempool and relayed.
-    fn minimum_required_transaction_relay_fee(&self, mass: u64) -> u64 {
+    pub(crate) fn minimum_required_transaction_relay_fee(&self, mass: u64) -> u64 {
         // Calculate the minimum fee for a transaction to be allowed into the
         // mempool and relayed by scaling the base fee. MinimumRelayTransactionFee is in
         // veni/kg so multiply by mass (which is in grams) and divide by 1000 to get
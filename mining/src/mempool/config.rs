@@ -1,4 +1,6 @@
+use super::policy::{DefaultMempoolPolicy, MempoolPolicy};
 use vecno_consensus_core::{config::params::ForkedParam, constants::TX_VERSION};
+use std::sync::Arc;
 
 pub(crate) const DEFAULT_MAXIMUM_TRANSACTION_COUNT: usize = 1_000_000;
 pub(crate) const DEFAULT_MEMPOOL_SIZE_LIMIT: usize = 1_000_000_000;
@@ -14,6 +16,10 @@ pub(crate) const DEFAULT_ORPHAN_EXPIRE_SCAN_INTERVAL_SECONDS: u64 = 10;
 pub(crate) const DEFAULT_MAXIMUM_ORPHAN_TRANSACTION_MASS: u64 = 100_000;
 pub(crate) const DEFAULT_MAXIMUM_ORPHAN_TRANSACTION_COUNT: u64 = 500;
 
+/// DEFAULT_MAXIMUM_PACKAGE_TRANSACTION_COUNT bounds both the size of a submitted package and the number of related
+/// transactions accounted for when computing the package feerate of a transaction.
+pub(crate) const DEFAULT_MAXIMUM_PACKAGE_TRANSACTION_COUNT: usize = 25;
+
 /// DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE specifies the minimum transaction fee for a transaction to be accepted to
 /// the mempool and relayed. It is specified in veni per 1kg (or 1000 grams) of transaction mass.
 pub(crate) const DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE: u64 = 1000;
@@ -40,12 +46,14 @@ pub struct Config {
     pub orphan_expire_scan_interval_daa_score: ForkedParam<u64>,
     pub maximum_orphan_transaction_mass: u64,
     pub maximum_orphan_transaction_count: u64,
+    pub maximum_package_transaction_count: usize,
     pub accept_non_standard: bool,
     pub maximum_mass_per_block: u64,
     pub minimum_relay_transaction_fee: u64,
     pub minimum_standard_transaction_version: u16,
     pub maximum_standard_transaction_version: u16,
     pub network_blocks_per_second: ForkedParam<u64>,
+    pub policy: Arc<dyn MempoolPolicy>,
 }
 
 impl Config {
@@ -64,6 +72,7 @@ impl Config {
         orphan_expire_scan_interval_daa_score: ForkedParam<u64>,
         maximum_orphan_transaction_mass: u64,
         maximum_orphan_transaction_count: u64,
+        maximum_package_transaction_count: usize,
         accept_non_standard: bool,
         maximum_mass_per_block: u64,
         minimum_relay_transaction_fee: u64,
@@ -85,12 +94,14 @@ impl Config {
             orphan_expire_scan_interval_daa_score,
             maximum_orphan_transaction_mass,
             maximum_orphan_transaction_count,
+            maximum_package_transaction_count,
             accept_non_standard,
             maximum_mass_per_block,
             minimum_relay_transaction_fee,
             minimum_standard_transaction_version,
             maximum_standard_transaction_version,
             network_blocks_per_second,
+            policy: Arc::new(DefaultMempoolPolicy::default()),
         }
     }
 
@@ -120,12 +131,14 @@ impl Config {
                 .map(|v| DEFAULT_ORPHAN_EXPIRE_SCAN_INTERVAL_SECONDS * 1000 / v),
             maximum_orphan_transaction_mass: DEFAULT_MAXIMUM_ORPHAN_TRANSACTION_MASS,
             maximum_orphan_transaction_count: DEFAULT_MAXIMUM_ORPHAN_TRANSACTION_COUNT,
+            maximum_package_transaction_count: DEFAULT_MAXIMUM_PACKAGE_TRANSACTION_COUNT,
             accept_non_standard: relay_non_std_transactions,
             maximum_mass_per_block: max_block_mass,
             minimum_relay_transaction_fee: DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE,
             minimum_standard_transaction_version: DEFAULT_MINIMUM_STANDARD_TRANSACTION_VERSION,
             maximum_standard_transaction_version: DEFAULT_MAXIMUM_STANDARD_TRANSACTION_VERSION,
             network_blocks_per_second: target_milliseconds_per_block.map(|v| 1000 / v),
+            policy: Arc::new(DefaultMempoolPolicy::default()),
         }
     }
 
@@ -136,6 +149,11 @@ impl Config {
         self
     }
 
+    pub fn with_policy(mut self, policy: Arc<dyn MempoolPolicy>) -> Self {
+        self.policy = policy;
+        self
+    }
+
     /// Returns the minimum standard fee/mass ratio currently required by the mempool
     pub(crate) fn minimum_feerate(&self) -> f64 {
         // The parameter minimum_relay_transaction_fee is in veni/kg units so divide by 1000 to get veni/gram
@@ -1,10 +1,14 @@
-use crate::mempool::{
-    errors::RuleResult,
-    model::{
-        pool::Pool,
-        tx::{MempoolTransaction, TxRemovalReason},
+use crate::{
+    feerate::history::FeerateInclusion,
+    mempool::{
+        errors::RuleResult,
+        model::{
+            pool::Pool,
+            tx::{MempoolTransaction, TxRemovalReason},
+        },
+        Mempool,
     },
-    Mempool,
+    model::tx_status::TransactionStatus,
 };
 use vecno_consensus_core::{
     api::ConsensusApi,
@@ -40,6 +44,7 @@ impl Mempool {
                 input_counts += transaction.inputs.len();
                 output_counts += transaction.outputs.len();
             }
+            self.transaction_statuses.update(transaction_id, TransactionStatus::Included { block_daa_score });
             unorphaned_transactions.extend(self.get_unorphaned_transactions_after_accepted_transaction(transaction));
         }
         self.counters.block_tx_counts.fetch_add(block_transactions.len() as u64 - 1, Ordering::Relaxed);
@@ -54,6 +59,24 @@ impl Mempool {
         Ok(unorphaned_transactions)
     }
 
+    /// Returns the feerate paid by and the time spent in the mempool by each of the block transactions found in the
+    /// transaction pool. Must be called before the block transactions get removed from the mempool.
+    pub(crate) fn collect_feerate_inclusions(
+        &self,
+        block_daa_score: u64,
+        block_transactions: &[Transaction],
+        blocks_per_second: u64,
+    ) -> Vec<FeerateInclusion> {
+        block_transactions[1..]
+            .iter()
+            .filter_map(|transaction| self.transaction_pool.get(&transaction.id()))
+            .map(|transaction| {
+                let waited_blocks = block_daa_score.saturating_sub(transaction.added_at_daa_score);
+                FeerateInclusion::new(transaction.feerate(), waited_blocks as f64 / blocks_per_second as f64)
+            })
+            .collect()
+    }
+
     pub(crate) fn expire_orphan_low_priority_transactions(&mut self, consensus: &dyn ConsensusApi) -> RuleResult<()> {
         self.orphan_pool.expire_low_priority_transactions(consensus.get_virtual_daa_score())
     }
@@ -75,6 +98,7 @@ impl Mempool {
         }
         transactions_to_remove.iter().try_for_each(|x| {
             self.remove_transaction(x, true, TxRemovalReason::DoubleSpend, format!(" favouring {}", transaction.id()).as_str())
+                .map(|_| ())
         })
     }
 }
@@ -1,7 +1,10 @@
 use crate::{
     feerate::{FeerateEstimator, FeerateEstimatorArgs},
     model::{
+        candidate_tx::CandidateTransaction,
+        mempool_entries::{MempoolEntriesPage, MempoolEntriesQuery},
         owner_txs::{GroupedOwnerTransactions, ScriptPublicKeySet},
+        persisted_tx::PersistedTransaction,
         tx_query::TransactionQuery,
     },
     MiningCounters,
@@ -9,7 +12,10 @@ use crate::{
 
 use self::{
     config::Config,
-    model::{accepted_transactions::AcceptedTransactions, orphan_pool::OrphanPool, pool::Pool, transactions_pool::TransactionsPool},
+    model::{
+        accepted_transactions::AcceptedTransactions, entries_selection::EntriesSelection, orphan_pool::OrphanPool, pool::Pool,
+        status_tracker::TransactionStatusTracker, transactions_pool::TransactionsPool,
+    },
     tx::Priority,
 };
 use vecno_consensus_core::{
@@ -24,9 +30,11 @@ pub mod config;
 pub mod errors;
 pub(crate) mod handle_new_block_transactions;
 pub(crate) mod model;
+pub mod policy;
 pub(crate) mod populate_entries_and_try_validate;
 pub(crate) mod remove_transaction;
 pub(crate) mod replace_by_fee;
+pub(crate) mod validate_and_insert_package;
 pub(crate) mod validate_and_insert_transaction;
 
 /// Mempool contains transactions intended to be inserted into a block and mined.
@@ -50,15 +58,21 @@ pub(crate) struct Mempool {
     transaction_pool: TransactionsPool,
     orphan_pool: OrphanPool,
     accepted_transactions: AcceptedTransactions,
+    transaction_statuses: Arc<TransactionStatusTracker>,
     counters: Arc<MiningCounters>,
 }
 
 impl Mempool {
     pub(crate) fn new(config: Arc<Config>, counters: Arc<MiningCounters>) -> Self {
         let transaction_pool = TransactionsPool::new(config.clone());
-        let orphan_pool = OrphanPool::new(config.clone());
+        let transaction_statuses = Arc::new(TransactionStatusTracker::default());
+        let orphan_pool = OrphanPool::new(config.clone(), transaction_statuses.clone());
         let accepted_transactions = AcceptedTransactions::new(config.clone());
-        Self { config, transaction_pool, orphan_pool, accepted_transactions, counters }
+        Self { config, transaction_pool, orphan_pool, accepted_transactions, transaction_statuses, counters }
+    }
+
+    pub(crate) fn transaction_statuses(&self) -> Arc<TransactionStatusTracker> {
+        self.transaction_statuses.clone()
     }
 
     pub(crate) fn get_transaction(&self, transaction_id: &TransactionId, query: TransactionQuery) -> Option<MutableTransaction> {
@@ -104,6 +118,19 @@ impl Mempool {
         owner_set
     }
 
+    /// Returns a page of the entries selected by `query`, only cloning the transactions of the page
+    pub(crate) fn get_entries_page(&self, query: &MempoolEntriesQuery) -> MempoolEntriesPage {
+        let _sw = Stopwatch::<50>::with_threshold("get_entries_page op");
+        let mut selection = EntriesSelection::new(query);
+        if query.query.include_transaction_pool() {
+            selection.scan(&self.transaction_pool, false);
+        }
+        if query.query.include_orphan_pool() {
+            selection.scan(&self.orphan_pool, true);
+        }
+        selection.into_page(&self.transaction_pool, &self.orphan_pool)
+    }
+
     pub(crate) fn transaction_count(&self, query: TransactionQuery) -> usize {
         let mut count = 0;
         if query.include_transaction_pool() {
@@ -129,6 +156,16 @@ impl Mempool {
         self.transaction_pool.build_selector()
     }
 
+    /// Returns all the ready transactions as block template candidates
+    pub(crate) fn ready_candidate_transactions(&self) -> Vec<CandidateTransaction> {
+        self.transaction_pool.ready_candidate_transactions()
+    }
+
+    /// Returns the transaction as a block template candidate if it is ready to be inserted into a block
+    pub(crate) fn get_ready_candidate_transaction(&self, transaction_id: &TransactionId) -> Option<CandidateTransaction> {
+        self.transaction_pool.get_ready_candidate_transaction(transaction_id)
+    }
+
     /// Builds a feerate estimator based on internal state of the ready transactions frontier
     pub(crate) fn build_feerate_estimator(&self, args: FeerateEstimatorArgs) -> FeerateEstimator {
         self.transaction_pool.build_feerate_estimator(args)
@@ -143,6 +180,26 @@ impl Mempool {
         self.transaction_pool.update_revalidated_transaction(transaction)
     }
 
+    /// Returns all the transactions of the mempool, orphans included, as they are saved on disk
+    pub(crate) fn persisted_transactions(&self) -> Vec<PersistedTransaction> {
+        let _sw = Stopwatch::<100>::with_threshold("persisted_transactions op");
+        let transactions = self.transaction_pool.all().values().map(|tx| (tx, false));
+        let orphans = self.orphan_pool.all().values().map(|tx| (tx, true));
+        transactions
+            .chain(orphans)
+            .map(|(tx, is_orphan)| {
+                PersistedTransaction::new(tx.mtx.tx.as_ref().clone(), tx.priority, tx.added_at_daa_score, is_orphan)
+            })
+            .collect()
+    }
+
+    /// Sets back the DAA score at which a transaction restored from disk was originally added, so that
+    /// restarting the node does not postpone its expiration
+    pub(crate) fn restore_added_at_daa_score(&mut self, transaction_id: &TransactionId, added_at_daa_score: u64) -> bool {
+        self.transaction_pool.restore_added_at_daa_score(transaction_id, added_at_daa_score)
+            || self.orphan_pool.restore_added_at_daa_score(transaction_id, added_at_daa_score)
+    }
+
     pub(crate) fn has_accepted_transaction(&self, transaction_id: &TransactionId) -> bool {
         self.accepted_transactions.has(transaction_id)
     }
@@ -165,7 +222,9 @@ impl Mempool {
 }
 
 pub mod tx {
-    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
     pub enum Priority {
         Low,
         High,
@@ -177,6 +236,14 @@ pub mod tx {
         Allowed,
     }
 
+    /// Whether the rate limits of the mempool policy apply to the insertion of a transaction
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum RateLimit {
+        Enforced,
+        /// Used for transactions restored from disk, which were already admitted before the node restarted
+        Skipped,
+    }
+
     /// Replace by Fee (RBF) policy
     #[derive(Debug, Clone, Copy, PartialEq, Eq)]
     pub enum RbfPolicy {
@@ -0,0 +1,72 @@
+use crate::{
+    mempool::model::{pool::Pool, tx::MempoolTransaction},
+    model::mempool_entries::{MempoolEntriesCursor, MempoolEntriesPage, MempoolEntriesQuery, MempoolEntry},
+};
+use std::collections::BinaryHeap;
+
+/// Selection of the entries of a [`MempoolEntriesPage`] out of the mempool pools.
+///
+/// The pools are scanned once while only the `limit + 1` first matching entries in the page order are retained,
+/// the extra one being the start of the next page. Nothing is cloned until the page is built.
+pub(crate) struct EntriesSelection<'a> {
+    query: &'a MempoolEntriesQuery,
+    /// Max-heap of the retained entries, so that the last one in the page order is evicted first
+    selected: BinaryHeap<(MempoolEntriesCursor, bool)>,
+}
+
+impl<'a> EntriesSelection<'a> {
+    pub(crate) fn new(query: &'a MempoolEntriesQuery) -> Self {
+        Self { query, selected: BinaryHeap::with_capacity(query.limit.saturating_add(1).min(1024)) }
+    }
+
+    fn matches(&self, transaction: &MempoolTransaction, feerate: Option<f64>) -> bool {
+        let query = self.query;
+        query.min_feerate.is_none_or(|min_feerate| feerate.unwrap_or_default() >= min_feerate)
+            && (query.script_public_keys.is_empty()
+                || transaction.mtx.tx.outputs.iter().any(|x| query.script_public_keys.contains(&x.script_public_key))
+                || transaction.mtx.entries.iter().flatten().any(|x| query.script_public_keys.contains(&x.script_public_key)))
+    }
+
+    /// Scans all the transactions of `pool`, retaining those belonging to the page
+    pub(crate) fn scan(&mut self, pool: &impl Pool, is_orphan: bool) {
+        for transaction in pool.all().values() {
+            // Orphans have no fee until their missing inputs get populated
+            let feerate = (!is_orphan).then(|| transaction.feerate());
+            if !self.matches(transaction, feerate) {
+                continue;
+            }
+            let cursor = MempoolEntriesCursor::from_entry(self.query.order, transaction.id(), transaction.added_at_daa_score, feerate);
+            if self.query.cursor.is_some_and(|first| cursor < first) {
+                continue;
+            }
+            if self.selected.len() <= self.query.limit {
+                self.selected.push((cursor, is_orphan));
+            } else if self.selected.peek().is_some_and(|(last, _)| cursor < *last) {
+                self.selected.pop();
+                self.selected.push((cursor, is_orphan));
+            }
+        }
+    }
+
+    /// Builds the page out of the retained entries, looking them up in the pools they were scanned from
+    pub(crate) fn into_page(self, transaction_pool: &impl Pool, orphan_pool: &impl Pool) -> MempoolEntriesPage {
+        let mut selected = self.selected.into_sorted_vec();
+        let next_cursor = if selected.len() > self.query.limit { selected.pop().map(|(cursor, _)| cursor) } else { None };
+        let entries = selected
+            .into_iter()
+            .filter_map(|(cursor, is_orphan)| {
+                let transaction_id = cursor.transaction_id();
+                let transaction = if is_orphan { orphan_pool.get(&transaction_id) } else { transaction_pool.get(&transaction_id) }?;
+                let masses = transaction.mtx.calculated_non_contextual_masses;
+                Some(MempoolEntry {
+                    transaction_id,
+                    mass: if is_orphan { masses.map_or(0, |x| x.max()) } else { transaction.mass() },
+                    fee: transaction.mtx.calculated_fee,
+                    is_orphan,
+                    transaction: (!self.query.compact).then(|| transaction.mtx.clone()),
+                })
+            })
+            .collect();
+        MempoolEntriesPage { entries, next_cursor }
+    }
+}
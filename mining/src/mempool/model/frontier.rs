@@ -264,6 +264,11 @@ impl Frontier {
         estimator
     }
 
+    /// Returns an iterator to the transactions in the frontier as block template candidates, in increasing feerate order
+    pub fn candidate_transactions(&self) -> impl Iterator<Item = CandidateTransaction> + '_ {
+        self.search_tree.ascending_iter().cloned().map(CandidateTransaction::from_key)
+    }
+
     /// Returns an iterator to the transactions in the frontier in increasing feerate order
     pub fn ascending_iter(&self) -> impl DoubleEndedIterator<Item = &Arc<Transaction>> + ExactSizeIterator + FusedIterator {
         self.search_tree.ascending_iter().map(|key| &key.tx)
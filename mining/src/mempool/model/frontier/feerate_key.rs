@@ -1,11 +1,12 @@
 use crate::{block_template::selector::ALPHA, mempool::model::tx::MempoolTransaction};
-use vecno_consensus_core::{mass::ContextualMasses, tx::Transaction};
+use vecno_consensus_core::tx::Transaction;
 use std::sync::Arc;
 
 #[derive(Clone, Debug)]
 pub struct FeerateTransactionKey {
     pub fee: u64,
     pub mass: u64,
+    feerate: f64,
     weight: f64,
     pub tx: Arc<Transaction>,
 }
@@ -20,14 +21,26 @@ impl PartialEq for FeerateTransactionKey {
 
 impl FeerateTransactionKey {
     pub fn new(fee: u64, mass: u64, tx: Arc<Transaction>) -> Self {
+        Self::with_feerate(fee, mass, fee as f64 / mass as f64, tx)
+    }
+
+    /// Builds a key ordered by the feerate of the package the transaction is mined with when higher than its own.
+    /// The fee and mass remain the ones of the transaction alone.
+    pub fn with_package(fee: u64, mass: u64, package_fee: u64, package_mass: u64, tx: Arc<Transaction>) -> Self {
+        let feerate = (fee as f64 / mass as f64).max(package_fee as f64 / package_mass as f64);
+        Self::with_feerate(fee, mass, feerate, tx)
+    }
+
+    fn with_feerate(fee: u64, mass: u64, feerate: f64, tx: Arc<Transaction>) -> Self {
         // NOTE: any change to the way this weight is calculated (such as scaling by some factor)
         // requires a reversed update to total_weight in `Frontier::build_feerate_estimator`. This
         // is because the math methods in FeeEstimator assume this specific weight function.
-        Self { fee, mass, weight: (fee as f64 / mass as f64).powi(ALPHA), tx }
+        Self { fee, mass, feerate, weight: feerate.powi(ALPHA), tx }
     }
 
+    /// The feerate of the transaction, raised to the one of its package when paid for by descendants
     pub fn feerate(&self) -> f64 {
-        self.fee as f64 / self.mass as f64
+        self.feerate
     }
 
     pub fn weight(&self) -> f64 {
@@ -77,14 +90,15 @@ impl Ord for FeerateTransactionKey {
 
 impl From<&MempoolTransaction> for FeerateTransactionKey {
     fn from(tx: &MempoolTransaction) -> Self {
-        // NOTE: The code below is a mempool simplification reducing the various block mass units to a
+        // NOTE: The mass is a mempool simplification reducing the various block mass units to a
         //       single one-dimension value (making it easier to select transactions for block templates).
         // Future mempool improvements are expected to refine this behavior and use the multi-dimension values
         // in order to optimize and increase block space usage.
-        let mass = ContextualMasses::new(tx.mtx.tx.mass())
-            .max(tx.mtx.calculated_non_contextual_masses.expect("masses are expected to be calculated"));
-        let fee = tx.mtx.calculated_fee.expect("fee is expected to be populated");
-        Self::new(fee, mass, tx.mtx.tx.clone())
+        let (fee, mass) = (tx.fee(), tx.mass());
+        match tx.package {
+            Some(package) => Self::with_package(fee, mass, package.fee, package.mass, tx.mtx.tx.clone()),
+            None => Self::new(fee, mass, tx.mtx.tx.clone()),
+        }
     }
 }
 
@@ -1,8 +1,11 @@
 pub(crate) mod accepted_transactions;
+pub(crate) mod entries_selection;
 pub(crate) mod frontier;
 pub(crate) mod map;
 pub(crate) mod orphan_pool;
+pub(crate) mod package;
 pub(crate) mod pool;
+pub(crate) mod status_tracker;
 pub(crate) mod transactions_pool;
 pub(crate) mod tx;
 pub(crate) mod utxo_set;
@@ -1,12 +1,16 @@
-use crate::mempool::{
-    config::Config,
-    errors::{RuleError, RuleResult},
-    model::{
-        map::{MempoolTransactionCollection, OutpointIndex},
-        pool::{Pool, TransactionsEdges},
-        tx::{MempoolTransaction, TxRemovalReason},
+use crate::{
+    mempool::{
+        config::Config,
+        errors::{RuleError, RuleResult},
+        model::{
+            map::{MempoolTransactionCollection, OutpointIndex},
+            pool::{Pool, TransactionsEdges},
+            status_tracker::TransactionStatusTracker,
+            tx::{MempoolTransaction, TxRemovalReason},
+        },
+        tx::Priority,
     },
-    tx::Priority,
+    model::tx_status::TransactionStatus,
 };
 use vecno_consensus_core::{
     tx::MutableTransaction,
@@ -38,16 +42,18 @@ pub(crate) struct OrphanPool {
     chained_orphans: TransactionsEdges,
     outpoint_owner_id: OutpointIndex,
     last_expire_scan: u64,
+    transaction_statuses: Arc<TransactionStatusTracker>,
 }
 
 impl OrphanPool {
-    pub(crate) fn new(config: Arc<Config>) -> Self {
+    pub(crate) fn new(config: Arc<Config>, transaction_statuses: Arc<TransactionStatusTracker>) -> Self {
         Self {
             config,
             all_orphans: MempoolTransactionCollection::default(),
             chained_orphans: TransactionsEdges::default(),
             outpoint_owner_id: OutpointIndex::default(),
             last_expire_scan: 0,
+            transaction_statuses,
         }
     }
 
@@ -150,10 +156,21 @@ impl OrphanPool {
         }
 
         self.all_orphans.insert(id, transaction);
+        self.transaction_statuses.update(id, TransactionStatus::Orphan);
         debug!("Added transaction to orphan pool: {}", id);
         Ok(())
     }
 
+    /// Moves the DAA score at which an orphan was added back to `added_at_daa_score` if it is earlier
+    pub(crate) fn restore_added_at_daa_score(&mut self, transaction_id: &TransactionId, added_at_daa_score: u64) -> bool {
+        if let Some(tx) = self.all_orphans.get_mut(transaction_id) {
+            tx.added_at_daa_score = tx.added_at_daa_score.min(added_at_daa_score);
+            true
+        } else {
+            false
+        }
+    }
+
     pub(crate) fn remove_orphan(
         &mut self,
         transaction_id: &TransactionId,
@@ -176,6 +193,9 @@ impl OrphanPool {
         }
         let removed_transactions =
             transaction_ids_to_remove.iter().map(|x| self.remove_single_orphan(x)).collect::<RuleResult<Vec<_>>>()?;
+        if reason.evicts() {
+            self.transaction_statuses.update_evicted(&transaction_ids_to_remove, &reason, extra_info);
+        }
         if reason.verbose() {
             match removed_transactions.len() {
                 0 => (), // This is not possible
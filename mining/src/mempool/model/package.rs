@@ -0,0 +1,31 @@
+use crate::mempool::model::tx::MempoolTransaction;
+use vecno_consensus_core::{mass::ContextualMasses, tx::MutableTransaction};
+
+/// Aggregated fee and mass of a set of related transactions which must be mined together.
+///
+/// A transaction can only enter a block once all its mempool ancestors did, so the feerate a child actually
+/// offers to a miner is the one of its ancestor package. Such a package paying more than the ancestor alone
+/// lets the child pay for its parent (CPFP).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct Package {
+    pub fee: u64,
+    pub mass: u64,
+}
+
+impl Package {
+    pub(crate) fn add(&mut self, transaction: &MempoolTransaction) {
+        self.fee += transaction.fee();
+        self.mass += transaction.mass();
+    }
+
+    /// Adds a validated transaction which is not in the mempool yet
+    pub(crate) fn add_validated(&mut self, transaction: &MutableTransaction) {
+        self.fee += transaction.calculated_fee.expect("fee is expected to be populated");
+        self.mass += ContextualMasses::new(transaction.tx.mass())
+            .max(transaction.calculated_non_contextual_masses.expect("masses are expected to be calculated"));
+    }
+
+    pub(crate) fn feerate(&self) -> f64 {
+        self.fee as f64 / self.mass as f64
+    }
+}
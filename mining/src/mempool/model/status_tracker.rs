@@ -0,0 +1,133 @@
+use crate::{
+    mempool::model::tx::TxRemovalReason,
+    model::tx_status::{TransactionStatus, TransactionStatusChange},
+};
+use vecno_consensus_core::tx::TransactionId;
+use vecno_utils::channel::Channel;
+use parking_lot::Mutex;
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// Default number of recent transaction outcomes kept by the [`TransactionStatusTracker`]
+pub(crate) const DEFAULT_MAXIMUM_TRANSACTION_OUTCOME_COUNT: usize = 100_000;
+
+/// Bounded map of the most recent outcomes, evicting the oldest first
+struct Outcomes {
+    statuses: HashMap<TransactionId, (TransactionStatus, u64)>,
+    /// Insertion order of the outcomes, along with their sequence number so that replaced ones can be told apart
+    order: VecDeque<(TransactionId, u64)>,
+    sequence: u64,
+    capacity: usize,
+}
+
+impl Outcomes {
+    fn new(capacity: usize) -> Self {
+        Self { statuses: HashMap::new(), order: VecDeque::new(), sequence: 0, capacity }
+    }
+
+    fn insert(&mut self, transaction_id: TransactionId, status: TransactionStatus) {
+        self.sequence += 1;
+        self.statuses.insert(transaction_id, (status, self.sequence));
+        self.order.push_back((transaction_id, self.sequence));
+        while self.order.len() > self.capacity {
+            let (transaction_id, sequence) = self.order.pop_front().unwrap();
+            if self.statuses.get(&transaction_id).is_some_and(|(_, x)| *x == sequence) {
+                self.statuses.remove(&transaction_id);
+            }
+        }
+    }
+}
+
+/// Tracker of the status of the transactions going through the mempool.
+///
+/// Keeps the outcome of the transactions recently removed from the pools, so it can be queried after the fact,
+/// and forwards every status change to a registered listener while the notifications are active.
+pub(crate) struct TransactionStatusTracker {
+    outcomes: Mutex<Outcomes>,
+    listener: Mutex<Option<Channel<TransactionStatusChange>>>,
+    active: AtomicBool,
+}
+
+impl TransactionStatusTracker {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self { outcomes: Mutex::new(Outcomes::new(capacity)), listener: Mutex::new(None), active: AtomicBool::new(false) }
+    }
+
+    /// Records a new status of a transaction, only outcomes being kept
+    pub(crate) fn update(&self, transaction_id: TransactionId, status: TransactionStatus) {
+        if self.active.load(Ordering::Relaxed) {
+            if let Some(listener) = self.listener.lock().as_ref() {
+                let _ = listener.try_send(TransactionStatusChange::new(transaction_id, status.clone()));
+            }
+        }
+        if status.is_outcome() {
+            self.outcomes.lock().insert(transaction_id, status);
+        }
+    }
+
+    pub(crate) fn update_evicted(&self, transaction_ids: &[TransactionId], reason: &TxRemovalReason, extra_info: &str) {
+        let reason = match extra_info.trim() {
+            "" => reason.to_string(),
+            extra_info => format!("{} {}", reason, extra_info),
+        };
+        transaction_ids.iter().for_each(|x| self.update(*x, TransactionStatus::Evicted { reason: reason.clone() }));
+    }
+
+    /// Returns the most recent outcome of a transaction, if still known
+    pub(crate) fn get(&self, transaction_id: &TransactionId) -> Option<TransactionStatus> {
+        self.outcomes.lock().statuses.get(transaction_id).map(|(status, _)| status.clone())
+    }
+
+    /// Registers the listener of the status changes, replacing any previous one
+    pub(crate) fn register_listener(&self) -> Channel<TransactionStatusChange> {
+        let channel = Channel::default();
+        *self.listener.lock() = Some(channel.clone());
+        channel
+    }
+
+    /// Starts or stops sending the status changes to the listener
+    pub(crate) fn set_active(&self, active: bool) {
+        self.active.store(active, Ordering::Relaxed);
+    }
+}
+
+impl Default for TransactionStatusTracker {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAXIMUM_TRANSACTION_OUTCOME_COUNT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vecno_hashes::Hash;
+
+    #[test]
+    fn test_transaction_status_tracker() {
+        let tracker = TransactionStatusTracker::new(2);
+        let ids = (1..=3).map(Hash::from_u64_word).collect::<Vec<_>>();
+        let receiver = tracker.register_listener();
+
+        // Inactive notifications are not sent
+        tracker.update(ids[0], TransactionStatus::InMempool);
+        assert!(receiver.try_recv().is_err());
+        assert_eq!(tracker.get(&ids[0]), None, "a transaction still in the mempool has no outcome");
+
+        tracker.set_active(true);
+        tracker.update(ids[0], TransactionStatus::Included { block_daa_score: 10 });
+        tracker.update_evicted(&ids[1..2], &TxRemovalReason::DoubleSpend, " favouring x");
+        assert_eq!(
+            receiver.try_recv().unwrap(),
+            TransactionStatusChange::new(ids[0], TransactionStatus::Included { block_daa_score: 10 })
+        );
+        assert_eq!(tracker.get(&ids[1]), Some(TransactionStatus::Evicted { reason: "double spend favouring x".to_string() }));
+
+        // Updating an outcome renews it, so the oldest one gets dropped first
+        tracker.update(ids[0], TransactionStatus::Rejected { reason: "bad".to_string() });
+        tracker.update(ids[2], TransactionStatus::Rejected { reason: "bad".to_string() });
+        assert_eq!(tracker.get(&ids[1]), None);
+        assert!(tracker.get(&ids[0]).is_some() && tracker.get(&ids[2]).is_some());
+    }
+}
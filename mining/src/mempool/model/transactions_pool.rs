@@ -5,13 +5,15 @@ use crate::{
         errors::{RuleError, RuleResult},
         model::{
             map::MempoolTransactionCollection,
+            package::Package,
             pool::{Pool, TransactionsEdges},
             tx::{DoubleSpend, MempoolTransaction},
             utxo_set::MempoolUtxoSet,
         },
+        policy::EvictionPriority,
         tx::Priority,
     },
-    model::{topological_index::TopologicalIndex, TransactionIdSet},
+    model::{candidate_tx::CandidateTransaction, topological_index::TopologicalIndex, TransactionIdSet},
     Policy,
 };
 use vecno_consensus_core::{
@@ -22,6 +24,7 @@ use vecno_core::{debug, time::unix_now, trace};
 use std::{
     collections::{hash_map::Keys, hash_set::Iter},
     iter::once,
+    slice::from_ref,
     sync::Arc,
 };
 
@@ -67,6 +70,9 @@ pub(crate) struct TransactionsPool {
     /// Transactions with no parents in the mempool -- ready to be inserted into a block template
     ready_transactions: Frontier,
 
+    /// Transactions the mempool policy requires to be evicted ahead of the others
+    evict_first_transactions: TransactionIdSet,
+
     last_expire_scan_daa_score: u64,
 
     /// last expire scan time in milliseconds
@@ -87,6 +93,7 @@ impl TransactionsPool {
             parent_transactions: TransactionsEdges::default(),
             chained_transactions: TransactionsEdges::default(),
             ready_transactions: Default::default(),
+            evict_first_transactions: Default::default(),
             last_expire_scan_daa_score: 0,
             last_expire_scan_time: unix_now(),
             utxo_set: MempoolUtxoSet::new(),
@@ -129,15 +136,24 @@ impl TransactionsPool {
             entry.insert(id);
         }
 
+        if self.config.policy.eviction_priority(&transaction.mtx) == EvictionPriority::First {
+            self.evict_first_transactions.insert(id);
+        }
         self.utxo_set.add_transaction(&transaction.mtx);
         self.estimated_size += transaction_size;
         self.all_transactions.insert(id, transaction);
+        // The transaction may pay for some of its ancestors
+        self.update_ready_ancestor_packages(&id);
         trace!("Added transaction {}", id);
         Ok(())
     }
 
     /// Fully removes the transaction from all relational sets, as well as from the UTXO set
     pub(crate) fn remove_transaction(&mut self, transaction_id: &TransactionId) -> RuleResult<MempoolTransaction> {
+        // The ready ancestors of the transaction may lose the package it was paying for them
+        let ready_ancestors = self.get_ready_ancestor_ids(transaction_id);
+        let mut newly_ready = vec![];
+
         // Remove all bijective parent/chained relations
         if let Some(parents) = self.parent_transactions.get(transaction_id) {
             for parent in parents.iter() {
@@ -151,8 +167,7 @@ impl TransactionsPool {
                 if let Some(parents) = self.parent_transactions.get_mut(chain) {
                     parents.remove(transaction_id);
                     if parents.is_empty() {
-                        let tx = self.all_transactions.get(chain).unwrap();
-                        self.ready_transactions.insert(tx.into());
+                        newly_ready.push(*chain);
                     }
                 }
             }
@@ -164,6 +179,7 @@ impl TransactionsPool {
         let removed_tx = self.all_transactions.remove(transaction_id).ok_or(RuleError::RejectMissingTransaction(*transaction_id))?;
 
         self.ready_transactions.remove(&(&removed_tx).into());
+        self.evict_first_transactions.remove(transaction_id);
 
         // TODO: consider using `self.parent_transactions.get(transaction_id)`
         // The tradeoff to consider is whether it might be possible that a parent tx exists in the pool
@@ -179,6 +195,14 @@ impl TransactionsPool {
             assert_eq!(0, self.estimated_size, "Sanity test -- if tx pool is empty, estimated byte size should be zero");
         }
 
+        for id in newly_ready {
+            let package = self.get_best_descendant_package(&id);
+            let tx = self.all_transactions.get_mut(&id).unwrap();
+            tx.package = package;
+            self.ready_transactions.insert((&*tx).into());
+        }
+        ready_ancestors.iter().for_each(|id| self.update_package(id));
+
         Ok(removed_tx)
     }
 
@@ -194,6 +218,16 @@ impl TransactionsPool {
         }
     }
 
+    /// Moves the DAA score at which a transaction was added back to `added_at_daa_score` if it is earlier
+    pub(crate) fn restore_added_at_daa_score(&mut self, transaction_id: &TransactionId, added_at_daa_score: u64) -> bool {
+        if let Some(tx) = self.all_transactions.get_mut(transaction_id) {
+            tx.added_at_daa_score = tx.added_at_daa_score.min(added_at_daa_score);
+            true
+        } else {
+            false
+        }
+    }
+
     pub(crate) fn ready_transaction_count(&self) -> usize {
         self.ready_transactions.len()
     }
@@ -207,58 +241,200 @@ impl TransactionsPool {
         self.ready_transactions.build_selector(&Policy::new(self.config.maximum_mass_per_block))
     }
 
+    /// Returns all the ready transactions as block template candidates
+    pub(crate) fn ready_candidate_transactions(&self) -> Vec<CandidateTransaction> {
+        self.ready_transactions.candidate_transactions().collect()
+    }
+
+    /// Returns the transaction as a block template candidate if it is in the pool and has no parent in the pool
+    pub(crate) fn get_ready_candidate_transaction(&self, transaction_id: &TransactionId) -> Option<CandidateTransaction> {
+        let transaction = self.all_transactions.get(transaction_id)?;
+        let is_ready = self.parent_transactions.get(transaction_id).map_or(true, |parents| parents.is_empty());
+        is_ready.then(|| CandidateTransaction::from_key(transaction.into()))
+    }
+
+    /// Returns the aggregated fee and mass of a transaction and all its ancestors in the pool, or `None` if they
+    /// exceed the maximum package transaction count
+    pub(crate) fn get_ancestor_package(&self, transaction_id: &TransactionId) -> Option<Package> {
+        let mut package = Package::default();
+        let mut visited = TransactionIdSet::new();
+        let mut stack = vec![*transaction_id];
+        while let Some(id) = stack.pop() {
+            if !visited.insert(id) {
+                continue;
+            }
+            if visited.len() > self.config.maximum_package_transaction_count {
+                return None;
+            }
+            package.add(self.all_transactions.get(&id)?);
+            if let Some(parents) = self.parent_transactions.get(&id) {
+                stack.extend(parents.iter().copied());
+            }
+        }
+        Some(package)
+    }
+
+    /// Returns the best paying ancestor package amongst the descendants of a transaction, if it pays a higher
+    /// feerate than the transaction alone
+    pub(crate) fn get_best_descendant_package(&self, transaction_id: &TransactionId) -> Option<Package> {
+        let feerate = self.all_transactions.get(transaction_id)?.feerate();
+        // Ties are resolved by the traversal order so the result does not depend on the hash set iteration order
+        self.get_bounded_descendant_ids(transaction_id)
+            .iter()
+            .filter_map(|id| self.get_ancestor_package(id))
+            .filter(|package| package.feerate() > feerate)
+            .reduce(|best, package| if package.feerate() > best.feerate() { package } else { best })
+    }
+
+    /// Returns the descendants of a transaction breadth-first, each depth being sorted by descending feerate then
+    /// by id, and stops once the maximum package transaction count is reached since the descendants beyond it
+    /// cannot belong to a package including the transaction
+    fn get_bounded_descendant_ids(&self, transaction_id: &TransactionId) -> Vec<TransactionId> {
+        let limit = self.config.maximum_package_transaction_count;
+        let mut visited = TransactionIdSet::new();
+        let mut descendants = vec![];
+        let mut depth = vec![*transaction_id];
+        while !depth.is_empty() && descendants.len() < limit {
+            let mut next_depth = depth
+                .iter()
+                .flat_map(|id| self.chained_transactions.get(id).into_iter().flatten())
+                .filter(|id| visited.insert(**id))
+                .filter_map(|id| self.all_transactions.get(id))
+                .collect::<Vec<_>>();
+            next_depth.sort_by(|a, b| b.feerate().total_cmp(&a.feerate()).then_with(|| a.id().cmp(&b.id())));
+            next_depth.truncate(limit - descendants.len());
+            depth = next_depth.into_iter().map(|tx| tx.id()).collect();
+            descendants.extend(depth.iter().copied());
+        }
+        descendants
+    }
+
+    /// Returns the feerate a transaction competes with, accounting for its descendants paying for it
+    pub(crate) fn get_package_feerate(&self, transaction: &MempoolTransaction) -> f64 {
+        self.get_best_descendant_package(&transaction.id())
+            .map_or_else(|| transaction.feerate(), |package| package.feerate().max(transaction.feerate()))
+    }
+
+    /// Returns the ids of the ancestors of a transaction which have no parent in the pool.
+    ///
+    /// Nothing is returned if the transaction has too many ancestors to pay for them, the traversal stopping there.
+    fn get_ready_ancestor_ids(&self, transaction_id: &TransactionId) -> Vec<TransactionId> {
+        let mut ready_ancestors = vec![];
+        let mut visited = TransactionIdSet::new();
+        let mut stack: Vec<_> = self.parent_transactions.get(transaction_id).into_iter().flatten().copied().collect();
+        while let Some(id) = stack.pop() {
+            if !visited.insert(id) {
+                continue;
+            }
+            if visited.len() >= self.config.maximum_package_transaction_count {
+                return vec![];
+            }
+            match self.parent_transactions.get(&id) {
+                Some(parents) if !parents.is_empty() => stack.extend(parents.iter().copied()),
+                _ => ready_ancestors.push(id),
+            }
+        }
+        ready_ancestors
+    }
+
+    fn update_ready_ancestor_packages(&mut self, transaction_id: &TransactionId) {
+        self.get_ready_ancestor_ids(transaction_id).iter().for_each(|id| self.update_package(id));
+    }
+
+    /// Recomputes the package of a ready transaction, repositioning it in the frontier if needed
+    fn update_package(&mut self, transaction_id: &TransactionId) {
+        let package = self.get_best_descendant_package(transaction_id);
+        if let Some(tx) = self.all_transactions.get_mut(transaction_id) {
+            if tx.package != package {
+                self.ready_transactions.remove(&(&*tx).into());
+                tx.package = package;
+                self.ready_transactions.insert((&*tx).into());
+            }
+        }
+    }
+
     /// Builds a feerate estimator based on internal state of the ready transactions frontier
     pub(crate) fn build_feerate_estimator(&self, args: FeerateEstimatorArgs) -> FeerateEstimator {
         self.ready_transactions.build_feerate_estimator(args)
     }
 
-    /// Returns the exceeding low-priority transactions having the lowest fee rates in order
+    /// Returns the exceeding low-priority transactions having the lowest package fee rates in order
     /// to make room for `transaction`. The returned transactions
     /// are guaranteed to be unchained (no successor in mempool) and to not be parent of
     /// `transaction`.
     ///
+    /// Unless `transaction` is itself to be evicted first, the ready transactions the mempool policy
+    /// requires to be evicted first are selected ahead of the others, whatever their fee rates.
+    ///
     /// An error is returned if the mempool is filled with high priority transactions, or
     /// there are not enough lower feerate transactions that can be removed to accommodate `transaction`
     pub(crate) fn limit_transaction_count(
         &self,
         transaction: &MutableTransaction,
         transaction_size: usize,
+    ) -> RuleResult<Vec<TransactionId>> {
+        self.limit_package_transaction_count(from_ref(transaction), transaction_size, transaction.calculated_feerate().unwrap())
+    }
+
+    /// Returns the exceeding low-priority transactions to be removed in order to make room for all the
+    /// `transactions` of a package, of total size `transactions_size`, following the rules of
+    /// [`Self::limit_transaction_count`] with `feerate_threshold` being the feerate of the package.
+    pub(crate) fn limit_package_transaction_count(
+        &self,
+        transactions: &[MutableTransaction],
+        transactions_size: usize,
+        feerate_threshold: f64,
     ) -> RuleResult<Vec<TransactionId>> {
         // No eviction needed -- return
-        if self.len() < self.config.maximum_transaction_count
-            && self.estimated_size + transaction_size <= self.config.mempool_size_limit
+        if self.len() + transactions.len() <= self.config.maximum_transaction_count
+            && self.estimated_size + transactions_size <= self.config.mempool_size_limit
         {
             return Ok(Default::default());
         }
 
+        let mut evict_first = vec![];
+        if transactions.iter().all(|transaction| self.config.policy.eviction_priority(transaction) == EvictionPriority::Normal) {
+            evict_first = self
+                .evict_first_transactions
+                .iter()
+                .filter(|id| self.parent_transactions.get(*id).is_some_and(|parents| parents.is_empty()))
+                .map(|id| self.all_transactions.get(id).unwrap())
+                .filter(|mtx| mtx.priority == Priority::Low)
+                .collect::<Vec<_>>();
+            evict_first.sort_by(|a, b| a.package_feerate().total_cmp(&b.package_feerate()));
+        }
+        let skip_evict_first = !evict_first.is_empty();
+
         // Returns a vector of transactions to be removed (the caller has to actually remove)
-        let feerate_threshold = transaction.calculated_feerate().unwrap();
         let mut txs_to_remove = Vec::with_capacity(1); // Normally we expect a single removal
         let mut selection_overall_size = 0;
-        for tx in self
-            .ready_transactions
-            .ascending_iter()
-            .map(|tx| self.all_transactions.get(&tx.id()).unwrap())
-            .filter(|mtx| mtx.priority == Priority::Low)
-        {
+        for (tx, bypass_feerate) in evict_first.iter().map(|tx| (*tx, true)).chain(
+            self.ready_transactions
+                .ascending_iter()
+                .map(|tx| self.all_transactions.get(&tx.id()).unwrap())
+                .filter(|mtx| {
+                    mtx.priority == Priority::Low && !(skip_evict_first && self.evict_first_transactions.contains(&mtx.id()))
+                })
+                .map(|tx| (tx, false)),
+        ) {
             // TODO (optimization): inline the `has_parent_in_set` check within the redeemer traversal and exit early if possible
             let redeemers = self.get_redeemer_ids_in_pool(&tx.id()).into_iter().chain(once(tx.id())).collect::<TransactionIdSet>();
-            if transaction.has_parent_in_set(&redeemers) {
+            if transactions.iter().any(|transaction| transaction.has_parent_in_set(&redeemers)) {
                 continue;
             }
 
-            // We are iterating ready txs by ascending feerate so the pending tx has lower feerate than all remaining txs
-            if tx.feerate() > feerate_threshold {
+            // We are iterating ready txs by ascending package feerate so the pending tx has lower feerate than all remaining txs
+            if !bypass_feerate && tx.package_feerate() > feerate_threshold {
                 let err = RuleError::RejectMempoolIsFull;
-                debug!("Transaction {} with feerate {} has been rejected: {}", transaction.id(), feerate_threshold, err);
+                debug!("Transaction {} with feerate {} has been rejected: {}", transactions[0].id(), feerate_threshold, err);
                 return Err(err);
             }
 
             txs_to_remove.push(tx.id());
             selection_overall_size += tx.mtx.mempool_estimated_bytes();
 
-            if self.len() + 1 - txs_to_remove.len() <= self.config.maximum_transaction_count
-                && self.estimated_size + transaction_size - selection_overall_size <= self.config.mempool_size_limit
+            if self.len() + transactions.len() - txs_to_remove.len() <= self.config.maximum_transaction_count
+                && self.estimated_size + transactions_size - selection_overall_size <= self.config.mempool_size_limit
             {
                 return Ok(txs_to_remove);
             }
@@ -269,9 +445,9 @@ impl TransactionsPool {
             "Mempool is filled with high-priority/ancestor txs (count: {}, bytes: {}). Transaction {} with feerate {} and size {} has been rejected: {}",
             self.len(),
             self.estimated_size,
-            transaction.id(),
+            transactions[0].id(),
             feerate_threshold,
-            transaction_size,
+            transactions_size,
             RuleError::RejectMempoolIsFull
         );
         Err(RuleError::RejectMempoolIsFull)
@@ -1,5 +1,11 @@
-use crate::mempool::tx::{Priority, RbfPolicy};
-use vecno_consensus_core::tx::{MutableTransaction, Transaction, TransactionId, TransactionOutpoint};
+use crate::mempool::{
+    model::package::Package,
+    tx::{Priority, RbfPolicy},
+};
+use vecno_consensus_core::{
+    mass::ContextualMasses,
+    tx::{MutableTransaction, Transaction, TransactionId, TransactionOutpoint},
+};
 use vecno_mining_errors::mempool::RuleError;
 use std::{
     fmt::{Display, Formatter},
@@ -10,21 +16,40 @@ pub(crate) struct MempoolTransaction {
     pub(crate) mtx: MutableTransaction,
     pub(crate) priority: Priority,
     pub(crate) added_at_daa_score: u64,
+    /// The best paying ancestor package of the transaction descendants, if paying a higher feerate than the
+    /// transaction alone. Only maintained for transactions ready to enter a block template.
+    pub(crate) package: Option<Package>,
 }
 
 impl MempoolTransaction {
     pub(crate) fn new(mtx: MutableTransaction, priority: Priority, added_at_daa_score: u64) -> Self {
         assert_eq!(mtx.tx.inputs.len(), mtx.entries.len());
-        Self { mtx, priority, added_at_daa_score }
+        Self { mtx, priority, added_at_daa_score, package: None }
     }
 
     pub(crate) fn id(&self) -> TransactionId {
         self.mtx.tx.id()
     }
 
+    pub(crate) fn fee(&self) -> u64 {
+        self.mtx.calculated_fee.expect("fee is expected to be populated")
+    }
+
+    /// Returns the mass of the transaction, reducing the various block mass units to a single one-dimension value
+    pub(crate) fn mass(&self) -> u64 {
+        ContextualMasses::new(self.mtx.tx.mass())
+            .max(self.mtx.calculated_non_contextual_masses.expect("masses are expected to be calculated"))
+    }
+
     pub(crate) fn feerate(&self) -> f64 {
         self.mtx.calculated_feerate().unwrap()
     }
+
+    /// Returns the feerate the transaction competes with for block space, accounting for its descendants
+    /// paying for it
+    pub(crate) fn package_feerate(&self) -> f64 {
+        self.package.map_or_else(|| self.feerate(), |package| package.feerate().max(self.feerate()))
+    }
 }
 
 impl RbfPolicy {
@@ -82,7 +107,9 @@ pub(crate) enum TxRemovalReason {
     DoubleSpend,
     InvalidInBlockTemplate,
     RevalidationWithMissingOutpoints,
+    FailedRevalidation,
     ReplacedByFee,
+    PackageRejected,
 }
 
 impl TxRemovalReason {
@@ -96,13 +123,22 @@ impl TxRemovalReason {
             TxRemovalReason::DoubleSpend => "double spend",
             TxRemovalReason::InvalidInBlockTemplate => "invalid in block template",
             TxRemovalReason::RevalidationWithMissingOutpoints => "revalidation with missing outpoints",
+            TxRemovalReason::FailedRevalidation => "failed revalidation",
             TxRemovalReason::ReplacedByFee => "replaced by fee",
+            TxRemovalReason::PackageRejected => "package rejected",
         }
     }
 
     pub(crate) fn verbose(&self) -> bool {
         !matches!(self, TxRemovalReason::Muted)
     }
+
+    /// Returns true if the removed transactions leave the mempool without being included in a block.
+    ///
+    /// Muted removals are excluded, their callers being in charge of recording the actual reason.
+    pub(crate) fn evicts(&self) -> bool {
+        !matches!(self, TxRemovalReason::Muted | TxRemovalReason::Accepted | TxRemovalReason::Unorphaned)
+    }
 }
 
 impl Display for TxRemovalReason {
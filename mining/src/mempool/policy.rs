@@ -0,0 +1,255 @@
+//! Operator defined mempool admission and eviction rules.
+//!
+//! A [`MempoolPolicy`] complements the built-in standardness rules of the mempool. The default implementation,
+//! [`DefaultMempoolPolicy`], is configured from the `[mempool-policy]` section of the node config file, e.g.:
+//!
+//! ```toml
+//! [mempool-policy]
+//! max-payload-size = 1024
+//!
+//! [mempool-policy.script-classes.scripthash]
+//! max-transaction-size = 10000
+//! max-transactions-per-minute = 600
+//! evict-first = true
+//! ```
+
+use crate::mempool::{
+    errors::{NonStandardError, NonStandardResult},
+    tx::Priority,
+};
+use parking_lot::Mutex;
+use serde::Deserialize;
+use vecno_consensus_core::{mass::transaction_estimated_serialized_size, tx::MutableTransaction};
+use vecno_txscript::script_class::{Error as ScriptClassError, ScriptClass};
+use std::{collections::HashMap, fmt::Debug, str::FromStr, time::Instant};
+
+/// Order in which the low priority transactions are evicted when the mempool is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EvictionPriority {
+    /// Evicted ahead of the normal transactions, whatever their feerate
+    First,
+    /// Evicted by ascending feerate
+    Normal,
+}
+
+/// Rules applied by the mempool on top of the standardness rules.
+///
+/// The rules are enforced whether or not the mempool accepts non-standard transactions.
+pub trait MempoolPolicy: Send + Sync + Debug {
+    /// Checks a transaction prior to its validation by consensus
+    fn check_in_isolation(&self, transaction: &MutableTransaction) -> NonStandardResult<()>;
+
+    /// Checks that a fully validated transaction may be inserted into the mempool, without consuming any allowance
+    fn admit(&self, transaction: &MutableTransaction, priority: Priority) -> NonStandardResult<()>;
+
+    /// Records the insertion into the mempool of a transaction previously admitted, consuming its allowance
+    fn record_admission(&self, transaction: &MutableTransaction, priority: Priority);
+
+    /// Returns the eviction priority of a transaction held in the mempool
+    fn eviction_priority(&self, transaction: &MutableTransaction) -> EvictionPriority;
+}
+
+/// Limits applied to the transactions having at least one output of a given script class
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct ScriptClassLimits {
+    /// Maximum estimated serialized size of a transaction, in bytes
+    pub max_transaction_size: Option<u64>,
+    /// Maximum number of transactions received through P2P admitted per minute
+    pub max_transactions_per_minute: Option<u32>,
+    /// Evict the transactions ahead of the others when the mempool is full
+    pub evict_first: bool,
+}
+
+/// Configuration of the [`DefaultMempoolPolicy`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct MempoolPolicyConfig {
+    /// Maximum size of a transaction payload, in bytes
+    pub max_payload_size: Option<usize>,
+    /// Limits keyed by script class name: `pubkey`, `pubkeyecdsa`, `scripthash` or `nonstandard`
+    pub script_classes: HashMap<String, ScriptClassLimits>,
+}
+
+/// Token bucket refilled continuously up to one minute worth of transactions
+#[derive(Debug)]
+struct RateLimiter {
+    capacity: f64,
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl RateLimiter {
+    fn new(transactions_per_minute: u32) -> Self {
+        let capacity = transactions_per_minute as f64;
+        Self { capacity, tokens: capacity, refilled_at: Instant::now() }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.refilled_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.capacity / 60.0).min(self.capacity);
+        self.refilled_at = now;
+    }
+}
+
+#[derive(Debug)]
+struct ScriptClassRule {
+    class: ScriptClass,
+    limits: ScriptClassLimits,
+    rate_limiter: Option<Mutex<RateLimiter>>,
+}
+
+/// Policy enforcing a payload size cap and per script class limits, imposing no restriction when left unconfigured
+#[derive(Debug, Default)]
+pub struct DefaultMempoolPolicy {
+    max_payload_size: Option<usize>,
+    rules: Vec<ScriptClassRule>,
+}
+
+impl DefaultMempoolPolicy {
+    pub fn new(config: MempoolPolicyConfig) -> Result<Self, ScriptClassError> {
+        let mut rules = Vec::with_capacity(config.script_classes.len());
+        for (class, limits) in config.script_classes {
+            let class = ScriptClass::from_str(&class)?;
+            let rate_limiter = limits.max_transactions_per_minute.map(|rate| Mutex::new(RateLimiter::new(rate)));
+            rules.push(ScriptClassRule { class, limits, rate_limiter });
+        }
+        Ok(Self { max_payload_size: config.max_payload_size, rules })
+    }
+
+    /// Returns the rules of the script classes the transaction pays to
+    fn matching_rules<'a>(&'a self, transaction: &'a MutableTransaction) -> impl Iterator<Item = &'a ScriptClassRule> + 'a {
+        self.rules.iter().filter(move |rule| {
+            transaction.tx.outputs.iter().any(|output| ScriptClass::from_script(&output.script_public_key) == rule.class)
+        })
+    }
+}
+
+impl MempoolPolicy for DefaultMempoolPolicy {
+    fn check_in_isolation(&self, transaction: &MutableTransaction) -> NonStandardResult<()> {
+        let transaction_id = transaction.id();
+        if let Some(max_payload_size) = self.max_payload_size {
+            if transaction.tx.payload.len() > max_payload_size {
+                return Err(NonStandardError::RejectPayloadSize(transaction_id, transaction.tx.payload.len(), max_payload_size));
+            }
+        }
+        for rule in self.matching_rules(transaction) {
+            if let Some(max_transaction_size) = rule.limits.max_transaction_size {
+                let size = transaction_estimated_serialized_size(&transaction.tx);
+                if size > max_transaction_size {
+                    return Err(NonStandardError::RejectScriptClassSize(
+                        transaction_id,
+                        rule.class.to_string(),
+                        size,
+                        max_transaction_size,
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn admit(&self, transaction: &MutableTransaction, priority: Priority) -> NonStandardResult<()> {
+        // Transactions submitted through RPC are owned by the node and never rate limited
+        if priority == Priority::High {
+            return Ok(());
+        }
+
+        // A transaction paying to several limited classes needs a token of each of them
+        let now = Instant::now();
+        for rule in self.matching_rules(transaction) {
+            if let Some(rate_limiter) = rule.rate_limiter.as_ref() {
+                let mut rate_limiter = rate_limiter.lock();
+                rate_limiter.refill(now);
+                if rate_limiter.tokens < 1.0 {
+                    return Err(NonStandardError::RejectScriptClassRate(
+                        transaction.id(),
+                        rule.class.to_string(),
+                        rate_limiter.capacity as u32,
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn record_admission(&self, transaction: &MutableTransaction, priority: Priority) {
+        if priority == Priority::High {
+            return;
+        }
+
+        let now = Instant::now();
+        for rate_limiter in self.matching_rules(transaction).filter_map(|rule| rule.rate_limiter.as_ref()) {
+            let mut rate_limiter = rate_limiter.lock();
+            rate_limiter.refill(now);
+            rate_limiter.tokens = (rate_limiter.tokens - 1.0).max(0.0);
+        }
+    }
+
+    fn eviction_priority(&self, transaction: &MutableTransaction) -> EvictionPriority {
+        match self.matching_rules(transaction).any(|rule| rule.limits.evict_first) {
+            true => EvictionPriority::First,
+            false => EvictionPriority::Normal,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vecno_consensus_core::{
+        subnets::SUBNETWORK_ID_NATIVE,
+        tx::{ScriptPublicKey, ScriptVec, Transaction, TransactionOutput},
+    };
+    use vecno_txscript::{opcodes::codes::OpCheckSig, pay_to_script_hash_script};
+
+    fn transaction(script_public_key: ScriptPublicKey, payload_size: usize) -> MutableTransaction {
+        let output = TransactionOutput::new(100_000_000, script_public_key);
+        MutableTransaction::from_tx(Transaction::new(0, vec![], vec![output], 0, SUBNETWORK_ID_NATIVE, 0, vec![0; payload_size]))
+    }
+
+    fn pay_to_pubkey() -> ScriptPublicKey {
+        let mut script = vec![32];
+        script.extend_from_slice(&[1; 32]);
+        script.push(OpCheckSig);
+        ScriptPublicKey::new(0, ScriptVec::from_vec(script))
+    }
+
+    #[test]
+    fn test_default_mempool_policy() {
+        let limits = ScriptClassLimits { max_transaction_size: Some(200), max_transactions_per_minute: Some(2), evict_first: true };
+        let config =
+            MempoolPolicyConfig { max_payload_size: Some(100), script_classes: HashMap::from([("scripthash".to_owned(), limits)]) };
+        let policy = DefaultMempoolPolicy::new(config).unwrap();
+
+        let p2pk = transaction(pay_to_pubkey(), 100);
+        assert_eq!(ScriptClass::from_script(&p2pk.tx.outputs[0].script_public_key), ScriptClass::PubKey);
+        assert!(policy.check_in_isolation(&p2pk).is_ok());
+        assert!(matches!(policy.check_in_isolation(&transaction(pay_to_pubkey(), 101)), Err(NonStandardError::RejectPayloadSize(..))));
+        assert_eq!(policy.eviction_priority(&p2pk), EvictionPriority::Normal);
+
+        let p2sh = transaction(pay_to_script_hash_script(&[1; 32]), 0);
+        assert!(policy.check_in_isolation(&p2sh).is_ok());
+        assert!(matches!(
+            policy.check_in_isolation(&transaction(pay_to_script_hash_script(&[1; 32]), 99)),
+            Err(NonStandardError::RejectScriptClassSize(..))
+        ));
+        assert_eq!(policy.eviction_priority(&p2sh), EvictionPriority::First);
+
+        // Only the low priority transactions of the limited class are rate limited, and only once actually inserted
+        for _ in 0..2 {
+            assert!(policy.admit(&p2sh, Priority::Low).is_ok());
+            assert!(policy.admit(&p2sh, Priority::Low).is_ok());
+            policy.record_admission(&p2sh, Priority::Low);
+        }
+        assert!(matches!(policy.admit(&p2sh, Priority::Low), Err(NonStandardError::RejectScriptClassRate(..))));
+        assert!(policy.admit(&p2sh, Priority::High).is_ok());
+        assert!(policy.admit(&p2pk, Priority::Low).is_ok());
+
+        let config = MempoolPolicyConfig {
+            script_classes: HashMap::from([("multisig".to_owned(), ScriptClassLimits::default())]),
+            ..Default::default()
+        };
+        assert!(DefaultMempoolPolicy::new(config).is_err());
+    }
+}
@@ -8,19 +8,24 @@ use vecno_core::debug;
 use vecno_utils::iter::IterExtensions;
 
 impl Mempool {
+    /// Removes a transaction from the mempool, along with its redeemers if `remove_redeemers` is true,
+    /// and returns the ids of all the removed transactions.
     pub(crate) fn remove_transaction(
         &mut self,
         transaction_id: &TransactionId,
         remove_redeemers: bool,
         reason: TxRemovalReason,
         extra_info: &str,
-    ) -> RuleResult<()> {
+    ) -> RuleResult<Vec<TransactionId>> {
         if self.orphan_pool.has(transaction_id) {
-            return self.orphan_pool.remove_orphan(transaction_id, true, reason, extra_info).map(|_| ());
+            return self
+                .orphan_pool
+                .remove_orphan(transaction_id, true, reason, extra_info)
+                .map(|removed| removed.iter().map(|x| x.id()).collect());
         }
 
         if !self.transaction_pool.has(transaction_id) {
-            return Ok(());
+            return Ok(vec![]);
         }
 
         let mut removed_transactions = vec![*transaction_id];
@@ -39,6 +44,10 @@ impl Mempool {
         }
         removed_transactions.extend(removed_orphans);
 
+        if reason.evicts() {
+            self.transaction_statuses.update_evicted(&removed_transactions, &reason, extra_info);
+        }
+
         match reason {
             TxRemovalReason::Muted => {}
             TxRemovalReason::DoubleSpend => match removed_transactions.len() {
@@ -65,6 +74,6 @@ impl Mempool {
             },
         }
 
-        Ok(())
+        Ok(removed_transactions)
     }
 }
@@ -120,7 +120,8 @@ impl Mempool {
     fn get_double_spend_feerate(&self, double_spend: &DoubleSpend) -> RuleResult<f64> {
         let owner = self.transaction_pool.get_double_spend_owner(double_spend)?;
         match owner.mtx.calculated_feerate() {
-            Some(double_spend_feerate) => Ok(double_spend_feerate),
+            // The replacement must outbid the owner along with the descendants paying for it
+            Some(_) => Ok(self.transaction_pool.get_package_feerate(owner)),
             // Getting here is unexpected since a mempool owned tx should be populated with fee
             // and mass at this stage but nonetheless we fail gracefully
             None => Err(double_spend.into()),
@@ -133,10 +134,8 @@ impl Mempool {
         double_spend: &DoubleSpend,
     ) -> RuleResult<&'a MempoolTransaction> {
         let owner = self.transaction_pool.get_double_spend_owner(double_spend)?;
-        if let (Some(transaction_feerate), Some(double_spend_feerate)) =
-            (transaction.calculated_feerate(), owner.mtx.calculated_feerate())
-        {
-            if transaction_feerate > double_spend_feerate {
+        if let (Some(transaction_feerate), Some(_)) = (transaction.calculated_feerate(), owner.mtx.calculated_feerate()) {
+            if transaction_feerate > self.transaction_pool.get_package_feerate(owner) {
                 return Ok(owner);
             } else {
                 return Err(double_spend.into());
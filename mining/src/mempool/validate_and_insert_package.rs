@@ -0,0 +1,211 @@
+use crate::{
+    mempool::{
+        errors::{RuleError, RuleResult},
+        model::{
+            package::Package,
+            pool::Pool,
+            tx::{TransactionPostValidation, TransactionPreValidation, TxRemovalReason},
+        },
+        tx::{Priority, RateLimit, RbfPolicy},
+        Mempool,
+    },
+    model::topological_sort::IntoIterTopologically,
+};
+use vecno_consensus_core::{
+    api::ConsensusApi,
+    constants::UNACCEPTED_DAA_SCORE,
+    tx::{MutableTransaction, Transaction, TransactionId, TransactionOutpoint, UtxoEntry},
+};
+use vecno_core::debug;
+use std::{
+    collections::{hash_map::Entry, HashMap, HashSet, VecDeque},
+    sync::Arc,
+};
+
+impl Mempool {
+    /// Validates the structure of a package, then pre-validates its transactions in topological order.
+    ///
+    /// The inputs spending outputs of other package transactions are populated so that the whole package
+    /// can be validated by consensus before any of its transactions enters the mempool.
+    pub(crate) fn pre_validate_and_populate_package(
+        &self,
+        consensus: &dyn ConsensusApi,
+        transactions: Vec<Transaction>,
+    ) -> RuleResult<Vec<MutableTransaction>> {
+        self.validate_package_structure(&transactions)?;
+
+        let mut outputs = HashMap::new();
+        let mut populated_transactions = Vec::with_capacity(transactions.len());
+        for transaction in transactions.into_iter().map(MutableTransaction::from_tx).topological_into_iter() {
+            let TransactionPreValidation { mut transaction, .. } =
+                self.pre_validate_and_populate_transaction(consensus, transaction, RbfPolicy::Forbidden)?;
+            for (i, input) in transaction.tx.inputs.iter().enumerate() {
+                if let Some(entry) = outputs.get(&input.previous_outpoint) {
+                    transaction.entries[i] = Some(entry.clone());
+                }
+            }
+            for (i, output) in transaction.tx.outputs.iter().enumerate() {
+                let entry = UtxoEntry::new(output.value, output.script_public_key.clone(), UNACCEPTED_DAA_SCORE, false);
+                outputs.insert(TransactionOutpoint::new(transaction.id(), i as u32), entry);
+            }
+            populated_transactions.push(transaction);
+        }
+        Ok(populated_transactions)
+    }
+
+    /// Inserts all the transactions of a consensus validated package into the mempool, or none of them.
+    ///
+    /// The package transactions are exempt from the individual minimum relay fee, the package paying
+    /// for the aggregated compute mass of its transactions instead.
+    ///
+    /// Every check, including the one of the room left in the mempool, is performed before the pool is
+    /// modified, so that a rejected package never evicts any transaction.
+    pub(crate) fn post_validate_and_insert_package(
+        &mut self,
+        consensus: &dyn ConsensusApi,
+        transactions: Vec<MutableTransaction>,
+        validation_results: Vec<RuleResult<()>>,
+        priority: Priority,
+    ) -> RuleResult<Vec<Arc<Transaction>>> {
+        for (transaction, validation_result) in transactions.iter().zip(validation_results) {
+            self.post_validate_package_transaction(validation_result, transaction, priority)?;
+        }
+
+        let (fee, compute_mass) = transactions.iter().fold((0u64, 0u64), |(fee, mass), transaction| {
+            (
+                fee.saturating_add(transaction.calculated_fee.unwrap_or_default()),
+                mass.saturating_add(transaction.calculated_non_contextual_masses.map_or(0, |masses| masses.compute_mass)),
+            )
+        });
+        if !self.config.accept_non_standard {
+            let minimum_fee = self.minimum_required_transaction_relay_fee(compute_mass);
+            if fee < minimum_fee {
+                return Err(RuleError::RejectPackageInsufficientFee(fee, minimum_fee));
+            }
+        }
+
+        // Make room for the whole package, competing with its feerate
+        let mut package = Package::default();
+        transactions.iter().for_each(|transaction| package.add_validated(transaction));
+        let transactions_size = transactions.iter().map(|transaction| transaction.mempool_estimated_bytes()).sum();
+        let txs_to_remove =
+            self.transaction_pool.limit_package_transaction_count(&transactions, transactions_size, package.feerate())?;
+        let extra_info = format!(" for package of {}", transactions[0].id());
+        self.make_room(&txs_to_remove, transactions.len(), transactions_size, extra_info.as_str())?;
+
+        let mut accepted_transactions = Vec::with_capacity(transactions.len());
+        for transaction in transactions {
+            match self.insert_package_transaction(consensus, transaction, priority) {
+                Ok(accepted_transaction) => accepted_transactions.push(accepted_transaction),
+                Err(err) => {
+                    self.revert_package(&accepted_transactions);
+                    return Err(err);
+                }
+            }
+        }
+        Ok(accepted_transactions)
+    }
+
+    /// Checks that a package transaction can enter the mempool, leaving the pool untouched
+    fn post_validate_package_transaction(
+        &self,
+        validation_result: RuleResult<()>,
+        transaction: &MutableTransaction,
+        priority: Priority,
+    ) -> RuleResult<()> {
+        let transaction_id = transaction.id();
+        if self.transaction_pool.has(&transaction_id) {
+            return Err(RuleError::RejectDuplicate(transaction_id));
+        }
+        self.validate_transaction_unacceptance(transaction)?;
+        match validation_result {
+            Ok(_) => {}
+            Err(RuleError::RejectMissingOutpoint) => return Err(RuleError::RejectDisallowedOrphan(transaction_id)),
+            Err(err) => return Err(err),
+        }
+        self.validate_transaction_in_context(transaction)?;
+        self.transaction_pool.check_double_spends(transaction)?;
+        self.config.policy.admit(transaction, priority)?;
+        Ok(())
+    }
+
+    /// Inserts a post validated package transaction, the room for it being already made
+    fn insert_package_transaction(
+        &mut self,
+        consensus: &dyn ConsensusApi,
+        transaction: MutableTransaction,
+        priority: Priority,
+    ) -> RuleResult<Arc<Transaction>> {
+        let transaction_id = transaction.id();
+        let accepted_transaction =
+            match self.insert_validated_transaction(consensus, transaction, priority, RbfPolicy::Forbidden, RateLimit::Skipped)? {
+                TransactionPostValidation { accepted: Some(accepted_transaction), .. } => accepted_transaction,
+                TransactionPostValidation { accepted: None, .. } => return Err(RuleError::RejectMissingTransaction(transaction_id)),
+            };
+        if let Some(mempool_transaction) = self.transaction_pool.get(&transaction_id) {
+            self.config.policy.record_admission(&mempool_transaction.mtx, priority);
+        }
+        Ok(accepted_transaction)
+    }
+
+    /// Removes the already inserted transactions of a package which failed to be fully inserted
+    fn revert_package(&mut self, accepted_transactions: &[Arc<Transaction>]) {
+        for transaction in accepted_transactions.iter().rev() {
+            if let Err(err) = self.remove_transaction(&transaction.id(), true, TxRemovalReason::PackageRejected, "") {
+                debug!("Failed to remove transaction {} of a rejected package: {}", transaction.id(), err);
+            }
+        }
+    }
+
+    /// Validates that a package is a non-empty and bounded set of distinct, connected and non conflicting transactions
+    fn validate_package_structure(&self, transactions: &[Transaction]) -> RuleResult<()> {
+        if transactions.is_empty() {
+            return Err(RuleError::RejectEmptyPackage);
+        }
+        if transactions.len() > self.config.maximum_package_transaction_count {
+            return Err(RuleError::RejectPackageTooLarge(transactions.len(), self.config.maximum_package_transaction_count));
+        }
+
+        let mut ids = HashSet::with_capacity(transactions.len());
+        for transaction in transactions.iter() {
+            if !ids.insert(transaction.id()) {
+                return Err(RuleError::RejectPackageDuplicate(transaction.id()));
+            }
+        }
+
+        // Build the undirected graph of the spending relations between package transactions
+        let mut spenders: HashMap<TransactionOutpoint, TransactionId> = HashMap::new();
+        let mut edges: HashMap<TransactionId, Vec<TransactionId>> = HashMap::with_capacity(transactions.len());
+        for transaction in transactions.iter() {
+            let transaction_id = transaction.id();
+            for input in transaction.inputs.iter() {
+                match spenders.entry(input.previous_outpoint) {
+                    Entry::Occupied(entry) => {
+                        return Err(RuleError::RejectPackageDoubleSpend(input.previous_outpoint, *entry.get(), transaction_id));
+                    }
+                    Entry::Vacant(entry) => {
+                        entry.insert(transaction_id);
+                    }
+                }
+                let parent_id = input.previous_outpoint.transaction_id;
+                if parent_id != transaction_id && ids.contains(&parent_id) {
+                    edges.entry(transaction_id).or_default().push(parent_id);
+                    edges.entry(parent_id).or_default().push(transaction_id);
+                }
+            }
+        }
+
+        // All the package transactions must be reachable from the first one
+        let mut visited = HashSet::with_capacity(transactions.len());
+        let mut queue = VecDeque::from([transactions[0].id()]);
+        while let Some(transaction_id) = queue.pop_front() {
+            if visited.insert(transaction_id) {
+                queue.extend(edges.get(&transaction_id).into_iter().flatten().copied());
+            }
+        }
+        match transactions.iter().find(|transaction| !visited.contains(&transaction.id())) {
+            Some(transaction) => Err(RuleError::RejectPackageUnrelatedTransaction(transaction.id())),
+            None => Ok(()),
+        }
+    }
+}
@@ -1,13 +1,16 @@
 use std::sync::atomic::Ordering;
 
-use crate::mempool::{
-    errors::{RuleError, RuleResult},
-    model::{
-        pool::Pool,
-        tx::{MempoolTransaction, TransactionPostValidation, TransactionPreValidation, TxRemovalReason},
+use crate::{
+    mempool::{
+        errors::{RuleError, RuleResult},
+        model::{
+            pool::Pool,
+            tx::{MempoolTransaction, TransactionPostValidation, TransactionPreValidation, TxRemovalReason},
+        },
+        tx::{Orphan, Priority, RateLimit, RbfPolicy},
+        Mempool,
     },
-    tx::{Orphan, Priority, RbfPolicy},
-    Mempool,
+    model::tx_status::TransactionStatus,
 };
 use vecno_consensus_core::{
     api::ConsensusApi,
@@ -40,6 +43,7 @@ impl Mempool {
         priority: Priority,
         orphan: Orphan,
         rbf_policy: RbfPolicy,
+        rate_limit: RateLimit,
     ) -> RuleResult<TransactionPostValidation> {
         let transaction_id = transaction.id();
 
@@ -71,6 +75,29 @@ impl Mempool {
 
         // Perform mempool in-context validations prior to possible RBF replacements
         self.validate_transaction_in_context(&transaction)?;
+        if !self.config.accept_non_standard {
+            self.check_transaction_relay_fee(&transaction)?;
+        }
+
+        self.insert_validated_transaction(consensus, transaction, priority, rbf_policy, rate_limit)
+    }
+
+    /// Inserts a transaction having passed all validations into the pool, replacing its double spends
+    /// and evicting lower feerate transactions as needed
+    pub(crate) fn insert_validated_transaction(
+        &mut self,
+        consensus: &dyn ConsensusApi,
+        transaction: MutableTransaction,
+        priority: Priority,
+        rbf_policy: RbfPolicy,
+        rate_limit: RateLimit,
+    ) -> RuleResult<TransactionPostValidation> {
+        let transaction_id = transaction.id();
+
+        // Let the mempool policy reject the transaction before it affects the pool content
+        if rate_limit == RateLimit::Enforced {
+            self.config.policy.admit(&transaction, priority)?;
+        }
 
         // Check double spends and try to remove them if the RBF policy requires it
         let removed_transaction = self.execute_replace_by_fee(&transaction, rbf_policy)?;
@@ -86,28 +113,7 @@ impl Mempool {
         // Before adding the transaction, check if there is room in the pool
         let transaction_size = transaction.mempool_estimated_bytes();
         let txs_to_remove = self.transaction_pool.limit_transaction_count(&transaction, transaction_size)?;
-        if !txs_to_remove.is_empty() {
-            let transaction_pool_len_before = self.transaction_pool.len();
-            for x in txs_to_remove.iter() {
-                self.remove_transaction(x, true, TxRemovalReason::MakingRoom, format!(" for {}", transaction_id).as_str())?;
-                // self.transaction_pool.limit_transaction_count(&transaction) returns the
-                // smallest prefix of `ready_transactions` (sorted by ascending fee-rate)
-                // that makes enough room for `transaction`, but since each call to `self.remove_transaction`
-                // also removes all transactions dependant on `x` we might already have sufficient space, so
-                // we constantly check the break condition.
-                //
-                // Note that self.transaction_pool.len() < self.config.maximum_transaction_count means we have
-                // at least one available slot in terms of the count limit
-                if self.transaction_pool.len() < self.config.maximum_transaction_count
-                    && self.transaction_pool.get_estimated_size() + transaction_size <= self.config.mempool_size_limit
-                {
-                    break;
-                }
-            }
-            self.counters
-                .tx_evicted_counts
-                .fetch_add(transaction_pool_len_before.saturating_sub(self.transaction_pool.len()) as u64, Ordering::Relaxed);
-        }
+        self.make_room(&txs_to_remove, 1, transaction_size, format!(" for {}", transaction_id).as_str())?;
 
         assert!(
             self.transaction_pool.len() < self.config.maximum_transaction_count
@@ -120,17 +126,51 @@ impl Mempool {
         );
 
         // Add the transaction to the mempool as a MempoolTransaction and return a clone of the embedded Arc<Transaction>
-        let accepted_transaction = self
-            .transaction_pool
-            .add_transaction(transaction, consensus.get_virtual_daa_score(), priority, transaction_size)?
-            .mtx
-            .tx
-            .clone();
+        let mempool_transaction =
+            self.transaction_pool.add_transaction(transaction, consensus.get_virtual_daa_score(), priority, transaction_size)?;
+        // The rate limit allowance is only consumed by transactions actually inserted
+        if rate_limit == RateLimit::Enforced {
+            self.config.policy.record_admission(&mempool_transaction.mtx, priority);
+        }
+        let accepted_transaction = mempool_transaction.mtx.tx.clone();
+        self.transaction_statuses.update(transaction_id, TransactionStatus::InMempool);
         Ok(TransactionPostValidation { removed: removed_transaction, accepted: Some(accepted_transaction) })
     }
 
+    /// Removes the transactions selected by the transactions pool to make room for `transaction_count` new
+    /// transactions of total size `transactions_size`
+    pub(crate) fn make_room(
+        &mut self,
+        txs_to_remove: &[TransactionId],
+        transaction_count: usize,
+        transactions_size: usize,
+        extra_info: &str,
+    ) -> RuleResult<()> {
+        if txs_to_remove.is_empty() {
+            return Ok(());
+        }
+        let transaction_pool_len_before = self.transaction_pool.len();
+        for x in txs_to_remove.iter() {
+            self.remove_transaction(x, true, TxRemovalReason::MakingRoom, extra_info)?;
+            // self.transaction_pool.limit_transaction_count(&transaction) returns the
+            // smallest prefix of `ready_transactions` (sorted by ascending fee-rate)
+            // that makes enough room for `transaction`, but since each call to `self.remove_transaction`
+            // also removes all transactions dependant on `x` we might already have sufficient space, so
+            // we constantly check the break condition.
+            if self.transaction_pool.len() + transaction_count <= self.config.maximum_transaction_count
+                && self.transaction_pool.get_estimated_size() + transactions_size <= self.config.mempool_size_limit
+            {
+                break;
+            }
+        }
+        self.counters
+            .tx_evicted_counts
+            .fetch_add(transaction_pool_len_before.saturating_sub(self.transaction_pool.len()) as u64, Ordering::Relaxed);
+        Ok(())
+    }
+
     /// Validates that the transaction wasn't already accepted into the DAG
-    fn validate_transaction_unacceptance(&self, transaction: &MutableTransaction) -> RuleResult<()> {
+    pub(crate) fn validate_transaction_unacceptance(&self, transaction: &MutableTransaction) -> RuleResult<()> {
         // Reject if the transaction is registered as an accepted transaction
         let transaction_id = transaction.id();
         match self.accepted_transactions.has(&transaction_id) {
@@ -148,10 +188,11 @@ impl Mempool {
         if !self.config.accept_non_standard {
             self.check_transaction_standard_in_isolation(transaction)?;
         }
+        self.config.policy.check_in_isolation(transaction)?;
         Ok(())
     }
 
-    fn validate_transaction_in_context(&self, transaction: &MutableTransaction) -> RuleResult<()> {
+    pub(crate) fn validate_transaction_in_context(&self, transaction: &MutableTransaction) -> RuleResult<()> {
         if !self.config.accept_non_standard {
             self.check_transaction_standard_in_context(transaction)?;
         }
@@ -199,6 +240,7 @@ impl Mempool {
                         // In case of validation error, we log the problem and drop the
                         // erroneous transaction.
                         info!("Failed to unorphan transaction {0} due to rule error: {1}", orphan_id, err.to_string());
+                        self.transaction_statuses.update(orphan_id, TransactionStatus::Rejected { reason: err.to_string() });
                     }
                 }
             }
@@ -0,0 +1,115 @@
+use super::{owner_txs::ScriptPublicKeySet, tx_query::TransactionQuery};
+use std::array::TryFromSliceError;
+use vecno_consensus_core::tx::{MutableTransaction, TransactionId};
+use vecno_hashes::{Hash, HASH_SIZE};
+
+/// Order in which the mempool entries are paged through
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u8)]
+pub enum MempoolEntriesOrder {
+    /// Oldest entries first, by the DAA score at which they entered the mempool
+    #[default]
+    Arrival = 0,
+    /// Best paying entries first, orphans coming last since their fee is unknown
+    Feerate = 1,
+}
+
+/// Size of the [`MempoolEntriesCursor`] in bytes.
+pub const MEMPOOL_ENTRIES_CURSOR_SIZE: usize = size_of::<MempoolEntriesOrder>() + size_of::<u64>() + HASH_SIZE;
+
+/// Position of an entry within the mempool entries paged through in some [`MempoolEntriesOrder`].
+///
+/// Consists of 1 byte of [`MempoolEntriesOrder`], 8 bytes of big endian rank and 32 bytes of [`TransactionId`],
+/// so that the byte order matches the page order. The rank is the DAA score of arrival of the entry, or its
+/// inverted feerate bits when ordering by feerate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MempoolEntriesCursor([u8; MEMPOOL_ENTRIES_CURSOR_SIZE]);
+
+impl MempoolEntriesCursor {
+    pub fn new(order: MempoolEntriesOrder, rank: u64, transaction_id: TransactionId) -> Self {
+        let mut bytes = [0; MEMPOOL_ENTRIES_CURSOR_SIZE];
+        bytes[0] = order as u8;
+        bytes[1..9].copy_from_slice(&rank.to_be_bytes());
+        bytes[9..].copy_from_slice(&transaction_id.as_bytes());
+        Self(bytes)
+    }
+
+    /// Returns the cursor of an entry of the page ordered by `order`
+    pub fn from_entry(
+        order: MempoolEntriesOrder,
+        transaction_id: TransactionId,
+        added_at_daa_score: u64,
+        feerate: Option<f64>,
+    ) -> Self {
+        let rank = match order {
+            MempoolEntriesOrder::Arrival => added_at_daa_score,
+            // Positive floats order as their bits do, so inverting these sorts the best feerates first
+            MempoolEntriesOrder::Feerate => u64::MAX - feerate.unwrap_or_default().max(0.0).to_bits(),
+        };
+        Self::new(order, rank, transaction_id)
+    }
+
+    pub fn order(&self) -> Option<MempoolEntriesOrder> {
+        match self.0[0] {
+            0 => Some(MempoolEntriesOrder::Arrival),
+            1 => Some(MempoolEntriesOrder::Feerate),
+            _ => None,
+        }
+    }
+
+    pub fn transaction_id(&self) -> TransactionId {
+        Hash::from_slice(&self.0[9..])
+    }
+}
+
+impl AsRef<[u8]> for MempoolEntriesCursor {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl TryFrom<&[u8]> for MempoolEntriesCursor {
+    type Error = TryFromSliceError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Ok(Self(bytes.try_into()?))
+    }
+}
+
+/// Selection of a page of mempool entries
+#[derive(Debug, Clone)]
+pub struct MempoolEntriesQuery {
+    pub query: TransactionQuery,
+    pub order: MempoolEntriesOrder,
+    /// The first entry of the page, `None` to start from the first entry in `order`
+    pub cursor: Option<MempoolEntriesCursor>,
+    /// The maximum number of entries of the page
+    pub limit: usize,
+    /// Excludes the entries paying a lower feerate, orphans included if positive
+    pub min_feerate: Option<f64>,
+    /// Only includes the entries spending from or paying to one of these script public keys, if not empty
+    pub script_public_keys: ScriptPublicKeySet,
+    /// Leaves out the transactions of the entries, only returning their summaries
+    pub compact: bool,
+}
+
+/// A mempool entry of a [`MempoolEntriesPage`]
+#[derive(Debug, Clone)]
+pub struct MempoolEntry {
+    pub transaction_id: TransactionId,
+    /// The mass of the transaction, reduced to a single one-dimension value
+    pub mass: u64,
+    /// The fee of the transaction, unknown for orphans
+    pub fee: Option<u64>,
+    pub is_orphan: bool,
+    /// The transaction, `None` in compact queries
+    pub transaction: Option<MutableTransaction>,
+}
+
+/// A page of mempool entries, in the order of its query.
+#[derive(Debug, Clone, Default)]
+pub struct MempoolEntriesPage {
+    pub entries: Vec<MempoolEntry>,
+    /// The position of the first entry of the next page, if any
+    pub next_cursor: Option<MempoolEntriesCursor>,
+}
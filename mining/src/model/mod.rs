@@ -2,11 +2,15 @@ use vecno_consensus_core::tx::TransactionId;
 use std::collections::HashSet;
 
 pub mod candidate_tx;
+pub mod mempool_entries;
 pub mod owner_txs;
+pub mod persisted_tx;
 pub mod topological_index;
 pub mod topological_sort;
 pub mod tx_insert;
 pub mod tx_query;
+pub mod tx_selection;
+pub mod tx_status;
 
 /// A set of unique transaction ids
 pub type TransactionIdSet = HashSet<TransactionId>;
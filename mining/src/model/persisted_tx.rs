@@ -0,0 +1,29 @@
+use crate::mempool::tx::Priority;
+use serde::{Deserialize, Serialize};
+use vecno_consensus_core::tx::Transaction;
+use vecno_utils::mem_size::MemSizeEstimator;
+
+/// A mempool transaction as saved on disk, to be revalidated when the node restarts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersistedTransaction {
+    pub transaction: Transaction,
+    pub priority: Priority,
+    /// The DAA score at which the transaction was first added to the mempool
+    pub added_at_daa_score: u64,
+    /// Whether the transaction was in the orphan pool
+    pub is_orphan: bool,
+}
+
+impl PersistedTransaction {
+    pub fn new(transaction: Transaction, priority: Priority, added_at_daa_score: u64, is_orphan: bool) -> Self {
+        Self { transaction, priority, added_at_daa_score, is_orphan }
+    }
+}
+
+impl AsRef<Transaction> for PersistedTransaction {
+    fn as_ref(&self) -> &Transaction {
+        &self.transaction
+    }
+}
+
+impl MemSizeEstimator for PersistedTransaction {}
@@ -1,4 +1,5 @@
 /// Indicates whether the mempool query result should include transactions/orphans or both
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TransactionQuery {
     /// Include only non-orphan transactions from the ordinary mempool tx pool
     TransactionsOnly,
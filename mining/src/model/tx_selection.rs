@@ -0,0 +1,43 @@
+use vecno_consensus_core::tx::{Transaction, TransactionId};
+
+/// Caller-supplied constraints on the transactions included in a block template.
+///
+/// Mandatory transactions are included ahead of any other, in the order they are given, as long as they are valid
+/// and fit in the block. The remaining block mass is filled with mempool transactions selected by feerate, skipping
+/// the excluded ones.
+#[derive(Debug, Clone, Default)]
+pub struct TransactionSelection {
+    /// Ids of mempool transactions which must be included
+    pub mandatory_transaction_ids: Vec<TransactionId>,
+    /// Transactions which must be included, whether or not they are in the mempool
+    pub mandatory_transactions: Vec<Transaction>,
+    /// Ids of mempool transactions which must not be selected to fill the block
+    pub excluded_transaction_ids: Vec<TransactionId>,
+}
+
+impl TransactionSelection {
+    pub fn new(
+        mandatory_transaction_ids: Vec<TransactionId>,
+        mandatory_transactions: Vec<Transaction>,
+        excluded_transaction_ids: Vec<TransactionId>,
+    ) -> Self {
+        Self { mandatory_transaction_ids, mandatory_transactions, excluded_transaction_ids }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mandatory_transaction_ids.is_empty() && self.mandatory_transactions.is_empty() && self.excluded_transaction_ids.is_empty()
+    }
+}
+
+/// A mandatory transaction which could not be included in a block template
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RejectedTransaction {
+    pub transaction_id: TransactionId,
+    pub reason: String,
+}
+
+impl RejectedTransaction {
+    pub fn new(transaction_id: TransactionId, reason: impl Into<String>) -> Self {
+        Self { transaction_id, reason: reason.into() }
+    }
+}
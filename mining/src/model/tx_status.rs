@@ -0,0 +1,38 @@
+use vecno_consensus_core::tx::TransactionId;
+
+/// Status of a transaction as known by the mempool
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransactionStatus {
+    /// The transaction is in the transaction pool, waiting for inclusion into a block
+    InMempool,
+    /// The transaction is in the orphan pool, waiting for its missing inputs
+    Orphan,
+    /// The transaction was included in a block handled by the mempool.
+    ///
+    /// Inclusion does not guarantee acceptance by the virtual chain, which must be checked against the consensus.
+    Included { block_daa_score: u64 },
+    /// The transaction was rejected when submitted or unorphaned
+    Rejected { reason: String },
+    /// The transaction was removed from the mempool without being included in a block
+    Evicted { reason: String },
+}
+
+impl TransactionStatus {
+    /// Returns true if the status is final as far as the mempool is concerned, the transaction being out of its pools
+    pub fn is_outcome(&self) -> bool {
+        !matches!(self, TransactionStatus::InMempool | TransactionStatus::Orphan)
+    }
+}
+
+/// A change of the [`TransactionStatus`] of a transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionStatusChange {
+    pub transaction_id: TransactionId,
+    pub status: TransactionStatus,
+}
+
+impl TransactionStatusChange {
+    pub fn new(transaction_id: TransactionId, status: TransactionStatus) -> Self {
+        Self { transaction_id, status }
+    }
+}
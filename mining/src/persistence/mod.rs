@@ -0,0 +1,108 @@
+//! Mempool persistence across node restarts.
+//!
+//! The content of the mempool is saved to disk periodically and on shutdown. On startup, the saved transactions
+//! are revalidated against the current virtual state and the valid ones are inserted back into the mempool.
+
+use self::store::DbMempoolStore;
+use crate::manager::MiningManagerProxy;
+use vecno_consensusmanager::ConsensusManager;
+use vecno_core::{
+    debug, info,
+    task::{
+        service::{AsyncService, AsyncServiceFuture},
+        tick::{TickReason, TickService},
+    },
+    trace, warn,
+};
+use vecno_database::prelude::DB;
+use std::{sync::Arc, time::Duration};
+
+pub mod store;
+
+const MEMPOOL_PERSISTENCE: &str = "mempool-persistence";
+
+/// Interval at which the mempool is saved to disk
+const SNAPSHOT_INTERVAL: Duration = Duration::from_secs(300);
+
+pub struct MempoolPersistence {
+    mining_manager: MiningManagerProxy,
+    consensus_manager: Arc<ConsensusManager>,
+    store: DbMempoolStore,
+    tick_service: Arc<TickService>,
+}
+
+impl MempoolPersistence {
+    pub fn new(
+        mining_manager: MiningManagerProxy,
+        consensus_manager: Arc<ConsensusManager>,
+        db: Arc<DB>,
+        tick_service: Arc<TickService>,
+    ) -> Self {
+        Self { mining_manager, consensus_manager, store: DbMempoolStore::new(db), tick_service }
+    }
+
+    async fn restore(&self) {
+        let transactions = match self.store.load() {
+            Ok(transactions) => transactions,
+            Err(err) => {
+                warn!("Failed to load the persisted mempool: {}", err);
+                return;
+            }
+        };
+        if transactions.is_empty() {
+            return;
+        }
+        let count = transactions.len();
+        let session = self.consensus_manager.consensus().session().await;
+        let restored = self.mining_manager.clone().restore_transactions(&session, transactions).await;
+        info!("Restored {} out of {} persisted mempool transactions", restored, count);
+    }
+
+    async fn save(&self) {
+        let transactions = self.mining_manager.clone().persisted_transactions().await;
+        let count = transactions.len();
+        let store = self.store.clone();
+        match tokio::task::spawn_blocking(move || store.save(transactions)).await.unwrap() {
+            Ok(()) => debug!("Saved {} mempool transactions to disk", count),
+            Err(err) => warn!("Failed to save the mempool to disk: {}", err),
+        }
+    }
+
+    pub async fn worker(self: &Arc<MempoolPersistence>) {
+        self.restore().await;
+        loop {
+            if let TickReason::Shutdown = self.tick_service.tick(SNAPSHOT_INTERVAL).await {
+                break;
+            }
+            self.save().await;
+        }
+
+        // Save a final snapshot so that the mempool survives the restart
+        self.save().await;
+        trace!("mempool persistence thread exiting");
+    }
+}
+
+impl AsyncService for MempoolPersistence {
+    fn ident(self: Arc<Self>) -> &'static str {
+        MEMPOOL_PERSISTENCE
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            self.worker().await;
+            Ok(())
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", MEMPOOL_PERSISTENCE);
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            trace!("{} stopped", MEMPOOL_PERSISTENCE);
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,46 @@
+use crate::model::persisted_tx::PersistedTransaction;
+use itertools::Itertools;
+use rocksdb::WriteBatch;
+use std::sync::Arc;
+use vecno_consensus_core::{tx::TransactionId, BlockHasher};
+use vecno_database::{
+    prelude::{BatchDbWriter, CachePolicy, CachedDbAccess, StoreError, StoreResult, DB},
+    registry::DatabaseStorePrefixes,
+};
+
+/// A DB store holding the latest snapshot of the mempool
+#[derive(Clone)]
+pub struct DbMempoolStore {
+    db: Arc<DB>,
+    access: CachedDbAccess<TransactionId, PersistedTransaction, BlockHasher>,
+}
+
+impl DbMempoolStore {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self {
+            db: Arc::clone(&db),
+            access: CachedDbAccess::new(db, CachePolicy::Empty, DatabaseStorePrefixes::MempoolTransactions.into()),
+        }
+    }
+
+    /// Returns all the transactions of the snapshot
+    pub fn load(&self) -> StoreResult<Vec<PersistedTransaction>> {
+        self.access
+            .iterator()
+            .map_ok(|(_, transaction)| transaction)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| StoreError::DataInconsistency(err.to_string()))
+    }
+
+    /// Atomically replaces the snapshot with `transactions`
+    pub fn save(&self, transactions: Vec<PersistedTransaction>) -> StoreResult<()> {
+        let mut batch = WriteBatch::default();
+        self.access.delete_all(BatchDbWriter::new(&mut batch))?;
+        self.access.write_many_without_cache(
+            BatchDbWriter::new(&mut batch),
+            &mut transactions.into_iter().map(|transaction| (transaction.transaction.id(), transaction)),
+        )?;
+        self.db.write(batch)?;
+        Ok(())
+    }
+}
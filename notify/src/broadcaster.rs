@@ -13,6 +13,7 @@ use derive_more::Deref;
 use futures::{future::FutureExt, select_biased};
 use indexmap::IndexMap;
 use vecno_core::{debug, trace};
+use parking_lot::Mutex;
 use std::{
     collections::HashMap,
     fmt::Display,
@@ -25,6 +26,58 @@ use workflow_core::channel::Channel;
 
 type ConnectionSet<T> = HashMap<ListenerId, T>;
 
+/// Maximum number of live messages held back for a listener while its missed notifications are replayed.
+/// Beyond it and unless the replayed notifications are already being sent, the replay is given up and the
+/// listener gets the live messages only.
+pub(crate) const MAX_HELD_MESSAGES: usize = 10_000;
+
+/// Progress of the replay of the notifications missed by a listener, shared by all the broadcasters holding back
+/// its live notifications meanwhile.
+///
+/// The replay is released in two phases: a single broadcaster first sends the replayed notifications, then every
+/// broadcaster sends the live ones it held back. A broadcaster may only give the replay up before the first phase,
+/// so no live notification ever overtakes a replayed one.
+#[derive(Debug, Default)]
+pub(crate) struct ReplayProgress(Mutex<ReplayPhase>);
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum ReplayPhase {
+    #[default]
+    Holding,
+    Replaying,
+    GivenUp,
+}
+
+impl ReplayProgress {
+    /// Returns whether the replayed notifications can be sent, the replay not being given up
+    fn start_replay(&self) -> bool {
+        let mut phase = self.0.lock();
+        if *phase == ReplayPhase::Holding {
+            *phase = ReplayPhase::Replaying;
+        }
+        *phase == ReplayPhase::Replaying
+    }
+
+    /// Returns whether the replay is given up, which is no longer possible once the replayed notifications are being sent
+    fn give_up(&self) -> bool {
+        let mut phase = self.0.lock();
+        if *phase == ReplayPhase::Holding {
+            *phase = ReplayPhase::GivenUp;
+        }
+        *phase == ReplayPhase::GivenUp
+    }
+
+    pub(crate) fn is_given_up(&self) -> bool {
+        *self.0.lock() == ReplayPhase::GivenUp
+    }
+}
+
+/// Live messages held back for a listener
+struct Held<M> {
+    messages: Vec<M>,
+    progress: Arc<ReplayProgress>,
+}
+
 /// Broadcasting plan structured by subscription, encoding and connection
 #[derive(Deref)]
 struct Plan<C: Connection>(IndexMap<DynSubscription, HashMap<C::Encoding, ConnectionSet<C>>>);
@@ -76,6 +129,12 @@ where
         result
     }
 
+    fn get(&self, id: &ListenerId) -> Option<(&DynSubscription, &C)> {
+        self.0.iter().find_map(|(subscription, encoding_set)| {
+            encoding_set.values().find_map(|connection_set| connection_set.get(id)).map(|connection| (subscription, connection))
+        })
+    }
+
     // fn len(&self) -> usize {
     //     self.0.values().map(|encodings| encodings.values().map(|connections| connections.len()).count()).count()
     // }
@@ -94,6 +153,12 @@ where
 {
     Register(DynSubscription, ListenerId, C),
     Unregister(EventType, ListenerId),
+    /// Holds back the notifications of an event type sent to a listener until its release
+    Hold(EventType, ListenerId, Arc<ReplayProgress>),
+    /// Sends the replayed notifications to a listener unless the replay was given up, then signals it on the sender
+    Replay(EventType, ListenerId, Arc<Vec<C::Notification>>, Arc<ReplayProgress>, Sender<()>),
+    /// Sends the notifications held back for a listener, then signals it on the sender
+    Release(EventType, ListenerId, Sender<()>),
 }
 
 #[derive(Debug)]
@@ -153,6 +218,8 @@ where
             let mut plan = EventArray::<Plan<C>>::default();
             // Create a store for closed connections to be removed from the plan
             let mut purge: Vec<ListenerId> = Vec::new();
+            // Messages held back by event type and listener while the listener gets its missed notifications replayed
+            let mut held = EventArray::<HashMap<ListenerId, Held<C::Message>>>::default();
             loop {
                 select_biased! {
                     ctl = self.ctl.recv().fuse() => {
@@ -165,8 +232,42 @@ where
                                 },
                                 Ctl::Unregister(event_type, id) => {
                                     plan[event_type].remove(&id);
+                                    held[event_type].remove(&id);
                                     debug!("[{}] remove {} subscription, count = {}, capacity = {}", self, event_type, plan[event_type].len(), plan[event_type].capacity());
                                 },
+                                Ctl::Hold(event_type, id, progress) => {
+                                    held[event_type].insert(id, Held { messages: vec![], progress });
+                                    debug!("[{}] hold {} notifications of listener {}", self, event_type, id);
+                                },
+                                Ctl::Replay(event_type, id, replayed, progress, done) => {
+                                    if let Some((subscription, connection)) = plan[event_type].get(&id).filter(|_| progress.start_replay()) {
+                                        debug!("[{}] replay {} {} notifications to listener {}", self, replayed.len(), event_type, id);
+                                        let messages = replayed
+                                            .iter()
+                                            .filter_map(|notification| notification.apply_subscription(&**subscription, &context))
+                                            .map(|notification| C::into_message(&notification, &connection.encoding()));
+                                        for message in messages {
+                                            if connection.send(message).await.is_err() {
+                                                trace!("[{}] could not replay a notification to listener {id}", self);
+                                                break;
+                                            }
+                                        }
+                                    }
+                                    let _ = done.try_send(());
+                                },
+                                Ctl::Release(event_type, id, done) => {
+                                    let messages = held[event_type].remove(&id).map(|held| held.messages).unwrap_or_default();
+                                    if let Some((_, connection)) = plan[event_type].get(&id) {
+                                        debug!("[{}] release {} held {} notifications of listener {}", self, messages.len(), event_type, id);
+                                        for message in messages {
+                                            if connection.send(message).await.is_err() {
+                                                trace!("[{}] could not release a notification to listener {id}", self);
+                                                break;
+                                            }
+                                        }
+                                    }
+                                    let _ = done.try_send(());
+                                },
                             }
                         } else {
                             break;
@@ -184,6 +285,20 @@ where
                                         // ... by message encoding
                                         let message = C::into_message(&applied_notification, encoding);
                                         for (id, connection) in connection_set.iter() {
+                                            // ... holding it back if the listener is being replayed its missed notifications
+                                            if let Some(Held { messages, progress }) = held[event].get_mut(id) {
+                                                if messages.len() < MAX_HELD_MESSAGES || !progress.give_up() {
+                                                    messages.push(message.clone());
+                                                    continue;
+                                                }
+                                                // ... or giving the replay up if too many are held back, sending first the held ones
+                                                debug!("[{}] too many {} notifications held back for listener {id}, giving its replay up", self, event);
+                                                for message in held[event].remove(id).map(|held| held.messages).unwrap_or_default() {
+                                                    if connection.send(message).await.is_err() {
+                                                        break;
+                                                    }
+                                                }
+                                            }
                                             // ... to listeners connections
                                             match connection.send(message.clone()).await {
                                                 Ok(_) => {
@@ -232,6 +347,28 @@ where
         Ok(())
     }
 
+    pub fn hold(&self, event_type: EventType, id: ListenerId, progress: Arc<ReplayProgress>) -> Result<()> {
+        self.ctl.try_send(Ctl::Hold(event_type, id, progress))?;
+        Ok(())
+    }
+
+    pub fn replay(
+        &self,
+        event_type: EventType,
+        id: ListenerId,
+        replayed: Arc<Vec<N>>,
+        progress: Arc<ReplayProgress>,
+        done: Sender<()>,
+    ) -> Result<()> {
+        self.ctl.try_send(Ctl::Replay(event_type, id, replayed, progress, done))?;
+        Ok(())
+    }
+
+    pub fn release(&self, event_type: EventType, id: ListenerId, done: Sender<()>) -> Result<()> {
+        self.ctl.try_send(Ctl::Release(event_type, id, done))?;
+        Ok(())
+    }
+
     async fn join_notification_broadcasting_task(&self) -> Result<()> {
         trace!("[{}] joining", self);
         self.shutdown.recv().await?;
@@ -264,7 +401,8 @@ mod tests {
         notifier::test_helpers::{
             overall_test_steps, utxos_changed_test_steps, virtual_chain_changed_test_steps, Step, TestConnection, SYNC_MAX_DELAY,
         },
-        subscription::context::SubscriptionContext,
+        scope::VirtualChainChangedScope,
+        subscription::{context::SubscriptionContext, Command, Mutation},
     };
     use async_channel::{unbounded, Sender};
     use tokio::time::timeout;
@@ -455,4 +593,170 @@ mod tests {
         let mut test = Test::new("UtxosChanged broadcast", 3, utxos_changed_test_steps(0));
         test.run().await;
     }
+
+    #[tokio::test]
+    async fn test_hold_and_release() {
+        vecno_core::log::try_init_logger("trace,vecno_notify=trace");
+        let test = Test::new("VirtualChainChanged hold and release", 1, vec![]);
+        let notification =
+            |data| TestNotification::VirtualChainChanged(VirtualChainChangedNotification { data, accepted_transaction_ids: None });
+        let sync = || async { assert!(timeout(SYNC_MAX_DELAY, test.sync_receiver.recv()).await.unwrap().is_ok()) };
+        test.broadcaster.start();
+
+        let mut listener = Listener::new(0, test.listeners[0].connection());
+        let mutation = Mutation::new(Command::Start, VirtualChainChangedScope::new(false).into());
+        listener.mutate(mutation, Default::default(), &test.subscription_context).unwrap();
+        let subscription = listener.subscriptions[EventType::VirtualChainChanged].clone();
+
+        // Notifications are held back until the release
+        let progress = Arc::new(ReplayProgress::default());
+        test.broadcaster.hold(EventType::VirtualChainChanged, 0, progress.clone()).unwrap();
+        sync().await;
+        test.broadcaster.register(subscription, 0, listener.connection()).unwrap();
+        sync().await;
+        for data in [3, 4] {
+            test.notification_sender.send(notification(data)).await.unwrap();
+            sync().await;
+        }
+        assert!(test.notification_receivers[0].is_empty());
+
+        // The replayed notifications come first, followed by the held back ones and then by the live ones
+        let (done_sender, _done_receiver) = unbounded();
+        let replayed = Arc::new(vec![notification(1), notification(2)]);
+        test.broadcaster.replay(EventType::VirtualChainChanged, 0, replayed, progress.clone(), done_sender.clone()).unwrap();
+        sync().await;
+        test.broadcaster.release(EventType::VirtualChainChanged, 0, done_sender).unwrap();
+        sync().await;
+        assert!(!progress.is_given_up());
+        test.notification_sender.send(notification(5)).await.unwrap();
+        sync().await;
+        for data in 1..=5 {
+            assert_eq!(test.notification_receivers[0].recv().await.unwrap(), notification(data));
+        }
+        assert!(test.notification_receivers[0].is_empty());
+
+        test.notification_sender.close();
+        assert!(test.broadcaster.join().await.is_ok(), "broadcaster failed to stop");
+    }
+
+    #[tokio::test]
+    async fn test_hold_overflow() {
+        vecno_core::log::try_init_logger("info,vecno_notify=info");
+        let test = Test::new("VirtualChainChanged hold overflow", 1, vec![]);
+        let notification =
+            |data| TestNotification::VirtualChainChanged(VirtualChainChangedNotification { data, accepted_transaction_ids: None });
+        let sync = || async { assert!(timeout(SYNC_MAX_DELAY, test.sync_receiver.recv()).await.unwrap().is_ok()) };
+        test.broadcaster.start();
+
+        let mut listener = Listener::new(0, test.listeners[0].connection());
+        let mutation = Mutation::new(Command::Start, VirtualChainChangedScope::new(false).into());
+        listener.mutate(mutation, Default::default(), &test.subscription_context).unwrap();
+        let subscription = listener.subscriptions[EventType::VirtualChainChanged].clone();
+        let progress = Arc::new(ReplayProgress::default());
+        test.broadcaster.hold(EventType::VirtualChainChanged, 0, progress.clone()).unwrap();
+        sync().await;
+        test.broadcaster.register(subscription, 0, listener.connection()).unwrap();
+        sync().await;
+
+        // Holding back one message more than the maximum gives the replay up and sends the held messages
+        for data in 0..=MAX_HELD_MESSAGES as u64 {
+            test.notification_sender.send(notification(data)).await.unwrap();
+            sync().await;
+        }
+        assert_eq!(test.notification_receivers[0].len(), MAX_HELD_MESSAGES + 1);
+
+        // The replay is given up and its notifications are dropped
+        assert!(progress.is_given_up());
+        let (done_sender, _done_receiver) = unbounded();
+        let replayed = Arc::new(vec![notification(u64::MAX)]);
+        test.broadcaster.replay(EventType::VirtualChainChanged, 0, replayed, progress.clone(), done_sender.clone()).unwrap();
+        sync().await;
+        test.broadcaster.release(EventType::VirtualChainChanged, 0, done_sender).unwrap();
+        sync().await;
+        for data in 0..=MAX_HELD_MESSAGES as u64 {
+            assert_eq!(test.notification_receivers[0].recv().await.unwrap(), notification(data));
+        }
+        assert!(test.notification_receivers[0].is_empty());
+
+        test.notification_sender.close();
+        assert!(test.broadcaster.join().await.is_ok(), "broadcaster failed to stop");
+    }
+
+    #[tokio::test]
+    async fn test_replay_with_several_broadcasters() {
+        vecno_core::log::try_init_logger("trace,vecno_notify=trace");
+        const IDENT: &str = "test";
+        const BROADCASTERS: usize = 3;
+        let event = EventType::VirtualChainChanged;
+        let notification =
+            |data| TestNotification::VirtualChainChanged(VirtualChainChangedNotification { data, accepted_transaction_ids: None });
+
+        // Broadcasters competing for the notifications, as in a notifier
+        let subscription_context = SubscriptionContext::new();
+        let (sync_sender, sync_receiver) = unbounded();
+        let (notification_sender, notification_receiver) = unbounded();
+        let broadcasters = (0..BROADCASTERS)
+            .map(|index| {
+                let (context, incoming, sync) =
+                    (subscription_context.clone(), notification_receiver.clone(), Some(sync_sender.clone()));
+                Arc::new(TestBroadcaster::new(IDENT, index, context, incoming, sync))
+            })
+            .collect::<Vec<_>>();
+        let sync = |count: usize| {
+            let sync_receiver = sync_receiver.clone();
+            async move {
+                for _ in 0..count {
+                    assert!(timeout(SYNC_MAX_DELAY, sync_receiver.recv()).await.unwrap().is_ok());
+                }
+            }
+        };
+        broadcasters.iter().for_each(|broadcaster| broadcaster.start());
+
+        let (sender, receiver) = unbounded();
+        let mut listener = Listener::new(0, TestConnection::new(IDENT, sender, ChannelType::Closable));
+        let mutation = Mutation::new(Command::Start, VirtualChainChangedScope::new(false).into());
+        listener.mutate(mutation, Default::default(), &subscription_context).unwrap();
+        let progress = Arc::new(ReplayProgress::default());
+        for broadcaster in broadcasters.iter() {
+            broadcaster.hold(event, 0, progress.clone()).unwrap();
+            broadcaster.register(listener.subscriptions[event].clone(), 0, listener.connection()).unwrap();
+        }
+        sync(2 * BROADCASTERS).await;
+
+        // The live notifications are held back by whichever broadcaster gets them
+        for data in 3..=5 {
+            notification_sender.send(notification(data)).await.unwrap();
+            sync(1).await;
+        }
+        assert!(receiver.is_empty());
+
+        // The replayed notifications are sent first, the live ones being still held back once they are
+        let (done_sender, done_receiver) = unbounded();
+        let replayed = Arc::new(vec![notification(1), notification(2)]);
+        broadcasters[0].replay(event, 0, replayed, progress.clone(), done_sender).unwrap();
+        assert!(done_receiver.recv().await.is_ok());
+        sync(1).await;
+        notification_sender.send(notification(6)).await.unwrap();
+        sync(1).await;
+        for data in [1, 2] {
+            assert_eq!(receiver.recv().await.unwrap(), notification(data));
+        }
+        assert!(receiver.is_empty());
+
+        // Every broadcaster then releases the live notifications it held back
+        let (done_sender, done_receiver) = unbounded();
+        broadcasters.iter().for_each(|broadcaster| broadcaster.release(event, 0, done_sender.clone()).unwrap());
+        drop(done_sender);
+        while done_receiver.recv().await.is_ok() {}
+        sync(BROADCASTERS).await;
+        let live = (3..=6).map(|_| receiver.try_recv().unwrap()).collect::<Vec<_>>();
+        assert!((3..=6).all(|data| live.contains(&notification(data))));
+        assert!(receiver.is_empty());
+        assert!(!progress.is_given_up());
+
+        notification_sender.close();
+        for broadcaster in broadcasters.iter() {
+            assert!(broadcaster.join().await.is_ok(), "broadcaster failed to stop");
+        }
+    }
 }
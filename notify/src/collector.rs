@@ -128,7 +128,10 @@ mod tests {
         notifier::test_helpers::NotifyMock,
         subscription::{
             context::SubscriptionContext,
-            single::{OverallSubscription, UtxosChangedSubscription, VirtualChainChangedSubscription},
+            single::{
+                BlockAddedSubscription, OverallSubscription, TransactionStatusChangedSubscription, UtxosChangedSubscription,
+                VirtualChainChangedSubscription,
+            },
         },
     };
     use derive_more::Display;
@@ -159,6 +162,10 @@ mod tests {
             unimplemented!()
         }
 
+        fn apply_block_added_subscription(&self, _: &BlockAddedSubscription, _: &SubscriptionContext) -> Option<Self> {
+            unimplemented!()
+        }
+
         fn apply_virtual_chain_changed_subscription(
             &self,
             _: &VirtualChainChangedSubscription,
@@ -171,6 +178,14 @@ mod tests {
             unimplemented!()
         }
 
+        fn apply_transaction_status_changed_subscription(
+            &self,
+            _: &TransactionStatusChangedSubscription,
+            _: &SubscriptionContext,
+        ) -> Option<Self> {
+            unimplemented!()
+        }
+
         fn event_type(&self) -> EventType {
             unimplemented!()
         }
@@ -26,6 +26,12 @@ pub enum Error {
     #[error("Invalid event type: {0}")]
     InvalidEventType(String),
 
+    #[error("subscription scope holds {1} {0}, exceeding the maximum of {2}")]
+    ScopeLimitExceeded(&'static str, usize, usize),
+
+    #[error("notifications since {0} cannot be replayed, the subscription continues with live notifications only: {1}")]
+    ResumeGap(String, String),
+
     #[error(transparent)]
     AddressError(#[from] crate::address::error::Error),
 }
@@ -3,7 +3,10 @@ use crate::subscription::context::SubscriptionContext;
 use super::{
     events::EventType,
     subscription::{
-        single::{OverallSubscription, UtxosChangedSubscription, VirtualChainChangedSubscription},
+        single::{
+            BlockAddedSubscription, OverallSubscription, TransactionStatusChangedSubscription, UtxosChangedSubscription,
+            VirtualChainChangedSubscription,
+        },
         Single,
     },
 };
@@ -13,6 +16,8 @@ use std::fmt::{Debug, Display};
 pub trait Notification: Clone + Debug + Display + Send + Sync + 'static {
     fn apply_overall_subscription(&self, subscription: &OverallSubscription, context: &SubscriptionContext) -> Option<Self>;
 
+    fn apply_block_added_subscription(&self, subscription: &BlockAddedSubscription, context: &SubscriptionContext) -> Option<Self>;
+
     fn apply_virtual_chain_changed_subscription(
         &self,
         subscription: &VirtualChainChangedSubscription,
@@ -22,14 +27,27 @@ pub trait Notification: Clone + Debug + Display + Send + Sync + 'static {
     fn apply_utxos_changed_subscription(&self, subscription: &UtxosChangedSubscription, context: &SubscriptionContext)
         -> Option<Self>;
 
+    fn apply_transaction_status_changed_subscription(
+        &self,
+        subscription: &TransactionStatusChangedSubscription,
+        context: &SubscriptionContext,
+    ) -> Option<Self>;
+
     fn apply_subscription(&self, subscription: &dyn Single, context: &SubscriptionContext) -> Option<Self> {
         match subscription.event_type() {
+            EventType::BlockAdded => {
+                self.apply_block_added_subscription(subscription.as_any().downcast_ref::<BlockAddedSubscription>().unwrap(), context)
+            }
             EventType::VirtualChainChanged => self.apply_virtual_chain_changed_subscription(
                 subscription.as_any().downcast_ref::<VirtualChainChangedSubscription>().unwrap(),
                 context,
             ),
             EventType::UtxosChanged => self
                 .apply_utxos_changed_subscription(subscription.as_any().downcast_ref::<UtxosChangedSubscription>().unwrap(), context),
+            EventType::TransactionStatusChanged => self.apply_transaction_status_changed_subscription(
+                subscription.as_any().downcast_ref::<TransactionStatusChangedSubscription>().unwrap(),
+                context,
+            ),
             _ => self.apply_overall_subscription(subscription.as_any().downcast_ref::<OverallSubscription>().unwrap(), context),
         }
     }
@@ -122,6 +140,13 @@ pub mod test_helpers {
             }
         }
 
+        fn apply_block_added_subscription(&self, subscription: &BlockAddedSubscription, _: &SubscriptionContext) -> Option<Self> {
+            match subscription.active() {
+                true => Some(self.clone()),
+                false => None,
+            }
+        }
+
         fn apply_virtual_chain_changed_subscription(
             &self,
             subscription: &VirtualChainChangedSubscription,
@@ -178,6 +203,17 @@ pub mod test_helpers {
             }
         }
 
+        fn apply_transaction_status_changed_subscription(
+            &self,
+            subscription: &TransactionStatusChangedSubscription,
+            _: &SubscriptionContext,
+        ) -> Option<Self> {
+            match subscription.active() {
+                true => Some(self.clone()),
+                false => None,
+            }
+        }
+
         fn event_type(&self) -> EventType {
             self.into()
         }
@@ -5,14 +5,15 @@ use crate::{
 };
 
 use super::{
-    broadcaster::Broadcaster,
+    broadcaster::{Broadcaster, ReplayProgress},
     collector::DynCollector,
     connection::Connection,
     error::{Error, Result},
     events::{EventArray, EventSwitches, EventType},
     listener::{Listener, ListenerId},
     notification::Notification,
-    scope::Scope,
+    replay::DynReplayer,
+    scope::{ResumeToken, Scope},
     subscriber::{Subscriber, SubscriptionManager},
     subscription::{array::ArrayBuilder, Command, CompoundedSubscription, Mutation},
 };
@@ -21,7 +22,7 @@ use async_trait::async_trait;
 use core::fmt::Debug;
 use futures::future::join_all;
 use itertools::Itertools;
-use vecno_core::{debug, trace};
+use vecno_core::{debug, trace, warn};
 use parking_lot::Mutex;
 use std::{
     collections::{hash_map::Entry, HashMap},
@@ -105,6 +106,13 @@ pub type DynNotify<N> = Arc<dyn Notify<N>>;
 /// A notifier is built with a specific set of enabled event types. It is however possible to manually subscribe
 /// to a disabled scope and thus have a custom-made collector of the notifier receive notifications of this disabled scope,
 /// allowing some handling of the notification into the collector before it gets dropped by the notifier.
+///
+/// ### Resumed subscriptions
+///
+/// A `VirtualChainChanged` or `UtxosChanged` scope may carry a [`ResumeToken`]. A notifier having a replayer
+/// (see [`Notifier::with_replayer`]) then sends the listener the notifications it missed since that position
+/// before the live ones. A notifier without replayer forwards the token to its parent along with the compounded
+/// subscription.
 #[derive(Debug)]
 pub struct Notifier<N, C>
 where
@@ -155,6 +163,15 @@ where
         }
     }
 
+    /// Sets the replayer providing the missed notifications of the subscriptions resumed through
+    /// the async [`SubscriptionManager::start_notify`].
+    ///
+    /// Must be called before the notifier is shared.
+    pub fn with_replayer(mut self, replayer: DynReplayer<N>) -> Self {
+        Arc::get_mut(&mut self.inner).expect("the replayer is set before the notifier is shared").replayer = Some(replayer);
+        self
+    }
+
     pub fn subscription_context(&self) -> &SubscriptionContext {
         &self.inner.subscription_context
     }
@@ -215,9 +232,17 @@ where
     N: Notification,
     C: Connection<Notification = N>,
 {
-    async fn start_notify(&self, id: ListenerId, scope: Scope) -> Result<()> {
+    async fn start_notify(&self, id: ListenerId, mut scope: Scope) -> Result<()> {
         trace!("[Notifier {}] start sending to listener {} notifications of scope {:?}", self.inner.name, id, scope);
-        self.inner.start_notify(id, scope)?;
+        match (self.inner.replayer.clone(), scope.take_resume_from()) {
+            (Some(replayer), Some(resume_from)) => {
+                self.inner.start_notify_with_replay(id, scope, resume_from, replayer).await?;
+            }
+            (_, resume_from) => {
+                scope.set_resume_from(resume_from);
+                self.inner.start_notify(id, scope)?;
+            }
+        }
         Ok(())
     }
 
@@ -264,6 +289,9 @@ where
     /// Subscription context
     subscription_context: SubscriptionContext,
 
+    /// Provider of the notifications missed by the listeners resuming a subscription
+    replayer: Option<DynReplayer<N>>,
+
     /// Mutation policies
     policies: MutationPolicies,
 
@@ -324,6 +352,7 @@ where
             subscribers,
             enabled_subscriber,
             subscription_context,
+            replayer: None,
             policies,
             name,
             _sync,
@@ -373,7 +402,7 @@ where
                 .filter_map(|subscription| if subscription.active() { Some(subscription.event_type()) } else { None })
                 .collect_vec();
             events.drain(..).for_each(|event| {
-                let _ = self.execute_subscribe_command_impl(id, &mut listener, event.into(), Command::Stop);
+                let _ = self.execute_subscribe_command_impl(id, &mut listener, event.into(), Command::Stop, None);
             });
 
             // Close the listener
@@ -385,12 +414,17 @@ where
         Ok(())
     }
 
-    pub fn execute_subscribe_command(&self, id: ListenerId, scope: Scope, command: Command) -> Result<()> {
+    pub fn execute_subscribe_command(&self, id: ListenerId, mut scope: Scope, command: Command) -> Result<()> {
+        if command == Command::Start {
+            scope.validate()?;
+        }
         let event = scope.event_type();
+        // The resume token is no part of the listener subscription
+        let resume_from = scope.take_resume_from().filter(|_| command == Command::Start);
         if self.enabled_events[event] {
             let mut listeners = self.listeners.lock();
             if let Some(listener) = listeners.get_mut(&id) {
-                self.execute_subscribe_command_impl(id, listener, scope, command)?;
+                self.execute_subscribe_command_impl(id, listener, scope, command, resume_from)?;
             } else {
                 trace!("[Notifier {}] {command} notifying listener {id} about {scope} error: listener id not found", self.name);
             }
@@ -407,6 +441,7 @@ where
         listener: &mut Listener<C>,
         scope: Scope,
         command: Command,
+        resume_from: Option<ResumeToken>,
     ) -> Result<()> {
         let mut sync_feedback: bool = false;
         let event = scope.event_type();
@@ -433,7 +468,7 @@ where
                     self.broadcasters.iter().try_for_each(|broadcaster| broadcaster.unregister(event, id))?;
                 }
             }
-            self.apply_mutations(event, outcome.mutations, &self.subscription_context)?;
+            self.apply_mutations(event, outcome.mutations, resume_from, &self.subscription_context)?;
         } else {
             trace!("[Notifier {}] {command} notifying listener {id} about {scope_trace} is ignored (no mutation)", self.name);
             sync_feedback = true;
@@ -448,7 +483,13 @@ where
         Ok(())
     }
 
-    fn apply_mutations(&self, event: EventType, mutations: Vec<Mutation>, context: &SubscriptionContext) -> Result<()> {
+    fn apply_mutations(
+        &self,
+        event: EventType,
+        mutations: Vec<Mutation>,
+        resume_from: Option<ResumeToken>,
+        context: &SubscriptionContext,
+    ) -> Result<()> {
         let mut subscriptions = self.subscriptions.lock();
         // Compound mutations
         let mut compound_result = None;
@@ -456,8 +497,12 @@ where
             compound_result = subscriptions[event].compound(mutation, context);
         }
         // Report to the parent if any
-        if let Some(mutation) = compound_result {
+        if let Some(mut mutation) = compound_result {
             if let Some(ref subscriber) = self.enabled_subscriber[event] {
+                // Let the parent replay the missed notifications when this notifier cannot
+                if mutation.active() && self.replayer.is_none() {
+                    mutation.scope.set_resume_from(resume_from);
+                }
                 subscriber.mutate(mutation)?;
             }
         }
@@ -468,6 +513,64 @@ where
         self.execute_subscribe_command(id, scope, Command::Start)
     }
 
+    /// Starts the subscription of a listener, sending it first the notifications it missed since `resume_from`.
+    ///
+    /// The live notifications are held back by the broadcasters until the replayed ones are sent. If the missed
+    /// notifications cannot be provided, or if too many live ones arrive meanwhile, the subscription goes on with
+    /// the live notifications and the gap is reported in the returned error.
+    async fn start_notify_with_replay(
+        &self,
+        id: ListenerId,
+        scope: Scope,
+        resume_from: ResumeToken,
+        replayer: DynReplayer<N>,
+    ) -> Result<()> {
+        let event = scope.event_type();
+        let progress = Arc::new(ReplayProgress::default());
+        self.broadcasters.iter().try_for_each(|broadcaster| broadcaster.hold(event, id, progress.clone()))?;
+        if let Err(err) = self.start_notify(id, scope.clone()) {
+            self.release(event, id, vec![], progress).await?;
+            return Err(err);
+        }
+        match replayer.replay(&scope, &resume_from).await {
+            Ok(replayed) => {
+                debug!("[Notifier {}] replaying {} notifications since {resume_from} to listener {id}", self.name, replayed.len());
+                if self.release(event, id, replayed, progress).await? {
+                    warn!("[Notifier {}] resuming {scope} for listener {id} was given up: too many live notifications", self.name);
+                    return Err(Error::ResumeGap(
+                        resume_from.to_string(),
+                        "too many live notifications arrived during the replay".into(),
+                    ));
+                }
+                Ok(())
+            }
+            Err(err) => {
+                warn!("[Notifier {}] resuming {scope} for listener {id} failed: {err}", self.name);
+                self.release(event, id, vec![], progress).await?;
+                Err(err)
+            }
+        }
+    }
+
+    /// Releases the notifications held back for a listener, sending it first `replayed`.
+    ///
+    /// The broadcasters compete for the live notifications, so the replayed ones are first sent by a single broadcaster
+    /// while all of them keep holding the live ones back, which are only released once the replay is done.
+    ///
+    /// Returns `true` if some broadcaster had to give the replay up.
+    async fn release(&self, event: EventType, id: ListenerId, replayed: Vec<N>, progress: Arc<ReplayProgress>) -> Result<bool> {
+        // A broadcaster signals it is done by sending on its copy of the sender or by dropping it
+        let (done_sender, done_receiver) = async_channel::unbounded();
+        self.broadcasters[0].replay(event, id, Arc::new(replayed), progress.clone(), done_sender)?;
+        let _ = done_receiver.recv().await;
+
+        let (done_sender, done_receiver) = async_channel::unbounded();
+        self.broadcasters.iter().try_for_each(|broadcaster| broadcaster.release(event, id, done_sender.clone()))?;
+        drop(done_sender);
+        while done_receiver.recv().await.is_ok() {}
+        Ok(progress.is_given_up())
+    }
+
     fn notify(&self, notification: N) -> Result<()> {
         if self.enabled_events[notification.event_type()] {
             self.notification_channel.try_send(notification)?;
@@ -597,10 +700,13 @@ pub mod test_helpers {
 
     pub fn overall_test_steps(listener_id: ListenerId) -> Vec<Step> {
         fn m(command: Command) -> Option<Mutation> {
-            Some(Mutation { command, scope: Scope::BlockAdded(BlockAddedScope {}) })
+            Some(Mutation { command, scope: Scope::BlockAdded(BlockAddedScope::default()) })
         }
         let s = |command: Command| -> Option<SubscriptionMessage> {
-            Some(SubscriptionMessage { listener_id, mutation: Mutation { command, scope: Scope::BlockAdded(BlockAddedScope {}) } })
+            Some(SubscriptionMessage {
+                listener_id,
+                mutation: Mutation { command, scope: Scope::BlockAdded(BlockAddedScope::default()) },
+            })
         };
         fn n() -> TestNotification {
             TestNotification::BlockAdded(BlockAddedNotification::default())
@@ -0,0 +1,25 @@
+use crate::{
+    error::Result,
+    notification::Notification,
+    scope::{ResumeToken, Scope},
+};
+use async_trait::async_trait;
+use core::fmt::Debug;
+use std::sync::Arc;
+
+/// A provider of the notifications a listener missed, used by a [`Notifier`](crate::notifier::Notifier)
+/// to resume the subscriptions carrying a [`ResumeToken`].
+#[async_trait]
+pub trait Replayer<N>: Send + Sync + Debug
+where
+    N: Notification,
+{
+    /// Returns, in order, the notifications of `scope` raised since `resume_from`.
+    ///
+    /// The notifications are not expected to be reduced to the scope, the notifier applying the subscription
+    /// of the listener to each of them. Returns [`Error::ResumeGap`](crate::error::Error::ResumeGap) when some
+    /// of the notifications can no longer be provided.
+    async fn replay(&self, scope: &Scope, resume_from: &ResumeToken) -> Result<Vec<N>>;
+}
+
+pub type DynReplayer<N> = Arc<dyn Replayer<N>>;
@@ -1,10 +1,35 @@
-use super::events::EventType;
+use super::{
+    error::Error,
+    events::{EventType, EVENT_TYPE_ARRAY},
+};
 use borsh::{BorshDeserialize, BorshSerialize};
 use derive_more::Display;
 use vecno_addresses::Address;
+use vecno_consensus_core::tx::TransactionId;
+use vecno_hashes::Hash;
 use serde::{Deserialize, Serialize};
 use workflow_serializer::prelude::*;
 
+/// Maximum number of addresses a [`TransactionFilter`] may hold
+pub const MAX_FILTER_ADDRESSES: usize = 1_000;
+
+/// Maximum size, in bytes, of the payload prefix of a [`TransactionFilter`]
+pub const MAX_FILTER_PAYLOAD_PREFIX_SIZE: usize = 256;
+
+/// Maximum number of transaction ids a subscription scope may select
+pub const MAX_SCOPE_TRANSACTION_IDS: usize = 10_000;
+
+fn check_limit(what: &'static str, count: usize, max: usize) -> crate::error::Result<()> {
+    match count > max {
+        true => Err(Error::ScopeLimitExceeded(what, count, max)),
+        false => Ok(()),
+    }
+}
+
+fn invalid_data(err: Error) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, err.to_string())
+}
+
 macro_rules! scope_enum {
     ($(#[$meta:meta])* $vis:vis enum $name:ident {
     $($(#[$variant_meta:meta])* $variant_name:ident,)*
@@ -45,6 +70,7 @@ pub enum Scope {
     VirtualDaaScoreChanged,
     PruningPointUtxoSetOverride,
     NewBlockTemplate,
+    TransactionStatusChanged,
 }
 }
 
@@ -52,70 +78,334 @@ impl Scope {
     pub fn event_type(&self) -> EventType {
         self.into()
     }
+
+    /// Checks that the selection criteria of the scope do not exceed their maximum sizes
+    pub fn validate(&self) -> crate::error::Result<()> {
+        match self {
+            Scope::BlockAdded(scope) => scope.filter.validate(),
+            Scope::VirtualChainChanged(scope) => scope.validate(),
+            Scope::TransactionStatusChanged(scope) => scope.validate(),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the position the notifications of this scope are requested to be resumed from, if any
+    pub fn resume_from(&self) -> Option<&ResumeToken> {
+        match self {
+            Scope::VirtualChainChanged(scope) => scope.resume_from.as_ref(),
+            Scope::UtxosChanged(scope) => scope.resume_from.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Removes the resume token from the scope, returning it
+    pub fn take_resume_from(&mut self) -> Option<ResumeToken> {
+        match self {
+            Scope::VirtualChainChanged(scope) => scope.resume_from.take(),
+            Scope::UtxosChanged(scope) => scope.resume_from.take(),
+            _ => None,
+        }
+    }
+
+    /// Sets the resume token of the scope, which is ignored by the scopes not supporting any
+    pub fn set_resume_from(&mut self, resume_from: Option<ResumeToken>) {
+        match self {
+            Scope::VirtualChainChanged(scope) => scope.resume_from = resume_from,
+            Scope::UtxosChanged(scope) => scope.resume_from = resume_from,
+            _ => {}
+        }
+    }
 }
 
 impl Serializer for Scope {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &2, writer)?;
+        // Each scope is written with its own versioned layout so its fields can evolve
+        store!(u16, &(self.event_type() as u16), writer)?;
+        match self {
+            Scope::BlockAdded(scope) => serialize!(BlockAddedScope, scope, writer),
+            Scope::VirtualChainChanged(scope) => serialize!(VirtualChainChangedScope, scope, writer),
+            Scope::FinalityConflict(scope) => serialize!(FinalityConflictScope, scope, writer),
+            Scope::FinalityConflictResolved(scope) => serialize!(FinalityConflictResolvedScope, scope, writer),
+            Scope::UtxosChanged(scope) => serialize!(UtxosChangedScope, scope, writer),
+            Scope::SinkBlueScoreChanged(scope) => serialize!(SinkBlueScoreChangedScope, scope, writer),
+            Scope::VirtualDaaScoreChanged(scope) => serialize!(VirtualDaaScoreChangedScope, scope, writer),
+            Scope::PruningPointUtxoSetOverride(scope) => serialize!(PruningPointUtxoSetOverrideScope, scope, writer),
+            Scope::NewBlockTemplate(scope) => serialize!(NewBlockTemplateScope, scope, writer),
+            Scope::TransactionStatusChanged(scope) => serialize!(TransactionStatusChangedScope, scope, writer),
+        }
+    }
+}
+
+impl Deserializer for Scope {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let version = load!(u16, reader)?;
+        // Version 1 is the plain borsh encoding of the scopes, indexed by a single byte
+        let index = if version > 1 { load!(u16, reader)? } else { load!(u8, reader)? as u16 };
+        let event_type = *EVENT_TYPE_ARRAY.get(index as usize).ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::InvalidData, format!("invalid subscription scope index {index}"))
+        })?;
+        if version > 1 {
+            return Ok(match event_type {
+                EventType::BlockAdded => deserialize!(BlockAddedScope, reader)?.into(),
+                EventType::VirtualChainChanged => deserialize!(VirtualChainChangedScope, reader)?.into(),
+                EventType::FinalityConflict => deserialize!(FinalityConflictScope, reader)?.into(),
+                EventType::FinalityConflictResolved => deserialize!(FinalityConflictResolvedScope, reader)?.into(),
+                EventType::UtxosChanged => deserialize!(UtxosChangedScope, reader)?.into(),
+                EventType::SinkBlueScoreChanged => deserialize!(SinkBlueScoreChangedScope, reader)?.into(),
+                EventType::VirtualDaaScoreChanged => deserialize!(VirtualDaaScoreChangedScope, reader)?.into(),
+                EventType::PruningPointUtxoSetOverride => deserialize!(PruningPointUtxoSetOverrideScope, reader)?.into(),
+                EventType::NewBlockTemplate => deserialize!(NewBlockTemplateScope, reader)?.into(),
+                EventType::TransactionStatusChanged => deserialize!(TransactionStatusChangedScope, reader)?.into(),
+            });
+        }
+
+        // Legacy scopes, as they were before gaining any selection criteria
+        let scope: Scope = match event_type {
+            EventType::VirtualChainChanged => VirtualChainChangedScope::new(load!(bool, reader)?).into(),
+            EventType::UtxosChanged => UtxosChangedScope::new(load!(Vec<Address>, reader)?).into(),
+            EventType::TransactionStatusChanged => TransactionStatusChangedScope::new(load!(Vec<TransactionId>, reader)?).into(),
+            event_type => event_type.into(),
+        };
+        scope.validate().map_err(invalid_data)?;
+        Ok(scope)
+    }
+}
+
+/// Position in the notification stream from which a subscription asks to get the notifications it missed.
+///
+/// A listener reconnecting after a disconnection provides the last position it processed, either as the sink
+/// reported by its last `VirtualChainChanged` notification or as a DAA score, and gets the chain changes since
+/// then replayed before the live notifications.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum ResumeToken {
+    /// Resume after this chain block, usually the last added chain block of the last processed notification
+    SinkHash(Hash),
+    /// Resume after the chain blocks having this DAA score or a lower one
+    DaaScore(u64),
+}
+
+impl std::fmt::Display for ResumeToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResumeToken::SinkHash(hash) => write!(f, "sink {hash}"),
+            ResumeToken::DaaScore(daa_score) => write!(f, "DAA score {daa_score}"),
+        }
+    }
+}
+
+impl Serializer for ResumeToken {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
         store!(u16, &1, writer)?;
-        store!(Scope, self, writer)?;
+        store!(ResumeToken, self, writer)?;
         Ok(())
     }
 }
 
-impl Deserializer for Scope {
+impl Deserializer for ResumeToken {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
         let _version = load!(u16, reader)?;
-        load!(Scope, reader)
+        load!(ResumeToken, reader)
     }
 }
 
-#[derive(Clone, Display, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
-pub struct BlockAddedScope {}
+/// Selection of the transactions of interest in block notifications.
+///
+/// A transaction matches when one of its outputs pays to any of `addresses`, when its payload starts
+/// with `payload_prefix` or when its id is in `transaction_ids`. An empty filter selects everything.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionFilter {
+    #[serde(default)]
+    pub addresses: Vec<Address>,
+    #[serde(default, with = "vecno_utils::serde_bytes")]
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
+    pub payload_prefix: Vec<u8>,
+    #[serde(default)]
+    pub transaction_ids: Vec<TransactionId>,
+}
 
-impl Serializer for BlockAddedScope {
+impl TransactionFilter {
+    pub fn new(addresses: Vec<Address>, payload_prefix: Vec<u8>, transaction_ids: Vec<TransactionId>) -> Self {
+        Self { addresses, payload_prefix, transaction_ids }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.addresses.is_empty() && self.payload_prefix.is_empty() && self.transaction_ids.is_empty()
+    }
+
+    /// Checks that the criteria of the filter do not exceed their maximum sizes
+    pub fn validate(&self) -> crate::error::Result<()> {
+        check_limit("addresses", self.addresses.len(), MAX_FILTER_ADDRESSES)?;
+        check_limit("payload prefix bytes", self.payload_prefix.len(), MAX_FILTER_PAYLOAD_PREFIX_SIZE)?;
+        check_limit("transaction ids", self.transaction_ids.len(), MAX_SCOPE_TRANSACTION_IDS)
+    }
+}
+
+impl std::fmt::Display for TransactionFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return write!(f, "all");
+        }
+        let mut criteria = vec![];
+        if !self.addresses.is_empty() {
+            criteria.push(format!("{} addresses", self.addresses.len()));
+        }
+        if !self.payload_prefix.is_empty() {
+            criteria.push(format!("payload prefix of {} bytes", self.payload_prefix.len()));
+        }
+        if !self.transaction_ids.is_empty() {
+            criteria.push(format!("{} transaction ids", self.transaction_ids.len()));
+        }
+        write!(f, "{}", criteria.join(", "))
+    }
+}
+
+impl Serializer for TransactionFilter {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
         store!(u16, &1, writer)?;
+        store!(Vec<Address>, &self.addresses, writer)?;
+        store!(Vec<u8>, &self.payload_prefix, writer)?;
+        store!(Vec<TransactionId>, &self.transaction_ids, writer)?;
         Ok(())
     }
 }
 
-impl Deserializer for BlockAddedScope {
+impl Deserializer for TransactionFilter {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
         let _version = load!(u16, reader)?;
-        Ok(Self {})
+        let addresses = load!(Vec<Address>, reader)?;
+        let payload_prefix = load!(Vec<u8>, reader)?;
+        let transaction_ids = load!(Vec<TransactionId>, reader)?;
+        let filter = Self { addresses, payload_prefix, transaction_ids };
+        filter.validate().map_err(invalid_data)?;
+        Ok(filter)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub struct BlockAddedScope {
+    /// Restricts the notifications to the blocks holding a matching transaction, reduced to the matching transactions
+    #[serde(default)]
+    pub filter: TransactionFilter,
+
+    /// Strips the transactions from the notified blocks
+    #[serde(default)]
+    pub header_only: bool,
+}
+
+impl BlockAddedScope {
+    pub fn new(filter: TransactionFilter, header_only: bool) -> Self {
+        Self { filter, header_only }
+    }
+}
+
+impl std::fmt::Display for BlockAddedScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "BlockAddedScope")?;
+        if !self.filter.is_empty() {
+            write!(f, " ({})", self.filter)?;
+        }
+        if self.header_only {
+            write!(f, " header only")?;
+        }
+        Ok(())
+    }
+}
+
+impl Serializer for BlockAddedScope {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &2, writer)?;
+        serialize!(TransactionFilter, &self.filter, writer)?;
+        store!(bool, &self.header_only, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for BlockAddedScope {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let version = load!(u16, reader)?;
+        if version > 1 {
+            let filter = deserialize!(TransactionFilter, reader)?;
+            let header_only = load!(bool, reader)?;
+            Ok(Self { filter, header_only })
+        } else {
+            Ok(Self::default())
+        }
     }
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 pub struct VirtualChainChangedScope {
     pub include_accepted_transaction_ids: bool,
+
+    /// Restricts the reported accepted transactions to this set, all of them if empty
+    #[serde(default)]
+    pub transaction_ids: Vec<TransactionId>,
+
+    /// Replays the chain changes missed since this position before the live notifications
+    #[serde(default)]
+    pub resume_from: Option<ResumeToken>,
 }
 
 impl VirtualChainChangedScope {
     pub fn new(include_accepted_transaction_ids: bool) -> Self {
-        Self { include_accepted_transaction_ids }
+        Self { include_accepted_transaction_ids, transaction_ids: vec![], resume_from: None }
+    }
+
+    pub fn with_transaction_ids(include_accepted_transaction_ids: bool, transaction_ids: Vec<TransactionId>) -> Self {
+        Self { include_accepted_transaction_ids, transaction_ids, resume_from: None }
+    }
+
+    pub fn with_resume_from(self, resume_from: Option<ResumeToken>) -> Self {
+        Self { resume_from, ..self }
+    }
+
+    /// Checks that the selected transactions do not exceed their maximum count
+    pub fn validate(&self) -> crate::error::Result<()> {
+        check_limit("transaction ids", self.transaction_ids.len(), MAX_SCOPE_TRANSACTION_IDS)
     }
 }
 
 impl std::fmt::Display for VirtualChainChangedScope {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "VirtualChainChangedScope{}", if self.include_accepted_transaction_ids { " with accepted transactions" } else { "" })
+        write!(
+            f,
+            "VirtualChainChangedScope{}",
+            if self.include_accepted_transaction_ids { " with accepted transactions" } else { "" }
+        )?;
+        if !self.transaction_ids.is_empty() {
+            write!(f, " ({} transaction ids)", self.transaction_ids.len())?;
+        }
+        if let Some(ref resume_from) = self.resume_from {
+            write!(f, " resumed from {resume_from}")?;
+        }
+        Ok(())
     }
 }
 
 impl Serializer for VirtualChainChangedScope {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &3, writer)?;
         store!(bool, &self.include_accepted_transaction_ids, writer)?;
+        store!(Vec<TransactionId>, &self.transaction_ids, writer)?;
+        store!(Option<ResumeToken>, &self.resume_from, writer)?;
         Ok(())
     }
 }
 
 impl Deserializer for VirtualChainChangedScope {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let version = load!(u16, reader)?;
         let include_accepted_transaction_ids = load!(bool, reader)?;
-        Ok(Self { include_accepted_transaction_ids })
+        let transaction_ids = if version > 1 { load!(Vec<TransactionId>, reader)? } else { vec![] };
+        let resume_from = if version > 2 { load!(Option<ResumeToken>, reader)? } else { None };
+        let scope = Self { include_accepted_transaction_ids, transaction_ids, resume_from };
+        scope.validate().map_err(invalid_data)?;
+        Ok(scope)
     }
 }
 
@@ -156,6 +446,10 @@ impl Deserializer for FinalityConflictResolvedScope {
 #[derive(Clone, Debug, Default, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 pub struct UtxosChangedScope {
     pub addresses: Vec<Address>,
+
+    /// Replays the UTXO changes missed since this position before the live notifications
+    #[serde(default)]
+    pub resume_from: Option<ResumeToken>,
 }
 
 impl std::fmt::Display for UtxosChangedScope {
@@ -165,7 +459,11 @@ impl std::fmt::Display for UtxosChangedScope {
             1 => format!("{}", self.addresses[0]),
             n => format!("{} addresses", n),
         };
-        write!(f, "UtxosChangedScope ({})", addresses)
+        write!(f, "UtxosChangedScope ({})", addresses)?;
+        if let Some(ref resume_from) = self.resume_from {
+            write!(f, " resumed from {resume_from}")?;
+        }
+        Ok(())
     }
 }
 
@@ -179,23 +477,29 @@ impl Eq for UtxosChangedScope {}
 
 impl UtxosChangedScope {
     pub fn new(addresses: Vec<Address>) -> Self {
-        Self { addresses }
+        Self { addresses, resume_from: None }
+    }
+
+    pub fn with_resume_from(self, resume_from: Option<ResumeToken>) -> Self {
+        Self { resume_from, ..self }
     }
 }
 
 impl Serializer for UtxosChangedScope {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(Vec<Address>, &self.addresses, writer)?;
+        store!(Option<ResumeToken>, &self.resume_from, writer)?;
         Ok(())
     }
 }
 
 impl Deserializer for UtxosChangedScope {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let version = load!(u16, reader)?;
         let addresses = load!(Vec<Address>, reader)?;
-        Ok(Self { addresses })
+        let resume_from = if version > 1 { load!(Option<ResumeToken>, reader)? } else { None };
+        Ok(Self { addresses, resume_from })
     }
 }
 
@@ -266,3 +570,106 @@ impl Deserializer for NewBlockTemplateScope {
         Ok(Self {})
     }
 }
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub struct TransactionStatusChangedScope {
+    /// Restricts the notifications to the status changes of these transactions, all of them if empty
+    #[serde(default)]
+    pub transaction_ids: Vec<TransactionId>,
+}
+
+impl TransactionStatusChangedScope {
+    pub fn new(transaction_ids: Vec<TransactionId>) -> Self {
+        Self { transaction_ids }
+    }
+
+    /// Checks that the selected transactions do not exceed their maximum count
+    pub fn validate(&self) -> crate::error::Result<()> {
+        check_limit("transaction ids", self.transaction_ids.len(), MAX_SCOPE_TRANSACTION_IDS)
+    }
+}
+
+impl std::fmt::Display for TransactionStatusChangedScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let transaction_ids = match self.transaction_ids.len() {
+            0 => "all".to_string(),
+            1 => format!("{}", self.transaction_ids[0]),
+            n => format!("{} transaction ids", n),
+        };
+        write!(f, "TransactionStatusChangedScope ({})", transaction_ids)
+    }
+}
+
+impl Serializer for TransactionStatusChangedScope {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(Vec<TransactionId>, &self.transaction_ids, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for TransactionStatusChangedScope {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction_ids = load!(Vec<TransactionId>, reader)?;
+        let scope = Self { transaction_ids };
+        scope.validate().map_err(invalid_data)?;
+        Ok(scope)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scope_limits() {
+        let transaction_ids = |count: u64| (0..count).map(TransactionId::from_u64_word).collect::<Vec<_>>();
+        let filter = TransactionFilter::new(vec![], vec![0; MAX_FILTER_PAYLOAD_PREFIX_SIZE], transaction_ids(10));
+        assert!(Scope::from(BlockAddedScope::new(filter, false)).validate().is_ok());
+
+        let filter = TransactionFilter::new(vec![], vec![0; MAX_FILTER_PAYLOAD_PREFIX_SIZE + 1], vec![]);
+        assert!(matches!(filter.validate(), Err(Error::ScopeLimitExceeded("payload prefix bytes", _, _))));
+        let scope = VirtualChainChangedScope::with_transaction_ids(true, transaction_ids(MAX_SCOPE_TRANSACTION_IDS as u64 + 1));
+        assert!(matches!(Scope::from(scope.clone()).validate(), Err(Error::ScopeLimitExceeded("transaction ids", _, _))));
+        let status_scope = TransactionStatusChangedScope::new(transaction_ids(MAX_SCOPE_TRANSACTION_IDS as u64 + 1));
+        assert!(matches!(Scope::from(status_scope).validate(), Err(Error::ScopeLimitExceeded("transaction ids", _, _))));
+
+        // Oversized scopes are refused when deserialized
+        let mut bytes = vec![];
+        Serializer::serialize(&scope, &mut bytes).unwrap();
+        assert!(<VirtualChainChangedScope as Deserializer>::deserialize(&mut bytes.as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_scope_serialization_versions() {
+        let filter = TransactionFilter::new(vec![], vec![1, 2], vec![TransactionId::from_u64_word(1)]);
+        let resume_from = Some(ResumeToken::DaaScore(7));
+        let scopes: Vec<Scope> = vec![
+            BlockAddedScope::new(filter, true).into(),
+            VirtualChainChangedScope::with_transaction_ids(true, vec![TransactionId::from_u64_word(2)])
+                .with_resume_from(resume_from.clone())
+                .into(),
+            UtxosChangedScope::new(vec![]).with_resume_from(resume_from).into(),
+            TransactionStatusChangedScope::new(vec![TransactionId::from_u64_word(3)]).into(),
+            EventType::NewBlockTemplate.into(),
+        ];
+        for scope in scopes {
+            let mut bytes = vec![];
+            Serializer::serialize(&scope, &mut bytes).unwrap();
+            let deserialized = <Scope as Deserializer>::deserialize(&mut bytes.as_slice()).unwrap();
+            assert_eq!(scope, deserialized);
+            assert_eq!(scope.resume_from(), deserialized.resume_from());
+        }
+
+        // Scopes encoded by the clients predating the scope selection criteria
+        let legacy = |scope: &[u8]| {
+            let bytes = [&1u16.to_le_bytes(), scope].concat();
+            <Scope as Deserializer>::deserialize(&mut bytes.as_slice()).unwrap()
+        };
+        assert_eq!(legacy(&[0]), Scope::BlockAdded(BlockAddedScope::default()));
+        assert_eq!(legacy(&[1, 1]), Scope::VirtualChainChanged(VirtualChainChangedScope::new(true)));
+        assert_eq!(legacy(&[4, 0, 0, 0, 0]), Scope::UtxosChanged(UtxosChangedScope::new(vec![])));
+        assert_eq!(legacy(&[8]), Scope::NewBlockTemplate(NewBlockTemplateScope {}));
+    }
+}
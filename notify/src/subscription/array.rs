@@ -12,12 +12,14 @@ impl ArrayBuilder {
         EventArray::from_fn(|i| {
             let event_type = EventType::try_from(i).unwrap();
             let subscription: DynSubscription = match event_type {
+                EventType::BlockAdded => Arc::<single::BlockAddedSubscription>::default(),
                 EventType::VirtualChainChanged => Arc::<single::VirtualChainChangedSubscription>::default(),
                 EventType::UtxosChanged => Arc::new(single::UtxosChangedSubscription::with_capacity(
                     single::UtxosChangedState::None,
                     listener_id,
                     utxos_changed_capacity.unwrap_or_default(),
                 )),
+                EventType::TransactionStatusChanged => Arc::<single::TransactionStatusChangedSubscription>::default(),
                 _ => Arc::new(single::OverallSubscription::new(event_type, false)),
             };
             subscription
@@ -26,14 +26,15 @@ impl Compounded for OverallSubscription {
             Command::Start => {
                 self.active += 1;
                 if self.active == 1 {
-                    return Some(mutation);
+                    // Filters of the scope are applied downstream, so the upstream subscription always covers the whole event
+                    return Some(Mutation::new(Command::Start, self.event_type.into()));
                 }
             }
             Command::Stop => {
                 assert!(self.active > 0);
                 self.active -= 1;
                 if self.active == 0 {
-                    return Some(mutation);
+                    return Some(Mutation::new(Command::Stop, self.event_type.into()));
                 }
             }
         }
@@ -289,8 +290,8 @@ mod tests {
     #[allow(clippy::redundant_clone)]
     fn test_overall_compounding() {
         let none = || Box::new(OverallSubscription::new(EventType::BlockAdded));
-        let add = || Mutation::new(Command::Start, Scope::BlockAdded(BlockAddedScope {}));
-        let remove = || Mutation::new(Command::Stop, Scope::BlockAdded(BlockAddedScope {}));
+        let add = || Mutation::new(Command::Start, Scope::BlockAdded(BlockAddedScope::default()));
+        let remove = || Mutation::new(Command::Stop, Scope::BlockAdded(BlockAddedScope::default()));
         let test = Test {
             name: "OverallSubscription 0 to 2 to 0",
             context: SubscriptionContext::new(),
@@ -314,7 +315,7 @@ mod tests {
     #[allow(clippy::redundant_clone)]
     fn test_virtual_chain_changed_compounding() {
         fn m(command: Command, include_accepted_transaction_ids: bool) -> Mutation {
-            Mutation { command, scope: Scope::VirtualChainChanged(VirtualChainChangedScope { include_accepted_transaction_ids }) }
+            Mutation { command, scope: Scope::VirtualChainChanged(VirtualChainChangedScope::new(include_accepted_transaction_ids)) }
         }
         let none = Box::<VirtualChainChangedSubscription>::default;
         let add_all = || m(Command::Start, true);
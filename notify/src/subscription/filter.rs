@@ -0,0 +1,117 @@
+use crate::scope::TransactionFilter;
+use vecno_consensus_core::tx::{ScriptPublicKey, TransactionId};
+use vecno_txscript::pay_to_address_script;
+use std::{
+    collections::HashSet,
+    hash::{Hash, Hasher},
+};
+
+/// A [`TransactionFilter`] prepared for matching transactions.
+///
+/// The addresses are converted once into the script public keys they are paid to. Equality and hash
+/// are fully determined by the source filter.
+#[derive(Clone, Debug, Default)]
+pub struct TransactionMatcher {
+    filter: TransactionFilter,
+    script_public_keys: HashSet<ScriptPublicKey>,
+    transaction_ids: HashSet<TransactionId>,
+}
+
+impl TransactionMatcher {
+    pub fn new(filter: TransactionFilter) -> Self {
+        let script_public_keys = filter.addresses.iter().map(pay_to_address_script).collect();
+        let transaction_ids = filter.transaction_ids.iter().copied().collect();
+        Self { filter, script_public_keys, transaction_ids }
+    }
+
+    #[inline(always)]
+    pub fn filter(&self) -> &TransactionFilter {
+        &self.filter
+    }
+
+    /// Returns true if the matcher selects all transactions
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.filter.is_empty()
+    }
+
+    pub fn matches_transaction_id(&self, transaction_id: &TransactionId) -> bool {
+        self.transaction_ids.contains(transaction_id)
+    }
+
+    pub fn matches_script_public_key(&self, script_public_key: &ScriptPublicKey) -> bool {
+        self.script_public_keys.contains(script_public_key)
+    }
+
+    pub fn matches_payload(&self, payload: &[u8]) -> bool {
+        !self.filter.payload_prefix.is_empty() && payload.starts_with(&self.filter.payload_prefix)
+    }
+
+    /// Returns true if a transaction with these properties matches, computing its id lazily since
+    /// the id criterion is the last one evaluated
+    pub fn matches<'a>(
+        &self,
+        transaction_id: impl FnOnce() -> Option<TransactionId>,
+        mut script_public_keys: impl Iterator<Item = &'a ScriptPublicKey>,
+        payload: &[u8],
+    ) -> bool {
+        self.is_empty()
+            || self.matches_payload(payload)
+            || script_public_keys.any(|spk| self.matches_script_public_key(spk))
+            || (!self.transaction_ids.is_empty() && transaction_id().is_some_and(|id| self.matches_transaction_id(&id)))
+    }
+}
+
+impl From<TransactionFilter> for TransactionMatcher {
+    fn from(filter: TransactionFilter) -> Self {
+        Self::new(filter)
+    }
+}
+
+impl PartialEq for TransactionMatcher {
+    fn eq(&self, other: &Self) -> bool {
+        self.filter == other.filter
+    }
+}
+
+impl Eq for TransactionMatcher {}
+
+impl Hash for TransactionMatcher {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.filter.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::address::test_helpers::get_3_addresses;
+    use vecno_hashes::Hash as TransactionHash;
+
+    #[test]
+    fn test_transaction_matcher() {
+        let addresses = get_3_addresses(false);
+        let spk = |i: usize| pay_to_address_script(&addresses[i]);
+        let id = TransactionHash::from_u64_word(7);
+
+        let empty = TransactionMatcher::default();
+        assert!(empty.matches(|| None, std::iter::empty(), &[]));
+
+        let by_address = TransactionMatcher::new(TransactionFilter::new(addresses[0..2].to_vec(), vec![], vec![]));
+        assert!(by_address.matches(|| None, [spk(2), spk(1)].iter(), &[]));
+        assert!(!by_address.matches(|| None, [spk(2)].iter(), &[]));
+
+        let by_payload = TransactionMatcher::new(TransactionFilter::new(vec![], vec![1, 2], vec![]));
+        assert!(by_payload.matches(|| None, std::iter::empty(), &[1, 2, 3]));
+        assert!(!by_payload.matches(|| None, std::iter::empty(), &[1]));
+        assert!(!by_payload.matches(|| None, std::iter::empty(), &[]));
+
+        let by_id = TransactionMatcher::new(TransactionFilter::new(vec![], vec![], vec![id]));
+        assert!(by_id.matches(|| Some(id), [spk(0)].iter(), &[1]));
+        assert!(!by_id.matches(|| Some(TransactionHash::from_u64_word(8)), std::iter::empty(), &[]));
+        assert!(!by_id.matches(|| None, std::iter::empty(), &[]));
+
+        assert_eq!(by_id, TransactionMatcher::from(TransactionFilter::new(vec![], vec![], vec![id])));
+        assert_ne!(by_id, by_payload);
+    }
+}
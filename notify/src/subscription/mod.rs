@@ -13,9 +13,11 @@ use std::{
 pub mod array;
 pub mod compounded;
 pub mod context;
+pub mod filter;
 pub mod single;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[borsh(use_discriminant = true)]
 pub enum Command {
     Start = 0,
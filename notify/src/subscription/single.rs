@@ -3,15 +3,15 @@ use crate::{
     error::Result,
     events::EventType,
     listener::ListenerId,
-    scope::{Scope, UtxosChangedScope, VirtualChainChangedScope},
+    scope::{BlockAddedScope, Scope, TransactionFilter, TransactionStatusChangedScope, UtxosChangedScope, VirtualChainChangedScope},
     subscription::{
-        context::SubscriptionContext, BroadcastingSingle, Command, DynSubscription, Mutation, MutationOutcome, MutationPolicies,
-        Single, Subscription, UtxosChangedMutationPolicy,
+        context::SubscriptionContext, filter::TransactionMatcher, BroadcastingSingle, Command, DynSubscription, Mutation,
+        MutationOutcome, MutationPolicies, Single, Subscription, UtxosChangedMutationPolicy,
     },
 };
 use itertools::Itertools;
 use vecno_addresses::{Address, Prefix};
-use vecno_consensus_core::tx::ScriptPublicKey;
+use vecno_consensus_core::tx::{ScriptPublicKey, TransactionId};
 use vecno_core::trace;
 use parking_lot::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 use std::{
@@ -73,20 +73,117 @@ impl Subscription for OverallSubscription {
     }
 }
 
+/// Subscription to BlockAdded notifications
+#[derive(Eq, PartialEq, Hash, Clone, Debug, Default)]
+pub struct BlockAddedSubscription {
+    active: bool,
+    filter: Arc<TransactionMatcher>,
+    header_only: bool,
+}
+
+impl BlockAddedSubscription {
+    pub fn new(active: bool, filter: TransactionFilter, header_only: bool) -> Self {
+        Self { active, filter: Arc::new(filter.into()), header_only }
+    }
+
+    pub fn filter(&self) -> &TransactionMatcher {
+        &self.filter
+    }
+
+    pub fn header_only(&self) -> bool {
+        self.header_only
+    }
+}
+
+impl Single for BlockAddedSubscription {
+    fn apply_mutation(
+        &self,
+        _: &Arc<dyn Single>,
+        mutation: Mutation,
+        _: MutationPolicies,
+        _: &SubscriptionContext,
+    ) -> Result<MutationOutcome> {
+        assert_eq!(self.event_type(), mutation.event_type());
+        let Scope::BlockAdded(scope) = mutation.scope else { return Ok(MutationOutcome::new()) };
+        // The filter is applied locally so the mutations propagated upwards carry an overall scope
+        let outcome = match (self.active, mutation.command) {
+            (false, Command::Start) => {
+                let mutated = Self::new(true, scope.filter, scope.header_only);
+                MutationOutcome::with_mutated(
+                    Arc::new(mutated),
+                    vec![Mutation::new(Command::Start, BlockAddedScope::default().into())],
+                )
+            }
+            (true, Command::Start) => {
+                let mutated = Self::new(true, scope.filter, scope.header_only);
+                if mutated != *self {
+                    // Only the filter changes, the upper levels are not affected
+                    MutationOutcome::with_mutated(Arc::new(mutated), vec![])
+                } else {
+                    MutationOutcome::new()
+                }
+            }
+            (true, Command::Stop) => MutationOutcome::with_mutated(
+                Arc::new(Self::default()),
+                vec![Mutation::new(Command::Stop, BlockAddedScope::default().into())],
+            ),
+            (false, Command::Stop) => MutationOutcome::new(),
+        };
+        Ok(outcome)
+    }
+}
+
+impl Subscription for BlockAddedSubscription {
+    #[inline(always)]
+    fn event_type(&self) -> EventType {
+        EventType::BlockAdded
+    }
+
+    #[inline(always)]
+    fn active(&self) -> bool {
+        self.active
+    }
+
+    fn scope(&self, _context: &SubscriptionContext) -> Scope {
+        BlockAddedScope::new(self.filter.filter().clone(), self.header_only).into()
+    }
+}
+
 /// Subscription to VirtualChainChanged notifications
 #[derive(Eq, PartialEq, Hash, Clone, Debug, Default)]
 pub struct VirtualChainChangedSubscription {
     active: bool,
     include_accepted_transaction_ids: bool,
+    filter: Arc<TransactionMatcher>,
 }
 
 impl VirtualChainChangedSubscription {
     pub fn new(active: bool, include_accepted_transaction_ids: bool) -> Self {
-        Self { active, include_accepted_transaction_ids }
+        Self { active, include_accepted_transaction_ids, filter: Default::default() }
     }
+
+    pub fn with_transaction_ids(active: bool, include_accepted_transaction_ids: bool, transaction_ids: Vec<TransactionId>) -> Self {
+        let filter = TransactionFilter::new(vec![], vec![], transaction_ids);
+        Self { active, include_accepted_transaction_ids, filter: Arc::new(filter.into()) }
+    }
+
     pub fn include_accepted_transaction_ids(&self) -> bool {
         self.include_accepted_transaction_ids
     }
+
+    /// Filter of the accepted transactions, holding transaction ids only
+    pub fn filter(&self) -> &TransactionMatcher {
+        &self.filter
+    }
+
+    /// Handles a mutation keeping the subscription level, which only may change the filter without affecting the upper levels
+    fn filter_mutation(&self, scope: &VirtualChainChangedScope) -> Option<(Arc<Self>, Vec<Mutation>)> {
+        (self.filter.filter().transaction_ids != scope.transaction_ids).then(|| {
+            let mutated =
+                Self::with_transaction_ids(self.active, self.include_accepted_transaction_ids, scope.transaction_ids.clone());
+            (Arc::new(mutated), vec![])
+        })
+    }
 }
 
 impl Single for VirtualChainChangedSubscription {
@@ -99,6 +196,7 @@ impl Single for VirtualChainChangedSubscription {
     ) -> Result<MutationOutcome> {
         assert_eq!(self.event_type(), mutation.event_type());
         let result = if let Scope::VirtualChainChanged(ref scope) = mutation.scope {
+            let all = scope.include_accepted_transaction_ids;
             // Here we want the code to (almost) match a double entry table structure
             // by subscription state and by mutation
             #[allow(clippy::collapsible_else_if)]
@@ -110,8 +208,8 @@ impl Single for VirtualChainChangedSubscription {
                 } else {
                     // Here is an exception to the aforementioned goal
                     // Mutations Reduced and All
-                    let mutated = Self::new(true, scope.include_accepted_transaction_ids);
-                    Some((Arc::new(mutated), vec![mutation]))
+                    let mutated = Self::with_transaction_ids(true, all, scope.transaction_ids.clone());
+                    Some((Arc::new(mutated), vec![Mutation::new(Command::Start, VirtualChainChangedScope::new(all).into())]))
                 }
             } else if !self.include_accepted_transaction_ids {
                 // State Reduced
@@ -121,13 +219,16 @@ impl Single for VirtualChainChangedSubscription {
                     Some((Arc::new(mutated), vec![Mutation::new(Command::Stop, VirtualChainChangedScope::new(false).into())]))
                 } else if !scope.include_accepted_transaction_ids {
                     // Mutation Reduced
-                    None
+                    self.filter_mutation(scope)
                 } else {
                     // Mutation All
-                    let mutated = Self::new(true, true);
+                    let mutated = Self::with_transaction_ids(true, true, scope.transaction_ids.clone());
                     Some((
                         Arc::new(mutated),
-                        vec![Mutation::new(Command::Stop, VirtualChainChangedScope::new(false).into()), mutation],
+                        vec![
+                            Mutation::new(Command::Stop, VirtualChainChangedScope::new(false).into()),
+                            Mutation::new(Command::Start, VirtualChainChangedScope::new(true).into()),
+                        ],
                     ))
                 }
             } else {
@@ -138,11 +239,17 @@ impl Single for VirtualChainChangedSubscription {
                     Some((Arc::new(mutated), vec![Mutation::new(Command::Stop, VirtualChainChangedScope::new(true).into())]))
                 } else if !scope.include_accepted_transaction_ids {
                     // Mutation Reduced
-                    let mutated = Self::new(true, false);
-                    Some((Arc::new(mutated), vec![mutation, Mutation::new(Command::Stop, VirtualChainChangedScope::new(true).into())]))
+                    let mutated = Self::with_transaction_ids(true, false, scope.transaction_ids.clone());
+                    Some((
+                        Arc::new(mutated),
+                        vec![
+                            Mutation::new(Command::Start, VirtualChainChangedScope::new(false).into()),
+                            Mutation::new(Command::Stop, VirtualChainChangedScope::new(true).into()),
+                        ],
+                    ))
                 } else {
                     // Mutation All
-                    None
+                    self.filter_mutation(scope)
                 }
             }
         } else {
@@ -168,7 +275,80 @@ impl Subscription for VirtualChainChangedSubscription {
     }
 
     fn scope(&self, _context: &SubscriptionContext) -> Scope {
-        VirtualChainChangedScope::new(self.include_accepted_transaction_ids).into()
+        VirtualChainChangedScope::with_transaction_ids(
+            self.include_accepted_transaction_ids,
+            self.filter.filter().transaction_ids.clone(),
+        )
+        .into()
+    }
+}
+
+/// Subscription to TransactionStatusChanged notifications
+#[derive(Eq, PartialEq, Hash, Clone, Debug, Default)]
+pub struct TransactionStatusChangedSubscription {
+    active: bool,
+    filter: Arc<TransactionMatcher>,
+}
+
+impl TransactionStatusChangedSubscription {
+    pub fn new(active: bool, transaction_ids: Vec<TransactionId>) -> Self {
+        let filter = TransactionFilter::new(vec![], vec![], transaction_ids);
+        Self { active, filter: Arc::new(filter.into()) }
+    }
+
+    /// Filter of the notified transactions, holding transaction ids only
+    pub fn filter(&self) -> &TransactionMatcher {
+        &self.filter
+    }
+}
+
+impl Single for TransactionStatusChangedSubscription {
+    fn apply_mutation(
+        &self,
+        _: &Arc<dyn Single>,
+        mutation: Mutation,
+        _: MutationPolicies,
+        _: &SubscriptionContext,
+    ) -> Result<MutationOutcome> {
+        assert_eq!(self.event_type(), mutation.event_type());
+        let Scope::TransactionStatusChanged(scope) = mutation.scope else { return Ok(MutationOutcome::new()) };
+        // The filter is applied locally so the mutations propagated upwards carry an overall scope
+        let outcome = match (self.active, mutation.command) {
+            (false, Command::Start) => MutationOutcome::with_mutated(
+                Arc::new(Self::new(true, scope.transaction_ids)),
+                vec![Mutation::new(Command::Start, TransactionStatusChangedScope::default().into())],
+            ),
+            (true, Command::Start) => {
+                if self.filter.filter().transaction_ids != scope.transaction_ids {
+                    // Only the filter changes, the upper levels are not affected
+                    MutationOutcome::with_mutated(Arc::new(Self::new(true, scope.transaction_ids)), vec![])
+                } else {
+                    MutationOutcome::new()
+                }
+            }
+            (true, Command::Stop) => MutationOutcome::with_mutated(
+                Arc::new(Self::default()),
+                vec![Mutation::new(Command::Stop, TransactionStatusChangedScope::default().into())],
+            ),
+            (false, Command::Stop) => MutationOutcome::new(),
+        };
+        Ok(outcome)
+    }
+}
+
+impl Subscription for TransactionStatusChangedSubscription {
+    #[inline(always)]
+    fn event_type(&self) -> EventType {
+        EventType::TransactionStatusChanged
+    }
+
+    #[inline(always)]
+    fn active(&self) -> bool {
+        self.active
+    }
+
+    fn scope(&self, _context: &SubscriptionContext) -> Scope {
+        TransactionStatusChangedScope::new(self.filter.filter().transaction_ids.clone()).into()
     }
 }
 
@@ -767,7 +947,7 @@ mod tests {
             Arc::new(OverallSubscription { event_type: EventType::BlockAdded, active })
         }
         fn m(command: Command) -> Mutation {
-            Mutation { command, scope: Scope::BlockAdded(BlockAddedScope {}) }
+            Mutation { command, scope: Scope::BlockAdded(BlockAddedScope::default()) }
         }
 
         // Subscriptions
@@ -812,15 +992,157 @@ mod tests {
         tests.run(&context)
     }
 
+    #[test]
+    fn test_block_added_mutation() {
+        let context = SubscriptionContext::new();
+        let addresses = get_3_addresses(false);
+
+        fn s(active: bool, filter: TransactionFilter, header_only: bool) -> DynSubscription {
+            Arc::new(BlockAddedSubscription::new(active, filter, header_only))
+        }
+        fn m(command: Command, filter: TransactionFilter, header_only: bool) -> Mutation {
+            Mutation { command, scope: Scope::BlockAdded(BlockAddedScope::new(filter, header_only)) }
+        }
+
+        // Filters
+        let by_address = || TransactionFilter::new(addresses[0..1].to_vec(), vec![], vec![]);
+        let by_payload = || TransactionFilter::new(vec![], vec![0xca, 0xfe], vec![]);
+
+        // Subscriptions
+        let none = || s(false, Default::default(), false);
+        let all = || s(true, Default::default(), false);
+        let headers = || s(true, Default::default(), true);
+        let filtered = || s(true, by_address(), false);
+
+        // Mutations
+        let start_all = || m(Command::Start, Default::default(), false);
+        let stop_all = || m(Command::Stop, Default::default(), false);
+        let start_headers = || m(Command::Start, Default::default(), true);
+        let start_filtered = || m(Command::Start, by_address(), false);
+        let stop_filtered = || m(Command::Stop, by_address(), false);
+        let start_other_filter = || m(Command::Start, by_payload(), false);
+
+        // Tests
+        let tests = MutationTests::new(vec![
+            MutationTest {
+                name: "BlockAddedSubscription None to All",
+                state: none(),
+                mutation: start_all(),
+                new_state: all(),
+                outcome: MutationOutcome::with_mutated(all(), vec![start_all()]),
+            },
+            MutationTest {
+                name: "BlockAddedSubscription None to Filtered",
+                state: none(),
+                mutation: start_filtered(),
+                new_state: filtered(),
+                outcome: MutationOutcome::with_mutated(filtered(), vec![start_all()]),
+            },
+            MutationTest {
+                name: "BlockAddedSubscription None to None",
+                state: none(),
+                mutation: stop_filtered(),
+                new_state: none(),
+                outcome: MutationOutcome::new(),
+            },
+            MutationTest {
+                name: "BlockAddedSubscription All to All",
+                state: all(),
+                mutation: start_all(),
+                new_state: all(),
+                outcome: MutationOutcome::new(),
+            },
+            MutationTest {
+                name: "BlockAddedSubscription All to Headers",
+                state: all(),
+                mutation: start_headers(),
+                new_state: headers(),
+                outcome: MutationOutcome::with_mutated(headers(), vec![]),
+            },
+            MutationTest {
+                name: "BlockAddedSubscription Filtered to other Filtered",
+                state: filtered(),
+                mutation: start_other_filter(),
+                new_state: s(true, by_payload(), false),
+                outcome: MutationOutcome::with_mutated(s(true, by_payload(), false), vec![]),
+            },
+            MutationTest {
+                name: "BlockAddedSubscription Filtered to None",
+                state: filtered(),
+                mutation: stop_filtered(),
+                new_state: none(),
+                outcome: MutationOutcome::with_mutated(none(), vec![stop_all()]),
+            },
+        ]);
+        tests.run(&context)
+    }
+
+    #[test]
+    fn test_virtual_chain_changed_filter_mutation() {
+        let context = SubscriptionContext::new();
+        let ids = || vec![TransactionId::from_u64_word(1), TransactionId::from_u64_word(2)];
+
+        fn s(active: bool, include_accepted_transaction_ids: bool, transaction_ids: Vec<TransactionId>) -> DynSubscription {
+            Arc::new(VirtualChainChangedSubscription::with_transaction_ids(active, include_accepted_transaction_ids, transaction_ids))
+        }
+        fn m(command: Command, include_accepted_transaction_ids: bool, transaction_ids: Vec<TransactionId>) -> Mutation {
+            let scope = VirtualChainChangedScope::with_transaction_ids(include_accepted_transaction_ids, transaction_ids);
+            Mutation { command, scope: Scope::VirtualChainChanged(scope) }
+        }
+
+        // Tests
+        let tests = MutationTests::new(vec![
+            MutationTest {
+                name: "VirtualChainChangedSubscription None to Filtered All",
+                state: s(false, false, vec![]),
+                mutation: m(Command::Start, true, ids()),
+                new_state: s(true, true, ids()),
+                outcome: MutationOutcome::with_mutated(s(true, true, ids()), vec![m(Command::Start, true, vec![])]),
+            },
+            MutationTest {
+                name: "VirtualChainChangedSubscription All to Filtered All",
+                state: s(true, true, vec![]),
+                mutation: m(Command::Start, true, ids()),
+                new_state: s(true, true, ids()),
+                outcome: MutationOutcome::with_mutated(s(true, true, ids()), vec![]),
+            },
+            MutationTest {
+                name: "VirtualChainChangedSubscription Filtered All to Filtered All",
+                state: s(true, true, ids()),
+                mutation: m(Command::Start, true, ids()),
+                new_state: s(true, true, ids()),
+                outcome: MutationOutcome::new(),
+            },
+            MutationTest {
+                name: "VirtualChainChangedSubscription Filtered All to Reduced",
+                state: s(true, true, ids()),
+                mutation: m(Command::Start, false, vec![]),
+                new_state: s(true, false, vec![]),
+                outcome: MutationOutcome::with_mutated(
+                    s(true, false, vec![]),
+                    vec![m(Command::Start, false, vec![]), m(Command::Stop, true, vec![])],
+                ),
+            },
+            MutationTest {
+                name: "VirtualChainChangedSubscription Filtered All to None",
+                state: s(true, true, ids()),
+                mutation: m(Command::Stop, true, ids()),
+                new_state: s(false, false, vec![]),
+                outcome: MutationOutcome::with_mutated(s(false, false, vec![]), vec![m(Command::Stop, true, vec![])]),
+            },
+        ]);
+        tests.run(&context)
+    }
+
     #[test]
     fn test_virtual_chain_changed_mutation() {
         let context = SubscriptionContext::new();
 
         fn s(active: bool, include_accepted_transaction_ids: bool) -> DynSubscription {
-            Arc::new(VirtualChainChangedSubscription { active, include_accepted_transaction_ids })
+            Arc::new(VirtualChainChangedSubscription::new(active, include_accepted_transaction_ids))
         }
         fn m(command: Command, include_accepted_transaction_ids: bool) -> Mutation {
-            Mutation { command, scope: Scope::VirtualChainChanged(VirtualChainChangedScope { include_accepted_transaction_ids }) }
+            Mutation { command, scope: Scope::VirtualChainChanged(VirtualChainChangedScope::new(include_accepted_transaction_ids)) }
         }
 
         // Subscriptions
@@ -1,5 +1,6 @@
 use crate::{
     flowcontext::{
+        misbehavior::{Misbehavior, MisbehaviorScores},
         orphans::{OrphanBlocksPool, OrphanOutput},
         process_queue::ProcessQueue,
         transactions::TransactionsSpread,
@@ -39,6 +40,7 @@ use vecno_p2p_lib::{
     convert::model::version::Version,
     make_message,
     pb::{vecnod_message::Payload, InvRelayBlockMessage},
+    secure::{SecureTransport, SecureTransportError, SERVICE_ENCRYPTED_TRANSPORT},
     ConnectionInitializer, Hub, VecnodHandshake, PeerKey, PeerProperties, Router,
 };
 use vecno_p2p_mining::rule_engine::MiningRuleEngine;
@@ -47,7 +49,7 @@ use vecno_utils::networking::PeerId;
 use parking_lot::{Mutex, RwLock};
 use std::collections::HashMap;
 use std::time::Instant;
-use std::{collections::hash_map::Entry, fmt::Display};
+use std::{collections::hash_map::Entry, fmt::Display, net::IpAddr};
 use std::{
     iter::once,
     ops::Deref,
@@ -65,7 +67,16 @@ use tokio_stream::{wrappers::UnboundedReceiverStream, StreamExt};
 use uuid::Uuid;
 
 /// The P2P protocol version.
-const PROTOCOL_VERSION: u32 = 1;
+const PROTOCOL_VERSION: u32 = 4;
+
+/// The first protocol version supporting the compact block relay
+pub const COMPACT_BLOCK_RELAY_PROTOCOL_VERSION: u32 = 2;
+
+/// The first protocol version supporting the stem phase of the transaction relay
+pub const STEM_RELAY_PROTOCOL_VERSION: u32 = 3;
+
+/// The first protocol version supporting onion addresses in the address gossip
+pub const ONION_ADDRESS_PROTOCOL_VERSION: u32 = 4;
 
 /// See `check_orphan_resolution_range`
 const BASELINE_ORPHAN_RESOLUTION_RANGE: u32 = 5;
@@ -244,6 +255,12 @@ pub struct FlowContextInner {
 
     // Mining rule engine
     mining_rule_engine: Arc<MiningRuleEngine>,
+
+    // Encrypted transport settings, the transport being disabled if undefined
+    secure_transport: Option<Arc<SecureTransport>>,
+
+    // Misbehavior scores of the peers, leading to their ban once above the configured threshold
+    misbehavior_scores: Mutex<MisbehaviorScores>,
 }
 
 #[derive(Clone)]
@@ -318,6 +335,7 @@ impl FlowContext {
         notification_root: Arc<ConsensusNotificationRoot>,
         hub: Hub,
         mining_rule_engine: Arc<MiningRuleEngine>,
+        secure_transport: Option<Arc<SecureTransport>>,
     ) -> Self {
         let bps_upper_bound = config.bps().upper_bound() as usize;
         let orphan_resolution_range = BASELINE_ORPHAN_RESOLUTION_RANGE + (bps_upper_bound as f64).log2().ceil() as u32;
@@ -332,7 +350,7 @@ impl FlowContext {
                 consensus_manager,
                 orphans_pool: AsyncRwLock::new(OrphanBlocksPool::new(max_orphans)),
                 shared_block_requests: Arc::new(Mutex::new(HashMap::new())),
-                transactions_spread: AsyncRwLock::new(TransactionsSpread::new(hub.clone())),
+                transactions_spread: AsyncRwLock::new(TransactionsSpread::new(hub.clone(), config.tx_stem_relay)),
                 shared_transaction_requests: Arc::new(Mutex::new(HashMap::new())),
                 is_ibd_running: Default::default(),
                 ibd_metadata: Default::default(),
@@ -348,6 +366,8 @@ impl FlowContext {
                 max_orphans,
                 config,
                 mining_rule_engine,
+                secure_transport,
+                misbehavior_scores: Default::default(),
             }),
         }
     }
@@ -407,6 +427,34 @@ impl FlowContext {
         self.is_ibd_running.load(Ordering::SeqCst)
     }
 
+    /// Adds the penalty of `misbehavior` to the score of the peer, banning its IP once the score reaches the ban threshold
+    pub async fn report_misbehavior(&self, router: &Router, misbehavior: Misbehavior) {
+        let ip = router.net_address().ip();
+        let score = self.misbehavior_scores.lock().add(ip, misbehavior, Instant::now());
+        debug!("Peer {} misbehaved ({:?}), its misbehavior score is now {:.0}", router, misbehavior, score);
+        if score < self.config.ban_threshold as f64 {
+            return;
+        }
+        // Inbound connections from onion services all come from the local Tor daemon, so that banning its IP would
+        // ban all of them. The peer is disconnected instead.
+        if ip.is_loopback() {
+            warn!("Disconnecting from peer {} since its misbehavior score reached {:.0}", router, score);
+            self.misbehavior_scores.lock().remove(ip);
+            self.hub.terminate(router.key()).await;
+            return;
+        }
+        if let Some(connection_manager) = self.connection_manager() {
+            warn!("Banning peer {} for {:?} since its misbehavior score reached {:.0}", router, self.config.ban_duration, score);
+            self.misbehavior_scores.lock().remove(ip);
+            connection_manager.ban(ip).await;
+        }
+    }
+
+    /// Returns the current misbehavior score of the peers of the given IP
+    pub fn misbehavior_score(&self, ip: IpAddr) -> u32 {
+        self.misbehavior_scores.lock().get(ip, Instant::now()).round() as u32
+    }
+
     /// If IBD is running, returns the IBD peer we are syncing from
     pub fn ibd_peer_key(&self) -> Option<PeerKey> {
         if self.is_ibd_running() {
@@ -601,6 +649,7 @@ impl FlowContext {
 
         // TODO: Throttle these transactions as well if needed
         self.broadcast_transactions(transactions_to_broadcast, false).await;
+        self.transactions_spread.write().await.broadcast_expired_stem_transactions().await;
 
         if self.should_run_mempool_scanning_task().await {
             // Spawn a task executing the removal of expired low priority transactions and, if time has come too,
@@ -672,14 +721,31 @@ impl FlowContext {
             .clone()
             .validate_and_insert_transaction(consensus, transaction, Priority::High, orphan, RbfPolicy::Forbidden)
             .await?;
-        self.broadcast_transactions(
-            transaction_insertion.accepted.iter().map(|x| x.id()),
-            false, // RPC transactions are considered high priority, so we don't want to throttle them
-        )
-        .await;
+        self.relay_stem_transactions(transaction_insertion.accepted, None).await;
         Ok(())
     }
 
+    /// Adds the rpc-submitted package of transactions into the mempool and announces its transactions to peers.
+    ///
+    /// There is no P2P package message, so the package transactions are relayed individually and peers only
+    /// accept the ones meeting their relay rules on their own.
+    ///
+    /// Returns the ids of the package transactions in topological order.
+    ///
+    /// Like transactions submitted through rpc, the package transactions are considered high priority.
+    pub async fn submit_rpc_package(
+        &self,
+        consensus: &ConsensusProxy,
+        transactions: Vec<Transaction>,
+    ) -> Result<Vec<TransactionId>, ProtocolError> {
+        let package_len = transactions.len();
+        let transaction_insertion =
+            self.mining_manager().clone().validate_and_insert_package(consensus, transactions, Priority::High).await?;
+        let package_ids = transaction_insertion.accepted.iter().take(package_len).map(|x| x.id()).collect();
+        self.relay_stem_transactions(transaction_insertion.accepted, None).await;
+        Ok(package_ids)
+    }
+
     /// Replaces the rpc-submitted transaction into the mempool and propagates it to peers.
     ///
     /// Returns the removed mempool transaction on successful replace by fee.
@@ -697,11 +763,7 @@ impl FlowContext {
             .clone()
             .validate_and_insert_transaction(consensus, transaction, Priority::High, Orphan::Forbidden, RbfPolicy::Mandatory)
             .await?;
-        self.broadcast_transactions(
-            transaction_insertion.accepted.iter().map(|x| x.id()),
-            false, // RPC transactions are considered high priority, so we don't want to throttle them
-        )
-        .await;
+        self.relay_stem_transactions(transaction_insertion.accepted, None).await;
         // The combination of args above of Orphan::Forbidden and RbfPolicy::Mandatory should always result
         // in a removed transaction returned, however we prefer failing gracefully in case of future internal mempool changes
         transaction_insertion.removed.ok_or(ProtocolError::Other(
@@ -735,6 +797,24 @@ impl FlowContext {
     pub async fn broadcast_transactions<I: IntoIterator<Item = TransactionId>>(&self, transaction_ids: I, should_throttle: bool) {
         self.transactions_spread.write().await.broadcast_transactions(transaction_ids, should_throttle).await
     }
+
+    /// Relays the given transactions along the stem if the stem phase of the transaction relay is enabled, or broadcasts
+    /// them otherwise. `source` is the peer the transactions were received from along its stem, or `None` for local ones.
+    ///
+    /// Like transactions submitted through rpc, stemmed transactions are never throttled.
+    pub async fn relay_stem_transactions(&self, transactions: Vec<Arc<Transaction>>, source: Option<PeerKey>) {
+        self.transactions_spread.write().await.relay_stem_transactions(transactions, source).await
+    }
+
+    /// Returns true if the transaction is under the embargo of the stem phase, in which case it must not be revealed to peers
+    pub async fn is_stem_embargoed(&self, transaction_id: &TransactionId) -> bool {
+        self.transactions_spread.read().await.is_stem_embargoed(transaction_id)
+    }
+
+    /// Lifts the stem embargo of the given transactions, which were seen diffused by the network
+    pub async fn lift_stem_embargoes(&self, transaction_ids: &[TransactionId]) {
+        self.transactions_spread.write().await.lift_stem_embargoes(transaction_ids)
+    }
 }
 
 #[async_trait]
@@ -754,6 +834,9 @@ impl ConnectionInitializer for FlowContext {
         // Subnets are not currently supported
         let mut self_version_message = Version::new(local_address, self.node_id, network_name.clone(), None, PROTOCOL_VERSION);
         self_version_message.add_user_agent(name(), version(), &self.config.user_agent_comments);
+        if self.secure_transport.is_some() {
+            self_version_message.services |= SERVICE_ENCRYPTED_TRANSPORT;
+        }
         // TODO: get number of live services
         // TODO: disable_relay_tx from config/cmd
 
@@ -779,17 +862,29 @@ impl ConnectionInitializer for FlowContext {
 
         debug!("protocol versions - self: {}, peer: {}", PROTOCOL_VERSION, peer_version.protocol_version);
 
+        // Switch to the encrypted transport if both peers support it
+        if let Some(secure_transport) = self.secure_transport.as_ref() {
+            if SecureTransport::is_supported_by(peer_version.services) {
+                let peer_identity = handshake.secure_handshake(secure_transport).await?;
+                debug!("P2P, encrypted transport established with peer {} of identity {}", router, peer_identity);
+            } else if secure_transport.is_restricted() {
+                return Err(SecureTransportError::EncryptionRequired.into());
+            }
+        }
+
         // Register all flows according to version
         let connect_only_new_versions = self.config.net.network_type() != NetworkType::Testnet;
 
         let (flows, applied_protocol_version) = if connect_only_new_versions {
             match peer_version.protocol_version {
                 v if v >= PROTOCOL_VERSION => (v7::register(self.clone(), router.clone()), PROTOCOL_VERSION),
+                v @ 1..PROTOCOL_VERSION => (v7::register(self.clone(), router.clone()), v),
                 v => return Err(ProtocolError::VersionMismatch(PROTOCOL_VERSION, v)),
             }
         } else {
             match peer_version.protocol_version {
                 v if v >= PROTOCOL_VERSION => (v7::register(self.clone(), router.clone()), PROTOCOL_VERSION),
+                v @ 1..PROTOCOL_VERSION => (v7::register(self.clone(), router.clone()), v),
                 6 => (v6::register(self.clone(), router.clone()), 6),
                 5 => (v5::register(self.clone(), router.clone()), 5),
                 v => return Err(ProtocolError::VersionMismatch(PROTOCOL_VERSION, v)),
@@ -814,7 +909,7 @@ impl ConnectionInitializer for FlowContext {
 
         // Launch all flows. Note we launch only after the ready signal was exchanged
         for flow in flows {
-            flow.launch();
+            flow.launch(self.clone());
         }
 
         if router.is_outbound() || peer_version.address.is_some() {
@@ -824,8 +919,8 @@ impl ConnectionInitializer for FlowContext {
                 address_manager.add_address(router.net_address().into());
             }
 
-            if let Some(peer_ip_address) = peer_version.address {
-                address_manager.add_address(peer_ip_address);
+            if let Some(peer_address) = peer_version.address {
+                address_manager.add_peer_address(peer_address);
             }
         }
 
@@ -0,0 +1,125 @@
+use vecno_consensus_core::errors::block::RuleError;
+use vecno_p2p_lib::common::ProtocolError;
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    time::{Duration, Instant},
+};
+
+/// Duration over which a misbehavior score decays by half
+const SCORE_HALF_LIFE: Duration = Duration::from_secs(10 * 60);
+
+/// Scores decayed below this value are forgotten
+const MIN_TRACKED_SCORE: f64 = 1.0;
+
+/// Kinds of peer misbehavior, each adding a penalty to the misbehavior score of the peer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Misbehavior {
+    /// Sent a block or header failing validation
+    InvalidBlock,
+
+    /// Relayed a transaction rejected as invalid by the mempool
+    InvalidTransaction,
+
+    /// Relayed a transaction rejected as non-standard by the mempool
+    NonStandardTransaction,
+
+    /// Sent an unexpected or malformed message
+    ProtocolViolation,
+
+    /// Did not respond in time to an IBD request
+    SlowIbdResponse,
+}
+
+impl Misbehavior {
+    pub fn penalty(self) -> f64 {
+        match self {
+            Misbehavior::InvalidBlock => 100.0,
+            Misbehavior::InvalidTransaction => 50.0,
+            Misbehavior::NonStandardTransaction => 1.0,
+            Misbehavior::ProtocolViolation => 20.0,
+            Misbehavior::SlowIbdResponse => 25.0,
+        }
+    }
+
+    /// Returns the misbehavior revealed by the error a flow of the peer failed with, if any
+    pub fn from_flow_error(err: &ProtocolError) -> Option<Self> {
+        match err {
+            // The clock of an honest peer might be ahead of ours
+            ProtocolError::RuleError(RuleError::TimeTooFarIntoTheFuture(..)) => None,
+            ProtocolError::RuleError(_) => Some(Misbehavior::InvalidBlock),
+            ProtocolError::UnexpectedMessage(..) | ProtocolError::ConversionError(_) => Some(Misbehavior::ProtocolViolation),
+            _ => None,
+        }
+    }
+}
+
+struct Score {
+    value: f64,
+    last_update: Instant,
+}
+
+impl Score {
+    fn decayed(&self, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(self.last_update);
+        self.value * 0.5f64.powf(elapsed.as_secs_f64() / SCORE_HALF_LIFE.as_secs_f64())
+    }
+}
+
+/// Misbehavior scores of the peers, decaying over time. Scores are tracked by IP so that they survive reconnections.
+#[derive(Default)]
+pub struct MisbehaviorScores {
+    scores: HashMap<IpAddr, Score>,
+}
+
+impl MisbehaviorScores {
+    /// Adds the penalty of `misbehavior` to the score of `ip` and returns the resulting score
+    pub fn add(&mut self, ip: IpAddr, misbehavior: Misbehavior, now: Instant) -> f64 {
+        self.scores.retain(|_, score| score.decayed(now) >= MIN_TRACKED_SCORE);
+        let value = self.get(ip, now) + misbehavior.penalty();
+        self.scores.insert(ip, Score { value, last_update: now });
+        value
+    }
+
+    pub fn get(&self, ip: IpAddr, now: Instant) -> f64 {
+        self.scores.get(&ip).map_or(0.0, |score| score.decayed(now))
+    }
+
+    pub fn remove(&mut self, ip: IpAddr) {
+        self.scores.remove(&ip);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_misbehavior_scores() {
+        let (ip1, ip2): (IpAddr, IpAddr) = ("1.1.1.1".parse().unwrap(), "2.2.2.2".parse().unwrap());
+        let mut scores = MisbehaviorScores::default();
+        let now = Instant::now();
+
+        assert_eq!(scores.add(ip1, Misbehavior::ProtocolViolation, now), 20.0);
+        assert_eq!(scores.add(ip1, Misbehavior::InvalidTransaction, now), 70.0);
+        assert_eq!(scores.add(ip2, Misbehavior::NonStandardTransaction, now), 1.0);
+        assert_eq!(scores.get(ip1, now), 70.0);
+
+        // Scores decay by half every half-life, and get forgotten once negligible
+        let later = now + SCORE_HALF_LIFE;
+        assert!((scores.get(ip1, later) - 35.0).abs() < 1e-9);
+        assert!((scores.add(ip1, Misbehavior::ProtocolViolation, later) - 55.0).abs() < 1e-9);
+        assert!(!scores.scores.contains_key(&ip2));
+
+        scores.remove(ip1);
+        assert_eq!(scores.get(ip1, later), 0.0);
+    }
+
+    #[test]
+    fn test_misbehavior_from_flow_error() {
+        assert_eq!(Misbehavior::from_flow_error(&RuleError::KnownInvalid.into()), Some(Misbehavior::InvalidBlock));
+        assert_eq!(Misbehavior::from_flow_error(&RuleError::TimeTooFarIntoTheFuture(1, 0).into()), None);
+        assert_eq!(Misbehavior::from_flow_error(&ProtocolError::UnexpectedMessage("", None)), Some(Misbehavior::ProtocolViolation));
+        assert_eq!(Misbehavior::from_flow_error(&ProtocolError::ConnectionClosed), None);
+    }
+}
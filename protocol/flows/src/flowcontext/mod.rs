@@ -1,3 +1,5 @@
+pub mod misbehavior;
 pub mod orphans;
 pub(crate) mod process_queue;
+pub mod stem;
 pub mod transactions;
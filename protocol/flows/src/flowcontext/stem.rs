@@ -0,0 +1,266 @@
+//!
+//! Stem phase of the stem/fluff transaction relay (a.k.a. Dandelion).
+//!
+//! Instead of being announced right away to all peers, a locally submitted transaction is first sent in full to a single
+//! peer, the stem peer, which in turn either passes it along its own stem or diffuses (fluffs) it with regular inv messages.
+//! Observers of the diffusion phase hence cannot tell which node the transaction originated from.
+//!
+//! A transaction sent along the stem is under embargo: it is not announced to the other peers, unless it was not seen
+//! diffused by the network before the embargo expires, in which case it gets broadcast as any other transaction.
+//!
+
+use crate::flow_context::STEM_RELAY_PROTOCOL_VERSION;
+use rand::{seq::IteratorRandom, thread_rng, Rng};
+use vecno_consensus_core::tx::{Transaction, TransactionId};
+use vecno_core::debug;
+use vecno_p2p_lib::{
+    make_message,
+    pb::{vecnod_message::Payload, StemTransactionsMessage},
+    Hub, PeerKey,
+};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// Duration after which the stem peer is replaced by a new random one
+const STEM_EPOCH: Duration = Duration::from_secs(600);
+
+/// Probability of a transaction received along the stem of a peer to be diffused rather than further stemmed
+const FLUFF_PROBABILITY: f64 = 0.1;
+
+/// Minimum duration of the embargo of a stemmed transaction
+const EMBARGO_DURATION: Duration = Duration::from_secs(30);
+
+/// Upper bound of the random delay added to the embargo, so that the node whose embargo expires first is not
+/// predictably the origin of the transaction
+const EMBARGO_JITTER: Duration = Duration::from_secs(15);
+
+pub struct TransactionsStem {
+    hub: Hub,
+
+    /// The current stem peer along with the time it was selected at
+    stem_peer: Option<(PeerKey, Instant)>,
+
+    /// Expiration times of the embargoes of the stemmed transactions
+    embargoes: HashMap<TransactionId, Instant>,
+}
+
+impl TransactionsStem {
+    pub fn new(hub: Hub) -> Self {
+        Self { hub, stem_peer: None, embargoes: HashMap::new() }
+    }
+
+    /// Relays the given transactions along the stem and returns the ids of the transactions to diffuse instead.
+    ///
+    /// `source` is the peer the transactions were received from along its stem, or `None` for locally submitted
+    /// transactions which are never diffused directly. All transactions are diffused when no stem peer is available.
+    pub async fn relay(&mut self, transactions: Vec<Arc<Transaction>>, source: Option<PeerKey>) -> Vec<TransactionId> {
+        if transactions.is_empty() {
+            return vec![];
+        }
+        let ids = || transactions.iter().map(|tx| tx.id()).collect();
+        if source.is_some() && thread_rng().gen_bool(FLUFF_PROBABILITY) {
+            return ids();
+        }
+        let Some(stem_peer) = self.stem_peer() else {
+            return ids();
+        };
+        // Sending back along the stem would only tell the source peer we are not the origin
+        if Some(stem_peer) == source {
+            return ids();
+        }
+
+        let msg = make_message!(
+            Payload::StemTransactions,
+            StemTransactionsMessage { transactions: transactions.iter().map(|tx| tx.as_ref().into()).collect() }
+        );
+        if !matches!(self.hub.send(stem_peer, msg).await, Ok(true)) {
+            self.stem_peer = None;
+            return ids();
+        }
+
+        debug!("Transaction propagation: stemming {} transactions to peer {}", transactions.len(), stem_peer);
+        let expiration = Instant::now() + EMBARGO_DURATION + EMBARGO_JITTER.mul_f64(thread_rng().gen());
+        self.embargoes.extend(transactions.iter().map(|tx| (tx.id(), expiration)));
+        vec![]
+    }
+
+    /// Returns the current stem peer, selecting a new random one if the current one disconnected or its epoch elapsed
+    fn stem_peer(&mut self) -> Option<PeerKey> {
+        if let Some((peer_key, selection_time)) = self.stem_peer {
+            if selection_time.elapsed() < STEM_EPOCH && self.hub.has_peer(peer_key) {
+                return Some(peer_key);
+            }
+        }
+        // Only outbound peers are selected since inbound connections are cheap for an adversary to accumulate
+        self.stem_peer = self
+            .hub
+            .active_peers()
+            .into_iter()
+            .filter(|peer| {
+                let properties = peer.properties();
+                peer.is_outbound() && !properties.disable_relay_tx && properties.protocol_version >= STEM_RELAY_PROTOCOL_VERSION
+            })
+            .choose(&mut thread_rng())
+            .map(|peer| (peer.key(), Instant::now()));
+        self.stem_peer.map(|(peer_key, _)| peer_key)
+    }
+
+    pub fn is_embargoed(&self, transaction_id: &TransactionId) -> bool {
+        self.embargoes.contains_key(transaction_id)
+    }
+
+    /// Lifts the embargo of the given transactions, which were seen diffused by the network
+    pub fn lift_embargoes(&mut self, transaction_ids: &[TransactionId]) {
+        for id in transaction_ids {
+            self.embargoes.remove(id);
+        }
+    }
+
+    /// Removes the embargoes expired at `now` and returns the ids of their transactions, which are to be diffused
+    pub fn expire_embargoes(&mut self, now: Instant) -> Vec<TransactionId> {
+        self.embargoes.extract_if(|_, expiration| *expiration <= now).map(|(id, _)| id).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vecno_consensus_core::{
+        subnets::SUBNETWORK_ID_NATIVE,
+        tx::{ScriptPublicKey, TransactionInput, TransactionOutpoint, TransactionOutput},
+    };
+    use vecno_core::time::unix_now;
+    use vecno_hashes::Hash;
+    use vecno_p2p_lib::{
+        common::ProtocolError,
+        pb::{self, VecnodMessage},
+        Adaptor, ConnectionInitializer, PeerProperties, Router, VecnodHandshake, VecnodMessagePayloadType,
+    };
+    use vecno_utils::networking::NetAddress;
+    use std::str::FromStr;
+    use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+    use uuid::Uuid;
+
+    /// Initializer performing the handshake and forwarding the received transaction relay messages to a channel
+    struct RelayRecorderInitializer {
+        sender: UnboundedSender<VecnodMessage>,
+    }
+
+    #[async_trait::async_trait]
+    impl ConnectionInitializer for RelayRecorderInitializer {
+        async fn initialize_connection(&self, router: Arc<Router>) -> Result<(), ProtocolError> {
+            let mut handshake = VecnodHandshake::new(&router);
+            router.start();
+            let version_message = pb::VersionMessage {
+                protocol_version: STEM_RELAY_PROTOCOL_VERSION,
+                services: 0,
+                timestamp: unix_now() as i64,
+                address: None,
+                id: Vec::from(Uuid::new_v4().as_bytes()),
+                user_agent: String::new(),
+                disable_relay_tx: false,
+                subnetwork_id: None,
+                network: "vecno-mainnet".to_string(),
+            };
+            handshake.handshake(version_message).await?;
+            router.set_properties(Arc::new(PeerProperties { protocol_version: STEM_RELAY_PROTOCOL_VERSION, ..Default::default() }));
+            let mut incoming_route =
+                router.subscribe(vec![VecnodMessagePayloadType::StemTransactions, VecnodMessagePayloadType::InvTransactions]);
+            let sender = self.sender.clone();
+            tokio::spawn(async move {
+                while let Some(msg) = incoming_route.recv().await {
+                    let _ = sender.send(msg);
+                }
+            });
+            handshake.exchange_ready_messages().await?;
+            Ok(())
+        }
+    }
+
+    fn adaptor(port: u16, hub: Hub) -> (Arc<Adaptor>, UnboundedReceiver<VecnodMessage>) {
+        let (sender, receiver) = unbounded_channel();
+        let address = NetAddress::from_str(&format!("[::1]:{port}")).unwrap();
+        let adaptor = Adaptor::bidirectional(address, hub, Arc::new(RelayRecorderInitializer { sender }), Default::default()).unwrap();
+        (adaptor, receiver)
+    }
+
+    fn transaction(seed: u64) -> Arc<Transaction> {
+        let inputs = vec![TransactionInput::new(TransactionOutpoint::new(Hash::from_u64_word(seed), 0), vec![], 0, 1)];
+        let outputs = vec![TransactionOutput::new(seed, ScriptPublicKey::from_vec(0, vec![]))];
+        Arc::new(Transaction::new(0, inputs, outputs, 0, SUBNETWORK_ID_NATIVE, 0, vec![]))
+    }
+
+    /// Returns the ids of the transactions stemmed to a peer since the last call
+    async fn stemmed_ids(receiver: &mut UnboundedReceiver<VecnodMessage>) -> Vec<TransactionId> {
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        let mut ids = vec![];
+        while let Ok(msg) = receiver.try_recv() {
+            let Some(Payload::StemTransactions(payload)) = msg.payload else { panic!("unexpected message {:?}", msg.payload) };
+            ids.extend(Vec::<Transaction>::try_from(payload).unwrap().into_iter().map(|tx| tx.id()));
+        }
+        ids
+    }
+
+    #[tokio::test]
+    async fn test_transactions_stem() {
+        vecno_core::log::try_init_logger("debug");
+
+        let hub = Hub::new();
+        let (adaptor, _receiver) = adaptor(50058, hub.clone());
+        let (adaptor1, mut receiver1) = adaptor(50059, Hub::new());
+        let (adaptor2, mut receiver2) = adaptor(50060, Hub::new());
+        for address in ["[::1]:50059", "[::1]:50060"] {
+            adaptor.connect_peer_with_retries(address.to_string(), 16, Duration::from_secs(1)).await.expect("peer connection failed");
+        }
+
+        // Wait for handshake completion
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        assert_eq!(hub.active_peers_len(), 2);
+
+        // Local transactions are stemmed to a single peer, the same one along the epoch
+        let mut stem = TransactionsStem::new(hub.clone());
+        let (tx1, tx2, tx3, tx4) = (transaction(1), transaction(2), transaction(3), transaction(4));
+        assert!(stem.relay(vec![tx1.clone()], None).await.is_empty());
+        assert!(stem.relay(vec![tx2.clone()], None).await.is_empty());
+        let (stemmed1, stemmed2) = (stemmed_ids(&mut receiver1).await, stemmed_ids(&mut receiver2).await);
+        let stem_peer = stem.stem_peer.unwrap().0;
+        let other_receiver = match (stemmed1.is_empty(), stemmed2.is_empty()) {
+            (false, true) => &mut receiver2,
+            (true, false) => &mut receiver1,
+            _ => panic!("transactions should be stemmed to exactly one peer"),
+        };
+        assert_eq!([stemmed1, stemmed2].concat(), vec![tx1.id(), tx2.id()]);
+
+        // Transactions are never stemmed back to their source
+        assert_eq!(stem.relay(vec![tx3.clone()], Some(stem_peer)).await, vec![tx3.id()]);
+        assert!(!stem.is_embargoed(&tx3.id()));
+
+        // Embargoes are lifted once the transactions are seen diffused, or expire so that they fall back to a broadcast
+        assert!(stem.is_embargoed(&tx1.id()) && stem.is_embargoed(&tx2.id()));
+        stem.lift_embargoes(&[tx2.id()]);
+        assert!(!stem.is_embargoed(&tx2.id()));
+        assert!(stem.expire_embargoes(Instant::now()).is_empty());
+        assert_eq!(stem.expire_embargoes(Instant::now() + EMBARGO_DURATION + EMBARGO_JITTER), vec![tx1.id()]);
+        assert!(!stem.is_embargoed(&tx1.id()));
+
+        // A new stem peer is selected once the current one disconnects
+        hub.terminate(stem_peer).await;
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        assert!(stem.relay(vec![tx4.clone()], None).await.is_empty());
+        assert_ne!(stem.stem_peer.unwrap().0, stem_peer);
+        assert_eq!(stemmed_ids(other_receiver).await, vec![tx4.id()]);
+
+        // Transactions are diffused when no stem peer is available
+        hub.terminate_all_peers().await;
+        let tx5 = transaction(5);
+        assert_eq!(stem.relay(vec![tx5.clone()], None).await, vec![tx5.id()]);
+
+        adaptor.close().await;
+        adaptor1.close().await;
+        adaptor2.close().await;
+        tokio::time::sleep(Duration::from_secs(2)).await;
+    }
+}
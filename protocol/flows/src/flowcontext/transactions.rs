@@ -1,13 +1,16 @@
-use super::process_queue::ProcessQueue;
+use super::{process_queue::ProcessQueue, stem::TransactionsStem};
 use itertools::Itertools;
-use vecno_consensus_core::tx::TransactionId;
+use vecno_consensus_core::tx::{Transaction, TransactionId};
 use vecno_core::debug;
 use vecno_p2p_lib::{
     make_message,
     pb::{vecnod_message::Payload, InvTransactionsMessage, VecnodMessage},
-    Hub,
+    Hub, PeerKey,
+};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
 };
-use std::time::{Duration, Instant};
 
 /// Interval between mempool scanning tasks (in seconds)
 const SCANNING_TASK_INTERVAL: u64 = 10;
@@ -22,11 +25,15 @@ pub struct TransactionsSpread {
     scanning_job_count: u64,
     transaction_ids: ProcessQueue<TransactionId>,
     last_broadcast_time: Instant,
+
+    /// The stem phase of the transaction relay, if enabled
+    stem: Option<TransactionsStem>,
 }
 
 impl TransactionsSpread {
-    pub fn new(hub: Hub) -> Self {
+    pub fn new(hub: Hub, stem_relay: bool) -> Self {
         Self {
+            stem: stem_relay.then(|| TransactionsStem::new(hub.clone())),
             hub,
             last_scanning_time: Instant::now(),
             scanning_task_running: false,
@@ -74,8 +81,13 @@ impl TransactionsSpread {
     /// The broadcast itself may happen only during a subsequent call to this function since it is done at most
     /// every `BROADCAST_INTERVAL` milliseconds or when the queue length is larger than the Inv message
     /// capacity.
+    ///
+    /// Transactions under the embargo of the stem phase are skipped.
     pub async fn broadcast_transactions<I: IntoIterator<Item = TransactionId>>(&mut self, transaction_ids: I, should_throttle: bool) {
-        self.transaction_ids.enqueue_chunk(transaction_ids);
+        match self.stem.as_ref() {
+            Some(stem) => self.transaction_ids.enqueue_chunk(transaction_ids.into_iter().filter(|id| !stem.is_embargoed(id))),
+            None => self.transaction_ids.enqueue_chunk(transaction_ids),
+        }
 
         let now = Instant::now();
         if now < self.last_broadcast_time + BROADCAST_INTERVAL && self.transaction_ids.len() < MAX_INV_PER_TX_INV_MSG {
@@ -92,6 +104,40 @@ impl TransactionsSpread {
         self.last_broadcast_time = Instant::now();
     }
 
+    /// Relays the given transactions along the stem if the stem phase is enabled, or broadcasts them otherwise.
+    ///
+    /// `source` is the peer the transactions were received from along its stem, or `None` for locally submitted transactions.
+    pub async fn relay_stem_transactions(&mut self, transactions: Vec<Arc<Transaction>>, source: Option<PeerKey>) {
+        let transaction_ids = match self.stem.as_mut() {
+            Some(stem) => stem.relay(transactions, source).await,
+            None => transactions.iter().map(|tx| tx.id()).collect(),
+        };
+        self.broadcast_transactions(transaction_ids, false).await;
+    }
+
+    pub fn is_stem_embargoed(&self, transaction_id: &TransactionId) -> bool {
+        self.stem.as_ref().is_some_and(|stem| stem.is_embargoed(transaction_id))
+    }
+
+    /// Lifts the stem embargo of the given transactions, which were seen diffused by the network
+    pub fn lift_stem_embargoes(&mut self, transaction_ids: &[TransactionId]) {
+        if let Some(stem) = self.stem.as_mut() {
+            stem.lift_embargoes(transaction_ids);
+        }
+    }
+
+    /// Broadcasts the stemmed transactions whose embargo expired before they were seen diffused by the network
+    pub async fn broadcast_expired_stem_transactions(&mut self) {
+        let Some(stem) = self.stem.as_mut() else {
+            return;
+        };
+        let transaction_ids = stem.expire_embargoes(Instant::now());
+        if !transaction_ids.is_empty() {
+            debug!("Transaction propagation: {} stemmed transactions reached their embargo", transaction_ids.len());
+            self.broadcast_transactions(transaction_ids, false).await;
+        }
+    }
+
     async fn broadcast(&self, msg: VecnodMessage, should_throttle: bool) {
         if should_throttle {
             // TODO: Figure out a better number
@@ -65,9 +65,14 @@ impl AsyncService for P2pService {
         // Prepare a shutdown signal receiver
         let shutdown_signal = self.shutdown.listener.clone();
 
-        let p2p_adaptor =
-            Adaptor::bidirectional(self.listen, self.flow_context.hub().clone(), self.flow_context.clone(), self.counters.clone())
-                .unwrap();
+        let p2p_adaptor = Adaptor::bidirectional_with_proxy(
+            self.listen,
+            self.flow_context.hub().clone(),
+            self.flow_context.clone(),
+            self.counters.clone(),
+            self.flow_context.config.proxy,
+        )
+        .unwrap();
         let connection_manager = ConnectionManager::new(
             p2p_adaptor.clone(),
             self.outbound_target,
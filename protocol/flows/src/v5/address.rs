@@ -1,13 +1,15 @@
-use crate::{flow_context::FlowContext, flow_trait::Flow};
+use crate::{
+    flow_context::{FlowContext, ONION_ADDRESS_PROTOCOL_VERSION},
+    flow_trait::Flow,
+};
 use itertools::Itertools;
-use vecno_addressmanager::NetAddress;
 use vecno_p2p_lib::{
     common::ProtocolError,
     dequeue, dequeue_with_timeout, make_message,
     pb::{vecnod_message::Payload, AddressesMessage, RequestAddressesMessage},
     IncomingRoute, Router,
 };
-use vecno_utils::networking::IpAddress;
+use vecno_utils::networking::PeerAddress;
 use rand::seq::SliceRandom;
 use std::sync::Arc;
 
@@ -49,13 +51,13 @@ impl ReceiveAddressesFlow {
             .await?;
 
         let msg = dequeue_with_timeout!(self.incoming_route, Payload::Addresses)?;
-        let address_list: Vec<(IpAddress, u16)> = msg.try_into()?;
+        let address_list: Vec<PeerAddress> = msg.try_into()?;
         if address_list.len() > MAX_ADDRESSES_RECEIVE {
             return Err(ProtocolError::OtherOwned(format!("address count {} exceeded {}", address_list.len(), MAX_ADDRESSES_RECEIVE)));
         }
         let mut amgr_lock = self.ctx.address_manager.lock();
-        for (ip, port) in address_list {
-            amgr_lock.add_address(NetAddress::new(ip, port))
+        for address in address_list {
+            amgr_lock.add_peer_address(address)
         }
 
         Ok(())
@@ -87,11 +89,17 @@ impl SendAddressesFlow {
     async fn start_impl(&mut self) -> Result<(), ProtocolError> {
         loop {
             dequeue!(self.incoming_route, Payload::RequestAddresses)?;
-            let addresses = self.ctx.address_manager.lock().iterate_addresses().collect_vec();
-            let address_list = addresses
-                .choose_multiple(&mut rand::thread_rng(), MAX_ADDRESSES_SEND)
-                .map(|addr| (addr.ip, addr.port).into())
-                .collect();
+            // Peers unaware of onion addresses would only see the IPs standing for them, which they cannot connect to
+            let supports_onion = self.router.properties().protocol_version >= ONION_ADDRESS_PROTOCOL_VERSION;
+            let addresses = self
+                .ctx
+                .address_manager
+                .lock()
+                .iterate_peer_addresses()
+                .filter(|addr| supports_onion || matches!(addr, PeerAddress::Ip(_)))
+                .collect_vec();
+            let address_list =
+                addresses.choose_multiple(&mut rand::thread_rng(), MAX_ADDRESSES_SEND).map(|addr| (*addr).into()).collect();
             self.router.enqueue(make_message!(Payload::Addresses, AddressesMessage { address_list })).await?;
         }
     }
@@ -1,20 +1,31 @@
 use crate::{
-    flow_context::{BlockLogEvent, FlowContext, RequestScope},
+    flow_context::{BlockLogEvent, FlowContext, RequestScope, COMPACT_BLOCK_RELAY_PROTOCOL_VERSION},
     flow_trait::Flow,
     flowcontext::orphans::OrphanOutput,
 };
-use vecno_consensus_core::{api::BlockValidationFutures, block::Block, blockstatus::BlockStatus, errors::block::RuleError};
+use vecno_consensus_core::{
+    api::BlockValidationFutures, block::Block, blockstatus::BlockStatus, errors::block::RuleError, tx::Transaction,
+};
 use vecno_consensusmanager::{BlockProcessingBatch, ConsensusProxy};
 use vecno_core::debug;
 use vecno_hashes::Hash;
+use vecno_mining::model::tx_query::TransactionQuery;
 use vecno_p2p_lib::{
     common::ProtocolError,
+    convert::model::compact::{short_transaction_id, CompactBlock, PartialBlock, ShortTransactionId},
     dequeue, dequeue_with_timeout, make_message, make_request,
-    pb::{vecnod_message::Payload, InvRelayBlockMessage, RequestBlockLocatorMessage, RequestRelayBlocksMessage},
+    pb::{
+        vecnod_message::Payload, InvRelayBlockMessage, RequestBlockLocatorMessage, RequestBlockTransactionsMessage,
+        RequestCompactBlockMessage, RequestRelayBlocksMessage,
+    },
     IncomingRoute, Router, SharedIncomingRoute,
 };
 use vecno_utils::channel::{JobSender, JobTrySendError as TrySendError};
-use std::{collections::VecDeque, sync::Arc};
+use std::{
+    collections::{HashSet, VecDeque},
+    future::Future,
+    sync::Arc,
+};
 
 pub struct RelayInvMessage {
     hash: Hash,
@@ -229,20 +240,20 @@ impl HandleRelayInvsFlow {
         let Some(request_scope) = self.ctx.try_adding_block_request(requested_hash) else {
             return Ok(None);
         };
-        self.router
-            .enqueue(make_request!(
-                Payload::RequestRelayBlocks,
-                RequestRelayBlocksMessage { hashes: vec![requested_hash.into()] },
-                request_id
-            ))
-            .await?;
-        let msg = dequeue_with_timeout!(self.msg_route, Payload::Block)?;
-        let block: Block = msg.try_into()?;
-        if block.hash() != requested_hash {
-            Err(ProtocolError::OtherOwned(format!("requested block hash {} but got block {}", requested_hash, block.hash())))
-        } else {
-            Ok(Some((block, request_scope)))
-        }
+        let mining_manager = self.ctx.mining_manager().clone();
+        let get_known_transactions = move |short_ids: HashSet<ShortTransactionId>| async move {
+            mining_manager
+                .get_transactions_by_filter(
+                    move |id| short_ids.contains(&short_transaction_id(requested_hash, *id)),
+                    TransactionQuery::All,
+                )
+                .await
+                .into_iter()
+                .map(|mtx| (*mtx.tx).clone())
+                .collect::<Vec<_>>()
+        };
+        let block = request_relay_block(&self.router, &mut self.msg_route, requested_hash, request_id, get_known_transactions).await?;
+        Ok(Some((block, request_scope)))
     }
 
     /// Process the orphan block. Returns `Some(BlockProcessingBatch)` if the block has no missing roots, where
@@ -357,3 +368,296 @@ impl HandleRelayInvsFlow {
         Ok(false)
     }
 }
+
+/// Requests the relay block of `requested_hash` from the peer of `router`. Peers supporting compact blocks are asked for
+/// the compact form, rebuilt out of the transactions `get_known_transactions` returns for its short ids. The block is
+/// requested in full from other peers, or if it does not match its header once rebuilt.
+async fn request_relay_block<F, Fut>(
+    router: &Router,
+    msg_route: &mut IncomingRoute,
+    requested_hash: Hash,
+    request_id: u32,
+    get_known_transactions: F,
+) -> Result<Block, ProtocolError>
+where
+    F: FnOnce(HashSet<ShortTransactionId>) -> Fut,
+    Fut: Future<Output = Vec<Transaction>>,
+{
+    if router.properties().protocol_version >= COMPACT_BLOCK_RELAY_PROTOCOL_VERSION {
+        if let Some(block) = request_compact_block(router, msg_route, requested_hash, request_id, get_known_transactions).await? {
+            return Ok(block);
+        }
+        debug!("Compact block {} does not match its header once rebuilt, requesting it in full", requested_hash);
+    }
+    router
+        .enqueue(make_request!(
+            Payload::RequestRelayBlocks,
+            RequestRelayBlocksMessage { hashes: vec![requested_hash.into()] },
+            request_id
+        ))
+        .await?;
+    let msg = dequeue_with_timeout!(msg_route, Payload::Block)?;
+    let block: Block = msg.try_into()?;
+    if block.hash() != requested_hash {
+        Err(ProtocolError::OtherOwned(format!("requested block hash {} but got block {}", requested_hash, block.hash())))
+    } else {
+        Ok(block)
+    }
+}
+
+/// Requests the compact form of a relay block and rebuilds it out of the known transactions, requesting the missing
+/// ones from the peer. Returns `None` if the rebuilt block does not match its header.
+async fn request_compact_block<F, Fut>(
+    router: &Router,
+    msg_route: &mut IncomingRoute,
+    requested_hash: Hash,
+    request_id: u32,
+    get_known_transactions: F,
+) -> Result<Option<Block>, ProtocolError>
+where
+    F: FnOnce(HashSet<ShortTransactionId>) -> Fut,
+    Fut: Future<Output = Vec<Transaction>>,
+{
+    router
+        .enqueue(make_request!(
+            Payload::RequestCompactBlock,
+            RequestCompactBlockMessage { hash: Some(requested_hash.into()) },
+            request_id
+        ))
+        .await?;
+    let msg = dequeue_with_timeout!(msg_route, Payload::CompactBlock)?;
+    let compact_block: CompactBlock = msg.try_into()?;
+    if compact_block.hash() != requested_hash {
+        return Err(ProtocolError::OtherOwned(format!(
+            "requested compact block hash {} but got block {}",
+            requested_hash,
+            compact_block.hash()
+        )));
+    }
+
+    let known_transactions = get_known_transactions(compact_block.short_ids.iter().copied().collect()).await;
+    let mut partial_block = PartialBlock::new(compact_block, known_transactions)?;
+
+    let missing_indexes = partial_block.missing_indexes();
+    if !missing_indexes.is_empty() {
+        debug!("Requesting {} missing transactions of compact block {}", missing_indexes.len(), requested_hash);
+        router
+            .enqueue(make_request!(
+                Payload::RequestBlockTransactions,
+                RequestBlockTransactionsMessage { block_hash: Some(requested_hash.into()), indexes: missing_indexes },
+                request_id
+            ))
+            .await?;
+        let msg = dequeue_with_timeout!(msg_route, Payload::BlockTransactions)?;
+        let (hash, transactions): (Hash, Vec<Transaction>) = msg.try_into()?;
+        if hash != requested_hash {
+            return Err(ProtocolError::OtherOwned(format!(
+                "requested transactions of block {} but got transactions of block {}",
+                requested_hash, hash
+            )));
+        }
+        partial_block.fill_missing(transactions)?;
+    }
+    Ok(partial_block.into_block())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v5::blockrelay::handle_compact_requests::serve_compact_block_request;
+    use futures::future::{ready, Ready};
+    use vecno_consensus_core::{
+        errors::consensus::ConsensusError,
+        header::Header,
+        merkle::calc_hash_merkle_root,
+        subnets::{SUBNETWORK_ID_COINBASE, SUBNETWORK_ID_NATIVE},
+        tx::{ScriptPublicKey, TransactionId, TransactionInput, TransactionOutpoint, TransactionOutput},
+    };
+    use vecno_core::time::unix_now;
+    use vecno_hashes::ZERO_HASH;
+    use vecno_p2p_lib::{
+        make_response, pb, Adaptor, ConnectionInitializer, Hub, PeerProperties, VecnodHandshake, VecnodMessagePayloadType,
+    };
+    use vecno_utils::networking::NetAddress;
+    use std::{collections::HashMap, str::FromStr, time::Duration};
+    use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+    use uuid::Uuid;
+
+    /// Performs the handshake with a peer supporting compact blocks and returns a route for the `msg_types` messages
+    async fn handshake(router: &Arc<Router>, msg_types: Vec<VecnodMessagePayloadType>) -> Result<IncomingRoute, ProtocolError> {
+        let mut handshake = VecnodHandshake::new(router);
+        router.start();
+        let version_message = pb::VersionMessage {
+            protocol_version: COMPACT_BLOCK_RELAY_PROTOCOL_VERSION,
+            services: 0,
+            timestamp: unix_now() as i64,
+            address: None,
+            id: Vec::from(Uuid::new_v4().as_bytes()),
+            user_agent: String::new(),
+            disable_relay_tx: false,
+            subnetwork_id: None,
+            network: "vecno-mainnet".to_string(),
+        };
+        handshake.handshake(version_message).await?;
+        router
+            .set_properties(Arc::new(PeerProperties { protocol_version: COMPACT_BLOCK_RELAY_PROTOCOL_VERSION, ..Default::default() }));
+        let incoming_route = router.subscribe(msg_types);
+        handshake.exchange_ready_messages().await?;
+        Ok(incoming_route)
+    }
+
+    /// Initializer of a peer serving the compact blocks out of `compact_blocks` and the full blocks out of `blocks`,
+    /// forwarding the type of every request it gets to a channel
+    struct BlockServerInitializer {
+        compact_blocks: Arc<HashMap<Hash, Block>>,
+        blocks: Arc<HashMap<Hash, Block>>,
+        sender: UnboundedSender<VecnodMessagePayloadType>,
+    }
+
+    #[async_trait::async_trait]
+    impl ConnectionInitializer for BlockServerInitializer {
+        async fn initialize_connection(&self, router: Arc<Router>) -> Result<(), ProtocolError> {
+            let mut incoming_route = handshake(
+                &router,
+                vec![
+                    VecnodMessagePayloadType::RequestCompactBlock,
+                    VecnodMessagePayloadType::RequestBlockTransactions,
+                    VecnodMessagePayloadType::RequestRelayBlocks,
+                ],
+            )
+            .await?;
+            let (compact_blocks, blocks, sender) = (self.compact_blocks.clone(), self.blocks.clone(), self.sender.clone());
+            tokio::spawn(async move {
+                while let Some(msg) = incoming_route.recv().await {
+                    let _ = sender.send(msg.payload.as_ref().unwrap().into());
+                    if let Some(Payload::RequestRelayBlocks(payload)) = msg.payload {
+                        for hash in Vec::<Hash>::try_from(payload).unwrap() {
+                            router.enqueue(make_response!(Payload::Block, (&blocks[&hash]).into(), msg.request_id)).await.unwrap();
+                        }
+                    } else {
+                        let get_block =
+                            |hash: Hash| ready(compact_blocks.get(&hash).cloned().ok_or(ConsensusError::BlockNotFound(hash)));
+                        serve_compact_block_request(&router, msg, get_block).await.unwrap();
+                    }
+                }
+            });
+            Ok(())
+        }
+    }
+
+    /// Initializer of a peer requesting blocks, forwarding its router and the route of the responses to a channel
+    struct BlockClientInitializer {
+        sender: UnboundedSender<(Arc<Router>, IncomingRoute)>,
+    }
+
+    #[async_trait::async_trait]
+    impl ConnectionInitializer for BlockClientInitializer {
+        async fn initialize_connection(&self, router: Arc<Router>) -> Result<(), ProtocolError> {
+            let incoming_route = handshake(
+                &router,
+                vec![
+                    VecnodMessagePayloadType::CompactBlock,
+                    VecnodMessagePayloadType::BlockTransactions,
+                    VecnodMessagePayloadType::Block,
+                ],
+            )
+            .await?;
+            let _ = self.sender.send((router, incoming_route));
+            Ok(())
+        }
+    }
+
+    fn transaction(seed: u64) -> Transaction {
+        let inputs = vec![TransactionInput::new(TransactionOutpoint::new(Hash::from_u64_word(seed), 0), vec![], 0, 1)];
+        let outputs = vec![TransactionOutput::new(seed, ScriptPublicKey::from_vec(0, vec![]))];
+        Transaction::new(0, inputs, outputs, 0, SUBNETWORK_ID_NATIVE, 0, vec![])
+    }
+
+    fn block(seed: u64, transactions: &[u64]) -> Block {
+        let coinbase = Transaction::new(0, vec![], vec![], 0, SUBNETWORK_ID_COINBASE, 0, seed.to_le_bytes().to_vec());
+        let transactions = [coinbase].into_iter().chain(transactions.iter().copied().map(transaction)).collect::<Vec<_>>();
+        let hash_merkle_root = calc_hash_merkle_root(transactions.iter(), true);
+        let parents = vec![vec![Hash::from_u64_word(seed)]];
+        let header =
+            Header::new_finalized(0, parents, hash_merkle_root, ZERO_HASH, ZERO_HASH, 0, 0, 0, 0, Default::default(), 0, ZERO_HASH);
+        Block::new(header, transactions)
+    }
+
+    /// Returns a lookup of the mempool transactions matching the short ids of the block, as the relay flow does
+    fn mempool_lookup(mempool: &[Transaction], hash: Hash) -> impl FnOnce(HashSet<ShortTransactionId>) -> Ready<Vec<Transaction>> {
+        let mempool = mempool.to_vec();
+        move |short_ids| ready(mempool.into_iter().filter(|tx| short_ids.contains(&short_transaction_id(hash, tx.id()))).collect())
+    }
+
+    fn transaction_ids(block: &Block) -> Vec<TransactionId> {
+        block.transactions.iter().map(|tx| tx.id()).collect()
+    }
+
+    /// Returns the type of the requests served by the peer since the last call
+    fn served_requests(receiver: &mut UnboundedReceiver<VecnodMessagePayloadType>) -> Vec<VecnodMessagePayloadType> {
+        let mut requests = vec![];
+        while let Ok(request) = receiver.try_recv() {
+            requests.push(request);
+        }
+        requests
+    }
+
+    #[tokio::test]
+    async fn test_compact_block_relay() {
+        vecno_core::log::try_init_logger("debug");
+
+        let mempool = (1..=6).map(transaction).collect::<Vec<_>>();
+        let (known, partially_known, colliding) = (block(1, &[1, 2, 3]), block(2, &[4, 7, 8]), block(3, &[9]));
+        // The transaction 5 of the mempool standing for a short id collision with the transaction 9
+        let collision = Block::from_arcs(colliding.header.clone(), Arc::new(vec![colliding.transactions[0].clone(), transaction(5)]));
+        let compact_blocks = [known.clone(), partially_known.clone(), collision].into_iter().map(|b| (b.hash(), b)).collect();
+        let blocks = [known.clone(), partially_known.clone(), colliding.clone()].into_iter().map(|b| (b.hash(), b)).collect();
+
+        let (sender, mut requests) = unbounded_channel();
+        let initializer = BlockServerInitializer { compact_blocks: Arc::new(compact_blocks), blocks: Arc::new(blocks), sender };
+        let address = NetAddress::from_str("[::1]:50061").unwrap();
+        let _server = Adaptor::bidirectional(address, Hub::new(), Arc::new(initializer), Default::default()).unwrap();
+        let (sender, mut receiver) = unbounded_channel();
+        let hub = Hub::new();
+        let client = Adaptor::client_only(hub.clone(), Arc::new(BlockClientInitializer { sender }), Default::default());
+        client.connect_peer_with_retries("[::1]:50061".to_string(), 16, Duration::from_secs(1)).await.expect("peer connection failed");
+        let (router, mut msg_route) = receiver.recv().await.unwrap();
+        let request_id = msg_route.id();
+
+        // A block whose transactions are all in the mempool is rebuilt out of its compact form alone
+        let hash = known.hash();
+        let block = request_relay_block(&router, &mut msg_route, hash, request_id, mempool_lookup(&mempool, hash)).await.unwrap();
+        assert_eq!(transaction_ids(&block), transaction_ids(&known));
+        assert_eq!(served_requests(&mut requests), vec![VecnodMessagePayloadType::RequestCompactBlock]);
+
+        // The transactions missing from the mempool are requested from the peer
+        let hash = partially_known.hash();
+        let block = request_relay_block(&router, &mut msg_route, hash, request_id, mempool_lookup(&mempool, hash)).await.unwrap();
+        assert_eq!(transaction_ids(&block), transaction_ids(&partially_known));
+        assert_eq!(
+            served_requests(&mut requests),
+            vec![VecnodMessagePayloadType::RequestCompactBlock, VecnodMessagePayloadType::RequestBlockTransactions]
+        );
+
+        // A short id collision with a mempool transaction fails the merkle root check, the block being requested in full
+        let hash = colliding.hash();
+        let block = request_relay_block(&router, &mut msg_route, hash, request_id, mempool_lookup(&mempool, hash)).await.unwrap();
+        assert_eq!(transaction_ids(&block), transaction_ids(&colliding));
+        assert_eq!(
+            served_requests(&mut requests),
+            vec![VecnodMessagePayloadType::RequestCompactBlock, VecnodMessagePayloadType::RequestRelayBlocks]
+        );
+
+        // Peers not supporting compact blocks are requested full blocks
+        router.set_properties(Arc::new(PeerProperties {
+            protocol_version: COMPACT_BLOCK_RELAY_PROTOCOL_VERSION - 1,
+            ..Default::default()
+        }));
+        let hash = known.hash();
+        let block = request_relay_block(&router, &mut msg_route, hash, request_id, mempool_lookup(&mempool, hash)).await.unwrap();
+        assert_eq!(transaction_ids(&block), transaction_ids(&known));
+        assert_eq!(served_requests(&mut requests), vec![VecnodMessagePayloadType::RequestRelayBlocks]);
+
+        hub.terminate_all_peers().await;
+    }
+}
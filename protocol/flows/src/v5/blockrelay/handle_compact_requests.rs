@@ -0,0 +1,88 @@
+use crate::{flow_context::FlowContext, flow_trait::Flow};
+use vecno_consensus_core::{block::Block, errors::consensus::ConsensusResult};
+use vecno_core::debug;
+use vecno_hashes::Hash;
+use vecno_p2p_lib::{
+    common::ProtocolError,
+    convert::model::compact::CompactBlock,
+    make_response,
+    pb::{vecnod_message::Payload, BlockTransactionsMessage, VecnodMessage},
+    IncomingRoute, Router,
+};
+use std::{future::Future, sync::Arc};
+
+/// Serves the compact blocks requested by a peer, along with the transactions it could not find in its mempool
+pub struct HandleCompactBlockRequests {
+    ctx: FlowContext,
+    router: Arc<Router>,
+    incoming_route: IncomingRoute,
+}
+
+#[async_trait::async_trait]
+impl Flow for HandleCompactBlockRequests {
+    fn router(&self) -> Option<Arc<Router>> {
+        Some(self.router.clone())
+    }
+
+    async fn start(&mut self) -> Result<(), ProtocolError> {
+        self.start_impl().await
+    }
+}
+
+impl HandleCompactBlockRequests {
+    pub fn new(ctx: FlowContext, router: Arc<Router>, incoming_route: IncomingRoute) -> Self {
+        Self { ctx, router, incoming_route }
+    }
+
+    async fn start_impl(&mut self) -> Result<(), ProtocolError> {
+        loop {
+            let Some(msg) = self.incoming_route.recv().await else {
+                return Err(ProtocolError::ConnectionClosed);
+            };
+            let session = self.ctx.consensus().unguarded_session();
+            serve_compact_block_request(&self.router, msg, |hash| session.async_get_block(hash)).await?;
+        }
+    }
+}
+
+/// Serves a compact block or missing transactions request of the peer of `router`, out of the blocks returned by `get_block`
+pub(super) async fn serve_compact_block_request<F, Fut>(router: &Router, msg: VecnodMessage, get_block: F) -> Result<(), ProtocolError>
+where
+    F: Fn(Hash) -> Fut,
+    Fut: Future<Output = ConsensusResult<Block>>,
+{
+    let request_id = msg.request_id;
+    match msg.payload {
+        Some(Payload::RequestCompactBlock(payload)) => {
+            let hash: Hash = payload.try_into()?;
+            let block = get_block(hash).await?;
+            let compact_block = CompactBlock::from_block(&block);
+            router.enqueue(make_response!(Payload::CompactBlock, (&compact_block).into(), request_id)).await?;
+            debug!("relayed compact block with hash {} to peer {}", hash, router);
+        }
+        Some(Payload::RequestBlockTransactions(payload)) => {
+            let (hash, indexes): (Hash, Vec<u32>) = payload.try_into()?;
+            let block = get_block(hash).await?;
+            let transactions = indexes
+                .into_iter()
+                .map(|i| block.transactions.get(i as usize).map(|tx| tx.into()))
+                .collect::<Option<Vec<_>>>()
+                .ok_or(ProtocolError::Other("requested transaction index is out of the block range"))?;
+            router
+                .enqueue(make_response!(
+                    Payload::BlockTransactions,
+                    BlockTransactionsMessage { block_hash: Some(hash.into()), transactions },
+                    request_id
+                ))
+                .await?;
+            debug!("relayed missing transactions of compact block {} to peer {}", hash, router);
+        }
+        _ => {
+            return Err(ProtocolError::UnexpectedMessage(
+                stringify!(Payload::RequestCompactBlock | Payload::RequestBlockTransactions),
+                msg.payload.as_ref().map(|v| v.into()),
+            ))
+        }
+    }
+    Ok(())
+}
@@ -1,2 +1,3 @@
 pub mod flow;
+pub mod handle_compact_requests;
 pub mod handle_requests;
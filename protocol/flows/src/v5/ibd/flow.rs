@@ -1,5 +1,6 @@
 use crate::{
     flow_context::FlowContext,
+    flowcontext::misbehavior::Misbehavior,
     v5::{
         ibd::{HeadersChunkStream, TrustedEntryStream},
         Flow,
@@ -85,6 +86,9 @@ impl IbdFlow {
                     Ok(_) => info!("IBD with peer {} completed successfully", self.router),
                     Err(e) => {
                         info!("IBD with peer {} completed with error: {}", self.router, e);
+                        if matches!(e, ProtocolError::Timeout(_)) {
+                            self.ctx.report_misbehavior(&self.router, Misbehavior::SlowIbdResponse).await;
+                        }
                         return Err(e);
                     }
                 }
@@ -1,7 +1,7 @@
 use crate::{
     flow_context::{FlowContext, RequestScope},
     flow_trait::Flow,
-    flowcontext::transactions::MAX_INV_PER_TX_INV_MSG,
+    flowcontext::{misbehavior::Misbehavior, transactions::MAX_INV_PER_TX_INV_MSG},
 };
 use vecno_consensus_core::tx::{Transaction, TransactionId};
 use vecno_consensusmanager::ConsensusProxy;
@@ -112,6 +112,11 @@ impl RelayTransactionsFlow {
                 return Err(ProtocolError::Other("Number of invs in tx inv message is over the limit"));
             }
 
+            // Transactions announced by a peer are diffused already, so they need no embargo anymore
+            if self.ctx.config.tx_stem_relay {
+                self.ctx.lift_stem_embargoes(&inv).await;
+            }
+
             let session = self.ctx.consensus().unguarded_session();
 
             // Transaction relay is disabled if the node is out of sync
@@ -225,10 +230,11 @@ impl RelayTransactionsFlow {
             match res {
                 Ok(_) => {}
                 Err(MiningManagerError::MempoolError(RuleError::RejectInvalid(transaction_id))) => {
-                    // TODO: discuss a banning process
+                    self.ctx.report_misbehavior(&self.router, Misbehavior::InvalidTransaction).await;
                     return Err(ProtocolError::MisbehavingPeer(format!("rejected invalid transaction {}", transaction_id)));
                 }
                 Err(MiningManagerError::MempoolError(RuleError::RejectNonStandard(..))) => {
+                    self.ctx.report_misbehavior(&self.router, Misbehavior::NonStandardTransaction).await;
                     self.spam_counter += 1;
                     if self.spam_counter % 100 == 0 {
                         vecno_core::warn!("Peer {} has shared {} spam/non-standard txs ({:?})", self.router, self.spam_counter, res);
@@ -282,9 +288,13 @@ impl RequestTransactionsFlow {
             let msg = dequeue!(self.incoming_route, Payload::RequestTransactions)?;
             let tx_ids: Vec<_> = msg.try_into()?;
             for transaction_id in tx_ids {
-                if let Some(mutable_tx) =
+                // Transactions under stem embargo are reported as missing, so that they cannot be used for locating their origin
+                let mutable_tx = if self.ctx.is_stem_embargoed(&transaction_id).await {
+                    None
+                } else {
                     self.ctx.mining_manager().clone().get_transaction(transaction_id, TransactionQuery::TransactionsOnly).await
-                {
+                };
+                if let Some(mutable_tx) = mutable_tx {
                     // trace!("Send transaction {} to {}", mutable_tx.id(), self.router.identity());
                     self.router.enqueue(make_message!(Payload::Transaction, (&*mutable_tx.tx).into())).await?;
                 } else {
@@ -0,0 +1,78 @@
+use crate::{
+    flow_context::FlowContext,
+    flow_trait::Flow,
+    flowcontext::{misbehavior::Misbehavior, transactions::MAX_INV_PER_TX_INV_MSG},
+};
+use vecno_consensus_core::tx::Transaction;
+use vecno_mining::{
+    errors::MiningManagerError,
+    mempool::{
+        errors::RuleError,
+        tx::{Orphan, Priority, RbfPolicy},
+    },
+};
+use vecno_p2p_lib::{common::ProtocolError, dequeue, pb::vecnod_message::Payload, IncomingRoute, Router};
+use std::sync::Arc;
+
+/// Flow listening to StemTransactions messages, adds the transactions to the mempool and relays them further along
+/// the stem, or diffuses them to the rest of the network.
+pub struct ReceiveStemTransactionsFlow {
+    ctx: FlowContext,
+    router: Arc<Router>,
+    incoming_route: IncomingRoute,
+}
+
+#[async_trait::async_trait]
+impl Flow for ReceiveStemTransactionsFlow {
+    fn router(&self) -> Option<Arc<Router>> {
+        Some(self.router.clone())
+    }
+
+    async fn start(&mut self) -> Result<(), ProtocolError> {
+        self.start_impl().await
+    }
+}
+
+impl ReceiveStemTransactionsFlow {
+    pub fn new(ctx: FlowContext, router: Arc<Router>, incoming_route: IncomingRoute) -> Self {
+        Self { ctx, router, incoming_route }
+    }
+
+    async fn start_impl(&mut self) -> Result<(), ProtocolError> {
+        loop {
+            let transactions: Vec<Transaction> = dequeue!(self.incoming_route, Payload::StemTransactions)?.try_into()?;
+            if transactions.len() > MAX_INV_PER_TX_INV_MSG {
+                return Err(ProtocolError::Other("Number of transactions in stem transactions message is over the limit"));
+            }
+
+            let session = self.ctx.consensus().unguarded_session();
+
+            // Transaction relay is disabled if the node is out of sync
+            if !self.ctx.is_nearly_synced(&session).await {
+                continue;
+            }
+
+            // Orphans are not accepted along the stem since they would get diffused without a stem phase once unorphaned
+            let insert_results = self
+                .ctx
+                .mining_manager()
+                .clone()
+                .validate_and_insert_transaction_batch(&session, transactions, Priority::Low, Orphan::Forbidden, RbfPolicy::Allowed)
+                .await;
+
+            let mut accepted = Vec::with_capacity(insert_results.len());
+            for res in insert_results {
+                match res {
+                    Ok(transaction) => accepted.push(transaction),
+                    Err(MiningManagerError::MempoolError(RuleError::RejectInvalid(transaction_id))) => {
+                        self.ctx.report_misbehavior(&self.router, Misbehavior::InvalidTransaction).await;
+                        return Err(ProtocolError::MisbehavingPeer(format!("rejected invalid transaction {}", transaction_id)));
+                    }
+                    Err(_) => {}
+                }
+            }
+
+            self.ctx.relay_stem_transactions(accepted, Some(self.router.key())).await;
+        }
+    }
+}
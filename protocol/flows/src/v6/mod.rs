@@ -1,6 +1,6 @@
 use crate::v5::{
     address::{ReceiveAddressesFlow, SendAddressesFlow},
-    blockrelay::{flow::HandleRelayInvsFlow, handle_requests::HandleRelayBlockRequests},
+    blockrelay::{flow::HandleRelayInvsFlow, handle_compact_requests::HandleCompactBlockRequests, handle_requests::HandleRelayBlockRequests},
     ibd::IbdFlow,
     ping::{ReceivePingsFlow, SendPingsFlow},
     request_antipast::HandleAntipastRequests,
@@ -10,7 +10,10 @@ use crate::v5::{
     request_ibd_chain_block_locator::RequestIbdChainBlockLocatorFlow,
     request_pp_proof::RequestPruningPointProofFlow,
     request_pruning_point_utxo_set::RequestPruningPointUtxoSetFlow,
-    txrelay::flow::{RelayTransactionsFlow, RequestTransactionsFlow},
+    txrelay::{
+        flow::{RelayTransactionsFlow, RequestTransactionsFlow},
+        stem::ReceiveStemTransactionsFlow,
+    },
 };
 use crate::{flow_context::FlowContext, flow_trait::Flow};
 
@@ -54,6 +57,11 @@ pub fn register(ctx: FlowContext, router: Arc<Router>) -> Vec<Box<dyn Flow>> {
             router.clone(),
             router.subscribe(vec![VecnodMessagePayloadType::RequestRelayBlocks]),
         )),
+        Box::new(HandleCompactBlockRequests::new(
+            ctx.clone(),
+            router.clone(),
+            router.subscribe(vec![VecnodMessagePayloadType::RequestCompactBlock, VecnodMessagePayloadType::RequestBlockTransactions]),
+        )),
         Box::new(ReceivePingsFlow::new(ctx.clone(), router.clone(), router.subscribe(vec![VecnodMessagePayloadType::Ping]))),
         Box::new(SendPingsFlow::new(ctx.clone(), router.clone(), router.subscribe(vec![VecnodMessagePayloadType::Pong]))),
         Box::new(RequestHeadersFlow::new(
@@ -112,6 +120,11 @@ pub fn register(ctx: FlowContext, router: Arc<Router>) -> Vec<Box<dyn Flow>> {
             router.clone(),
             router.subscribe(vec![VecnodMessagePayloadType::RequestTransactions]),
         )),
+        Box::new(ReceiveStemTransactionsFlow::new(
+            ctx.clone(),
+            router.clone(),
+            router.subscribe(vec![VecnodMessagePayloadType::StemTransactions]),
+        )),
         Box::new(ReceiveAddressesFlow::new(ctx.clone(), router.clone(), router.subscribe(vec![VecnodMessagePayloadType::Addresses]))),
         Box::new(SendAddressesFlow::new(
             ctx.clone(),
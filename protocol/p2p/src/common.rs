@@ -1,4 +1,4 @@
-use crate::{convert::error::ConversionError, core::peer::PeerKey, VecnodMessagePayloadType};
+use crate::{convert::error::ConversionError, core::peer::PeerKey, secure::SecureTransportError, VecnodMessagePayloadType};
 use vecno_consensus_core::errors::{block::RuleError, consensus::ConsensusError, pruning::PruningImportError};
 use vecno_mining_errors::manager::MiningManagerError;
 use std::time::Duration;
@@ -40,6 +40,9 @@ pub enum ProtocolError {
     #[error("{0}")]
     IdentityError(#[from] uuid::Error),
 
+    #[error("{0}")]
+    SecureTransportError(#[from] SecureTransportError),
+
     #[error("{0}")]
     Other(&'static str),
 
@@ -12,6 +12,9 @@ pub enum ConversionError {
     #[error("IP has illegal length {0}")]
     IllegalIPLength(usize),
 
+    #[error("Onion public key has illegal length {0}")]
+    IllegalOnionPublicKeyLength(usize),
+
     #[error("Bytes size mismatch error {0}")]
     ArrayBytesSizeError(#[from] std::array::TryFromSliceError),
 
@@ -10,10 +10,10 @@ use crate::pb as protowire;
 use vecno_consensus_core::{
     header::Header,
     pruning::{PruningPointProof, PruningPointsList},
-    tx::{TransactionId, TransactionOutpoint, UtxoEntry},
+    tx::{Transaction, TransactionId, TransactionOutpoint, UtxoEntry},
 };
 use vecno_hashes::Hash;
-use vecno_utils::networking::{IpAddress, PeerId};
+use vecno_utils::networking::{PeerAddress, PeerId};
 
 use std::{collections::HashMap, sync::Arc};
 
@@ -191,7 +191,7 @@ impl TryFrom<protowire::BlockLocatorMessage> for Vec<Hash> {
     }
 }
 
-impl TryFrom<protowire::AddressesMessage> for Vec<(IpAddress, u16)> {
+impl TryFrom<protowire::AddressesMessage> for Vec<PeerAddress> {
     type Error = ConversionError;
 
     fn try_from(msg: protowire::AddressesMessage) -> Result<Self, Self::Error> {
@@ -236,3 +236,33 @@ impl TryFrom<protowire::RequestAntipastMessage> for (Hash, Hash) {
         Ok((msg.block_hash.try_into_ex()?, msg.context_hash.try_into_ex()?))
     }
 }
+
+impl TryFrom<protowire::RequestCompactBlockMessage> for Hash {
+    type Error = ConversionError;
+
+    fn try_from(msg: protowire::RequestCompactBlockMessage) -> Result<Self, Self::Error> {
+        msg.hash.try_into_ex()
+    }
+}
+
+impl TryFrom<protowire::RequestBlockTransactionsMessage> for (Hash, Vec<u32>) {
+    type Error = ConversionError;
+    fn try_from(msg: protowire::RequestBlockTransactionsMessage) -> Result<Self, Self::Error> {
+        Ok((msg.block_hash.try_into_ex()?, msg.indexes))
+    }
+}
+
+impl TryFrom<protowire::BlockTransactionsMessage> for (Hash, Vec<Transaction>) {
+    type Error = ConversionError;
+    fn try_from(msg: protowire::BlockTransactionsMessage) -> Result<Self, Self::Error> {
+        Ok((msg.block_hash.try_into_ex()?, msg.transactions.into_iter().map(|tx| tx.try_into()).collect::<Result<_, _>>()?))
+    }
+}
+
+impl TryFrom<protowire::StemTransactionsMessage> for Vec<Transaction> {
+    type Error = ConversionError;
+
+    fn try_from(msg: protowire::StemTransactionsMessage) -> Result<Self, Self::Error> {
+        msg.transactions.into_iter().map(|tx| tx.try_into()).collect()
+    }
+}
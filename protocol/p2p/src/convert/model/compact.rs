@@ -0,0 +1,201 @@
+//!
+//! Model structures of the compact block relay. A compact block carries the header of a relayed block along with
+//! short ids in place of the transactions the receiving node most likely holds in its mempool already.
+//!
+
+use crate::common::ProtocolError;
+use vecno_consensus_core::{
+    block::Block,
+    header::Header,
+    merkle::calc_hash_merkle_root,
+    tx::{Transaction, TransactionId},
+};
+use vecno_hashes::{Hash, HasherBase, ShortTransactionID};
+use std::{collections::HashMap, sync::Arc};
+
+/// Short id of a transaction within a compact block
+pub type ShortTransactionId = u64;
+
+/// Returns the short id of a transaction within the block of `block_hash`. Salting the short ids with the block hash
+/// prevents crafting colliding transactions ahead of the block.
+pub fn short_transaction_id(block_hash: Hash, transaction_id: TransactionId) -> ShortTransactionId {
+    let mut hasher = ShortTransactionID::new();
+    hasher.update(block_hash).update(transaction_id);
+    hasher.finalize().to_le_u64()[0]
+}
+
+pub struct CompactBlock {
+    pub header: Arc<Header>,
+
+    /// Short ids of the transactions which are not prefilled, in block order
+    pub short_ids: Vec<ShortTransactionId>,
+
+    /// Transactions sent in full along with their index within the block, sorted by index
+    pub prefilled_transactions: Vec<(u32, Transaction)>,
+}
+
+impl CompactBlock {
+    pub fn new(header: Arc<Header>, short_ids: Vec<ShortTransactionId>, prefilled_transactions: Vec<(u32, Transaction)>) -> Self {
+        Self { header, short_ids, prefilled_transactions }
+    }
+
+    /// Builds the compact form of `block`, prefilling its coinbase transaction which no peer can know in advance
+    pub fn from_block(block: &Block) -> Self {
+        let hash = block.hash();
+        let short_ids = block.transactions.iter().skip(1).map(|tx| short_transaction_id(hash, tx.id())).collect();
+        let prefilled_transactions = block.transactions.first().map(|tx| (0, tx.clone())).into_iter().collect();
+        Self::new(block.header.clone(), short_ids, prefilled_transactions)
+    }
+
+    pub fn hash(&self) -> Hash {
+        self.header.hash
+    }
+
+    pub fn transaction_count(&self) -> usize {
+        self.short_ids.len() + self.prefilled_transactions.len()
+    }
+}
+
+/// A block being rebuilt out of a compact block
+pub struct PartialBlock {
+    header: Arc<Header>,
+    transactions: Vec<Option<Transaction>>,
+}
+
+impl PartialBlock {
+    /// Places the prefilled transactions of `compact_block` and fills the other slots with the matching `known_transactions`.
+    /// A short id matching several known transactions is ambiguous, so its slot is left missing.
+    pub fn new(compact_block: CompactBlock, known_transactions: impl IntoIterator<Item = Transaction>) -> Result<Self, ProtocolError> {
+        let hash = compact_block.hash();
+        let mut known: HashMap<ShortTransactionId, Option<Transaction>> = HashMap::new();
+        for tx in known_transactions {
+            let id = tx.id();
+            known
+                .entry(short_transaction_id(hash, id))
+                .and_modify(|entry| {
+                    if entry.as_ref().is_some_and(|x| x.id() != id) {
+                        *entry = None;
+                    }
+                })
+                .or_insert(Some(tx));
+        }
+
+        let count = compact_block.transaction_count();
+        let mut transactions = vec![None; count];
+        let mut previous_index = None;
+        for (index, tx) in compact_block.prefilled_transactions {
+            if previous_index.is_some_and(|previous| index <= previous) || index as usize >= count {
+                return Err(ProtocolError::Other("invalid prefilled transaction index in compact block"));
+            }
+            previous_index = Some(index);
+            transactions[index as usize] = Some(tx);
+        }
+        for (slot, short_id) in transactions.iter_mut().filter(|slot| slot.is_none()).zip(compact_block.short_ids) {
+            *slot = known.get(&short_id).cloned().flatten();
+        }
+        Ok(Self { header: compact_block.header, transactions })
+    }
+
+    pub fn hash(&self) -> Hash {
+        self.header.hash
+    }
+
+    /// Returns the indexes of the transactions which could not be found locally
+    pub fn missing_indexes(&self) -> Vec<u32> {
+        self.transactions.iter().enumerate().filter(|(_, tx)| tx.is_none()).map(|(i, _)| i as u32).collect()
+    }
+
+    /// Fills the missing transactions, expected in the order of [`Self::missing_indexes`]
+    pub fn fill_missing(&mut self, transactions: Vec<Transaction>) -> Result<(), ProtocolError> {
+        let mut transactions = transactions.into_iter();
+        for slot in self.transactions.iter_mut().filter(|slot| slot.is_none()) {
+            *slot = Some(transactions.next().ok_or(ProtocolError::Other("missing transactions in block transactions response"))?);
+        }
+        if transactions.next().is_some() {
+            return Err(ProtocolError::Other("unexpected transactions in block transactions response"));
+        }
+        Ok(())
+    }
+
+    /// Returns the rebuilt block, or `None` if transactions are still missing or do not match the merkle root of
+    /// the header, in which case the block should be requested in full
+    pub fn into_block(self) -> Option<Block> {
+        let transactions: Vec<Transaction> = self.transactions.into_iter().collect::<Option<_>>()?;
+        // Whether the storage mass fields are committed to depends on the hardfork activation, which is unknown here,
+        // so both roots are checked. When only the root without the masses matches, they are not committed to and get
+        // cleared, a zero mass being skipped by the hashing, hence making the block valid under both rules.
+        let hash_merkle_root = self.header.hash_merkle_root;
+        if calc_hash_merkle_root(transactions.iter(), true) != hash_merkle_root {
+            if calc_hash_merkle_root(transactions.iter(), false) != hash_merkle_root {
+                return None;
+            }
+            transactions.iter().for_each(|tx| tx.set_mass(0));
+        }
+        Some(Block::from_arcs(self.header, Arc::new(transactions)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vecno_consensus_core::{
+        subnets::{SUBNETWORK_ID_COINBASE, SUBNETWORK_ID_NATIVE},
+        tx::{ScriptPublicKey, TransactionInput, TransactionOutpoint, TransactionOutput},
+    };
+
+    fn transaction(seed: u64) -> Transaction {
+        let subnetwork_id = if seed == 0 { SUBNETWORK_ID_COINBASE } else { SUBNETWORK_ID_NATIVE };
+        let inputs = if seed == 0 {
+            vec![]
+        } else {
+            vec![TransactionInput::new(TransactionOutpoint::new(Hash::from_u64_word(seed), 0), vec![], 0, 1)]
+        };
+        let outputs = vec![TransactionOutput::new(seed + 1, ScriptPublicKey::from_vec(0, vec![]))];
+        Transaction::new(0, inputs, outputs, 0, subnetwork_id, 0, vec![]).with_mass(seed * 10)
+    }
+
+    fn block(transactions: Vec<Transaction>) -> Block {
+        let mut header = Header::from_precomputed_hash(Hash::from_u64_word(100), vec![]);
+        header.hash_merkle_root = calc_hash_merkle_root(transactions.iter(), true);
+        Block::new(header, transactions)
+    }
+
+    #[test]
+    fn test_compact_block_reconstruction() {
+        let block = block((0..5).map(transaction).collect());
+        let compact_block = CompactBlock::from_block(&block);
+        assert_eq!(compact_block.transaction_count(), 5);
+        assert_eq!(compact_block.prefilled_transactions.len(), 1);
+
+        // Transactions 1 and 3 are known, along with some unrelated transaction
+        let known = [1, 3, 7].map(transaction);
+        let mut partial_block = PartialBlock::new(compact_block, known).unwrap();
+        assert_eq!(partial_block.missing_indexes(), vec![2, 4]);
+
+        // Too few or too many transactions are rejected
+        assert!(partial_block.fill_missing(vec![transaction(2)]).is_err());
+        let mut partial_block = PartialBlock::new(CompactBlock::from_block(&block), [1, 3].map(transaction)).unwrap();
+        assert!(partial_block.fill_missing(vec![transaction(2), transaction(4), transaction(5)]).is_err());
+
+        let mut partial_block = PartialBlock::new(CompactBlock::from_block(&block), [1, 3].map(transaction)).unwrap();
+        partial_block.fill_missing(vec![transaction(2), transaction(4)]).unwrap();
+        let rebuilt = partial_block.into_block().unwrap();
+        assert_eq!(
+            rebuilt.transactions.iter().map(|tx| tx.id()).collect::<Vec<_>>(),
+            block.transactions.iter().map(|tx| tx.id()).collect::<Vec<_>>()
+        );
+
+        // A known transaction whose mass commitment differs does not match the merkle root
+        let mut partial_block = PartialBlock::new(CompactBlock::from_block(&block), [transaction(1).with_mass(1)]).unwrap();
+        partial_block.fill_missing(vec![transaction(2), transaction(3), transaction(4)]).unwrap();
+        assert!(partial_block.into_block().is_none());
+
+        // Prefilled transactions must have increasing indexes within the block
+        let mut compact_block = CompactBlock::from_block(&block);
+        compact_block.prefilled_transactions.push((0, transaction(1)));
+        assert!(PartialBlock::new(compact_block, []).is_err());
+        let mut compact_block = CompactBlock::from_block(&block);
+        compact_block.prefilled_transactions.push((5, transaction(1)));
+        assert!(PartialBlock::new(compact_block, []).is_err());
+    }
+}
@@ -3,7 +3,7 @@ use vecno_core::{
     vecnod_env::{name, version},
     time::unix_now,
 };
-use vecno_utils::networking::{NetAddress, PeerId};
+use vecno_utils::networking::{PeerAddress, PeerId};
 
 /// Maximum allowed length for the user agent field in a version message `VersionMessage`.
 pub const MAX_USER_AGENT_LEN: usize = 256;
@@ -11,9 +11,9 @@ pub const MAX_USER_AGENT_LEN: usize = 256;
 pub struct Version {
     pub protocol_version: u32,
     pub network: String,
-    pub services: u64, // TODO
+    pub services: u64, // Bit flags of the supported services, see `SERVICE_ENCRYPTED_TRANSPORT`
     pub timestamp: u64,
-    pub address: Option<NetAddress>,
+    pub address: Option<PeerAddress>,
     pub id: PeerId,
     pub user_agent: String,
     pub disable_relay_tx: bool,
@@ -22,7 +22,7 @@ pub struct Version {
 
 impl Version {
     pub fn new(
-        address: Option<NetAddress>,
+        address: Option<PeerAddress>,
         id: PeerId,
         network: String,
         subnetwork_id: Option<SubnetworkId>,
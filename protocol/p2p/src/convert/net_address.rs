@@ -4,7 +4,7 @@ use super::error::ConversionError;
 use crate::pb as protowire;
 
 use itertools::Itertools;
-use vecno_utils::networking::{IpAddress, NetAddress};
+use vecno_utils::networking::{IpAddress, NetAddress, OnionAddress, PeerAddress};
 
 // ----------------------------------------------------------------------------
 // consensus_core to protowire
@@ -20,6 +20,7 @@ impl From<(IpAddress, u16)> for protowire::NetAddress {
                 IpAddr::V6(ip) => ip.octets().to_vec(),
             },
             port: port as u32,
+            onion_public_key: vec![],
         }
     }
 }
@@ -30,6 +31,22 @@ impl From<NetAddress> for protowire::NetAddress {
     }
 }
 
+impl From<OnionAddress> for protowire::NetAddress {
+    fn from(item: OnionAddress) -> Self {
+        // The IP standing for the onion address is sent along so that peers unaware of onion addresses can still parse it
+        Self { onion_public_key: item.public_key().to_vec(), ..item.net_address().into() }
+    }
+}
+
+impl From<PeerAddress> for protowire::NetAddress {
+    fn from(item: PeerAddress) -> Self {
+        match item {
+            PeerAddress::Ip(address) => address.into(),
+            PeerAddress::Onion(address) => address.into(),
+        }
+    }
+}
+
 // ----------------------------------------------------------------------------
 // protowire to consensus_core
 // ----------------------------------------------------------------------------
@@ -65,9 +82,25 @@ impl TryFrom<protowire::NetAddress> for NetAddress {
     }
 }
 
+impl TryFrom<protowire::NetAddress> for PeerAddress {
+    type Error = ConversionError;
+
+    fn try_from(item: protowire::NetAddress) -> Result<Self, Self::Error> {
+        if item.onion_public_key.is_empty() {
+            return Ok(PeerAddress::Ip(item.try_into()?));
+        }
+        let public_key = item
+            .onion_public_key
+            .as_slice()
+            .try_into()
+            .map_err(|_| ConversionError::IllegalOnionPublicKeyLength(item.onion_public_key.len()))?;
+        Ok(PeerAddress::Onion(OnionAddress::new(public_key, item.port.try_into()?)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use vecno_utils::networking::IpAddress;
+    use vecno_utils::networking::{IpAddress, NetAddress, OnionAddress, PeerAddress};
 
     use crate::pb;
     use std::{
@@ -77,14 +110,28 @@ mod tests {
 
     #[test]
     fn test_netaddress() {
-        let net_addr_ipv4 = pb::NetAddress { timestamp: 0, ip: hex::decode("6a0a8af0").unwrap(), port: 123 };
+        let net_addr_ipv4 = pb::NetAddress { timestamp: 0, ip: hex::decode("6a0a8af0").unwrap(), port: 123, onion_public_key: vec![] };
         let ipv4 = Ipv4Addr::from_str("106.10.138.240").unwrap().into();
         assert_eq!(<(IpAddress, u16)>::try_from(net_addr_ipv4.clone()).unwrap(), (ipv4, 123u16));
         assert_eq!(pb::NetAddress::from((ipv4, 123u16)), net_addr_ipv4);
 
-        let net_addr_ipv6 = pb::NetAddress { timestamp: 0, ip: hex::decode("20010db885a3000000008a2e03707334").unwrap(), port: 456 };
+        let net_addr_ipv6 = pb::NetAddress {
+            timestamp: 0,
+            ip: hex::decode("20010db885a3000000008a2e03707334").unwrap(),
+            port: 456,
+            onion_public_key: vec![],
+        };
         let ipv6 = Ipv6Addr::from_str("2001:0db8:85a3:0000:0000:8a2e:0370:7334").unwrap().into();
         assert_eq!(<(IpAddress, u16)>::try_from(net_addr_ipv6.clone()).unwrap(), (ipv6, 456u16));
         assert_eq!(pb::NetAddress::from((ipv6, 456u16)), net_addr_ipv6);
+        assert_eq!(PeerAddress::try_from(net_addr_ipv6).unwrap(), PeerAddress::Ip(NetAddress::new(ipv6, 456)));
+
+        // Onion addresses carry the IP standing for them, which is all that peers unaware of onion addresses see
+        let onion = OnionAddress::from_str("2gzyxa5ihm7nsggfxnu52rck2vv4rvmdlkiu3zzui5du4xyclen53wid.onion:789").unwrap();
+        let net_addr_onion = pb::NetAddress::from(onion);
+        assert_eq!(<(IpAddress, u16)>::try_from(net_addr_onion.clone()).unwrap(), (onion.net_address().ip, 789u16));
+        assert_eq!(PeerAddress::try_from(net_addr_onion.clone()).unwrap(), PeerAddress::Onion(onion));
+        let net_addr_invalid = pb::NetAddress { onion_public_key: vec![1; 16], ..net_addr_onion };
+        assert!(PeerAddress::try_from(net_addr_invalid).is_err());
     }
 }
@@ -48,7 +48,7 @@ impl Adaptor {
     /// Creates a P2P adaptor with only client-side support. Typical Vecno nodes should use `Adaptor::bidirectional`
     pub fn client_only(hub: Hub, initializer: Arc<dyn ConnectionInitializer>, counters: Arc<TowerConnectionCounters>) -> Arc<Self> {
         let (hub_sender, hub_receiver) = mpsc_channel(Self::hub_channel_size());
-        let connection_handler = ConnectionHandler::new(hub_sender, initializer.clone(), counters);
+        let connection_handler = ConnectionHandler::new(hub_sender, initializer.clone(), counters, None);
         let adaptor = Arc::new(Adaptor::new(None, connection_handler, hub));
         adaptor.hub.clone().start_event_loop(hub_receiver, initializer);
         adaptor
@@ -60,9 +60,21 @@ impl Adaptor {
         hub: Hub,
         initializer: Arc<dyn ConnectionInitializer>,
         counters: Arc<TowerConnectionCounters>,
+    ) -> Result<Arc<Self>, ConnectionError> {
+        Self::bidirectional_with_proxy(serve_address, hub, initializer, counters, None)
+    }
+
+    /// Creates a bidirectional P2P adaptor as `Adaptor::bidirectional`, routing the outbound connections through
+    /// the SOCKS5 `proxy` if set
+    pub fn bidirectional_with_proxy(
+        serve_address: NetAddress,
+        hub: Hub,
+        initializer: Arc<dyn ConnectionInitializer>,
+        counters: Arc<TowerConnectionCounters>,
+        proxy: Option<NetAddress>,
     ) -> Result<Arc<Self>, ConnectionError> {
         let (hub_sender, hub_receiver) = mpsc_channel(Self::hub_channel_size());
-        let connection_handler = ConnectionHandler::new(hub_sender, initializer.clone(), counters);
+        let connection_handler = ConnectionHandler::new(hub_sender, initializer.clone(), counters, proxy.map(Into::into));
         let server_termination = connection_handler.serve(serve_address)?;
         let adaptor = Arc::new(Adaptor::new(Some(server_termination), connection_handler, hub));
         adaptor.hub.clone().start_event_loop(hub_receiver, initializer);
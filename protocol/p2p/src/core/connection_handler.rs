@@ -1,17 +1,19 @@
 use crate::common::ProtocolError;
 use crate::core::hub::HubEvent;
+use crate::core::socks;
 use crate::pb::{
     p2p_client::P2pClient as ProtoP2pClient, p2p_server::P2p as ProtoP2p, p2p_server::P2pServer as ProtoP2pServer, VecnodMessage,
 };
 use crate::{ConnectionInitializer, Router};
 use futures::FutureExt;
+use hyper_util::rt::TokioIo;
 use vecno_core::{debug, info};
-use vecno_utils::networking::NetAddress;
+use vecno_utils::networking::{NetAddress, OnionAddress};
 use vecno_utils_tower::{
     counters::TowerConnectionCounters,
     middleware::{BodyExt, CountBytesBody, MapRequestBodyLayer, MapResponseBodyLayer, ServiceBuilder},
 };
-use std::net::ToSocketAddrs;
+use std::net::{Ipv4Addr, SocketAddr, ToSocketAddrs};
 use std::pin::Pin;
 use std::sync::Arc;
 use std::time::Duration;
@@ -20,14 +22,18 @@ use tokio::sync::mpsc::{channel as mpsc_channel, Sender as MpscSender};
 use tokio::sync::oneshot::{channel as oneshot_channel, Sender as OneshotSender};
 use tokio_stream::wrappers::ReceiverStream;
 use tokio_stream::StreamExt;
-use tonic::transport::{Error as TonicError, Server as TonicServer};
+use tonic::transport::{Error as TonicError, Server as TonicServer, Uri};
 use tonic::{Request, Response, Status as TonicStatus, Streaming};
+use tower::service_fn;
 
 #[derive(Error, Debug)]
 pub enum ConnectionError {
     #[error("missing socket address")]
     NoAddress,
 
+    #[error("onion addresses can only be connected to through a proxy")]
+    NoProxy,
+
     #[error("{0}")]
     IoError(#[from] std::io::Error),
 
@@ -51,6 +57,8 @@ pub struct ConnectionHandler {
     hub_sender: MpscSender<HubEvent>,
     initializer: Arc<dyn ConnectionInitializer>,
     counters: Arc<TowerConnectionCounters>,
+    /// The SOCKS5 proxy outbound connections are routed through, if any
+    proxy: Option<SocketAddr>,
 }
 
 impl ConnectionHandler {
@@ -58,8 +66,9 @@ impl ConnectionHandler {
         hub_sender: MpscSender<HubEvent>,
         initializer: Arc<dyn ConnectionInitializer>,
         counters: Arc<TowerConnectionCounters>,
+        proxy: Option<SocketAddr>,
     ) -> Self {
-        Self { hub_sender, initializer, counters }
+        Self { hub_sender, initializer, counters, proxy }
     }
 
     /// Launches a P2P server listener loop
@@ -95,17 +104,41 @@ impl ConnectionHandler {
 
     /// Connect to a new peer
     pub(crate) async fn connect(&self, peer_address: String) -> Result<Arc<Router>, ConnectionError> {
-        let Some(socket_address) = peer_address.to_socket_addrs()?.next() else {
-            return Err(ConnectionError::NoAddress);
+        // Onion service peers are tracked by the address standing for their onion address
+        let socket_address = match peer_address.parse::<OnionAddress>() {
+            Ok(_) if self.proxy.is_none() => return Err(ConnectionError::NoProxy),
+            Ok(onion_address) => onion_address.net_address().into(),
+            // Host names are left for the proxy to resolve, never resolved locally, so such peers stand for the unspecified IP
+            Err(_) if self.proxy.is_some() => match peer_address.parse::<SocketAddr>() {
+                Ok(socket_address) => socket_address,
+                Err(_) => {
+                    let port = peer_address.rsplit_once(':').and_then(|(_, port)| port.parse().ok());
+                    SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), port.ok_or(ConnectionError::NoAddress)?)
+                }
+            },
+            Err(_) => peer_address.to_socket_addrs()?.next().ok_or(ConnectionError::NoAddress)?,
         };
         let peer_address = format!("http://{}", peer_address); // Add scheme prefix as required by Tonic
 
-        let channel = tonic::transport::Endpoint::new(peer_address)?
+        let endpoint = tonic::transport::Endpoint::new(peer_address)?
             .timeout(Duration::from_millis(Self::communication_timeout()))
-            .connect_timeout(Duration::from_millis(Self::connect_timeout()))
-            .tcp_keepalive(Some(Duration::from_millis(Self::keep_alive())))
-            .connect()
-            .await?;
+            .tcp_keepalive(Some(Duration::from_millis(Self::keep_alive())));
+        let channel = match self.proxy {
+            Some(proxy) => {
+                let connector = service_fn(move |uri: Uri| async move {
+                    let (Some(host), Some(port)) = (uri.host(), uri.port_u16()) else {
+                        return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("invalid peer address {uri}")));
+                    };
+                    socks::connect(proxy, host, port).await.map(TokioIo::new)
+                });
+                // Building circuits makes connecting through a proxy such as Tor much slower
+                endpoint
+                    .connect_timeout(Duration::from_millis(Self::proxy_connect_timeout()))
+                    .connect_with_connector(connector)
+                    .await?
+            }
+            None => endpoint.connect_timeout(Duration::from_millis(Self::connect_timeout())).connect().await?,
+        };
 
         let channel = ServiceBuilder::new()
             .layer(MapResponseBodyLayer::new(move |body| CountBytesBody::new(body, self.counters.bytes_rx.clone())))
@@ -192,6 +225,10 @@ impl ConnectionHandler {
     fn connect_timeout() -> u64 {
         1_000
     }
+
+    fn proxy_connect_timeout() -> u64 {
+        10_000
+    }
 }
 
 #[tonic::async_trait]
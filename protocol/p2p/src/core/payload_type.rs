@@ -45,6 +45,14 @@ pub enum VecnodMessagePayloadType {
     IbdChainBlockLocator,
     RequestAntipast,
     RequestNextPruningPointAndItsAnticoneBlocks,
+    SecureHandshake,
+    SecureAuth,
+    Encrypted,
+    RequestCompactBlock,
+    CompactBlock,
+    RequestBlockTransactions,
+    BlockTransactions,
+    StemTransactions,
 }
 
 impl From<&VecnodMessagePayload> for VecnodMessagePayloadType {
@@ -98,6 +106,14 @@ impl From<&VecnodMessagePayload> for VecnodMessagePayloadType {
             VecnodMessagePayload::RequestNextPruningPointAndItsAnticoneBlocks(_) => {
                 VecnodMessagePayloadType::RequestNextPruningPointAndItsAnticoneBlocks
             }
+            VecnodMessagePayload::SecureHandshake(_) => VecnodMessagePayloadType::SecureHandshake,
+            VecnodMessagePayload::SecureAuth(_) => VecnodMessagePayloadType::SecureAuth,
+            VecnodMessagePayload::Encrypted(_) => VecnodMessagePayloadType::Encrypted,
+            VecnodMessagePayload::RequestCompactBlock(_) => VecnodMessagePayloadType::RequestCompactBlock,
+            VecnodMessagePayload::CompactBlock(_) => VecnodMessagePayloadType::CompactBlock,
+            VecnodMessagePayload::RequestBlockTransactions(_) => VecnodMessagePayloadType::RequestBlockTransactions,
+            VecnodMessagePayload::BlockTransactions(_) => VecnodMessagePayloadType::BlockTransactions,
+            VecnodMessagePayload::StemTransactions(_) => VecnodMessagePayloadType::StemTransactions,
         }
     }
 }
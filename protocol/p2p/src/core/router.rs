@@ -1,11 +1,13 @@
 use crate::core::hub::HubEvent;
-use crate::pb::RejectMessage;
+use crate::pb::{RejectMessage, SecureHandshakeMessage};
 use crate::pb::{vecnod_message::Payload as VecnodMessagePayload, VecnodMessage};
+use crate::secure::SecureSessionState;
 use crate::{common::ProtocolError, VecnodMessagePayloadType};
 use crate::{make_message, Peer};
 use vecno_core::{debug, error, info, trace, warn};
 use vecno_utils::networking::PeerId;
 use parking_lot::{Mutex, RwLock};
+use secp256k1::{PublicKey, SecretKey};
 use seqlock::SeqLock;
 use std::fmt::{Debug, Display};
 use std::net::SocketAddr;
@@ -139,6 +141,9 @@ pub struct Router {
 
     /// Used for managing router mutable state
     mutable_state: Mutex<RouterMutableState>,
+
+    /// State of the encrypted transport. Locked while sending so that messages leave in the order of their encryption
+    secure_session: Mutex<SecureSessionState>,
 }
 
 impl Display for Router {
@@ -193,6 +198,7 @@ impl Router {
             outgoing_route,
             hub_sender,
             mutable_state: Mutex::new(RouterMutableState::new(Some(start_sender), Some(shutdown_sender))),
+            secure_session: Default::default(),
         });
 
         let router_clone = router.clone();
@@ -367,6 +373,7 @@ impl Router {
             debug!("P2P, Route to flow got empty payload, peer: {}", self);
             return Err(ProtocolError::Other("received vecnod p2p message with empty payload"));
         }
+        let msg = self.secure_session.lock().open(msg, self.is_outbound)?;
         let msg_type: VecnodMessagePayloadType = msg.payload.as_ref().expect("payload was just verified").into();
         // Handle the special case of a reject message ending the connection
         if msg_type == VecnodMessagePayloadType::Reject {
@@ -402,6 +409,12 @@ impl Router {
     /// Enqueues a locally-originated message to be sent to the network peer
     pub async fn enqueue(&self, msg: VecnodMessage) -> Result<(), ProtocolError> {
         assert!(msg.payload.is_some(), "Vecnod P2P message should always have a value");
+        let mut secure_session = self.secure_session.lock();
+        let msg = secure_session.seal(msg)?;
+        self.send(msg)
+    }
+
+    fn send(&self, msg: VecnodMessage) -> Result<(), ProtocolError> {
         match self.outgoing_route.try_send(msg) {
             Ok(_) => Ok(()),
             Err(TrySendError::Closed(_)) => Err(ProtocolError::ConnectionClosed),
@@ -409,6 +422,22 @@ impl Router {
         }
     }
 
+    /// Starts the key exchange of the encrypted transport by sending the public key of `ephemeral_secret` in plaintext.
+    ///
+    /// The following messages are sent encrypted as soon as the ephemeral key of the peer is received.
+    pub(crate) fn begin_secure_session(&self, ephemeral_secret: SecretKey) -> Result<(), ProtocolError> {
+        let ephemeral_public_key = PublicKey::from_secret_key_global(&ephemeral_secret).serialize().to_vec();
+        let mut secure_session = self.secure_session.lock();
+        self.send(make_message!(VecnodMessagePayload::SecureHandshake, SecureHandshakeMessage { ephemeral_public_key }))?;
+        Ok(secure_session.begin(ephemeral_secret, self.is_outbound)?)
+    }
+
+    /// Returns the digest signed by the `initiator` or responder side of the encrypted transport to authenticate,
+    /// once the session is established
+    pub(crate) fn secure_authentication_digest(&self, initiator: bool) -> Option<[u8; 32]> {
+        self.secure_session.lock().authentication_digest(initiator)
+    }
+
     /// Based on the type of the protocol error, tries sending a reject message before shutting down the connection
     pub async fn try_sending_reject_message(&self, err: &ProtocolError) {
         if err.can_send_outgoing_message() {
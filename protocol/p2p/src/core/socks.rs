@@ -0,0 +1,131 @@
+//!
+//! Minimal SOCKS5 client (RFC 1928) routing the outbound P2P connections through a proxy, such as the one of a Tor daemon.
+//!
+//! Host names are resolved by the proxy, which is required for reaching onion services and avoids leaking DNS requests.
+//! Only proxies requiring no authentication are supported.
+//!
+
+use std::{
+    io::{Error, ErrorKind, Result},
+    net::{IpAddr, SocketAddr},
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+
+const SOCKS_VERSION: u8 = 5;
+const NO_AUTHENTICATION: u8 = 0;
+const CONNECT_COMMAND: u8 = 1;
+const RESERVED: u8 = 0;
+const ADDRESS_TYPE_IPV4: u8 = 1;
+const ADDRESS_TYPE_DOMAIN: u8 = 3;
+const ADDRESS_TYPE_IPV6: u8 = 4;
+const REPLY_SUCCEEDED: u8 = 0;
+
+/// Opens a TCP stream to `host:port` through the SOCKS5 proxy listening at `proxy`
+pub(crate) async fn connect(proxy: SocketAddr, host: &str, port: u16) -> Result<TcpStream> {
+    let mut stream = TcpStream::connect(proxy).await?;
+    stream.set_nodelay(true)?;
+
+    // Offer a single authentication method: none
+    stream.write_all(&[SOCKS_VERSION, 1, NO_AUTHENTICATION]).await?;
+    let mut method_selection = [0u8; 2];
+    stream.read_exact(&mut method_selection).await?;
+    if method_selection != [SOCKS_VERSION, NO_AUTHENTICATION] {
+        return Err(Error::new(ErrorKind::PermissionDenied, "SOCKS5 proxy requires an unsupported authentication method"));
+    }
+
+    // IPv6 hosts of URIs are enclosed in brackets
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    let mut request = vec![SOCKS_VERSION, CONNECT_COMMAND, RESERVED];
+    match host.parse::<IpAddr>() {
+        Ok(IpAddr::V4(ip)) => {
+            request.push(ADDRESS_TYPE_IPV4);
+            request.extend(ip.octets());
+        }
+        Ok(IpAddr::V6(ip)) => {
+            request.push(ADDRESS_TYPE_IPV6);
+            request.extend(ip.octets());
+        }
+        Err(_) => {
+            let len = u8::try_from(host.len()).map_err(|_| Error::new(ErrorKind::InvalidInput, "host name is too long"))?;
+            request.extend([ADDRESS_TYPE_DOMAIN, len]);
+            request.extend(host.as_bytes());
+        }
+    }
+    request.extend(port.to_be_bytes());
+    stream.write_all(&request).await?;
+
+    let mut reply = [0u8; 4];
+    stream.read_exact(&mut reply).await?;
+    if reply[0] != SOCKS_VERSION {
+        return Err(Error::new(ErrorKind::InvalidData, "invalid SOCKS5 proxy reply"));
+    }
+    if reply[1] != REPLY_SUCCEEDED {
+        return Err(Error::new(
+            ErrorKind::ConnectionRefused,
+            format!("SOCKS5 proxy failed to connect to {host}:{port} with reply code {}", reply[1]),
+        ));
+    }
+
+    // Skip the address bound by the proxy, along with its port
+    let bound_address_len = match reply[3] {
+        ADDRESS_TYPE_IPV4 => 4,
+        ADDRESS_TYPE_IPV6 => 16,
+        ADDRESS_TYPE_DOMAIN => stream.read_u8().await? as usize,
+        _ => return Err(Error::new(ErrorKind::InvalidData, "invalid address type in SOCKS5 proxy reply")),
+    };
+    let mut bound_address = vec![0u8; bound_address_len + 2];
+    stream.read_exact(&mut bound_address).await?;
+
+    Ok(stream)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    /// Accepts a single connection, expects a request to connect to `host:port` and replies with `reply_code`
+    async fn mock_proxy(listener: TcpListener, host: &'static str, port: u16, reply_code: u8) {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut greeting = [0u8; 3];
+        stream.read_exact(&mut greeting).await.unwrap();
+        assert_eq!(greeting, [SOCKS_VERSION, 1, NO_AUTHENTICATION]);
+        stream.write_all(&[SOCKS_VERSION, NO_AUTHENTICATION]).await.unwrap();
+
+        let mut request = vec![0u8; 7 + host.len()];
+        stream.read_exact(&mut request).await.unwrap();
+        let expected: [&[u8]; 3] =
+            [&[SOCKS_VERSION, CONNECT_COMMAND, RESERVED, ADDRESS_TYPE_DOMAIN, host.len() as u8], host.as_bytes(), &port.to_be_bytes()];
+        assert_eq!(request, expected.concat());
+
+        stream.write_all(&[SOCKS_VERSION, reply_code, RESERVED, ADDRESS_TYPE_IPV4, 0, 0, 0, 0, 0, 0]).await.unwrap();
+        if reply_code == REPLY_SUCCEEDED {
+            // Echo a single byte, standing for the relayed connection
+            let byte = stream.read_u8().await.unwrap();
+            stream.write_u8(byte).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_socks5_connect() {
+        let host = "2gzyxa5ihm7nsggfxnu52rck2vv4rvmdlkiu3zzui5du4xyclen53wid.onion";
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let proxy = listener.local_addr().unwrap();
+        let server = tokio::spawn(mock_proxy(listener, host, 7111, REPLY_SUCCEEDED));
+        let mut stream = connect(proxy, host, 7111).await.unwrap();
+        stream.write_u8(42).await.unwrap();
+        assert_eq!(stream.read_u8().await.unwrap(), 42);
+        server.await.unwrap();
+
+        // Host unreachable
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let proxy = listener.local_addr().unwrap();
+        let server = tokio::spawn(mock_proxy(listener, host, 7111, 4));
+        assert_eq!(connect(proxy, host, 7111).await.unwrap_err().kind(), ErrorKind::ConnectionRefused);
+        server.await.unwrap();
+    }
+}
@@ -3,6 +3,7 @@ use crate::{
     core::adaptor::ConnectionInitializer,
     handshake::VecnodHandshake,
     pb::{self, VersionMessage},
+    secure::SecureTransport,
     IncomingRoute, VecnodMessagePayloadType, Router,
 };
 use vecno_core::{debug, time::unix_now, trace, warn};
@@ -87,7 +88,9 @@ impl EchoFlow {
 
 /// An example initializer, performing handshake and registering a simple echo flow
 #[derive(Default)]
-pub struct EchoFlowInitializer {}
+pub struct EchoFlowInitializer {
+    secure_transport: Option<Arc<SecureTransport>>,
+}
 
 fn build_dummy_version_message() -> VersionMessage {
     pb::VersionMessage {
@@ -105,7 +108,12 @@ fn build_dummy_version_message() -> VersionMessage {
 
 impl EchoFlowInitializer {
     pub fn new() -> Self {
-        EchoFlowInitializer {}
+        EchoFlowInitializer { secure_transport: None }
+    }
+
+    /// Builds an initializer switching all connections to the encrypted transport
+    pub fn with_secure_transport(secure_transport: Arc<SecureTransport>) -> Self {
+        EchoFlowInitializer { secure_transport: Some(secure_transport) }
     }
 }
 
@@ -129,6 +137,12 @@ impl ConnectionInitializer for EchoFlowInitializer {
         let peer_version_message = handshake.handshake(self_version_message).await?;
         debug!("protocol versions - self: {}, peer: {}", 5, peer_version_message.protocol_version);
 
+        // Switch to the encrypted transport if enabled
+        if let Some(secure_transport) = self.secure_transport.as_ref() {
+            let peer_identity = handshake.secure_handshake(secure_transport).await?;
+            debug!("encrypted transport established with peer identity {}", peer_identity);
+        }
+
         // Subscribe to remaining messages. In this example we simply subscribe to all messages with a single echo flow
         EchoFlow::register(router.clone()).await;
 
@@ -147,7 +161,7 @@ mod tests {
     use std::{str::FromStr, time::Duration};
 
     use super::*;
-    use crate::{Adaptor, Hub};
+    use crate::{secure::NodeIdentity, Adaptor, Hub};
     use vecno_core::debug;
     use vecno_utils::networking::NetAddress;
 
@@ -200,4 +214,45 @@ mod tests {
         drop(adaptor2);
         tokio::time::sleep(std::time::Duration::from_secs(2)).await;
     }
+
+    #[tokio::test]
+    async fn test_secure_handshake() {
+        vecno_core::log::try_init_logger("debug");
+
+        let (identity1, identity2, identity3) = (NodeIdentity::generate(), NodeIdentity::generate(), NodeIdentity::generate());
+        let allowed_identity = identity1.public_key();
+        let initializer = |identity, allowed_peers: Vec<_>| {
+            Arc::new(EchoFlowInitializer::with_secure_transport(Arc::new(SecureTransport::new(identity, allowed_peers))))
+        };
+
+        let address1 = NetAddress::from_str("[::1]:50055").unwrap();
+        let adaptor1 = Adaptor::bidirectional(address1, Hub::new(), initializer(identity1, vec![]), Default::default()).unwrap();
+
+        // `adaptor2` only accepts the identity of `adaptor1`
+        let address2 = NetAddress::from_str("[::1]:50056").unwrap();
+        let adaptor2 =
+            Adaptor::bidirectional(address2, Hub::new(), initializer(identity2, vec![allowed_identity]), Default::default()).unwrap();
+
+        let address3 = NetAddress::from_str("[::1]:50057").unwrap();
+        let adaptor3 = Adaptor::bidirectional(address3, Hub::new(), initializer(identity3, vec![]), Default::default()).unwrap();
+
+        adaptor1
+            .connect_peer_with_retries(String::from("[::1]:50056"), 16, Duration::from_secs(1))
+            .await
+            .expect("peer connection failed");
+        assert!(adaptor3.connect_peer_with_retries(String::from("[::1]:50056"), 16, Duration::from_secs(1)).await.is_err());
+
+        // Wait for handshake completion
+        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+
+        // The ready messages being exchanged encrypted, the handshake only completes if the transport works
+        assert_eq!(adaptor1.active_peers().len(), 1, "secure handshake failed -- outbound peer is missing");
+        assert_eq!(adaptor2.active_peers().len(), 1, "secure handshake failed -- the allowlist was not applied");
+        assert_eq!(adaptor3.active_peers().len(), 0, "secure handshake failed -- the peer of a rejected identity remains");
+
+        adaptor1.close().await;
+        adaptor2.close().await;
+        adaptor3.close().await;
+        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+    }
 }
@@ -1,9 +1,11 @@
 use std::time::Duration;
 
 use crate::pb::{vecnod_message::Payload, ReadyMessage, VerackMessage, VersionMessage};
+use crate::secure::{verify_authentication, IdentityKey, SecureTransport, SecureTransportError};
 use crate::{common::ProtocolError, dequeue_with_timeout, make_message};
 use crate::{IncomingRoute, VecnodMessagePayloadType, Router};
 use vecno_core::debug;
+use secp256k1::SecretKey;
 
 /// Implements the Vecno peer-to-peer handshake protocol
 pub struct VecnodHandshake<'a> {
@@ -11,6 +13,8 @@ pub struct VecnodHandshake<'a> {
     version_receiver: IncomingRoute,
     verack_receiver: IncomingRoute,
     ready_receiver: IncomingRoute,
+    secure_handshake_receiver: IncomingRoute,
+    secure_auth_receiver: IncomingRoute,
 }
 
 impl<'a> VecnodHandshake<'a> {
@@ -21,6 +25,8 @@ impl<'a> VecnodHandshake<'a> {
             version_receiver: router.subscribe(vec![VecnodMessagePayloadType::Version]),
             verack_receiver: router.subscribe(vec![VecnodMessagePayloadType::Verack]),
             ready_receiver: router.subscribe(vec![VecnodMessagePayloadType::Ready]),
+            secure_handshake_receiver: router.subscribe(vec![VecnodMessagePayloadType::SecureHandshake]),
+            secure_auth_receiver: router.subscribe(vec![VecnodMessagePayloadType::SecureAuth]),
         }
     }
 
@@ -67,6 +73,31 @@ impl<'a> VecnodHandshake<'a> {
         Ok(())
     }
 
+    /// Switches the connection to the encrypted transport and authenticates the peer, returning its identity.
+    ///
+    /// Should only be called after the version exchange, if both peers advertised the encrypted transport service.
+    pub async fn secure_handshake(&mut self, secure_transport: &SecureTransport) -> Result<IdentityKey, ProtocolError> {
+        debug!("starting secure handshake");
+
+        self.router.begin_secure_session(SecretKey::new(&mut rand::thread_rng()))?;
+        // The router establishes the session as soon as it receives the ephemeral key of the peer
+        dequeue_with_timeout!(self.secure_handshake_receiver, Payload::SecureHandshake, Duration::from_secs(4))?;
+
+        let is_initiator = self.router.is_outbound();
+        let digest = self.router.secure_authentication_digest(is_initiator).ok_or(SecureTransportError::UnexpectedHandshake)?;
+        self.router.enqueue(make_message!(Payload::SecureAuth, secure_transport.identity().authenticate(digest))).await?;
+
+        let peer_auth_message = dequeue_with_timeout!(self.secure_auth_receiver, Payload::SecureAuth, Duration::from_secs(4))?;
+        let peer_digest = self.router.secure_authentication_digest(!is_initiator).ok_or(SecureTransportError::UnexpectedHandshake)?;
+        let peer_identity = verify_authentication(&peer_auth_message, peer_digest)?;
+        if !secure_transport.is_allowed(&peer_identity) {
+            return Err(SecureTransportError::PeerNotAllowed(peer_identity).into());
+        }
+        debug!("accepted peer identity: {peer_identity}");
+
+        Ok(peer_identity)
+    }
+
     /// Performs the handshake with the peer, essentially exchanging version messages
     pub async fn handshake(&mut self, self_version_message: VersionMessage) -> Result<VersionMessage, ProtocolError> {
         // Run both send and receive flows concurrently -- this is critical in order to avoid a handshake deadlock
@@ -0,0 +1,61 @@
+use super::{IdentityKey, SecureTransportError};
+use crate::pb::SecureAuthMessage;
+use secp256k1::{schnorr::Signature, Keypair, Message, SECP256K1};
+use std::{
+    fs,
+    io::{self, Write},
+    path::Path,
+};
+
+/// Persistent identity of a node, authenticating it to its peers over the encrypted transport
+pub struct NodeIdentity {
+    keypair: Keypair,
+}
+
+impl NodeIdentity {
+    /// Generates a new random identity
+    pub fn generate() -> Self {
+        Self { keypair: Keypair::new(SECP256K1, &mut rand::thread_rng()) }
+    }
+
+    /// Loads the identity stored in the file at `path`, generating and storing a new one if the file does not exist.
+    ///
+    /// The file holds the hex encoded secret key and is created readable by the node user only.
+    pub fn load_or_create(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(secret) => Keypair::from_seckey_str(SECP256K1, secret.trim())
+                .map(|keypair| Self { keypair })
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                let identity = Self::generate();
+                let mut options = fs::OpenOptions::new();
+                options.write(true).create_new(true);
+                #[cfg(unix)]
+                std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+                options.open(path)?.write_all(identity.keypair.display_secret().to_string().as_bytes())?;
+                Ok(identity)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn public_key(&self) -> IdentityKey {
+        self.keypair.x_only_public_key().0
+    }
+
+    /// Builds the message proving the ownership of the identity by signing `digest`
+    pub(crate) fn authenticate(&self, digest: [u8; 32]) -> SecureAuthMessage {
+        let signature = self.keypair.sign_schnorr(Message::from_digest(digest));
+        SecureAuthMessage { identity_public_key: self.public_key().serialize().to_vec(), signature: signature.as_ref().to_vec() }
+    }
+}
+
+/// Verifies the authentication message of a peer against `digest`, returning the proven identity
+pub(crate) fn verify_authentication(message: &SecureAuthMessage, digest: [u8; 32]) -> Result<IdentityKey, SecureTransportError> {
+    let identity = IdentityKey::from_slice(&message.identity_public_key).map_err(|_| SecureTransportError::InvalidAuthentication)?;
+    let signature = Signature::from_slice(&message.signature).map_err(|_| SecureTransportError::InvalidAuthentication)?;
+    SECP256K1
+        .verify_schnorr(&signature, &Message::from_digest(digest), &identity)
+        .map_err(|_| SecureTransportError::InvalidAuthentication)?;
+    Ok(identity)
+}
@@ -0,0 +1,85 @@
+//! Optional encrypted and authenticated transport of the P2P messages.
+//!
+//! Peers advertising the [`SERVICE_ENCRYPTED_TRANSPORT`] bit in their version message run a key exchange right after
+//! the version handshake. Each side sends an ephemeral secp256k1 public key in plaintext, both sides derive a pair of
+//! ChaCha20-Poly1305 keys out of the ECDH shared secret, and every following message travels inside an encrypted envelope.
+//! The peers then authenticate each other by signing the key exchange transcript with their persistent [`NodeIdentity`].
+
+mod identity;
+mod session;
+
+use secp256k1::XOnlyPublicKey;
+use std::collections::HashSet;
+use thiserror::Error;
+
+pub(crate) use identity::verify_authentication;
+pub use identity::NodeIdentity;
+pub(crate) use session::SecureSessionState;
+
+/// Service bit of the version message advertising the support of the encrypted transport
+pub const SERVICE_ENCRYPTED_TRANSPORT: u64 = 1 << 0;
+
+/// Public key identifying a node over the encrypted transport
+pub type IdentityKey = XOnlyPublicKey;
+
+#[derive(Error, Debug, Clone)]
+pub enum SecureTransportError {
+    #[error("peer does not support the encrypted transport required by this node")]
+    EncryptionRequired,
+
+    #[error("invalid ephemeral key in secure handshake")]
+    InvalidEphemeralKey,
+
+    #[error("unexpected secure handshake message")]
+    UnexpectedHandshake,
+
+    #[error("got an encrypted message before the secure session was established")]
+    UnexpectedEncryptedMessage,
+
+    #[error("got a plaintext message over an established secure session")]
+    UnexpectedPlaintextMessage,
+
+    #[error("message authentication failed")]
+    DecryptionFailed,
+
+    #[error("secure session nonces are exhausted")]
+    NonceExhausted,
+
+    #[error("invalid identity authentication")]
+    InvalidAuthentication,
+
+    #[error("peer identity {0} is not allowed")]
+    PeerNotAllowed(IdentityKey),
+}
+
+/// Settings of the encrypted transport of a node
+pub struct SecureTransport {
+    identity: NodeIdentity,
+
+    /// Identities of the only peers allowed to connect, any peer being allowed if empty
+    allowed_peers: HashSet<IdentityKey>,
+}
+
+impl SecureTransport {
+    pub fn new(identity: NodeIdentity, allowed_peers: impl IntoIterator<Item = IdentityKey>) -> Self {
+        Self { identity, allowed_peers: allowed_peers.into_iter().collect() }
+    }
+
+    pub fn identity(&self) -> &NodeIdentity {
+        &self.identity
+    }
+
+    /// Returns true if the connections are restricted to the allowed peers, in which case the encrypted transport is mandatory
+    pub fn is_restricted(&self) -> bool {
+        !self.allowed_peers.is_empty()
+    }
+
+    pub fn is_allowed(&self, peer_identity: &IdentityKey) -> bool {
+        self.allowed_peers.is_empty() || self.allowed_peers.contains(peer_identity)
+    }
+
+    /// Returns true if the services advertised by a peer include the encrypted transport
+    pub fn is_supported_by(services: u64) -> bool {
+        services & SERVICE_ENCRYPTED_TRANSPORT != 0
+    }
+}
@@ -0,0 +1,232 @@
+use super::SecureTransportError;
+use crate::{
+    make_message,
+    pb::{vecnod_message::Payload, EncryptedMessage, VecnodMessage},
+};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit},
+    ChaCha20Poly1305, Nonce,
+};
+use prost::Message;
+use secp256k1::{ecdh::SharedSecret, PublicKey, SecretKey};
+use sha2::{Digest, Sha256};
+use std::fmt::{Debug, Formatter};
+
+/// Domain separation tag of the key exchange transcript
+const TRANSCRIPT_DOMAIN: &[u8] = b"vecno-p2p-secure-transport-v1";
+
+/// Keys and nonces of an established encrypted session
+pub(crate) struct SecureSession {
+    transcript: [u8; 32],
+    sealing_cipher: ChaCha20Poly1305,
+    sealing_nonce: u64,
+    opening_cipher: ChaCha20Poly1305,
+    opening_nonce: u64,
+}
+
+impl SecureSession {
+    /// Derives the session out of the local ephemeral secret and the peer ephemeral public key.
+    /// The initiator of the key exchange is the side of the outbound connection.
+    fn new(local_secret: &SecretKey, peer_public: &PublicKey, is_initiator: bool) -> Self {
+        let local_public = PublicKey::from_secret_key_global(local_secret);
+        let (initiator_public, responder_public) =
+            if is_initiator { (local_public, *peer_public) } else { (*peer_public, local_public) };
+        let transcript: [u8; 32] = Sha256::new()
+            .chain_update(TRANSCRIPT_DOMAIN)
+            .chain_update(initiator_public.serialize())
+            .chain_update(responder_public.serialize())
+            .finalize()
+            .into();
+        let shared_secret = SharedSecret::new(peer_public, local_secret);
+        let derive_cipher = |label: &[u8]| {
+            let key: [u8; 32] = Sha256::new()
+                .chain_update(shared_secret.secret_bytes())
+                .chain_update(transcript)
+                .chain_update(label)
+                .finalize()
+                .into();
+            ChaCha20Poly1305::new(&key.into())
+        };
+        let (initiator_cipher, responder_cipher) = (derive_cipher(b"initiator"), derive_cipher(b"responder"));
+        let (sealing_cipher, opening_cipher) =
+            if is_initiator { (initiator_cipher, responder_cipher) } else { (responder_cipher, initiator_cipher) };
+        Self { transcript, sealing_cipher, sealing_nonce: 0, opening_cipher, opening_nonce: 0 }
+    }
+
+    fn authentication_digest(&self, initiator: bool) -> [u8; 32] {
+        let role: &[u8] = if initiator { b"initiator" } else { b"responder" };
+        Sha256::new().chain_update(self.transcript).chain_update(role).finalize().into()
+    }
+
+    fn seal(&mut self, msg: &VecnodMessage) -> Result<VecnodMessage, SecureTransportError> {
+        let nonce = next_nonce(&mut self.sealing_nonce)?;
+        let ciphertext =
+            self.sealing_cipher.encrypt(&nonce, msg.encode_to_vec().as_slice()).expect("messages are far below the cipher size limit");
+        Ok(make_message!(Payload::Encrypted, EncryptedMessage { ciphertext }))
+    }
+
+    fn open(&mut self, msg: EncryptedMessage) -> Result<VecnodMessage, SecureTransportError> {
+        let nonce = next_nonce(&mut self.opening_nonce)?;
+        let plaintext =
+            self.opening_cipher.decrypt(&nonce, msg.ciphertext.as_slice()).map_err(|_| SecureTransportError::DecryptionFailed)?;
+        VecnodMessage::decode(plaintext.as_slice()).map_err(|_| SecureTransportError::DecryptionFailed)
+    }
+}
+
+/// Returns the nonce of the given message counter, advancing it
+fn next_nonce(counter: &mut u64) -> Result<Nonce, SecureTransportError> {
+    let mut nonce = Nonce::default();
+    nonce[4..].copy_from_slice(&counter.to_le_bytes());
+    *counter = counter.checked_add(1).ok_or(SecureTransportError::NonceExhausted)?;
+    Ok(nonce)
+}
+
+/// State of the encrypted transport of a router
+#[derive(Default)]
+pub(crate) enum SecureSessionState {
+    /// No key exchange is in progress, messages travel in plaintext
+    #[default]
+    Plaintext,
+
+    /// The local ephemeral key was sent, the one of the peer being awaited
+    AwaitingPeer(SecretKey),
+
+    /// The peer ephemeral key was received before the local one was sent
+    AwaitingLocal(PublicKey),
+
+    /// The key exchange is complete, messages travel encrypted
+    Established(Box<SecureSession>),
+}
+
+impl SecureSessionState {
+    /// Records the local ephemeral secret of the key exchange, its public key having just been sent to the peer
+    pub(crate) fn begin(&mut self, local_secret: SecretKey, is_initiator: bool) -> Result<(), SecureTransportError> {
+        match std::mem::take(self) {
+            Self::Plaintext => *self = Self::AwaitingPeer(local_secret),
+            Self::AwaitingLocal(peer_public) => {
+                *self = Self::Established(Box::new(SecureSession::new(&local_secret, &peer_public, is_initiator)))
+            }
+            state => {
+                *self = state;
+                return Err(SecureTransportError::UnexpectedHandshake);
+            }
+        }
+        Ok(())
+    }
+
+    fn receive_peer_key(&mut self, peer_public: PublicKey, is_initiator: bool) -> Result<(), SecureTransportError> {
+        match std::mem::take(self) {
+            Self::Plaintext => *self = Self::AwaitingLocal(peer_public),
+            Self::AwaitingPeer(local_secret) => {
+                *self = Self::Established(Box::new(SecureSession::new(&local_secret, &peer_public, is_initiator)))
+            }
+            state => {
+                *self = state;
+                return Err(SecureTransportError::UnexpectedHandshake);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the digest signed by the `initiator` or responder side to authenticate, once the session is established
+    pub(crate) fn authentication_digest(&self, initiator: bool) -> Option<[u8; 32]> {
+        match self {
+            Self::Established(session) => Some(session.authentication_digest(initiator)),
+            _ => None,
+        }
+    }
+
+    /// Wraps an outgoing message into an encrypted envelope once the session is established
+    pub(crate) fn seal(&mut self, msg: VecnodMessage) -> Result<VecnodMessage, SecureTransportError> {
+        match self {
+            Self::Established(session) => session.seal(&msg),
+            _ => Ok(msg),
+        }
+    }
+
+    /// Unwraps an incoming message, decrypting it once the session is established.
+    ///
+    /// The ephemeral key of a secure handshake message is processed here, on the receive loop of the router, so that the
+    /// session is established before the next message of the peer, already encrypted, gets received.
+    pub(crate) fn open(&mut self, msg: VecnodMessage, is_initiator: bool) -> Result<VecnodMessage, SecureTransportError> {
+        match &msg.payload {
+            Some(Payload::Encrypted(_)) => {
+                let Self::Established(session) = self else { return Err(SecureTransportError::UnexpectedEncryptedMessage) };
+                let Some(Payload::Encrypted(encrypted)) = msg.payload else { unreachable!() };
+                let msg = session.open(encrypted)?;
+                if matches!(msg.payload, Some(Payload::Encrypted(_) | Payload::SecureHandshake(_))) {
+                    return Err(SecureTransportError::UnexpectedHandshake);
+                }
+                Ok(msg)
+            }
+            Some(Payload::SecureHandshake(handshake)) => {
+                let peer_public =
+                    PublicKey::from_slice(&handshake.ephemeral_public_key).map_err(|_| SecureTransportError::InvalidEphemeralKey)?;
+                self.receive_peer_key(peer_public, is_initiator)?;
+                Ok(msg)
+            }
+            // Once the session is established, any plaintext message is refused
+            _ if matches!(self, Self::Established(_)) => Err(SecureTransportError::UnexpectedPlaintextMessage),
+            // A peer failing before establishing its side of the session rejects in plaintext
+            _ => Ok(msg),
+        }
+    }
+}
+
+impl Debug for SecureSessionState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Plaintext => write!(f, "Plaintext"),
+            Self::AwaitingPeer(_) => write!(f, "AwaitingPeer"),
+            Self::AwaitingLocal(_) => write!(f, "AwaitingLocal"),
+            Self::Established(_) => write!(f, "Established"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        pb::{PingMessage, RejectMessage, SecureHandshakeMessage},
+        secure::{verify_authentication, NodeIdentity},
+    };
+
+    fn handshake_message(secret: &SecretKey) -> VecnodMessage {
+        let ephemeral_public_key = PublicKey::from_secret_key_global(secret).serialize().to_vec();
+        make_message!(Payload::SecureHandshake, SecureHandshakeMessage { ephemeral_public_key })
+    }
+
+    #[test]
+    fn test_secure_session() {
+        let (initiator_secret, responder_secret) = (SecretKey::new(&mut rand::thread_rng()), SecretKey::new(&mut rand::thread_rng()));
+        let (mut initiator, mut responder) = (SecureSessionState::default(), SecureSessionState::default());
+
+        // The initiator sends its key before receiving the peer one, the responder receives the peer key first
+        let reject = make_message!(Payload::Reject, RejectMessage { reason: "rejected".to_string() });
+        initiator.begin(initiator_secret, true).unwrap();
+        assert_eq!(initiator.open(reject.clone(), true).unwrap(), reject);
+        responder.open(handshake_message(&initiator_secret), false).unwrap();
+        responder.begin(responder_secret, false).unwrap();
+        initiator.open(handshake_message(&responder_secret), true).unwrap();
+        assert!(matches!(initiator.begin(initiator_secret, true), Err(SecureTransportError::UnexpectedHandshake)));
+
+        // Both sides agree on the transcript and the signatures are bound to the role of the signer
+        let digest = initiator.authentication_digest(true).unwrap();
+        assert_eq!(responder.authentication_digest(true), Some(digest));
+        let identity = NodeIdentity::generate();
+        assert_eq!(verify_authentication(&identity.authenticate(digest), digest).unwrap(), identity.public_key());
+        assert!(verify_authentication(&identity.authenticate(digest), responder.authentication_digest(false).unwrap()).is_err());
+
+        // Messages are encrypted and can be opened only once
+        let ping = make_message!(Payload::Ping, PingMessage { nonce: 7 });
+        let sealed = initiator.seal(ping.clone()).unwrap();
+        assert!(matches!(sealed.payload, Some(Payload::Encrypted(_))));
+        assert_eq!(responder.open(sealed.clone(), false).unwrap(), ping);
+        assert!(matches!(responder.open(sealed, false), Err(SecureTransportError::DecryptionFailed)));
+
+        // Plaintext messages, rejects included, are no longer accepted
+        assert!(matches!(initiator.open(ping, true), Err(SecureTransportError::UnexpectedPlaintextMessage)));
+        assert!(matches!(initiator.open(reject, true), Err(SecureTransportError::UnexpectedPlaintextMessage)));
+    }
+}
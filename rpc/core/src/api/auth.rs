@@ -0,0 +1,197 @@
+//!
+//! Authentication of the RPC clients, permissions granted to their credentials and TLS identity of the RPC servers.
+//!
+
+use crate::{api::ops::RpcApiOps, RpcError, RpcResult};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use subtle::ConstantTimeEq;
+use std::{
+    fmt::{Debug, Display, Formatter},
+    path::Path,
+    str::FromStr,
+};
+
+/// Class of RPC methods a client can be granted access to
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[repr(u8)]
+pub enum RpcPermission {
+    /// Querying the node state and subscribing to notifications
+    Read = 1,
+    /// Submitting transactions to the mempool
+    Submit = 2,
+    /// Getting block templates and submitting blocks
+    Mining = 4,
+    /// Managing the peers and the node itself
+    Admin = 8,
+}
+
+impl RpcPermission {
+    pub const ALL: [RpcPermission; 4] = [RpcPermission::Read, RpcPermission::Submit, RpcPermission::Mining, RpcPermission::Admin];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            RpcPermission::Read => "read",
+            RpcPermission::Submit => "submit",
+            RpcPermission::Mining => "mining",
+            RpcPermission::Admin => "admin",
+        }
+    }
+}
+
+impl Display for RpcPermission {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RpcPermission {
+    type Err = String;
+
+    fn from_str(permission: &str) -> Result<Self, Self::Err> {
+        Self::ALL.into_iter().find(|x| x.as_str() == permission).ok_or_else(|| format!("unknown RPC permission {permission}"))
+    }
+}
+
+/// A set of [`RpcPermission`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RpcPermissions(u8);
+
+impl RpcPermissions {
+    pub fn all() -> Self {
+        RpcPermission::ALL.into_iter().collect()
+    }
+
+    pub fn contains(&self, permission: RpcPermission) -> bool {
+        self.0 & permission as u8 != 0
+    }
+
+    /// Returns whether the permissions allow calling `op`
+    pub fn allows(&self, op: RpcApiOps) -> bool {
+        self.contains(op.permission())
+    }
+}
+
+impl FromIterator<RpcPermission> for RpcPermissions {
+    fn from_iter<T: IntoIterator<Item = RpcPermission>>(iter: T) -> Self {
+        Self(iter.into_iter().fold(0, |bits, permission| bits | permission as u8))
+    }
+}
+
+/// A token and the permissions it grants, as configured by the node operator
+#[derive(Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct RpcCredential {
+    pub token: String,
+    pub permissions: Vec<RpcPermission>,
+}
+
+impl Debug for RpcCredential {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RpcCredential").field("permissions", &self.permissions).finish_non_exhaustive()
+    }
+}
+
+/// Authenticates the RPC clients by the bearer token they present.
+///
+/// Authentication is disabled when no token is registered, every client being then granted all permissions.
+#[derive(Clone, Default)]
+pub struct RpcAuthenticator {
+    credentials: Vec<(String, RpcPermissions)>,
+}
+
+impl RpcAuthenticator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&mut self, token: String, permissions: RpcPermissions) {
+        self.credentials.push((token, permissions));
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.credentials.is_empty()
+    }
+
+    /// Returns the permissions granted to `token`
+    pub fn authenticate(&self, token: Option<&str>) -> RpcResult<RpcPermissions> {
+        if !self.is_enabled() {
+            return Ok(RpcPermissions::all());
+        }
+        let token = token.ok_or(RpcError::Unauthenticated)?;
+        self.credentials
+            .iter()
+            .find(|(registered, _)| registered.as_bytes().ct_eq(token.as_bytes()).into())
+            .map(|(_, permissions)| *permissions)
+            .ok_or(RpcError::Unauthenticated)
+    }
+
+    /// Extracts the token out of an HTTP `Authorization` header value using the `Bearer` scheme
+    pub fn bearer_token(authorization: &str) -> Option<&str> {
+        let (scheme, token) = authorization.trim().split_once(' ')?;
+        scheme.eq_ignore_ascii_case("bearer").then(|| token.trim())
+    }
+
+    /// Generates a random token suitable for a cookie file
+    pub fn generate_token() -> String {
+        let mut bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        faster_hex::hex_string(&bytes)
+    }
+}
+
+impl Debug for RpcAuthenticator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RpcAuthenticator").field("credentials", &self.credentials.len()).finish()
+    }
+}
+
+/// PEM encoded certificate chain and private key of an RPC server
+#[derive(Clone)]
+pub struct RpcTlsIdentity {
+    pub cert: Vec<u8>,
+    pub key: Vec<u8>,
+}
+
+impl RpcTlsIdentity {
+    pub fn from_files(cert_path: impl AsRef<Path>, key_path: impl AsRef<Path>) -> std::io::Result<Self> {
+        Ok(Self { cert: std::fs::read(cert_path)?, key: std::fs::read(key_path)? })
+    }
+}
+
+impl Debug for RpcTlsIdentity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RpcTlsIdentity").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_authenticator() {
+        let authenticator = RpcAuthenticator::new();
+        assert_eq!(authenticator.authenticate(None).unwrap(), RpcPermissions::all());
+
+        let mut authenticator = RpcAuthenticator::new();
+        authenticator.register("reader".to_owned(), [RpcPermission::Read].into_iter().collect());
+        authenticator.register("miner".to_owned(), [RpcPermission::Read, RpcPermission::Mining].into_iter().collect());
+        assert!(authenticator.authenticate(None).is_err());
+        assert!(authenticator.authenticate(Some("reade")).is_err());
+
+        let reader = authenticator.authenticate(RpcAuthenticator::bearer_token("Bearer reader")).unwrap();
+        assert!(reader.allows(RpcApiOps::GetBlockDagInfo));
+        assert!(!reader.allows(RpcApiOps::GetBlockTemplate));
+        assert!(!reader.allows(RpcApiOps::SubmitTransaction));
+
+        let miner = authenticator.authenticate(RpcAuthenticator::bearer_token("bearer  miner ")).unwrap();
+        assert!(miner.allows(RpcApiOps::GetBlockTemplate));
+        assert!(miner.allows(RpcApiOps::SubmitBlock));
+        assert!(!miner.allows(RpcApiOps::Shutdown));
+
+        assert_eq!("mining".parse::<RpcPermission>().unwrap(), RpcPermission::Mining);
+        assert!("root".parse::<RpcPermission>().is_err());
+    }
+}
@@ -2,8 +2,10 @@
 //!  API module for the RPC server. Implements core RPC primitives.
 //!
 
+pub mod auth;
 pub mod connection;
 pub mod ctl;
 pub mod notifications;
 pub mod ops;
+pub mod rate_limit;
 pub mod rpc;
@@ -9,12 +9,14 @@ use vecno_notify::{
     notification::{full_featured, Notification as NotificationTrait},
     subscription::{
         context::SubscriptionContext,
-        single::{OverallSubscription, UtxosChangedSubscription, VirtualChainChangedSubscription},
+        single::{
+            BlockAddedSubscription, OverallSubscription, TransactionStatusChangedSubscription, UtxosChangedSubscription,
+            VirtualChainChangedSubscription,
+        },
         Subscription,
     },
 };
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
 use wasm_bindgen::JsValue;
 use workflow_serializer::prelude::*;
 use workflow_wasm::serde::to_value;
@@ -48,6 +50,9 @@ pub enum Notification {
 
     #[display(fmt = "NewBlockTemplate notification")]
     NewBlockTemplate(NewBlockTemplateNotification),
+
+    #[display(fmt = "TransactionStatusChanged notification: transaction {} {:?}", "_0.status.transaction_id", "_0.status.state")]
+    TransactionStatusChanged(TransactionStatusChangedNotification),
 }
 }
 
@@ -64,6 +69,7 @@ impl Notification {
             Notification::VirtualDaaScoreChanged(v) => to_value(&v),
             Notification::SinkBlueScoreChanged(v) => to_value(&v),
             Notification::VirtualChainChanged(v) => to_value(&v),
+            Notification::TransactionStatusChanged(v) => to_value(&v),
         }
     }
 }
@@ -76,6 +82,16 @@ impl NotificationTrait for Notification {
         }
     }
 
+    fn apply_block_added_subscription(&self, subscription: &BlockAddedSubscription, _context: &SubscriptionContext) -> Option<Self> {
+        match subscription.active() {
+            true => {
+                let Self::BlockAdded(notification) = self else { return None };
+                notification.apply_block_added_subscription(subscription).map(Self::BlockAdded)
+            }
+            false => None,
+        }
+    }
+
     fn apply_virtual_chain_changed_subscription(
         &self,
         subscription: &VirtualChainChangedSubscription,
@@ -83,13 +99,13 @@ impl NotificationTrait for Notification {
     ) -> Option<Self> {
         match subscription.active() {
             true => {
+                // If the subscription excludes or filters the accepted transaction ids and the notification includes some
+                // then we must re-create the object and drop the excluded ids, otherwise we can clone it as is.
                 if let Notification::VirtualChainChanged(ref payload) = self {
-                    if !subscription.include_accepted_transaction_ids() && !payload.accepted_transaction_ids.is_empty() {
-                        return Some(Notification::VirtualChainChanged(VirtualChainChangedNotification {
-                            removed_chain_block_hashes: payload.removed_chain_block_hashes.clone(),
-                            added_chain_block_hashes: payload.added_chain_block_hashes.clone(),
-                            accepted_transaction_ids: Arc::new(vec![]),
-                        }));
+                    if !payload.accepted_transaction_ids.is_empty() {
+                        return Some(Notification::VirtualChainChanged(
+                            payload.apply_virtual_chain_changed_subscription(subscription),
+                        ));
                     }
                 }
                 Some(self.clone())
@@ -112,6 +128,20 @@ impl NotificationTrait for Notification {
         }
     }
 
+    fn apply_transaction_status_changed_subscription(
+        &self,
+        subscription: &TransactionStatusChangedSubscription,
+        _context: &SubscriptionContext,
+    ) -> Option<Self> {
+        match subscription.active() {
+            true => {
+                let Self::TransactionStatusChanged(notification) = self else { return None };
+                notification.apply_transaction_status_changed_subscription(subscription).map(Self::TransactionStatusChanged)
+            }
+            false => None,
+        }
+    }
+
     fn event_type(&self) -> EventType {
         self.into()
     }
@@ -157,6 +187,10 @@ impl Serializer for Notification {
                 store!(u16, &8, writer)?;
                 serialize!(NewBlockTemplateNotification, notification, writer)?;
             }
+            Notification::TransactionStatusChanged(notification) => {
+                store!(u16, &9, writer)?;
+                serialize!(TransactionStatusChangedNotification, notification, writer)?;
+            }
         }
         Ok(())
     }
@@ -202,6 +236,10 @@ impl Deserializer for Notification {
                 let notification = deserialize!(NewBlockTemplateNotification, reader)?;
                 Ok(Notification::NewBlockTemplate(notification))
             }
+            9 => {
+                let notification = deserialize!(TransactionStatusChangedNotification, reader)?;
+                Ok(Notification::TransactionStatusChanged(notification))
+            }
             _ => Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "Invalid variant")),
         }
     }
@@ -2,6 +2,7 @@
 //! RPC Operations used to identify RPC methods during transport and in various RPC-related macros.
 //!
 
+use crate::api::{auth::RpcPermission, rate_limit::RpcRateCategory};
 use borsh::{BorshDeserialize, BorshSerialize};
 use vecno_notify::events::EventType;
 use serde::{Deserialize, Serialize};
@@ -40,6 +41,7 @@ pub enum RpcApiOps {
     NotifyVirtualDaaScoreChanged = 16,
     NotifyVirtualChainChanged = 17,
     NotifySinkBlueScoreChanged = 18,
+    NotifyTransactionStatusChanged = 19,
 
     // Notification ops required by wRPC
 
@@ -54,6 +56,7 @@ pub enum RpcApiOps {
     VirtualDaaScoreChangedNotification = 66,
     PruningPointUtxoSetOverrideNotification = 67,
     NewBlockTemplateNotification = 68,
+    TransactionStatusChangedNotification = 69,
 
     // RPC methods
     /// Ping the node to check if connection is alive
@@ -138,6 +141,18 @@ pub enum RpcApiOps {
     GetCurrentBlockColor = 149,
     /// Get UTXO Return Addresses
     GetUtxoReturnAddress = 150,
+    /// Get an accepted transaction by its id (requires the transaction index)
+    GetTransaction = 151,
+    /// Get a page of the received and spent events of an address (requires the address history index)
+    GetAddressHistory = 152,
+    /// Submit a package of related transactions to the mempool
+    SubmitPackage = 153,
+    /// Get a chunk of the virtual selected parent chain with the transactions accepted by each chain block
+    GetVirtualChainChunk = 154,
+    /// Get the fee estimate with its confidence levels along with the feerates paid in the recently mined blocks
+    GetFeeEstimateHistory = 155,
+    /// Get the status of a transaction, from its submission to its acceptance by the virtual chain
+    GetTransactionStatus = 156,
 }
 
 impl RpcApiOps {
@@ -153,10 +168,110 @@ impl RpcApiOps {
                 | RpcApiOps::NotifyFinalityConflictResolved
                 | RpcApiOps::NotifySinkBlueScoreChanged
                 | RpcApiOps::NotifyVirtualDaaScoreChanged
+                | RpcApiOps::NotifyTransactionStatusChanged
                 | RpcApiOps::Subscribe
                 | RpcApiOps::Unsubscribe
         )
     }
+
+    /// Returns the permission required to call the method
+    pub fn permission(&self) -> RpcPermission {
+        match self {
+            RpcApiOps::SubmitTransaction | RpcApiOps::SubmitTransactionReplacement | RpcApiOps::SubmitPackage => RpcPermission::Submit,
+            RpcApiOps::GetBlockTemplate
+            | RpcApiOps::SubmitBlock
+            | RpcApiOps::NotifyNewBlockTemplate
+            | RpcApiOps::NewBlockTemplateNotification => RpcPermission::Mining,
+            RpcApiOps::Shutdown
+            | RpcApiOps::AddPeer
+            | RpcApiOps::Ban
+            | RpcApiOps::Unban
+            | RpcApiOps::ResolveFinalityConflict
+            | RpcApiOps::GetPeerAddresses
+            | RpcApiOps::GetConnectedPeerInfo => RpcPermission::Admin,
+            RpcApiOps::NoOp
+            | RpcApiOps::Connect
+            | RpcApiOps::Disconnect
+            | RpcApiOps::Subscribe
+            | RpcApiOps::Unsubscribe
+            | RpcApiOps::NotifyBlockAdded
+            | RpcApiOps::NotifyUtxosChanged
+            | RpcApiOps::NotifyPruningPointUtxoSetOverride
+            | RpcApiOps::NotifyFinalityConflict
+            | RpcApiOps::NotifyFinalityConflictResolved
+            | RpcApiOps::NotifyVirtualDaaScoreChanged
+            | RpcApiOps::NotifyVirtualChainChanged
+            | RpcApiOps::NotifySinkBlueScoreChanged
+            | RpcApiOps::NotifyTransactionStatusChanged
+            | RpcApiOps::BlockAddedNotification
+            | RpcApiOps::VirtualChainChangedNotification
+            | RpcApiOps::FinalityConflictNotification
+            | RpcApiOps::FinalityConflictResolvedNotification
+            | RpcApiOps::UtxosChangedNotification
+            | RpcApiOps::SinkBlueScoreChangedNotification
+            | RpcApiOps::VirtualDaaScoreChangedNotification
+            | RpcApiOps::PruningPointUtxoSetOverrideNotification
+            | RpcApiOps::TransactionStatusChangedNotification
+            | RpcApiOps::Ping
+            | RpcApiOps::GetMetrics
+            | RpcApiOps::GetSystemInfo
+            | RpcApiOps::GetConnections
+            | RpcApiOps::GetServerInfo
+            | RpcApiOps::GetSyncStatus
+            | RpcApiOps::GetCurrentNetwork
+            | RpcApiOps::GetSink
+            | RpcApiOps::GetMempoolEntry
+            | RpcApiOps::GetMempoolEntries
+            | RpcApiOps::GetBlock
+            | RpcApiOps::GetSubnetwork
+            | RpcApiOps::GetVirtualChainFromBlock
+            | RpcApiOps::GetBlocks
+            | RpcApiOps::GetBlockCount
+            | RpcApiOps::GetBlockDagInfo
+            | RpcApiOps::GetHeaders
+            | RpcApiOps::GetUtxosByAddresses
+            | RpcApiOps::GetBalanceByAddress
+            | RpcApiOps::GetBalancesByAddresses
+            | RpcApiOps::GetSinkBlueScore
+            | RpcApiOps::GetInfo
+            | RpcApiOps::EstimateNetworkHashesPerSecond
+            | RpcApiOps::GetMempoolEntriesByAddresses
+            | RpcApiOps::GetCoinSupply
+            | RpcApiOps::GetDaaScoreTimestampEstimate
+            | RpcApiOps::GetFeeEstimate
+            | RpcApiOps::GetFeeEstimateExperimental
+            | RpcApiOps::GetCurrentBlockColor
+            | RpcApiOps::GetUtxoReturnAddress
+            | RpcApiOps::GetTransaction
+            | RpcApiOps::GetAddressHistory
+            | RpcApiOps::GetVirtualChainChunk
+            | RpcApiOps::GetFeeEstimateHistory
+            | RpcApiOps::GetTransactionStatus => RpcPermission::Read,
+        }
+    }
+
+    /// Returns the category of the method rate limits
+    pub fn rate_category(&self) -> RpcRateCategory {
+        match self {
+            RpcApiOps::GetBlocks
+            | RpcApiOps::GetHeaders
+            | RpcApiOps::GetVirtualChainFromBlock
+            | RpcApiOps::GetVirtualChainChunk
+            | RpcApiOps::GetUtxosByAddresses
+            | RpcApiOps::GetBalancesByAddresses
+            | RpcApiOps::GetMempoolEntries
+            | RpcApiOps::GetMempoolEntriesByAddresses
+            | RpcApiOps::GetAddressHistory
+            | RpcApiOps::GetUtxoReturnAddress
+            | RpcApiOps::EstimateNetworkHashesPerSecond => RpcRateCategory::Heavy,
+            RpcApiOps::SubmitTransaction | RpcApiOps::SubmitTransactionReplacement | RpcApiOps::SubmitPackage => {
+                RpcRateCategory::Submit
+            }
+            RpcApiOps::GetBlockTemplate | RpcApiOps::SubmitBlock => RpcRateCategory::Mining,
+            op if op.is_subscription() => RpcRateCategory::Subscription,
+            _ => RpcRateCategory::Query,
+        }
+    }
 }
 
 impl From<RpcApiOps> for u32 {
@@ -179,6 +294,7 @@ impl From<EventType> for RpcApiOps {
             EventType::VirtualDaaScoreChanged => RpcApiOps::VirtualDaaScoreChangedNotification,
             EventType::PruningPointUtxoSetOverride => RpcApiOps::PruningPointUtxoSetOverrideNotification,
             EventType::NewBlockTemplate => RpcApiOps::NewBlockTemplateNotification,
+            EventType::TransactionStatusChanged => RpcApiOps::TransactionStatusChangedNotification,
         }
     }
 }
@@ -0,0 +1,206 @@
+//!
+//! Rate limiting of the RPC requests per connection and per IP address.
+//!
+//! The limits are set per [`RpcRateCategory`] in the `[rpc-rate-limits]` section of the node config file, e.g.:
+//!
+//! ```toml
+//! [rpc-rate-limits.heavy]
+//! per-connection = 5
+//! per-ip = 20
+//! ```
+//!
+
+use crate::{api::ops::RpcApiOps, RpcError, RpcResult};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+    net::IpAddr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Instant,
+};
+
+/// Class of RPC methods sharing a rate limit
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RpcRateCategory {
+    /// Cheap queries of the node state
+    Query,
+    /// Queries scanning large parts of the DAG, the mempool or the UTXO set
+    Heavy,
+    /// Transaction submissions
+    Submit,
+    /// Block templates and block submissions
+    Mining,
+    /// Notification subscriptions
+    Subscription,
+}
+
+impl Display for RpcRateCategory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let category = match self {
+            RpcRateCategory::Query => "query",
+            RpcRateCategory::Heavy => "heavy",
+            RpcRateCategory::Submit => "submit",
+            RpcRateCategory::Mining => "mining",
+            RpcRateCategory::Subscription => "subscription",
+        };
+        f.write_str(category)
+    }
+}
+
+/// Requests per second allowed in a category, each limit being unbounded when unset
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct RpcRateLimit {
+    /// Requests per second allowed to each connection
+    pub per_connection: Option<u32>,
+    /// Requests per second allowed to all the connections of an IP address
+    pub per_ip: Option<u32>,
+}
+
+#[derive(Debug, Default)]
+pub struct RpcRateLimitCounters {
+    /// Requests refused for exceeding a per connection limit
+    pub connection_limited_requests: AtomicU64,
+    /// Requests refused for exceeding a per IP address limit
+    pub ip_limited_requests: AtomicU64,
+}
+
+/// Token bucket holding up to one second worth of requests
+#[derive(Debug)]
+struct TokenBucket {
+    rate: f64,
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: u32) -> Self {
+        Self { rate: rate as f64, tokens: rate as f64, refilled_at: Instant::now() }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.refilled_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.rate);
+        self.refilled_at = now;
+    }
+}
+
+type Buckets = Mutex<HashMap<RpcRateCategory, TokenBucket>>;
+
+/// Rate limiter shared by all the RPC servers so that the per IP address limits apply across them
+#[derive(Debug, Default)]
+pub struct RpcRateLimiter {
+    limits: HashMap<RpcRateCategory, RpcRateLimit>,
+    ips: Mutex<HashMap<IpAddr, Arc<Buckets>>>,
+    counters: Arc<RpcRateLimitCounters>,
+}
+
+impl RpcRateLimiter {
+    pub fn new(limits: HashMap<RpcRateCategory, RpcRateLimit>, counters: Arc<RpcRateLimitCounters>) -> Self {
+        Self { limits, ips: Default::default(), counters }
+    }
+
+    /// Returns the limiter of a new connection from `ip`
+    pub fn connection_limiter(self: &Arc<Self>, ip: IpAddr) -> RpcConnectionRateLimiter {
+        let ip_buckets = self.ips.lock().unwrap().entry(ip).or_default().clone();
+        RpcConnectionRateLimiter { limiter: self.clone(), ip, connection_buckets: Default::default(), ip_buckets }
+    }
+}
+
+/// Rate limiter of a single connection
+#[derive(Debug)]
+pub struct RpcConnectionRateLimiter {
+    limiter: Arc<RpcRateLimiter>,
+    ip: IpAddr,
+    connection_buckets: Buckets,
+    ip_buckets: Arc<Buckets>,
+}
+
+impl RpcConnectionRateLimiter {
+    /// Consumes a request of the category of `op`, failing if either the connection or the IP address limit is exceeded
+    pub fn check(&self, op: RpcApiOps) -> RpcResult<()> {
+        let category = op.rate_category();
+        let Some(limit) = self.limiter.limits.get(&category) else {
+            return Ok(());
+        };
+        let now = Instant::now();
+
+        let mut connection_buckets = self.connection_buckets.lock().unwrap();
+        let mut connection_bucket = limit.per_connection.map(|rate| {
+            let bucket = connection_buckets.entry(category).or_insert_with(|| TokenBucket::new(rate));
+            bucket.refill(now);
+            bucket
+        });
+        if let Some(bucket) = connection_bucket.as_ref() {
+            if bucket.tokens < 1.0 {
+                self.limiter.counters.connection_limited_requests.fetch_add(1, Ordering::Relaxed);
+                return Err(RpcError::ConnectionRateLimitExceeded(category.to_string(), bucket.rate as u32));
+            }
+        }
+
+        let mut ip_buckets = self.ip_buckets.lock().unwrap();
+        let ip_bucket = limit.per_ip.map(|rate| {
+            let bucket = ip_buckets.entry(category).or_insert_with(|| TokenBucket::new(rate));
+            bucket.refill(now);
+            bucket
+        });
+        if let Some(bucket) = ip_bucket {
+            if bucket.tokens < 1.0 {
+                self.limiter.counters.ip_limited_requests.fetch_add(1, Ordering::Relaxed);
+                return Err(RpcError::IpRateLimitExceeded(category.to_string(), bucket.rate as u32));
+            }
+            bucket.tokens -= 1.0;
+        }
+        if let Some(bucket) = connection_bucket.as_mut() {
+            bucket.tokens -= 1.0;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for RpcConnectionRateLimiter {
+    fn drop(&mut self) {
+        // Release the IP address buckets with its last connection
+        let mut ips = self.limiter.ips.lock().unwrap();
+        if Arc::strong_count(&self.ip_buckets) == 2 {
+            ips.remove(&self.ip);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limiter() {
+        let limits = HashMap::from([(RpcRateCategory::Heavy, RpcRateLimit { per_connection: Some(2), per_ip: Some(3) })]);
+        let counters = Arc::new(RpcRateLimitCounters::default());
+        let limiter = Arc::new(RpcRateLimiter::new(limits, counters.clone()));
+        let ip: IpAddr = "192.168.0.1".parse().unwrap();
+
+        let first = limiter.connection_limiter(ip);
+        let second = limiter.connection_limiter(ip);
+        assert!(first.check(RpcApiOps::GetBlocks).is_ok());
+        assert!(first.check(RpcApiOps::GetUtxosByAddresses).is_ok());
+        assert!(matches!(first.check(RpcApiOps::GetBlocks), Err(RpcError::ConnectionRateLimitExceeded(..))));
+        assert!(second.check(RpcApiOps::GetBlocks).is_ok());
+        assert!(matches!(second.check(RpcApiOps::GetBlocks), Err(RpcError::IpRateLimitExceeded(..))));
+
+        // Unlimited categories and other addresses are not affected
+        assert!(first.check(RpcApiOps::GetInfo).is_ok());
+        assert!(limiter.connection_limiter("192.168.0.2".parse().unwrap()).check(RpcApiOps::GetBlocks).is_ok());
+
+        assert_eq!(counters.connection_limited_requests.load(Ordering::Relaxed), 1);
+        assert_eq!(counters.ip_limited_requests.load(Ordering::Relaxed), 1);
+
+        drop(first);
+        drop(second);
+        assert!(limiter.ips.lock().unwrap().is_empty());
+    }
+}
@@ -10,10 +10,33 @@ use crate::api::connection::DynRpcConnection;
 use crate::{model::*, notify::connection::ChannelConnection, RpcResult};
 use async_trait::async_trait;
 use downcast::{downcast_sync, AnySync};
+use futures::{
+    stream::{self, BoxStream},
+    StreamExt,
+};
 use vecno_notify::{listener::ListenerId, scope::Scope, subscription::Command};
 use std::sync::Arc;
 
 pub const MAX_SAFE_WINDOW_SIZE: u32 = 10_000;
+/// The maximum number of entries returned by a single `GetAddressHistory` call
+pub const MAX_ADDRESS_HISTORY_PAGE_SIZE: u32 = 1_000;
+/// The maximum number of entries returned by a single paged `GetMempoolEntries` call
+pub const MAX_MEMPOOL_ENTRIES_PAGE_SIZE: u32 = 10_000;
+/// The maximum number of added chain blocks returned by a single `GetVirtualChainChunk` call
+pub const MAX_VIRTUAL_CHAIN_CHUNK_SIZE: u32 = 100;
+/// The number of accepted transactions past which a `GetVirtualChainChunk` call stops adding chain blocks.
+/// A chunk always holds at least one added chain block, whatever the number of transactions it accepted.
+pub const MAX_VIRTUAL_CHAIN_CHUNK_TRANSACTIONS: usize = 10_000;
+/// The maximum number of block feerate records returned by a single `GetFeeEstimateHistory` call
+pub const MAX_FEE_ESTIMATE_HISTORY_SIZE: u32 = 1_000;
+/// The default duration, in milliseconds, a long-polling `GetBlockTemplate` call waits for a new template
+pub const DEFAULT_BLOCK_TEMPLATE_LONG_POLL_TIMEOUT: u64 = 4_000;
+/// The maximum duration, in milliseconds, a long-polling `GetBlockTemplate` call waits for a new template
+pub const MAX_BLOCK_TEMPLATE_LONG_POLL_TIMEOUT: u64 = 60_000;
+/// The maximum number of mandatory transactions, by id or raw, a `GetBlockTemplate` call may request
+pub const MAX_BLOCK_TEMPLATE_MANDATORY_TRANSACTIONS: usize = 1_000;
+/// The maximum number of transaction ids a `GetBlockTemplate` call may exclude from its template
+pub const MAX_BLOCK_TEMPLATE_EXCLUDED_TRANSACTIONS: usize = 10_000;
 
 /// Client RPC Api
 ///
@@ -226,6 +249,18 @@ pub trait RpcApi: Sync + Send + AnySync {
         request: SubmitTransactionReplacementRequest,
     ) -> RpcResult<SubmitTransactionReplacementResponse>;
 
+    /// Submits a package of related transactions to the mempool, all of them being accepted or none.
+    ///
+    /// Returns the IDs of the package transactions in topological order.
+    async fn submit_package(&self, transactions: Vec<RpcTransaction>) -> RpcResult<Vec<RpcTransactionId>> {
+        Ok(self.submit_package_call(None, SubmitPackageRequest { transactions }).await?.transaction_ids)
+    }
+    async fn submit_package_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: SubmitPackageRequest,
+    ) -> RpcResult<SubmitPackageResponse>;
+
     /// Requests information about a specific block.
     async fn get_block(&self, hash: RpcHash, include_transactions: bool) -> RpcResult<RpcBlock> {
         Ok(self.get_block_call(None, GetBlockRequest::new(hash, include_transactions)).await?.block)
@@ -261,6 +296,31 @@ pub trait RpcApi: Sync + Send + AnySync {
         request: GetVirtualChainFromBlockRequest,
     ) -> RpcResult<GetVirtualChainFromBlockResponse>;
 
+    /// Requests a chunk of at most `max_chain_blocks` blocks of the virtual selected parent chain from some `start_hash`,
+    /// along with the transactions accepted by each of them and the UTXO entries these spend.
+    async fn get_virtual_chain_chunk(&self, start_hash: RpcHash, max_chain_blocks: u32) -> RpcResult<GetVirtualChainChunkResponse> {
+        self.get_virtual_chain_chunk_call(None, GetVirtualChainChunkRequest::new(start_hash, max_chain_blocks)).await
+    }
+    async fn get_virtual_chain_chunk_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetVirtualChainChunkRequest,
+    ) -> RpcResult<GetVirtualChainChunkResponse>;
+
+    /// Streams the virtual selected parent chain from some `start_hash` up to this node's current sink, in chunks
+    /// of at most `max_chain_blocks` blocks.
+    ///
+    /// The stream ends after the chunk reaching the sink or after the first error. By default, the chunks are
+    /// requested one after the other (see [`stream_virtual_chain_in_chunks`]). The gRPC client has them streamed
+    /// by the server instead.
+    fn stream_virtual_chain_from_block(
+        &self,
+        start_hash: RpcHash,
+        max_chain_blocks: u32,
+    ) -> BoxStream<'_, RpcResult<GetVirtualChainChunkResponse>> {
+        stream_virtual_chain_in_chunks(self, start_hash, max_chain_blocks)
+    }
+
     /// Requests blocks between a certain block `low_hash` up to this node's current virtual.
     async fn get_blocks(
         &self,
@@ -451,6 +511,41 @@ pub trait RpcApi: Sync + Send + AnySync {
         request: GetUtxoReturnAddressRequest,
     ) -> RpcResult<GetUtxoReturnAddressResponse>;
 
+    /// Requests an accepted transaction by its id. Requires the node to run with `--txindex`.
+    async fn get_transaction(&self, transaction_id: RpcTransactionId) -> RpcResult<GetTransactionResponse> {
+        self.get_transaction_call(None, GetTransactionRequest { transaction_id }).await
+    }
+    async fn get_transaction_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetTransactionRequest,
+    ) -> RpcResult<GetTransactionResponse>;
+
+    /// Requests a page of the received and spent events of an address. Requires the node to run with `--addressindex`.
+    async fn get_address_history(
+        &self,
+        address: RpcAddress,
+        cursor: Option<String>,
+        limit: u32,
+    ) -> RpcResult<GetAddressHistoryResponse> {
+        self.get_address_history_call(None, GetAddressHistoryRequest { address, cursor, limit }).await
+    }
+    async fn get_address_history_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetAddressHistoryRequest,
+    ) -> RpcResult<GetAddressHistoryResponse>;
+
+    /// Requests the status of a transaction, from the mempool outcome to its acceptance by the virtual chain.
+    async fn get_transaction_status(&self, transaction_id: RpcTransactionId) -> RpcResult<RpcTransactionStatus> {
+        Ok(self.get_transaction_status_call(None, GetTransactionStatusRequest::new(transaction_id)).await?.status)
+    }
+    async fn get_transaction_status_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetTransactionStatusRequest,
+    ) -> RpcResult<GetTransactionStatusResponse>;
+
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Fee estimation API
 
@@ -472,6 +567,17 @@ pub trait RpcApi: Sync + Send + AnySync {
         request: GetFeeEstimateExperimentalRequest,
     ) -> RpcResult<GetFeeEstimateExperimentalResponse>;
 
+    /// Requests the current fee estimate, with the confidence of each bucket as observed in the recently mined blocks,
+    /// along with the feerate records of at most `max_blocks` of these blocks.
+    async fn get_fee_estimate_history(&self, max_blocks: u32) -> RpcResult<GetFeeEstimateHistoryResponse> {
+        self.get_fee_estimate_history_call(None, GetFeeEstimateHistoryRequest::new(max_blocks)).await
+    }
+    async fn get_fee_estimate_history_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetFeeEstimateHistoryRequest,
+    ) -> RpcResult<GetFeeEstimateHistoryResponse>;
+
     ///
     async fn get_current_block_color(&self, hash: RpcHash) -> RpcResult<GetCurrentBlockColorResponse> {
         Ok(self.get_current_block_color_call(None, GetCurrentBlockColorRequest { hash }).await?)
@@ -509,6 +615,27 @@ pub trait RpcApi: Sync + Send + AnySync {
     }
 }
 
+/// Streams the virtual selected parent chain of `rpc` from some `start_hash` up to its current sink, in chunks of at most
+/// `max_chain_blocks` blocks requested one after the other (see [`RpcApi::get_virtual_chain_chunk`]).
+///
+/// The stream ends after the chunk reaching the sink or after the first error.
+pub fn stream_virtual_chain_in_chunks<R: RpcApi + ?Sized>(
+    rpc: &R,
+    start_hash: RpcHash,
+    max_chain_blocks: u32,
+) -> BoxStream<'_, RpcResult<GetVirtualChainChunkResponse>> {
+    stream::unfold(Some(start_hash), move |start_hash| async move {
+        let start_hash = start_hash?;
+        let chunk = rpc.get_virtual_chain_chunk(start_hash, max_chain_blocks).await;
+        let next_start_hash = match chunk {
+            Ok(ref chunk) if !chunk.is_tip => chunk.added_chain_blocks.last().map(|x| x.hash),
+            _ => None,
+        };
+        Some((chunk, next_start_hash))
+    })
+    .boxed()
+}
+
 pub type DynRpcService = Arc<dyn RpcApi>;
 
 downcast_sync!(dyn RpcApi);
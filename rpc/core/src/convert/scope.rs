@@ -2,8 +2,8 @@
 
 use crate::{
     NotifyBlockAddedRequest, NotifyFinalityConflictRequest, NotifyNewBlockTemplateRequest, NotifyPruningPointUtxoSetOverrideRequest,
-    NotifySinkBlueScoreChangedRequest, NotifyUtxosChangedRequest, NotifyVirtualChainChangedRequest,
-    NotifyVirtualDaaScoreChangedRequest,
+    NotifySinkBlueScoreChangedRequest, NotifyTransactionStatusChangedRequest, NotifyUtxosChangedRequest,
+    NotifyVirtualChainChangedRequest, NotifyVirtualDaaScoreChangedRequest,
 };
 use vecno_notify::scope::*;
 
@@ -45,9 +45,11 @@ macro_rules! from {
     };
 }
 
-from!(BlockAdded);
+from!(item: BlockAdded, {
+    Self::new(item.filter, item.header_only)
+});
 from!(item: VirtualChainChanged, {
-    Self::new(item.include_accepted_transaction_ids)
+    Self::with_transaction_ids(item.include_accepted_transaction_ids, item.transaction_ids).with_resume_from(item.resume_from)
 });
 from!(FinalityConflict);
 impl From<&NotifyFinalityConflictRequest> for FinalityConflictResolvedScope {
@@ -56,9 +58,12 @@ impl From<&NotifyFinalityConflictRequest> for FinalityConflictResolvedScope {
     }
 }
 from!(item: UtxosChanged, {
-    Self::new(item.addresses.clone())
+    Self::new(item.addresses.clone()).with_resume_from(item.resume_from)
 });
 from!(SinkBlueScoreChanged);
 from!(VirtualDaaScoreChanged);
 from!(PruningPointUtxoSetOverride);
 from!(NewBlockTemplate);
+from!(item: TransactionStatusChanged, {
+    Self::new(item.transaction_ids)
+});
@@ -51,6 +51,9 @@ pub enum RpcError {
     #[error("Rejected transaction {0}: {1}")]
     RejectedTransaction(RpcTransactionId, String),
 
+    #[error("Rejected package: {0}")]
+    RejectedPackage(String),
+
     #[error("Block {0} is invalid. No verbose data can be built.")]
     InvalidBlock(RpcHash),
 
@@ -63,18 +66,48 @@ pub enum RpcError {
     #[error("Method unavailable. Run the node with the --utxoindex argument.")]
     NoUtxoIndex,
 
+    #[error("Method unavailable. Run the node with the --txindex argument.")]
+    NoTxIndex,
+
+    #[error("Method unavailable. Run the node with the --addressindex argument.")]
+    NoAddressIndex,
+
+    #[error("Invalid address history cursor {0}")]
+    InvalidAddressHistoryCursor(String),
+
+    #[error("Invalid mempool entries cursor {0}")]
+    InvalidMempoolEntriesCursor(String),
+
     #[error("Method unavailable. No connection manager is currently available.")]
     NoConnectionManager,
 
     #[error("Requested window size {0} is larger than max {1} allowed in RPC safe mode.")]
     WindowSizeExceedingMaximum(u32, u32),
 
+    #[error("Requested {0} mandatory transactions while at most {1} are allowed.")]
+    MandatoryTransactionsExceedingMaximum(usize, usize),
+
+    #[error("Requested {0} excluded transactions while at most {1} are allowed.")]
+    ExcludedTransactionsExceedingMaximum(usize, usize),
+
     #[error("Requested window size {0} is larger than pruning point depth {1}.")]
     WindowSizeExceedingPruningDepth(u32, u64),
 
     #[error("Method unavailable in safe mode. Run the node with --unsaferpc argument.")]
     UnavailableInSafeMode,
 
+    #[error("Missing or invalid RPC authentication token.")]
+    Unauthenticated,
+
+    #[error("Method {0} is not permitted by the RPC credentials.")]
+    PermissionDenied(String),
+
+    #[error("Rate limit of {1} {0} requests per second per connection exceeded.")]
+    ConnectionRateLimitExceeded(String, u32),
+
+    #[error("Rate limit of {1} {0} requests per second per IP address exceeded.")]
+    IpRateLimitExceeded(String, u32),
+
     #[error("Cannot ban IP {0} because it has some permanent connection.")]
     IpHasPermanentConnection(IpAddress),
 
@@ -1,11 +1,13 @@
-use crate::{RpcTransactionOutpoint, RpcUtxoEntry};
+use crate::{RpcHash, RpcTransactionId, RpcTransactionOutpoint, RpcUtxoEntry};
+use borsh::{BorshDeserialize, BorshSerialize};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use workflow_serializer::prelude::*;
 
 pub type RpcAddress = vecno_addresses::Address;
 
 /// Represents a UTXO entry of an address returned by the `GetUtxosByAddresses` RPC.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcUtxosByAddressesEntry {
     pub address: Option<RpcAddress>,
@@ -33,7 +35,7 @@ impl Deserializer for RpcUtxosByAddressesEntry {
 }
 
 /// Represents a balance of an address returned by the `GetBalancesByAddresses` RPC.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcBalancesByAddressesEntry {
     pub address: RpcAddress,
@@ -58,3 +60,51 @@ impl Deserializer for RpcBalancesByAddressesEntry {
         Ok(Self { address, balance })
     }
 }
+
+/// The way a transaction affected the balance of an address.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[borsh(use_discriminant = true)]
+pub enum RpcAddressActivityKind {
+    Received = 0,
+    Spent = 1,
+}
+
+/// Represents a received or spent event of an address returned by the `GetAddressHistory` RPC.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcAddressHistoryEntry {
+    pub transaction_id: RpcTransactionId,
+    pub kind: RpcAddressActivityKind,
+
+    /// The created output when `kind` is `Received`, the spent one when `kind` is `Spent`
+    pub outpoint: RpcTransactionOutpoint,
+    pub amount: u64,
+    pub accepting_block_hash: RpcHash,
+    pub accepting_block_daa_score: u64,
+}
+
+impl Serializer for RpcAddressHistoryEntry {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u8, &1, writer)?; // version
+        store!(RpcTransactionId, &self.transaction_id, writer)?;
+        store!(RpcAddressActivityKind, &self.kind, writer)?;
+        serialize!(RpcTransactionOutpoint, &self.outpoint, writer)?;
+        store!(u64, &self.amount, writer)?;
+        store!(RpcHash, &self.accepting_block_hash, writer)?;
+        store!(u64, &self.accepting_block_daa_score, writer)
+    }
+}
+
+impl Deserializer for RpcAddressHistoryEntry {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version: u8 = load!(u8, reader)?;
+        let transaction_id = load!(RpcTransactionId, reader)?;
+        let kind = load!(RpcAddressActivityKind, reader)?;
+        let outpoint = deserialize!(RpcTransactionOutpoint, reader)?;
+        let amount = load!(u64, reader)?;
+        let accepting_block_hash = load!(RpcHash, reader)?;
+        let accepting_block_daa_score = load!(u64, reader)?;
+        Ok(Self { transaction_id, kind, outpoint, amount, accepting_block_hash, accepting_block_daa_score })
+    }
+}
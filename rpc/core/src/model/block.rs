@@ -1,18 +1,19 @@
 use super::RpcRawHeader;
 use crate::prelude::{RpcHash, RpcHeader, RpcTransaction};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use workflow_serializer::prelude::*;
 
 /// Raw Rpc block type - without a cached header hash and without verbose data.
 /// Used for mining APIs (get_block_template & submit_block)
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcRawBlock {
     pub header: RpcRawHeader,
     pub transactions: Vec<RpcTransaction>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcBlock {
     pub header: RpcHeader,
@@ -62,7 +63,7 @@ impl Deserializer for RpcRawBlock {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcBlockVerboseData {
     pub hash: RpcHash,
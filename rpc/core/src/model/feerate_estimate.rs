@@ -1,8 +1,9 @@
 use borsh::{BorshDeserialize, BorshSerialize};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use workflow_serializer::prelude::*;
 
-#[derive(Clone, Copy, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, JsonSchema, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcFeerateBucket {
     /// The fee/mass ratio estimated to be required for inclusion time <= estimated_seconds
@@ -10,9 +11,15 @@ pub struct RpcFeerateBucket {
 
     /// The estimated inclusion time for a transaction with fee/mass = feerate
     pub estimated_seconds: f64,
+
+    /// The share of the recently mined transactions paying at least feerate which got included within
+    /// estimated_seconds, if the node has enough fee estimation history to tell
+    #[serde(default)]
+    #[borsh(skip)]
+    pub confidence: Option<f64>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcFeeEstimate {
     /// *Top-priority* feerate bucket. Provides an estimation of the feerate required for sub-second DAG inclusion.
@@ -45,25 +52,35 @@ impl RpcFeeEstimate {
 
 impl Serializer for RpcFeeEstimate {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(RpcFeerateBucket, &self.priority_bucket, writer)?;
         store!(Vec<RpcFeerateBucket>, &self.normal_buckets, writer)?;
         store!(Vec<RpcFeerateBucket>, &self.low_buckets, writer)?;
+        // The bucket confidences are not part of their borsh encoding
+        store!(Vec<Option<f64>>, &self.ordered_buckets().iter().map(|bucket| bucket.confidence).collect::<Vec<_>>(), writer)?;
         Ok(())
     }
 }
 
 impl Deserializer for RpcFeeEstimate {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let version = load!(u16, reader)?;
         let priority_bucket = load!(RpcFeerateBucket, reader)?;
         let normal_buckets = load!(Vec<RpcFeerateBucket>, reader)?;
         let low_buckets = load!(Vec<RpcFeerateBucket>, reader)?;
-        Ok(Self { priority_bucket, normal_buckets, low_buckets })
+        let mut estimate = Self { priority_bucket, normal_buckets, low_buckets };
+        if version > 1 {
+            let confidences = load!(Vec<Option<f64>>, reader)?;
+            let buckets = std::iter::once(&mut estimate.priority_bucket)
+                .chain(estimate.normal_buckets.iter_mut())
+                .chain(estimate.low_buckets.iter_mut());
+            buckets.zip(confidences).for_each(|(bucket, confidence)| bucket.confidence = confidence);
+        }
+        Ok(estimate)
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcFeeEstimateVerboseExperimentalData {
     pub mempool_ready_transactions_count: u64,
@@ -107,3 +124,47 @@ impl Deserializer for RpcFeeEstimateVerboseExperimentalData {
         })
     }
 }
+
+/// Feerate statistics of the mempool transactions included by a recently mined block
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcBlockFeerateRecord {
+    pub daa_score: u64,
+    /// Unix time in milliseconds at which the node handled the block
+    pub timestamp: u64,
+    /// The number of block transactions the node had in its mempool, the only ones the statistics are about
+    pub transaction_count: u32,
+    pub min_feerate: f64,
+    pub median_feerate: f64,
+    pub max_feerate: f64,
+    /// The median time the included transactions spent in the mempool
+    pub median_waited_seconds: f64,
+}
+
+impl Serializer for RpcBlockFeerateRecord {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(u64, &self.daa_score, writer)?;
+        store!(u64, &self.timestamp, writer)?;
+        store!(u32, &self.transaction_count, writer)?;
+        store!(f64, &self.min_feerate, writer)?;
+        store!(f64, &self.median_feerate, writer)?;
+        store!(f64, &self.max_feerate, writer)?;
+        store!(f64, &self.median_waited_seconds, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for RpcBlockFeerateRecord {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let daa_score = load!(u64, reader)?;
+        let timestamp = load!(u64, reader)?;
+        let transaction_count = load!(u32, reader)?;
+        let min_feerate = load!(f64, reader)?;
+        let median_feerate = load!(f64, reader)?;
+        let max_feerate = load!(f64, reader)?;
+        let median_waited_seconds = load!(f64, reader)?;
+        Ok(Self { daa_score, timestamp, transaction_count, min_feerate, median_feerate, max_feerate, median_waited_seconds })
+    }
+}
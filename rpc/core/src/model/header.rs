@@ -1,12 +1,13 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use vecno_consensus_core::{header::Header, BlueWorkType};
 use vecno_hashes::Hash;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use workflow_serializer::prelude::*;
 
 /// Raw Rpc header type - without a cached header hash.
 /// Used for mining APIs (get_block_template & submit_block)
-#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcRawHeader {
     pub version: u16,
@@ -24,7 +25,7 @@ pub struct RpcRawHeader {
     pub pruning_point: Hash,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcHeader {
     /// Cached hash
@@ -1,9 +1,12 @@
 use super::RpcAddress;
 use super::RpcTransaction;
+use crate::RpcTransactionId;
+use borsh::{BorshDeserialize, BorshSerialize};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use workflow_serializer::prelude::*;
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct RpcMempoolEntry {
     pub fee: u64,
     pub transaction: RpcTransaction,
@@ -33,7 +36,7 @@ impl Deserializer for RpcMempoolEntry {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct RpcMempoolEntryByAddress {
     pub address: RpcAddress,
     pub sending: Vec<RpcMempoolEntry>,
@@ -63,6 +66,48 @@ impl Deserializer for RpcMempoolEntryByAddress {
     }
 }
 
+/// Order in which `GetMempoolEntries` pages through the mempool entries.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[borsh(use_discriminant = true)]
+pub enum RpcMempoolEntriesOrder {
+    /// Oldest entries first, by the DAA score at which they entered the mempool
+    #[default]
+    Arrival = 0,
+    /// Best paying entries first, orphans coming last
+    Feerate = 1,
+}
+
+/// Represents a mempool entry returned by a compact `GetMempoolEntries` call.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcCompactMempoolEntry {
+    pub transaction_id: RpcTransactionId,
+    /// The mass of the transaction, reduced to a single one-dimension value
+    pub mass: u64,
+}
+
+impl RpcCompactMempoolEntry {
+    pub fn new(transaction_id: RpcTransactionId, mass: u64) -> Self {
+        Self { transaction_id, mass }
+    }
+}
+
+impl Serializer for RpcCompactMempoolEntry {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(RpcTransactionId, &self.transaction_id, writer)?;
+        store!(u64, &self.mass, writer)
+    }
+}
+
+impl Deserializer for RpcCompactMempoolEntry {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let transaction_id = load!(RpcTransactionId, reader)?;
+        let mass = load!(u64, reader)?;
+        Ok(Self { transaction_id, mass })
+    }
+}
+
 cfg_if::cfg_if! {
     if #[cfg(feature = "wasm32-sdk")] {
         use wasm_bindgen::prelude::*;
@@ -79,6 +124,16 @@ cfg_if::cfg_if! {
                 transaction : ITransaction;
                 isOrphan : boolean;
             }
+
+            /**
+             * Mempool entry returned by a compact request.
+             * 
+             * @category Node RPC
+             */
+            export interface ICompactMempoolEntry {
+                transactionId : HexString;
+                mass : bigint;
+            }
         "#;
     }
 }
@@ -2,8 +2,16 @@ use crate::model::*;
 use borsh::{BorshDeserialize, BorshSerialize};
 use vecno_consensus_core::api::stats::BlockCount;
 use vecno_core::debug;
-use vecno_notify::subscription::{context::SubscriptionContext, single::UtxosChangedSubscription, Command};
+use vecno_consensus_core::tx::Transaction;
+use vecno_notify::subscription::{
+    context::SubscriptionContext,
+    single::{
+        BlockAddedSubscription, TransactionStatusChangedSubscription, UtxosChangedSubscription, VirtualChainChangedSubscription,
+    },
+    Command,
+};
 use vecno_utils::hex::ToHex;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::{
@@ -18,7 +26,7 @@ pub type RpcExtraData = Vec<u8>;
 /// Blocks are generally expected to have been generated using the getBlockTemplate call.
 ///
 /// See: [`GetBlockTemplateRequest`]
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SubmitBlockRequest {
     pub block: RpcRawBlock,
@@ -51,7 +59,7 @@ impl Deserializer for SubmitBlockRequest {
     }
 }
 
-#[derive(Clone, Copy, Eq, PartialEq, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Serialize, Deserialize, JsonSchema, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
 #[borsh(use_discriminant = true)]
 pub enum SubmitBlockRejectReason {
@@ -75,7 +83,7 @@ impl Display for SubmitBlockRejectReason {
     }
 }
 
-#[derive(Eq, PartialEq, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[derive(Eq, PartialEq, Debug, Serialize, Deserialize, JsonSchema, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "lowercase")]
 #[serde(tag = "type", content = "reason")]
 #[borsh(use_discriminant = true)]
@@ -89,7 +97,7 @@ impl SubmitBlockReport {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SubmitBlockResponse {
     pub report: SubmitBlockReport,
@@ -115,26 +123,70 @@ impl Deserializer for SubmitBlockResponse {
 /// GetBlockTemplateRequest requests a current block template.
 /// Callers are expected to solve the block template and submit it using the submitBlock call
 ///
+/// When `long_poll_id` is set to the `template_id` of a previous response, the call waits until a template with
+/// a different id is available, or until `long_poll_timeout` (in milliseconds) elapses, in which case the current
+/// template is returned.
+///
+/// Callers can also constrain the transactions of the template: mandatory transactions, given by mempool id or as
+/// raw transactions, are included ahead of any other while excluded mempool transactions are skipped when filling
+/// the rest of the block by feerate. Mandatory transactions which cannot be included are reported in the response.
+///
 /// See: [`SubmitBlockRequest`]
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetBlockTemplateRequest {
     /// Which vecno address should the coinbase block reward transaction pay into
     pub pay_address: RpcAddress,
     // TODO: replace with hex serialization
     pub extra_data: RpcExtraData,
+    #[serde(default)]
+    pub long_poll_id: Option<String>,
+    #[serde(default)]
+    pub long_poll_timeout: Option<u64>,
+    #[serde(default)]
+    pub mandatory_transaction_ids: Vec<RpcTransactionId>,
+    #[serde(default)]
+    pub mandatory_transactions: Vec<RpcTransaction>,
+    #[serde(default)]
+    pub excluded_transaction_ids: Vec<RpcTransactionId>,
 }
 impl GetBlockTemplateRequest {
     pub fn new(pay_address: RpcAddress, extra_data: RpcExtraData) -> Self {
-        Self { pay_address, extra_data }
+        Self {
+            pay_address,
+            extra_data,
+            long_poll_id: None,
+            long_poll_timeout: None,
+            mandatory_transaction_ids: vec![],
+            mandatory_transactions: vec![],
+            excluded_transaction_ids: vec![],
+        }
+    }
+
+    pub fn with_long_poll(self, long_poll_id: String, long_poll_timeout: Option<u64>) -> Self {
+        Self { long_poll_id: Some(long_poll_id), long_poll_timeout, ..self }
+    }
+
+    pub fn with_transaction_selection(
+        self,
+        mandatory_transaction_ids: Vec<RpcTransactionId>,
+        mandatory_transactions: Vec<RpcTransaction>,
+        excluded_transaction_ids: Vec<RpcTransactionId>,
+    ) -> Self {
+        Self { mandatory_transaction_ids, mandatory_transactions, excluded_transaction_ids, ..self }
     }
 }
 
 impl Serializer for GetBlockTemplateRequest {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &3, writer)?;
         store!(RpcAddress, &self.pay_address, writer)?;
         store!(RpcExtraData, &self.extra_data, writer)?;
+        store!(Option<String>, &self.long_poll_id, writer)?;
+        store!(Option<u64>, &self.long_poll_timeout, writer)?;
+        store!(Vec<RpcTransactionId>, &self.mandatory_transaction_ids, writer)?;
+        serialize!(Vec<RpcTransaction>, &self.mandatory_transactions, writer)?;
+        store!(Vec<RpcTransactionId>, &self.excluded_transaction_ids, writer)?;
 
         Ok(())
     }
@@ -142,15 +194,30 @@ impl Serializer for GetBlockTemplateRequest {
 
 impl Deserializer for GetBlockTemplateRequest {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let version = load!(u16, reader)?;
         let pay_address = load!(RpcAddress, reader)?;
         let extra_data = load!(RpcExtraData, reader)?;
+        let (long_poll_id, long_poll_timeout) =
+            if version > 1 { (load!(Option<String>, reader)?, load!(Option<u64>, reader)?) } else { (None, None) };
+        let (mandatory_transaction_ids, mandatory_transactions, excluded_transaction_ids) = if version > 2 {
+            (load!(Vec<RpcTransactionId>, reader)?, deserialize!(Vec<RpcTransaction>, reader)?, load!(Vec<RpcTransactionId>, reader)?)
+        } else {
+            Default::default()
+        };
 
-        Ok(Self { pay_address, extra_data })
+        Ok(Self {
+            pay_address,
+            extra_data,
+            long_poll_id,
+            long_poll_timeout,
+            mandatory_transaction_ids,
+            mandatory_transactions,
+            excluded_transaction_ids,
+        })
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetBlockTemplateResponse {
     pub block: RpcRawBlock,
@@ -160,13 +227,24 @@ pub struct GetBlockTemplateResponse {
     /// That is because when vecnod isn't in sync with the rest of the network there's a high
     /// chance the block will never be accepted, thus the solving effort would have been wasted.
     pub is_synced: bool,
+
+    /// Identifies the template up to its coinbase and timestamp, so that callers can skip templates they already work on.
+    /// It changes when the template is built over a new sink or with materially higher fees.
+    #[serde(default)]
+    pub template_id: String,
+
+    /// The mandatory transactions of the request which could not be included in the template
+    #[serde(default)]
+    pub rejected_transactions: Vec<RpcRejectedTransaction>,
 }
 
 impl Serializer for GetBlockTemplateResponse {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &3, writer)?;
         serialize!(RpcRawBlock, &self.block, writer)?;
         store!(bool, &self.is_synced, writer)?;
+        store!(String, &self.template_id, writer)?;
+        store!(Vec<RpcRejectedTransaction>, &self.rejected_transactions, writer)?;
 
         Ok(())
     }
@@ -174,16 +252,18 @@ impl Serializer for GetBlockTemplateResponse {
 
 impl Deserializer for GetBlockTemplateResponse {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let version = load!(u16, reader)?;
         let block = deserialize!(RpcRawBlock, reader)?;
         let is_synced = load!(bool, reader)?;
+        let template_id = if version > 1 { load!(String, reader)? } else { Default::default() };
+        let rejected_transactions = if version > 2 { load!(Vec<RpcRejectedTransaction>, reader)? } else { Default::default() };
 
-        Ok(Self { block, is_synced })
+        Ok(Self { block, is_synced, template_id, rejected_transactions })
     }
 }
 
 /// GetBlockRequest requests information about a specific block
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetBlockRequest {
     /// The hash of the requested block
@@ -218,7 +298,7 @@ impl Deserializer for GetBlockRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetBlockResponse {
     pub block: RpcBlock,
@@ -243,7 +323,7 @@ impl Deserializer for GetBlockResponse {
 }
 
 /// GetInfoRequest returns info about the node.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetInfoRequest {}
 
@@ -261,7 +341,7 @@ impl Deserializer for GetInfoRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetInfoResponse {
     pub p2p_id: String,
@@ -303,7 +383,7 @@ impl Deserializer for GetInfoResponse {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetCurrentNetworkRequest {}
 
@@ -321,7 +401,7 @@ impl Deserializer for GetCurrentNetworkRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetCurrentNetworkResponse {
     pub network: RpcNetworkType,
@@ -349,7 +429,7 @@ impl Deserializer for GetCurrentNetworkResponse {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetPeerAddressesRequest {}
 
@@ -367,7 +447,7 @@ impl Deserializer for GetPeerAddressesRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetPeerAddressesResponse {
     pub known_addresses: Vec<RpcPeerAddress>,
@@ -398,7 +478,7 @@ impl Deserializer for GetPeerAddressesResponse {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetSinkRequest {}
 
@@ -416,7 +496,7 @@ impl Deserializer for GetSinkRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetSinkResponse {
     pub sink: RpcHash,
@@ -444,7 +524,7 @@ impl Deserializer for GetSinkResponse {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetMempoolEntryRequest {
     pub transaction_id: RpcTransactionId,
@@ -481,7 +561,7 @@ impl Deserializer for GetMempoolEntryRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetMempoolEntryResponse {
     pub mempool_entry: RpcMempoolEntry,
@@ -509,25 +589,80 @@ impl Deserializer for GetMempoolEntryResponse {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+/// GetMempoolEntriesRequest requests a page of the transactions currently in the mempool.
+///
+/// Without a `limit`, all the selected entries are returned at once.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetMempoolEntriesRequest {
     pub include_orphan_pool: bool,
     // TODO: replace with `include_transaction_pool`
     pub filter_transaction_pool: bool,
+    /// The `next_cursor` of a previous response, or `None` to start from the first entry
+    #[serde(default)]
+    pub cursor: Option<String>,
+    /// The maximum number of entries to return
+    #[serde(default)]
+    pub limit: Option<u32>,
+    #[serde(default)]
+    pub order: RpcMempoolEntriesOrder,
+    /// Excludes the entries paying a lower feerate, in veni per gram, orphans included if positive
+    #[serde(default)]
+    pub min_feerate: Option<f64>,
+    /// Only includes the entries spending from or paying to one of these addresses, if not empty
+    #[serde(default)]
+    pub addresses: Vec<RpcAddress>,
+    /// Returns only the ids and masses of the entries in `compact_entries`
+    #[serde(default)]
+    pub compact: bool,
 }
 
 impl GetMempoolEntriesRequest {
     pub fn new(include_orphan_pool: bool, filter_transaction_pool: bool) -> Self {
-        Self { include_orphan_pool, filter_transaction_pool }
+        Self {
+            include_orphan_pool,
+            filter_transaction_pool,
+            cursor: None,
+            limit: None,
+            order: Default::default(),
+            min_feerate: None,
+            addresses: vec![],
+            compact: false,
+        }
+    }
+
+    pub fn with_page(self, cursor: Option<String>, limit: u32) -> Self {
+        Self { cursor, limit: Some(limit), ..self }
+    }
+
+    pub fn with_order(self, order: RpcMempoolEntriesOrder) -> Self {
+        Self { order, ..self }
+    }
+
+    pub fn with_min_feerate(self, min_feerate: f64) -> Self {
+        Self { min_feerate: Some(min_feerate), ..self }
+    }
+
+    pub fn with_addresses(self, addresses: Vec<RpcAddress>) -> Self {
+        Self { addresses, ..self }
+    }
+
+    pub fn with_compact(self, compact: bool) -> Self {
+        Self { compact, ..self }
     }
 }
 
 impl Serializer for GetMempoolEntriesRequest {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(bool, &self.include_orphan_pool, writer)?;
         store!(bool, &self.filter_transaction_pool, writer)?;
+        store!(Option<String>, &self.cursor, writer)?;
+        store!(Option<u32>, &self.limit, writer)?;
+        store!(RpcMempoolEntriesOrder, &self.order, writer)?;
+        store!(Option<f64>, &self.min_feerate, writer)?;
+        store!(Vec<RpcAddress>, &self.addresses, writer)?;
+        store!(bool, &self.compact, writer)?;
 
         Ok(())
     }
@@ -535,43 +670,66 @@ impl Serializer for GetMempoolEntriesRequest {
 
 impl Deserializer for GetMempoolEntriesRequest {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let version = load!(u16, reader)?;
         let include_orphan_pool = load!(bool, reader)?;
         let filter_transaction_pool = load!(bool, reader)?;
+        let mut request = Self::new(include_orphan_pool, filter_transaction_pool);
+        if version > 1 {
+            request.cursor = load!(Option<String>, reader)?;
+            request.limit = load!(Option<u32>, reader)?;
+            request.order = load!(RpcMempoolEntriesOrder, reader)?;
+            request.min_feerate = load!(Option<f64>, reader)?;
+            request.addresses = load!(Vec<RpcAddress>, reader)?;
+            request.compact = load!(bool, reader)?;
+        }
 
-        Ok(Self { include_orphan_pool, filter_transaction_pool })
+        Ok(request)
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetMempoolEntriesResponse {
+    /// The entries of the page, empty in compact requests
     pub mempool_entries: Vec<RpcMempoolEntry>,
+    /// The entries of the page in compact requests
+    #[serde(default)]
+    pub compact_entries: Vec<RpcCompactMempoolEntry>,
+    /// The cursor to pass in order to fetch the next page, `None` if this is the last page
+    #[serde(default)]
+    pub next_cursor: Option<String>,
 }
 
 impl GetMempoolEntriesResponse {
     pub fn new(mempool_entries: Vec<RpcMempoolEntry>) -> Self {
-        Self { mempool_entries }
+        Self { mempool_entries, compact_entries: vec![], next_cursor: None }
     }
 }
 
 impl Serializer for GetMempoolEntriesResponse {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         serialize!(Vec<RpcMempoolEntry>, &self.mempool_entries, writer)?;
+        serialize!(Vec<RpcCompactMempoolEntry>, &self.compact_entries, writer)?;
+        store!(Option<String>, &self.next_cursor, writer)?;
         Ok(())
     }
 }
 
 impl Deserializer for GetMempoolEntriesResponse {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let version = load!(u16, reader)?;
         let mempool_entries = deserialize!(Vec<RpcMempoolEntry>, reader)?;
-        Ok(Self { mempool_entries })
+        let (compact_entries, next_cursor) = if version > 1 {
+            (deserialize!(Vec<RpcCompactMempoolEntry>, reader)?, load!(Option<String>, reader)?)
+        } else {
+            (vec![], None)
+        };
+        Ok(Self { mempool_entries, compact_entries, next_cursor })
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetConnectedPeerInfoRequest {}
 
@@ -589,7 +747,7 @@ impl Deserializer for GetConnectedPeerInfoRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetConnectedPeerInfoResponse {
     pub peer_info: Vec<RpcPeerInfo>,
@@ -603,21 +761,27 @@ impl GetConnectedPeerInfoResponse {
 
 impl Serializer for GetConnectedPeerInfoResponse {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(Vec<RpcPeerInfo>, &self.peer_info, writer)?;
+        // The misbehavior scores are not part of the borsh encoding of the peer info
+        store!(Vec<u32>, &self.peer_info.iter().map(|peer| peer.misbehavior_score).collect::<Vec<_>>(), writer)?;
         Ok(())
     }
 }
 
 impl Deserializer for GetConnectedPeerInfoResponse {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
-        let peer_info = load!(Vec<RpcPeerInfo>, reader)?;
+        let version = load!(u16, reader)?;
+        let mut peer_info = load!(Vec<RpcPeerInfo>, reader)?;
+        if version > 1 {
+            let misbehavior_scores = load!(Vec<u32>, reader)?;
+            peer_info.iter_mut().zip(misbehavior_scores).for_each(|(peer, score)| peer.misbehavior_score = score);
+        }
         Ok(Self { peer_info })
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AddPeerRequest {
     pub peer_address: RpcContextualPeerAddress,
@@ -650,7 +814,7 @@ impl Deserializer for AddPeerRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AddPeerResponse {}
 
@@ -668,7 +832,7 @@ impl Deserializer for AddPeerResponse {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SubmitTransactionRequest {
     pub transaction: RpcTransaction,
@@ -701,7 +865,7 @@ impl Deserializer for SubmitTransactionRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SubmitTransactionResponse {
     pub transaction_id: RpcTransactionId,
@@ -731,7 +895,7 @@ impl Deserializer for SubmitTransactionResponse {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SubmitTransactionReplacementRequest {
     pub transaction: RpcTransaction,
@@ -761,7 +925,7 @@ impl Deserializer for SubmitTransactionReplacementRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SubmitTransactionReplacementResponse {
     pub transaction_id: RpcTransactionId,
@@ -794,7 +958,7 @@ impl Deserializer for SubmitTransactionReplacementResponse {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GetSubnetworkRequest {
     pub subnetwork_id: RpcSubnetworkId,
@@ -824,7 +988,7 @@ impl Deserializer for GetSubnetworkRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetSubnetworkResponse {
     pub gas_limit: u64,
@@ -854,7 +1018,7 @@ impl Deserializer for GetSubnetworkResponse {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetVirtualChainFromBlockRequest {
     pub start_hash: RpcHash,
@@ -891,7 +1055,7 @@ impl Deserializer for GetVirtualChainFromBlockRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetVirtualChainFromBlockResponse {
     pub removed_chain_block_hashes: Vec<RpcHash>,
@@ -931,7 +1095,85 @@ impl Deserializer for GetVirtualChainFromBlockResponse {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+/// GetVirtualChainChunkRequest requests a chunk of the virtual selected parent chain from `start_hash`,
+/// along with the transactions accepted by each chain block.
+///
+/// The whole chain up to the sink is followed by requesting the next chunk from the last added chain block
+/// until a chunk reaching the sink (`is_tip`) is returned.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetVirtualChainChunkRequest {
+    pub start_hash: RpcHash,
+    /// The maximum number of added chain blocks to return
+    pub max_chain_blocks: u32,
+}
+
+impl GetVirtualChainChunkRequest {
+    pub fn new(start_hash: RpcHash, max_chain_blocks: u32) -> Self {
+        Self { start_hash, max_chain_blocks }
+    }
+}
+
+impl Serializer for GetVirtualChainChunkRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(RpcHash, &self.start_hash, writer)?;
+        store!(u32, &self.max_chain_blocks, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetVirtualChainChunkRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let start_hash = load!(RpcHash, reader)?;
+        let max_chain_blocks = load!(u32, reader)?;
+
+        Ok(Self { start_hash, max_chain_blocks })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetVirtualChainChunkResponse {
+    /// The chain blocks removed since `start_hash` was on the selected chain, from the highest
+    pub removed_chain_block_hashes: Vec<RpcHash>,
+    /// The added chain blocks, from the lowest, with the transactions they accepted
+    pub added_chain_blocks: Vec<RpcAcceptingChainBlock>,
+    /// True if the last added chain block is the sink
+    pub is_tip: bool,
+}
+
+impl GetVirtualChainChunkResponse {
+    pub fn new(removed_chain_block_hashes: Vec<RpcHash>, added_chain_blocks: Vec<RpcAcceptingChainBlock>, is_tip: bool) -> Self {
+        Self { removed_chain_block_hashes, added_chain_blocks, is_tip }
+    }
+}
+
+impl Serializer for GetVirtualChainChunkResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(Vec<RpcHash>, &self.removed_chain_block_hashes, writer)?;
+        serialize!(Vec<RpcAcceptingChainBlock>, &self.added_chain_blocks, writer)?;
+        store!(bool, &self.is_tip, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetVirtualChainChunkResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let removed_chain_block_hashes = load!(Vec<RpcHash>, reader)?;
+        let added_chain_blocks = deserialize!(Vec<RpcAcceptingChainBlock>, reader)?;
+        let is_tip = load!(bool, reader)?;
+
+        Ok(Self { removed_chain_block_hashes, added_chain_blocks, is_tip })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetBlocksRequest {
     pub low_hash: Option<RpcHash>,
@@ -967,7 +1209,7 @@ impl Deserializer for GetBlocksRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetBlocksResponse {
     pub block_hashes: Vec<RpcHash>,
@@ -1000,7 +1242,7 @@ impl Deserializer for GetBlocksResponse {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetBlockCountRequest {}
 
@@ -1020,7 +1262,7 @@ impl Deserializer for GetBlockCountRequest {
 
 pub type GetBlockCountResponse = BlockCount;
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetBlockDagInfoRequest {}
 
@@ -1038,7 +1280,7 @@ impl Deserializer for GetBlockDagInfoRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetBlockDagInfoResponse {
     pub network: RpcNetworkId,
@@ -1128,7 +1370,7 @@ impl Deserializer for GetBlockDagInfoResponse {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ResolveFinalityConflictRequest {
     pub finality_block_hash: RpcHash,
@@ -1158,7 +1400,7 @@ impl Deserializer for ResolveFinalityConflictRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ResolveFinalityConflictResponse {}
 
@@ -1176,7 +1418,7 @@ impl Deserializer for ResolveFinalityConflictResponse {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ShutdownRequest {}
 
@@ -1194,7 +1436,7 @@ impl Deserializer for ShutdownRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ShutdownResponse {}
 
@@ -1212,7 +1454,7 @@ impl Deserializer for ShutdownResponse {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetHeadersRequest {
     pub start_hash: RpcHash,
@@ -1248,7 +1490,7 @@ impl Deserializer for GetHeadersRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetHeadersResponse {
     pub headers: Vec<RpcHeader>,
@@ -1278,7 +1520,7 @@ impl Deserializer for GetHeadersResponse {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetBalanceByAddressRequest {
     pub address: RpcAddress,
@@ -1308,7 +1550,7 @@ impl Deserializer for GetBalanceByAddressRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetBalanceByAddressResponse {
     pub balance: u64,
@@ -1338,7 +1580,7 @@ impl Deserializer for GetBalanceByAddressResponse {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetBalancesByAddressesRequest {
     pub addresses: Vec<RpcAddress>,
@@ -1368,7 +1610,7 @@ impl Deserializer for GetBalancesByAddressesRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetBalancesByAddressesResponse {
     pub entries: Vec<RpcBalancesByAddressesEntry>,
@@ -1398,7 +1640,7 @@ impl Deserializer for GetBalancesByAddressesResponse {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetSinkBlueScoreRequest {}
 
@@ -1416,7 +1658,7 @@ impl Deserializer for GetSinkBlueScoreRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetSinkBlueScoreResponse {
     pub blue_score: u64,
@@ -1446,7 +1688,7 @@ impl Deserializer for GetSinkBlueScoreResponse {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetUtxosByAddressesRequest {
     pub addresses: Vec<RpcAddress>,
@@ -1476,7 +1718,7 @@ impl Deserializer for GetUtxosByAddressesRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetUtxosByAddressesResponse {
     pub entries: Vec<RpcUtxosByAddressesEntry>,
@@ -1506,7 +1748,7 @@ impl Deserializer for GetUtxosByAddressesResponse {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct BanRequest {
     pub ip: RpcIpAddress,
@@ -1536,7 +1778,7 @@ impl Deserializer for BanRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct BanResponse {}
 
@@ -1554,7 +1796,7 @@ impl Deserializer for BanResponse {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct UnbanRequest {
     pub ip: RpcIpAddress,
@@ -1584,7 +1826,7 @@ impl Deserializer for UnbanRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct UnbanResponse {}
 
@@ -1602,7 +1844,7 @@ impl Deserializer for UnbanResponse {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct EstimateNetworkHashesPerSecondRequest {
     pub window_size: u32,
@@ -1635,7 +1877,7 @@ impl Deserializer for EstimateNetworkHashesPerSecondRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct EstimateNetworkHashesPerSecondResponse {
     pub network_hashes_per_second: u64,
@@ -1665,7 +1907,7 @@ impl Deserializer for EstimateNetworkHashesPerSecondResponse {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetMempoolEntriesByAddressesRequest {
     pub addresses: Vec<RpcAddress>,
@@ -1702,7 +1944,7 @@ impl Deserializer for GetMempoolEntriesByAddressesRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetMempoolEntriesByAddressesResponse {
     pub entries: Vec<RpcMempoolEntryByAddress>,
@@ -1732,7 +1974,7 @@ impl Deserializer for GetMempoolEntriesByAddressesResponse {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetCoinSupplyRequest {}
 
@@ -1750,7 +1992,7 @@ impl Deserializer for GetCoinSupplyRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetCoinSupplyResponse {
     pub max_veni: u64,
@@ -1783,7 +2025,7 @@ impl Deserializer for GetCoinSupplyResponse {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct PingRequest {}
 
@@ -1799,7 +2041,7 @@ impl Deserializer for PingRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct PingResponse {}
 
@@ -1817,14 +2059,14 @@ impl Deserializer for PingResponse {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ConnectionsProfileData {
     pub cpu_usage: f32,
     pub memory_usage: u64,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetConnectionsRequest {
     pub include_profile_data: bool,
@@ -1846,7 +2088,7 @@ impl Deserializer for GetConnectionsRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetConnectionsResponse {
     pub clients: u32,
@@ -1874,7 +2116,7 @@ impl Deserializer for GetConnectionsResponse {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetSystemInfoRequest {}
 
@@ -1894,7 +2136,7 @@ impl Deserializer for GetSystemInfoRequest {
     }
 }
 
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetSystemInfoResponse {
     pub version: String,
@@ -1951,7 +2193,7 @@ impl Deserializer for GetSystemInfoResponse {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetMetricsRequest {
     pub process_metrics: bool,
@@ -1990,7 +2232,7 @@ impl Deserializer for GetMetricsRequest {
     }
 }
 
-#[derive(Default, Clone, Debug, Serialize, Deserialize)]
+#[derive(Default, Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ProcessMetrics {
     pub resident_set_size: u64,
@@ -2048,7 +2290,7 @@ impl Deserializer for ProcessMetrics {
     }
 }
 
-#[derive(Default, Clone, Debug, Serialize, Deserialize)]
+#[derive(Default, Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ConnectionMetrics {
     pub borsh_live_connections: u32,
@@ -2059,11 +2301,14 @@ pub struct ConnectionMetrics {
     pub json_handshake_failures: u64,
 
     pub active_peers: u32,
+
+    pub connection_rate_limited_requests: u64,
+    pub ip_rate_limited_requests: u64,
 }
 
 impl Serializer for ConnectionMetrics {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(u32, &self.borsh_live_connections, writer)?;
         store!(u64, &self.borsh_connection_attempts, writer)?;
         store!(u64, &self.borsh_handshake_failures, writer)?;
@@ -2071,6 +2316,8 @@ impl Serializer for ConnectionMetrics {
         store!(u64, &self.json_connection_attempts, writer)?;
         store!(u64, &self.json_handshake_failures, writer)?;
         store!(u32, &self.active_peers, writer)?;
+        store!(u64, &self.connection_rate_limited_requests, writer)?;
+        store!(u64, &self.ip_rate_limited_requests, writer)?;
 
         Ok(())
     }
@@ -2078,7 +2325,7 @@ impl Serializer for ConnectionMetrics {
 
 impl Deserializer for ConnectionMetrics {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let version = load!(u16, reader)?;
         let borsh_live_connections = load!(u32, reader)?;
         let borsh_connection_attempts = load!(u64, reader)?;
         let borsh_handshake_failures = load!(u64, reader)?;
@@ -2086,6 +2333,8 @@ impl Deserializer for ConnectionMetrics {
         let json_connection_attempts = load!(u64, reader)?;
         let json_handshake_failures = load!(u64, reader)?;
         let active_peers = load!(u32, reader)?;
+        let (connection_rate_limited_requests, ip_rate_limited_requests) =
+            if version > 1 { (load!(u64, reader)?, load!(u64, reader)?) } else { (0, 0) };
 
         Ok(Self {
             borsh_live_connections,
@@ -2095,11 +2344,13 @@ impl Deserializer for ConnectionMetrics {
             json_connection_attempts,
             json_handshake_failures,
             active_peers,
+            connection_rate_limited_requests,
+            ip_rate_limited_requests,
         })
     }
 }
 
-#[derive(Default, Clone, Debug, Serialize, Deserialize)]
+#[derive(Default, Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct BandwidthMetrics {
     pub borsh_bytes_tx: u64,
@@ -2153,7 +2404,7 @@ impl Deserializer for BandwidthMetrics {
     }
 }
 
-#[derive(Default, Clone, Debug, Serialize, Deserialize)]
+#[derive(Default, Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ConsensusMetrics {
     pub node_blocks_submitted_count: u64,
@@ -2237,7 +2488,7 @@ impl Deserializer for ConsensusMetrics {
     }
 }
 
-#[derive(Default, Clone, Debug, Serialize, Deserialize)]
+#[derive(Default, Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct StorageMetrics {
     pub storage_size_bytes: u64,
@@ -2262,7 +2513,7 @@ impl Deserializer for StorageMetrics {
 }
 
 // TODO: Custom metrics dictionary
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub enum CustomMetricValue {
     Placeholder,
 }
@@ -2283,7 +2534,7 @@ impl Deserializer for CustomMetricValue {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetMetricsResponse {
     pub server_time: u64,
@@ -2356,7 +2607,7 @@ impl Deserializer for GetMetricsResponse {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
 #[borsh(use_discriminant = true)]
 pub enum RpcCaps {
@@ -2369,7 +2620,7 @@ pub enum RpcCaps {
     Mining,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetServerInfoRequest {}
 
@@ -2387,7 +2638,7 @@ impl Deserializer for GetServerInfoRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetServerInfoResponse {
     pub rpc_api_version: u16,
@@ -2433,7 +2684,7 @@ impl Deserializer for GetServerInfoResponse {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetSyncStatusRequest {}
 
@@ -2451,7 +2702,7 @@ impl Deserializer for GetSyncStatusRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetSyncStatusResponse {
     pub is_synced: bool,
@@ -2473,7 +2724,7 @@ impl Deserializer for GetSyncStatusResponse {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetDaaScoreTimestampEstimateRequest {
     pub daa_scores: Vec<u64>,
@@ -2501,7 +2752,7 @@ impl Deserializer for GetDaaScoreTimestampEstimateRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetDaaScoreTimestampEstimateResponse {
     pub timestamps: Vec<u64>,
@@ -2532,7 +2783,7 @@ impl Deserializer for GetDaaScoreTimestampEstimateResponse {
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // Fee rate estimations
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetFeeEstimateRequest {}
 
@@ -2550,7 +2801,7 @@ impl Deserializer for GetFeeEstimateRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetFeeEstimateResponse {
     pub estimate: RpcFeeEstimate,
@@ -2572,7 +2823,7 @@ impl Deserializer for GetFeeEstimateResponse {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetFeeEstimateExperimentalRequest {
     pub verbose: bool,
@@ -2594,7 +2845,7 @@ impl Deserializer for GetFeeEstimateExperimentalRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetFeeEstimateExperimentalResponse {
     /// The usual feerate estimate response
@@ -2622,7 +2873,71 @@ impl Deserializer for GetFeeEstimateExperimentalResponse {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+/// GetFeeEstimateHistoryRequest requests the current fee estimate, calibrated against the recently mined blocks,
+/// along with the feerate records of these blocks.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetFeeEstimateHistoryRequest {
+    /// The maximum number of block feerate records to return
+    pub max_blocks: u32,
+}
+
+impl GetFeeEstimateHistoryRequest {
+    pub fn new(max_blocks: u32) -> Self {
+        Self { max_blocks }
+    }
+}
+
+impl Serializer for GetFeeEstimateHistoryRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(u32, &self.max_blocks, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for GetFeeEstimateHistoryRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let max_blocks = load!(u32, reader)?;
+        Ok(Self { max_blocks })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetFeeEstimateHistoryResponse {
+    /// The current fee estimate, with the confidence of each bucket
+    pub estimate: RpcFeeEstimate,
+    /// The feerate records of the most recently mined blocks, from the oldest
+    pub blocks: Vec<RpcBlockFeerateRecord>,
+}
+
+impl GetFeeEstimateHistoryResponse {
+    pub fn new(estimate: RpcFeeEstimate, blocks: Vec<RpcBlockFeerateRecord>) -> Self {
+        Self { estimate, blocks }
+    }
+}
+
+impl Serializer for GetFeeEstimateHistoryResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(RpcFeeEstimate, &self.estimate, writer)?;
+        serialize!(Vec<RpcBlockFeerateRecord>, &self.blocks, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for GetFeeEstimateHistoryResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let estimate = deserialize!(RpcFeeEstimate, reader)?;
+        let blocks = deserialize!(Vec<RpcBlockFeerateRecord>, reader)?;
+        Ok(Self { estimate, blocks })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetCurrentBlockColorRequest {
     pub hash: RpcHash,
@@ -2646,7 +2961,7 @@ impl Deserializer for GetCurrentBlockColorRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetCurrentBlockColorResponse {
     pub blue: bool,
@@ -2670,7 +2985,7 @@ impl Deserializer for GetCurrentBlockColorResponse {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetUtxoReturnAddressRequest {
     pub txid: RpcHash,
@@ -2703,7 +3018,7 @@ impl Deserializer for GetUtxoReturnAddressRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetUtxoReturnAddressResponse {
     pub return_address: RpcAddress,
@@ -2733,6 +3048,293 @@ impl Deserializer for GetUtxoReturnAddressResponse {
     }
 }
 
+/// GetTransactionRequest looks up an accepted transaction by its id.
+///
+/// Requires the node to run with the transaction index enabled (`--txindex`).
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionRequest {
+    pub transaction_id: RpcTransactionId,
+}
+
+impl GetTransactionRequest {
+    pub fn new(transaction_id: RpcTransactionId) -> Self {
+        Self { transaction_id }
+    }
+}
+
+impl Serializer for GetTransactionRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(RpcTransactionId, &self.transaction_id, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetTransactionRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction_id = load!(RpcTransactionId, reader)?;
+
+        Ok(Self { transaction_id })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionResponse {
+    /// The transaction, including its verbose data
+    pub transaction: RpcTransaction,
+    /// The hash of the block containing the transaction
+    pub block_hash: RpcHash,
+    /// The hash of the selected chain block which accepted the transaction
+    pub accepting_block_hash: RpcHash,
+    /// The DAA score of the accepting block
+    pub accepting_block_daa_score: u64,
+}
+
+impl GetTransactionResponse {
+    pub fn new(
+        transaction: RpcTransaction,
+        block_hash: RpcHash,
+        accepting_block_hash: RpcHash,
+        accepting_block_daa_score: u64,
+    ) -> Self {
+        Self { transaction, block_hash, accepting_block_hash, accepting_block_daa_score }
+    }
+}
+
+impl Serializer for GetTransactionResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(RpcTransaction, &self.transaction, writer)?;
+        store!(RpcHash, &self.block_hash, writer)?;
+        store!(RpcHash, &self.accepting_block_hash, writer)?;
+        store!(u64, &self.accepting_block_daa_score, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetTransactionResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction = deserialize!(RpcTransaction, reader)?;
+        let block_hash = load!(RpcHash, reader)?;
+        let accepting_block_hash = load!(RpcHash, reader)?;
+        let accepting_block_daa_score = load!(u64, reader)?;
+
+        Ok(Self { transaction, block_hash, accepting_block_hash, accepting_block_daa_score })
+    }
+}
+
+/// GetAddressHistoryRequest retrieves a page of the received and spent events of an address,
+/// ordered by accepting DAA score.
+///
+/// Requires the node to run with the address history index enabled (`--addressindex`).
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAddressHistoryRequest {
+    pub address: RpcAddress,
+    /// The `next_cursor` of a previous response, or `None` to start from the oldest indexed event
+    pub cursor: Option<String>,
+    /// The maximum number of entries to return
+    pub limit: u32,
+}
+
+impl GetAddressHistoryRequest {
+    pub fn new(address: RpcAddress, cursor: Option<String>, limit: u32) -> Self {
+        Self { address, cursor, limit }
+    }
+}
+
+impl Serializer for GetAddressHistoryRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(RpcAddress, &self.address, writer)?;
+        store!(Option<String>, &self.cursor, writer)?;
+        store!(u32, &self.limit, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetAddressHistoryRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let address = load!(RpcAddress, reader)?;
+        let cursor = load!(Option<String>, reader)?;
+        let limit = load!(u32, reader)?;
+
+        Ok(Self { address, cursor, limit })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAddressHistoryResponse {
+    pub entries: Vec<RpcAddressHistoryEntry>,
+    /// The cursor to pass in order to fetch the next page, `None` if this is the last page
+    pub next_cursor: Option<String>,
+}
+
+impl GetAddressHistoryResponse {
+    pub fn new(entries: Vec<RpcAddressHistoryEntry>, next_cursor: Option<String>) -> Self {
+        Self { entries, next_cursor }
+    }
+}
+
+impl Serializer for GetAddressHistoryResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(Vec<RpcAddressHistoryEntry>, &self.entries, writer)?;
+        store!(Option<String>, &self.next_cursor, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetAddressHistoryResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let entries = deserialize!(Vec<RpcAddressHistoryEntry>, reader)?;
+        let next_cursor = load!(Option<String>, reader)?;
+
+        Ok(Self { entries, next_cursor })
+    }
+}
+
+/// SubmitPackageRequest submits a package of related transactions to the mempool, all of them being
+/// accepted or none.
+///
+/// The transactions of a package pay the minimum relay fee as a whole, so a child transaction can pay
+/// for parents which would be rejected on their own (child-pays-for-parent). The package is not relayed
+/// as such, its transactions being announced to peers individually.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmitPackageRequest {
+    pub transactions: Vec<RpcTransaction>,
+}
+
+impl SubmitPackageRequest {
+    pub fn new(transactions: Vec<RpcTransaction>) -> Self {
+        Self { transactions }
+    }
+}
+
+impl Serializer for SubmitPackageRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(Vec<RpcTransaction>, &self.transactions, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for SubmitPackageRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transactions = deserialize!(Vec<RpcTransaction>, reader)?;
+
+        Ok(Self { transactions })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmitPackageResponse {
+    /// The ids of the package transactions, in topological order
+    pub transaction_ids: Vec<RpcTransactionId>,
+}
+
+impl SubmitPackageResponse {
+    pub fn new(transaction_ids: Vec<RpcTransactionId>) -> Self {
+        Self { transaction_ids }
+    }
+}
+
+impl Serializer for SubmitPackageResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(Vec<RpcTransactionId>, &self.transaction_ids, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for SubmitPackageResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction_ids = load!(Vec<RpcTransactionId>, reader)?;
+
+        Ok(Self { transaction_ids })
+    }
+}
+
+/// GetTransactionStatusRequest looks up what happened to a transaction since its submission.
+///
+/// The outcomes of the transactions leaving the mempool are only kept for a bounded number of recent transactions.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionStatusRequest {
+    pub transaction_id: RpcTransactionId,
+}
+
+impl GetTransactionStatusRequest {
+    pub fn new(transaction_id: RpcTransactionId) -> Self {
+        Self { transaction_id }
+    }
+}
+
+impl Serializer for GetTransactionStatusRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(RpcTransactionId, &self.transaction_id, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetTransactionStatusRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction_id = load!(RpcTransactionId, reader)?;
+
+        Ok(Self { transaction_id })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionStatusResponse {
+    pub status: RpcTransactionStatus,
+}
+
+impl GetTransactionStatusResponse {
+    pub fn new(status: RpcTransactionStatus) -> Self {
+        Self { status }
+    }
+}
+
+impl Serializer for GetTransactionStatusResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(RpcTransactionStatus, &self.status, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetTransactionStatusResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let status = load!(RpcTransactionStatus, reader)?;
+
+        Ok(Self { status })
+    }
+}
+
 // ----------------------------------------------------------------------------
 // Subscriptions & notifications
 // ----------------------------------------------------------------------------
@@ -2743,34 +3345,48 @@ impl Deserializer for GetUtxoReturnAddressResponse {
 /// NotifyBlockAddedRequest registers this connection for blockAdded notifications.
 ///
 /// See: BlockAddedNotification
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct NotifyBlockAddedRequest {
+    /// Restricts the notifications to the blocks holding a matching transaction, reduced to the matching transactions
+    #[serde(default)]
+    pub filter: RpcTransactionFilter,
+    /// Strips the transactions from the notified blocks
+    #[serde(default)]
+    pub header_only: bool,
     pub command: Command,
 }
 impl NotifyBlockAddedRequest {
     pub fn new(command: Command) -> Self {
-        Self { command }
+        Self { filter: Default::default(), header_only: false, command }
+    }
+
+    pub fn with_filter(filter: RpcTransactionFilter, header_only: bool, command: Command) -> Self {
+        Self { filter, header_only, command }
     }
 }
 
 impl Serializer for NotifyBlockAddedRequest {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(Command, &self.command, writer)?;
+        serialize!(RpcTransactionFilter, &self.filter, writer)?;
+        store!(bool, &self.header_only, writer)?;
         Ok(())
     }
 }
 
 impl Deserializer for NotifyBlockAddedRequest {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let version = load!(u16, reader)?;
         let command = load!(Command, reader)?;
-        Ok(Self { command })
+        let (filter, header_only) =
+            if version > 1 { (deserialize!(RpcTransactionFilter, reader)?, load!(bool, reader)?) } else { Default::default() };
+        Ok(Self { filter, header_only, command })
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct NotifyBlockAddedResponse {}
 
@@ -2792,12 +3408,57 @@ impl Deserializer for NotifyBlockAddedResponse {
 /// into the DAG.
 ///
 /// See: NotifyBlockAddedRequest
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct BlockAddedNotification {
     pub block: Arc<RpcBlock>,
 }
 
+impl BlockAddedNotification {
+    pub(crate) fn apply_block_added_subscription(&self, subscription: &BlockAddedSubscription) -> Option<Self> {
+        let filter = subscription.filter();
+        if filter.is_empty() && !subscription.header_only() {
+            return Some(self.clone());
+        }
+
+        let mut block =
+            RpcBlock { header: self.block.header.clone(), transactions: vec![], verbose_data: self.block.verbose_data.clone() };
+        let mut transaction_ids = vec![];
+        if filter.is_empty() {
+            transaction_ids = block.verbose_data.as_ref().map(|x| x.transaction_ids.clone()).unwrap_or_default();
+        } else {
+            let mut matched = false;
+            for (i, transaction) in self.block.transactions.iter().enumerate() {
+                let transaction_id = || {
+                    transaction
+                        .verbose_data
+                        .as_ref()
+                        .map(|x| x.transaction_id)
+                        .or_else(|| self.block.verbose_data.as_ref().and_then(|x| x.transaction_ids.get(i).copied()))
+                        .or_else(|| Transaction::try_from(transaction.clone()).ok().map(|x| x.id()))
+                };
+                let script_public_keys = transaction.outputs.iter().map(|x| &x.script_public_key);
+                if filter.matches(transaction_id, script_public_keys, &transaction.payload) {
+                    matched = true;
+                    if let Some(id) = self.block.verbose_data.as_ref().and_then(|x| x.transaction_ids.get(i)) {
+                        transaction_ids.push(*id);
+                    }
+                    if !subscription.header_only() {
+                        block.transactions.push(transaction.clone());
+                    }
+                }
+            }
+            if !matched {
+                return None;
+            }
+        }
+        if let Some(ref mut verbose_data) = block.verbose_data {
+            verbose_data.transaction_ids = transaction_ids;
+        }
+        Some(Self { block: Arc::new(block) })
+    }
+}
+
 impl Serializer for BlockAddedNotification {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
         store!(u16, &1, writer)?;
@@ -2821,38 +3482,61 @@ impl Deserializer for BlockAddedNotification {
 // virtualDaaScoreChanged notifications.
 //
 // See: VirtualChainChangedNotification
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct NotifyVirtualChainChangedRequest {
     pub include_accepted_transaction_ids: bool,
+    /// Restricts the reported accepted transactions to this set, all of them if empty
+    #[serde(default)]
+    pub transaction_ids: Vec<RpcTransactionId>,
+    /// Replays the chain changes missed since this position before the live notifications.
+    /// If they cannot be replayed, the call fails while the subscription still goes on.
+    #[serde(default)]
+    pub resume_from: Option<RpcResumeToken>,
     pub command: Command,
 }
 
 impl NotifyVirtualChainChangedRequest {
     pub fn new(include_accepted_transaction_ids: bool, command: Command) -> Self {
-        Self { include_accepted_transaction_ids, command }
+        Self { include_accepted_transaction_ids, transaction_ids: vec![], resume_from: None, command }
+    }
+
+    pub fn with_transaction_ids(
+        include_accepted_transaction_ids: bool,
+        transaction_ids: Vec<RpcTransactionId>,
+        command: Command,
+    ) -> Self {
+        Self { include_accepted_transaction_ids, transaction_ids, resume_from: None, command }
+    }
+
+    pub fn with_resume_from(self, resume_from: Option<RpcResumeToken>) -> Self {
+        Self { resume_from, ..self }
     }
 }
 
 impl Serializer for NotifyVirtualChainChangedRequest {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &3, writer)?;
         store!(bool, &self.include_accepted_transaction_ids, writer)?;
         store!(Command, &self.command, writer)?;
+        store!(Vec<RpcTransactionId>, &self.transaction_ids, writer)?;
+        store!(Option<RpcResumeToken>, &self.resume_from, writer)?;
         Ok(())
     }
 }
 
 impl Deserializer for NotifyVirtualChainChangedRequest {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let version = load!(u16, reader)?;
         let include_accepted_transaction_ids = load!(bool, reader)?;
         let command = load!(Command, reader)?;
-        Ok(Self { include_accepted_transaction_ids, command })
+        let transaction_ids = if version > 1 { load!(Vec<RpcTransactionId>, reader)? } else { vec![] };
+        let resume_from = if version > 2 { load!(Option<RpcResumeToken>, reader)? } else { None };
+        Ok(Self { include_accepted_transaction_ids, transaction_ids, resume_from, command })
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct NotifyVirtualChainChangedResponse {}
 
@@ -2874,7 +3558,7 @@ impl Deserializer for NotifyVirtualChainChangedResponse {
 // chain had changed.
 //
 // See: NotifyVirtualChainChangedRequest
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct VirtualChainChangedNotification {
     pub removed_chain_block_hashes: Arc<Vec<RpcHash>>,
@@ -2882,6 +3566,34 @@ pub struct VirtualChainChangedNotification {
     pub accepted_transaction_ids: Arc<Vec<RpcAcceptedTransactionIds>>,
 }
 
+impl VirtualChainChangedNotification {
+    pub(crate) fn apply_virtual_chain_changed_subscription(&self, subscription: &VirtualChainChangedSubscription) -> Self {
+        let accepted_transaction_ids = if !subscription.include_accepted_transaction_ids() {
+            vec![]
+        } else if subscription.filter().is_empty() {
+            return self.clone();
+        } else {
+            let filter = subscription.filter();
+            self.accepted_transaction_ids
+                .iter()
+                .filter_map(|x| {
+                    let accepted_transaction_ids: Vec<_> =
+                        x.accepted_transaction_ids.iter().filter(|id| filter.matches_transaction_id(id)).copied().collect();
+                    (!accepted_transaction_ids.is_empty()).then_some(RpcAcceptedTransactionIds {
+                        accepting_block_hash: x.accepting_block_hash,
+                        accepted_transaction_ids,
+                    })
+                })
+                .collect()
+        };
+        Self {
+            removed_chain_block_hashes: self.removed_chain_block_hashes.clone(),
+            added_chain_block_hashes: self.added_chain_block_hashes.clone(),
+            accepted_transaction_ids: Arc::new(accepted_transaction_ids),
+        }
+    }
+}
+
 impl Serializer for VirtualChainChangedNotification {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
         store!(u16, &1, writer)?;
@@ -2909,7 +3621,7 @@ impl Deserializer for VirtualChainChangedNotification {
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // FinalityConflictNotification
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct NotifyFinalityConflictRequest {
     pub command: Command,
@@ -2937,7 +3649,7 @@ impl Deserializer for NotifyFinalityConflictRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct NotifyFinalityConflictResponse {}
 
@@ -2955,7 +3667,7 @@ impl Deserializer for NotifyFinalityConflictResponse {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct FinalityConflictNotification {
     pub violating_block_hash: RpcHash,
@@ -2980,7 +3692,7 @@ impl Deserializer for FinalityConflictNotification {
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // FinalityConflictResolvedNotification
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct NotifyFinalityConflictResolvedRequest {
     pub command: Command,
@@ -3008,7 +3720,7 @@ impl Deserializer for NotifyFinalityConflictResolvedRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct NotifyFinalityConflictResolvedResponse {}
 
@@ -3026,7 +3738,7 @@ impl Deserializer for NotifyFinalityConflictResolvedResponse {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct FinalityConflictResolvedNotification {
     pub finality_block_hash: RpcHash,
@@ -3060,38 +3772,48 @@ impl Deserializer for FinalityConflictResolvedNotification {
 // This call is only available when this vecnod was started with `--utxoindex`
 //
 // See: UtxosChangedNotification
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct NotifyUtxosChangedRequest {
     pub addresses: Vec<RpcAddress>,
+    /// Replays the UTXO changes of `addresses` missed since this position before the live notifications.
+    /// If they cannot be replayed, the call fails while the subscription still goes on.
+    #[serde(default)]
+    pub resume_from: Option<RpcResumeToken>,
     pub command: Command,
 }
 
 impl NotifyUtxosChangedRequest {
     pub fn new(addresses: Vec<RpcAddress>, command: Command) -> Self {
-        Self { addresses, command }
+        Self { addresses, resume_from: None, command }
+    }
+
+    pub fn with_resume_from(self, resume_from: Option<RpcResumeToken>) -> Self {
+        Self { resume_from, ..self }
     }
 }
 
 impl Serializer for NotifyUtxosChangedRequest {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(Vec<RpcAddress>, &self.addresses, writer)?;
         store!(Command, &self.command, writer)?;
+        store!(Option<RpcResumeToken>, &self.resume_from, writer)?;
         Ok(())
     }
 }
 
 impl Deserializer for NotifyUtxosChangedRequest {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let version = load!(u16, reader)?;
         let addresses = load!(Vec<RpcAddress>, reader)?;
         let command = load!(Command, reader)?;
-        Ok(Self { addresses, command })
+        let resume_from = if version > 1 { load!(Option<RpcResumeToken>, reader)? } else { None };
+        Ok(Self { addresses, resume_from, command })
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct NotifyUtxosChangedResponse {}
 
@@ -3112,7 +3834,7 @@ impl Deserializer for NotifyUtxosChangedResponse {
 // UtxosChangedNotificationMessage is sent whenever the UTXO index had been updated.
 //
 // See: NotifyUtxosChangedRequest
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct UtxosChangedNotification {
     pub added: Arc<Vec<RpcUtxosByAddressesEntry>>,
@@ -3174,7 +3896,7 @@ impl Deserializer for UtxosChangedNotification {
 // sinkBlueScoreChanged notifications.
 //
 // See: SinkBlueScoreChangedNotification
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct NotifySinkBlueScoreChangedRequest {
     pub command: Command,
@@ -3202,7 +3924,7 @@ impl Deserializer for NotifySinkBlueScoreChangedRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct NotifySinkBlueScoreChangedResponse {}
 
@@ -3224,7 +3946,7 @@ impl Deserializer for NotifySinkBlueScoreChangedResponse {
 // of the virtual's selected parent changes.
 //
 /// See: NotifySinkBlueScoreChangedRequest
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SinkBlueScoreChangedNotification {
     pub sink_blue_score: u64,
@@ -3253,7 +3975,7 @@ impl Deserializer for SinkBlueScoreChangedNotification {
 // virtualDaaScoreChanged notifications.
 //
 // See: VirtualDaaScoreChangedNotification
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct NotifyVirtualDaaScoreChangedRequest {
     pub command: Command,
@@ -3281,7 +4003,7 @@ impl Deserializer for NotifyVirtualDaaScoreChangedRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct NotifyVirtualDaaScoreChangedResponse {}
 
@@ -3303,7 +4025,7 @@ impl Deserializer for NotifyVirtualDaaScoreChangedResponse {
 // of the virtual changes.
 //
 // See NotifyVirtualDaaScoreChangedRequest
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct VirtualDaaScoreChangedNotification {
     pub virtual_daa_score: u64,
@@ -3328,7 +4050,7 @@ impl Deserializer for VirtualDaaScoreChangedNotification {
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // PruningPointUtxoSetOverrideNotification
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct NotifyPruningPointUtxoSetOverrideRequest {
     pub command: Command,
@@ -3356,7 +4078,7 @@ impl Deserializer for NotifyPruningPointUtxoSetOverrideRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct NotifyPruningPointUtxoSetOverrideResponse {}
 
@@ -3374,7 +4096,7 @@ impl Deserializer for NotifyPruningPointUtxoSetOverrideResponse {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct PruningPointUtxoSetOverrideNotification {}
 
@@ -3398,7 +4120,7 @@ impl Deserializer for PruningPointUtxoSetOverrideNotification {
 /// NotifyNewBlockTemplateRequest registers this connection for blockAdded notifications.
 ///
 /// See: NewBlockTemplateNotification
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct NotifyNewBlockTemplateRequest {
     pub command: Command,
@@ -3425,7 +4147,7 @@ impl Deserializer for NotifyNewBlockTemplateRequest {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct NotifyNewBlockTemplateResponse {}
 
@@ -3447,7 +4169,7 @@ impl Deserializer for NotifyNewBlockTemplateResponse {
 /// into the DAG.
 ///
 /// See: NotifyNewBlockTemplateRequest
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct NewBlockTemplateNotification {}
 
@@ -3465,10 +4187,103 @@ impl Deserializer for NewBlockTemplateNotification {
     }
 }
 
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TransactionStatusChangedNotification
+
+/// NotifyTransactionStatusChangedRequest registers this connection for transactionStatusChanged notifications.
+///
+/// See: TransactionStatusChangedNotification
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyTransactionStatusChangedRequest {
+    /// Restricts the notifications to these transactions, all transactions being notified if empty
+    #[serde(default)]
+    pub transaction_ids: Vec<RpcTransactionId>,
+    pub command: Command,
+}
+
+impl NotifyTransactionStatusChangedRequest {
+    pub fn new(transaction_ids: Vec<RpcTransactionId>, command: Command) -> Self {
+        Self { transaction_ids, command }
+    }
+}
+
+impl Serializer for NotifyTransactionStatusChangedRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(Vec<RpcTransactionId>, &self.transaction_ids, writer)?;
+        store!(Command, &self.command, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for NotifyTransactionStatusChangedRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction_ids = load!(Vec<RpcTransactionId>, reader)?;
+        let command = load!(Command, reader)?;
+        Ok(Self { transaction_ids, command })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyTransactionStatusChangedResponse {}
+
+impl Serializer for NotifyTransactionStatusChangedResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for NotifyTransactionStatusChangedResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        Ok(Self {})
+    }
+}
+
+/// TransactionStatusChangedNotification is sent whenever a transaction enters, moves within
+/// or leaves the mempool.
+///
+/// See: NotifyTransactionStatusChangedRequest
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionStatusChangedNotification {
+    pub status: RpcTransactionStatus,
+}
+
+impl TransactionStatusChangedNotification {
+    pub(crate) fn apply_transaction_status_changed_subscription(
+        &self,
+        subscription: &TransactionStatusChangedSubscription,
+    ) -> Option<Self> {
+        let filter = subscription.filter();
+        (filter.is_empty() || filter.matches_transaction_id(&self.status.transaction_id)).then(|| self.clone())
+    }
+}
+
+impl Serializer for TransactionStatusChangedNotification {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(RpcTransactionStatus, &self.status, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for TransactionStatusChangedNotification {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let status = load!(RpcTransactionStatus, reader)?;
+        Ok(Self { status })
+    }
+}
+
 ///
 ///  wRPC response for RpcApiOps::Subscribe request
 ///
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SubscribeResponse {
     id: u64,
@@ -3499,7 +4314,7 @@ impl Deserializer for SubscribeResponse {
 ///
 ///  wRPC response for RpcApiOps::Unsubscribe request
 ///
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct UnsubscribeResponse {}
 
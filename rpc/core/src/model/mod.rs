@@ -12,6 +12,7 @@ pub mod mempool;
 pub mod message;
 pub mod network;
 pub mod peer;
+pub mod schema;
 pub mod script_class;
 pub mod subnets;
 mod tests;
@@ -1,5 +1,6 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use vecno_utils::networking::{ContextualNetAddress, IpAddress, NetAddress, PeerId};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 pub type RpcNodeId = PeerId;
@@ -7,7 +8,7 @@ pub type RpcIpAddress = IpAddress;
 pub type RpcPeerAddress = NetAddress;
 pub type RpcContextualPeerAddress = ContextualNetAddress;
 
-#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, BorshSerialize, BorshDeserialize)]
 pub struct RpcPeerInfo {
     pub id: RpcNodeId,
     pub address: RpcPeerAddress,
@@ -20,4 +21,7 @@ pub struct RpcPeerInfo {
     pub advertised_protocol_version: u32,
     pub time_connected: u64, // NOTE: i64 in gRPC protowire
     pub is_ibd_peer: bool,
+    #[serde(default)]
+    #[borsh(skip)]
+    pub misbehavior_score: u32,
 }
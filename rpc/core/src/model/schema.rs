@@ -0,0 +1,188 @@
+//!
+//! JSON Schema of the RPC model.
+//!
+//! The schemas are derived from the Rust types of this module, which remain the single source of truth
+//! for the shape of every request, response and notification exchanged over the JSON encodings of the RPC.
+//!
+
+use crate::api::ops::RpcApiOps;
+use crate::model::*;
+use schemars::{
+    gen::{SchemaGenerator, SchemaSettings},
+    schema::{RootSchema, Schema, SchemaObject, SubschemaValidation},
+    Map,
+};
+
+/// Schemas of the payloads of an [`RpcApiOps`] variant.
+#[derive(Clone, Debug)]
+pub enum RpcOpSchema {
+    /// A method called by the client, answered by a single response.
+    Method { request: Schema, response: Schema },
+    /// A notification pushed by the server to its subscribers.
+    Notification(Schema),
+}
+
+macro_rules! op_schema {
+    ($op: expr, $generator: expr, [$($method: ident),* $(,)?], [$($notification: ident),* $(,)?]) => {
+        paste::paste! {
+            match $op {
+                $(RpcApiOps::$method => Some(RpcOpSchema::Method {
+                    request: $generator.subschema_for::<[<$method Request>]>(),
+                    response: $generator.subschema_for::<[<$method Response>]>(),
+                }),)*
+                $(RpcApiOps::$notification => Some(RpcOpSchema::Notification($generator.subschema_for::<$notification>())),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+/// Returns the schemas of the payloads of `op`, registering the definitions of the types they reference in `generator`.
+///
+/// The returned schemas are references into these definitions. Returns `None` for the ops carrying no payload
+/// of their own (the connection lifecycle ops and the generic `Subscribe`/`Unsubscribe`).
+pub fn op_schema(op: RpcApiOps, generator: &mut SchemaGenerator) -> Option<RpcOpSchema> {
+    op_schema!(
+        op,
+        generator,
+        [
+            NotifyBlockAdded,
+            NotifyNewBlockTemplate,
+            NotifyUtxosChanged,
+            NotifyPruningPointUtxoSetOverride,
+            NotifyFinalityConflict,
+            NotifyFinalityConflictResolved,
+            NotifyVirtualDaaScoreChanged,
+            NotifyVirtualChainChanged,
+            NotifySinkBlueScoreChanged,
+            NotifyTransactionStatusChanged,
+            Ping,
+            GetMetrics,
+            GetSystemInfo,
+            GetConnections,
+            GetServerInfo,
+            GetSyncStatus,
+            GetCurrentNetwork,
+            SubmitBlock,
+            GetBlockTemplate,
+            GetPeerAddresses,
+            GetSink,
+            GetMempoolEntry,
+            GetMempoolEntries,
+            GetConnectedPeerInfo,
+            AddPeer,
+            SubmitTransaction,
+            GetBlock,
+            GetSubnetwork,
+            GetVirtualChainFromBlock,
+            GetBlocks,
+            GetBlockCount,
+            GetBlockDagInfo,
+            ResolveFinalityConflict,
+            Shutdown,
+            GetHeaders,
+            GetUtxosByAddresses,
+            GetBalanceByAddress,
+            GetBalancesByAddresses,
+            GetSinkBlueScore,
+            Ban,
+            Unban,
+            GetInfo,
+            EstimateNetworkHashesPerSecond,
+            GetMempoolEntriesByAddresses,
+            GetCoinSupply,
+            GetDaaScoreTimestampEstimate,
+            SubmitTransactionReplacement,
+            GetFeeEstimate,
+            GetFeeEstimateExperimental,
+            GetCurrentBlockColor,
+            GetUtxoReturnAddress,
+            GetTransaction,
+            GetAddressHistory,
+            SubmitPackage,
+            GetVirtualChainChunk,
+            GetFeeEstimateHistory,
+            GetTransactionStatus,
+        ],
+        [
+            BlockAddedNotification,
+            VirtualChainChangedNotification,
+            FinalityConflictNotification,
+            FinalityConflictResolvedNotification,
+            UtxosChangedNotification,
+            SinkBlueScoreChangedNotification,
+            VirtualDaaScoreChangedNotification,
+            PruningPointUtxoSetOverrideNotification,
+            NewBlockTemplateNotification,
+            TransactionStatusChangedNotification,
+        ]
+    )
+}
+
+/// Registers the schemas of every request, response and notification type of the RPC in `generator`,
+/// returning the references to them.
+fn register_all(generator: &mut SchemaGenerator) -> Vec<Schema> {
+    let mut schemas = RpcApiOps::iter()
+        .filter_map(|op| op_schema(*op, generator))
+        .flat_map(|schema| match schema {
+            RpcOpSchema::Method { request, response } => vec![request, response],
+            RpcOpSchema::Notification(notification) => vec![notification],
+        })
+        .collect::<Vec<_>>();
+    schemas.push(generator.subschema_for::<SubscribeResponse>());
+    schemas.push(generator.subschema_for::<UnsubscribeResponse>());
+    schemas
+}
+
+/// Returns the schema of every request, response and notification type of the RPC, keyed by type name,
+/// along with the schemas of all the types they reference.
+pub fn json_schema_definitions(settings: SchemaSettings) -> Map<String, Schema> {
+    let mut generator = settings.into_generator();
+    register_all(&mut generator);
+    generator.take_definitions()
+}
+
+/// Returns a JSON Schema (draft 7) document holding every type of [`json_schema_definitions`] in its `definitions`,
+/// whose root schema accepts any request, response or notification of the RPC.
+pub fn json_schema() -> RootSchema {
+    let mut generator = SchemaSettings::draft07().into_generator();
+    let any_of = register_all(&mut generator);
+    let schema = SchemaObject {
+        subschemas: Some(Box::new(SubschemaValidation { any_of: Some(any_of), ..Default::default() })),
+        ..Default::default()
+    };
+    RootSchema { meta_schema: generator.settings().meta_schema.clone(), schema, definitions: generator.take_definitions() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_op_schema_coverage() {
+        let mut generator = SchemaSettings::draft07().into_generator();
+        for op in RpcApiOps::iter() {
+            let expected = !matches!(
+                op,
+                RpcApiOps::NoOp | RpcApiOps::Connect | RpcApiOps::Disconnect | RpcApiOps::Subscribe | RpcApiOps::Unsubscribe
+            );
+            assert_eq!(op_schema(*op, &mut generator).is_some(), expected, "unexpected schema coverage of op {op:?}");
+        }
+    }
+
+    #[test]
+    fn test_schema_references() {
+        let definitions = json_schema_definitions(SchemaSettings::draft07());
+        let json = serde_json::to_string(&definitions).unwrap();
+        for reference in json.split("\"$ref\":\"#/definitions/").skip(1) {
+            let name = reference.split('"').next().unwrap();
+            assert!(definitions.contains_key(name), "unresolved schema reference {name}");
+        }
+        for name in ["GetBlockRequest", "GetBlockResponse", "BlockCount", "BlockAddedNotification", "SubscribeResponse"] {
+            assert!(definitions.contains_key(name), "missing schema of {name}");
+        }
+
+        let root = json_schema();
+        assert!(root.schema.subschemas.unwrap().any_of.unwrap().len() > 100);
+    }
+}
@@ -13,6 +13,8 @@ mod mockery {
     use vecno_rpc_macros::test_wrpc_serializer as test;
     use vecno_utils::networking::{ContextualNetAddress, IpAddress, NetAddress};
     use rand::Rng;
+    use schemars::schema::{InstanceType, RootSchema, Schema, SingleOrVec};
+    use serde_json::Value;
     use std::net::{IpAddr, Ipv4Addr};
     use std::sync::Arc;
     use uuid::Uuid;
@@ -60,13 +62,20 @@ mod mockery {
 
     // this function tests serialization and deserialization of a type
     // by serializing it (A), deserializing it, serializing it again (B)
-    // and comparing A and B buffers.
+    // and comparing A and B buffers. It also checks that the JSON encoding
+    // of the type conforms to its published JSON Schema.
     fn test<T>(kind: &str)
     where
-        T: Serializer + Deserializer + Mock,
+        T: Serializer + Deserializer + Mock + serde::Serialize + schemars::JsonSchema,
     {
         let data = T::mock();
 
+        let root = schemars::schema_for!(T);
+        let json = serde_json::to_value(&data).unwrap();
+        if let Err(err) = validate(&json, &Schema::Object(root.schema.clone()), &root, "$") {
+            panic!("JSON encoding of `{kind}` drifted from its schema: {err}\n{json}");
+        }
+
         const PREFIX: u32 = 0x12345678;
         const SUFFIX: u32 = 0x90abcdef;
 
@@ -94,6 +103,117 @@ mod mockery {
         assert!(buffer1 == buffer2, "serialization/deserialization failure while testing `{kind}`");
     }
 
+    // minimal JSON Schema validator covering the keywords emitted by schemars
+    // for the RPC model; `pattern` and `format` are not checked
+    fn validate(value: &Value, schema: &Schema, root: &RootSchema, path: &str) -> Result<(), String> {
+        let schema = match schema {
+            Schema::Bool(true) => return Ok(()),
+            Schema::Bool(false) => return Err(format!("{path}: no value is allowed")),
+            Schema::Object(schema) => schema,
+        };
+
+        if let Some(reference) = &schema.reference {
+            let name = reference.strip_prefix("#/definitions/").ok_or_else(|| format!("{path}: unsupported reference {reference}"))?;
+            let target = root.definitions.get(name).ok_or_else(|| format!("{path}: unresolved reference {reference}"))?;
+            validate(value, target, root, path)?;
+        }
+
+        if let Some(instance_type) = &schema.instance_type {
+            let matches = |instance_type: &InstanceType| match instance_type {
+                InstanceType::Null => value.is_null(),
+                InstanceType::Boolean => value.is_boolean(),
+                InstanceType::Object => value.is_object(),
+                InstanceType::Array => value.is_array(),
+                InstanceType::Number => value.is_number(),
+                InstanceType::String => value.is_string(),
+                InstanceType::Integer => value.is_i64() || value.is_u64(),
+            };
+            let valid = match instance_type {
+                SingleOrVec::Single(instance_type) => matches(instance_type),
+                SingleOrVec::Vec(instance_types) => instance_types.iter().any(matches),
+            };
+            if !valid {
+                return Err(format!("{path}: expected type {instance_type:?}, found {value}"));
+            }
+        }
+
+        if let Some(values) = &schema.enum_values {
+            if !values.contains(value) {
+                return Err(format!("{path}: {value} is not one of {values:?}"));
+            }
+        }
+        if let Some(constant) = &schema.const_value {
+            if constant != value {
+                return Err(format!("{path}: expected {constant}, found {value}"));
+            }
+        }
+
+        if let Some(subschemas) = &schema.subschemas {
+            if let Some(all_of) = &subschemas.all_of {
+                all_of.iter().try_for_each(|schema| validate(value, schema, root, path))?;
+            }
+            if let Some(any_of) = &subschemas.any_of {
+                if !any_of.iter().any(|schema| validate(value, schema, root, path).is_ok()) {
+                    return Err(format!("{path}: {value} matches none of the `anyOf` schemas"));
+                }
+            }
+            if let Some(one_of) = &subschemas.one_of {
+                let count = one_of.iter().filter(|schema| validate(value, schema, root, path).is_ok()).count();
+                if count != 1 {
+                    return Err(format!("{path}: {value} matches {count} of the `oneOf` schemas"));
+                }
+            }
+        }
+
+        if let (Some(object), Some(map)) = (&schema.object, value.as_object()) {
+            if let Some(name) = object.required.iter().find(|name| !map.contains_key(*name)) {
+                return Err(format!("{path}: missing required property `{name}`"));
+            }
+            for (name, value) in map {
+                let path = format!("{path}.{name}");
+                match (object.properties.get(name), &object.additional_properties) {
+                    (Some(schema), _) => validate(value, schema, root, &path)?,
+                    (None, Some(schema)) => validate(value, schema, root, &path)?,
+                    (None, None) => {}
+                }
+            }
+        }
+
+        if let (Some(array), Some(items)) = (&schema.array, value.as_array()) {
+            if array.min_items.is_some_and(|min| items.len() < min as usize)
+                || array.max_items.is_some_and(|max| items.len() > max as usize)
+            {
+                return Err(format!("{path}: unexpected item count {}", items.len()));
+            }
+            match &array.items {
+                Some(SingleOrVec::Single(schema)) => {
+                    items.iter().enumerate().try_for_each(|(i, item)| validate(item, schema, root, &format!("{path}[{i}]")))?
+                }
+                Some(SingleOrVec::Vec(schemas)) => items
+                    .iter()
+                    .zip(schemas.iter())
+                    .enumerate()
+                    .try_for_each(|(i, (item, schema))| validate(item, schema, root, &format!("{path}[{i}]")))?,
+                None => {}
+            }
+        }
+
+        if let (Some(number), Some(x)) = (&schema.number, value.as_f64()) {
+            if number.minimum.is_some_and(|min| x < min) || number.maximum.is_some_and(|max| x > max) {
+                return Err(format!("{path}: {value} is out of range"));
+            }
+        }
+
+        if let (Some(string), Some(s)) = (&schema.string, value.as_str()) {
+            let len = s.chars().count();
+            if string.min_length.is_some_and(|min| len < min as usize) || string.max_length.is_some_and(|max| len > max as usize) {
+                return Err(format!("{path}: unexpected length {len} of string {value}"));
+            }
+        }
+
+        Ok(())
+    }
+
     #[macro_export]
     macro_rules! impl_mock {
         ($($type:ty),*) => {
@@ -303,6 +423,7 @@ mod mockery {
                 advertised_protocol_version: mock(),
                 time_connected: mock(),
                 is_ibd_peer: mock(),
+                misbehavior_score: mock(),
             }
         }
     }
@@ -371,6 +492,8 @@ mod mockery {
                 json_connection_attempts: mock(),
                 json_handshake_failures: mock(),
                 active_peers: mock(),
+                connection_rate_limited_requests: mock(),
+                ip_rate_limited_requests: mock(),
             }
         }
     }
@@ -440,15 +563,34 @@ mod mockery {
 
     impl Mock for GetBlockTemplateRequest {
         fn mock() -> Self {
-            GetBlockTemplateRequest { pay_address: mock(), extra_data: vec![4, 2] }
+            GetBlockTemplateRequest {
+                pay_address: mock(),
+                extra_data: vec![4, 2],
+                long_poll_id: Some("00000000000000ff".to_string()),
+                long_poll_timeout: Some(mock()),
+                mandatory_transaction_ids: mock(),
+                mandatory_transactions: mock(),
+                excluded_transaction_ids: mock(),
+            }
         }
     }
 
     test!(GetBlockTemplateRequest);
 
+    impl Mock for RpcRejectedTransaction {
+        fn mock() -> Self {
+            RpcRejectedTransaction { transaction_id: mock(), reason: "not found in the mempool".to_string() }
+        }
+    }
+
     impl Mock for GetBlockTemplateResponse {
         fn mock() -> Self {
-            GetBlockTemplateResponse { block: mock(), is_synced: true }
+            GetBlockTemplateResponse {
+                block: mock(),
+                is_synced: true,
+                template_id: "00000000000000ff".to_string(),
+                rejected_transactions: mock(),
+            }
         }
     }
 
@@ -560,15 +702,30 @@ mod mockery {
 
     impl Mock for GetMempoolEntriesRequest {
         fn mock() -> Self {
-            GetMempoolEntriesRequest { include_orphan_pool: true, filter_transaction_pool: false }
+            GetMempoolEntriesRequest {
+                include_orphan_pool: true,
+                filter_transaction_pool: false,
+                cursor: mock(),
+                limit: mock(),
+                order: RpcMempoolEntriesOrder::Feerate,
+                min_feerate: Some(1.5),
+                addresses: mock(),
+                compact: true,
+            }
         }
     }
 
     test!(GetMempoolEntriesRequest);
 
+    impl Mock for RpcCompactMempoolEntry {
+        fn mock() -> Self {
+            RpcCompactMempoolEntry { transaction_id: mock(), mass: mock() }
+        }
+    }
+
     impl Mock for GetMempoolEntriesResponse {
         fn mock() -> Self {
-            GetMempoolEntriesResponse { mempool_entries: mock() }
+            GetMempoolEntriesResponse { mempool_entries: mock(), compact_entries: mock(), next_cursor: mock() }
         }
     }
 
@@ -622,6 +779,46 @@ mod mockery {
 
     test!(SubmitTransactionResponse);
 
+    impl Mock for SubmitPackageRequest {
+        fn mock() -> Self {
+            SubmitPackageRequest { transactions: mock() }
+        }
+    }
+
+    test!(SubmitPackageRequest);
+
+    impl Mock for SubmitPackageResponse {
+        fn mock() -> Self {
+            SubmitPackageResponse { transaction_ids: mock() }
+        }
+    }
+
+    test!(SubmitPackageResponse);
+
+    impl Mock for GetTransactionStatusRequest {
+        fn mock() -> Self {
+            GetTransactionStatusRequest { transaction_id: mock() }
+        }
+    }
+
+    test!(GetTransactionStatusRequest);
+
+    impl Mock for RpcTransactionStatus {
+        fn mock() -> Self {
+            RpcTransactionStatus::new(mock(), RpcTransactionState::Accepted)
+                .with_including_block_daa_score(mock())
+                .with_accepting_block(mock(), mock(), mock())
+        }
+    }
+
+    impl Mock for GetTransactionStatusResponse {
+        fn mock() -> Self {
+            GetTransactionStatusResponse { status: mock() }
+        }
+    }
+
+    test!(GetTransactionStatusResponse);
+
     impl Mock for GetSubnetworkRequest {
         fn mock() -> Self {
             GetSubnetworkRequest { subnetwork_id: mock() }
@@ -668,6 +865,76 @@ mod mockery {
 
     test!(GetVirtualChainFromBlockResponse);
 
+    impl Mock for GetVirtualChainChunkRequest {
+        fn mock() -> Self {
+            GetVirtualChainChunkRequest { start_hash: mock(), max_chain_blocks: mock() }
+        }
+    }
+
+    test!(GetVirtualChainChunkRequest);
+
+    impl Mock for RpcAcceptedTransaction {
+        fn mock() -> Self {
+            RpcAcceptedTransaction { transaction_id: mock(), transaction: mock(), input_utxo_entries: mock() }
+        }
+    }
+
+    impl Mock for RpcAcceptingChainBlock {
+        fn mock() -> Self {
+            RpcAcceptingChainBlock { hash: mock(), daa_score: mock(), accepted_transactions: mock() }
+        }
+    }
+
+    impl Mock for GetVirtualChainChunkResponse {
+        fn mock() -> Self {
+            GetVirtualChainChunkResponse { removed_chain_block_hashes: mock(), added_chain_blocks: mock(), is_tip: true }
+        }
+    }
+
+    test!(GetVirtualChainChunkResponse);
+
+    impl Mock for GetFeeEstimateHistoryRequest {
+        fn mock() -> Self {
+            GetFeeEstimateHistoryRequest { max_blocks: mock() }
+        }
+    }
+
+    test!(GetFeeEstimateHistoryRequest);
+
+    impl Mock for RpcFeerateBucket {
+        fn mock() -> Self {
+            RpcFeerateBucket { feerate: mock(), estimated_seconds: mock(), confidence: mock() }
+        }
+    }
+
+    impl Mock for RpcFeeEstimate {
+        fn mock() -> Self {
+            RpcFeeEstimate { priority_bucket: mock(), normal_buckets: mock(), low_buckets: mock() }
+        }
+    }
+
+    impl Mock for RpcBlockFeerateRecord {
+        fn mock() -> Self {
+            RpcBlockFeerateRecord {
+                daa_score: mock(),
+                timestamp: mock(),
+                transaction_count: mock(),
+                min_feerate: mock(),
+                median_feerate: mock(),
+                max_feerate: mock(),
+                median_waited_seconds: mock(),
+            }
+        }
+    }
+
+    impl Mock for GetFeeEstimateHistoryResponse {
+        fn mock() -> Self {
+            GetFeeEstimateHistoryResponse { estimate: mock(), blocks: mock() }
+        }
+    }
+
+    test!(GetFeeEstimateHistoryResponse);
+
     impl Mock for GetBlocksRequest {
         fn mock() -> Self {
             GetBlocksRequest { low_hash: mock(), include_blocks: mock(), include_transactions: mock() }
@@ -1068,9 +1335,21 @@ mod mockery {
 
     test!(GetDaaScoreTimestampEstimateResponse);
 
+    impl Mock for RpcTransactionFilter {
+        fn mock() -> Self {
+            RpcTransactionFilter::new(mock(), vec![0x01, 0x02], mock())
+        }
+    }
+
+    impl Mock for RpcResumeToken {
+        fn mock() -> Self {
+            RpcResumeToken::SinkHash(mock())
+        }
+    }
+
     impl Mock for NotifyBlockAddedRequest {
         fn mock() -> Self {
-            NotifyBlockAddedRequest { command: Command::Start }
+            NotifyBlockAddedRequest { filter: mock(), header_only: true, command: Command::Start }
         }
     }
 
@@ -1094,7 +1373,12 @@ mod mockery {
 
     impl Mock for NotifyVirtualChainChangedRequest {
         fn mock() -> Self {
-            NotifyVirtualChainChangedRequest { command: Command::Start, include_accepted_transaction_ids: true }
+            NotifyVirtualChainChangedRequest {
+                command: Command::Start,
+                include_accepted_transaction_ids: true,
+                transaction_ids: mock(),
+                resume_from: mock(),
+            }
         }
     }
 
@@ -1170,7 +1454,7 @@ mod mockery {
 
     impl Mock for NotifyUtxosChangedRequest {
         fn mock() -> Self {
-            NotifyUtxosChangedRequest { addresses: mock(), command: Command::Start }
+            NotifyUtxosChangedRequest { addresses: mock(), resume_from: mock(), command: Command::Start }
         }
     }
 
@@ -1240,6 +1524,30 @@ mod mockery {
 
     test!(VirtualDaaScoreChangedNotification);
 
+    impl Mock for NotifyTransactionStatusChangedRequest {
+        fn mock() -> Self {
+            NotifyTransactionStatusChangedRequest { transaction_ids: mock(), command: Command::Start }
+        }
+    }
+
+    test!(NotifyTransactionStatusChangedRequest);
+
+    impl Mock for NotifyTransactionStatusChangedResponse {
+        fn mock() -> Self {
+            NotifyTransactionStatusChangedResponse {}
+        }
+    }
+
+    test!(NotifyTransactionStatusChangedResponse);
+
+    impl Mock for TransactionStatusChangedNotification {
+        fn mock() -> Self {
+            TransactionStatusChangedNotification { status: mock() }
+        }
+    }
+
+    test!(TransactionStatusChangedNotification);
+
     impl Mock for NotifyPruningPointUtxoSetOverrideRequest {
         fn mock() -> Self {
             NotifyPruningPointUtxoSetOverrideRequest { command: Command::Start }
@@ -1304,6 +1612,7 @@ mod mockery {
 
     test!(UnsubscribeResponse);
 
+    #[derive(serde::Serialize, schemars::JsonSchema)]
     struct Misalign;
 
     impl Mock for Misalign {
@@ -5,6 +5,7 @@ use vecno_consensus_core::tx::{
     UtxoEntry,
 };
 use vecno_utils::{hex::ToHex, serde_bytes_fixed_ref};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use workflow_serializer::prelude::*;
 
@@ -16,7 +17,13 @@ pub type RpcTransactionId = TransactionId;
 pub type RpcScriptVec = ScriptVec;
 pub type RpcScriptPublicKey = ScriptPublicKey;
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+/// Selection of the transactions of interest in block notifications
+pub type RpcTransactionFilter = vecno_notify::scope::TransactionFilter;
+
+/// Position in the virtual chain a subscription resumes from
+pub type RpcResumeToken = vecno_notify::scope::ResumeToken;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcUtxoEntry {
     pub amount: u64,
@@ -78,7 +85,7 @@ impl Deserializer for RpcUtxoEntry {
 }
 
 /// Represents a Vecno transaction outpoint
-#[derive(Eq, Hash, PartialEq, Debug, Copy, Clone, Serialize, Deserialize)]
+#[derive(Eq, Hash, PartialEq, Debug, Copy, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcTransactionOutpoint {
     #[serde(with = "serde_bytes_fixed_ref")]
@@ -131,11 +138,12 @@ impl Deserializer for RpcTransactionOutpoint {
 }
 
 /// Represents a Vecno transaction input
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcTransactionInput {
     pub previous_outpoint: RpcTransactionOutpoint,
     #[serde(with = "hex::serde")]
+    #[schemars(with = "String")]
     pub signature_script: Vec<u8>,
     pub sequence: u64,
     pub sig_op_count: u8,
@@ -199,7 +207,7 @@ impl Deserializer for RpcTransactionInput {
 }
 
 /// Represent Vecno transaction input verbose data
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcTransactionInputVerboseData {}
 
@@ -218,7 +226,7 @@ impl Deserializer for RpcTransactionInputVerboseData {
 }
 
 /// Represents a Vecnod transaction output
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcTransactionOutput {
     pub value: u64,
@@ -261,7 +269,7 @@ impl Deserializer for RpcTransactionOutput {
 }
 
 /// Represent Vecno transaction output verbose data
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcTransactionOutputVerboseData {
     pub script_public_key_type: RpcScriptClass,
@@ -289,7 +297,7 @@ impl Deserializer for RpcTransactionOutputVerboseData {
 }
 
 /// Represents a Vecno transaction
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcTransaction {
     pub version: u16,
@@ -299,6 +307,7 @@ pub struct RpcTransaction {
     pub subnetwork_id: RpcSubnetworkId,
     pub gas: u64,
     #[serde(with = "hex::serde")]
+    #[schemars(with = "String")]
     pub payload: Vec<u8>,
     pub mass: u64,
     pub verbose_data: Option<RpcTransactionVerboseData>,
@@ -355,7 +364,7 @@ impl Deserializer for RpcTransaction {
 }
 
 /// Represent Vecno transaction verbose data
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcTransactionVerboseData {
     pub transaction_id: RpcTransactionId,
@@ -392,9 +401,150 @@ impl Deserializer for RpcTransactionVerboseData {
 }
 
 /// Represents accepted transaction ids
-#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcAcceptedTransactionIds {
     pub accepting_block_hash: RpcHash,
     pub accepted_transaction_ids: Vec<RpcTransactionId>,
 }
+
+/// Represents a transaction accepted by a selected chain block, along with the UTXO entries spent by its inputs
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcAcceptedTransaction {
+    pub transaction_id: RpcTransactionId,
+    pub transaction: RpcTransaction,
+    /// The UTXO entries spent by the transaction, in the order of its inputs
+    pub input_utxo_entries: Vec<RpcUtxoEntry>,
+}
+
+impl Serializer for RpcAcceptedTransaction {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u8, &1, writer)?;
+        store!(RpcTransactionId, &self.transaction_id, writer)?;
+        serialize!(RpcTransaction, &self.transaction, writer)?;
+        serialize!(Vec<RpcUtxoEntry>, &self.input_utxo_entries, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for RpcAcceptedTransaction {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u8, reader)?;
+        let transaction_id = load!(RpcTransactionId, reader)?;
+        let transaction = deserialize!(RpcTransaction, reader)?;
+        let input_utxo_entries = deserialize!(Vec<RpcUtxoEntry>, reader)?;
+
+        Ok(Self { transaction_id, transaction, input_utxo_entries })
+    }
+}
+
+/// Represents a selected chain block along with the transactions it accepted
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcAcceptingChainBlock {
+    pub hash: RpcHash,
+    pub daa_score: u64,
+    pub accepted_transactions: Vec<RpcAcceptedTransaction>,
+}
+
+impl Serializer for RpcAcceptingChainBlock {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u8, &1, writer)?;
+        store!(RpcHash, &self.hash, writer)?;
+        store!(u64, &self.daa_score, writer)?;
+        serialize!(Vec<RpcAcceptedTransaction>, &self.accepted_transactions, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for RpcAcceptingChainBlock {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u8, reader)?;
+        let hash = load!(RpcHash, reader)?;
+        let daa_score = load!(u64, reader)?;
+        let accepted_transactions = deserialize!(Vec<RpcAcceptedTransaction>, reader)?;
+
+        Ok(Self { hash, daa_score, accepted_transactions })
+    }
+}
+
+/// Represents a mandatory block template transaction which could not be included, along with the reason
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcRejectedTransaction {
+    pub transaction_id: RpcTransactionId,
+    pub reason: String,
+}
+
+/// State of a transaction in its way from submission to acceptance by the virtual chain
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[borsh(use_discriminant = true)]
+pub enum RpcTransactionState {
+    /// The transaction is not known by the node, or its outcome was forgotten
+    Unknown = 0,
+    /// The transaction is in the mempool, waiting for inclusion into a block
+    InMempool = 1,
+    /// The transaction is in the orphan pool, waiting for its missing inputs
+    Orphan = 2,
+    /// The transaction was included in a block but is not accepted by the virtual chain yet
+    Included = 3,
+    /// The transaction was accepted by a chain block
+    Accepted = 4,
+    /// The transaction was rejected by the mempool
+    Rejected = 5,
+    /// The transaction was removed from the mempool without being included in a block
+    Evicted = 6,
+}
+
+/// Represents the status of a transaction
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcTransactionStatus {
+    pub transaction_id: RpcTransactionId,
+    pub state: RpcTransactionState,
+    /// The reason of a rejection or an eviction
+    pub reason: Option<String>,
+    /// The DAA score of the block which included the transaction
+    pub including_block_daa_score: Option<u64>,
+    /// The chain block which accepted the transaction
+    pub accepting_block_hash: Option<RpcHash>,
+    pub accepting_block_daa_score: Option<u64>,
+    /// The blue score distance between the sink and the accepting block
+    pub confirmations: Option<u64>,
+}
+
+impl RpcTransactionStatus {
+    pub fn new(transaction_id: RpcTransactionId, state: RpcTransactionState) -> Self {
+        Self {
+            transaction_id,
+            state,
+            reason: None,
+            including_block_daa_score: None,
+            accepting_block_hash: None,
+            accepting_block_daa_score: None,
+            confirmations: None,
+        }
+    }
+
+    pub fn with_reason(self, reason: String) -> Self {
+        Self { reason: Some(reason), ..self }
+    }
+
+    pub fn with_including_block_daa_score(self, daa_score: u64) -> Self {
+        Self { including_block_daa_score: Some(daa_score), ..self }
+    }
+
+    pub fn with_accepting_block(self, hash: RpcHash, daa_score: u64, confirmations: u64) -> Self {
+        Self {
+            state: RpcTransactionState::Accepted,
+            accepting_block_hash: Some(hash),
+            accepting_block_daa_score: Some(daa_score),
+            confirmations: Some(confirmations),
+            ..self
+        }
+    }
+}
@@ -44,6 +44,36 @@ const TS_ACCEPTED_TRANSACTION_IDS: &'static str = r#"
     }
 "#;
 
+#[wasm_bindgen(typescript_custom_section)]
+const TS_ACCEPTING_CHAIN_BLOCK: &'static str = r#"
+    /**
+     * Transaction accepted by a chain block, along with the UTXO entries spent by its inputs.
+     * 
+     * @category Node RPC
+     */
+    export interface IAcceptedTransaction {
+        transactionId : HexString;
+        transaction : ITransaction;
+        inputUtxoEntries : {
+            amount : bigint;
+            scriptPublicKey : IScriptPublicKey;
+            blockDaaScore : bigint;
+            isCoinbase : boolean;
+        }[];
+    }
+
+    /**
+     * Selected chain block along with the transactions it accepted.
+     * 
+     * @category Node RPC
+     */
+    export interface IAcceptingChainBlock {
+        hash : HexString;
+        daaScore : bigint;
+        acceptedTransactions : IAcceptedTransaction[];
+    }
+"#;
+
 // ---
 
 declare! {
@@ -819,6 +849,26 @@ declare! {
          * `extraData` can contain a user-supplied plain text or a byte array represented by `Uint8array`.
          */
         extraData? : string | Uint8Array;
+        /**
+         * `templateId` of a previous response, making the call wait until a different template is available.
+         */
+        longPollId? : string;
+        /**
+         * Maximum duration of the wait in milliseconds.
+         */
+        longPollTimeout? : number;
+        /**
+         * Ids of mempool transactions which must be included in the template.
+         */
+        mandatoryTransactionIds? : HexString[];
+        /**
+         * Transactions which must be included in the template, whether or not they are in the mempool.
+         */
+        mandatoryTransactions? : Transaction[];
+        /**
+         * Ids of mempool transactions which must not be selected to fill the template.
+         */
+        excludedTransactionIds? : HexString[];
     }
     "#,
 }
@@ -834,9 +884,26 @@ try_from! ( args: IGetBlockTemplateRequest, GetBlockTemplateRequest, {
     } else {
         Default::default()
     };
+    let long_poll_id = args.try_get_string("longPollId")?;
+    let long_poll_timeout = args.try_get_value("longPollTimeout")?.and_then(|value| value.as_f64()).map(|value| value as u64);
+    let mandatory_transaction_ids = args.try_get_value("mandatoryTransactionIds")?.map(from_value).transpose()?.unwrap_or_default();
+    let mandatory_transactions = if let Some(transactions) = args.try_get_value("mandatoryTransactions")? {
+        js_sys::Array::from(&transactions)
+            .iter()
+            .map(|transaction| Ok(Transaction::try_owned_from(&transaction)?.into()))
+            .collect::<Result<Vec<RpcTransaction>>>()?
+    } else {
+        Default::default()
+    };
+    let excluded_transaction_ids = args.try_get_value("excludedTransactionIds")?.map(from_value).transpose()?.unwrap_or_default();
     Ok(GetBlockTemplateRequest {
         pay_address,
         extra_data,
+        long_poll_id,
+        long_poll_timeout,
+        mandatory_transaction_ids,
+        mandatory_transactions,
+        excluded_transaction_ids,
     })
 });
 
@@ -850,6 +917,12 @@ declare! {
      */
     export interface IGetBlockTemplateResponse {
         block : IRawBlock;
+        isSynced : boolean;
+        templateId : string;
+        rejectedTransactions : {
+            transactionId : HexString;
+            reason : string;
+        }[];
     }
     "#,
 }
@@ -1023,6 +1096,24 @@ declare! {
     export interface IGetMempoolEntriesRequest {
         includeOrphanPool? : boolean;
         filterTransactionPool? : boolean;
+        /**
+         * The nextCursor of a previous response, to fetch the next page.
+         */
+        cursor? : string;
+        /**
+         * The maximum number of entries to return, all of them if not passed.
+         */
+        limit? : number;
+        order? : "arrival" | "feerate";
+        /**
+         * Excludes the entries paying a lower feerate, orphans included if positive.
+         */
+        minFeerate? : number;
+        addresses? : Address[] | string[];
+        /**
+         * Returns only the ids and masses of the entries in compactEntries.
+         */
+        compact? : boolean;
     }
     "#,
 }
@@ -1041,6 +1132,8 @@ declare! {
      */
     export interface IGetMempoolEntriesResponse {
         mempoolEntries : IMempoolEntry[];
+        compactEntries : ICompactMempoolEntry[];
+        nextCursor? : string;
     }
     "#,
 }
@@ -1546,10 +1639,25 @@ declare! {
          * The estimated inclusion time for a transaction with fee/mass = feerate
          */
         estimatedSeconds : number;
+        /**
+         * The share of the recently mined transactions paying at least feerate which got included
+         * within estimatedSeconds, if the node has enough fee estimation history to tell
+         */
+        confidence? : number;
     }
     "#,
 }
 
+try_from!( bucket: RpcFeerateBucket, IFeerateBucket, {
+    let object = IFeerateBucket::default();
+    object.set("feerate", &bucket.feerate.into())?;
+    object.set("estimatedSeconds", &bucket.estimated_seconds.into())?;
+    if let Some(confidence) = bucket.confidence {
+        object.set("confidence", &confidence.into())?;
+    }
+    Ok(object)
+});
+
 declare! {
     IFeeEstimate,
     r#"
@@ -1588,23 +1696,9 @@ declare! {
 
 try_from!( estimate: RpcFeeEstimate, IFeeEstimate, {
 
-    let priority_bucket = IFeerateBucket::default();
-    priority_bucket.set("feerate", &estimate.priority_bucket.feerate.into())?;
-    priority_bucket.set("estimatedSeconds", &estimate.priority_bucket.estimated_seconds.into())?;
-
-    let normal_buckets = estimate.normal_buckets.into_iter().map(|normal_bucket| {
-        let bucket = IFeerateBucket::default();
-        bucket.set("feerate", &normal_bucket.feerate.into())?;
-        bucket.set("estimatedSeconds", &normal_bucket.estimated_seconds.into())?;
-        Ok(bucket)
-    }).collect::<Result<Vec<IFeerateBucket>>>()?;
-
-    let low_buckets = estimate.low_buckets.into_iter().map(|low_bucket| {
-        let bucket = IFeerateBucket::default();
-        bucket.set("feerate", &low_bucket.feerate.into())?;
-        bucket.set("estimatedSeconds", &low_bucket.estimated_seconds.into())?;
-        Ok(bucket)
-    }).collect::<Result<Vec<IFeerateBucket>>>()?;
+    let priority_bucket = IFeerateBucket::try_from(estimate.priority_bucket)?;
+    let normal_buckets = estimate.normal_buckets.into_iter().map(IFeerateBucket::try_from).collect::<Result<Vec<IFeerateBucket>>>()?;
+    let low_buckets = estimate.low_buckets.into_iter().map(IFeerateBucket::try_from).collect::<Result<Vec<IFeerateBucket>>>()?;
 
     let estimate = IFeeEstimate::default();
     estimate.set("priorityBucket", &priority_bucket)?;
@@ -1770,3 +1864,313 @@ try_from!(args: GetUtxoReturnAddressResponse, IGetUtxoReturnAddressResponse, {
 });
 
 // ---
+
+declare! {
+    IGetTransactionRequest,
+    r#"
+    /**
+     * Requires the node to run with the transaction index enabled (`--txindex`).
+     *
+     * @category Node RPC
+     */
+    export interface IGetTransactionRequest {
+        transactionId: HexString;
+    }
+    "#,
+}
+
+try_from!(args: IGetTransactionRequest, GetTransactionRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetTransactionResponse,
+    r#"
+    /**
+     *
+     *
+     * @category Node RPC
+     */
+    export interface IGetTransactionResponse {
+        transaction: ITransaction;
+        blockHash: HexString;
+        acceptingBlockHash: HexString;
+        acceptingBlockDaaScore: bigint;
+    }
+    "#,
+}
+
+try_from!(args: GetTransactionResponse, IGetTransactionResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetAddressHistoryRequest,
+    r#"
+    /**
+     * Requires the node to run with the address history index enabled (`--addressindex`).
+     * Pass the `nextCursor` of a previous response as `cursor` to fetch the following page.
+     *
+     * @category Node RPC
+     */
+    export interface IGetAddressHistoryRequest {
+        address : Address | string;
+        cursor? : string;
+        limit : number;
+    }
+    "#,
+}
+
+try_from!(args: IGetAddressHistoryRequest, GetAddressHistoryRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetAddressHistoryResponse,
+    r#"
+    /**
+     *
+     *
+     * @category Node RPC
+     */
+    export interface IGetAddressHistoryResponse {
+        entries : {
+            transactionId : HexString;
+            kind : "received" | "spent";
+            outpoint : ITransactionOutpoint;
+            amount : bigint;
+            acceptingBlockHash : HexString;
+            acceptingBlockDaaScore : bigint;
+        }[];
+        nextCursor? : string;
+    }
+    "#,
+}
+
+try_from!(args: GetAddressHistoryResponse, IGetAddressHistoryResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    ISubmitPackageRequest,
+    r#"
+    /**
+     * Submit a package of related transactions to the node, all of them being accepted or none.
+     * The package pays the minimum relay fee as a whole, so a child can pay for its parents.
+     *
+     * @category Node RPC
+     */
+    export interface ISubmitPackageRequest {
+        transactions : Transaction[];
+    }
+    "#,
+}
+
+try_from!(args: ISubmitPackageRequest, SubmitPackageRequest, {
+    let transactions = js_sys::Array::from(&args.get_value("transactions")?)
+        .iter()
+        .map(|transaction| Ok(Transaction::try_owned_from(&transaction)?.into()))
+        .collect::<Result<Vec<RpcTransaction>>>()?;
+    Ok(SubmitPackageRequest { transactions })
+});
+
+declare! {
+    ISubmitPackageResponse,
+    r#"
+    /**
+     *
+     *
+     * @category Node RPC
+     */
+    export interface ISubmitPackageResponse {
+        transactionIds : HexString[];
+    }
+    "#,
+}
+
+try_from!(args: SubmitPackageResponse, ISubmitPackageResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetVirtualChainChunkRequest,
+    r#"
+    /**
+     * Requests a chunk of the virtual selected parent chain from some start hash, along with
+     * the transactions accepted by each chain block. The next chunk is requested from the last
+     * added chain block until a chunk reaching the sink is returned.
+     *
+     * @category Node RPC
+     */
+    export interface IGetVirtualChainChunkRequest {
+        startHash : HexString;
+        maxChainBlocks : number;
+    }
+    "#,
+}
+
+try_from!(args: IGetVirtualChainChunkRequest, GetVirtualChainChunkRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetVirtualChainChunkResponse,
+    r#"
+    /**
+     *
+     *
+     * @category Node RPC
+     */
+    export interface IGetVirtualChainChunkResponse {
+        removedChainBlockHashes : HexString[];
+        addedChainBlocks : IAcceptingChainBlock[];
+        isTip : boolean;
+    }
+    "#,
+}
+
+try_from!(args: GetVirtualChainChunkResponse, IGetVirtualChainChunkResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IBlockFeerateRecord,
+    r#"
+    /**
+     * Feerate statistics of the mempool transactions included by a recently mined block.
+     *
+     * @category Node RPC
+     */
+    export interface IBlockFeerateRecord {
+        daaScore : bigint;
+        /**
+         * Unix time in milliseconds at which the node handled the block
+         */
+        timestamp : bigint;
+        /**
+         * The number of block transactions the node had in its mempool, the only ones the statistics are about
+         */
+        transactionCount : number;
+        minFeerate : number;
+        medianFeerate : number;
+        maxFeerate : number;
+        /**
+         * The median time the included transactions spent in the mempool
+         */
+        medianWaitedSeconds : number;
+    }
+    "#,
+}
+
+declare! {
+    IGetFeeEstimateHistoryRequest,
+    r#"
+    /**
+     * Requests the current fee estimate, with the confidence of each feerate bucket as observed
+     * in the recently mined blocks, along with the feerate records of at most maxBlocks of these blocks.
+     *
+     * @category Node RPC
+     */
+    export interface IGetFeeEstimateHistoryRequest {
+        maxBlocks : number;
+    }
+    "#,
+}
+
+try_from!(args: IGetFeeEstimateHistoryRequest, GetFeeEstimateHistoryRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetFeeEstimateHistoryResponse,
+    r#"
+    /**
+     *
+     *
+     * @category Node RPC
+     */
+    export interface IGetFeeEstimateHistoryResponse {
+        estimate : IFeeEstimate;
+        blocks : IBlockFeerateRecord[];
+    }
+    "#,
+}
+
+try_from!(args: GetFeeEstimateHistoryResponse, IGetFeeEstimateHistoryResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    ITransactionStatus,
+    r#"
+    /**
+     * Status of a transaction, from its submission to its acceptance by the virtual chain.
+     *
+     * @category Node RPC
+     */
+    export interface ITransactionStatus {
+        transactionId : HexString;
+        state : "unknown" | "inMempool" | "orphan" | "included" | "accepted" | "rejected" | "evicted";
+        /**
+         * The reason of a rejection or an eviction
+         */
+        reason? : string;
+        includingBlockDaaScore? : bigint;
+        acceptingBlockHash? : HexString;
+        acceptingBlockDaaScore? : bigint;
+        /**
+         * The blue score distance between the sink and the accepting block
+         */
+        confirmations? : bigint;
+    }
+    "#,
+}
+
+declare! {
+    IGetTransactionStatusRequest,
+    r#"
+    /**
+     * Requests what happened to a transaction since its submission. The outcomes of the
+     * transactions leaving the mempool are only kept for a bounded number of recent transactions.
+     *
+     * @category Node RPC
+     */
+    export interface IGetTransactionStatusRequest {
+        transactionId : HexString;
+    }
+    "#,
+}
+
+try_from!(args: IGetTransactionStatusRequest, GetTransactionStatusRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetTransactionStatusResponse,
+    r#"
+    /**
+     *
+     *
+     * @category Node RPC
+     */
+    export interface IGetTransactionStatusResponse {
+        status : ITransactionStatus;
+    }
+    "#,
+}
+
+try_from!(args: GetTransactionStatusResponse, IGetTransactionStatusResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
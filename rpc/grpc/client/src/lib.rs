@@ -1,17 +1,24 @@
 use self::{
     error::{Error, Result},
-    resolver::{id::IdResolver, queue::QueueResolver, DynResolver},
+    resolver::{id::IdResolver, queue::QueueResolver, DynResolver, VecnodResponseStreamReceiver},
 };
 use async_channel::{Receiver, Sender};
 use async_trait::async_trait;
 pub use client_pool::ClientPool;
 use connection_event::ConnectionEvent;
-use futures::{future::FutureExt, pin_mut, select};
+use futures::{
+    future::FutureExt,
+    pin_mut, select,
+    stream::{self, BoxStream, StreamExt},
+};
 use vecno_core::{debug, error, trace};
 use vecno_grpc_core::{
     channel::NotificationChannel,
     ops::VecnodPayloadOps,
-    protowire::{vecnod_request, rpc_client::RpcClient, GetInfoRequestMessage, VecnodRequest, VecnodResponse},
+    protowire::{
+        vecnod_request, rpc_client::RpcClient, GetInfoRequestMessage, GetVirtualChainChunkRequestMessage, VecnodRequest,
+        VecnodResponse,
+    },
     RPC_MAX_MESSAGE_SIZE,
 };
 use vecno_notify::{
@@ -28,12 +35,12 @@ use vecno_notify::{
     },
 };
 use vecno_rpc_core::{
-    api::rpc::RpcApi,
+    api::rpc::{stream_virtual_chain_in_chunks, RpcApi},
     error::RpcError,
     error::RpcResult,
     model::message::*,
     notify::{collector::RpcCoreConverter, connection::ChannelConnection, mode::NotificationMode},
-    Notification,
+    Notification, RpcHash,
 };
 use vecno_utils::{channel::Channel, triggers::DuplexTrigger};
 use vecno_utils_tower::{
@@ -277,6 +284,36 @@ impl RpcApi for GrpcClient {
     route!(get_fee_estimate_experimental_call, GetFeeEstimateExperimental);
     route!(get_current_block_color_call, GetCurrentBlockColor);
     route!(get_utxo_return_address_call, GetUtxoReturnAddress);
+    route!(get_transaction_call, GetTransaction);
+    route!(get_address_history_call, GetAddressHistory);
+    route!(submit_package_call, SubmitPackage);
+    route!(get_virtual_chain_chunk_call, GetVirtualChainChunk);
+    route!(get_fee_estimate_history_call, GetFeeEstimateHistory);
+    route!(get_transaction_status_call, GetTransactionStatus);
+
+    /// Has the server stream the virtual selected parent chain, or requests it chunk by chunk if the server
+    /// cannot tell the responses to concurrent requests apart.
+    fn stream_virtual_chain_from_block(
+        &self,
+        start_hash: RpcHash,
+        max_chain_blocks: u32,
+    ) -> BoxStream<'_, RpcResult<GetVirtualChainChunkResponse>> {
+        if !self.handle_message_id() {
+            return stream_virtual_chain_in_chunks(self, start_hash, max_chain_blocks);
+        }
+        let request = GetVirtualChainChunkRequestMessage {
+            stream: true,
+            ..GetVirtualChainChunkRequestMessage::from(&GetVirtualChainChunkRequest::new(start_hash, max_chain_blocks))
+        };
+        stream::once(self.inner.call_streamed(VecnodPayloadOps::GetVirtualChainChunk, request))
+            .flat_map(|receiver| match receiver {
+                Ok(mut receiver) => stream::poll_fn(move |cx| receiver.poll_recv(cx))
+                    .map(|response| -> RpcResult<GetVirtualChainChunkResponse> { response?.as_ref().try_into() })
+                    .boxed(),
+                Err(err) => stream::iter([Err(RpcError::from(err))]).boxed(),
+            })
+            .boxed()
+    }
 
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
@@ -701,6 +738,28 @@ impl Inner {
         }
     }
 
+    /// Sends a request whose responses are streamed by the server and returns the receiver of these responses,
+    /// which ends after the last one.
+    async fn call_streamed(&self, op: VecnodPayloadOps, request: impl Into<VecnodRequest>) -> Result<VecnodResponseStreamReceiver> {
+        // Calls are only allowed if the client is connected to the server
+        if self.is_connected() {
+            let id = u64::from_le_bytes(rand::random::<[u8; 8]>());
+            let mut request: VecnodRequest = request.into();
+            request.id = id;
+
+            trace!("GRPC client: resolver streamed call: {:?}", request);
+            if request.payload.is_some() {
+                let receiver = self.resolver().register_stream_request(op, &request);
+                self.request_sender.send(request).await.map_err(|_| Error::ChannelRecvError)?;
+                Ok(receiver)
+            } else {
+                Err(Error::MissingRequestPayload)
+            }
+        } else {
+            Err(Error::NotConnected)
+        }
+    }
+
     /// Launch a task that periodically checks pending requests and deletes those that have
     /// waited longer than a predefined delay.
     fn spawn_request_timeout_monitor(self: Arc<Self>) {
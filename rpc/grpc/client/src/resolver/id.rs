@@ -1,6 +1,6 @@
 use crate::{
     error::{Error, Result},
-    resolver::{VecnodResponseReceiver, VecnodResponseSender, Resolver},
+    resolver::{PendingSender, VecnodResponseReceiver, VecnodResponseStreamReceiver, Resolver},
 };
 use vecno_core::trace;
 use vecno_grpc_core::{
@@ -12,16 +12,16 @@ use std::{
     sync::{Arc, Mutex},
     time::Instant,
 };
-use tokio::sync::oneshot;
+use tokio::sync::{mpsc, oneshot};
 
 #[derive(Debug)]
 struct Pending {
     timestamp: Instant,
-    sender: VecnodResponseSender,
+    sender: PendingSender,
 }
 
 impl Pending {
-    fn new(sender: VecnodResponseSender) -> Self {
+    fn new(sender: PendingSender) -> Self {
         Self { timestamp: Instant::now(), sender }
     }
 }
@@ -42,14 +42,38 @@ impl Resolver for IdResolver {
         let (sender, receiver) = oneshot::channel::<Result<VecnodResponse>>();
         {
             let mut pending_calls = self.pending_calls.lock().unwrap();
-            pending_calls.insert(request.id, Pending::new(sender));
+            pending_calls.insert(request.id, Pending::new(PendingSender::Single(sender)));
+            drop(pending_calls);
+        }
+        receiver
+    }
+
+    fn register_stream_request(&self, _: VecnodPayloadOps, request: &VecnodRequest) -> VecnodResponseStreamReceiver {
+        let (sender, receiver) = mpsc::unbounded_channel::<Result<VecnodResponse>>();
+        {
+            let mut pending_calls = self.pending_calls.lock().unwrap();
+            pending_calls.insert(request.id, Pending::new(PendingSender::Stream(sender)));
             drop(pending_calls);
         }
         receiver
     }
 
     fn handle_response(&self, response: VecnodResponse) {
-        match self.pending_calls.lock().unwrap().remove(&response.id) {
+        let mut pending_calls = self.pending_calls.lock().unwrap();
+        // A streamed request keeps pending until the response ending its stream, the timeout applying between responses
+        if let Some(pending) = pending_calls.get_mut(&response.id).filter(|pending| pending.sender.expects_more_than(&response)) {
+            trace!("[Resolver] handle_response has matching streamed request with id {}", response.id);
+            pending.timestamp = Instant::now();
+            let id = response.id;
+            if !pending.sender.send_streamed(response) {
+                trace!("[Resolver] handle_response: the responses streamed to request {} are no longer awaited", id);
+                pending_calls.remove(&id);
+            }
+            return;
+        }
+        let pending = pending_calls.remove(&response.id);
+        drop(pending_calls);
+        match pending {
             Some(pending) => {
                 trace!("[Resolver] handle_response has matching request with id {}", response.id);
                 match pending.sender.send(Ok(response)) {
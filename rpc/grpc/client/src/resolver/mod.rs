@@ -5,7 +5,7 @@ use vecno_grpc_core::{
     protowire::{VecnodRequest, VecnodResponse},
 };
 use std::{sync::Arc, time::Duration};
-use tokio::sync::oneshot;
+use tokio::sync::{mpsc, oneshot};
 
 pub(crate) mod id;
 pub(crate) mod matcher;
@@ -13,6 +13,7 @@ pub(crate) mod queue;
 
 pub(crate) trait Resolver: Send + Sync + Debug {
     fn register_request(&self, op: VecnodPayloadOps, request: &VecnodRequest) -> VecnodResponseReceiver;
+    fn register_stream_request(&self, op: VecnodPayloadOps, request: &VecnodRequest) -> VecnodResponseStreamReceiver;
     fn handle_response(&self, response: VecnodResponse);
     fn remove_expired_requests(&self, timeout: Duration);
 }
@@ -21,3 +22,38 @@ pub(crate) type DynResolver = Arc<dyn Resolver>;
 
 pub(crate) type VecnodResponseSender = oneshot::Sender<Result<VecnodResponse>>;
 pub(crate) type VecnodResponseReceiver = oneshot::Receiver<Result<VecnodResponse>>;
+
+pub(crate) type VecnodResponseStreamSender = mpsc::UnboundedSender<Result<VecnodResponse>>;
+pub(crate) type VecnodResponseStreamReceiver = mpsc::UnboundedReceiver<Result<VecnodResponse>>;
+
+/// The sender of the responses to a pending request
+#[derive(Debug)]
+pub(crate) enum PendingSender {
+    /// Sends the single response of a request
+    Single(VecnodResponseSender),
+    /// Sends the responses streamed by the server, up to the one ending the stream
+    Stream(VecnodResponseStreamSender),
+}
+
+impl PendingSender {
+    /// Returns true if the request keeps pending after `response`
+    pub(crate) fn expects_more_than(&self, response: &VecnodResponse) -> bool {
+        matches!(self, Self::Stream(_)) && !response.is_stream_end()
+    }
+
+    /// Sends a response of a request still pending, returning false if it is no longer awaited
+    pub(crate) fn send_streamed(&self, response: VecnodResponse) -> bool {
+        match self {
+            Self::Single(_) => false,
+            Self::Stream(sender) => sender.send(Ok(response)).is_ok(),
+        }
+    }
+
+    /// Sends the last response of a request
+    pub(crate) fn send(self, result: Result<VecnodResponse>) -> std::result::Result<(), Result<VecnodResponse>> {
+        match self {
+            Self::Single(sender) => sender.send(result),
+            Self::Stream(sender) => sender.send(result).map_err(|err| err.0),
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     error::{Error, Result},
-    resolver::{matcher::Matcher, VecnodResponseReceiver, VecnodResponseSender, Resolver},
+    resolver::{matcher::Matcher, PendingSender, VecnodResponseReceiver, VecnodResponseStreamReceiver, Resolver},
 };
 use vecno_core::trace;
 use vecno_grpc_core::{
@@ -12,18 +12,18 @@ use std::{
     sync::{Arc, Mutex},
     time::Instant,
 };
-use tokio::sync::oneshot;
+use tokio::sync::{mpsc, oneshot};
 
 #[derive(Debug)]
 struct Pending {
     timestamp: Instant,
     op: VecnodPayloadOps,
     request: VecnodRequest,
-    sender: VecnodResponseSender,
+    sender: PendingSender,
 }
 
 impl Pending {
-    fn new(op: VecnodPayloadOps, request: VecnodRequest, sender: VecnodResponseSender) -> Self {
+    fn new(op: VecnodPayloadOps, request: VecnodRequest, sender: PendingSender) -> Self {
         Self { timestamp: Instant::now(), op, request, sender }
     }
 
@@ -47,7 +47,19 @@ impl Resolver for QueueResolver {
     fn register_request(&self, op: VecnodPayloadOps, request: &VecnodRequest) -> VecnodResponseReceiver {
         let (sender, receiver) = oneshot::channel::<Result<VecnodResponse>>();
         {
-            let pending = Pending::new(op, request.clone(), sender);
+            let pending = Pending::new(op, request.clone(), PendingSender::Single(sender));
+
+            let mut pending_calls = self.pending_calls.lock().unwrap();
+            pending_calls.push_back(pending);
+            drop(pending_calls);
+        }
+        receiver
+    }
+
+    fn register_stream_request(&self, op: VecnodPayloadOps, request: &VecnodRequest) -> VecnodResponseStreamReceiver {
+        let (sender, receiver) = mpsc::unbounded_channel::<Result<VecnodResponse>>();
+        {
+            let pending = Pending::new(op, request.clone(), PendingSender::Stream(sender));
 
             let mut pending_calls = self.pending_calls.lock().unwrap();
             pending_calls.push_back(pending);
@@ -60,21 +72,22 @@ impl Resolver for QueueResolver {
         let response_op: VecnodPayloadOps = response.payload.as_ref().unwrap().try_into().expect("response is not a notification");
         trace!("[Resolver] handle_response type: {:?}", response_op);
         let mut pending_calls = self.pending_calls.lock().unwrap();
-        let mut pending: Option<Pending> = None;
-        if pending_calls.front().is_some() {
-            if pending_calls.front().unwrap().is_matching(&response, response_op) {
-                pending = pending_calls.pop_front();
-            } else {
-                let pending_slice = pending_calls.make_contiguous();
-                // Iterate the queue front to back, so older pendings first
-                for i in 0..pending_slice.len() {
-                    if pending_calls.get(i).unwrap().is_matching(&response, response_op) {
-                        pending = pending_calls.remove(i);
-                        break;
-                    }
+        // Iterate the queue front to back, so older pendings first
+        let index = pending_calls.iter().position(|pending| pending.is_matching(&response, response_op));
+        let pending = match index {
+            // A streamed request keeps pending until the response ending its stream, the timeout applying between responses
+            Some(index) if pending_calls[index].sender.expects_more_than(&response) => {
+                let pending = &mut pending_calls[index];
+                pending.timestamp = Instant::now();
+                if !pending.sender.send_streamed(response) {
+                    trace!("[Resolver] handle_response: the responses streamed to a request are no longer awaited");
+                    pending_calls.remove(index);
                 }
+                return;
             }
-        }
+            Some(index) => pending_calls.remove(index),
+            None => None,
+        };
         drop(pending_calls);
         if let Some(pending) = pending {
             trace!("[Resolver] handle_response matching request found: {:?}", pending.request);
@@ -10,6 +10,7 @@ from!(item: &vecno_rpc_core::RpcFeerateBucket, protowire::RpcFeerateBucket, {
     Self {
         feerate: item.feerate,
         estimated_seconds: item.estimated_seconds,
+        confidence: item.confidence,
     }
 });
 
@@ -32,6 +33,18 @@ from!(item: &vecno_rpc_core::RpcFeeEstimateVerboseExperimentalData, protowire::R
     }
 });
 
+from!(item: &vecno_rpc_core::RpcBlockFeerateRecord, protowire::RpcBlockFeerateRecord, {
+    Self {
+        daa_score: item.daa_score,
+        timestamp: item.timestamp,
+        transaction_count: item.transaction_count,
+        min_feerate: item.min_feerate,
+        median_feerate: item.median_feerate,
+        max_feerate: item.max_feerate,
+        median_waited_seconds: item.median_waited_seconds,
+    }
+});
+
 // ----------------------------------------------------------------------------
 // protowire to rpc_core
 // ----------------------------------------------------------------------------
@@ -40,6 +53,7 @@ try_from!(item: &protowire::RpcFeerateBucket, vecno_rpc_core::RpcFeerateBucket,
     Self {
         feerate: item.feerate,
         estimated_seconds: item.estimated_seconds,
+        confidence: item.confidence,
     }
 });
 
@@ -64,3 +78,15 @@ try_from!(item: &protowire::RpcFeeEstimateVerboseExperimentalData, vecno_rpc_cor
         next_block_template_feerate_max: item.next_block_template_feerate_max,
     }
 });
+
+try_from!(item: &protowire::RpcBlockFeerateRecord, vecno_rpc_core::RpcBlockFeerateRecord, {
+    Self {
+        daa_score: item.daa_score,
+        timestamp: item.timestamp,
+        transaction_count: item.transaction_count,
+        min_feerate: item.min_feerate,
+        median_feerate: item.median_feerate,
+        max_feerate: item.max_feerate,
+        median_waited_seconds: item.median_waited_seconds,
+    }
+});
@@ -1,6 +1,7 @@
 use crate::protowire;
 use crate::{from, try_from};
-use vecno_rpc_core::RpcError;
+use vecno_rpc_core::{RpcError, RpcHash};
+use std::str::FromStr;
 
 // ----------------------------------------------------------------------------
 // rpc_core to protowire
@@ -10,6 +11,17 @@ from!(item: &vecno_rpc_core::RpcMempoolEntry, protowire::RpcMempoolEntry, {
     Self { fee: item.fee, transaction: Some((&item.transaction).into()), is_orphan: item.is_orphan }
 });
 
+from!(item: &vecno_rpc_core::RpcCompactMempoolEntry, protowire::RpcCompactMempoolEntry, {
+    Self { transaction_id: item.transaction_id.to_string(), mass: item.mass }
+});
+
+from!(item: &vecno_rpc_core::RpcMempoolEntriesOrder, protowire::RpcMempoolEntriesOrder, {
+    match item {
+        vecno_rpc_core::RpcMempoolEntriesOrder::Arrival => protowire::RpcMempoolEntriesOrder::Arrival,
+        vecno_rpc_core::RpcMempoolEntriesOrder::Feerate => protowire::RpcMempoolEntriesOrder::Feerate,
+    }
+});
+
 from!(item: &vecno_rpc_core::RpcMempoolEntryByAddress, protowire::RpcMempoolEntryByAddress, {
     Self {
         address: (&item.address).into(),
@@ -33,6 +45,17 @@ try_from!(item: &protowire::RpcMempoolEntry, vecno_rpc_core::RpcMempoolEntry, {
     )
 });
 
+try_from!(item: &protowire::RpcCompactMempoolEntry, vecno_rpc_core::RpcCompactMempoolEntry, {
+    Self::new(RpcHash::from_str(&item.transaction_id)?, item.mass)
+});
+
+from!(item: protowire::RpcMempoolEntriesOrder, vecno_rpc_core::RpcMempoolEntriesOrder, {
+    match item {
+        protowire::RpcMempoolEntriesOrder::Arrival => vecno_rpc_core::RpcMempoolEntriesOrder::Arrival,
+        protowire::RpcMempoolEntriesOrder::Feerate => vecno_rpc_core::RpcMempoolEntriesOrder::Feerate,
+    }
+});
+
 try_from!(item: &protowire::RpcMempoolEntryByAddress, vecno_rpc_core::RpcMempoolEntryByAddress, {
     Self::new(
         item.address.as_str().try_into()?,
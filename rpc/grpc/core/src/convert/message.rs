@@ -157,10 +157,21 @@ from!(item: &vecno_rpc_core::GetBlockTemplateRequest, protowire::GetBlockTemplat
     Self {
         pay_address: (&item.pay_address).into(),
         extra_data: String::from_utf8(item.extra_data.clone()).expect("extra data has to be valid UTF-8"),
+        long_poll_id: item.long_poll_id.clone(),
+        long_poll_timeout: item.long_poll_timeout,
+        mandatory_transaction_ids: item.mandatory_transaction_ids.iter().map(|x| x.to_string()).collect(),
+        mandatory_transactions: item.mandatory_transactions.iter().map(|x| x.into()).collect(),
+        excluded_transaction_ids: item.excluded_transaction_ids.iter().map(|x| x.to_string()).collect(),
     }
 });
 from!(item: RpcResult<&vecno_rpc_core::GetBlockTemplateResponse>, protowire::GetBlockTemplateResponseMessage, {
-    Self { block: Some((&item.block).into()), is_synced: item.is_synced, error: None }
+    Self {
+        block: Some((&item.block).into()),
+        is_synced: item.is_synced,
+        template_id: item.template_id.clone(),
+        rejected_transactions: item.rejected_transactions.iter().map(|x| x.into()).collect(),
+        error: None,
+    }
 });
 
 from!(item: &vecno_rpc_core::GetBlockRequest, protowire::GetBlockRequestMessage, {
@@ -171,7 +182,7 @@ from!(item: RpcResult<&vecno_rpc_core::GetBlockResponse>, protowire::GetBlockRes
 });
 
 from!(item: &vecno_rpc_core::NotifyBlockAddedRequest, protowire::NotifyBlockAddedRequestMessage, {
-    Self { command: item.command.into() }
+    Self { filter: Some((&item.filter).into()), header_only: item.header_only, command: item.command.into() }
 });
 from!(RpcResult<&vecno_rpc_core::NotifyBlockAddedResponse>, protowire::NotifyBlockAddedResponseMessage);
 
@@ -227,10 +238,24 @@ from!(item: RpcResult<&vecno_rpc_core::GetMempoolEntryResponse>, protowire::GetM
 });
 
 from!(item: &vecno_rpc_core::GetMempoolEntriesRequest, protowire::GetMempoolEntriesRequestMessage, {
-    Self { include_orphan_pool: item.include_orphan_pool, filter_transaction_pool: item.filter_transaction_pool }
+    Self {
+        include_orphan_pool: item.include_orphan_pool,
+        filter_transaction_pool: item.filter_transaction_pool,
+        cursor: item.cursor.clone().unwrap_or_default(),
+        limit: item.limit,
+        order: protowire::RpcMempoolEntriesOrder::from(&item.order) as i32,
+        min_feerate: item.min_feerate,
+        addresses: item.addresses.iter().map(|x| x.into()).collect(),
+        compact: item.compact,
+    }
 });
 from!(item: RpcResult<&vecno_rpc_core::GetMempoolEntriesResponse>, protowire::GetMempoolEntriesResponseMessage, {
-    Self { entries: item.mempool_entries.iter().map(|x| x.into()).collect(), error: None }
+    Self {
+        entries: item.mempool_entries.iter().map(|x| x.into()).collect(),
+        compact_entries: item.compact_entries.iter().map(|x| x.into()).collect(),
+        next_cursor: item.next_cursor.clone().unwrap_or_default(),
+        error: None,
+    }
 });
 
 from!(&vecno_rpc_core::GetConnectedPeerInfoRequest, protowire::GetConnectedPeerInfoRequestMessage);
@@ -278,6 +303,18 @@ from!(item: RpcResult<&vecno_rpc_core::GetVirtualChainFromBlockResponse>, protow
     }
 });
 
+from!(item: &vecno_rpc_core::GetVirtualChainChunkRequest, protowire::GetVirtualChainChunkRequestMessage, {
+    Self { start_hash: item.start_hash.to_string(), max_chain_blocks: item.max_chain_blocks, stream: false }
+});
+from!(item: RpcResult<&vecno_rpc_core::GetVirtualChainChunkResponse>, protowire::GetVirtualChainChunkResponseMessage, {
+    Self {
+        removed_chain_block_hashes: item.removed_chain_block_hashes.iter().map(|x| x.to_string()).collect(),
+        added_chain_blocks: item.added_chain_blocks.iter().map(|x| x.into()).collect(),
+        is_tip: item.is_tip,
+        error: None,
+    }
+});
+
 from!(item: &vecno_rpc_core::GetBlocksRequest, protowire::GetBlocksRequestMessage, {
     Self {
         low_hash: item.low_hash.map_or(Default::default(), |x| x.to_string()),
@@ -422,6 +459,17 @@ from!(item: RpcResult<&vecno_rpc_core::GetFeeEstimateExperimentalResponse>, prot
     }
 });
 
+from!(item: &vecno_rpc_core::GetFeeEstimateHistoryRequest, protowire::GetFeeEstimateHistoryRequestMessage, {
+    Self { max_blocks: item.max_blocks }
+});
+from!(item: RpcResult<&vecno_rpc_core::GetFeeEstimateHistoryResponse>, protowire::GetFeeEstimateHistoryResponseMessage, {
+    Self {
+        estimate: Some((&item.estimate).into()),
+        blocks: item.blocks.iter().map(|x| x.into()).collect(),
+        error: None,
+    }
+});
+
 from!(item: &vecno_rpc_core::GetCurrentBlockColorRequest, protowire::GetCurrentBlockColorRequestMessage, {
     Self {
         hash: item.hash.to_string()
@@ -441,6 +489,44 @@ from!(item: RpcResult<&vecno_rpc_core::GetUtxoReturnAddressResponse>, protowire:
     Self { return_address: item.return_address.address_to_string(), error: None }
 });
 
+from!(item: &vecno_rpc_core::GetTransactionRequest, protowire::GetTransactionRequestMessage, {
+    Self { transaction_id: item.transaction_id.to_string() }
+});
+from!(item: RpcResult<&vecno_rpc_core::GetTransactionResponse>, protowire::GetTransactionResponseMessage, {
+    Self {
+        transaction: Some((&item.transaction).into()),
+        block_hash: item.block_hash.to_string(),
+        accepting_block_hash: item.accepting_block_hash.to_string(),
+        accepting_block_daa_score: item.accepting_block_daa_score,
+        error: None,
+    }
+});
+
+from!(item: &vecno_rpc_core::GetTransactionStatusRequest, protowire::GetTransactionStatusRequestMessage, {
+    Self { transaction_id: item.transaction_id.to_string() }
+});
+from!(item: RpcResult<&vecno_rpc_core::GetTransactionStatusResponse>, protowire::GetTransactionStatusResponseMessage, {
+    Self { status: Some((&item.status).into()), error: None }
+});
+
+from!(item: &vecno_rpc_core::GetAddressHistoryRequest, protowire::GetAddressHistoryRequestMessage, {
+    Self { address: (&item.address).into(), cursor: item.cursor.clone().unwrap_or_default(), limit: item.limit }
+});
+from!(item: RpcResult<&vecno_rpc_core::GetAddressHistoryResponse>, protowire::GetAddressHistoryResponseMessage, {
+    Self {
+        entries: item.entries.iter().map(|x| x.into()).collect(),
+        next_cursor: item.next_cursor.clone().unwrap_or_default(),
+        error: None,
+    }
+});
+
+from!(item: &vecno_rpc_core::SubmitPackageRequest, protowire::SubmitPackageRequestMessage, {
+    Self { transactions: item.transactions.iter().map(|x| x.into()).collect() }
+});
+from!(item: RpcResult<&vecno_rpc_core::SubmitPackageResponse>, protowire::SubmitPackageResponseMessage, {
+    Self { transaction_ids: item.transaction_ids.iter().map(|x| x.to_string()).collect(), error: None }
+});
+
 from!(&vecno_rpc_core::PingRequest, protowire::PingRequestMessage);
 from!(RpcResult<&vecno_rpc_core::PingResponse>, protowire::PingResponseMessage);
 
@@ -519,7 +605,11 @@ from!(item: RpcResult<&vecno_rpc_core::GetSyncStatusResponse>, protowire::GetSyn
 });
 
 from!(item: &vecno_rpc_core::NotifyUtxosChangedRequest, protowire::NotifyUtxosChangedRequestMessage, {
-    Self { addresses: item.addresses.iter().map(|x| x.into()).collect(), command: item.command.into() }
+    Self {
+        addresses: item.addresses.iter().map(|x| x.into()).collect(),
+        resume_from: item.resume_from.as_ref().map(|x| x.into()),
+        command: item.command.into(),
+    }
 });
 from!(item: &vecno_rpc_core::NotifyUtxosChangedRequest, protowire::StopNotifyingUtxosChangedRequestMessage, {
     Self { addresses: item.addresses.iter().map(|x| x.into()).collect() }
@@ -551,7 +641,12 @@ from!(item: &vecno_rpc_core::NotifyVirtualDaaScoreChangedRequest, protowire::Not
 from!(RpcResult<&vecno_rpc_core::NotifyVirtualDaaScoreChangedResponse>, protowire::NotifyVirtualDaaScoreChangedResponseMessage);
 
 from!(item: &vecno_rpc_core::NotifyVirtualChainChangedRequest, protowire::NotifyVirtualChainChangedRequestMessage, {
-    Self { include_accepted_transaction_ids: item.include_accepted_transaction_ids, command: item.command.into() }
+    Self {
+        include_accepted_transaction_ids: item.include_accepted_transaction_ids,
+        transaction_ids: item.transaction_ids.iter().map(|x| x.to_string()).collect(),
+        resume_from: item.resume_from.as_ref().map(|x| x.into()),
+        command: item.command.into(),
+    }
 });
 from!(RpcResult<&vecno_rpc_core::NotifyVirtualChainChangedResponse>, protowire::NotifyVirtualChainChangedResponseMessage);
 
@@ -560,6 +655,11 @@ from!(item: &vecno_rpc_core::NotifySinkBlueScoreChangedRequest, protowire::Notif
 });
 from!(RpcResult<&vecno_rpc_core::NotifySinkBlueScoreChangedResponse>, protowire::NotifySinkBlueScoreChangedResponseMessage);
 
+from!(item: &vecno_rpc_core::NotifyTransactionStatusChangedRequest, protowire::NotifyTransactionStatusChangedRequestMessage, {
+    Self { transaction_ids: item.transaction_ids.iter().map(|x| x.to_string()).collect(), command: item.command.into() }
+});
+from!(RpcResult<&vecno_rpc_core::NotifyTransactionStatusChangedResponse>, protowire::NotifyTransactionStatusChangedResponseMessage);
+
 // ----------------------------------------------------------------------------
 // protowire to rpc_core
 // ----------------------------------------------------------------------------
@@ -609,7 +709,27 @@ impl TryFrom<&protowire::SubmitBlockResponseMessage> for vecno_rpc_core::SubmitB
 }
 
 try_from!(item: &protowire::GetBlockTemplateRequestMessage, vecno_rpc_core::GetBlockTemplateRequest, {
-    Self { pay_address: item.pay_address.clone().try_into()?, extra_data: RpcExtraData::from_iter(item.extra_data.bytes()) }
+    Self {
+        pay_address: item.pay_address.clone().try_into()?,
+        extra_data: RpcExtraData::from_iter(item.extra_data.bytes()),
+        long_poll_id: item.long_poll_id.clone(),
+        long_poll_timeout: item.long_poll_timeout,
+        mandatory_transaction_ids: item
+            .mandatory_transaction_ids
+            .iter()
+            .map(|x| vecno_rpc_core::RpcTransactionId::from_str(x))
+            .collect::<Result<Vec<_>, _>>()?,
+        mandatory_transactions: item
+            .mandatory_transactions
+            .iter()
+            .map(vecno_rpc_core::RpcTransaction::try_from)
+            .collect::<Result<Vec<_>, _>>()?,
+        excluded_transaction_ids: item
+            .excluded_transaction_ids
+            .iter()
+            .map(|x| vecno_rpc_core::RpcTransactionId::from_str(x))
+            .collect::<Result<Vec<_>, _>>()?,
+    }
 });
 try_from!(item: &protowire::GetBlockTemplateResponseMessage, RpcResult<vecno_rpc_core::GetBlockTemplateResponse>, {
     Self {
@@ -619,6 +739,12 @@ try_from!(item: &protowire::GetBlockTemplateResponseMessage, RpcResult<vecno_rpc
             .ok_or_else(|| RpcError::MissingRpcFieldError("GetBlockTemplateResponseMessage".to_string(), "block".to_string()))?
             .try_into()?,
         is_synced: item.is_synced,
+        template_id: item.template_id.clone(),
+        rejected_transactions: item
+            .rejected_transactions
+            .iter()
+            .map(vecno_rpc_core::RpcRejectedTransaction::try_from)
+            .collect::<Result<Vec<_>, _>>()?,
     }
 });
 
@@ -636,7 +762,11 @@ try_from!(item: &protowire::GetBlockResponseMessage, RpcResult<vecno_rpc_core::G
 });
 
 try_from!(item: &protowire::NotifyBlockAddedRequestMessage, vecno_rpc_core::NotifyBlockAddedRequest, {
-    Self { command: item.command.into() }
+    Self {
+        filter: item.filter.as_ref().map(vecno_rpc_core::RpcTransactionFilter::try_from).transpose()?.unwrap_or_default(),
+        header_only: item.header_only,
+        command: item.command.into(),
+    }
 });
 try_from!(&protowire::NotifyBlockAddedResponseMessage, RpcResult<vecno_rpc_core::NotifyBlockAddedResponse>);
 
@@ -698,10 +828,23 @@ try_from!(item: &protowire::GetMempoolEntryResponseMessage, RpcResult<vecno_rpc_
 });
 
 try_from!(item: &protowire::GetMempoolEntriesRequestMessage, vecno_rpc_core::GetMempoolEntriesRequest, {
-    Self { include_orphan_pool: item.include_orphan_pool, filter_transaction_pool: item.filter_transaction_pool }
+    Self {
+        include_orphan_pool: item.include_orphan_pool,
+        filter_transaction_pool: item.filter_transaction_pool,
+        cursor: if item.cursor.is_empty() { None } else { Some(item.cursor.clone()) },
+        limit: item.limit,
+        order: protowire::RpcMempoolEntriesOrder::try_from(item.order).map_err(|_| RpcError::PrimitiveToEnumConversionError)?.into(),
+        min_feerate: item.min_feerate,
+        addresses: item.addresses.iter().map(|x| x.as_str().try_into()).collect::<Result<Vec<_>, _>>()?,
+        compact: item.compact,
+    }
 });
 try_from!(item: &protowire::GetMempoolEntriesResponseMessage, RpcResult<vecno_rpc_core::GetMempoolEntriesResponse>, {
-    Self { mempool_entries: item.entries.iter().map(vecno_rpc_core::RpcMempoolEntry::try_from).collect::<Result<Vec<_>, _>>()? }
+    Self {
+        mempool_entries: item.entries.iter().map(vecno_rpc_core::RpcMempoolEntry::try_from).collect::<Result<Vec<_>, _>>()?,
+        compact_entries: item.compact_entries.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        next_cursor: if item.next_cursor.is_empty() { None } else { Some(item.next_cursor.clone()) },
+    }
 });
 
 try_from!(&protowire::GetConnectedPeerInfoRequestMessage, vecno_rpc_core::GetConnectedPeerInfoRequest);
@@ -770,6 +913,21 @@ try_from!(item: &protowire::GetVirtualChainFromBlockResponseMessage, RpcResult<v
     }
 });
 
+try_from!(item: &protowire::GetVirtualChainChunkRequestMessage, vecno_rpc_core::GetVirtualChainChunkRequest, {
+    Self { start_hash: RpcHash::from_str(&item.start_hash)?, max_chain_blocks: item.max_chain_blocks }
+});
+try_from!(item: &protowire::GetVirtualChainChunkResponseMessage, RpcResult<vecno_rpc_core::GetVirtualChainChunkResponse>, {
+    Self {
+        removed_chain_block_hashes: item
+            .removed_chain_block_hashes
+            .iter()
+            .map(|x| RpcHash::from_str(x))
+            .collect::<Result<Vec<_>, _>>()?,
+        added_chain_blocks: item.added_chain_blocks.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        is_tip: item.is_tip,
+    }
+});
+
 try_from!(item: &protowire::GetBlocksRequestMessage, vecno_rpc_core::GetBlocksRequest, {
     Self {
         low_hash: if item.low_hash.is_empty() { None } else { Some(RpcHash::from_str(&item.low_hash)?) },
@@ -916,6 +1074,19 @@ try_from!(item: &protowire::GetFeeEstimateExperimentalResponseMessage, RpcResult
     }
 });
 
+try_from!(item: &protowire::GetFeeEstimateHistoryRequestMessage, vecno_rpc_core::GetFeeEstimateHistoryRequest, {
+    Self { max_blocks: item.max_blocks }
+});
+try_from!(item: &protowire::GetFeeEstimateHistoryResponseMessage, RpcResult<vecno_rpc_core::GetFeeEstimateHistoryResponse>, {
+    Self {
+        estimate: item.estimate
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("GetFeeEstimateHistoryResponseMessage".to_string(), "estimate".to_string()))?
+            .try_into()?,
+        blocks: item.blocks.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+    }
+});
+
 try_from!(item: &protowire::GetCurrentBlockColorRequestMessage, vecno_rpc_core::GetCurrentBlockColorRequest, {
     Self {
         hash: RpcHash::from_str(&item.hash)?
@@ -936,6 +1107,62 @@ try_from!(item: &protowire::GetUtxoReturnAddressResponseMessage, RpcResult<vecno
     Self { return_address: Address::try_from(item.return_address.clone())? }
 });
 
+try_from!(item: &protowire::GetTransactionRequestMessage, vecno_rpc_core::GetTransactionRequest, {
+    Self { transaction_id: RpcHash::from_str(&item.transaction_id)? }
+});
+try_from!(item: &protowire::GetTransactionResponseMessage, RpcResult<vecno_rpc_core::GetTransactionResponse>, {
+    Self {
+        transaction: item
+            .transaction
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("GetTransactionResponseMessage".to_string(), "transaction".to_string()))?
+            .try_into()?,
+        block_hash: RpcHash::from_str(&item.block_hash)?,
+        accepting_block_hash: RpcHash::from_str(&item.accepting_block_hash)?,
+        accepting_block_daa_score: item.accepting_block_daa_score,
+    }
+});
+
+try_from!(item: &protowire::GetTransactionStatusRequestMessage, vecno_rpc_core::GetTransactionStatusRequest, {
+    Self { transaction_id: RpcHash::from_str(&item.transaction_id)? }
+});
+try_from!(item: &protowire::GetTransactionStatusResponseMessage, RpcResult<vecno_rpc_core::GetTransactionStatusResponse>, {
+    Self {
+        status: item
+            .status
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("GetTransactionStatusResponseMessage".to_string(), "status".to_string()))?
+            .try_into()?,
+    }
+});
+
+try_from!(item: &protowire::GetAddressHistoryRequestMessage, vecno_rpc_core::GetAddressHistoryRequest, {
+    Self {
+        address: item.address.as_str().try_into()?,
+        cursor: if item.cursor.is_empty() { None } else { Some(item.cursor.clone()) },
+        limit: item.limit,
+    }
+});
+try_from!(item: &protowire::GetAddressHistoryResponseMessage, RpcResult<vecno_rpc_core::GetAddressHistoryResponse>, {
+    Self {
+        entries: item.entries.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        next_cursor: if item.next_cursor.is_empty() { None } else { Some(item.next_cursor.clone()) },
+    }
+});
+
+try_from!(item: &protowire::SubmitPackageRequestMessage, vecno_rpc_core::SubmitPackageRequest, {
+    Self { transactions: item.transactions.iter().map(vecno_rpc_core::RpcTransaction::try_from).collect::<Result<Vec<_>, _>>()? }
+});
+try_from!(item: &protowire::SubmitPackageResponseMessage, RpcResult<vecno_rpc_core::SubmitPackageResponse>, {
+    Self {
+        transaction_ids: item
+            .transaction_ids
+            .iter()
+            .map(|x| vecno_rpc_core::RpcTransactionId::from_str(x))
+            .collect::<Result<Vec<_>, _>>()?,
+    }
+});
+
 try_from!(&protowire::PingRequestMessage, vecno_rpc_core::PingRequest);
 try_from!(&protowire::PingResponseMessage, RpcResult<vecno_rpc_core::PingResponse>);
 
@@ -1009,12 +1236,14 @@ try_from!(item: &protowire::GetSyncStatusResponseMessage, RpcResult<vecno_rpc_co
 try_from!(item: &protowire::NotifyUtxosChangedRequestMessage, vecno_rpc_core::NotifyUtxosChangedRequest, {
     Self {
         addresses: item.addresses.iter().map(|x| x.as_str().try_into()).collect::<Result<Vec<_>, _>>()?,
+        resume_from: item.resume_from.as_ref().map(vecno_rpc_core::RpcResumeToken::try_from).transpose()?,
         command: item.command.into(),
     }
 });
 try_from!(item: &protowire::StopNotifyingUtxosChangedRequestMessage, vecno_rpc_core::NotifyUtxosChangedRequest, {
     Self {
         addresses: item.addresses.iter().map(|x| x.as_str().try_into()).collect::<Result<Vec<_>, _>>()?,
+        resume_from: None,
         command: Command::Stop,
     }
 });
@@ -1051,7 +1280,12 @@ try_from!(item: &protowire::NotifyVirtualDaaScoreChangedRequestMessage, vecno_rp
 try_from!(&protowire::NotifyVirtualDaaScoreChangedResponseMessage, RpcResult<vecno_rpc_core::NotifyVirtualDaaScoreChangedResponse>);
 
 try_from!(item: &protowire::NotifyVirtualChainChangedRequestMessage, vecno_rpc_core::NotifyVirtualChainChangedRequest, {
-    Self { include_accepted_transaction_ids: item.include_accepted_transaction_ids, command: item.command.into() }
+    Self {
+        include_accepted_transaction_ids: item.include_accepted_transaction_ids,
+        transaction_ids: item.transaction_ids.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()?,
+        resume_from: item.resume_from.as_ref().map(vecno_rpc_core::RpcResumeToken::try_from).transpose()?,
+        command: item.command.into(),
+    }
 });
 try_from!(&protowire::NotifyVirtualChainChangedResponseMessage, RpcResult<vecno_rpc_core::NotifyVirtualChainChangedResponse>);
 
@@ -1060,6 +1294,17 @@ try_from!(item: &protowire::NotifySinkBlueScoreChangedRequestMessage, vecno_rpc_
 });
 try_from!(&protowire::NotifySinkBlueScoreChangedResponseMessage, RpcResult<vecno_rpc_core::NotifySinkBlueScoreChangedResponse>);
 
+try_from!(item: &protowire::NotifyTransactionStatusChangedRequestMessage, vecno_rpc_core::NotifyTransactionStatusChangedRequest, {
+    Self {
+        transaction_ids: item.transaction_ids.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()?,
+        command: item.command.into(),
+    }
+});
+try_from!(
+    &protowire::NotifyTransactionStatusChangedResponseMessage,
+    RpcResult<vecno_rpc_core::NotifyTransactionStatusChangedResponse>
+);
+
 // ----------------------------------------------------------------------------
 // Unit tests
 // ----------------------------------------------------------------------------
@@ -37,6 +37,8 @@ from!(item: &vecno_rpc_core::ConnectionMetrics, protowire::ConnectionMetrics, {
         json_connection_attempts: item.json_connection_attempts,
         json_handshake_failures: item.json_handshake_failures,
         active_peers: item.active_peers,
+        connection_rate_limited_requests: item.connection_rate_limited_requests,
+        ip_rate_limited_requests: item.ip_rate_limited_requests,
     }
 });
 
@@ -111,6 +113,8 @@ try_from!(item: &protowire::ConnectionMetrics, vecno_rpc_core::ConnectionMetrics
         json_connection_attempts: item.json_connection_attempts,
         json_handshake_failures: item.json_handshake_failures,
         active_peers: item.active_peers,
+        connection_rate_limited_requests: item.connection_rate_limited_requests,
+        ip_rate_limited_requests: item.ip_rate_limited_requests,
     }
 });
 
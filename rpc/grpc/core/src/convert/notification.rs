@@ -6,8 +6,8 @@ use crate::protowire::{
     NotifyPruningPointUtxoSetOverrideResponseMessage, NotifyUtxosChangedRequestMessage, NotifyUtxosChangedResponseMessage,
     PruningPointUtxoSetOverrideNotificationMessage, SinkBlueScoreChangedNotificationMessage,
     StopNotifyingPruningPointUtxoSetOverrideRequestMessage, StopNotifyingPruningPointUtxoSetOverrideResponseMessage,
-    StopNotifyingUtxosChangedRequestMessage, StopNotifyingUtxosChangedResponseMessage, UtxosChangedNotificationMessage,
-    VirtualChainChangedNotificationMessage, VirtualDaaScoreChangedNotificationMessage,
+    StopNotifyingUtxosChangedRequestMessage, StopNotifyingUtxosChangedResponseMessage, TransactionStatusChangedNotificationMessage,
+    UtxosChangedNotificationMessage, VirtualChainChangedNotificationMessage, VirtualDaaScoreChangedNotificationMessage,
 };
 use crate::{from, try_from};
 use vecno_notify::subscription::Command;
@@ -34,6 +34,7 @@ from!(item: &vecno_rpc_core::Notification, Payload, {
         Notification::PruningPointUtxoSetOverride(ref notification) => {
             Payload::PruningPointUtxoSetOverrideNotification(notification.into())
         }
+        Notification::TransactionStatusChanged(ref notification) => Payload::TransactionStatusChangedNotification(notification.into()),
     }
 });
 
@@ -74,6 +75,10 @@ from!(item: &vecno_rpc_core::VirtualDaaScoreChangedNotification, VirtualDaaScore
 
 from!(&vecno_rpc_core::PruningPointUtxoSetOverrideNotification, PruningPointUtxoSetOverrideNotificationMessage);
 
+from!(item: &vecno_rpc_core::TransactionStatusChangedNotification, TransactionStatusChangedNotificationMessage, {
+    Self { status: Some((&item.status).into()) }
+});
+
 from!(item: Command, RpcNotifyCommand, {
     match item {
         Command::Start => RpcNotifyCommand::NotifyStart,
@@ -82,7 +87,7 @@ from!(item: Command, RpcNotifyCommand, {
 });
 
 from!(item: &StopNotifyingUtxosChangedRequestMessage, NotifyUtxosChangedRequestMessage, {
-    Self { addresses: item.addresses.clone(), command: Command::Stop.into() }
+    Self { addresses: item.addresses.clone(), resume_from: None, command: Command::Stop.into() }
 });
 
 from!(_item: &StopNotifyingPruningPointUtxoSetOverrideRequestMessage, NotifyPruningPointUtxoSetOverrideRequestMessage, {
@@ -117,6 +122,9 @@ try_from!(item: &Payload, vecno_rpc_core::Notification, {
         Payload::PruningPointUtxoSetOverrideNotification(ref notification) => {
             Notification::PruningPointUtxoSetOverride(notification.try_into()?)
         }
+        Payload::TransactionStatusChangedNotification(ref notification) => {
+            Notification::TransactionStatusChanged(notification.try_into()?)
+        }
         _ => Err(RpcError::UnsupportedFeature)?,
     }
 });
@@ -171,6 +179,16 @@ try_from!(item: &VirtualDaaScoreChangedNotificationMessage, vecno_rpc_core::Virt
 
 try_from!(&PruningPointUtxoSetOverrideNotificationMessage, vecno_rpc_core::PruningPointUtxoSetOverrideNotification);
 
+try_from!(item: &TransactionStatusChangedNotificationMessage, vecno_rpc_core::TransactionStatusChangedNotification, {
+    Self {
+        status: item
+            .status
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("TransactionStatusChangedNotificationMessage".to_string(), "status".to_string()))?
+            .try_into()?,
+    }
+});
+
 from!(item: RpcNotifyCommand, Command, {
     match item {
         RpcNotifyCommand::NotifyStart => Command::Start,
@@ -1,4 +1,4 @@
-use crate::protowire;
+use crate::protowire::{self, rpc_address_history_entry::Kind, rpc_resume_token::Token};
 use crate::{from, try_from};
 use vecno_rpc_core::{FromRpcHex, RpcError, RpcHash, RpcResult, RpcScriptVec, ToRpcHex};
 use std::str::FromStr;
@@ -82,6 +82,26 @@ from!(item: &vecno_rpc_core::RpcAcceptedTransactionIds, protowire::RpcAcceptedTr
     }
 });
 
+from!(item: &vecno_rpc_core::RpcAcceptedTransaction, protowire::RpcAcceptedTransaction, {
+    Self {
+        transaction_id: item.transaction_id.to_string(),
+        transaction: Some((&item.transaction).into()),
+        input_utxo_entries: item.input_utxo_entries.iter().map(|x| x.into()).collect(),
+    }
+});
+
+from!(item: &vecno_rpc_core::RpcAcceptingChainBlock, protowire::RpcAcceptingChainBlock, {
+    Self {
+        hash: item.hash.to_string(),
+        daa_score: item.daa_score,
+        accepted_transactions: item.accepted_transactions.iter().map(|x| x.into()).collect(),
+    }
+});
+
+from!(item: &vecno_rpc_core::RpcRejectedTransaction, protowire::RpcRejectedTransaction, {
+    Self { transaction_id: item.transaction_id.to_string(), reason: item.reason.clone() }
+});
+
 from!(item: &vecno_rpc_core::RpcUtxosByAddressesEntry, protowire::RpcUtxosByAddressesEntry, {
     Self {
         address: item.address.as_ref().map_or("".to_string(), |x| x.into()),
@@ -90,6 +110,64 @@ from!(item: &vecno_rpc_core::RpcUtxosByAddressesEntry, protowire::RpcUtxosByAddr
     }
 });
 
+from!(item: &vecno_rpc_core::RpcAddressActivityKind, Kind, {
+    match item {
+        vecno_rpc_core::RpcAddressActivityKind::Received => Kind::Received,
+        vecno_rpc_core::RpcAddressActivityKind::Spent => Kind::Spent,
+    }
+});
+
+from!(item: &vecno_rpc_core::RpcAddressHistoryEntry, protowire::RpcAddressHistoryEntry, {
+    Self {
+        transaction_id: item.transaction_id.to_string(),
+        kind: Kind::from(&item.kind) as i32,
+        outpoint: Some((&item.outpoint).into()),
+        amount: item.amount,
+        accepting_block_hash: item.accepting_block_hash.to_string(),
+        accepting_block_daa_score: item.accepting_block_daa_score,
+    }
+});
+
+from!(item: &vecno_rpc_core::RpcTransactionFilter, protowire::RpcTransactionFilter, {
+    Self {
+        addresses: item.addresses.iter().map(|x| x.into()).collect(),
+        payload_prefix: item.payload_prefix.to_rpc_hex(),
+        transaction_ids: item.transaction_ids.iter().map(|x| x.to_string()).collect(),
+    }
+});
+
+from!(item: &vecno_rpc_core::RpcResumeToken, protowire::RpcResumeToken, {
+    let token = match item {
+        vecno_rpc_core::RpcResumeToken::SinkHash(hash) => Token::SinkHash(hash.to_string()),
+        vecno_rpc_core::RpcResumeToken::DaaScore(daa_score) => Token::DaaScore(*daa_score),
+    };
+    Self { token: Some(token) }
+});
+
+from!(item: &vecno_rpc_core::RpcTransactionState, protowire::RpcTransactionState, {
+    match item {
+        vecno_rpc_core::RpcTransactionState::Unknown => protowire::RpcTransactionState::Unknown,
+        vecno_rpc_core::RpcTransactionState::InMempool => protowire::RpcTransactionState::InMempool,
+        vecno_rpc_core::RpcTransactionState::Orphan => protowire::RpcTransactionState::Orphan,
+        vecno_rpc_core::RpcTransactionState::Included => protowire::RpcTransactionState::Included,
+        vecno_rpc_core::RpcTransactionState::Accepted => protowire::RpcTransactionState::Accepted,
+        vecno_rpc_core::RpcTransactionState::Rejected => protowire::RpcTransactionState::Rejected,
+        vecno_rpc_core::RpcTransactionState::Evicted => protowire::RpcTransactionState::Evicted,
+    }
+});
+
+from!(item: &vecno_rpc_core::RpcTransactionStatus, protowire::RpcTransactionStatus, {
+    Self {
+        transaction_id: item.transaction_id.to_string(),
+        state: protowire::RpcTransactionState::from(&item.state) as i32,
+        reason: item.reason.clone(),
+        including_block_daa_score: item.including_block_daa_score,
+        accepting_block_hash: item.accepting_block_hash.map(|x| x.to_string()),
+        accepting_block_daa_score: item.accepting_block_daa_score,
+        confirmations: item.confirmations,
+    }
+});
+
 // ----------------------------------------------------------------------------
 // protowire to rpc_core
 // ----------------------------------------------------------------------------
@@ -116,6 +194,21 @@ try_from!(item: &protowire::RpcTransaction, vecno_rpc_core::RpcTransaction, {
     }
 });
 
+try_from!(item: &protowire::RpcTransactionFilter, vecno_rpc_core::RpcTransactionFilter, {
+    Self {
+        addresses: item.addresses.iter().map(|x| x.as_str().try_into()).collect::<Result<Vec<_>, _>>()?,
+        payload_prefix: Vec::from_rpc_hex(&item.payload_prefix)?,
+        transaction_ids: item.transaction_ids.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()?,
+    }
+});
+
+try_from!(item: &protowire::RpcResumeToken, vecno_rpc_core::RpcResumeToken, {
+    match item.token.as_ref().ok_or_else(|| RpcError::MissingRpcFieldError("RpcResumeToken".to_string(), "token".to_string()))? {
+        Token::SinkHash(hash) => Self::SinkHash(RpcHash::from_str(hash)?),
+        Token::DaaScore(daa_score) => Self::DaaScore(*daa_score),
+    }
+});
+
 try_from!(item: &protowire::RpcTransactionInput, vecno_rpc_core::RpcTransactionInput, {
     Self {
         previous_outpoint: item
@@ -189,6 +282,30 @@ try_from!(item: &protowire::RpcAcceptedTransactionIds, vecno_rpc_core::RpcAccept
     }
 });
 
+try_from!(item: &protowire::RpcAcceptedTransaction, vecno_rpc_core::RpcAcceptedTransaction, {
+    Self {
+        transaction_id: RpcHash::from_str(&item.transaction_id)?,
+        transaction: item
+            .transaction
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("RpcAcceptedTransaction".to_string(), "transaction".to_string()))?
+            .try_into()?,
+        input_utxo_entries: item.input_utxo_entries.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+    }
+});
+
+try_from!(item: &protowire::RpcAcceptingChainBlock, vecno_rpc_core::RpcAcceptingChainBlock, {
+    Self {
+        hash: RpcHash::from_str(&item.hash)?,
+        daa_score: item.daa_score,
+        accepted_transactions: item.accepted_transactions.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+    }
+});
+
+try_from!(item: &protowire::RpcRejectedTransaction, vecno_rpc_core::RpcRejectedTransaction, {
+    Self { transaction_id: RpcHash::from_str(&item.transaction_id)?, reason: item.reason.clone() }
+});
+
 try_from!(item: &protowire::RpcUtxosByAddressesEntry, vecno_rpc_core::RpcUtxosByAddressesEntry, {
     let address = if item.address.is_empty() { None } else { Some(item.address.as_str().try_into()?) };
     Self {
@@ -205,3 +322,49 @@ try_from!(item: &protowire::RpcUtxosByAddressesEntry, vecno_rpc_core::RpcUtxosBy
             .try_into()?,
     }
 });
+
+from!(item: Kind, vecno_rpc_core::RpcAddressActivityKind, {
+    match item {
+        Kind::Received => vecno_rpc_core::RpcAddressActivityKind::Received,
+        Kind::Spent => vecno_rpc_core::RpcAddressActivityKind::Spent,
+    }
+});
+
+try_from!(item: &protowire::RpcAddressHistoryEntry, vecno_rpc_core::RpcAddressHistoryEntry, {
+    Self {
+        transaction_id: RpcHash::from_str(&item.transaction_id)?,
+        kind: Kind::try_from(item.kind).map_err(|_| RpcError::PrimitiveToEnumConversionError)?.into(),
+        outpoint: item
+            .outpoint
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("RpcAddressHistoryEntry".to_string(), "outpoint".to_string()))?
+            .try_into()?,
+        amount: item.amount,
+        accepting_block_hash: RpcHash::from_str(&item.accepting_block_hash)?,
+        accepting_block_daa_score: item.accepting_block_daa_score,
+    }
+});
+
+from!(item: protowire::RpcTransactionState, vecno_rpc_core::RpcTransactionState, {
+    match item {
+        protowire::RpcTransactionState::Unknown => vecno_rpc_core::RpcTransactionState::Unknown,
+        protowire::RpcTransactionState::InMempool => vecno_rpc_core::RpcTransactionState::InMempool,
+        protowire::RpcTransactionState::Orphan => vecno_rpc_core::RpcTransactionState::Orphan,
+        protowire::RpcTransactionState::Included => vecno_rpc_core::RpcTransactionState::Included,
+        protowire::RpcTransactionState::Accepted => vecno_rpc_core::RpcTransactionState::Accepted,
+        protowire::RpcTransactionState::Rejected => vecno_rpc_core::RpcTransactionState::Rejected,
+        protowire::RpcTransactionState::Evicted => vecno_rpc_core::RpcTransactionState::Evicted,
+    }
+});
+
+try_from!(item: &protowire::RpcTransactionStatus, vecno_rpc_core::RpcTransactionStatus, {
+    Self {
+        transaction_id: RpcHash::from_str(&item.transaction_id)?,
+        state: protowire::RpcTransactionState::try_from(item.state).map_err(|_| RpcError::PrimitiveToEnumConversionError)?.into(),
+        reason: item.reason.clone(),
+        including_block_daa_score: item.including_block_daa_score,
+        accepting_block_hash: item.accepting_block_hash.as_deref().map(RpcHash::from_str).transpose()?,
+        accepting_block_daa_score: item.accepting_block_daa_score,
+        confirmations: item.confirmations,
+    }
+});
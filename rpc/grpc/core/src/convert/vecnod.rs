@@ -64,6 +64,12 @@ pub mod vecnod_request_convert {
     impl_into_vecnod_request!(GetFeeEstimateExperimental);
     impl_into_vecnod_request!(GetCurrentBlockColor);
     impl_into_vecnod_request!(GetUtxoReturnAddress);
+    impl_into_vecnod_request!(GetTransaction);
+    impl_into_vecnod_request!(GetAddressHistory);
+    impl_into_vecnod_request!(SubmitPackage);
+    impl_into_vecnod_request!(GetVirtualChainChunk);
+    impl_into_vecnod_request!(GetFeeEstimateHistory);
+    impl_into_vecnod_request!(GetTransactionStatus);
 
     impl_into_vecnod_request!(NotifyBlockAdded);
     impl_into_vecnod_request!(NotifyNewBlockTemplate);
@@ -73,6 +79,7 @@ pub mod vecnod_request_convert {
     impl_into_vecnod_request!(NotifyVirtualDaaScoreChanged);
     impl_into_vecnod_request!(NotifyVirtualChainChanged);
     impl_into_vecnod_request!(NotifySinkBlueScoreChanged);
+    impl_into_vecnod_request!(NotifyTransactionStatusChanged);
 
     macro_rules! impl_into_vecnod_request {
         ($name:tt) => {
@@ -202,6 +209,12 @@ pub mod vecnod_response_convert {
     impl_into_vecnod_response!(GetFeeEstimateExperimental);
     impl_into_vecnod_response!(GetCurrentBlockColor);
     impl_into_vecnod_response!(GetUtxoReturnAddress);
+    impl_into_vecnod_response!(GetTransaction);
+    impl_into_vecnod_response!(GetAddressHistory);
+    impl_into_vecnod_response!(SubmitPackage);
+    impl_into_vecnod_response!(GetVirtualChainChunk);
+    impl_into_vecnod_response!(GetFeeEstimateHistory);
+    impl_into_vecnod_response!(GetTransactionStatus);
 
     impl_into_vecnod_notify_response!(NotifyBlockAdded);
     impl_into_vecnod_notify_response!(NotifyNewBlockTemplate);
@@ -211,6 +224,7 @@ pub mod vecnod_response_convert {
     impl_into_vecnod_notify_response!(NotifyVirtualDaaScoreChanged);
     impl_into_vecnod_notify_response!(NotifyVirtualChainChanged);
     impl_into_vecnod_notify_response!(NotifySinkBlueScoreChanged);
+    impl_into_vecnod_notify_response!(NotifyTransactionStatusChanged);
 
     impl_into_vecnod_notify_response!(NotifyUtxosChanged, StopNotifyingUtxosChanged);
     impl_into_vecnod_notify_response!(NotifyPruningPointUtxoSetOverride, StopNotifyingPruningPointUtxoSetOverride);
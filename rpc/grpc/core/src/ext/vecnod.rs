@@ -3,8 +3,8 @@ use vecno_notify::{scope::Scope, subscription::Command};
 use crate::protowire::{
     vecnod_request, vecnod_response, VecnodRequest, VecnodResponse, NotifyBlockAddedRequestMessage,
     NotifyFinalityConflictRequestMessage, NotifyNewBlockTemplateRequestMessage, NotifyPruningPointUtxoSetOverrideRequestMessage,
-    NotifySinkBlueScoreChangedRequestMessage, NotifyUtxosChangedRequestMessage, NotifyVirtualChainChangedRequestMessage,
-    NotifyVirtualDaaScoreChangedRequestMessage,
+    NotifySinkBlueScoreChangedRequestMessage, NotifyTransactionStatusChangedRequestMessage, NotifyUtxosChangedRequestMessage,
+    NotifyVirtualChainChangedRequestMessage, NotifyVirtualDaaScoreChangedRequestMessage,
 };
 
 impl VecnodRequest {
@@ -20,9 +20,11 @@ impl VecnodRequest {
 impl vecnod_request::Payload {
     pub fn from_notification_type(scope: &Scope, command: Command) -> Self {
         match scope {
-            Scope::BlockAdded(_) => {
-                vecnod_request::Payload::NotifyBlockAddedRequest(NotifyBlockAddedRequestMessage { command: command.into() })
-            }
+            Scope::BlockAdded(ref scope) => vecnod_request::Payload::NotifyBlockAddedRequest(NotifyBlockAddedRequestMessage {
+                filter: Some((&scope.filter).into()),
+                header_only: scope.header_only,
+                command: command.into(),
+            }),
             Scope::NewBlockTemplate(_) => {
                 vecnod_request::Payload::NotifyNewBlockTemplateRequest(NotifyNewBlockTemplateRequestMessage {
                     command: command.into(),
@@ -33,6 +35,8 @@ impl vecnod_request::Payload {
                 vecnod_request::Payload::NotifyVirtualChainChangedRequest(NotifyVirtualChainChangedRequestMessage {
                     command: command.into(),
                     include_accepted_transaction_ids: scope.include_accepted_transaction_ids,
+                    transaction_ids: scope.transaction_ids.iter().map(|x| x.to_string()).collect(),
+                    resume_from: scope.resume_from.as_ref().map(|x| x.into()),
                 })
             }
             Scope::FinalityConflict(_) => {
@@ -47,6 +51,7 @@ impl vecnod_request::Payload {
             }
             Scope::UtxosChanged(ref scope) => vecnod_request::Payload::NotifyUtxosChangedRequest(NotifyUtxosChangedRequestMessage {
                 addresses: scope.addresses.iter().map(|x| x.into()).collect::<Vec<String>>(),
+                resume_from: scope.resume_from.as_ref().map(|x| x.into()),
                 command: command.into(),
             }),
             Scope::SinkBlueScoreChanged(_) => {
@@ -64,6 +69,12 @@ impl vecnod_request::Payload {
                     command: command.into(),
                 })
             }
+            Scope::TransactionStatusChanged(ref scope) => {
+                vecnod_request::Payload::NotifyTransactionStatusChangedRequest(NotifyTransactionStatusChangedRequestMessage {
+                    transaction_ids: scope.transaction_ids.iter().map(|x| x.to_string()).collect(),
+                    command: command.into(),
+                })
+            }
         }
     }
 
@@ -79,6 +90,7 @@ impl vecnod_request::Payload {
                 | Payload::NotifyVirtualDaaScoreChangedRequest(_)
                 | Payload::NotifyPruningPointUtxoSetOverrideRequest(_)
                 | Payload::NotifyNewBlockTemplateRequest(_)
+                | Payload::NotifyTransactionStatusChangedRequest(_)
                 | Payload::StopNotifyingUtxosChangedRequest(_)
                 | Payload::StopNotifyingPruningPointUtxoSetOverrideRequest(_)
         )
@@ -92,6 +104,14 @@ impl VecnodResponse {
             None => false,
         }
     }
+
+    /// Returns true if no further response follows this one for the same streamed request
+    pub fn is_stream_end(&self) -> bool {
+        match self.payload {
+            Some(vecnod_response::Payload::GetVirtualChainChunkResponse(ref response)) => response.is_tip || response.error.is_some(),
+            _ => true,
+        }
+    }
 }
 
 #[allow(clippy::match_like_matches_macro)]
@@ -108,6 +128,7 @@ impl vecnod_response::Payload {
             Payload::VirtualDaaScoreChangedNotification(_) => true,
             Payload::PruningPointUtxoSetOverrideNotification(_) => true,
             Payload::NewBlockTemplateNotification(_) => true,
+            Payload::TransactionStatusChangedNotification(_) => true,
             _ => false,
         }
     }
@@ -1,5 +1,5 @@
 use crate::protowire::{vecnod_request::Payload as RequestPayload, vecnod_response::Payload as ResponsePayload, *};
-use vecno_rpc_core::RpcError;
+use vecno_rpc_core::{api::ops::RpcApiOps, RpcError};
 use workflow_core::enums::Describe;
 
 macro_rules! payload_type_enum {
@@ -88,6 +88,12 @@ pub enum VecnodPayloadOps {
     GetFeeEstimateExperimental,
     GetCurrentBlockColor,
     GetUtxoReturnAddress,
+    GetTransaction,
+    GetAddressHistory,
+    SubmitPackage,
+    GetVirtualChainChunk,
+    GetFeeEstimateHistory,
+    GetTransactionStatus,
 
     // Subscription commands for starting/stopping notifications
     NotifyBlockAdded,
@@ -98,6 +104,7 @@ pub enum VecnodPayloadOps {
     NotifyPruningPointUtxoSetOverride,
     NotifyVirtualDaaScoreChanged,
     NotifyVirtualChainChanged,
+    NotifyTransactionStatusChanged,
 
     // Legacy stop subscription commands
     StopNotifyingUtxosChanged,
@@ -108,3 +115,69 @@ pub enum VecnodPayloadOps {
     // The conversion from a notification ResponsePayload into VecnodPayloadOps fails.
 }
 }
+
+// The legacy stop subscription commands map to their subscription command
+impl From<VecnodPayloadOps> for RpcApiOps {
+    fn from(item: VecnodPayloadOps) -> Self {
+        match item {
+            VecnodPayloadOps::SubmitBlock => RpcApiOps::SubmitBlock,
+            VecnodPayloadOps::GetBlockTemplate => RpcApiOps::GetBlockTemplate,
+            VecnodPayloadOps::GetCurrentNetwork => RpcApiOps::GetCurrentNetwork,
+            VecnodPayloadOps::GetBlock => RpcApiOps::GetBlock,
+            VecnodPayloadOps::GetBlocks => RpcApiOps::GetBlocks,
+            VecnodPayloadOps::GetInfo => RpcApiOps::GetInfo,
+            VecnodPayloadOps::Shutdown => RpcApiOps::Shutdown,
+            VecnodPayloadOps::GetPeerAddresses => RpcApiOps::GetPeerAddresses,
+            VecnodPayloadOps::GetSink => RpcApiOps::GetSink,
+            VecnodPayloadOps::GetMempoolEntry => RpcApiOps::GetMempoolEntry,
+            VecnodPayloadOps::GetMempoolEntries => RpcApiOps::GetMempoolEntries,
+            VecnodPayloadOps::GetConnectedPeerInfo => RpcApiOps::GetConnectedPeerInfo,
+            VecnodPayloadOps::AddPeer => RpcApiOps::AddPeer,
+            VecnodPayloadOps::SubmitTransaction => RpcApiOps::SubmitTransaction,
+            VecnodPayloadOps::SubmitTransactionReplacement => RpcApiOps::SubmitTransactionReplacement,
+            VecnodPayloadOps::GetSubnetwork => RpcApiOps::GetSubnetwork,
+            VecnodPayloadOps::GetVirtualChainFromBlock => RpcApiOps::GetVirtualChainFromBlock,
+            VecnodPayloadOps::GetBlockCount => RpcApiOps::GetBlockCount,
+            VecnodPayloadOps::GetBlockDagInfo => RpcApiOps::GetBlockDagInfo,
+            VecnodPayloadOps::ResolveFinalityConflict => RpcApiOps::ResolveFinalityConflict,
+            VecnodPayloadOps::GetHeaders => RpcApiOps::GetHeaders,
+            VecnodPayloadOps::GetUtxosByAddresses => RpcApiOps::GetUtxosByAddresses,
+            VecnodPayloadOps::GetBalanceByAddress => RpcApiOps::GetBalanceByAddress,
+            VecnodPayloadOps::GetBalancesByAddresses => RpcApiOps::GetBalancesByAddresses,
+            VecnodPayloadOps::GetSinkBlueScore => RpcApiOps::GetSinkBlueScore,
+            VecnodPayloadOps::Ban => RpcApiOps::Ban,
+            VecnodPayloadOps::Unban => RpcApiOps::Unban,
+            VecnodPayloadOps::EstimateNetworkHashesPerSecond => RpcApiOps::EstimateNetworkHashesPerSecond,
+            VecnodPayloadOps::GetMempoolEntriesByAddresses => RpcApiOps::GetMempoolEntriesByAddresses,
+            VecnodPayloadOps::GetCoinSupply => RpcApiOps::GetCoinSupply,
+            VecnodPayloadOps::Ping => RpcApiOps::Ping,
+            VecnodPayloadOps::GetMetrics => RpcApiOps::GetMetrics,
+            VecnodPayloadOps::GetConnections => RpcApiOps::GetConnections,
+            VecnodPayloadOps::GetSystemInfo => RpcApiOps::GetSystemInfo,
+            VecnodPayloadOps::GetServerInfo => RpcApiOps::GetServerInfo,
+            VecnodPayloadOps::GetSyncStatus => RpcApiOps::GetSyncStatus,
+            VecnodPayloadOps::GetDaaScoreTimestampEstimate => RpcApiOps::GetDaaScoreTimestampEstimate,
+            VecnodPayloadOps::GetFeeEstimate => RpcApiOps::GetFeeEstimate,
+            VecnodPayloadOps::GetFeeEstimateExperimental => RpcApiOps::GetFeeEstimateExperimental,
+            VecnodPayloadOps::GetCurrentBlockColor => RpcApiOps::GetCurrentBlockColor,
+            VecnodPayloadOps::GetUtxoReturnAddress => RpcApiOps::GetUtxoReturnAddress,
+            VecnodPayloadOps::GetTransaction => RpcApiOps::GetTransaction,
+            VecnodPayloadOps::GetAddressHistory => RpcApiOps::GetAddressHistory,
+            VecnodPayloadOps::SubmitPackage => RpcApiOps::SubmitPackage,
+            VecnodPayloadOps::GetVirtualChainChunk => RpcApiOps::GetVirtualChainChunk,
+            VecnodPayloadOps::GetFeeEstimateHistory => RpcApiOps::GetFeeEstimateHistory,
+            VecnodPayloadOps::GetTransactionStatus => RpcApiOps::GetTransactionStatus,
+            VecnodPayloadOps::NotifyBlockAdded => RpcApiOps::NotifyBlockAdded,
+            VecnodPayloadOps::NotifyNewBlockTemplate => RpcApiOps::NotifyNewBlockTemplate,
+            VecnodPayloadOps::NotifyFinalityConflict => RpcApiOps::NotifyFinalityConflict,
+            VecnodPayloadOps::NotifyUtxosChanged => RpcApiOps::NotifyUtxosChanged,
+            VecnodPayloadOps::NotifySinkBlueScoreChanged => RpcApiOps::NotifySinkBlueScoreChanged,
+            VecnodPayloadOps::NotifyPruningPointUtxoSetOverride => RpcApiOps::NotifyPruningPointUtxoSetOverride,
+            VecnodPayloadOps::NotifyVirtualDaaScoreChanged => RpcApiOps::NotifyVirtualDaaScoreChanged,
+            VecnodPayloadOps::NotifyVirtualChainChanged => RpcApiOps::NotifyVirtualChainChanged,
+            VecnodPayloadOps::NotifyTransactionStatusChanged => RpcApiOps::NotifyTransactionStatusChanged,
+            VecnodPayloadOps::StopNotifyingUtxosChanged => RpcApiOps::NotifyUtxosChanged,
+            VecnodPayloadOps::StopNotifyingPruningPointUtxoSetOverride => RpcApiOps::NotifyPruningPointUtxoSetOverride,
+        }
+    }
+}
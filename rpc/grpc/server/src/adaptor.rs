@@ -1,7 +1,15 @@
 use crate::{connection_handler::ConnectionHandler, manager::Manager};
 use vecno_core::debug;
-use vecno_notify::{notifier::Notifier, subscription::context::SubscriptionContext};
-use vecno_rpc_core::{api::rpc::DynRpcService, notify::connection::ChannelConnection, Notification, RpcResult};
+use vecno_notify::{notifier::Notifier, replay::DynReplayer, subscription::context::SubscriptionContext};
+use vecno_rpc_core::{
+    api::{
+        auth::{RpcAuthenticator, RpcTlsIdentity},
+        rate_limit::RpcRateLimiter,
+        rpc::DynRpcService,
+    },
+    notify::connection::ChannelConnection,
+    Notification, RpcResult,
+};
 use vecno_utils::networking::NetAddress;
 use vecno_utils_tower::counters::TowerConnectionCounters;
 use std::{ops::Deref, sync::Arc};
@@ -31,15 +39,20 @@ impl Adaptor {
         Self { _server_termination: server_termination, connection_handler, manager, serve_address }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn server(
         serve_address: NetAddress,
         network_bps: u64,
         manager: Manager,
         core_service: DynRpcService,
         core_notifier: Arc<Notifier<Notification, ChannelConnection>>,
+        replayer: Option<DynReplayer<Notification>>,
         subscription_context: SubscriptionContext,
         broadcasters: usize,
         counters: Arc<TowerConnectionCounters>,
+        authenticator: Arc<RpcAuthenticator>,
+        tls: Option<RpcTlsIdentity>,
+        rate_limiter: Arc<RpcRateLimiter>,
     ) -> Arc<Self> {
         let (manager_sender, manager_receiver) = mpsc_channel(Self::manager_channel_size());
         let connection_handler = ConnectionHandler::new(
@@ -47,9 +60,13 @@ impl Adaptor {
             manager_sender,
             core_service.clone(),
             core_notifier,
+            replayer,
             subscription_context,
             broadcasters,
             counters,
+            authenticator,
+            tls,
+            rate_limiter,
         );
         let server_termination = connection_handler.serve(serve_address);
         let adaptor = Arc::new(Adaptor::new(Some(server_termination), connection_handler, manager, serve_address));
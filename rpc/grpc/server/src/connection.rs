@@ -21,7 +21,10 @@ use vecno_notify::{
     listener::{ListenerId, ListenerLifespan},
     notifier::Notifier,
 };
-use vecno_rpc_core::Notification;
+use vecno_rpc_core::{
+    api::{auth::RpcPermissions, ops::RpcApiOps, rate_limit::RpcConnectionRateLimiter},
+    Notification, RpcError,
+};
 use parking_lot::Mutex;
 use std::{
     collections::{hash_map::Entry, HashMap},
@@ -69,6 +72,12 @@ struct Inner {
     /// The socket address of this client
     net_address: SocketAddr,
 
+    /// The permissions granted to the credentials of this client
+    permissions: RpcPermissions,
+
+    /// The request rate limits of this client
+    rate_limiter: RpcConnectionRateLimiter,
+
     /// The outgoing route for sending messages to this client
     outgoing_route: GrpcSender,
 
@@ -175,7 +184,17 @@ impl Router {
             debug!("GRPC, Route to handler got empty payload, client: {}", connection);
             return Err(GrpcServerError::InvalidRequestPayload);
         }
-        let rpc_op = request.payload.as_ref().unwrap().into();
+        let rpc_op: VecnodPayloadOps = request.payload.as_ref().unwrap().into();
+        let api_op = RpcApiOps::from(rpc_op);
+        if !connection.inner.permissions.allows(api_op) {
+            debug!("GRPC, Route to handler refused {:?} to client: {}", rpc_op, connection);
+            let response = VecnodResponse {
+                id: request.id,
+                payload: Some(rpc_op.to_error_response(RpcError::PermissionDenied(format!("{api_op:?}")))),
+            };
+            connection.enqueue(response).await?;
+            return Ok(());
+        }
         let route = self.get_or_subscribe(connection, rpc_op);
         match route.policy {
             RoutingPolicy::Enqueue => match route.send(request).await {
@@ -215,6 +234,8 @@ impl Display for Connection {
 impl Connection {
     pub(crate) fn new(
         net_address: SocketAddr,
+        permissions: RpcPermissions,
+        rate_limiter: RpcConnectionRateLimiter,
         server_context: ServerContext,
         interface: Arc<Interface>,
         manager_sender: MpscSender<ManagerEvent>,
@@ -227,6 +248,8 @@ impl Connection {
             inner: Arc::new(Inner {
                 connection_id: Uuid::new_v4(),
                 net_address,
+                permissions,
+                rate_limiter,
                 outgoing_route,
                 manager_sender,
                 server_context,
@@ -310,6 +333,10 @@ impl Connection {
         self.inner.net_address
     }
 
+    pub fn rate_limiter(&self) -> &RpcConnectionRateLimiter {
+        &self.inner.rate_limiter
+    }
+
     pub fn identity(&self) -> ConnectionId {
         self.inner.connection_id
     }
@@ -356,6 +383,18 @@ impl Connection {
             }
         }
     }
+
+    /// Sends to the client a response streamed ahead of the final one of a request.
+    ///
+    /// Unlike [`Self::enqueue`], waits for the outgoing route to have some capacity, so the stream follows the pace
+    /// of the client.
+    pub async fn send_streamed(&self, response: VecnodResponse) -> GrpcServerResult<()> {
+        assert!(response.payload.is_some(), "Vecnod gRPC message should always have a value");
+        if self.is_closed() {
+            return Err(GrpcServerError::ConnectionClosed);
+        }
+        self.inner.outgoing_route.send(response).await.map_err(|_| GrpcServerError::ConnectionClosed)
+    }
 }
 
 fn match_for_io_error(err_status: &tonic::Status) -> Option<&std::io::Error> {
@@ -5,7 +5,7 @@ use crate::{
     request_handler::{factory::Factory, interface::Interface},
 };
 use futures::{FutureExt, Stream};
-use vecno_core::{debug, info, warn};
+use vecno_core::{debug, error, info, warn};
 use vecno_grpc_core::{
     protowire::{
         rpc_server::{Rpc, RpcServer},
@@ -18,11 +18,16 @@ use vecno_notify::{
     events::EVENT_TYPE_ARRAY,
     listener::ListenerLifespan,
     notifier::Notifier,
+    replay::DynReplayer,
     subscriber::Subscriber,
     subscription::{context::SubscriptionContext, MutationPolicies, UtxosChangedMutationPolicy},
 };
 use vecno_rpc_core::{
-    api::rpc::DynRpcService,
+    api::{
+        auth::{RpcAuthenticator, RpcTlsIdentity},
+        rate_limit::RpcRateLimiter,
+        rpc::DynRpcService,
+    },
     notify::{channel::NotificationChannel, connection::ChannelConnection},
     Notification, RpcResult,
 };
@@ -46,7 +51,11 @@ use tokio::{
     time::timeout,
 };
 use tokio_stream::{wrappers::ReceiverStream, StreamExt};
-use tonic::{codec::CompressionEncoding, transport::Server as TonicServer, Request, Response};
+use tonic::{
+    codec::CompressionEncoding,
+    transport::{Identity, Server as TonicServer, ServerTlsConfig},
+    Request, Response,
+};
 
 #[derive(Clone)]
 pub struct ServerContext {
@@ -76,19 +85,27 @@ pub struct ConnectionHandler {
     interface: Arc<Interface>,
     running: Arc<AtomicBool>,
     counters: Arc<TowerConnectionCounters>,
+    authenticator: Arc<RpcAuthenticator>,
+    tls: Option<RpcTlsIdentity>,
+    rate_limiter: Arc<RpcRateLimiter>,
 }
 
 const GRPC_SERVER: &str = "grpc-server";
 
 impl ConnectionHandler {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         network_bps: u64,
         manager_sender: MpscSender<ManagerEvent>,
         core_service: DynRpcService,
         core_notifier: Arc<Notifier<Notification, ChannelConnection>>,
+        replayer: Option<DynReplayer<Notification>>,
         subscription_context: SubscriptionContext,
         broadcasters: usize,
         counters: Arc<TowerConnectionCounters>,
+        authenticator: Arc<RpcAuthenticator>,
+        tls: Option<RpcTlsIdentity>,
+        rate_limiter: Arc<RpcRateLimiter>,
     ) -> Self {
         // This notifier UTXOs subscription granularity to rpc-core notifier
         let policies = MutationPolicies::new(UtxosChangedMutationPolicy::AddressSet);
@@ -105,20 +122,18 @@ impl ConnectionHandler {
         let converter = Arc::new(GrpcServiceConverter::new());
         let collector = Arc::new(GrpcServiceCollector::new(GRPC_SERVER, core_channel.receiver(), converter));
         let subscriber = Arc::new(Subscriber::new(GRPC_SERVER, core_events, core_notifier, core_listener_id));
-        let notifier: Arc<Notifier<Notification, Connection>> = Arc::new(Notifier::new(
-            GRPC_SERVER,
-            core_events,
-            vec![collector],
-            vec![subscriber],
-            subscription_context,
-            broadcasters,
-            policies,
-        ));
+        let notifier =
+            Notifier::new(GRPC_SERVER, core_events, vec![collector], vec![subscriber], subscription_context, broadcasters, policies);
+        // Subscriptions carrying a resume token get their missed notifications replayed by the core service
+        let notifier: Arc<Notifier<Notification, Connection>> = Arc::new(match replayer {
+            Some(replayer) => notifier.with_replayer(replayer),
+            None => notifier,
+        });
         let server_context = ServerContext::new(core_service, notifier);
         let interface = Arc::new(Factory::new_interface(server_context.clone(), network_bps));
         let running = Default::default();
 
-        Self { manager_sender, server_context, interface, running, counters }
+        Self { manager_sender, server_context, interface, running, counters, authenticator, tls, rate_limiter }
     }
 
     /// Launches a gRPC server listener loop
@@ -126,7 +141,8 @@ impl ConnectionHandler {
         let (termination_sender, termination_receiver) = oneshot_channel::<()>();
         let (signal_sender, signal_receiver) = oneshot_channel::<()>();
         let connection_handler = self.clone();
-        info!("GRPC Server starting on: {}", serve_address);
+        info!("GRPC Server starting on: {}{}", serve_address, if self.tls.is_some() { " (TLS)" } else { "" });
+        let tls_config = self.tls.as_ref().map(|tls| ServerTlsConfig::new().identity(Identity::from_pem(&tls.cert, &tls.key)));
 
         let bytes_tx = self.counters.bytes_tx.clone();
         let bytes_rx = self.counters.bytes_rx.clone();
@@ -138,10 +154,22 @@ impl ConnectionHandler {
                 .send_compressed(CompressionEncoding::Gzip)
                 .max_decoding_message_size(RPC_MAX_MESSAGE_SIZE);
 
+            let mut server_builder = TonicServer::builder();
+            if let Some(tls_config) = tls_config {
+                // The TLS identity is validated along with the node arguments so this is not expected to fail
+                server_builder = match server_builder.tls_config(tls_config) {
+                    Ok(server_builder) => server_builder,
+                    Err(err) => {
+                        error!("GRPC Server {} has an invalid TLS configuration: {}", serve_address, err);
+                        return;
+                    }
+                };
+            }
+
             // TODO: check whether we should set tcp_keepalive
             // const GRPC_KEEP_ALIVE_PING_INTERVAL: Duration = Duration::from_secs(5);
             // const GRPC_KEEP_ALIVE_PING_TIMEOUT: Duration = Duration::from_secs(120);
-            let serve_result = TonicServer::builder()
+            let serve_result = server_builder
                 // .http2_keepalive_interval(Some(GRPC_KEEP_ALIVE_PING_INTERVAL))
                 // .http2_keepalive_timeout(Some(GRPC_KEEP_ALIVE_PING_TIMEOUT))
                 .layer(MapRequestBodyLayer::new(move |body| CountBytesBody::new(body, bytes_rx.clone()).boxed_unsync()))
@@ -254,6 +282,16 @@ impl Rpc for ConnectionHandler {
 
         debug!("GRPC, Incoming message stream from {:?}", remote_address);
 
+        // Authenticate the client by the bearer token of the `authorization` metadata
+        let authorization = request.metadata().get("authorization").and_then(|value| value.to_str().ok());
+        let permissions = match self.authenticator.authenticate(authorization.and_then(RpcAuthenticator::bearer_token)) {
+            Ok(permissions) => permissions,
+            Err(err) => {
+                debug!("GRPC, refusing incoming message stream from {:?} - {}", remote_address, err);
+                return Err(tonic::Status::new(tonic::Code::Unauthenticated, err.to_string()));
+            }
+        };
+
         // Build the in/out pipes
         let (outgoing_route, outgoing_receiver) = mpsc_channel(Self::outgoing_route_channel_size());
         let incoming_stream = request.into_inner();
@@ -261,6 +299,8 @@ impl Rpc for ConnectionHandler {
         // Build the connection object
         let connection = Connection::new(
             remote_address,
+            permissions,
+            self.rate_limiter.connection_limiter(remote_address.ip()),
             self.server_context(),
             self.interface(),
             self.manager_sender(),
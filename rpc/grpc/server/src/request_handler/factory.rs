@@ -82,6 +82,12 @@ impl Factory {
                 GetFeeEstimateExperimental,
                 GetCurrentBlockColor,
                 GetUtxoReturnAddress,
+                GetTransaction,
+                GetAddressHistory,
+                SubmitPackage,
+                GetVirtualChainChunk,
+                GetFeeEstimateHistory,
+                GetTransactionStatus,
                 NotifyBlockAdded,
                 NotifyNewBlockTemplate,
                 NotifyFinalityConflict,
@@ -90,6 +96,7 @@ impl Factory {
                 NotifyPruningPointUtxoSetOverride,
                 NotifyVirtualDaaScoreChanged,
                 NotifyVirtualChainChanged,
+                NotifyTransactionStatusChanged,
                 StopNotifyingUtxosChanged,
                 StopNotifyingPruningPointUtxoSetOverride,
             ]
@@ -136,6 +143,44 @@ impl Factory {
         });
         interface.replace_method(VecnodPayloadOps::NotifyFinalityConflict, method);
 
+        // Manually reimplementing the GetVirtualChainChunkRequest method so a streamed request gets
+        // the chain sent in successive chunks up to the sink.
+        let method: VecnodMethod = Method::new(|server_ctx: ServerContext, connection: Connection, request: VecnodRequest| {
+            Box::pin(async move {
+                let mut response: VecnodResponse = match request.payload {
+                    Some(Payload::GetVirtualChainChunkRequest(ref message)) => {
+                        match vecno_rpc_core::GetVirtualChainChunkRequest::try_from(message) {
+                            Ok(mut chunk_request) => loop {
+                                let chunk = server_ctx.core_service.get_virtual_chain_chunk_call(None, chunk_request.clone()).await;
+                                let next_start_hash = match chunk {
+                                    Ok(ref chunk) if message.stream && !chunk.is_tip => {
+                                        chunk.added_chain_blocks.last().map(|x| x.hash)
+                                    }
+                                    _ => None,
+                                };
+                                let mut response: VecnodResponse = chunk.into();
+                                match next_start_hash {
+                                    Some(start_hash) => {
+                                        response.id = request.id;
+                                        connection.send_streamed(response).await?;
+                                        chunk_request.start_hash = start_hash;
+                                    }
+                                    None => break response,
+                                }
+                            },
+                            Err(err) => GetVirtualChainChunkResponseMessage::from(err).into(),
+                        }
+                    }
+                    _ => {
+                        return Err(GrpcServerError::InvalidRequestPayload);
+                    }
+                };
+                response.id = request.id;
+                Ok(response)
+            })
+        });
+        interface.replace_method(VecnodPayloadOps::GetVirtualChainChunk, method);
+
         // Methods with special properties
         let network_bps = network_bps as usize;
         interface.set_method_properties(
@@ -12,9 +12,11 @@ use vecno_grpc_core::{
     ops::VecnodPayloadOps,
     protowire::{VecnodRequest, VecnodResponse},
 };
+use vecno_rpc_core::api::ops::RpcApiOps;
 
 pub struct RequestHandler {
     rpc_op: VecnodPayloadOps,
+    api_op: RpcApiOps,
     incoming_route: IncomingRoute,
     server_ctx: ServerContext,
     method: DynVecnodMethod,
@@ -30,11 +32,14 @@ impl RequestHandler {
         connection: Connection,
     ) -> Self {
         let method = interface.get_method(&rpc_op);
-        Self { rpc_op, incoming_route, server_ctx: server_context, method, connection }
+        Self { rpc_op, api_op: rpc_op.into(), incoming_route, server_ctx: server_context, method, connection }
     }
 
     pub async fn handle_request(&self, request: VecnodRequest) -> GrpcServerResult<VecnodResponse> {
         let id = request.id;
+        if let Err(err) = self.connection.rate_limiter().check(self.api_op) {
+            return Ok(VecnodResponse { id, payload: Some(self.rpc_op.to_error_response(err)) });
+        }
         let mut response = self.method.call(self.server_ctx.clone(), self.connection.clone(), request).await?;
         response.id = id;
         Ok(response)
@@ -5,6 +5,10 @@ use vecno_core::{
     task::service::{AsyncService, AsyncServiceFuture},
     trace, warn,
 };
+use vecno_rpc_core::api::{
+    auth::{RpcAuthenticator, RpcTlsIdentity},
+    rate_limit::RpcRateLimiter,
+};
 use vecno_rpc_service::service::RpcCoreService;
 use vecno_utils::{networking::NetAddress, triggers::SingleTrigger};
 use vecno_utils_tower::counters::TowerConnectionCounters;
@@ -20,6 +24,9 @@ pub struct GrpcService {
     started: SingleTrigger,
     shutdown: SingleTrigger,
     counters: Arc<TowerConnectionCounters>,
+    authenticator: Arc<RpcAuthenticator>,
+    tls: Option<RpcTlsIdentity>,
+    rate_limiter: Arc<RpcRateLimiter>,
 }
 
 impl GrpcService {
@@ -32,6 +39,9 @@ impl GrpcService {
         rpc_max_clients: usize,
         broadcasters: usize,
         counters: Arc<TowerConnectionCounters>,
+        authenticator: Arc<RpcAuthenticator>,
+        tls: Option<RpcTlsIdentity>,
+        rate_limiter: Arc<RpcRateLimiter>,
     ) -> Self {
         Self {
             net_address: address,
@@ -42,6 +52,9 @@ impl GrpcService {
             started: Default::default(),
             shutdown: Default::default(),
             counters,
+            authenticator,
+            tls,
+            rate_limiter,
         }
     }
 
@@ -68,9 +81,13 @@ impl AsyncService for GrpcService {
             manager,
             self.core_service.clone(),
             self.core_service.notifier(),
+            Some(self.core_service.clone()),
             self.core_service.subscription_context(),
             self.broadcasters,
             self.counters.clone(),
+            self.authenticator.clone(),
+            self.tls.clone(),
+            self.rate_limiter.clone(),
         );
 
         // Signal the server was started
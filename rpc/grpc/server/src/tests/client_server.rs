@@ -1,9 +1,10 @@
-use super::rpc_core_mock::RpcCoreMock;
+use super::rpc_core_mock::{RpcCoreMock, MOCK_CHAIN_LENGTH};
 use crate::{adaptor::Adaptor, manager::Manager};
+use futures::StreamExt;
 use vecno_core::info;
 use vecno_grpc_client::GrpcClient;
 use vecno_notify::scope::{NewBlockTemplateScope, Scope};
-use vecno_rpc_core::api::rpc::RpcApi;
+use vecno_rpc_core::{api::rpc::RpcApi, RpcHash, RpcResult};
 use vecno_utils::networking::{ContextualNetAddress, NetAddress};
 use std::sync::Arc;
 
@@ -188,6 +189,42 @@ async fn test_client_server_notifications() {
     tokio::time::sleep(std::time::Duration::from_millis(50)).await;
 }
 
+#[tokio::test]
+async fn test_client_server_virtual_chain_stream() {
+    vecno_core::log::try_init_logger("info, vecno_grpc_core=trace, vecno_grpc_server=trace, vecno_grpc_client=trace");
+
+    // Create and start a fake core service and a server
+    let rpc_core_service = Arc::new(RpcCoreMock::new());
+    rpc_core_service.start();
+    let server = create_server(rpc_core_service.clone());
+    let client = create_client(server.serve_address()).await;
+
+    // The server streams the chain in chunks up to the tip
+    let chunks = client.stream_virtual_chain_from_block(RpcHash::from_u64_word(0), 3).collect::<Vec<_>>().await;
+    let chunks = chunks.into_iter().collect::<RpcResult<Vec<_>>>().unwrap();
+    assert_eq!(chunks.iter().map(|x| x.added_chain_blocks.len()).collect::<Vec<_>>(), vec![3, 3, 3, 1]);
+    assert_eq!(chunks.iter().map(|x| x.is_tip).collect::<Vec<_>>(), vec![false, false, false, true]);
+    let hashes = chunks.iter().flat_map(|x| x.added_chain_blocks.iter().map(|x| x.hash)).collect::<Vec<_>>();
+    assert_eq!(hashes, (1..=MOCK_CHAIN_LENGTH).map(RpcHash::from_u64_word).collect::<Vec<_>>());
+
+    // An error ends the stream
+    let chunks = client.stream_virtual_chain_from_block(RpcHash::from_u64_word(MOCK_CHAIN_LENGTH + 1), 3).collect::<Vec<_>>().await;
+    assert!(matches!(chunks.as_slice(), [Err(_)]), "unexpected chunks {chunks:?}");
+
+    // A chunk requested without streaming gets a single response
+    let chunk = client.get_virtual_chain_chunk(RpcHash::from_u64_word(MOCK_CHAIN_LENGTH - 2), 3).await.unwrap();
+    assert_eq!(chunk.added_chain_blocks.len(), 2);
+    assert!(chunk.is_tip);
+
+    // Stop the fake service and the server
+    rpc_core_service.join().await;
+    assert!(server.stop().await.is_ok(), "error stopping the server");
+    assert!(client.disconnect().await.is_ok(), "client failed to disconnect");
+    drop(client);
+    drop(server);
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+}
+
 fn create_server(core_service: Arc<RpcCoreMock>) -> Arc<Adaptor> {
     let manager = Manager::new(128);
     Adaptor::server(
@@ -196,9 +233,13 @@ fn create_server(core_service: Arc<RpcCoreMock>) -> Arc<Adaptor> {
         manager,
         core_service.clone(),
         core_service.core_notifier(),
+        None,
         core_service.subscription_context(),
         3,
         Default::default(),
+        Default::default(),
+        None,
+        Default::default(),
     )
 }
 
@@ -12,6 +12,9 @@ use std::sync::Arc;
 
 pub(super) type RpcCoreNotifier = Notifier<Notification, ChannelConnection>;
 
+/// Length of the virtual chain served by the mock, the chain block at height `i` having hash `i`
+pub(super) const MOCK_CHAIN_LENGTH: u64 = 10;
+
 pub(super) struct RpcCoreMock {
     core_notifier: Arc<RpcCoreNotifier>,
     _sync_receiver: Receiver<()>,
@@ -370,6 +373,62 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
+    async fn get_transaction_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetTransactionRequest,
+    ) -> RpcResult<GetTransactionResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_address_history_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetAddressHistoryRequest,
+    ) -> RpcResult<GetAddressHistoryResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn submit_package_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: SubmitPackageRequest,
+    ) -> RpcResult<SubmitPackageResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_virtual_chain_chunk_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: GetVirtualChainChunkRequest,
+    ) -> RpcResult<GetVirtualChainChunkResponse> {
+        let height = request.start_hash.to_le_u64()[3];
+        if height > MOCK_CHAIN_LENGTH {
+            return Err(RpcError::General(format!("block {} is not on the chain", request.start_hash)));
+        }
+        let end = (height + request.max_chain_blocks as u64).min(MOCK_CHAIN_LENGTH);
+        let added_chain_blocks = (height + 1..=end)
+            .map(|i| RpcAcceptingChainBlock { hash: RpcHash::from_u64_word(i), daa_score: i, accepted_transactions: vec![] })
+            .collect();
+        Ok(GetVirtualChainChunkResponse::new(vec![], added_chain_blocks, end == MOCK_CHAIN_LENGTH))
+    }
+
+    async fn get_fee_estimate_history_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetFeeEstimateHistoryRequest,
+    ) -> RpcResult<GetFeeEstimateHistoryResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_transaction_status_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetTransactionStatusRequest,
+    ) -> RpcResult<GetTransactionStatusResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
 
@@ -0,0 +1,28 @@
+//!
+//! Writes the schemas of the RPC to a directory:
+//! - `openapi.json`: the OpenAPI document of the JSON-RPC surface
+//! - `schema.json`: a JSON Schema document defining every request, response and notification type
+//!
+//! Usage: `vecno-rpc-schema [output directory]`, defaulting to the current directory.
+//!
+
+use std::{path::PathBuf, process::ExitCode};
+use vecno_jsonrpc_server::openapi::openapi_document;
+use vecno_rpc_core::model::schema::json_schema;
+
+fn main() -> ExitCode {
+    let directory = std::env::args().nth(1).map(PathBuf::from).unwrap_or_default();
+    let documents = [
+        ("openapi.json", serde_json::to_string_pretty(&openapi_document())),
+        ("schema.json", serde_json::to_string_pretty(&json_schema())),
+    ];
+    for (name, document) in documents {
+        let path = directory.join(name);
+        if let Err(err) = document.map_err(std::io::Error::from).and_then(|document| std::fs::write(&path, document + "\n")) {
+            eprintln!("failed to write {}: {err}", path.display());
+            return ExitCode::FAILURE;
+        }
+        println!("wrote {}", path.display());
+    }
+    ExitCode::SUCCESS
+}
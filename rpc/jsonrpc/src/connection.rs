@@ -0,0 +1,172 @@
+use crate::{
+    error::{JsonRpcServerError, JsonRpcServerResult},
+    http::{read_request_body, read_request_head, write_response, StatusCode},
+    openapi::{OPENAPI_DOCUMENT, OPENAPI_PATH},
+    protocol::{JsonRpcError, JsonRpcResult, Request, Response},
+    router::{route, routed_op},
+};
+use futures::future::join_all;
+use serde_json::Value;
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+use tokio::io::{split, AsyncRead, AsyncWrite, BufReader};
+use triggered::Listener;
+use vecno_core::debug;
+use vecno_rpc_core::{
+    api::{
+        auth::{RpcAuthenticator, RpcPermissions},
+        rate_limit::RpcConnectionRateLimiter,
+    },
+    RpcError,
+};
+use vecno_rpc_service::service::RpcCoreService;
+
+/// Maximum number of calls in a batch request
+pub const MAX_BATCH_SIZE: usize = 100;
+
+/// Time a kept-alive connection may stay idle before being closed
+const IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Maximum time to receive the body of an authenticated request once its head is read
+const BODY_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// An HTTP client of the JSON-RPC server
+pub struct Connection {
+    address: SocketAddr,
+    core_service: Arc<RpcCoreService>,
+    authenticator: Arc<RpcAuthenticator>,
+    rate_limiter: RpcConnectionRateLimiter,
+}
+
+impl Connection {
+    pub fn new(
+        address: SocketAddr,
+        core_service: Arc<RpcCoreService>,
+        authenticator: Arc<RpcAuthenticator>,
+        rate_limiter: RpcConnectionRateLimiter,
+    ) -> Self {
+        Self { address, core_service, authenticator, rate_limiter }
+    }
+
+    /// Serves the HTTP requests of the client until it closes the connection or a shutdown signal is received
+    pub async fn run<S: AsyncRead + AsyncWrite + Unpin>(self, stream: S, shutdown: Listener) -> JsonRpcServerResult<()> {
+        let (reader, mut writer) = split(stream);
+        let mut reader = BufReader::new(reader);
+        loop {
+            let request = tokio::select! {
+                _ = shutdown.clone() => return Ok(()),
+                request = tokio::time::timeout(IDLE_TIMEOUT, read_request_head(&mut reader)) => match request {
+                    Ok(request) => request,
+                    Err(_) => return Ok(()),
+                },
+            };
+            let mut request = match request {
+                Ok(Some(request)) => request,
+                Ok(None) => return Ok(()),
+                Err(err) => {
+                    let status = match err {
+                        JsonRpcServerError::InvalidHttpRequest(_) => StatusCode::BAD_REQUEST,
+                        JsonRpcServerError::LengthRequired => StatusCode::LENGTH_REQUIRED,
+                        JsonRpcServerError::RequestTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+                        _ => return Err(err),
+                    };
+                    write_response(&mut writer, status, &[], &[], false).await?;
+                    return Err(err);
+                }
+            };
+            // Only the bodies of authenticated calls are read, the connection being closed after responding to any other
+            // request carrying a body since its next request cannot be located
+            let keep_alive = request.keep_alive();
+            let unread_body_keep_alive = keep_alive && request.content_length == 0;
+            let keep_alive = if request.method == "GET" && request.target == OPENAPI_PATH {
+                write_response(&mut writer, StatusCode::OK, &[], &OPENAPI_DOCUMENT, unread_body_keep_alive).await?;
+                unread_body_keep_alive
+            } else if request.method != "POST" {
+                write_response(&mut writer, StatusCode::METHOD_NOT_ALLOWED, &[("Allow", "POST")], &[], unread_body_keep_alive).await?;
+                unread_body_keep_alive
+            } else {
+                // The request is authenticated out of its head so that unauthenticated clients get no body buffered
+                let token = request.header("authorization").and_then(RpcAuthenticator::bearer_token);
+                match self.authenticator.authenticate(token) {
+                    Ok(permissions) => {
+                        tokio::select! {
+                            _ = shutdown.clone() => return Ok(()),
+                            result = tokio::time::timeout(BODY_TIMEOUT, read_request_body(&mut reader, &mut request)) => {
+                                result.map_err(|_| JsonRpcServerError::Timeout("request body"))??
+                            }
+                        }
+                        match self.handle_body(permissions, &request.body).await {
+                            Some(body) => write_response(&mut writer, StatusCode::OK, &[], &body, keep_alive).await?,
+                            None => write_response(&mut writer, StatusCode::NO_CONTENT, &[], &[], keep_alive).await?,
+                        }
+                        keep_alive
+                    }
+                    Err(err) => {
+                        debug!("JSON-RPC request of {} refused: {}", self.address, err);
+                        let headers = [("WWW-Authenticate", "Bearer")];
+                        write_response(&mut writer, StatusCode::UNAUTHORIZED, &headers, &[], unread_body_keep_alive).await?;
+                        unread_body_keep_alive
+                    }
+                }
+            };
+
+            if !keep_alive {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Handles a single or batch JSON-RPC request, returning the encoded responses unless it only holds notifications
+    async fn handle_body(&self, permissions: RpcPermissions, body: &[u8]) -> Option<Vec<u8>> {
+        let payload = match serde_json::from_slice::<Value>(body) {
+            Ok(payload) => payload,
+            Err(err) => return Some(encode(&Response::error(Value::Null, JsonRpcError::ParseError(err.to_string())))),
+        };
+        match payload {
+            Value::Array(calls) if calls.is_empty() => {
+                Some(encode(&Response::error(Value::Null, JsonRpcError::InvalidRequest("empty batch".to_owned()))))
+            }
+            Value::Array(calls) if calls.len() > MAX_BATCH_SIZE => Some(encode(&Response::error(
+                Value::Null,
+                JsonRpcError::InvalidRequest(format!("batch exceeds the maximum of {MAX_BATCH_SIZE} calls")),
+            ))),
+            Value::Array(calls) => {
+                let responses = join_all(calls.into_iter().map(|call| self.handle_call(permissions, call))).await;
+                let responses = responses.into_iter().flatten().collect::<Vec<_>>();
+                (!responses.is_empty()).then(|| encode(&responses))
+            }
+            call => self.handle_call(permissions, call).await.map(|response| encode(&response)),
+        }
+    }
+
+    /// Handles a single call, returning its response unless it is a notification
+    async fn handle_call(&self, permissions: RpcPermissions, call: Value) -> Option<Response> {
+        let request = match Request::try_from(call) {
+            Ok(request) => request,
+            Err(err) => return Some(Response::error(Value::Null, err)),
+        };
+        let result = self.call(permissions, &request.method, request.params).await;
+        if let Err(err) = result.as_ref() {
+            debug!("JSON-RPC call {} of {} failed: {}", request.method, self.address, err);
+        }
+        // Notifications get no response, even when failing
+        let id = request.id?;
+        Some(match result {
+            Ok(result) => Response::ok(id, result),
+            Err(err) => Response::error(id, err),
+        })
+    }
+
+    async fn call(&self, permissions: RpcPermissions, method: &str, params: Value) -> JsonRpcResult<Value> {
+        let op = routed_op(method).ok_or_else(|| JsonRpcError::MethodNotFound(method.to_owned()))?;
+        if !permissions.allows(op) {
+            return Err(RpcError::PermissionDenied(format!("{op:?}")).into());
+        }
+        self.rate_limiter.check(op)?;
+        route(&self.core_service, op, params).await
+    }
+}
+
+fn encode<T: serde::Serialize>(value: &T) -> Vec<u8> {
+    // Responses only hold JSON values and strings, which always serialize
+    serde_json::to_vec(value).expect("JSON-RPC responses are serializable")
+}
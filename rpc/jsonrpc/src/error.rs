@@ -0,0 +1,27 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum JsonRpcServerError {
+    #[error("I/O error: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("TLS configuration error: {0}")]
+    TlsConfigError(String),
+
+    #[error("invalid HTTP request: {0}")]
+    InvalidHttpRequest(String),
+
+    #[error("HTTP request has no content length")]
+    LengthRequired,
+
+    #[error("HTTP request exceeds the maximum size of {0} bytes")]
+    RequestTooLarge(usize),
+
+    #[error("client connection is closed")]
+    ConnectionClosed,
+
+    #[error("{0} timed out")]
+    Timeout(&'static str),
+}
+
+pub type JsonRpcServerResult<T> = std::result::Result<T, JsonRpcServerError>;
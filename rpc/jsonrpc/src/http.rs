@@ -0,0 +1,210 @@
+//! Minimal HTTP/1.1 framing: reads requests carrying a `Content-Length` body and writes responses,
+//! keeping the connection alive unless the client asks otherwise.
+
+use crate::error::{JsonRpcServerError, JsonRpcServerResult};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+/// Maximum size of the request line and headers of a request
+pub const MAX_HEAD_SIZE: usize = 16 * 1024;
+
+/// Maximum size of a request body, large enough for a block submission
+pub const MAX_BODY_SIZE: usize = 32 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatusCode(pub u16, pub &'static str);
+
+impl StatusCode {
+    pub const OK: StatusCode = StatusCode(200, "OK");
+    pub const NO_CONTENT: StatusCode = StatusCode(204, "No Content");
+    pub const BAD_REQUEST: StatusCode = StatusCode(400, "Bad Request");
+    pub const UNAUTHORIZED: StatusCode = StatusCode(401, "Unauthorized");
+    pub const METHOD_NOT_ALLOWED: StatusCode = StatusCode(405, "Method Not Allowed");
+    pub const LENGTH_REQUIRED: StatusCode = StatusCode(411, "Length Required");
+    pub const PAYLOAD_TOO_LARGE: StatusCode = StatusCode(413, "Payload Too Large");
+}
+
+#[derive(Debug)]
+pub struct HttpRequest {
+    pub method: String,
+    pub target: String,
+    pub version: String,
+    pub headers: Vec<(String, String)>,
+    /// Length of the body announced by the head of the request
+    pub content_length: usize,
+    /// Body of the request, empty until read by [`read_request_body`]
+    pub body: Vec<u8>,
+}
+
+impl HttpRequest {
+    /// Returns the value of the first header named `name`, compared case-insensitively
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter().find(|(header, _)| header.eq_ignore_ascii_case(name)).map(|(_, value)| value.as_str())
+    }
+
+    /// Returns whether the connection stays open after the response, which is the default as of HTTP/1.1
+    pub fn keep_alive(&self) -> bool {
+        match self.header("connection") {
+            Some(connection) if connection.eq_ignore_ascii_case("close") => false,
+            Some(connection) if connection.eq_ignore_ascii_case("keep-alive") => true,
+            _ => self.version == "HTTP/1.1",
+        }
+    }
+}
+
+/// Reads the next request of the connection, body included, returning `None` if the client closed it between two requests.
+pub async fn read_request<R: AsyncBufRead + Unpin>(reader: &mut R) -> JsonRpcServerResult<Option<HttpRequest>> {
+    let Some(mut request) = read_request_head(reader).await? else {
+        return Ok(None);
+    };
+    read_request_body(reader, &mut request).await?;
+    Ok(Some(request))
+}
+
+/// Reads the head of the next request of the connection, returning `None` if the client closed it between two requests.
+///
+/// The body is left unread so that the request can be authenticated first. A request without a `Content-Length` header
+/// yields a [`StatusCode::LENGTH_REQUIRED`] error so chunked bodies are refused.
+pub async fn read_request_head<R: AsyncBufRead + Unpin>(reader: &mut R) -> JsonRpcServerResult<Option<HttpRequest>> {
+    let mut head_size = 0;
+    let Some(request_line) = read_line(reader, &mut head_size).await? else {
+        return Ok(None);
+    };
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target), Some(version), None) = (parts.next(), parts.next(), parts.next(), parts.next()) else {
+        return Err(JsonRpcServerError::InvalidHttpRequest(format!("malformed request line {request_line}")));
+    };
+    let (method, target, version) = (method.to_owned(), target.to_owned(), version.to_owned());
+    if version != "HTTP/1.1" && version != "HTTP/1.0" {
+        return Err(JsonRpcServerError::InvalidHttpRequest(format!("unsupported version {version}")));
+    }
+
+    let mut headers = Vec::new();
+    loop {
+        let line = read_line(reader, &mut head_size).await?.ok_or(JsonRpcServerError::ConnectionClosed)?;
+        if line.is_empty() {
+            break;
+        }
+        let (name, value) =
+            line.split_once(':').ok_or_else(|| JsonRpcServerError::InvalidHttpRequest(format!("malformed header {line}")))?;
+        headers.push((name.trim().to_owned(), value.trim().to_owned()));
+    }
+    let mut request = HttpRequest { method, target, version, headers, content_length: 0, body: Vec::new() };
+
+    if request.header("transfer-encoding").is_some() {
+        return Err(JsonRpcServerError::LengthRequired);
+    }
+    let content_length = match request.header("content-length") {
+        Some(length) => {
+            length.parse::<usize>().map_err(|_| JsonRpcServerError::InvalidHttpRequest(format!("invalid content length {length}")))?
+        }
+        None if request.method == "POST" => return Err(JsonRpcServerError::LengthRequired),
+        None => 0,
+    };
+    if content_length > MAX_BODY_SIZE {
+        return Err(JsonRpcServerError::RequestTooLarge(MAX_BODY_SIZE));
+    }
+    request.content_length = content_length;
+    Ok(Some(request))
+}
+
+/// Reads the body of a request whose head was read by [`read_request_head`].
+///
+/// The body buffer grows along with the bytes actually received rather than being allocated upfront.
+pub async fn read_request_body<R: AsyncBufRead + Unpin>(reader: &mut R, request: &mut HttpRequest) -> JsonRpcServerResult<()> {
+    request.body.clear();
+    (&mut *reader).take(request.content_length as u64).read_to_end(&mut request.body).await?;
+    if request.body.len() < request.content_length {
+        return Err(JsonRpcServerError::ConnectionClosed);
+    }
+    Ok(())
+}
+
+/// Reads a CRLF or LF terminated line of the request head, stripped of its terminator
+async fn read_line<R: AsyncBufRead + Unpin>(reader: &mut R, head_size: &mut usize) -> JsonRpcServerResult<Option<String>> {
+    let mut line = String::new();
+    let limit = (MAX_HEAD_SIZE - *head_size) as u64;
+    let read = (&mut *reader).take(limit).read_line(&mut line).await?;
+    *head_size += read;
+    if read == 0 {
+        return match limit {
+            0 => Err(JsonRpcServerError::RequestTooLarge(MAX_HEAD_SIZE)),
+            _ if *head_size == 0 => Ok(None),
+            _ => Err(JsonRpcServerError::ConnectionClosed),
+        };
+    }
+    if !line.ends_with('\n') {
+        return match *head_size >= MAX_HEAD_SIZE {
+            true => Err(JsonRpcServerError::RequestTooLarge(MAX_HEAD_SIZE)),
+            false => Err(JsonRpcServerError::ConnectionClosed),
+        };
+    }
+    Ok(Some(line.trim_end_matches(['\r', '\n']).to_owned()))
+}
+
+/// Writes a response with a JSON body, or with no body if `body` is empty
+pub async fn write_response<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    status: StatusCode,
+    extra_headers: &[(&str, &str)],
+    body: &[u8],
+    keep_alive: bool,
+) -> JsonRpcServerResult<()> {
+    let StatusCode(code, reason) = status;
+    let mut response = format!("HTTP/1.1 {code} {reason}\r\n");
+    if !body.is_empty() {
+        response.push_str("Content-Type: application/json\r\n");
+    }
+    if status != StatusCode::NO_CONTENT {
+        response.push_str(&format!("Content-Length: {}\r\n", body.len()));
+    }
+    for (name, value) in extra_headers {
+        response.push_str(&format!("{name}: {value}\r\n"));
+    }
+    response.push_str(if keep_alive { "Connection: keep-alive\r\n\r\n" } else { "Connection: close\r\n\r\n" });
+
+    let mut bytes = response.into_bytes();
+    bytes.extend_from_slice(body);
+    writer.write_all(&bytes).await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_read_request() {
+        let mut input: &[u8] = b"POST / HTTP/1.1\r\nHost: localhost\r\nAuthorization: Bearer abc\r\nContent-Length: 2\r\n\r\n{}\
+            POST /rpc HTTP/1.0\nContent-Length: 0\n\n";
+        let request = read_request(&mut input).await.unwrap().unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.header("AUTHORIZATION"), Some("Bearer abc"));
+        assert_eq!(request.body, b"{}");
+        assert!(request.keep_alive());
+
+        let request = read_request(&mut input).await.unwrap().unwrap();
+        assert_eq!(request.target, "/rpc");
+        assert!(request.body.is_empty());
+        assert!(!request.keep_alive());
+
+        assert!(read_request(&mut input).await.unwrap().is_none());
+
+        let mut input: &[u8] = b"POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n";
+        assert!(matches!(read_request(&mut input).await, Err(JsonRpcServerError::LengthRequired)));
+        let mut input: &[u8] = b"POST / HTTP/1.1\r\nContent-Length: 99999999999\r\n\r\n";
+        assert!(matches!(read_request(&mut input).await, Err(JsonRpcServerError::RequestTooLarge(_))));
+        let head = format!("POST / HTTP/1.1\r\nX-Padding: {}\r\n\r\n", "a".repeat(MAX_HEAD_SIZE));
+        assert!(matches!(read_request(&mut head.as_bytes()).await, Err(JsonRpcServerError::RequestTooLarge(_))));
+        let mut input: &[u8] = b"POST / HTTP/1.1\r\nContent-Length: 10\r\n\r\n{}";
+        assert!(read_request(&mut input).await.is_err());
+
+        // The head announces the length of the body, which is only read on demand
+        let mut input: &[u8] = b"POST / HTTP/1.1\r\nContent-Length: 2\r\n\r\n{}";
+        let mut request = read_request_head(&mut input).await.unwrap().unwrap();
+        assert_eq!(request.content_length, 2);
+        assert!(request.body.is_empty());
+        read_request_body(&mut input, &mut request).await.unwrap();
+        assert_eq!(request.body, b"{}");
+    }
+}
@@ -0,0 +1,7 @@
+pub mod connection;
+pub mod error;
+pub mod http;
+pub mod openapi;
+pub mod protocol;
+pub mod router;
+pub mod service;
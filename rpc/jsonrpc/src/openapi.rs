@@ -0,0 +1,198 @@
+//!
+//! OpenAPI document of the JSON-RPC surface, generated from the RPC model.
+//!
+
+use crate::{connection::MAX_BATCH_SIZE, router::ROUTED_OPS};
+use schemars::gen::SchemaSettings;
+use serde_json::{json, Map, Value};
+use std::sync::LazyLock;
+use vecno_rpc_core::{
+    api::ops::RpcApiOps,
+    model::schema::{op_schema, RpcOpSchema},
+};
+
+/// Path of the OpenAPI document served by the JSON-RPC server
+pub const OPENAPI_PATH: &str = "/openapi.json";
+
+/// The OpenAPI document, encoded once on first use
+pub static OPENAPI_DOCUMENT: LazyLock<Vec<u8>> =
+    LazyLock::new(|| serde_json::to_vec_pretty(&openapi_document()).expect("the OpenAPI document is serializable"));
+
+/// Returns the JSON-RPC method name of `op`
+pub fn method_name(op: RpcApiOps) -> String {
+    match serde_json::to_value(op) {
+        Ok(Value::String(name)) => name,
+        _ => unreachable!("RpcApiOps serializes as a string"),
+    }
+}
+
+fn component_ref(name: &str) -> Value {
+    json!({ "$ref": format!("#/components/schemas/{name}") })
+}
+
+/// Builds the OpenAPI 3.0 document describing the JSON-RPC calls of every routed method.
+///
+/// Each method gets a `JsonRpc<Method>Call` envelope carrying its request as `params` and a `JsonRpc<Method>Result`
+/// envelope carrying its response as `result`, while the model types live in the components under their Rust names.
+pub fn openapi_document() -> Value {
+    let mut generator = SchemaSettings::openapi3().into_generator();
+    let mut schemas = Map::new();
+    let mut calls = Vec::with_capacity(ROUTED_OPS.len());
+    let mut replies = Vec::with_capacity(ROUTED_OPS.len() + 1);
+
+    for op in ROUTED_OPS.iter().copied() {
+        let Some(RpcOpSchema::Method { request, response }) = op_schema(op, &mut generator) else {
+            unreachable!("routed op {op:?} is a method");
+        };
+        let method = method_name(op);
+        let call = format!("JsonRpc{op:?}Call");
+        let reply = format!("JsonRpc{op:?}Result");
+        schemas.insert(
+            call.clone(),
+            json!({
+                "type": "object",
+                "required": ["jsonrpc", "method"],
+                "properties": {
+                    "jsonrpc": component_ref("JsonRpcVersion"),
+                    "id": component_ref("JsonRpcId"),
+                    "method": { "type": "string", "enum": [method] },
+                    "params": request,
+                },
+            }),
+        );
+        schemas.insert(
+            reply.clone(),
+            json!({
+                "type": "object",
+                "required": ["jsonrpc", "id", "result"],
+                "properties": {
+                    "jsonrpc": component_ref("JsonRpcVersion"),
+                    "id": component_ref("JsonRpcId"),
+                    "result": response,
+                },
+            }),
+        );
+        calls.push(component_ref(&call));
+        replies.push(component_ref(&reply));
+    }
+    replies.push(component_ref("JsonRpcErrorResult"));
+
+    schemas.insert("JsonRpcVersion".to_owned(), json!({ "type": "string", "enum": ["2.0"] }));
+    schemas.insert(
+        "JsonRpcId".to_owned(),
+        json!({
+            "description": "Identifier of the call echoed in its result, calls without one are notifications and get no result",
+            "oneOf": [{ "type": "string" }, { "type": "number" }],
+            "nullable": true,
+        }),
+    );
+    schemas.insert(
+        "JsonRpcErrorResult".to_owned(),
+        json!({
+            "type": "object",
+            "required": ["jsonrpc", "id", "error"],
+            "properties": {
+                "jsonrpc": component_ref("JsonRpcVersion"),
+                "id": component_ref("JsonRpcId"),
+                "error": {
+                    "type": "object",
+                    "required": ["code", "message"],
+                    "properties": {
+                        "code": { "type": "integer" },
+                        "message": { "type": "string" },
+                    },
+                },
+            },
+        }),
+    );
+    schemas.insert("JsonRpcCall".to_owned(), json!({ "oneOf": calls }));
+    schemas.insert("JsonRpcResult".to_owned(), json!({ "oneOf": replies }));
+    for (name, schema) in generator.take_definitions() {
+        schemas.insert(name, serde_json::to_value(schema).expect("schemas are serializable"));
+    }
+
+    let batch = |item: &str| {
+        json!({
+            "oneOf": [
+                component_ref(item),
+                { "type": "array", "items": component_ref(item), "minItems": 1, "maxItems": MAX_BATCH_SIZE },
+            ],
+        })
+    };
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "Vecno JSON-RPC",
+            "description": "JSON-RPC 2.0 interface of the Vecno node. Every method is called by a POST of a single call or a batch of calls.",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "paths": {
+            "/": {
+                "post": {
+                    "operationId": "call",
+                    "requestBody": {
+                        "required": true,
+                        "content": { "application/json": { "schema": batch("JsonRpcCall") } },
+                    },
+                    "responses": {
+                        "200": {
+                            "description": "Results of the calls, in the shape of the request",
+                            "content": { "application/json": { "schema": batch("JsonRpcResult") } },
+                        },
+                        "204": { "description": "The request only held notifications" },
+                        "401": { "description": "Missing or invalid bearer token" },
+                    },
+                },
+            },
+        },
+        "components": {
+            "schemas": schemas,
+            "securitySchemes": { "bearer": { "type": "http", "scheme": "bearer" } },
+        },
+        "security": [{}, { "bearer": [] }],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect_refs<'a>(value: &'a Value, refs: &mut Vec<&'a str>) {
+        match value {
+            Value::Object(map) => map.iter().for_each(|(key, value)| match (key.as_str(), value) {
+                ("$ref", Value::String(reference)) => refs.push(reference),
+                _ => collect_refs(value, refs),
+            }),
+            Value::Array(values) => values.iter().for_each(|value| collect_refs(value, refs)),
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn test_openapi_document() {
+        let document = openapi_document();
+        let schemas = document["components"]["schemas"].as_object().unwrap();
+
+        for op in ROUTED_OPS {
+            let call = &schemas[&format!("JsonRpc{op:?}Call")];
+            assert_eq!(call["properties"]["method"]["enum"][0], Value::String(method_name(*op)));
+            assert!(call["properties"]["params"]["$ref"].is_string(), "{op:?} has no request schema");
+            assert!(
+                schemas[&format!("JsonRpc{op:?}Result")]["properties"]["result"]["$ref"].is_string(),
+                "{op:?} has no response schema"
+            );
+        }
+        assert_eq!(document["components"]["schemas"]["JsonRpcCall"]["oneOf"].as_array().unwrap().len(), ROUTED_OPS.len());
+
+        let mut refs = Vec::new();
+        collect_refs(&document, &mut refs);
+        assert!(!refs.is_empty());
+        for reference in refs {
+            let name = reference.strip_prefix("#/components/schemas/").unwrap_or_else(|| panic!("unexpected reference {reference}"));
+            assert!(schemas.contains_key(name), "unresolved reference {reference}");
+        }
+
+        assert!(serde_json::from_slice::<Value>(&OPENAPI_DOCUMENT).is_ok());
+    }
+}
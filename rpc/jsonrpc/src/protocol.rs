@@ -0,0 +1,159 @@
+//! JSON-RPC 2.0 messages.
+//!
+//! A request names an [`RpcApiOps`](vecno_rpc_core::api::ops::RpcApiOps) method in camelCase, e.g. `getBlockDagInfo`,
+//! and carries the fields of the matching request type as its `params` object. Requests without an `id` are
+//! notifications and get no response.
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+use thiserror::Error;
+use vecno_rpc_core::RpcError;
+
+pub const JSONRPC_VERSION: &str = "2.0";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request {
+    /// The request id, `None` for a notification
+    pub id: Option<Value>,
+    pub method: String,
+    pub params: Value,
+}
+
+impl TryFrom<Value> for Request {
+    type Error = JsonRpcError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        let Value::Object(mut object) = value else {
+            return Err(JsonRpcError::InvalidRequest("request is not an object".to_owned()));
+        };
+        if object.get("jsonrpc").and_then(Value::as_str) != Some(JSONRPC_VERSION) {
+            return Err(JsonRpcError::InvalidRequest(format!("jsonrpc member must be \"{JSONRPC_VERSION}\"")));
+        }
+        let Some(Value::String(method)) = object.remove("method") else {
+            return Err(JsonRpcError::InvalidRequest("method member must be a string".to_owned()));
+        };
+        let id = object.remove("id");
+        if id.as_ref().is_some_and(|id| !(id.is_string() || id.is_number() || id.is_null())) {
+            return Err(JsonRpcError::InvalidRequest("id member must be a string, a number or null".to_owned()));
+        }
+        let params = object.remove("params").unwrap_or(Value::Null);
+        Ok(Self { id, method, params })
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Response {
+    pub jsonrpc: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorObject>,
+    pub id: Value,
+}
+
+impl Response {
+    pub fn ok(id: Value, result: Value) -> Self {
+        Self { jsonrpc: JSONRPC_VERSION, result: Some(result), error: None, id }
+    }
+
+    pub fn error(id: Value, error: JsonRpcError) -> Self {
+        Self {
+            jsonrpc: JSONRPC_VERSION,
+            result: None,
+            error: Some(ErrorObject { code: error.code(), message: error.to_string() }),
+            id,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ErrorObject {
+    pub code: i32,
+    pub message: String,
+}
+
+/// Errors reported to the client, using the JSON-RPC 2.0 error codes and the implementation-defined
+/// server error range for the errors of the RPC service
+#[derive(Debug, Clone, Error)]
+pub enum JsonRpcError {
+    #[error("Parse error: {0}")]
+    ParseError(String),
+
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+
+    #[error("Method not found: {0}")]
+    MethodNotFound(String),
+
+    #[error("Invalid params: {0}")]
+    InvalidParams(String),
+
+    #[error("Internal error: {0}")]
+    InternalError(String),
+
+    #[error("{0}")]
+    RpcError(#[from] RpcError),
+}
+
+impl JsonRpcError {
+    pub fn code(&self) -> i32 {
+        match self {
+            JsonRpcError::ParseError(_) => -32700,
+            JsonRpcError::InvalidRequest(_) => -32600,
+            JsonRpcError::MethodNotFound(_) => -32601,
+            JsonRpcError::InvalidParams(_) => -32602,
+            JsonRpcError::InternalError(_) => -32603,
+            JsonRpcError::RpcError(RpcError::Unauthenticated) => -32001,
+            JsonRpcError::RpcError(RpcError::PermissionDenied(_)) => -32002,
+            JsonRpcError::RpcError(RpcError::ConnectionRateLimitExceeded(..) | RpcError::IpRateLimitExceeded(..)) => -32003,
+            JsonRpcError::RpcError(_) => -32000,
+        }
+    }
+}
+
+pub type JsonRpcResult<T> = std::result::Result<T, JsonRpcError>;
+
+/// Returns the `params` object of a request, an omitted `params` standing for a request without fields
+pub fn params_object(params: Value) -> JsonRpcResult<Value> {
+    match params {
+        Value::Null => Ok(Value::Object(Map::new())),
+        Value::Object(_) => Ok(params),
+        _ => Err(JsonRpcError::InvalidParams("params must be an object holding the request fields".to_owned())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_request_parsing() {
+        let request = Request::try_from(json!({"jsonrpc": "2.0", "id": 1, "method": "getInfo"})).unwrap();
+        assert_eq!(request, Request { id: Some(json!(1)), method: "getInfo".to_owned(), params: Value::Null });
+
+        let notification = Request::try_from(json!({"jsonrpc": "2.0", "method": "ping", "params": {}})).unwrap();
+        assert_eq!(notification.id, None);
+        let null_id = Request::try_from(json!({"jsonrpc": "2.0", "id": null, "method": "ping"})).unwrap();
+        assert_eq!(null_id.id, Some(Value::Null));
+
+        assert!(Request::try_from(json!({"jsonrpc": "1.0", "id": 1, "method": "getInfo"})).is_err());
+        assert!(Request::try_from(json!({"jsonrpc": "2.0", "id": 1, "method": 5})).is_err());
+        assert!(Request::try_from(json!({"jsonrpc": "2.0", "id": [1], "method": "getInfo"})).is_err());
+        assert!(Request::try_from(json!([1, 2])).is_err());
+
+        assert_eq!(params_object(Value::Null).unwrap(), json!({}));
+        assert!(params_object(json!([1])).is_err());
+    }
+
+    #[test]
+    fn test_response_serialization() {
+        let response = Response::ok(json!(1), json!({"blockCount": 2}));
+        assert_eq!(serde_json::to_value(response).unwrap(), json!({"jsonrpc": "2.0", "result": {"blockCount": 2}, "id": 1}));
+        let response = Response::error(json!("a"), JsonRpcError::MethodNotFound("foo".to_owned()));
+        assert_eq!(
+            serde_json::to_value(response).unwrap(),
+            json!({"jsonrpc": "2.0", "error": {"code": -32601, "message": "Method not found: foo"}, "id": "a"})
+        );
+    }
+}
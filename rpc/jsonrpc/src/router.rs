@@ -0,0 +1,111 @@
+//!
+//! Routing of the JSON-RPC methods to the [`RpcApi`] implementation of the node.
+//!
+
+use crate::protocol::{params_object, JsonRpcError, JsonRpcResult};
+use serde_json::Value;
+use vecno_rpc_core::api::{ops::RpcApiOps, rpc::RpcApi};
+use vecno_rpc_service::service::RpcCoreService;
+
+/// Builds the [`ROUTED_OPS`] table and the [`route()`] function out of a list of [`RpcApiOps`] variants.
+///
+/// `GetInfo` yields a call to `get_info_call()` decoding a `GetInfoRequest` and encoding a `GetInfoResponse`.
+macro_rules! build_router {
+    ([$($op:ident),* $(,)?]) => {
+        paste::paste! {
+            /// The RPC methods callable over JSON-RPC, i.e. every method except the notification subscriptions
+            pub const ROUTED_OPS: &[RpcApiOps] = &[$(RpcApiOps::$op),*];
+
+            /// Decodes `params` as the request of `op`, calls the RPC service and encodes its response
+            pub async fn route(service: &RpcCoreService, op: RpcApiOps, params: Value) -> JsonRpcResult<Value> {
+                match op {
+                    $(
+                        RpcApiOps::$op => {
+                            let request: vecno_rpc_core::[<$op Request>] = serde_json::from_value(params_object(params)?)
+                                .map_err(|err| JsonRpcError::InvalidParams(err.to_string()))?;
+                            let response = service.[<$op:snake _call>](None, request).await?;
+                            serde_json::to_value(response).map_err(|err| JsonRpcError::InternalError(err.to_string()))
+                        }
+                    )*
+                    _ => Err(JsonRpcError::MethodNotFound(format!("{op:?}"))),
+                }
+            }
+        }
+    };
+}
+
+build_router!([
+    Ping,
+    AddPeer,
+    Ban,
+    EstimateNetworkHashesPerSecond,
+    GetBalanceByAddress,
+    GetBalancesByAddresses,
+    GetBlock,
+    GetBlockCount,
+    GetBlockDagInfo,
+    GetBlocks,
+    GetBlockTemplate,
+    GetCurrentBlockColor,
+    GetCoinSupply,
+    GetConnectedPeerInfo,
+    GetDaaScoreTimestampEstimate,
+    GetUtxoReturnAddress,
+    GetTransaction,
+    GetAddressHistory,
+    SubmitPackage,
+    GetVirtualChainChunk,
+    GetFeeEstimateHistory,
+    GetTransactionStatus,
+    GetCurrentNetwork,
+    GetFeeEstimate,
+    GetFeeEstimateExperimental,
+    GetHeaders,
+    GetInfo,
+    GetMempoolEntries,
+    GetMempoolEntriesByAddresses,
+    GetMempoolEntry,
+    GetMetrics,
+    GetConnections,
+    GetPeerAddresses,
+    GetServerInfo,
+    GetSink,
+    GetSinkBlueScore,
+    GetSubnetwork,
+    GetSyncStatus,
+    GetSystemInfo,
+    GetUtxosByAddresses,
+    GetVirtualChainFromBlock,
+    ResolveFinalityConflict,
+    Shutdown,
+    SubmitBlock,
+    SubmitTransaction,
+    SubmitTransactionReplacement,
+    Unban,
+]);
+
+/// Returns the routed op named `method`, which is the camelCase name of the [`RpcApiOps`] variant
+pub fn routed_op(method: &str) -> Option<RpcApiOps> {
+    serde_json::from_value::<RpcApiOps>(Value::String(method.to_owned())).ok().filter(|op| ROUTED_OPS.contains(op))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_routed_ops() {
+        // RPC methods start at discriminant 110, below are the control, subscription and notification ops
+        for op in RpcApiOps::iter().filter(|op| u32::from(**op) >= 110) {
+            assert!(ROUTED_OPS.contains(op), "{op:?} is not routed");
+        }
+        assert!(ROUTED_OPS.iter().all(|op| !op.is_subscription()));
+
+        assert_eq!(routed_op("getBlockDagInfo"), Some(RpcApiOps::GetBlockDagInfo));
+        assert_eq!(routed_op("estimateNetworkHashesPerSecond"), Some(RpcApiOps::EstimateNetworkHashesPerSecond));
+        assert_eq!(routed_op("GetBlockDagInfo"), None);
+        assert_eq!(routed_op("subscribe"), None);
+        assert_eq!(routed_op("notifyBlockAdded"), None);
+        assert_eq!(routed_op("blockAddedNotification"), None);
+    }
+}
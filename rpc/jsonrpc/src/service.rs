@@ -0,0 +1,148 @@
+use crate::{
+    connection::Connection,
+    error::{JsonRpcServerError, JsonRpcServerResult},
+};
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+use tokio::{
+    net::{TcpListener, TcpStream},
+    sync::{OwnedSemaphorePermit, Semaphore},
+    time::timeout,
+};
+use tokio_rustls::{rustls, TlsAcceptor};
+use vecno_core::{
+    debug, info,
+    task::service::{AsyncService, AsyncServiceError, AsyncServiceFuture},
+    trace, warn,
+};
+use vecno_rpc_core::api::{
+    auth::{RpcAuthenticator, RpcTlsIdentity},
+    rate_limit::RpcRateLimiter,
+};
+use vecno_rpc_service::service::RpcCoreService;
+use vecno_utils::{networking::NetAddress, triggers::SingleTrigger};
+
+/// Maximum duration of the TLS handshake of a connection
+const TLS_ACCEPT_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub struct JsonRpcOptions {
+    /// Authenticator of the clients, admitting all of them if no token is registered
+    pub authenticator: Arc<RpcAuthenticator>,
+    /// Identity of the server, which accepts only HTTPS connections when set
+    pub tls: Option<RpcTlsIdentity>,
+    /// Request rate limits of the clients, shared with the other RPC servers
+    pub rate_limiter: Arc<RpcRateLimiter>,
+    /// Maximum number of connections served at once
+    pub max_clients: usize,
+}
+
+impl Default for JsonRpcOptions {
+    fn default() -> Self {
+        Self { authenticator: Default::default(), tls: None, rate_limiter: Default::default(), max_clients: 128 }
+    }
+}
+
+/// JSON-RPC 2.0 over HTTP server dispatching the calls to the RPC core service
+pub struct JsonRpcService {
+    net_address: NetAddress,
+    core_service: Arc<RpcCoreService>,
+    options: JsonRpcOptions,
+    acceptor: Option<TlsAcceptor>,
+    /// Permits of the connections served at once, from their acceptance to their closing
+    connections: Arc<Semaphore>,
+    shutdown: SingleTrigger,
+}
+
+impl JsonRpcService {
+    pub const IDENT: &'static str = "jsonrpc-service";
+
+    pub fn new(address: NetAddress, core_service: Arc<RpcCoreService>, options: JsonRpcOptions) -> JsonRpcServerResult<Self> {
+        let acceptor = options.tls.as_ref().map(Self::tls_acceptor).transpose()?;
+        let connections = Arc::new(Semaphore::new(options.max_clients));
+        Ok(Self { net_address: address, core_service, options, acceptor, connections, shutdown: Default::default() })
+    }
+
+    fn tls_acceptor(tls: &RpcTlsIdentity) -> JsonRpcServerResult<TlsAcceptor> {
+        let certs = rustls_pemfile::certs(&mut tls.cert.as_slice())
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|err| JsonRpcServerError::TlsConfigError(err.to_string()))?;
+        let key = rustls_pemfile::private_key(&mut tls.key.as_slice())
+            .map_err(|err| JsonRpcServerError::TlsConfigError(err.to_string()))?
+            .ok_or_else(|| JsonRpcServerError::TlsConfigError("no private key found".to_string()))?;
+        let config = rustls::ServerConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .and_then(|builder| builder.with_no_client_auth().with_single_cert(certs, key))
+            .map_err(|err| JsonRpcServerError::TlsConfigError(err.to_string()))?;
+        Ok(TlsAcceptor::from(Arc::new(config)))
+    }
+
+    fn spawn_connection(&self, stream: TcpStream, address: SocketAddr, permit: OwnedSemaphorePermit) {
+        let rate_limiter = self.options.rate_limiter.connection_limiter(address.ip());
+        let connection = Connection::new(address, self.core_service.clone(), self.options.authenticator.clone(), rate_limiter);
+        let acceptor = self.acceptor.clone();
+        let shutdown = self.shutdown.listener.clone();
+        tokio::spawn(async move {
+            let result = match acceptor {
+                Some(acceptor) => match timeout(TLS_ACCEPT_TIMEOUT, acceptor.accept(stream)).await {
+                    Ok(Ok(stream)) => connection.run(stream, shutdown).await,
+                    Ok(Err(err)) => Err(err.into()),
+                    Err(_) => Err(JsonRpcServerError::Timeout("TLS handshake")),
+                },
+                None => connection.run(stream, shutdown).await,
+            };
+            match result {
+                Ok(()) => trace!("JSON-RPC connection {} closed", address),
+                Err(err) => debug!("JSON-RPC connection {} closed: {}", address, err),
+            }
+            drop(permit);
+        });
+    }
+}
+
+impl AsyncService for JsonRpcService {
+    fn ident(self: Arc<Self>) -> &'static str {
+        Self::IDENT
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        trace!("{} starting", Self::IDENT);
+
+        // Prepare a shutdown signal receiver
+        let shutdown_signal = self.shutdown.listener.clone();
+
+        Box::pin(async move {
+            let listener = TcpListener::bind(SocketAddr::from(self.net_address)).await.map_err(|err| {
+                AsyncServiceError::Service(format!("JSON-RPC server failed listening on {}: {}", self.net_address, err))
+            })?;
+            info!("JSON-RPC Server listening on {}{}", self.net_address, if self.acceptor.is_some() { " (TLS)" } else { "" });
+
+            loop {
+                tokio::select! {
+                    _ = shutdown_signal.clone() => break,
+                    accepted = listener.accept() => match accepted {
+                        Ok((stream, address)) => match self.connections.clone().try_acquire_owned() {
+                            Ok(permit) => {
+                                let _ = stream.set_nodelay(true);
+                                self.spawn_connection(stream, address, permit);
+                            }
+                            Err(_) => debug!("JSON-RPC server refused the connection of {}: too many connections", address),
+                        },
+                        Err(err) => warn!("JSON-RPC server failed accepting a connection: {}", err),
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", Self::IDENT);
+        self.shutdown.trigger.trigger();
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            trace!("{} stopped", Self::IDENT);
+            Ok(())
+        })
+    }
+}
@@ -1,6 +1,8 @@
-use crate::converter::{consensus::ConsensusConverter, index::IndexConverter};
+use crate::converter::{consensus::ConsensusConverter, index::IndexConverter, mining::MiningConverter};
 use vecno_notify::collector::CollectorFrom;
 
 pub(crate) type CollectorFromConsensus = CollectorFrom<ConsensusConverter>;
 
 pub(crate) type CollectorFromIndex = CollectorFrom<IndexConverter>;
+
+pub(crate) type CollectorFromMining = CollectorFrom<MiningConverter>;
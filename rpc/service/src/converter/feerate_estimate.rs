@@ -1,6 +1,6 @@
-use vecno_mining::feerate::{FeeEstimateVerbose, FeerateBucket, FeerateEstimations};
+use vecno_mining::feerate::{history::BlockFeerateRecord, FeeEstimateVerbose, FeerateBucket, FeerateEstimations};
 use vecno_rpc_core::{
-    message::GetFeeEstimateExperimentalResponse as RpcFeeEstimateVerboseResponse, RpcFeeEstimate,
+    message::GetFeeEstimateExperimentalResponse as RpcFeeEstimateVerboseResponse, RpcBlockFeerateRecord, RpcFeeEstimate,
     RpcFeeEstimateVerboseExperimentalData as RpcFeeEstimateVerbose, RpcFeerateBucket,
 };
 
@@ -10,7 +10,7 @@ pub trait FeerateBucketConverter {
 
 impl FeerateBucketConverter for FeerateBucket {
     fn into_rpc(self) -> RpcFeerateBucket {
-        RpcFeerateBucket { feerate: self.feerate, estimated_seconds: self.estimated_seconds }
+        RpcFeerateBucket { feerate: self.feerate, estimated_seconds: self.estimated_seconds, confidence: self.confidence }
     }
 }
 
@@ -28,6 +28,24 @@ impl FeeEstimateConverter for FeerateEstimations {
     }
 }
 
+pub trait BlockFeerateRecordConverter {
+    fn into_rpc(self) -> RpcBlockFeerateRecord;
+}
+
+impl BlockFeerateRecordConverter for BlockFeerateRecord {
+    fn into_rpc(self) -> RpcBlockFeerateRecord {
+        RpcBlockFeerateRecord {
+            daa_score: self.daa_score,
+            timestamp: self.timestamp,
+            transaction_count: self.transaction_count,
+            min_feerate: self.min_feerate,
+            median_feerate: self.median_feerate,
+            max_feerate: self.max_feerate,
+            median_waited_seconds: self.median_waited_seconds,
+        }
+    }
+}
+
 pub trait FeeEstimateVerboseConverter {
     fn into_rpc(self) -> RpcFeeEstimateVerboseResponse;
 }
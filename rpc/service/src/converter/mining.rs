@@ -0,0 +1,45 @@
+use async_trait::async_trait;
+use vecno_mining::model::tx_status::{TransactionStatus, TransactionStatusChange};
+use vecno_notify::converter::Converter;
+use vecno_rpc_core::{
+    Notification, RpcTransactionId, RpcTransactionState, RpcTransactionStatus, TransactionStatusChangedNotification,
+};
+
+/// Conversion of mining to rpc_core structures
+#[derive(Debug, Default)]
+pub struct MiningConverter {}
+
+impl MiningConverter {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn get_transaction_status(&self, transaction_id: RpcTransactionId, status: TransactionStatus) -> RpcTransactionStatus {
+        match status {
+            TransactionStatus::InMempool => RpcTransactionStatus::new(transaction_id, RpcTransactionState::InMempool),
+            TransactionStatus::Orphan => RpcTransactionStatus::new(transaction_id, RpcTransactionState::Orphan),
+            TransactionStatus::Included { block_daa_score } => {
+                RpcTransactionStatus::new(transaction_id, RpcTransactionState::Included)
+                    .with_including_block_daa_score(block_daa_score)
+            }
+            TransactionStatus::Rejected { reason } => {
+                RpcTransactionStatus::new(transaction_id, RpcTransactionState::Rejected).with_reason(reason)
+            }
+            TransactionStatus::Evicted { reason } => {
+                RpcTransactionStatus::new(transaction_id, RpcTransactionState::Evicted).with_reason(reason)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Converter for MiningConverter {
+    type Incoming = TransactionStatusChange;
+    type Outgoing = Notification;
+
+    async fn convert(&self, incoming: TransactionStatusChange) -> Notification {
+        Notification::TransactionStatusChanged(TransactionStatusChangedNotification {
+            status: self.get_transaction_status(incoming.transaction_id, incoming.status),
+        })
+    }
+}
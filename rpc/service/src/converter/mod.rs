@@ -1,4 +1,5 @@
 pub mod consensus;
 pub mod feerate_estimate;
 pub mod index;
+pub mod mining;
 pub mod protocol;
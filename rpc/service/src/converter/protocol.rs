@@ -25,6 +25,7 @@ impl ProtocolConverter {
             user_agent: properties.user_agent.clone(),
             advertised_protocol_version: properties.advertised_protocol_version,
             time_connected: peer.time_connected(),
+            misbehavior_score: self.flow_context.misbehavior_score(peer.net_address().ip()),
         }
     }
 
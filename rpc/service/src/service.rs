@@ -1,9 +1,14 @@
 //! Core server implementation for ClientAPI
 
-use super::collector::{CollectorFromConsensus, CollectorFromIndex};
-use crate::converter::feerate_estimate::{FeeEstimateConverter, FeeEstimateVerboseConverter};
-use crate::converter::{consensus::ConsensusConverter, index::IndexConverter, protocol::ProtocolConverter};
+use super::collector::{CollectorFromConsensus, CollectorFromIndex, CollectorFromMining};
+use super::subscriber::MiningSubscriptionManager;
+use crate::converter::feerate_estimate::{BlockFeerateRecordConverter, FeeEstimateConverter, FeeEstimateVerboseConverter};
+use crate::converter::{consensus::ConsensusConverter, index::IndexConverter, mining::MiningConverter, protocol::ProtocolConverter};
 use async_trait::async_trait;
+use vecno_addressindex::{
+    api::AddressIndexProxy,
+    model::{AddressActivityKind, AddressHistoryCursor},
+};
 use vecno_consensus_core::api::counters::ProcessingCounters;
 use vecno_consensus_core::daa_score_timestamp::DaaScoreTimestamp;
 use vecno_consensus_core::errors::block::RuleError;
@@ -15,13 +20,14 @@ use vecno_consensus_core::{
     config::Config,
     constants::MAX_VENI,
     network::NetworkType,
-    tx::{Transaction, COINBASE_TRANSACTION_INDEX},
+    tx::{Transaction, TransactionOutpoint, COINBASE_TRANSACTION_INDEX},
+    ChainPath,
 };
 use vecno_consensus_notify::{
     notifier::ConsensusNotifier,
     {connection::ConsensusChannelConnection, notification::Notification as ConsensusNotification},
 };
-use vecno_consensusmanager::ConsensusManager;
+use vecno_consensusmanager::{ConsensusManager, ConsensusProxy};
 use vecno_core::time::unix_now;
 use vecno_core::{
     core::Core,
@@ -38,7 +44,10 @@ use vecno_index_core::{
     notifier::IndexNotifier,
 };
 use vecno_mining::feerate::FeeEstimateVerbose;
+use vecno_mining::model::mempool_entries::{MempoolEntriesCursor, MempoolEntriesOrder, MempoolEntriesQuery};
 use vecno_mining::model::tx_query::TransactionQuery;
+use vecno_mining::model::tx_selection::TransactionSelection;
+use vecno_mining::model::tx_status::{TransactionStatus, TransactionStatusChange};
 use vecno_mining::{manager::MiningManagerProxy, mempool::tx::Orphan};
 use vecno_notify::listener::ListenerLifespan;
 use vecno_notify::subscription::context::SubscriptionContext;
@@ -46,10 +55,12 @@ use vecno_notify::subscription::{MutationPolicies, UtxosChangedMutationPolicy};
 use vecno_notify::{
     collector::DynCollector,
     connection::ChannelType,
+    error::{Error as NotifyError, Result as NotifyResult},
     events::{EventSwitches, EventType, EVENT_TYPE_ARRAY},
     listener::ListenerId,
     notifier::Notifier,
-    scope::Scope,
+    replay::Replayer,
+    scope::{ResumeToken, Scope, UtxosChangedScope, VirtualChainChangedScope},
     subscriber::{Subscriber, SubscriptionManager},
 };
 use vecno_p2p_flows::flow_context::FlowContext;
@@ -60,21 +71,29 @@ use vecno_rpc_core::{
     api::{
         connection::DynRpcConnection,
         ops::{RPC_API_REVISION, RPC_API_VERSION},
-        rpc::{RpcApi, MAX_SAFE_WINDOW_SIZE},
+        rate_limit::RpcRateLimitCounters,
+        rpc::{
+            RpcApi, DEFAULT_BLOCK_TEMPLATE_LONG_POLL_TIMEOUT, MAX_ADDRESS_HISTORY_PAGE_SIZE, MAX_BLOCK_TEMPLATE_EXCLUDED_TRANSACTIONS,
+            MAX_BLOCK_TEMPLATE_LONG_POLL_TIMEOUT, MAX_BLOCK_TEMPLATE_MANDATORY_TRANSACTIONS, MAX_FEE_ESTIMATE_HISTORY_SIZE,
+            MAX_MEMPOOL_ENTRIES_PAGE_SIZE, MAX_SAFE_WINDOW_SIZE, MAX_VIRTUAL_CHAIN_CHUNK_SIZE, MAX_VIRTUAL_CHAIN_CHUNK_TRANSACTIONS,
+        },
     },
     model::*,
     notify::connection::ChannelConnection,
     Notification, RpcError, RpcResult,
 };
+use vecno_txindex::api::TxIndexProxy;
 use vecno_txscript::{extract_script_pub_key_address, pay_to_address_script};
 use vecno_utils::expiring_cache::ExpiringCache;
+use vecno_utils::hex::{FromHex, ToHex};
 use vecno_utils::sysinfo::SystemInfo;
 use vecno_utils::{channel::Channel, triggers::SingleTrigger};
 use vecno_utils_tower::counters::TowerConnectionCounters;
 use vecno_utxoindex::api::UtxoIndexProxy;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
+    fmt::Debug,
     iter::once,
     sync::{atomic::Ordering, Arc},
     vec,
@@ -105,9 +124,13 @@ pub struct RpcCoreService {
     mining_manager: MiningManagerProxy,
     flow_context: Arc<FlowContext>,
     utxoindex: Option<UtxoIndexProxy>,
+    txindex: Option<TxIndexProxy>,
+    addressindex: Option<AddressIndexProxy>,
     config: Arc<Config>,
     consensus_converter: Arc<ConsensusConverter>,
     index_converter: Arc<IndexConverter>,
+    mining_converter: Arc<MiningConverter>,
+    mining_notify_channel: Channel<TransactionStatusChange>,
     protocol_converter: Arc<ProtocolConverter>,
     core: Arc<Core>,
     processing_counters: Arc<ProcessingCounters>,
@@ -118,6 +141,7 @@ pub struct RpcCoreService {
     perf_monitor: Arc<PerfMonitor<Arc<TickService>>>,
     p2p_tower_counters: Arc<TowerConnectionCounters>,
     grpc_tower_counters: Arc<TowerConnectionCounters>,
+    rate_limit_counters: Arc<RpcRateLimitCounters>,
     system_info: SystemInfo,
     fee_estimate_cache: ExpiringCache<RpcFeeEstimate>,
     fee_estimate_verbose_cache: ExpiringCache<vecno_mining::errors::MiningManagerResult<GetFeeEstimateExperimentalResponse>>,
@@ -126,6 +150,13 @@ pub struct RpcCoreService {
 
 const RPC_CORE: &str = "rpc-core";
 
+/// Interval at which a long-polling `GetBlockTemplate` call rebuilds the template in the absence of any update signal
+const BLOCK_TEMPLATE_LONG_POLL_RECHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Maximum number of chain blocks whose acceptance data is scanned for the accepting block of a transaction
+/// when the transaction index is disabled
+const MAX_TRANSACTION_STATUS_CHAIN_SCAN: usize = 100;
+
 impl RpcCoreService {
     pub const IDENT: &'static str = "rpc-core-service";
 
@@ -138,6 +169,8 @@ impl RpcCoreService {
         flow_context: Arc<FlowContext>,
         subscription_context: SubscriptionContext,
         utxoindex: Option<UtxoIndexProxy>,
+        txindex: Option<TxIndexProxy>,
+        addressindex: Option<AddressIndexProxy>,
         config: Arc<Config>,
         core: Arc<Core>,
         processing_counters: Arc<ProcessingCounters>,
@@ -146,6 +179,7 @@ impl RpcCoreService {
         perf_monitor: Arc<PerfMonitor<Arc<TickService>>>,
         p2p_tower_counters: Arc<TowerConnectionCounters>,
         grpc_tower_counters: Arc<TowerConnectionCounters>,
+        rate_limit_counters: Arc<RpcRateLimitCounters>,
         system_info: SystemInfo,
         mining_rule_engine: Arc<MiningRuleEngine>,
     ) -> Self {
@@ -166,6 +200,7 @@ impl RpcCoreService {
         let mut consensus_events: EventSwitches = EVENT_TYPE_ARRAY[..].into();
         consensus_events[EventType::UtxosChanged] = false;
         consensus_events[EventType::PruningPointUtxoSetOverride] = index_notifier.is_none();
+        consensus_events[EventType::TransactionStatusChanged] = false;
         let consensus_converter = Arc::new(ConsensusConverter::new(consensus_manager.clone(), config.clone()));
         let consensus_collector = Arc::new(CollectorFromConsensus::new(
             "rpc-core <= consensus",
@@ -197,6 +232,18 @@ impl RpcCoreService {
             subscribers.push(index_subscriber);
         }
 
+        // Prepare mining objects
+        let mining_notify_channel = mining_manager.register_transaction_status_listener();
+        let mining_events: EventSwitches = [EventType::TransactionStatusChanged].as_ref().into();
+        let mining_converter = Arc::new(MiningConverter::new());
+        let mining_collector =
+            Arc::new(CollectorFromMining::new("rpc-core <= mining", mining_notify_channel.receiver(), mining_converter.clone()));
+        let mining_subscription_manager = Arc::new(MiningSubscriptionManager::new(mining_manager.clone()));
+        let mining_subscriber =
+            Arc::new(Subscriber::new("rpc-core => mining", mining_events, mining_subscription_manager, ListenerId::default()));
+        collectors.push(mining_collector);
+        subscribers.push(mining_subscriber);
+
         // Protocol converter
         let protocol_converter = Arc::new(ProtocolConverter::new(flow_context.clone()));
 
@@ -210,9 +257,13 @@ impl RpcCoreService {
             mining_manager,
             flow_context,
             utxoindex,
+            txindex,
+            addressindex,
             config,
             consensus_converter,
             index_converter,
+            mining_converter,
+            mining_notify_channel,
             protocol_converter,
             core,
             processing_counters,
@@ -223,6 +274,7 @@ impl RpcCoreService {
             perf_monitor,
             p2p_tower_counters,
             grpc_tower_counters,
+            rate_limit_counters,
             system_info,
             fee_estimate_cache: ExpiringCache::new(Duration::from_millis(500), Duration::from_millis(1000)),
             fee_estimate_verbose_cache: ExpiringCache::new(Duration::from_millis(500), Duration::from_millis(1000)),
@@ -236,6 +288,8 @@ impl RpcCoreService {
 
     pub async fn join(&self) -> RpcResult<()> {
         trace!("{} joining notifier", Self::IDENT);
+        // Unlike the consensus and index notifiers, the mining manager does not close its notification channel
+        self.mining_notify_channel.close();
         self.notifier().join().await?;
         Ok(())
     }
@@ -266,6 +320,168 @@ impl RpcCoreService {
             .unwrap_or_default()
     }
 
+    /// Returns the highest selected chain block whose DAA score does not exceed `daa_score`.
+    ///
+    /// The search narrows down the bracket of the DAA score through the exponentially spaced blocks
+    /// of successive chain block locators, until both ends of the bracket are adjacent.
+    async fn find_chain_block_by_daa_score(&self, session: &ConsensusProxy, daa_score: u64) -> Result<RpcHash, String> {
+        let (mut low, mut high) = (None, None);
+        loop {
+            // The locator is ordered from high to low
+            let locator = session.async_create_virtual_selected_chain_block_locator(low, high).await.map_err(|err| err.to_string())?;
+            let mut above = None;
+            let mut found = None;
+            for (index, hash) in locator.into_iter().enumerate() {
+                if session.async_get_header(hash).await.map_err(|err| err.to_string())?.daa_score <= daa_score {
+                    found = Some((index, hash));
+                    break;
+                }
+                above = Some(hash);
+            }
+            match found {
+                Some((index, hash)) if index <= 1 => return Ok(hash),
+                Some((_, hash)) => (low, high) = (Some(hash), above),
+                None => return Err("the DAA score precedes the pruning point".to_string()),
+            }
+        }
+    }
+
+    /// Returns the chain block accepting `transaction_id`, if any, among the chain blocks following the inclusion
+    /// of the transaction in a block with DAA score `daa_score`
+    async fn find_accepting_chain_block(
+        &self,
+        session: &ConsensusProxy,
+        transaction_id: RpcTransactionId,
+        daa_score: u64,
+    ) -> RpcResult<Option<RpcHash>> {
+        // The accepting chain block merges the including block, so it follows any chain block not exceeding its DAA score
+        let Ok(start_hash) = self.find_chain_block_by_daa_score(session, daa_score).await else { return Ok(None) };
+        let chain_path = session.async_get_virtual_chain_from_block(start_hash, Some(MAX_TRANSACTION_STATUS_CHAIN_SCAN)).await?;
+        for hash in chain_path.added {
+            let acceptance_data = session.async_get_block_acceptance_data(hash).await?;
+            if acceptance_data.iter().flat_map(|x| x.accepted_transactions.iter()).any(|x| x.transaction_id == transaction_id) {
+                return Ok(Some(hash));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the selected chain path from the position of `resume_from` to the sink
+    async fn get_resumed_chain_path(
+        &self,
+        session: &ConsensusProxy,
+        resume_from: &ResumeToken,
+        batch_size: usize,
+    ) -> Result<ChainPath, String> {
+        let start_hash = match resume_from {
+            ResumeToken::SinkHash(hash) => *hash,
+            ResumeToken::DaaScore(daa_score) => self.find_chain_block_by_daa_score(session, *daa_score).await?,
+        };
+        let chain_path =
+            session.async_get_virtual_chain_from_block(start_hash, Some(batch_size)).await.map_err(|err| err.to_string())?;
+        if chain_path.added.len() >= batch_size {
+            return Err(format!("the missed chain blocks exceed the replay limit of {batch_size}"));
+        }
+        Ok(chain_path)
+    }
+
+    async fn replay_virtual_chain_changed(
+        &self,
+        session: &ConsensusProxy,
+        scope: &VirtualChainChangedScope,
+        resume_from: &ResumeToken,
+        batch_size: usize,
+    ) -> Result<Vec<Notification>, String> {
+        let chain_path = self.get_resumed_chain_path(session, resume_from, batch_size).await?;
+        if chain_path.added.is_empty() && chain_path.removed.is_empty() {
+            return Ok(vec![]);
+        }
+        let accepted_transaction_ids = if scope.include_accepted_transaction_ids {
+            let accepted_transaction_ids = self
+                .consensus_converter
+                .get_virtual_chain_accepted_transaction_ids(session, &chain_path, Some(batch_size))
+                .await
+                .map_err(|err| err.to_string())?;
+            if accepted_transaction_ids.len() < chain_path.added.len() {
+                return Err(format!("the blocks merged by the missed chain blocks exceed the replay limit of {batch_size}"));
+            }
+            accepted_transaction_ids
+        } else {
+            vec![]
+        };
+        Ok(vec![Notification::VirtualChainChanged(VirtualChainChangedNotification {
+            removed_chain_block_hashes: Arc::new(chain_path.removed),
+            added_chain_block_hashes: Arc::new(chain_path.added),
+            accepted_transaction_ids: Arc::new(accepted_transaction_ids),
+        })])
+    }
+
+    /// Replays the UTXO changes of the scope addresses, one notification per chain block holding some.
+    ///
+    /// The changes of the chain blocks removed since `resume_from` are reverted first.
+    async fn replay_utxos_changed(
+        &self,
+        session: &ConsensusProxy,
+        scope: &UtxosChangedScope,
+        resume_from: &ResumeToken,
+        batch_size: usize,
+    ) -> Result<Vec<Notification>, String> {
+        if scope.addresses.is_empty() {
+            return Err("the UTXO changes of all addresses cannot be replayed".to_string());
+        }
+        let script_public_keys: HashSet<_> = scope.addresses.iter().map(pay_to_address_script).collect();
+        let chain_path = self.get_resumed_chain_path(session, resume_from, batch_size).await?;
+        let chain_blocks =
+            chain_path.removed.iter().map(|hash| (*hash, true)).chain(chain_path.added.iter().map(|hash| (*hash, false)));
+
+        let mut notifications = vec![];
+        for (hash, reverted) in chain_blocks {
+            let daa_score = session.async_get_header(hash).await.map_err(|err| err.to_string())?.daa_score;
+            let transactions = session.async_get_populated_accepted_transactions(hash).await.map_err(|err| err.to_string())?;
+            let (mut spent, mut created) = (vec![], vec![]);
+            for populated_tx in transactions.iter() {
+                let transaction_id = populated_tx.tx.id();
+                for (input, utxo_entry) in populated_tx.tx.inputs.iter().zip(populated_tx.entries.iter()) {
+                    let Some(utxo_entry) = utxo_entry.as_ref().filter(|x| script_public_keys.contains(&x.script_public_key)) else {
+                        continue;
+                    };
+                    spent.push(RpcUtxosByAddressesEntry {
+                        address: extract_script_pub_key_address(&utxo_entry.script_public_key, self.config.prefix()).ok(),
+                        outpoint: input.previous_outpoint.into(),
+                        utxo_entry: RpcUtxoEntry::new(
+                            utxo_entry.amount,
+                            utxo_entry.script_public_key.clone(),
+                            utxo_entry.block_daa_score,
+                            utxo_entry.is_coinbase,
+                        ),
+                    });
+                }
+                for (index, output) in populated_tx.tx.outputs.iter().enumerate() {
+                    if !script_public_keys.contains(&output.script_public_key) {
+                        continue;
+                    }
+                    created.push(RpcUtxosByAddressesEntry {
+                        address: extract_script_pub_key_address(&output.script_public_key, self.config.prefix()).ok(),
+                        outpoint: TransactionOutpoint::new(transaction_id, index as u32).into(),
+                        utxo_entry: RpcUtxoEntry::new(
+                            output.value,
+                            output.script_public_key.clone(),
+                            daa_score,
+                            populated_tx.tx.is_coinbase(),
+                        ),
+                    });
+                }
+            }
+            if spent.is_empty() && created.is_empty() {
+                continue;
+            }
+            let (added, removed) = if reverted { (spent, created) } else { (created, spent) };
+            notifications
+                .push(Notification::UtxosChanged(UtxosChangedNotification { added: Arc::new(added), removed: Arc::new(removed) }));
+        }
+        Ok(notifications)
+    }
+
     async fn get_balance_by_script_public_key<'a>(&self, addresses: impl Iterator<Item = &'a RpcAddress>) -> BalanceByScriptPublicKey {
         self.utxoindex
             .clone()
@@ -431,12 +647,71 @@ NOTE: This error usually indicates an RPC conversion error between the node and
             return Err(vecno_addresses::AddressError::InvalidPrefix(request.pay_address.prefix.to_string()))?;
         }
 
+        // Bound the transaction selection, which is processed on every build of the template
+        let mandatory_count = request.mandatory_transaction_ids.len() + request.mandatory_transactions.len();
+        if mandatory_count > MAX_BLOCK_TEMPLATE_MANDATORY_TRANSACTIONS {
+            return Err(RpcError::MandatoryTransactionsExceedingMaximum(mandatory_count, MAX_BLOCK_TEMPLATE_MANDATORY_TRANSACTIONS));
+        }
+        if request.excluded_transaction_ids.len() > MAX_BLOCK_TEMPLATE_EXCLUDED_TRANSACTIONS {
+            return Err(RpcError::ExcludedTransactionsExceedingMaximum(
+                request.excluded_transaction_ids.len(),
+                MAX_BLOCK_TEMPLATE_EXCLUDED_TRANSACTIONS,
+            ));
+        }
+
         // Build block template
         let script_public_key = vecno_txscript::pay_to_address_script(&request.pay_address);
         let extra_data = version().as_bytes().iter().chain(once(&(b'/'))).chain(&request.extra_data).cloned().collect::<Vec<_>>();
         let miner_data: MinerData = MinerData::new(script_public_key, extra_data);
-        let session = self.consensus_manager.consensus().unguarded_session();
-        let block_template = self.mining_manager.clone().get_block_template(&session, miner_data).await?;
+        let selection = TransactionSelection::new(
+            request.mandatory_transaction_ids,
+            request.mandatory_transactions.into_iter().map(Transaction::try_from).collect::<Result<Vec<_>, _>>()?,
+            request.excluded_transaction_ids,
+        );
+
+        // Subscribe before building so that no template change goes unnoticed while long-polling
+        let mut template_updates = self.mining_manager.block_template_updates();
+        let long_poll_deadline = request.long_poll_id.as_ref().map(|_| {
+            let timeout = request.long_poll_timeout.unwrap_or(DEFAULT_BLOCK_TEMPLATE_LONG_POLL_TIMEOUT);
+            Instant::now() + Duration::from_millis(timeout.min(MAX_BLOCK_TEMPLATE_LONG_POLL_TIMEOUT))
+        });
+        // A template honoring a transaction selection is built anew only when the shared template id changes, so that
+        // long-polling callers do not each rebuild theirs on every recheck
+        let mut selection_template = None;
+        let (block_template, template_id, rejected_transactions) = loop {
+            let session = self.consensus_manager.consensus().unguarded_session();
+            let (block_template, template_id) =
+                self.mining_manager.clone().get_block_template_with_id(&session, miner_data.clone()).await?;
+            let (block_template, template_id, rejected_transactions) = if selection.is_empty() {
+                (block_template, template_id, vec![])
+            } else {
+                let built = match selection_template.take() {
+                    Some((shared_template_id, built)) if shared_template_id == template_id => built,
+                    _ => {
+                        self.mining_manager
+                            .clone()
+                            .get_block_template_with_selection(&session, miner_data.clone(), selection.clone())
+                            .await?
+                    }
+                };
+                selection_template = Some((template_id, built.clone()));
+                built
+            };
+            drop(session);
+            let template_id = format!("{template_id:016x}");
+            let unchanged = request.long_poll_id.as_deref() == Some(template_id.as_str());
+            match long_poll_deadline {
+                Some(deadline) if unchanged && Instant::now() < deadline => {
+                    // Fees may rise with no signal as transactions enter the mempool, so the shared template is also fetched
+                    // periodically, which rebuilds it at most once per cache lifetime whatever the number of callers
+                    let wait = deadline.saturating_duration_since(Instant::now()).min(BLOCK_TEMPLATE_LONG_POLL_RECHECK_INTERVAL);
+                    if let Ok(Err(_)) = tokio::time::timeout(wait, template_updates.changed()).await {
+                        break (block_template, template_id, rejected_transactions);
+                    }
+                }
+                _ => break (block_template, template_id, rejected_transactions),
+            }
+        };
 
         // Check coinbase tx payload length
         if block_template.block.transactions[COINBASE_TRANSACTION_INDEX].payload.len() > self.config.max_coinbase_payload_len {
@@ -449,6 +724,11 @@ NOTE: This error usually indicates an RPC conversion error between the node and
                 timestamp: block_template.selected_parent_timestamp,
                 daa_score: block_template.selected_parent_daa_score,
             }),
+            template_id,
+            rejected_transactions: rejected_transactions
+                .into_iter()
+                .map(|x| RpcRejectedTransaction { transaction_id: x.transaction_id, reason: x.reason })
+                .collect(),
         })
     }
 
@@ -563,14 +843,45 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         request: GetMempoolEntriesRequest,
     ) -> RpcResult<GetMempoolEntriesResponse> {
         let query = self.extract_tx_query(request.filter_transaction_pool, request.include_orphan_pool)?;
+        let order = match request.order {
+            RpcMempoolEntriesOrder::Arrival => MempoolEntriesOrder::Arrival,
+            RpcMempoolEntriesOrder::Feerate => MempoolEntriesOrder::Feerate,
+        };
+        let cursor = request
+            .cursor
+            .as_deref()
+            .map(|cursor| {
+                Vec::<u8>::from_hex(cursor)
+                    .ok()
+                    .and_then(|bytes| MempoolEntriesCursor::try_from(bytes.as_slice()).ok())
+                    .filter(|x| x.order() == Some(order))
+                    .ok_or_else(|| RpcError::InvalidMempoolEntriesCursor(cursor.to_string()))
+            })
+            .transpose()?;
+        let query = MempoolEntriesQuery {
+            query,
+            order,
+            cursor,
+            limit: request.limit.map_or(usize::MAX, |limit| limit.clamp(1, MAX_MEMPOOL_ENTRIES_PAGE_SIZE) as usize),
+            min_feerate: request.min_feerate,
+            script_public_keys: request.addresses.iter().map(pay_to_address_script).collect(),
+            compact: request.compact,
+        };
+
+        let page = self.mining_manager.clone().get_mempool_entries(query).await;
+        let next_cursor = page.next_cursor.map(|cursor| cursor.as_ref().to_vec().to_hex());
+        if request.compact {
+            let compact_entries = page.entries.iter().map(|x| RpcCompactMempoolEntry::new(x.transaction_id, x.mass)).collect();
+            return Ok(GetMempoolEntriesResponse { mempool_entries: vec![], compact_entries, next_cursor });
+        }
         let session = self.consensus_manager.consensus().unguarded_session();
-        let (transactions, orphans) = self.mining_manager.clone().get_all_transactions(query).await;
-        let mempool_entries = transactions
+        let mempool_entries = page
+            .entries
             .iter()
-            .chain(orphans.iter())
+            .filter_map(|entry| entry.transaction.as_ref())
             .map(|transaction| self.consensus_converter.get_mempool_entry(&session, transaction))
             .collect();
-        Ok(GetMempoolEntriesResponse::new(mempool_entries))
+        Ok(GetMempoolEntriesResponse { mempool_entries, compact_entries: vec![], next_cursor })
     }
 
     async fn get_mempool_entries_by_addresses_call(
@@ -641,6 +952,21 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         Ok(SubmitTransactionReplacementResponse::new(transaction_id, (&*replaced_transaction).into()))
     }
 
+    async fn submit_package_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: SubmitPackageRequest,
+    ) -> RpcResult<SubmitPackageResponse> {
+        let transactions = request.transactions.into_iter().map(Transaction::try_from).collect::<RpcResult<Vec<_>>>()?;
+        let session = self.consensus_manager.consensus().unguarded_session();
+        let transaction_ids = self.flow_context.submit_rpc_package(&session, transactions).await.map_err(|err| {
+            let err = RpcError::RejectedPackage(err.to_string());
+            debug!("{err}");
+            err
+        })?;
+        Ok(SubmitPackageResponse::new(transaction_ids))
+    }
+
     async fn get_current_network_call(
         &self,
         _connection: Option<&DynRpcConnection>,
@@ -718,6 +1044,54 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         Ok(GetVirtualChainFromBlockResponse::new(virtual_chain_batch.removed, virtual_chain_batch.added, accepted_transaction_ids))
     }
 
+    async fn get_virtual_chain_chunk_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: GetVirtualChainChunkRequest,
+    ) -> RpcResult<GetVirtualChainChunkResponse> {
+        let session = self.consensus_manager.consensus().session().await;
+        let limit = request.max_chain_blocks.clamp(1, MAX_VIRTUAL_CHAIN_CHUNK_SIZE) as usize;
+        let sink = session.async_get_sink().await;
+        let chain_path = session.async_get_virtual_chain_from_block(request.start_hash, Some(limit)).await?;
+
+        let mut added_chain_blocks = Vec::with_capacity(chain_path.added.len());
+        let mut transaction_count = 0;
+        for hash in chain_path.added.iter().copied() {
+            // Always return at least one chain block so the chain can be followed whatever the size of its mergesets
+            if transaction_count >= MAX_VIRTUAL_CHAIN_CHUNK_TRANSACTIONS {
+                break;
+            }
+            let daa_score = session.async_get_header(hash).await?.daa_score;
+            let transactions =
+                session.async_get_populated_accepted_transactions(hash).await.map_err(|err| RpcError::General(err.to_string()))?;
+            let accepted_transactions = transactions
+                .iter()
+                .map(|populated_tx| {
+                    let input_utxo_entries = populated_tx
+                        .entries
+                        .iter()
+                        .map(|entry| {
+                            entry
+                                .as_ref()
+                                .map(|x| RpcUtxoEntry::new(x.amount, x.script_public_key.clone(), x.block_daa_score, x.is_coinbase))
+                        })
+                        .collect::<Option<Vec<_>>>()
+                        .ok_or_else(|| RpcError::General(UtxoInquirerError::UnfilledUtxoEntry.to_string()))?;
+                    Ok(RpcAcceptedTransaction {
+                        transaction_id: populated_tx.tx.id(),
+                        transaction: self.consensus_converter.get_transaction(&session, &populated_tx.tx, None, false),
+                        input_utxo_entries,
+                    })
+                })
+                .collect::<RpcResult<Vec<_>>>()?;
+            transaction_count += accepted_transactions.len();
+            added_chain_blocks.push(RpcAcceptingChainBlock { hash, daa_score, accepted_transactions });
+        }
+
+        let is_tip = added_chain_blocks.len() == chain_path.added.len() && chain_path.added.last().is_none_or(|hash| *hash == sink);
+        Ok(GetVirtualChainChunkResponse::new(chain_path.removed, added_chain_blocks, is_tip))
+    }
+
     async fn get_block_count_call(
         &self,
         _connection: Option<&DynRpcConnection>,
@@ -895,6 +1269,17 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         }
     }
 
+    async fn get_fee_estimate_history_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetFeeEstimateHistoryRequest,
+    ) -> RpcResult<GetFeeEstimateHistoryResponse> {
+        let estimate = self.get_fee_estimate_call(connection, GetFeeEstimateRequest {}).await?.estimate;
+        let max_blocks = request.max_blocks.min(MAX_FEE_ESTIMATE_HISTORY_SIZE) as usize;
+        let blocks = self.mining_manager.clone().get_feerate_history(max_blocks).await;
+        Ok(GetFeeEstimateHistoryResponse::new(estimate, blocks.into_iter().map(BlockFeerateRecordConverter::into_rpc).collect()))
+    }
+
     async fn get_utxo_return_address_call(
         &self,
         _connection: Option<&DynRpcConnection>,
@@ -922,6 +1307,125 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         }
     }
 
+    async fn get_transaction_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: GetTransactionRequest,
+    ) -> RpcResult<GetTransactionResponse> {
+        if !self.config.txindex {
+            return Err(RpcError::NoTxIndex);
+        }
+
+        let entry = self
+            .txindex
+            .clone()
+            .unwrap()
+            .get_transaction_entry(request.transaction_id)
+            .await
+            .map_err(|err| RpcError::General(err.to_string()))?
+            .ok_or(RpcError::TransactionNotFound(request.transaction_id))?;
+
+        let session = self.consensus_manager.consensus().session().await;
+        // The containing block body may have been pruned since the transaction was indexed
+        let block = session.async_get_block(entry.containing_block).await?;
+        let transaction = block
+            .transactions
+            .get(entry.index_within_block as usize)
+            .filter(|tx| tx.id() == request.transaction_id)
+            .ok_or(RpcError::TransactionNotFound(request.transaction_id))?;
+        let accepting_block_daa_score = session.async_get_header(entry.accepting_block).await?.daa_score;
+
+        Ok(GetTransactionResponse {
+            transaction: self.consensus_converter.get_transaction(&session, transaction, Some(&block.header), true),
+            block_hash: entry.containing_block,
+            accepting_block_hash: entry.accepting_block,
+            accepting_block_daa_score,
+        })
+    }
+
+    async fn get_transaction_status_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: GetTransactionStatusRequest,
+    ) -> RpcResult<GetTransactionStatusResponse> {
+        let transaction_id = request.transaction_id;
+        let status = match self.mining_manager.clone().get_transaction_status(transaction_id).await {
+            Some(status @ (TransactionStatus::InMempool | TransactionStatus::Orphan)) => {
+                return Ok(GetTransactionStatusResponse::new(self.mining_converter.get_transaction_status(transaction_id, status)));
+            }
+            Some(status) => self.mining_converter.get_transaction_status(transaction_id, status),
+            None => RpcTransactionStatus::new(transaction_id, RpcTransactionState::Unknown),
+        };
+
+        // A transaction which left the mempool, or was never seen by it, may have been accepted by the virtual chain
+        let session = self.consensus_manager.consensus().session().await;
+        let accepting_block = if self.config.txindex {
+            self.txindex
+                .clone()
+                .unwrap()
+                .get_transaction_entry(transaction_id)
+                .await
+                .map_err(|err| RpcError::General(err.to_string()))?
+                .map(|entry| entry.accepting_block)
+        } else if let Some(daa_score) = status.including_block_daa_score {
+            self.find_accepting_chain_block(&session, transaction_id, daa_score).await?
+        } else {
+            None
+        };
+        let Some(accepting_block) = accepting_block else { return Ok(GetTransactionStatusResponse::new(status)) };
+        let header = session.async_get_header(accepting_block).await?;
+        let confirmations = session.async_get_sink_blue_score().await.saturating_sub(header.blue_score);
+        Ok(GetTransactionStatusResponse::new(status.with_accepting_block(accepting_block, header.daa_score, confirmations)))
+    }
+
+    async fn get_address_history_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: GetAddressHistoryRequest,
+    ) -> RpcResult<GetAddressHistoryResponse> {
+        if !self.config.addressindex {
+            return Err(RpcError::NoAddressIndex);
+        }
+
+        let cursor = request
+            .cursor
+            .as_deref()
+            .map(|cursor| {
+                Vec::<u8>::from_hex(cursor)
+                    .ok()
+                    .and_then(|bytes| AddressHistoryCursor::try_from(bytes.as_slice()).ok())
+                    .ok_or_else(|| RpcError::InvalidAddressHistoryCursor(cursor.to_string()))
+            })
+            .transpose()?;
+        let limit = request.limit.clamp(1, MAX_ADDRESS_HISTORY_PAGE_SIZE) as usize;
+
+        let page = self
+            .addressindex
+            .clone()
+            .unwrap()
+            .get_address_history(pay_to_address_script(&request.address), cursor, limit)
+            .await
+            .map_err(|err| RpcError::General(err.to_string()))?;
+
+        Ok(GetAddressHistoryResponse::new(
+            page.entries
+                .into_iter()
+                .map(|entry| RpcAddressHistoryEntry {
+                    transaction_id: entry.transaction_id,
+                    kind: match entry.kind {
+                        AddressActivityKind::Received => RpcAddressActivityKind::Received,
+                        AddressActivityKind::Spent => RpcAddressActivityKind::Spent,
+                    },
+                    outpoint: entry.outpoint.into(),
+                    amount: entry.amount,
+                    accepting_block_hash: entry.accepting_block,
+                    accepting_block_daa_score: entry.accepting_block_daa_score,
+                })
+                .collect(),
+            page.next_cursor.map(|cursor| cursor.as_ref().to_vec().to_hex()),
+        ))
+    }
+
     async fn ping_call(&self, _connection: Option<&DynRpcConnection>, _: PingRequest) -> RpcResult<PingResponse> {
         Ok(PingResponse {})
     }
@@ -1137,6 +1641,9 @@ NOTE: This error usually indicates an RPC conversion error between the node and
             json_handshake_failures: self.wrpc_json_counters.handshake_failures.load(Ordering::Relaxed) as u64,
 
             active_peers: self.flow_context.hub().active_peers_len() as u32,
+
+            connection_rate_limited_requests: self.rate_limit_counters.connection_limited_requests.load(Ordering::Relaxed),
+            ip_rate_limited_requests: self.rate_limit_counters.ip_limited_requests.load(Ordering::Relaxed),
         });
 
         let bandwidth_metrics = req.bandwidth_metrics.then(|| BandwidthMetrics {
@@ -1289,6 +1796,31 @@ NOTE: This error usually indicates an RPC conversion error between the node and
     }
 }
 
+#[async_trait]
+impl Replayer<Notification> for RpcCoreService {
+    /// Replays the chain changes, or the UTXO changes of the subscribed addresses, since `resume_from`.
+    ///
+    /// The replay is read from consensus while the live notifications are held back, so the first live
+    /// notifications may overlap the replayed ones.
+    async fn replay(&self, scope: &Scope, resume_from: &ResumeToken) -> NotifyResult<Vec<Notification>> {
+        let session = self.consensus_manager.consensus().session().await;
+        // Same bound as the one of GetVirtualChainFromBlock
+        let batch_size = (self.config.mergeset_size_limit().upper_bound() * 10) as usize;
+        let replayed = match scope {
+            Scope::VirtualChainChanged(scope) => self.replay_virtual_chain_changed(&session, scope, resume_from, batch_size).await,
+            Scope::UtxosChanged(scope) => self.replay_utxos_changed(&session, scope, resume_from, batch_size).await,
+            _ => Err(format!("{} notifications cannot be replayed", scope.event_type())),
+        };
+        replayed.map_err(|reason| NotifyError::ResumeGap(resume_from.to_string(), reason))
+    }
+}
+
+impl Debug for RpcCoreService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RpcCoreService").finish()
+    }
+}
+
 // It might be necessary to opt this out in the context of wasm32
 
 impl AsyncService for RpcCoreService {
@@ -0,0 +1,40 @@
+use async_trait::async_trait;
+use vecno_mining::manager::MiningManagerProxy;
+use vecno_notify::{error::Result, events::EventType, listener::ListenerId, scope::Scope, subscriber::SubscriptionManager};
+use std::fmt::Debug;
+
+/// Subscription manager turning the transaction status notifications of the mining manager on and off.
+///
+/// The mining manager has a single listener so the listener id is ignored.
+pub(crate) struct MiningSubscriptionManager {
+    mining_manager: MiningManagerProxy,
+}
+
+impl MiningSubscriptionManager {
+    pub(crate) fn new(mining_manager: MiningManagerProxy) -> Self {
+        Self { mining_manager }
+    }
+}
+
+impl Debug for MiningSubscriptionManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MiningSubscriptionManager").finish()
+    }
+}
+
+#[async_trait]
+impl SubscriptionManager for MiningSubscriptionManager {
+    async fn start_notify(&self, _id: ListenerId, scope: Scope) -> Result<()> {
+        if scope.event_type() == EventType::TransactionStatusChanged {
+            self.mining_manager.set_transaction_status_notifications(true);
+        }
+        Ok(())
+    }
+
+    async fn stop_notify(&self, _id: ListenerId, scope: Scope) -> Result<()> {
+        if scope.event_type() == EventType::TransactionStatusChanged {
+            self.mining_manager.set_transaction_status_notifications(false);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,220 @@
+use crate::{
+    error::{StratumServerError, StratumServerResult},
+    job::{Job, Jobs, MAX_JOBS},
+    protocol::{self, parse_nonce, Notification, Request, Response, StratumError},
+    vardiff::{VarDiff, VARDIFF_INTERVAL},
+};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::{collections::HashMap, net::SocketAddr, sync::Arc, time::Instant};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::{
+        tcp::{OwnedReadHalf, OwnedWriteHalf},
+        TcpStream,
+    },
+};
+use triggered::Listener;
+use vecno_consensus_core::header::Header;
+use vecno_core::{debug, info, warn};
+use vecno_math::Uint256;
+use vecno_rpc_core::{api::rpc::RpcApi, SubmitBlockRequest};
+use vecno_rpc_service::service::RpcCoreService;
+
+/// Maximum size of a single message sent by a miner
+const MAX_MESSAGE_SIZE: usize = 16 * 1024;
+
+/// Reads newline-delimited messages, keeping partially received ones across cancellations
+struct MessageReader {
+    reader: BufReader<OwnedReadHalf>,
+    buffer: Vec<u8>,
+}
+
+impl MessageReader {
+    /// Reads the next message. Cancel safe: bytes received so far are kept in the internal buffer.
+    async fn read_message(&mut self) -> StratumServerResult<String> {
+        loop {
+            let available = self.reader.fill_buf().await?;
+            if available.is_empty() {
+                return Err(StratumServerError::ConnectionClosed);
+            }
+            let (consumed, complete) = match available.iter().position(|&byte| byte == b'\n') {
+                Some(position) => (position + 1, true),
+                None => (available.len(), false),
+            };
+            self.buffer.extend_from_slice(&available[..consumed]);
+            self.reader.consume(consumed);
+            if self.buffer.len() > MAX_MESSAGE_SIZE {
+                return Err(StratumServerError::MessageTooLarge(MAX_MESSAGE_SIZE));
+            }
+            if complete {
+                let message = String::from_utf8_lossy(&self.buffer).trim().to_owned();
+                self.buffer.clear();
+                return Ok(message);
+            }
+        }
+    }
+}
+
+/// A miner connected to the stratum server
+pub struct Connection {
+    address: SocketAddr,
+    extranonce: u16,
+    core_service: Arc<RpcCoreService>,
+    jobs: Arc<Jobs>,
+    reader: MessageReader,
+    writer: OwnedWriteHalf,
+    subscribed: bool,
+    worker: Option<String>,
+    vardiff: VarDiff,
+    /// The share target in force when each recent job was notified to the miner
+    share_targets: HashMap<u64, Uint256>,
+}
+
+impl Connection {
+    pub fn new(
+        stream: TcpStream,
+        address: SocketAddr,
+        extranonce: u16,
+        core_service: Arc<RpcCoreService>,
+        jobs: Arc<Jobs>,
+        vardiff: VarDiff,
+    ) -> Self {
+        let (reader, writer) = stream.into_split();
+        Self {
+            address,
+            extranonce,
+            core_service,
+            jobs,
+            reader: MessageReader { reader: BufReader::new(reader), buffer: Vec::new() },
+            writer,
+            subscribed: false,
+            worker: None,
+            vardiff,
+            share_targets: HashMap::new(),
+        }
+    }
+
+    /// Serves the miner until it disconnects or a shutdown signal is received
+    pub async fn run(mut self, shutdown: Listener) -> StratumServerResult<()> {
+        let mut job_receiver = self.jobs.subscribe();
+        let mut vardiff_interval = tokio::time::interval(VARDIFF_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = shutdown.clone() => return Ok(()),
+                message = self.reader.read_message() => {
+                    let message = message?;
+                    if !message.is_empty() {
+                        self.handle_message(&message).await?;
+                    }
+                }
+                changed = job_receiver.changed(), if self.worker.is_some() => {
+                    if changed.is_err() {
+                        return Ok(());
+                    }
+                    let job = job_receiver.borrow_and_update().clone();
+                    if let Some(job) = job {
+                        self.notify_job(&job).await?;
+                    }
+                }
+                _ = vardiff_interval.tick(), if self.worker.is_some() => {
+                    if let Some(difficulty) = self.vardiff.evaluate(Instant::now()) {
+                        debug!("Stratum connection {} difficulty set to {}", self.address, difficulty);
+                        // The new difficulty applies starting from the next job
+                        self.send(&Notification::new(protocol::SET_DIFFICULTY, json!([difficulty]))).await?;
+                    }
+                }
+            }
+        }
+    }
+
+    async fn handle_message(&mut self, message: &str) -> StratumServerResult<()> {
+        let request: Request = match serde_json::from_str(message) {
+            Ok(request) => request,
+            Err(err) => {
+                debug!("Stratum connection {} sent an invalid message: {}", self.address, err);
+                return self.send(&Response::error(Value::Null, StratumError::Other("Invalid message".to_owned()))).await;
+            }
+        };
+
+        match request.method.as_str() {
+            protocol::SUBSCRIBE => {
+                self.subscribed = true;
+                self.send(&Response::ok(request.id, json!([true, protocol::PROTOCOL_VERSION]))).await?;
+                self.send(&Notification::new(
+                    protocol::SET_EXTRANONCE,
+                    json!([protocol::extranonce_hex(self.extranonce), protocol::NONCE_SEARCH_SIZE]),
+                ))
+                .await
+            }
+            protocol::EXTRANONCE_SUBSCRIBE => self.send(&Response::ok(request.id, json!(true))).await,
+            protocol::AUTHORIZE => {
+                if !self.subscribed {
+                    return self.send(&Response::error(request.id, StratumError::NotSubscribed)).await;
+                }
+                let Some(worker) = request.params.get(0).and_then(Value::as_str) else {
+                    return self.send(&Response::error(request.id, StratumError::UnauthorizedWorker)).await;
+                };
+                info!("Stratum worker {} authorized from {}", worker, self.address);
+                self.worker = Some(worker.to_owned());
+                self.send(&Response::ok(request.id, json!(true))).await?;
+                self.send(&Notification::new(protocol::SET_DIFFICULTY, json!([self.vardiff.difficulty()]))).await?;
+                match self.jobs.latest() {
+                    Some(job) => self.notify_job(&job).await,
+                    None => Ok(()),
+                }
+            }
+            protocol::SUBMIT => match self.submit(&request.params).await {
+                Ok(()) => self.send(&Response::ok(request.id, json!(true))).await,
+                Err(err) => {
+                    debug!("Stratum connection {} share rejected: {}", self.address, err);
+                    self.send(&Response::error(request.id, err)).await
+                }
+            },
+            method => self.send(&Response::error(request.id, StratumError::Other(format!("Unknown method {method}")))).await,
+        }
+    }
+
+    async fn notify_job(&mut self, job: &Job) -> StratumServerResult<()> {
+        self.share_targets.insert(job.id, self.vardiff.target());
+        self.share_targets.retain(|&id, _| id + MAX_JOBS as u64 > job.id);
+        self.send(&Notification::new(protocol::NOTIFY, job.notify_params())).await
+    }
+
+    /// Validates a share, submitting it to consensus if it solves the block
+    async fn submit(&mut self, params: &Value) -> Result<(), StratumError> {
+        if !self.subscribed {
+            return Err(StratumError::NotSubscribed);
+        }
+        let worker = self.worker.clone().ok_or(StratumError::UnauthorizedWorker)?;
+        let (Some(job_id), Some(nonce)) = (params.get(1).and_then(Value::as_str), params.get(2).and_then(Value::as_str)) else {
+            return Err(StratumError::Other("Invalid submit parameters".to_owned()));
+        };
+        let job_id = job_id.parse::<u64>().map_err(|_| StratumError::JobNotFound)?;
+        let job = self.jobs.get(job_id).ok_or(StratumError::JobNotFound)?;
+        let share_target = *self.share_targets.get(&job_id).ok_or(StratumError::JobNotFound)?;
+        let nonce = parse_nonce(nonce, self.extranonce)?;
+        if job.validate_share(nonce, share_target)? {
+            self.submit_block(&job, nonce, &worker).await;
+        }
+        self.vardiff.record_share();
+        Ok(())
+    }
+
+    async fn submit_block(&self, job: &Job, nonce: u64, worker: &str) {
+        let block = job.solved_block(nonce);
+        let hash = Header::from(&block.header).hash;
+        match self.core_service.submit_block_call(None, SubmitBlockRequest::new(block, false)).await {
+            Ok(response) if response.report.is_success() => info!("Stratum worker {} found block {}", worker, hash),
+            Ok(response) => warn!("Block {} found by stratum worker {} was rejected: {:?}", hash, worker, response.report),
+            Err(err) => warn!("Block {} found by stratum worker {} could not be submitted: {}", hash, worker, err),
+        }
+    }
+
+    async fn send<T: Serialize>(&mut self, message: &T) -> StratumServerResult<()> {
+        let mut bytes = serde_json::to_vec(message)?;
+        bytes.push(b'\n');
+        self.writer.write_all(&bytes).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,24 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum StratumServerError {
+    #[error("RpcApi error: {0}")]
+    RpcApiError(#[from] vecno_rpc_core::error::RpcError),
+
+    #[error("Notification subsystem error: {0}")]
+    NotificationError(#[from] vecno_notify::error::Error),
+
+    #[error("I/O error: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("JSON error: {0}")]
+    JsonError(#[from] serde_json::Error),
+
+    #[error("message exceeds the maximum size of {0} bytes")]
+    MessageTooLarge(usize),
+
+    #[error("client connection is closed")]
+    ConnectionClosed,
+}
+
+pub type StratumServerResult<T> = std::result::Result<T, StratumServerError>;
@@ -0,0 +1,186 @@
+use crate::protocol::StratumError;
+use parking_lot::{Mutex, RwLock};
+use serde_json::{json, Value};
+use std::{
+    collections::{HashSet, VecDeque},
+    sync::Arc,
+};
+use tokio::sync::watch;
+use vecno_consensus_core::{hashing, header::Header};
+use vecno_hashes::Hash;
+use vecno_math::Uint256;
+use vecno_pow::State;
+use vecno_rpc_core::RpcRawBlock;
+
+/// Number of recent jobs for which shares are still accepted
+pub const MAX_JOBS: usize = 64;
+
+/// Number of shares recorded per job, beyond which the shares not solving the block are refused
+pub const MAX_JOB_SHARES: usize = 16 * 1024;
+
+/// A block template handed out to the miners
+pub struct Job {
+    pub id: u64,
+    pub block: RpcRawBlock,
+    pub state: State,
+    pub pre_pow_hash: Hash,
+    pub timestamp: u64,
+    /// Nonces of the valid shares already submitted for this job, across all connections
+    submitted: Mutex<HashSet<u64>>,
+}
+
+impl Job {
+    pub fn new(id: u64, block: RpcRawBlock) -> Self {
+        let header = Header::from(&block.header);
+        let state = State::new(&header);
+        let pre_pow_hash = hashing::header::hash_override_nonce_time(&header, 0, 0);
+        Self { id, timestamp: header.timestamp, block, state, pre_pow_hash, submitted: Default::default() }
+    }
+
+    /// The `mining.notify` parameters of this job
+    pub fn notify_params(&self) -> Value {
+        json!([self.id.to_string(), self.pre_pow_hash.to_le_u64(), self.timestamp])
+    }
+
+    /// Validates a share against the share target of its connection, returning whether it also solves the block.
+    ///
+    /// The PoW is checked first so that only the nonces of valid shares are recorded.
+    pub fn validate_share(&self, nonce: u64, share_target: Uint256) -> Result<bool, StratumError> {
+        let (is_block, pow) = self.state.check_pow(nonce);
+        if !is_block && pow > share_target {
+            return Err(StratumError::LowDifficultyShare);
+        }
+        let mut submitted = self.submitted.lock();
+        if submitted.contains(&nonce) {
+            return Err(StratumError::DuplicateShare);
+        }
+        // Blocks are never refused, a full job only stops recording them
+        if submitted.len() >= MAX_JOB_SHARES {
+            return if is_block { Ok(true) } else { Err(StratumError::TooManyShares) };
+        }
+        submitted.insert(nonce);
+        Ok(is_block)
+    }
+
+    /// The job block with its nonce set to a solution
+    pub fn solved_block(&self, nonce: u64) -> RpcRawBlock {
+        let mut block = self.block.clone();
+        block.header.nonce = nonce;
+        block
+    }
+}
+
+/// The recent jobs, the latest of which is broadcast to all connections
+pub struct Jobs {
+    jobs: RwLock<VecDeque<Arc<Job>>>,
+    next_id: Mutex<u64>,
+    latest: watch::Sender<Option<Arc<Job>>>,
+}
+
+impl Jobs {
+    pub fn new() -> Self {
+        Self { jobs: Default::default(), next_id: Default::default(), latest: watch::channel(None).0 }
+    }
+
+    pub fn subscribe(&self) -> watch::Receiver<Option<Arc<Job>>> {
+        self.latest.subscribe()
+    }
+
+    pub fn latest(&self) -> Option<Arc<Job>> {
+        self.latest.borrow().clone()
+    }
+
+    pub fn get(&self, id: u64) -> Option<Arc<Job>> {
+        self.jobs.read().iter().find(|job| job.id == id).cloned()
+    }
+
+    /// Publishes a block template as a new job, unless it only differs from the latest job by its nonce
+    pub fn push(&self, block: RpcRawBlock) {
+        let mut next_id = self.next_id.lock();
+        let job = Arc::new(Job::new(*next_id, block));
+        if self.latest().is_some_and(|latest| latest.pre_pow_hash == job.pre_pow_hash && latest.timestamp == job.timestamp) {
+            return;
+        }
+        *next_id += 1;
+
+        let mut jobs = self.jobs.write();
+        if jobs.len() == MAX_JOBS {
+            jobs.pop_front();
+        }
+        jobs.push_back(job.clone());
+        self.latest.send_replace(Some(job));
+    }
+
+    /// Stops handing out jobs until the next call to [`Self::push`], returning `true` if there was a job to withdraw
+    pub fn withdraw(&self) -> bool {
+        self.latest.send_replace(None).is_some()
+    }
+}
+
+impl Default for Jobs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(bits: u32, timestamp: u64) -> RpcRawBlock {
+        let mut header = Header::from_precomputed_hash(Hash::from_u64_word(timestamp), vec![]);
+        header.bits = bits;
+        header.timestamp = timestamp;
+        RpcRawBlock { header: (&header).into(), transactions: vec![] }
+    }
+
+    #[test]
+    fn test_validate_share() {
+        // A block target of 1 is never met
+        let job = Job::new(0, block(0x03000001, 1));
+
+        // Low difficulty shares are refused without being recorded
+        assert_eq!(job.validate_share(7, Uint256::ZERO), Err(StratumError::LowDifficultyShare));
+        assert!(job.submitted.lock().is_empty());
+
+        // Valid shares are recorded and cannot be submitted again
+        assert_eq!(job.validate_share(7, Uint256::MAX), Ok(false));
+        assert_eq!(job.validate_share(7, Uint256::MAX), Err(StratumError::DuplicateShare));
+
+        // Once a job records the maximum number of shares, the other shares are refused
+        job.submitted.lock().extend(100..(100 + MAX_JOB_SHARES - 1) as u64);
+        assert_eq!(job.validate_share(8, Uint256::MAX), Err(StratumError::TooManyShares));
+        assert_eq!(job.submitted.lock().len(), MAX_JOB_SHARES);
+
+        // Blocks are accepted whatever the share target, even once the job is full
+        let job = Job::new(1, block(0x207fffff, 1));
+        let nonces: Vec<u64> = (0..).filter(|&nonce| job.state.check_pow(nonce).0).take(2).collect();
+        assert_eq!(job.validate_share(nonces[0], Uint256::ZERO), Ok(true));
+        assert_eq!(job.validate_share(nonces[0], Uint256::ZERO), Err(StratumError::DuplicateShare));
+        job.submitted.lock().extend(u64::MAX - MAX_JOB_SHARES as u64..u64::MAX);
+        assert_eq!(job.validate_share(nonces[1], Uint256::ZERO), Ok(true));
+    }
+
+    #[test]
+    fn test_stale_jobs() {
+        let jobs = Jobs::new();
+
+        // A template only differing by its nonce does not yield a new job
+        jobs.push(block(0x03000001, 0));
+        jobs.push(block(0x03000001, 0));
+        assert_eq!(jobs.latest().unwrap().id, 0);
+
+        // Only the most recent jobs are kept
+        for timestamp in 1..=MAX_JOBS as u64 {
+            jobs.push(block(0x03000001, timestamp));
+        }
+        assert_eq!(jobs.latest().unwrap().id, MAX_JOBS as u64);
+        assert!(jobs.get(0).is_none());
+        assert!(jobs.get(1).is_some());
+
+        // Withdrawn jobs are no longer handed out, but their shares are still accepted
+        assert!(jobs.withdraw());
+        assert!(jobs.latest().is_none());
+        assert!(jobs.get(MAX_JOBS as u64).is_some());
+    }
+}
@@ -0,0 +1,6 @@
+pub mod connection;
+pub mod error;
+pub mod job;
+pub mod protocol;
+pub mod service;
+pub mod vardiff;
@@ -0,0 +1,138 @@
+//! Stratum v1 messages, in the `EthereumStratum/1.0.0` flavour used by Vecno miners.
+//!
+//! Messages are newline-delimited JSON objects. Jobs are notified as `[job_id, [pre_pow_hash as 4 LE u64], timestamp]`
+//! and shares are submitted as `[worker, job_id, nonce]` where the nonce is a big-endian hex string.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use thiserror::Error;
+
+pub const SUBSCRIBE: &str = "mining.subscribe";
+pub const EXTRANONCE_SUBSCRIBE: &str = "mining.extranonce.subscribe";
+pub const AUTHORIZE: &str = "mining.authorize";
+pub const SUBMIT: &str = "mining.submit";
+pub const SET_EXTRANONCE: &str = "mining.set_extranonce";
+pub const SET_DIFFICULTY: &str = "mining.set_difficulty";
+pub const NOTIFY: &str = "mining.notify";
+
+pub const PROTOCOL_VERSION: &str = "EthereumStratum/1.0.0";
+
+/// Number of nonce bytes reserved for the per-connection extranonce
+pub const EXTRANONCE_SIZE: usize = 2;
+
+/// Number of nonce bytes left for the miner to search
+pub const NONCE_SEARCH_SIZE: usize = 8 - EXTRANONCE_SIZE;
+
+#[derive(Debug, Deserialize)]
+pub struct Request {
+    #[serde(default)]
+    pub id: Value,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Response {
+    pub id: Value,
+    pub result: Value,
+    pub error: Value,
+}
+
+impl Response {
+    pub fn ok(id: Value, result: Value) -> Self {
+        Self { id, result, error: Value::Null }
+    }
+
+    pub fn error(id: Value, error: StratumError) -> Self {
+        Self { id, result: Value::Null, error: json!([error.code(), error.to_string(), Value::Null]) }
+    }
+}
+
+/// A server-initiated message
+#[derive(Debug, Serialize)]
+pub struct Notification {
+    pub id: Value,
+    pub method: &'static str,
+    pub params: Value,
+}
+
+impl Notification {
+    pub fn new(method: &'static str, params: Value) -> Self {
+        Self { id: Value::Null, method, params }
+    }
+}
+
+/// Errors reported to the miner, using the customary stratum error codes
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum StratumError {
+    #[error("{0}")]
+    Other(String),
+
+    #[error("Job not found")]
+    JobNotFound,
+
+    #[error("Duplicate share")]
+    DuplicateShare,
+
+    #[error("Low difficulty share")]
+    LowDifficultyShare,
+
+    #[error("Unauthorized worker")]
+    UnauthorizedWorker,
+
+    #[error("Not subscribed")]
+    NotSubscribed,
+
+    #[error("Too many shares for this job")]
+    TooManyShares,
+}
+
+impl StratumError {
+    pub fn code(&self) -> u32 {
+        match self {
+            StratumError::Other(_) => 20,
+            StratumError::JobNotFound => 21,
+            StratumError::DuplicateShare => 22,
+            StratumError::LowDifficultyShare => 23,
+            StratumError::UnauthorizedWorker => 24,
+            StratumError::NotSubscribed => 25,
+            StratumError::TooManyShares => 20,
+        }
+    }
+}
+
+/// Formats the extranonce as sent in `mining.set_extranonce`
+pub fn extranonce_hex(extranonce: u16) -> String {
+    format!("{extranonce:04x}")
+}
+
+/// Parses a submitted nonce and checks that it lies within the connection extranonce space.
+///
+/// Miners either submit the full 8-byte nonce or only the part they searched, in which case the extranonce is prepended.
+pub fn parse_nonce(nonce: &str, extranonce: u16) -> Result<u64, StratumError> {
+    let nonce = nonce.strip_prefix("0x").unwrap_or(nonce);
+    let value = u64::from_str_radix(nonce, 16).map_err(|_| StratumError::Other(format!("Invalid nonce {nonce}")))?;
+    let prefix = (extranonce as u64) << (NONCE_SEARCH_SIZE * 8);
+    match nonce.len() {
+        16 if value & !(u64::MAX >> (EXTRANONCE_SIZE * 8)) == prefix => Ok(value),
+        16 => Err(StratumError::Other("Nonce is outside the extranonce space".to_owned())),
+        len if len == NONCE_SEARCH_SIZE * 2 => Ok(prefix | value),
+        _ => Err(StratumError::Other(format!("Invalid nonce length {}", nonce.len()))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_nonce() {
+        assert_eq!(parse_nonce("0x12340000000000ff", 0x1234), Ok(0x12340000000000ff));
+        assert_eq!(parse_nonce("12340000000000ff", 0x1234), Ok(0x12340000000000ff));
+        assert_eq!(parse_nonce("0000000000ff", 0x1234), Ok(0x12340000000000ff));
+        assert!(parse_nonce("43210000000000ff", 0x1234).is_err());
+        assert!(parse_nonce("00ff", 0x1234).is_err());
+        assert!(parse_nonce("zz", 0x1234).is_err());
+    }
+}
@@ -0,0 +1,192 @@
+use crate::{connection::Connection, job::Jobs, vardiff::VarDiff};
+use std::{
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicU16, Ordering},
+        Arc,
+    },
+};
+use tokio::{
+    net::{TcpListener, TcpStream},
+    sync::{OwnedSemaphorePermit, Semaphore},
+};
+use triggered::Listener;
+use vecno_addresses::Address;
+use vecno_consensus_core::config::Config;
+use vecno_core::{
+    debug, info,
+    task::service::{AsyncService, AsyncServiceError, AsyncServiceFuture},
+    trace, warn,
+};
+use vecno_notify::scope::{NewBlockTemplateScope, Scope};
+use vecno_rpc_core::{
+    api::rpc::RpcApi,
+    notify::{
+        channel::NotificationChannel,
+        connection::{ChannelConnection, ChannelType},
+    },
+    GetBlockTemplateRequest,
+};
+use vecno_rpc_service::service::RpcCoreService;
+use vecno_utils::{networking::NetAddress, triggers::SingleTrigger};
+
+/// Default port of the stratum server
+pub const DEFAULT_STRATUM_PORT: u16 = 5555;
+
+/// Extra data appended to the coinbase payload of the blocks mined through the stratum server
+const EXTRA_DATA: &[u8] = b"stratum";
+
+pub struct StratumOptions {
+    /// Address receiving the rewards of the mined blocks
+    pub pay_address: Address,
+    /// Initial share difficulty of every connection
+    pub difficulty: f64,
+    /// Share rate targeted by the variable difficulty, `0` keeps the initial difficulty
+    pub shares_per_minute: u32,
+    /// Maximum number of miner connections handled at once
+    pub max_connections: usize,
+}
+
+pub struct StratumService {
+    net_address: NetAddress,
+    config: Arc<Config>,
+    core_service: Arc<RpcCoreService>,
+    options: StratumOptions,
+    jobs: Arc<Jobs>,
+    next_extranonce: AtomicU16,
+    /// Permits of the connections handled at once, from their acceptance to their closing
+    connections: Arc<Semaphore>,
+    started: SingleTrigger,
+    shutdown: SingleTrigger,
+}
+
+impl StratumService {
+    pub const IDENT: &'static str = "stratum-service";
+
+    pub fn new(address: NetAddress, config: Arc<Config>, core_service: Arc<RpcCoreService>, options: StratumOptions) -> Self {
+        let connections = Arc::new(Semaphore::new(options.max_connections));
+        Self {
+            net_address: address,
+            config,
+            core_service,
+            options,
+            jobs: Default::default(),
+            next_extranonce: Default::default(),
+            connections,
+            started: Default::default(),
+            shutdown: Default::default(),
+        }
+    }
+
+    pub fn started(&self) -> Listener {
+        self.started.listener.clone()
+    }
+
+    /// Fetches a fresh block template and publishes it as the latest job
+    async fn update_job(&self) {
+        let request = GetBlockTemplateRequest::new(self.options.pay_address.clone(), EXTRA_DATA.to_vec());
+        match self.core_service.get_block_template_call(None, request).await {
+            Ok(response) if response.is_synced || self.config.enable_unsynced_mining => self.jobs.push(response.block),
+            Ok(_) => {
+                if self.jobs.withdraw() {
+                    info!("The node is not synced, the stratum server stops handing out jobs");
+                }
+            }
+            Err(err) => warn!("The stratum server failed getting a block template: {}", err),
+        }
+    }
+
+    fn spawn_connection(&self, stream: TcpStream, address: SocketAddr, permit: OwnedSemaphorePermit) {
+        // Connections are given distinct extranonces until the 2-byte space wraps around
+        let extranonce = self.next_extranonce.fetch_add(1, Ordering::Relaxed);
+        let vardiff = VarDiff::new(self.options.difficulty, self.options.shares_per_minute);
+        let connection = Connection::new(stream, address, extranonce, self.core_service.clone(), self.jobs.clone(), vardiff);
+        let shutdown = self.shutdown.listener.clone();
+        tokio::spawn(async move {
+            debug!("Stratum connection from {} assigned extranonce {:04x}", address, extranonce);
+            match connection.run(shutdown).await {
+                Ok(()) => debug!("Stratum connection {} closed", address),
+                Err(err) => debug!("Stratum connection {} closed: {}", address, err),
+            }
+            drop(permit);
+        });
+    }
+}
+
+impl AsyncService for StratumService {
+    fn ident(self: Arc<Self>) -> &'static str {
+        Self::IDENT
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        trace!("{} starting", Self::IDENT);
+
+        // Prepare a shutdown signal receiver
+        let shutdown_signal = self.shutdown.listener.clone();
+
+        Box::pin(async move {
+            let listener = TcpListener::bind(SocketAddr::from(self.net_address)).await.map_err(|err| {
+                AsyncServiceError::Service(format!("stratum server failed listening on {}: {}", self.net_address, err))
+            })?;
+            info!("Stratum server listening on {}", self.net_address);
+
+            // Every new block template yields a new job
+            let notification_channel = NotificationChannel::default();
+            let listener_id = self.core_service.register_new_listener(ChannelConnection::new(
+                Self::IDENT,
+                notification_channel.sender(),
+                ChannelType::Closable,
+            ));
+            self.core_service
+                .start_notify(listener_id, Scope::NewBlockTemplate(NewBlockTemplateScope {}))
+                .await
+                .map_err(|err| AsyncServiceError::Service(err.to_string()))?;
+
+            // Signal the server was started
+            self.started.trigger.trigger();
+
+            self.update_job().await;
+            loop {
+                tokio::select! {
+                    _ = shutdown_signal.clone() => break,
+                    notification = notification_channel.recv() => {
+                        if notification.is_err() {
+                            break;
+                        }
+                        // A single template fetch covers any notification received in the meantime
+                        while notification_channel.try_recv().is_ok() {}
+                        self.update_job().await;
+                    }
+                    accepted = listener.accept() => match accepted {
+                        Ok((stream, address)) => {
+                            let Ok(permit) = self.connections.clone().try_acquire_owned() else {
+                                debug!("Stratum server refused the connection of {}: too many connections", address);
+                                continue;
+                            };
+                            let _ = stream.set_nodelay(true);
+                            self.spawn_connection(stream, address, permit);
+                        }
+                        Err(err) => warn!("Stratum server failed accepting a connection: {}", err),
+                    }
+                }
+            }
+
+            if let Err(err) = self.core_service.unregister_listener(listener_id).await {
+                warn!("{} error while unregistering its notification listener: {}", Self::IDENT, err);
+            }
+            Ok(())
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", Self::IDENT);
+        self.shutdown.trigger.trigger();
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            trace!("{} stopped", Self::IDENT);
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,114 @@
+//! Per-connection variable difficulty, keeping each miner close to a target share rate regardless of its hashrate.
+
+use std::time::{Duration, Instant};
+use vecno_math::Uint256;
+
+/// Interval at which the share rate of a connection is evaluated
+pub const VARDIFF_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Maximum factor by which the difficulty can change in a single evaluation
+const MAX_ADJUSTMENT: f64 = 4.0;
+
+/// Relative deviation of the share rate below which the difficulty is left unchanged
+const TOLERANCE: f64 = 0.1;
+
+/// Lowest difficulty a connection can be assigned
+pub const MIN_DIFFICULTY: f64 = 0.001;
+
+/// Fixed-point precision used when converting a fractional difficulty to a target
+const DIFFICULTY_PRECISION_BITS: u32 = 16;
+
+/// Converts a share difficulty to its target, where difficulty 1 is a target of `2^224 - 1`, so that a share
+/// is expected every `difficulty * 2^32` hashes.
+pub fn difficulty_to_target(difficulty: f64) -> Uint256 {
+    let scaled = (difficulty * (1u64 << DIFFICULTY_PRECISION_BITS) as f64).round().max(1.0) as u64;
+    let (numerator, _) = Uint256::MAX.overflowing_shr(32 - DIFFICULTY_PRECISION_BITS);
+    numerator.div_rem_u64(scaled).0
+}
+
+pub struct VarDiff {
+    difficulty: f64,
+    /// Expected number of shares per minute, `0` disables the adjustments
+    shares_per_minute: u32,
+    shares: u32,
+    window_start: Instant,
+}
+
+impl VarDiff {
+    pub fn new(difficulty: f64, shares_per_minute: u32) -> Self {
+        Self { difficulty: difficulty.max(MIN_DIFFICULTY), shares_per_minute, shares: 0, window_start: Instant::now() }
+    }
+
+    pub fn difficulty(&self) -> f64 {
+        self.difficulty
+    }
+
+    pub fn target(&self) -> Uint256 {
+        difficulty_to_target(self.difficulty)
+    }
+
+    pub fn record_share(&mut self) {
+        self.shares += 1;
+    }
+
+    /// Evaluates the share rate observed since the last evaluation and returns the new difficulty if it changed
+    pub fn evaluate(&mut self, now: Instant) -> Option<f64> {
+        let elapsed = now.saturating_duration_since(self.window_start);
+        if self.shares_per_minute == 0 || elapsed < VARDIFF_INTERVAL {
+            return None;
+        }
+        let shares_per_minute = self.shares as f64 * 60.0 / elapsed.as_secs_f64();
+        let factor = (shares_per_minute / self.shares_per_minute as f64).clamp(1.0 / MAX_ADJUSTMENT, MAX_ADJUSTMENT);
+        self.shares = 0;
+        self.window_start = now;
+
+        let difficulty = (self.difficulty * factor).max(MIN_DIFFICULTY);
+        if (difficulty / self.difficulty - 1.0).abs() < TOLERANCE {
+            return None;
+        }
+        self.difficulty = difficulty;
+        Some(difficulty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_difficulty_to_target() {
+        assert_eq!(difficulty_to_target(1.0), Uint256::MAX.overflowing_shr(32).0);
+        assert_eq!(difficulty_to_target(2.0), Uint256::MAX.overflowing_shr(33).0);
+        assert_eq!(difficulty_to_target(0.5), Uint256::MAX.overflowing_shr(31).0);
+    }
+
+    #[test]
+    fn test_vardiff_adjustments() {
+        let start = Instant::now();
+
+        // Too early to evaluate
+        let mut vardiff = VarDiff::new(16.0, 20);
+        vardiff.window_start = start;
+        assert_eq!(vardiff.evaluate(start + VARDIFF_INTERVAL / 2), None);
+
+        // No shares at all, the difficulty drops by the maximum factor
+        assert_eq!(vardiff.evaluate(start + VARDIFF_INTERVAL), Some(4.0));
+
+        // Way too many shares, the difficulty rises by the maximum factor
+        (0..1000).for_each(|_| vardiff.record_share());
+        assert_eq!(vardiff.evaluate(start + VARDIFF_INTERVAL * 2), Some(16.0));
+
+        // The expected rate, nothing changes
+        (0..10).for_each(|_| vardiff.record_share());
+        assert_eq!(vardiff.evaluate(start + VARDIFF_INTERVAL * 3), None);
+
+        // Twice the expected rate
+        (0..20).for_each(|_| vardiff.record_share());
+        assert_eq!(vardiff.evaluate(start + VARDIFF_INTERVAL * 4), Some(32.0));
+
+        // Disabled
+        let mut vardiff = VarDiff::new(16.0, 0);
+        vardiff.window_start = start;
+        assert_eq!(vardiff.evaluate(start + VARDIFF_INTERVAL * 10), None);
+    }
+}
@@ -76,6 +76,7 @@ impl Inner {
             RpcApiOps::VirtualDaaScoreChangedNotification,
             RpcApiOps::PruningPointUtxoSetOverrideNotification,
             RpcApiOps::NewBlockTemplateNotification,
+            RpcApiOps::TransactionStatusChangedNotification,
         ]
         .into_iter()
         .for_each(|notification_op| {
@@ -645,6 +646,12 @@ impl RpcApi for VecnoRpcClient {
             GetSyncStatus,
             GetSystemInfo,
             GetUtxoReturnAddress,
+            GetTransaction,
+            GetAddressHistory,
+            SubmitPackage,
+            GetVirtualChainChunk,
+            GetFeeEstimateHistory,
+            GetTransactionStatus,
             GetUtxosByAddresses,
             GetVirtualChainFromBlock,
             ResolveFinalityConflict,
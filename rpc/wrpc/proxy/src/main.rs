@@ -70,14 +70,14 @@ async fn main() -> Result<()> {
         listen_address: interface.unwrap_or_else(|| format!("wrpc://127.0.0.1:{proxy_port}")),
         grpc_proxy_address: Some(grpc_proxy_address.unwrap_or_else(|| format!("grpc://127.0.0.1:{vecnod_port}"))),
         verbose,
-        // ..Options::default()
+        ..Options::default()
     });
     log_info!("");
     log_info!("Proxy routing to `{}` on {}", network_type, options.grpc_proxy_address.as_ref().unwrap());
 
     let counters = Arc::new(WebSocketCounters::default());
     let tasks = threads.unwrap_or_else(num_cpus::get);
-    let rpc_handler = Arc::new(VecnoRpcHandler::new(tasks, encoding, None, options.clone()));
+    let rpc_handler = Arc::new(VecnoRpcHandler::new(tasks, encoding, None, options.clone())?);
 
     let router = Arc::new(Router::new(rpc_handler.server.clone()));
     let server = RpcServer::new_with_encoding::<Server, Connection, RpcApiOps, Id64>(
@@ -6,7 +6,11 @@ use vecno_notify::{
     notification::Notification as NotificationT,
     notifier::Notify,
 };
-use vecno_rpc_core::{api::ops::RpcApiOps, notify::mode::NotificationMode, Notification};
+use vecno_rpc_core::{
+    api::{auth::RpcPermissions, ops::RpcApiOps, rate_limit::RpcConnectionRateLimiter},
+    notify::mode::NotificationMode,
+    Notification,
+};
 use std::{
     fmt::{Debug, Display},
     sync::{Arc, Mutex},
@@ -47,6 +51,8 @@ impl From<NotifyEncoding> for Encoding {
 struct ConnectionInner {
     pub id: u64,
     pub peer: SocketAddr,
+    pub permissions: RpcPermissions,
+    pub rate_limiter: RpcConnectionRateLimiter,
     pub messenger: Arc<Messenger>,
     pub grpc_client: Option<Arc<GrpcClient>>,
     // not using an atomic in case an Id will change type in the future...
@@ -83,12 +89,21 @@ pub struct Connection {
 }
 
 impl Connection {
-    pub fn new(id: u64, peer: &SocketAddr, messenger: Arc<Messenger>, grpc_client: Option<Arc<GrpcClient>>) -> Connection {
+    pub fn new(
+        id: u64,
+        peer: &SocketAddr,
+        permissions: RpcPermissions,
+        rate_limiter: RpcConnectionRateLimiter,
+        messenger: Arc<Messenger>,
+        grpc_client: Option<Arc<GrpcClient>>,
+    ) -> Connection {
         // If a GrpcClient is provided, it has to come configured in direct mode
         assert!(grpc_client.is_none() || grpc_client.as_ref().unwrap().notification_mode() == NotificationMode::Direct);
         // Should a gRPC client be provided, no listener_id is required for subscriptions so the listener id is set to default
         let listener_id = Mutex::new(grpc_client.clone().map(|_| ListenerId::default()));
-        Connection { inner: Arc::new(ConnectionInner { id, peer: *peer, messenger, grpc_client, listener_id }) }
+        Connection {
+            inner: Arc::new(ConnectionInner { id, peer: *peer, permissions, rate_limiter, messenger, grpc_client, listener_id }),
+        }
     }
 
     /// Obtain the connection id
@@ -125,6 +140,16 @@ impl Connection {
         &self.inner.peer
     }
 
+    /// Get the permissions granted to the credentials of the client
+    pub fn permissions(&self) -> RpcPermissions {
+        self.inner.permissions
+    }
+
+    /// Get the request rate limiter of the client
+    pub fn rate_limiter(&self) -> &RpcConnectionRateLimiter {
+        &self.inner.rate_limiter
+    }
+
     /// Creates a WebSocket [`Message`] that can be posted to the connection ([`Messenger`]) sink
     /// directly.
     pub fn create_serialized_notification_message<Ops, Msg>(encoding: Encoding, op: Ops, msg: Msg) -> WrpcResult<Message>
@@ -20,6 +20,12 @@ pub enum Error {
 
     #[error("Notify error: {0}")]
     NotifyError(#[from] NotifyError),
+
+    #[error("TLS configuration error: {0}")]
+    TlsConfigError(String),
+
+    #[error("Gateway error: {0}")]
+    GatewayError(String),
 }
 
 impl<T> From<PoisonError<T>> for Error {
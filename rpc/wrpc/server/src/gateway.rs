@@ -0,0 +1,247 @@
+//!
+//! Authenticating and TLS terminating gateway of the wRPC server.
+//!
+//! The gateway accepts the public connections, performs the TLS handshake if configured, authenticates
+//! the WebSocket upgrade request and then forwards the connection to the wRPC server listening internally
+//! on the loopback interface. The token is read from the `Authorization: Bearer` header or, for clients
+//! unable to set headers, from the `token` query parameter of the request URI.
+//!
+//! The TLS handshake and the request head must both complete within a timeout and the number of connections
+//! handled at once is capped, so that slow or idle clients cannot exhaust the resources of the node.
+//!
+
+use crate::{error::Error, result::Result};
+use vecno_core::{debug, trace, warn};
+use vecno_rpc_core::api::auth::{RpcAuthenticator, RpcPermissions, RpcTlsIdentity};
+use vecno_utils::triggers::SingleTrigger;
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::{
+    io::{copy_bidirectional, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    sync::Semaphore,
+    time::{sleep, timeout},
+};
+use tokio_rustls::{rustls, TlsAcceptor};
+
+/// Maximum size of the HTTP head of a WebSocket upgrade request
+const MAX_REQUEST_HEAD_SIZE: usize = 8 * 1024;
+
+/// Maximum duration of the TLS handshake of a connection
+const TLS_ACCEPT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Maximum duration between the acceptance of a connection, or the end of its TLS handshake, and the end of its request head
+const REQUEST_HEAD_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Delay before accepting connections again after a failure, typically due to the exhaustion of file descriptors
+const ACCEPT_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+const UNAUTHORIZED_RESPONSE: &[u8] = b"HTTP/1.1 401 Unauthorized\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+
+/// A client admitted by the gateway
+#[derive(Clone, Copy, Debug)]
+pub struct GatewayPeer {
+    /// The socket address of the client
+    pub address: SocketAddr,
+    /// The permissions granted to the credentials of the client
+    pub permissions: RpcPermissions,
+}
+
+pub struct Gateway {
+    authenticator: Arc<RpcAuthenticator>,
+    acceptor: Option<TlsAcceptor>,
+
+    /// Admitted clients keyed by the local address of their forwarded connection
+    peers: Mutex<HashMap<SocketAddr, GatewayPeer>>,
+
+    /// Permits of the connections handled at once, from their acceptance to their closing
+    connections: Arc<Semaphore>,
+
+    shutdown: SingleTrigger,
+}
+
+impl Gateway {
+    pub fn new(authenticator: Arc<RpcAuthenticator>, tls: Option<&RpcTlsIdentity>, max_connections: usize) -> Result<Self> {
+        let acceptor = tls.map(Self::tls_acceptor).transpose()?;
+        let connections = Arc::new(Semaphore::new(max_connections));
+        Ok(Self { authenticator, acceptor, peers: Default::default(), connections, shutdown: Default::default() })
+    }
+
+    /// Checks that a TLS identity holds a certificate chain and a matching private key usable by the gateway
+    pub fn validate_tls_identity(tls: &RpcTlsIdentity) -> Result<()> {
+        Self::tls_acceptor(tls).map(|_| ())
+    }
+
+    fn tls_acceptor(tls: &RpcTlsIdentity) -> Result<TlsAcceptor> {
+        let certs = rustls_pemfile::certs(&mut tls.cert.as_slice())
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|err| Error::TlsConfigError(err.to_string()))?;
+        let key = rustls_pemfile::private_key(&mut tls.key.as_slice())
+            .map_err(|err| Error::TlsConfigError(err.to_string()))?
+            .ok_or_else(|| Error::TlsConfigError("no private key found".to_string()))?;
+        let config = rustls::ServerConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .and_then(|builder| builder.with_no_client_auth().with_single_cert(certs, key))
+            .map_err(|err| Error::TlsConfigError(err.to_string()))?;
+        Ok(TlsAcceptor::from(Arc::new(config)))
+    }
+
+    pub fn is_tls(&self) -> bool {
+        self.acceptor.is_some()
+    }
+
+    /// Removes and returns the client whose connection was forwarded from `local_address`
+    pub fn take_peer(&self, local_address: &SocketAddr) -> Option<GatewayPeer> {
+        self.peers.lock().unwrap().remove(local_address)
+    }
+
+    /// Accepts the public connections and forwards them to `internal_address` until stopped
+    pub async fn listen(self: Arc<Self>, listener: TcpListener, internal_address: SocketAddr) {
+        let shutdown = self.shutdown.listener.clone();
+        tokio::pin!(shutdown);
+        loop {
+            let (stream, address) = tokio::select! {
+                _ = &mut shutdown => return,
+                accepted = listener.accept() => match accepted {
+                    Ok(accepted) => accepted,
+                    Err(err) => {
+                        warn!("WRPC, gateway failed to accept a connection: {}", err);
+                        sleep(ACCEPT_RETRY_DELAY).await;
+                        continue;
+                    }
+                },
+            };
+            let Ok(permit) = self.connections.clone().try_acquire_owned() else {
+                debug!("WRPC, gateway refused the connection of {}: too many connections", address);
+                continue;
+            };
+            let gateway = self.clone();
+            tokio::spawn(async move {
+                if let Err(err) = gateway.handle(stream, address, internal_address).await {
+                    debug!("WRPC, gateway dropped the connection of {}: {}", address, err);
+                }
+                drop(permit);
+            });
+        }
+    }
+
+    /// Stops accepting new connections
+    pub fn stop(&self) {
+        self.shutdown.trigger.trigger();
+    }
+
+    async fn handle(&self, stream: TcpStream, address: SocketAddr, internal_address: SocketAddr) -> std::io::Result<()> {
+        match self.acceptor.as_ref() {
+            Some(acceptor) => {
+                let stream = timeout(TLS_ACCEPT_TIMEOUT, acceptor.accept(stream)).await.map_err(|_| timed_out("TLS handshake"))??;
+                self.forward(stream, address, internal_address).await
+            }
+            None => self.forward(stream, address, internal_address).await,
+        }
+    }
+
+    async fn forward<S>(&self, mut stream: S, address: SocketAddr, internal_address: SocketAddr) -> std::io::Result<()>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let head = timeout(REQUEST_HEAD_TIMEOUT, read_request_head(&mut stream)).await.map_err(|_| timed_out("request head"))??;
+        let permissions = match self.authenticator.authenticate(request_token(&head).as_deref()) {
+            Ok(permissions) => permissions,
+            Err(err) => {
+                debug!("WRPC, gateway refused the connection of {}: {}", address, err);
+                return stream.write_all(UNAUTHORIZED_RESPONSE).await;
+            }
+        };
+
+        let mut internal = TcpStream::connect(internal_address).await?;
+        let local_address = internal.local_addr()?;
+        self.peers.lock().unwrap().insert(local_address, GatewayPeer { address, permissions });
+        trace!("WRPC, gateway forwarding {} through {}", address, local_address);
+
+        internal.write_all(&head).await?;
+        let result = copy_bidirectional(&mut stream, &mut internal).await;
+
+        // The peer is already taken if the WebSocket handshake succeeded
+        self.take_peer(&local_address);
+        result.map(|_| ())
+    }
+}
+
+fn timed_out(stage: &str) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::TimedOut, format!("{stage} timed out"))
+}
+
+/// Reads the bytes of the stream up to the end of the HTTP request head
+async fn read_request_head<S: AsyncRead + Unpin>(stream: &mut S) -> std::io::Result<Vec<u8>> {
+    let mut head = Vec::with_capacity(1024);
+    let mut buffer = [0u8; 1024];
+    while !head.windows(4).any(|window| window == b"\r\n\r\n") {
+        if head.len() >= MAX_REQUEST_HEAD_SIZE {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "request head is too large"));
+        }
+        let read = stream.read(&mut buffer).await?;
+        if read == 0 {
+            return Err(std::io::ErrorKind::UnexpectedEof.into());
+        }
+        head.extend_from_slice(&buffer[..read]);
+    }
+    Ok(head)
+}
+
+/// Extracts the token of an HTTP request head, looking first at the `Authorization` header and then at the `token` query parameter
+fn request_token(head: &[u8]) -> Option<String> {
+    let head = std::str::from_utf8(head).ok()?;
+    let mut lines = head.lines();
+    let request_line = lines.next()?;
+    let header_token = lines.take_while(|line| !line.is_empty()).find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        if name.trim().eq_ignore_ascii_case("authorization") {
+            RpcAuthenticator::bearer_token(value)
+        } else {
+            None
+        }
+    });
+    header_token
+        .or_else(|| {
+            let uri = request_line.split_whitespace().nth(1)?;
+            let (_, query) = uri.split_once('?')?;
+            query.split('&').find_map(|parameter| parameter.strip_prefix("token="))
+        })
+        .map(str::to_owned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_token() {
+        let head = b"GET / HTTP/1.1\r\nHost: localhost\r\nAuthorization: Bearer abc\r\nUpgrade: websocket\r\n\r\n";
+        assert_eq!(request_token(head).as_deref(), Some("abc"));
+        let head = b"GET /?encoding=borsh&token=def HTTP/1.1\r\nHost: localhost\r\n\r\n";
+        assert_eq!(request_token(head).as_deref(), Some("def"));
+        let head = b"GET / HTTP/1.1\r\nHost: localhost\r\nAuthorization: Basic abc\r\n\r\n";
+        assert_eq!(request_token(head), None);
+    }
+
+    #[tokio::test]
+    async fn test_connection_cap() {
+        let gateway = Arc::new(Gateway::new(Default::default(), None, 1).unwrap());
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let internal_listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        tokio::spawn(gateway.clone().listen(listener, internal_listener.local_addr().unwrap()));
+
+        // The second connection is closed right away while the first one still holds the only permit waiting for its request head
+        let mut first = TcpStream::connect(address).await.unwrap();
+        let mut second = TcpStream::connect(address).await.unwrap();
+        let mut buffer = [0u8; 1];
+        assert_eq!(timeout(Duration::from_secs(5), second.read(&mut buffer)).await.unwrap().unwrap(), 0);
+        assert!(timeout(Duration::from_millis(200), first.read(&mut buffer)).await.is_err());
+        gateway.stop();
+    }
+}
@@ -49,6 +49,12 @@ impl Router {
                 GetConnectedPeerInfo,
                 GetDaaScoreTimestampEstimate,
                 GetUtxoReturnAddress,
+                GetTransaction,
+                GetAddressHistory,
+                SubmitPackage,
+                GetVirtualChainChunk,
+                GetFeeEstimateHistory,
+                GetTransactionStatus,
                 GetCurrentNetwork,
                 GetDaaScoreTimestampEstimate,
                 GetFeeEstimate,
@@ -82,7 +88,10 @@ impl Router {
             RpcApiOps::Subscribe,
             workflow_rpc::server::Method::new(move |manager: Server, connection: Connection, scope: Serializable<Scope>| {
                 Box::pin(async move {
-                    manager.start_notify(&connection, scope.into_inner()).await.map_err(|err| err.to_string())?;
+                    let scope = scope.into_inner();
+                    manager.authorize(&connection, scope.event_type().into()).map_err(|err| err.to_string())?;
+                    manager.check_rate_limit(&connection, RpcApiOps::Subscribe).map_err(|err| err.to_string())?;
+                    manager.start_notify(&connection, scope).await.map_err(|err| err.to_string())?;
                     Ok(Serializable(SubscribeResponse::new(connection.id())))
                 })
             }),
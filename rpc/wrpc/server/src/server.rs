@@ -1,6 +1,7 @@
 use crate::{
     collector::{WrpcServiceCollector, WrpcServiceConverter},
     connection::Connection,
+    gateway::{Gateway, GatewayPeer},
     result::Result,
     service::Options,
 };
@@ -11,13 +12,17 @@ use vecno_notify::{
     listener::ListenerLifespan,
     notifier::Notifier,
     scope::Scope,
-    subscriber::Subscriber,
+    subscriber::{Subscriber, SubscriptionManager},
     subscription::{MutationPolicies, UtxosChangedMutationPolicy},
 };
 use vecno_rpc_core::{
-    api::rpc::{DynRpcService, RpcApi},
+    api::{
+        auth::RpcPermissions,
+        ops::RpcApiOps,
+        rpc::{DynRpcService, RpcApi},
+    },
     notify::{channel::NotificationChannel, connection::ChannelConnection, mode::NotificationMode},
-    Notification, RpcResult,
+    Notification, RpcError, RpcResult,
 };
 use vecno_rpc_service::service::RpcCoreService;
 use std::{
@@ -43,6 +48,7 @@ struct ServerInner {
     pub sockets: Mutex<HashMap<u64, Connection>>,
     pub rpc_core: Option<RpcCore>,
     pub options: Arc<Options>,
+    pub gateway: Option<Arc<Gateway>>,
 }
 
 #[derive(Clone)]
@@ -53,7 +59,7 @@ pub struct Server {
 const WRPC_SERVER: &str = "wrpc-server";
 
 impl Server {
-    pub fn new(tasks: usize, encoding: Encoding, core_service: Option<Arc<RpcCoreService>>, options: Arc<Options>) -> Self {
+    pub fn new(tasks: usize, encoding: Encoding, core_service: Option<Arc<RpcCoreService>>, options: Arc<Options>) -> Result<Self> {
         // This notifier UTXOs subscription granularity to rpc-core notifier
         let policies = MutationPolicies::new(UtxosChangedMutationPolicy::AddressSet);
 
@@ -77,29 +83,38 @@ impl Server {
             let converter = Arc::new(WrpcServiceConverter::new());
             let collector = Arc::new(WrpcServiceCollector::new(WRPC_SERVER, notification_channel.receiver(), converter));
             let subscriber = Arc::new(Subscriber::new(WRPC_SERVER, enabled_events, service.notifier(), listener_id));
-            let wrpc_notifier = Arc::new(Notifier::new(
-                WRPC_SERVER,
-                enabled_events,
-                vec![collector],
-                vec![subscriber],
-                service.subscription_context(),
-                tasks,
-                policies,
-            ));
+            let wrpc_notifier = Arc::new(
+                Notifier::new(
+                    WRPC_SERVER,
+                    enabled_events,
+                    vec![collector],
+                    vec![subscriber],
+                    service.subscription_context(),
+                    tasks,
+                    policies,
+                )
+                .with_replayer(service.clone()),
+            );
             Some(RpcCore { service, wrpc_notifier })
         } else {
             None
         };
 
-        Server {
+        let gateway = match options.is_gated() {
+            true => Some(Arc::new(Gateway::new(options.authenticator.clone(), options.tls.as_ref(), options.max_clients)?)),
+            false => None,
+        };
+
+        Ok(Server {
             inner: Arc::new(ServerInner {
                 next_connection_id: AtomicU64::new(0),
                 _encoding: encoding,
                 sockets: Mutex::new(HashMap::new()),
                 rpc_core,
                 options,
+                gateway,
             }),
-        }
+        })
     }
 
     pub fn start(&self) {
@@ -111,6 +126,16 @@ impl Server {
 
     pub async fn connect(&self, peer: &SocketAddr, messenger: Arc<Messenger>) -> Result<Connection> {
         // log_trace!("WebSocket connected: {}", peer);
+
+        // A gated server only accepts the connections forwarded by its gateway
+        let GatewayPeer { address, permissions } = match &self.inner.gateway {
+            Some(gateway) => gateway
+                .take_peer(peer)
+                .ok_or_else(|| WebSocketError::Other(format!("connection from {peer} did not go through the gateway")))?,
+            None => GatewayPeer { address: *peer, permissions: RpcPermissions::all() },
+        };
+        let peer = &address;
+
         let id = self.inner.next_connection_id.fetch_add(1, Ordering::SeqCst);
 
         let grpc_client = if let Some(grpc_proxy_address) = &self.inner.options.grpc_proxy_address {
@@ -134,7 +159,8 @@ impl Server {
         } else {
             None
         };
-        let connection = Connection::new(id, peer, messenger, grpc_client);
+        let rate_limiter = self.inner.options.rate_limiter.connection_limiter(peer.ip());
+        let connection = Connection::new(id, peer, permissions, rate_limiter, messenger, grpc_client);
         if self.inner.options.grpc_proxy_address.is_some() {
             // log_trace!("starting gRPC");
             connection.grpc_client().start(Some(connection.grpc_client_notify_target())).await;
@@ -191,7 +217,7 @@ impl Server {
         };
         workflow_log::log_trace!("notification subscribe[0x{listener_id:x}] {scope:?}");
         if let Some(rpc_core) = &self.inner.rpc_core {
-            rpc_core.wrpc_notifier.clone().try_start_notify(listener_id, scope)?;
+            rpc_core.wrpc_notifier.start_notify(listener_id, scope).await?;
         } else {
             connection.grpc_client().start_notify(listener_id, scope).await?;
         }
@@ -212,6 +238,24 @@ impl Server {
         Ok(())
     }
 
+    /// Returns an error if the credentials of the connection do not permit calling `op`
+    pub fn authorize(&self, connection: &Connection, op: RpcApiOps) -> RpcResult<()> {
+        match connection.permissions().allows(op) {
+            true => Ok(()),
+            false => Err(RpcError::PermissionDenied(format!("{op:?}"))),
+        }
+    }
+
+    /// Returns an error if the connection exceeds the rate limits of `op`
+    pub fn check_rate_limit(&self, connection: &Connection, op: RpcApiOps) -> RpcResult<()> {
+        connection.rate_limiter().check(op)
+    }
+
+    #[inline(always)]
+    pub fn gateway(&self) -> Option<Arc<Gateway>> {
+        self.inner.gateway.clone()
+    }
+
     pub fn verbose(&self) -> bool {
         self.inner.options.verbose
     }
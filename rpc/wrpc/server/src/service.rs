@@ -1,15 +1,22 @@
-use crate::{connection::*, router::*, server::*};
+use crate::{connection::*, result::Result, router::*, server::*};
 use async_trait::async_trait;
 use vecno_core::{
-    info,
+    error, info,
     task::service::{AsyncService, AsyncServiceError, AsyncServiceFuture},
     trace, warn,
 };
-use vecno_rpc_core::api::ops::RpcApiOps;
+use vecno_rpc_core::api::{
+    auth::{RpcAuthenticator, RpcTlsIdentity},
+    ops::RpcApiOps,
+    rate_limit::RpcRateLimiter,
+};
 use vecno_rpc_service::service::RpcCoreService;
 use vecno_utils::triggers::SingleTrigger;
 use std::sync::Arc;
-use tokio::sync::oneshot::{channel as oneshot_channel, Sender as OneshotSender};
+use tokio::{
+    net::TcpListener,
+    sync::oneshot::{channel as oneshot_channel, Receiver as OneshotReceiver, Sender as OneshotSender},
+};
 use workflow_rpc::server::prelude::*;
 pub use workflow_rpc::server::{Encoding as WrpcEncoding, WebSocketConfig, WebSocketCounters};
 
@@ -20,11 +27,34 @@ pub struct Options {
     pub listen_address: String,
     pub grpc_proxy_address: Option<String>,
     pub verbose: bool,
+    /// Authenticator of the clients, admitting all of them if no token is registered
+    pub authenticator: Arc<RpcAuthenticator>,
+    /// Identity of the server, which accepts only TLS connections when set
+    pub tls: Option<RpcTlsIdentity>,
+    /// Request rate limits of the clients, shared with the other RPC servers
+    pub rate_limiter: Arc<RpcRateLimiter>,
+    /// Maximum number of connections handled at once by the gateway of a gated server
+    pub max_clients: usize,
+}
+
+impl Options {
+    /// Returns whether the connections must go through a [`Gateway`](crate::gateway::Gateway)
+    pub fn is_gated(&self) -> bool {
+        self.authenticator.is_enabled() || self.tls.is_some()
+    }
 }
 
 impl Default for Options {
     fn default() -> Self {
-        Options { listen_address: "127.0.0.1:17110".to_owned(), verbose: false, grpc_proxy_address: None }
+        Options {
+            listen_address: "127.0.0.1:17110".to_owned(),
+            verbose: false,
+            grpc_proxy_address: None,
+            authenticator: Default::default(),
+            tls: None,
+            rate_limiter: Default::default(),
+            max_clients: 128,
+        }
     }
 }
 
@@ -54,8 +84,8 @@ impl VecnoRpcHandler {
         encoding: WrpcEncoding,
         core_service: Option<Arc<RpcCoreService>>,
         options: Arc<Options>,
-    ) -> VecnoRpcHandler {
-        VecnoRpcHandler { server: Server::new(tasks, encoding, core_service, options.clone()), options }
+    ) -> Result<VecnoRpcHandler> {
+        Ok(VecnoRpcHandler { server: Server::new(tasks, encoding, core_service, options.clone())?, options })
     }
 }
 
@@ -110,10 +140,10 @@ impl WrpcService {
         encoding: &Encoding,
         counters: Arc<WebSocketCounters>,
         options: Options,
-    ) -> Self {
+    ) -> Result<Self> {
         let options = Arc::new(options);
         // Create handle to manage connections
-        let rpc_handler = Arc::new(VecnoRpcHandler::new(tasks, *encoding, core_service, options.clone()));
+        let rpc_handler = Arc::new(VecnoRpcHandler::new(tasks, *encoding, core_service, options.clone())?);
 
         // Create router (initializes Interface registering RPC method and notification handlers)
         let router = Arc::new(Router::new(rpc_handler.server.clone()));
@@ -126,40 +156,73 @@ impl WrpcService {
             false,
         );
 
-        WrpcService { options, server, rpc_handler, shutdown: SingleTrigger::default() }
+        Ok(WrpcService { options, server, rpc_handler, shutdown: SingleTrigger::default() })
     }
 
-    /// Start listening on the configured address (will panic if the socket listen() fails)
-    pub fn serve(self: Arc<Self>) -> OneshotSender<()> {
+    /// Start listening on the configured address.
+    ///
+    /// Returns the sender terminating the server along with a receiver of the error preventing it from listening, if any.
+    pub fn serve(self: Arc<Self>) -> (OneshotSender<()>, OneshotReceiver<String>) {
         let (termination_sender, termination_receiver) = oneshot_channel::<()>();
+        let (failure_sender, failure_receiver) = oneshot_channel::<String>();
         let listen_address = self.options.listen_address.clone();
         self.rpc_handler.server.start();
 
+        let gateway = self.rpc_handler.server.gateway();
+
         // Spawn a task stopping the server on termination signal
         let service = self.clone();
+        let gateway_clone = gateway.clone();
         tokio::spawn(async move {
             let _ = termination_receiver.await;
+            if let Some(gateway) = gateway_clone {
+                gateway.stop();
+            }
             service.server.stop().unwrap_or_else(|err| warn!("wRPC unable to signal shutdown: `{err}`"));
             service.server.join().await.unwrap_or_else(|err| warn!("wRPC error: `{err}"));
         });
 
         // Spawn a task running the server
-        info!("WRPC Server starting on: {}", listen_address);
+        let tls = if gateway.as_ref().is_some_and(|gateway| gateway.is_tls()) { " (TLS)" } else { "" };
+        info!("WRPC Server starting on: {}{}", listen_address, tls);
         tokio::spawn(async move {
             let config = WebSocketConfig { max_message_size: Some(MAX_WRPC_MESSAGE_SIZE), ..Default::default() };
-            match self.server.bind(&listen_address).await {
-                Ok(listener) => {
-                    let serve_result = self.server.listen(listener, Some(config)).await;
-                    match serve_result {
-                        Ok(_) => info!("WRPC Server stopped on: {}", listen_address),
-                        Err(err) => panic!("WRPC Server {listen_address} stopped with error: {err:?}"),
+            // A gated server listens on an ephemeral loopback port only reachable through the gateway
+            let bind_address = if gateway.is_some() { "127.0.0.1:0" } else { listen_address.as_str() };
+            let listener = match self.server.bind(bind_address).await {
+                Ok(listener) => listener,
+                Err(err) => {
+                    let _ = failure_sender.send(format!("WRPC Server bind error on {listen_address}: {err:?}"));
+                    return;
+                }
+            };
+            if let Some(gateway) = gateway {
+                let internal_address = match listener.local_addr() {
+                    Ok(internal_address) => internal_address,
+                    Err(err) => {
+                        let _ = failure_sender.send(format!("WRPC Server internal listener error: {err}"));
+                        return;
+                    }
+                };
+                match TcpListener::bind(listen_address.as_str()).await {
+                    Ok(public_listener) => {
+                        tokio::spawn(gateway.listen(public_listener, internal_address));
+                    }
+                    Err(err) => {
+                        let _ = failure_sender.send(format!("WRPC Server bind error on {listen_address}: {err}"));
+                        return;
                     }
                 }
-                Err(err) => panic!("WRPC Server bind error on {listen_address}: {err:?}"),
+            }
+            match self.server.listen(listener, Some(config)).await {
+                Ok(_) => info!("WRPC Server stopped on: {}", listen_address),
+                Err(err) => {
+                    let _ = failure_sender.send(format!("WRPC Server {listen_address} stopped with error: {err:?}"));
+                }
             }
         });
 
-        termination_sender
+        (termination_sender, failure_receiver)
     }
 }
 
@@ -178,11 +241,19 @@ impl AsyncService for WrpcService {
 
         // Run the server
         trace!("{} running the wRPC server", WRPC_SERVER);
-        let terminate_server = self.clone().serve();
+        let (terminate_server, failure) = self.clone().serve();
 
         Box::pin(async move {
-            // Keep the gRPC server running until a service shutdown signal is received
-            shutdown_signal.await;
+            // Keep the gRPC server running until a service shutdown signal is received, or until it fails,
+            // in which case the error is returned so that the whole node shuts down
+            tokio::select! {
+                _ = shutdown_signal => {}
+                Ok(err) = failure => {
+                    error!("{}", err);
+                    drop(terminate_server);
+                    return Err(AsyncServiceError::Service(err));
+                }
+            }
 
             // Wait for the notifier to shutdown
             self.clone()
@@ -820,7 +820,7 @@ impl RpcClient {
     pub async fn subscribe_utxos_changed(&self, addresses: AddressOrStringArrayT) -> Result<()> {
         if let Some(listener_id) = self.listener_id() {
             let addresses: Vec<Address> = addresses.try_into()?;
-            self.inner.client.start_notify(listener_id, Scope::UtxosChanged(UtxosChangedScope { addresses })).await?;
+            self.inner.client.start_notify(listener_id, Scope::UtxosChanged(UtxosChangedScope::new(addresses))).await?;
         } else {
             log_error!("RPC subscribe on a closed connection");
         }
@@ -834,7 +834,7 @@ impl RpcClient {
     pub async fn unsubscribe_utxos_changed(&self, addresses: AddressOrStringArrayT) -> Result<()> {
         if let Some(listener_id) = self.listener_id() {
             let addresses: Vec<Address> = addresses.try_into()?;
-            self.inner.client.stop_notify(listener_id, Scope::UtxosChanged(UtxosChangedScope { addresses })).await?;
+            self.inner.client.stop_notify(listener_id, Scope::UtxosChanged(UtxosChangedScope::new(addresses))).await?;
         } else {
             log_error!("RPC unsubscribe on a closed connection");
         }
@@ -851,7 +851,7 @@ impl RpcClient {
         if let Some(listener_id) = self.listener_id() {
             self.inner
                 .client
-                .start_notify(listener_id, Scope::VirtualChainChanged(VirtualChainChangedScope { include_accepted_transaction_ids }))
+                .start_notify(listener_id, Scope::VirtualChainChanged(VirtualChainChangedScope::new(include_accepted_transaction_ids)))
                 .await?;
         } else {
             log_error!("RPC subscribe on a closed connection");
@@ -867,7 +867,41 @@ impl RpcClient {
         if let Some(listener_id) = self.listener_id() {
             self.inner
                 .client
-                .stop_notify(listener_id, Scope::VirtualChainChanged(VirtualChainChangedScope { include_accepted_transaction_ids }))
+                .stop_notify(listener_id, Scope::VirtualChainChanged(VirtualChainChangedScope::new(include_accepted_transaction_ids)))
+                .await?;
+        } else {
+            log_error!("RPC unsubscribe on a closed connection");
+        }
+        Ok(())
+    }
+
+    /// Manage subscription for a transaction status changed notification event.
+    /// Transaction status changed notification event is produced when a transaction
+    /// enters, moves within or leaves the mempool. The event notification will be
+    /// scoped to the provided list of transaction ids, or to all transactions if empty.
+    #[wasm_bindgen(js_name = subscribeTransactionStatusChanged)]
+    pub async fn subscribe_transaction_status_changed(&self, transaction_ids: JsValue) -> Result<()> {
+        if let Some(listener_id) = self.listener_id() {
+            let transaction_ids: Vec<RpcTransactionId> = from_value(transaction_ids)?;
+            self.inner
+                .client
+                .start_notify(listener_id, Scope::TransactionStatusChanged(TransactionStatusChangedScope::new(transaction_ids)))
+                .await?;
+        } else {
+            log_error!("RPC subscribe on a closed connection");
+        }
+        Ok(())
+    }
+
+    /// Manage subscription for a transaction status changed notification event.
+    /// Transaction status changed notification event is produced when a transaction
+    /// enters, moves within or leaves the mempool.
+    #[wasm_bindgen(js_name = unsubscribeTransactionStatusChanged)]
+    pub async fn unsubscribe_transaction_status_changed(&self) -> Result<()> {
+        if let Some(listener_id) = self.listener_id() {
+            self.inner
+                .client
+                .stop_notify(listener_id, Scope::TransactionStatusChanged(TransactionStatusChangedScope::default()))
                 .await?;
         } else {
             log_error!("RPC unsubscribe on a closed connection");
@@ -882,6 +916,7 @@ build_wrpc_wasm_bindgen_subscriptions!([
     // - VirtualChainChanged, // can't used this here due to non-C-style enum variant
     // - UtxosChanged, // can't used this here due to non-C-style enum variant
     // - VirtualDaaScoreChanged,
+    // - TransactionStatusChanged, // can't used this here due to non-C-style enum variant
     /// Manage subscription for a block added notification event.
     /// Block added notification event is produced when a new
     /// block is added to the Vecno BlockDAG.
@@ -1056,6 +1091,23 @@ build_wrpc_wasm_bindgen_interface!(
         /// Returned information: None.
         Unban,
         /// Get UTXO Return Addresses.
-        GetUtxoReturnAddress
+        GetUtxoReturnAddress,
+        /// Retrieves an accepted transaction by its id (requires --txindex).
+        GetTransaction,
+        /// Get a page of the received and spent events of an address.
+        /// Requires the node to run with `--addressindex`.
+        GetAddressHistory,
+        /// Submit a package of related transactions to the mempool.
+        /// All of them are accepted or none, the package paying the relay fee as a whole.
+        SubmitPackage,
+        /// Get a chunk of the virtual selected parent chain from a block, along with the transactions
+        /// accepted by each chain block and the UTXO entries they spend.
+        GetVirtualChainChunk,
+        /// Get the fee estimate, with the confidence of each feerate bucket as observed
+        /// in the recently mined blocks, along with the feerates paid in these blocks.
+        GetFeeEstimateHistory,
+        /// Get the status of a transaction, from the mempool outcome to its acceptance by the virtual chain.
+        /// Returned information: State, rejection or eviction reason, accepting block and confirmations.
+        GetTransactionStatus
     ]
 );
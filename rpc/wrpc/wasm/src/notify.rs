@@ -25,6 +25,7 @@ export enum RpcEventType {
     VirtualDaaScoreChanged = "virtual-daa-score-changed",
     PruningPointUtxoSetOverride = "pruning-point-utxo-set-override",
     NewBlockTemplate = "new-block-template",
+    TransactionStatusChanged = "transaction-status-changed",
 }
 
 /**
@@ -40,7 +41,8 @@ export type RpcEventData = IBlockAdded
     | ISinkBlueScoreChanged 
     | IVirtualDaaScoreChanged 
     | IPruningPointUtxoSetOverride 
-    | INewBlockTemplate;
+    | INewBlockTemplate 
+    | ITransactionStatusChanged;
 
 /**
  * RPC notification event data map.
@@ -59,6 +61,7 @@ export type RpcEventMap = {
     "virtual-daa-score-changed" : IVirtualDaaScoreChanged,
     "pruning-point-utxo-set-override" : IPruningPointUtxoSetOverride,
     "new-block-template" : INewBlockTemplate,
+    "transaction-status-changed" : ITransactionStatusChanged,
 }
 
 /**
@@ -84,6 +87,7 @@ export type RpcEvent = {
  * {@link RpcClient.subscribeSinkBlueScoreChanged},
  * {@link RpcClient.subscribePruningPointUtxoSetOverride},
  * {@link RpcClient.subscribeNewBlockTemplate},
+ * {@link RpcClient.subscribeTransactionStatusChanged},
  * 
  * @category Node RPC
  */
@@ -241,3 +245,19 @@ declare! {
     }
     "#,
 }
+
+declare! {
+    ITransactionStatusChanged,
+    r#"
+    /**
+     * Transaction status changed notification event is produced when a transaction
+     * enters, moves within or leaves the mempool. The event notification is scoped
+     * to the transaction ids specified during the subscription, if any.
+     * 
+     * @category Node RPC
+     */
+    export interface ITransactionStatusChanged {
+        [key: string]: any;
+    }
+    "#,
+}
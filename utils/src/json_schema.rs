@@ -0,0 +1,59 @@
+//!
+//! JSON Schema support for the types serialized by hand, enabled in the crates defining them by their `schemars` feature.
+//!
+
+/// Implements `schemars::JsonSchema` for a type serialized as a lowercase hex string, such as the types using
+/// [`serde_impl_ser_fixed_bytes_ref`](crate::serde_impl_ser_fixed_bytes_ref). The string holds exactly `$size` bytes
+/// when `$size` is `Some`.
+///
+/// The invoking crate must depend on `schemars`.
+#[macro_export]
+macro_rules! json_schema_impl_hex_string {
+    ($t: ty, $size: expr) => {
+        impl schemars::JsonSchema for $t {
+            fn schema_name() -> std::string::String {
+                stringify!($t).to_owned()
+            }
+
+            fn json_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+                let size: Option<usize> = $size;
+                schemars::schema::SchemaObject {
+                    instance_type: Some(schemars::schema::InstanceType::String.into()),
+                    string: Some(Box::new(schemars::schema::StringValidation {
+                        max_length: size.map(|size| size as u32 * 2),
+                        min_length: size.map(|size| size as u32 * 2),
+                        pattern: Some("^([0-9a-f]{2})*$".to_owned()),
+                    })),
+                    ..Default::default()
+                }
+                .into()
+            }
+        }
+    };
+}
+
+/// Implements `schemars::JsonSchema` for a type serialized as a string through its `Display` implementation.
+///
+/// The invoking crate must depend on `schemars`.
+#[macro_export]
+macro_rules! json_schema_impl_display_string {
+    ($t: ty, $description: expr) => {
+        impl schemars::JsonSchema for $t {
+            fn schema_name() -> std::string::String {
+                stringify!($t).to_owned()
+            }
+
+            fn json_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+                schemars::schema::SchemaObject {
+                    instance_type: Some(schemars::schema::InstanceType::String.into()),
+                    metadata: Some(Box::new(schemars::schema::Metadata {
+                        description: Some($description.to_owned()),
+                        ..Default::default()
+                    })),
+                    ..Default::default()
+                }
+                .into()
+            }
+        }
+    };
+}
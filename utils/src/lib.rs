@@ -12,6 +12,7 @@ pub mod expiring_cache;
 pub mod hashmap;
 pub mod hex;
 pub mod iter;
+pub mod json_schema;
 pub mod mem_size;
 pub mod networking;
 pub mod refs;
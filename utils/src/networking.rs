@@ -2,12 +2,15 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use ipnet::IpNet;
 use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
 use std::{
     fmt::Display,
     net::{AddrParseError, IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    num::ParseIntError,
     ops::Deref,
     str::FromStr,
 };
+use thiserror::Error;
 use uuid::Uuid;
 use wasm_bindgen::prelude::*;
 
@@ -72,6 +75,7 @@ impl From<&NetAddress> for PrefixBucket {
 
 /// An IP address, newtype of [IpAddr].
 #[derive(PartialEq, Eq, Hash, Copy, Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[repr(transparent)]
 pub struct IpAddress(pub IpAddr);
 
@@ -128,6 +132,11 @@ impl IpAddress {
     pub fn prefix_bucket(&self) -> PrefixBucket {
         PrefixBucket::from(self)
     }
+
+    /// Returns whether this IP stands for an onion service address, see [`OnionAddress::net_address`]
+    pub fn is_onion(&self) -> bool {
+        matches!(self.0, IpAddr::V6(ip) if ip.octets().starts_with(&ONION_IP_PREFIX))
+    }
 }
 
 impl From<IpAddr> for IpAddress {
@@ -220,6 +229,7 @@ impl BorshDeserialize for IpAddress {
 
 /// A network address, equivalent of a [SocketAddr].
 #[derive(PartialEq, Eq, Hash, Copy, Clone, Serialize, Deserialize, Debug, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct NetAddress {
     pub ip: IpAddress,
     pub port: u16,
@@ -261,10 +271,193 @@ impl Display for NetAddress {
     }
 }
 
+/// The fd87:d87e:eb43::/48 range (a.k.a. OnionCat range) of the IPs standing for onion service addresses
+const ONION_IP_PREFIX: [u8; 6] = [0xfd, 0x87, 0xd8, 0x7e, 0xeb, 0x43];
+
+const ONION_SUFFIX: &str = ".onion";
+const ONION_VERSION: u8 = 3;
+const ONION_CHECKSUM_LEN: usize = 2;
+const BASE32_ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum OnionAddressError {
+    #[error("onion address host must end with {ONION_SUFFIX}")]
+    MissingSuffix,
+
+    #[error("onion address is missing a port")]
+    MissingPort,
+
+    #[error("invalid onion address port: {0}")]
+    InvalidPort(#[from] ParseIntError),
+
+    #[error("onion address host is not a valid v3 onion service encoding")]
+    InvalidEncoding,
+
+    #[error("onion address host has an invalid checksum")]
+    InvalidChecksum,
+}
+
+/// A v3 onion service address of the Tor network, along with a port.
+///
+/// Onion addresses are reachable through a SOCKS5 proxy only. Elsewhere, the peer is tracked by the IP returned by
+/// [`OnionAddress::net_address`], so that connections, bans and the address store keep working with IPs.
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
+pub struct OnionAddress {
+    public_key: [u8; 32],
+    pub port: u16,
+}
+
+impl OnionAddress {
+    pub fn new(public_key: [u8; 32], port: u16) -> Self {
+        Self { public_key, port }
+    }
+
+    /// Parses an onion service host of the form `<base32 of public key, checksum and version>.onion`
+    pub fn from_host(host: &str, port: u16) -> Result<Self, OnionAddressError> {
+        let encoded = host.strip_suffix(ONION_SUFFIX).ok_or(OnionAddressError::MissingSuffix)?;
+        let bytes = base32_decode(encoded).ok_or(OnionAddressError::InvalidEncoding)?;
+        let Ok::<[u8; 35], _>(bytes) = bytes.try_into() else {
+            return Err(OnionAddressError::InvalidEncoding);
+        };
+        if bytes[34] != ONION_VERSION {
+            return Err(OnionAddressError::InvalidEncoding);
+        }
+        let public_key: [u8; 32] = bytes[..32].try_into().unwrap();
+        if bytes[32..34] != onion_checksum(&public_key) {
+            return Err(OnionAddressError::InvalidChecksum);
+        }
+        Ok(Self::new(public_key, port))
+    }
+
+    /// Parses `<host>.onion[:port]`, using `default_port` when the port is not specified
+    pub fn from_str_with_default_port(s: &str, default_port: u16) -> Result<Self, OnionAddressError> {
+        match s.rsplit_once(':') {
+            Some(_) => Self::from_str(s),
+            None => Self::from_host(s, default_port),
+        }
+    }
+
+    pub fn public_key(&self) -> &[u8; 32] {
+        &self.public_key
+    }
+
+    pub fn host(&self) -> String {
+        let mut bytes = [0u8; 35];
+        bytes[..32].copy_from_slice(&self.public_key);
+        bytes[32..34].copy_from_slice(&onion_checksum(&self.public_key));
+        bytes[34] = ONION_VERSION;
+        format!("{}{ONION_SUFFIX}", base32_encode(&bytes))
+    }
+
+    /// Returns the address standing for this onion address, made of the fd87:d87e:eb43::/48 prefix followed by
+    /// the first bytes of the public key
+    pub fn net_address(&self) -> NetAddress {
+        let mut octets = [0u8; 16];
+        octets[..ONION_IP_PREFIX.len()].copy_from_slice(&ONION_IP_PREFIX);
+        octets[ONION_IP_PREFIX.len()..].copy_from_slice(&self.public_key[..16 - ONION_IP_PREFIX.len()]);
+        NetAddress::new(Ipv6Addr::from(octets).into(), self.port)
+    }
+}
+
+impl FromStr for OnionAddress {
+    type Err = OnionAddressError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (host, port) = s.rsplit_once(':').ok_or(OnionAddressError::MissingPort)?;
+        Self::from_host(host, port.parse()?)
+    }
+}
+
+impl Display for OnionAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.host(), self.port)
+    }
+}
+
+/// The address of a peer as gossiped over the network, either an IP address or an onion service address
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
+pub enum PeerAddress {
+    Ip(NetAddress),
+    Onion(OnionAddress),
+}
+
+impl PeerAddress {
+    /// Returns the IP address of the peer, or the address standing for it if it is an onion service
+    pub fn net_address(&self) -> NetAddress {
+        match self {
+            PeerAddress::Ip(address) => *address,
+            PeerAddress::Onion(address) => address.net_address(),
+        }
+    }
+}
+
+impl From<NetAddress> for PeerAddress {
+    fn from(value: NetAddress) -> Self {
+        Self::Ip(value)
+    }
+}
+
+impl From<OnionAddress> for PeerAddress {
+    fn from(value: OnionAddress) -> Self {
+        Self::Onion(value)
+    }
+}
+
+impl Display for PeerAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PeerAddress::Ip(address) => address.fmt(f),
+            PeerAddress::Onion(address) => address.fmt(f),
+        }
+    }
+}
+
+fn onion_checksum(public_key: &[u8; 32]) -> [u8; ONION_CHECKSUM_LEN] {
+    let mut hasher = Sha3_256::new();
+    hasher.update(b".onion checksum");
+    hasher.update(public_key);
+    hasher.update([ONION_VERSION]);
+    hasher.finalize()[..ONION_CHECKSUM_LEN].try_into().unwrap()
+}
+
+/// Encodes to lowercase unpadded base32 (RFC 4648), as used by onion service hosts
+fn base32_encode(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity((bytes.len() * 8).div_ceil(5));
+    let (mut buffer, mut bits) = (0u32, 0);
+    for &byte in bytes {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            encoded.push(BASE32_ALPHABET[((buffer >> bits) & 31) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        encoded.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 31) as usize] as char);
+    }
+    encoded
+}
+
+fn base32_decode(encoded: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(encoded.len() * 5 / 8);
+    let (mut buffer, mut bits) = (0u32, 0);
+    for c in encoded.bytes() {
+        let value = BASE32_ALPHABET.iter().position(|&x| x == c.to_ascii_lowercase())? as u32;
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+    Some(bytes)
+}
+
 /// A network address possibly without explicit port.
 ///
 /// Use `normalize` to get a fully determined address.
 #[derive(PartialEq, Eq, Hash, Copy, Clone, Serialize, Deserialize, Debug, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ContextualNetAddress {
     ip: IpAddress,
     port: Option<u16>,
@@ -342,6 +535,7 @@ impl Display for ContextualNetAddress {
     }
 }
 #[derive(PartialEq, Eq, Hash, Copy, Clone, Serialize, Deserialize, Debug, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[repr(transparent)]
 pub struct PeerId(pub Uuid);
 
@@ -447,6 +641,31 @@ mod tests {
         assert!(addr_v6.is_ok());
     }
 
+    #[test]
+    fn test_onion_address() {
+        let host = "2gzyxa5ihm7nsggfxnu52rck2vv4rvmdlkiu3zzui5du4xyclen53wid.onion";
+        let addr = OnionAddress::from_str(&format!("{host}:7111")).unwrap();
+        assert_eq!(addr.public_key()[..4], [0xd1, 0xb3, 0x8b, 0x83]);
+        assert_eq!(addr.to_string(), format!("{host}:7111"));
+        assert_eq!(OnionAddress::from_str_with_default_port(host, 7111), Ok(addr));
+
+        let net_addr = addr.net_address();
+        assert!(net_addr.ip.is_onion() && !net_addr.ip.is_publicly_routable());
+        assert_eq!(net_addr.to_string(), "[fd87:d87e:eb43:d1b3:8b83:a83b:3ed9:18c5]:7111");
+        assert!(!IpAddress::from_str("fd87:d87e:eb44::").unwrap().is_onion());
+
+        assert_eq!(OnionAddress::from_str(host), Err(OnionAddressError::MissingPort));
+        assert_eq!(
+            OnionAddress::from_str("2gzyxa5ihm7nsggfxnu52rck2vv4rvmdlkiu3zzui5du4xyclen53wid:7111"),
+            Err(OnionAddressError::MissingSuffix)
+        );
+        assert_eq!(
+            OnionAddress::from_str("2gzyxa5ihm7nsggfxnu52rck2vv4rvmdlkiu3zzui5du4xyclen53aid.onion:7111"),
+            Err(OnionAddressError::InvalidChecksum)
+        );
+        assert_eq!(OnionAddress::from_str("expyuzz4wqqyqhjn.onion:7111"), Err(OnionAddressError::InvalidEncoding));
+    }
+
     #[test]
     fn test_prefix_bucket() {
         let prefix_bytes: [u8; 2] = [42u8, 43u8];
@@ -4,12 +4,17 @@ use vecno_consensus_core::{
     network::{NetworkId, NetworkType},
 };
 use vecno_core::vecnod_env::version;
+use vecno_mining::mempool::policy::MempoolPolicyConfig;
 use vecno_notify::address::tracker::Tracker;
-use vecno_utils::networking::ContextualNetAddress;
+use vecno_rpc_core::api::{
+    auth::RpcCredential,
+    rate_limit::{RpcRateCategory, RpcRateLimit},
+};
+use vecno_utils::networking::{ContextualNetAddress, OnionAddress};
 use vecno_wrpc_server::address::WrpcNetAddress;
 use serde::Deserialize;
 use serde_with::{serde_as, DisplayFromStr};
-use std::{ffi::OsString, fs};
+use std::{collections::HashMap, ffi::OsString, fs, time::Duration};
 use toml::from_str;
 
 #[cfg(feature = "devnet-prealloc")]
@@ -21,6 +26,9 @@ use vecno_txscript::pay_to_address_script;
 #[cfg(feature = "devnet-prealloc")]
 use std::sync::Arc;
 
+/// The default port of the SOCKS5 proxy of a Tor daemon
+const DEFAULT_PROXY_PORT: u16 = 9050;
+
 #[serde_as]
 #[derive(Debug, Clone, Deserialize)]
 #[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
@@ -36,8 +44,17 @@ pub struct Args {
     pub rpclisten_borsh: Option<WrpcNetAddress>,
     #[serde_as(as = "Option<DisplayFromStr>")]
     pub rpclisten_json: Option<WrpcNetAddress>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub rpclisten_http: Option<ContextualNetAddress>,
     #[serde(rename = "unsaferpc")]
     pub unsafe_rpc: bool,
+    pub rpc_tls_cert: Option<String>,
+    pub rpc_tls_key: Option<String>,
+    pub rpc_auth_cookie: bool,
+    /// RPC credentials, only configurable through the `[[rpc-credentials]]` tables of the config file
+    pub rpc_credentials: Vec<RpcCredential>,
+    /// RPC rate limits, only configurable through the `[rpc-rate-limits]` section of the config file
+    pub rpc_rate_limits: HashMap<RpcRateCategory, RpcRateLimit>,
     pub wrpc_verbose: bool,
     #[serde(rename = "loglevel")]
     pub log_level: String,
@@ -50,9 +67,23 @@ pub struct Args {
     pub add_peers: Vec<ContextualNetAddress>,
     #[serde_as(as = "Option<DisplayFromStr>")]
     pub listen: Option<ContextualNetAddress>,
+    pub p2p_encryption: bool,
+    #[serde(rename = "p2p-allow-peer")]
+    pub p2p_allowed_peers: Vec<String>,
+    pub tx_stem_relay: bool,
+    #[serde(rename = "banthreshold")]
+    pub ban_threshold: u32,
+    #[serde(rename = "banduration")]
+    pub ban_duration: u64,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub proxy: Option<ContextualNetAddress>,
+    pub onion_address: Option<String>,
     #[serde(rename = "uacomment")]
     pub user_agent_comments: Vec<String>,
     pub utxoindex: bool,
+    pub txindex: bool,
+    pub addressindex: bool,
+    pub persist_mempool: bool,
     pub reset_db: bool,
     #[serde(rename = "outpeers")]
     pub outbound_target: usize,
@@ -76,6 +107,17 @@ pub struct Args {
     pub perf_metrics: bool,
     pub perf_metrics_interval_sec: u64,
     pub block_template_cache_lifetime: Option<u64>,
+    pub mine: bool,
+    #[serde(rename = "miningaddr")]
+    pub mining_address: Option<String>,
+    pub mining_threads: usize,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub stratumlisten: Option<ContextualNetAddress>,
+    #[serde(rename = "stratum-payaddr")]
+    pub stratum_pay_address: Option<String>,
+    pub stratum_difficulty: f64,
+    pub stratum_shares_per_minute: u32,
+    pub stratum_max_connections: usize,
 
     #[cfg(feature = "devnet-prealloc")]
     pub num_prealloc_utxos: Option<u64>,
@@ -91,6 +133,8 @@ pub struct Args {
     pub disable_grpc: bool,
     pub ram_scale: f64,
     pub retention_period_days: Option<f64>,
+    /// Mempool policy, only configurable through the `[mempool-policy]` section of the config file
+    pub mempool_policy: MempoolPolicyConfig,
 }
 
 impl Default for Args {
@@ -100,9 +144,18 @@ impl Default for Args {
             no_log_files: false,
             rpclisten_borsh: None,
             rpclisten_json: None,
+            rpclisten_http: None,
             unsafe_rpc: false,
+            rpc_tls_cert: None,
+            rpc_tls_key: None,
+            rpc_auth_cookie: false,
+            rpc_credentials: vec![],
+            rpc_rate_limits: Default::default(),
             async_threads: num_cpus::get(),
             utxoindex: false,
+            txindex: false,
+            addressindex: false,
+            persist_mempool: false,
             reset_db: false,
             outbound_target: 8,
             inbound_limit: 128,
@@ -123,12 +176,27 @@ impl Default for Args {
             connect_peers: vec![],
             add_peers: vec![],
             listen: None,
+            p2p_encryption: false,
+            p2p_allowed_peers: vec![],
+            tx_stem_relay: false,
+            ban_threshold: 100,
+            ban_duration: 24 * 60 * 60,
+            proxy: None,
+            onion_address: None,
             user_agent_comments: vec![],
             yes: false,
             perf_metrics: false,
             perf_metrics_interval_sec: 10,
             externalip: None,
             block_template_cache_lifetime: None,
+            mine: false,
+            mining_address: None,
+            mining_threads: 1,
+            stratumlisten: None,
+            stratum_pay_address: None,
+            stratum_difficulty: 1.0,
+            stratum_shares_per_minute: 20,
+            stratum_max_connections: 128,
 
             #[cfg(feature = "devnet-prealloc")]
             num_prealloc_utxos: None,
@@ -142,6 +210,7 @@ impl Default for Args {
             disable_grpc: false,
             ram_scale: 1.0,
             retention_period_days: None,
+            mempool_policy: Default::default(),
         }
     }
 }
@@ -149,7 +218,16 @@ impl Default for Args {
 impl Args {
     pub fn apply_to_config(&self, config: &mut Config) {
         config.utxoindex = self.utxoindex;
+        config.txindex = self.txindex;
+        config.addressindex = self.addressindex;
         config.disable_upnp = self.disable_upnp;
+        config.tx_stem_relay = self.tx_stem_relay;
+        config.ban_threshold = self.ban_threshold;
+        config.ban_duration = Duration::from_secs(self.ban_duration);
+        config.proxy = self.proxy.map(|v| v.normalize(DEFAULT_PROXY_PORT));
+        // Invalid onion addresses are rejected by the arguments validation
+        config.onion_address =
+            self.onion_address.as_deref().and_then(|v| OnionAddress::from_str_with_default_port(v, config.default_p2p_port()).ok());
         config.unsafe_rpc = self.unsafe_rpc;
         config.enable_unsynced_mining = self.enable_unsynced_mining;
         config.enable_mainnet_mining = self.enable_mainnet_mining;
@@ -252,7 +330,32 @@ pub fn cli() -> Command {
                 .value_parser(clap::value_parser!(WrpcNetAddress))
                 .help("Interface:port to listen for wRPC JSON connections (default port: 18110, testnet: 18210)."),
         )
+        .arg(
+            Arg::new("rpclisten-http")
+                .long("rpclisten-http")
+                .value_name("IP[:PORT]")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(ContextualNetAddress))
+                .help("Interface:port to listen for JSON-RPC 2.0 over HTTP requests (default port: 10110, testnet: 10210). The HTTP server is disabled if omitted."),
+        )
         .arg(arg!(--unsaferpc "Enable RPC commands which affect the state of the node"))
+        .arg(
+            Arg::new("rpc-tls-cert")
+                .long("rpc-tls-cert")
+                .value_name("PATH")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("PEM encoded certificate chain served by the gRPC, wRPC and JSON-RPC listeners, which then only accept TLS connections (requires --rpc-tls-key)."),
+        )
+        .arg(
+            Arg::new("rpc-tls-key")
+                .long("rpc-tls-key")
+                .value_name("PATH")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("PEM encoded private key of the --rpc-tls-cert certificate."),
+        )
+        .arg(arg!(--"rpc-auth-cookie" "Require RPC clients to present the token written to the rpc.cookie file of the network directory on startup"))
         .arg(
             Arg::new("connect-peers")
                 .long("connect")
@@ -279,6 +382,49 @@ pub fn cli() -> Command {
                 .value_parser(clap::value_parser!(ContextualNetAddress))
                 .help("Add an interface:port to listen for connections (default all interfaces port: 16111, testnet: 16211)."),
         )
+        .arg(arg!(--"p2p-encryption" "Encrypt the P2P connections with the peers supporting it, authenticating with the node identity key stored in the network directory"))
+        .arg(
+            Arg::new("p2p-allow-peer")
+                .long("p2p-allow-peer")
+                .value_name("IDENTITY_KEY")
+                .action(ArgAction::Append)
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Only connect with the peers of the specified hex encoded identity keys over the encrypted transport (implies --p2p-encryption)."),
+        )
+        .arg(arg!(--"tx-stem-relay" "Relay locally submitted transactions through a single peer before diffusing them, hiding their origin"))
+        .arg(
+            Arg::new("banthreshold")
+                .long("banthreshold")
+                .value_name("banthreshold")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u32))
+                .help("Misbehavior score at which peers get disconnected and banned (default: 100)."),
+        )
+        .arg(
+            Arg::new("banduration")
+                .long("banduration")
+                .value_name("SECONDS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help("How long to ban misbehaving peers, in seconds (default: 86400)."),
+        )
+        .arg(
+            Arg::new("proxy")
+                .long("proxy")
+                .value_name("IP[:PORT]")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(ContextualNetAddress))
+                .help("Connect to peers through a SOCKS5 proxy, required to connect to onion addresses (eg. 127.0.0.1:9050, default port: 9050)."),
+        )
+        .arg(
+            Arg::new("onion-address")
+                .long("onion-address")
+                .value_name("HOST.onion[:PORT]")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Advertise the onion address forwarding to the P2P listener to peers, in place of the IP addresses of the node (default port: the P2P port)."),
+        )
         .arg(
             Arg::new("outpeers")
                 .long("outpeers")
@@ -312,7 +458,67 @@ pub fn cli() -> Command {
                 .hide(true)
                 .help("Allow mainnet mining (currently enabled by default while the flag is kept for backwards compatibility)"),
         )
+        .arg(arg!(--mine "Enable the built-in CPU miner, mainly meant for devnets and simnets (see also --enable-unsynced-mining)"))
+        .arg(
+            Arg::new("miningaddr")
+                .long("miningaddr")
+                .value_name("ADDRESS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Address receiving the rewards of the blocks mined by the built-in CPU miner."),
+        )
+        .arg(
+            Arg::new("mining-threads")
+                .long("mining-threads")
+                .value_name("mining-threads")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(usize))
+                .help("Number of threads used by the built-in CPU miner (default: 1)."),
+        )
+        .arg(
+            Arg::new("stratumlisten")
+                .long("stratumlisten")
+                .value_name("IP[:PORT]")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(ContextualNetAddress))
+                .help("Interface:port to listen for stratum miner connections (default port: 5555). The stratum server is disabled if omitted."),
+        )
+        .arg(
+            Arg::new("stratum-payaddr")
+                .long("stratum-payaddr")
+                .value_name("ADDRESS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Address receiving the rewards of the blocks mined through the stratum server."),
+        )
+        .arg(
+            Arg::new("stratum-difficulty")
+                .long("stratum-difficulty")
+                .value_name("stratum-difficulty")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(f64))
+                .help("Initial share difficulty of stratum connections (default: 1.0)."),
+        )
+        .arg(
+            Arg::new("stratum-shares-per-minute")
+                .long("stratum-shares-per-minute")
+                .value_name("stratum-shares-per-minute")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u32))
+                .help("Share rate targeted by the stratum variable difficulty, 0 disables the adjustments (default: 20)."),
+        )
+        .arg(
+            Arg::new("stratum-max-connections")
+                .long("stratum-max-connections")
+                .value_name("stratum-max-connections")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(usize))
+                .help("Maximum number of stratum miner connections handled at once (default: 128)."),
+        )
         .arg(arg!(--utxoindex "Enable the UTXO index"))
+        .arg(arg!(--txindex "Enable the transaction index"))
+        .arg(arg!(--addressindex "Enable the address history index"))
+        .arg(arg!(--"persist-mempool" "Save the mempool to disk periodically and on shutdown, and restore it on startup"))
         .arg(
             Arg::new("max-tracked-addresses")
                 .long("max-tracked-addresses")
@@ -425,13 +631,26 @@ impl Args {
             rpclisten: m.get_one::<ContextualNetAddress>("rpclisten").cloned().or(defaults.rpclisten),
             rpclisten_borsh: m.get_one::<WrpcNetAddress>("rpclisten-borsh").cloned().or(defaults.rpclisten_borsh),
             rpclisten_json: m.get_one::<WrpcNetAddress>("rpclisten-json").cloned().or(defaults.rpclisten_json),
+            rpclisten_http: m.get_one::<ContextualNetAddress>("rpclisten-http").cloned().or(defaults.rpclisten_http),
             unsafe_rpc: arg_match_unwrap_or::<bool>(&m, "unsaferpc", defaults.unsafe_rpc),
+            rpc_tls_cert: m.get_one::<String>("rpc-tls-cert").cloned().or(defaults.rpc_tls_cert),
+            rpc_tls_key: m.get_one::<String>("rpc-tls-key").cloned().or(defaults.rpc_tls_key),
+            rpc_auth_cookie: arg_match_unwrap_or::<bool>(&m, "rpc-auth-cookie", defaults.rpc_auth_cookie),
+            rpc_credentials: defaults.rpc_credentials,
+            rpc_rate_limits: defaults.rpc_rate_limits,
             wrpc_verbose: false,
             log_level: arg_match_unwrap_or::<String>(&m, "log_level", defaults.log_level),
             async_threads: arg_match_unwrap_or::<usize>(&m, "async_threads", defaults.async_threads),
             connect_peers: arg_match_many_unwrap_or::<ContextualNetAddress>(&m, "connect-peers", defaults.connect_peers),
             add_peers: arg_match_many_unwrap_or::<ContextualNetAddress>(&m, "add-peers", defaults.add_peers),
             listen: m.get_one::<ContextualNetAddress>("listen").cloned().or(defaults.listen),
+            p2p_encryption: arg_match_unwrap_or::<bool>(&m, "p2p-encryption", defaults.p2p_encryption),
+            p2p_allowed_peers: arg_match_many_unwrap_or::<String>(&m, "p2p-allow-peer", defaults.p2p_allowed_peers),
+            tx_stem_relay: arg_match_unwrap_or::<bool>(&m, "tx-stem-relay", defaults.tx_stem_relay),
+            ban_threshold: arg_match_unwrap_or::<u32>(&m, "banthreshold", defaults.ban_threshold),
+            ban_duration: arg_match_unwrap_or::<u64>(&m, "banduration", defaults.ban_duration),
+            proxy: m.get_one::<ContextualNetAddress>("proxy").cloned().or(defaults.proxy),
+            onion_address: m.get_one::<String>("onion-address").cloned().or(defaults.onion_address),
             outbound_target: arg_match_unwrap_or::<usize>(&m, "outpeers", defaults.outbound_target),
             inbound_limit: arg_match_unwrap_or::<usize>(&m, "maxinpeers", defaults.inbound_limit),
             rpc_max_clients: arg_match_unwrap_or::<usize>(&m, "rpcmaxclients", defaults.rpc_max_clients),
@@ -440,6 +659,9 @@ impl Args {
             enable_unsynced_mining: arg_match_unwrap_or::<bool>(&m, "enable-unsynced-mining", defaults.enable_unsynced_mining),
             enable_mainnet_mining: arg_match_unwrap_or::<bool>(&m, "enable-mainnet-mining", defaults.enable_mainnet_mining),
             utxoindex: arg_match_unwrap_or::<bool>(&m, "utxoindex", defaults.utxoindex),
+            txindex: arg_match_unwrap_or::<bool>(&m, "txindex", defaults.txindex),
+            addressindex: arg_match_unwrap_or::<bool>(&m, "addressindex", defaults.addressindex),
+            persist_mempool: arg_match_unwrap_or::<bool>(&m, "persist-mempool", defaults.persist_mempool),
             testnet: arg_match_unwrap_or::<bool>(&m, "testnet", defaults.testnet),
             testnet_suffix: arg_match_unwrap_or::<u32>(&m, "netsuffix", defaults.testnet_suffix),
             devnet: arg_match_unwrap_or::<bool>(&m, "devnet", defaults.devnet),
@@ -453,11 +675,20 @@ impl Args {
             perf_metrics_interval_sec: arg_match_unwrap_or::<u64>(&m, "perf-metrics-interval-sec", defaults.perf_metrics_interval_sec),
             // Note: currently used programmatically by benchmarks and not exposed to CLI users
             block_template_cache_lifetime: defaults.block_template_cache_lifetime,
+            mine: arg_match_unwrap_or::<bool>(&m, "mine", defaults.mine),
+            mining_address: m.get_one::<String>("miningaddr").cloned().or(defaults.mining_address),
+            mining_threads: arg_match_unwrap_or::<usize>(&m, "mining-threads", defaults.mining_threads),
+            stratumlisten: m.get_one::<ContextualNetAddress>("stratumlisten").cloned().or(defaults.stratumlisten),
+            stratum_pay_address: m.get_one::<String>("stratum-payaddr").cloned().or(defaults.stratum_pay_address),
+            stratum_difficulty: arg_match_unwrap_or::<f64>(&m, "stratum-difficulty", defaults.stratum_difficulty),
+            stratum_shares_per_minute: arg_match_unwrap_or::<u32>(&m, "stratum-shares-per-minute", defaults.stratum_shares_per_minute),
+            stratum_max_connections: arg_match_unwrap_or::<usize>(&m, "stratum-max-connections", defaults.stratum_max_connections),
             disable_upnp: arg_match_unwrap_or::<bool>(&m, "disable-upnp", defaults.disable_upnp),
             disable_dns_seeding: arg_match_unwrap_or::<bool>(&m, "nodnsseed", defaults.disable_dns_seeding),
             disable_grpc: arg_match_unwrap_or::<bool>(&m, "nogrpc", defaults.disable_grpc),
             ram_scale: arg_match_unwrap_or::<f64>(&m, "ram-scale", defaults.ram_scale),
             retention_period_days: m.get_one::<f64>("retention-period-days").cloned().or(defaults.retention_period_days),
+            mempool_policy: defaults.mempool_policy,
 
             #[cfg(feature = "devnet-prealloc")]
             num_prealloc_utxos: m.get_one::<u64>("num-prealloc-utxos").cloned(),
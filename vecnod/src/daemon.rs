@@ -1,4 +1,12 @@
-use std::{fs, path::PathBuf, process::exit, sync::Arc, time::Duration};
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    process::exit,
+    str::FromStr,
+    sync::Arc,
+    time::Duration,
+};
 
 use async_channel::unbounded;
 use vecno_consensus_core::{
@@ -15,16 +23,26 @@ use vecno_database::{
     registry::DatabaseStorePrefixes,
 };
 use vecno_grpc_server::service::GrpcService;
+use vecno_jsonrpc_server::service::{JsonRpcOptions, JsonRpcService};
 use vecno_notify::{address::tracker::Tracker, subscription::context::SubscriptionContext};
-use vecno_p2p_lib::Hub;
+use vecno_p2p_lib::{
+    secure::{IdentityKey, NodeIdentity, SecureTransport},
+    Hub,
+};
 use vecno_p2p_mining::rule_engine::MiningRuleEngine;
+use vecno_rpc_core::api::{
+    auth::{RpcAuthenticator, RpcPermissions, RpcTlsIdentity},
+    rate_limit::{RpcRateLimitCounters, RpcRateLimiter},
+};
 use vecno_rpc_service::service::RpcCoreService;
+use vecno_stratum_server::service::{StratumOptions, StratumService, DEFAULT_STRATUM_PORT};
 use vecno_txscript::caches::TxScriptCacheCounters;
 use vecno_utils::git;
-use vecno_utils::networking::ContextualNetAddress;
+use vecno_utils::networking::{ContextualNetAddress, OnionAddress};
 use vecno_utils::sysinfo::SystemInfo;
 use vecno_utils_tower::counters::TowerConnectionCounters;
 
+use vecno_addresses::{Address, Prefix};
 use vecno_addressmanager::AddressManager;
 use vecno_consensus::{consensus::factory::Factory as ConsensusFactory, pipeline::ProcessingCounters};
 use vecno_consensus::{
@@ -35,15 +53,22 @@ use vecno_core::task::runtime::AsyncRuntime;
 use vecno_index_processor::service::IndexService;
 use vecno_mining::{
     manager::{MiningManager, MiningManagerProxy},
+    mempool::policy::DefaultMempoolPolicy,
     monitor::MiningMonitor,
+    persistence::MempoolPersistence,
     MiningCounters,
 };
 use vecno_p2p_flows::{flow_context::FlowContext, service::P2pService};
 
 use itertools::Itertools;
 use vecno_perf_monitor::{builder::Builder as PerfMonitorBuilder, counters::CountersSnapshot};
+use vecno_addressindex::{api::AddressIndexProxy, AddressIndex};
+use vecno_txindex::{api::TxIndexProxy, TxIndex};
 use vecno_utxoindex::{api::UtxoIndexProxy, UtxoIndex};
-use vecno_wrpc_server::service::{Options as WrpcServerOptions, WebSocketCounters as WrpcServerCounters, WrpcEncoding, WrpcService};
+use vecno_wrpc_server::{
+    gateway::Gateway,
+    service::{Options as WrpcServerOptions, WebSocketCounters as WrpcServerCounters, WrpcEncoding, WrpcService},
+};
 
 /// Desired soft FD limit that needs to be configured
 /// for the vecnod process.
@@ -59,14 +84,18 @@ pub const MINIMUM_DAEMON_SOFT_FD_LIMIT: u64 = 4 * 1024;
 const MINIMUM_RETENTION_PERIOD_DAYS: f64 = 2.0;
 const ONE_GIGABYTE: f64 = 1_000_000_000.0;
 
-use crate::args::Args;
+use crate::{args::Args, miner::Miner};
 
 const DEFAULT_DATA_DIR: &str = "datadir";
 const CONSENSUS_DB: &str = "consensus";
 const UTXOINDEX_DB: &str = "utxoindex";
+const TXINDEX_DB: &str = "txindex";
+const ADDRESSINDEX_DB: &str = "addressindex";
 const META_DB: &str = "meta";
 const META_DB_FILE_LIMIT: i32 = 5;
 const DEFAULT_LOG_DIR: &str = "logs";
+const RPC_COOKIE_FILE: &str = "rpc.cookie";
+const P2P_IDENTITY_FILE: &str = "p2p-identity.key";
 
 fn get_home_dir() -> PathBuf {
     #[cfg(target_os = "windows")]
@@ -110,9 +139,104 @@ pub fn validate_args(args: &Args) -> ConfigResult<()> {
     if args.max_tracked_addresses > Tracker::MAX_ADDRESS_UPPER_BOUND {
         return Err(ConfigError::MaxTrackedAddressesTooHigh(Tracker::MAX_ADDRESS_UPPER_BOUND));
     }
+    if args.mine {
+        mining_address(args)?;
+        if args.mining_threads == 0 {
+            return Err(ConfigError::MiningThreadsZero);
+        }
+    }
+    if args.stratumlisten.is_some() {
+        stratum_pay_address(args)?;
+        if !(args.stratum_difficulty.is_finite() && args.stratum_difficulty > 0.0) {
+            return Err(ConfigError::InvalidStratumDifficulty);
+        }
+    }
+    mempool_policy(args)?;
+    rpc_tls_identity(args)?;
+    if args.rpc_credentials.iter().any(|credential| credential.token.is_empty() || credential.permissions.is_empty()) {
+        return Err(ConfigError::InvalidRpcCredential);
+    }
+    p2p_allowed_peers(args)?;
+    if args.ban_threshold == 0 {
+        return Err(ConfigError::BanThresholdZero);
+    }
+    if args.ban_duration == 0 {
+        return Err(ConfigError::BanDurationZero);
+    }
+    onion_address(args)?;
     Ok(())
 }
 
+/// Parses the address receiving the rewards of the built-in CPU miner and makes sure it belongs to the node network.
+fn mining_address(args: &Args) -> ConfigResult<Address> {
+    let mining_address = args.mining_address.as_deref().ok_or(ConfigError::MissingMiningAddress)?;
+    pay_address(args, mining_address).map_err(ConfigError::InvalidMiningAddress)
+}
+
+/// Parses the address receiving the rewards of the blocks mined through the stratum server.
+fn stratum_pay_address(args: &Args) -> ConfigResult<Address> {
+    let stratum_pay_address = args.stratum_pay_address.as_deref().ok_or(ConfigError::MissingStratumPayAddress)?;
+    pay_address(args, stratum_pay_address).map_err(ConfigError::InvalidStratumPayAddress)
+}
+
+/// Builds the mempool policy out of the `[mempool-policy]` section of the config file.
+fn mempool_policy(args: &Args) -> ConfigResult<DefaultMempoolPolicy> {
+    DefaultMempoolPolicy::new(args.mempool_policy.clone()).map_err(|err| ConfigError::InvalidMempoolPolicy(err.to_string()))
+}
+
+/// Loads the TLS identity of the RPC servers out of the `--rpc-tls-cert` and `--rpc-tls-key` files,
+/// making sure they hold a usable certificate chain and private key.
+fn rpc_tls_identity(args: &Args) -> ConfigResult<Option<RpcTlsIdentity>> {
+    match (args.rpc_tls_cert.as_deref(), args.rpc_tls_key.as_deref()) {
+        (Some(cert_path), Some(key_path)) => {
+            let identity =
+                RpcTlsIdentity::from_files(cert_path, key_path).map_err(|err| ConfigError::InvalidRpcTlsIdentity(err.to_string()))?;
+            Gateway::validate_tls_identity(&identity).map_err(|err| ConfigError::InvalidRpcTlsIdentity(err.to_string()))?;
+            Ok(Some(identity))
+        }
+        (None, None) => Ok(None),
+        _ => Err(ConfigError::MissingRpcTlsCertOrKey),
+    }
+}
+
+/// Parses the identity keys of the only peers allowed to connect over the encrypted P2P transport.
+fn p2p_allowed_peers(args: &Args) -> ConfigResult<Vec<IdentityKey>> {
+    args.p2p_allowed_peers
+        .iter()
+        .map(|key| IdentityKey::from_str(key).map_err(|_| ConfigError::InvalidP2pAllowedPeer(key.clone())))
+        .collect()
+}
+
+/// Parses the onion address advertised by the node, the port defaulting to the P2P port of the network.
+fn onion_address(args: &Args) -> ConfigResult<Option<OnionAddress>> {
+    let Some(address) = args.onion_address.as_deref() else {
+        return Ok(None);
+    };
+    OnionAddress::from_str_with_default_port(address, args.network().default_p2p_port())
+        .map(Some)
+        .map_err(|err| ConfigError::InvalidOnionAddress(address.to_string(), err.to_string()))
+}
+
+/// Writes the RPC authentication cookie, readable by the node user only.
+fn write_rpc_cookie(path: &Path, token: &str) -> std::io::Result<()> {
+    // Remove any previous cookie so that the file is created with restricted permissions
+    let _ = fs::remove_file(path);
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options.open(path)?.write_all(token.as_bytes())
+}
+
+fn pay_address(args: &Args, address: &str) -> Result<Address, String> {
+    let address = Address::try_from(address).map_err(|err| err.to_string())?;
+    let network = args.network();
+    if address.prefix != Prefix::from(network) {
+        return Err(format!("address {address} does not belong to network {network}"));
+    }
+    Ok(address)
+}
+
 fn get_user_approval_or_exit(message: &str, approve: bool) {
     if approve {
         return;
@@ -229,6 +353,20 @@ pub fn create_core_with_runtime(runtime: &Runtime, args: &Args, fd_total_budget:
     } else {
         0
     };
+    let tx_files_limit = if args.txindex {
+        let tx_files_limit = fd_remaining / 10;
+        fd_remaining -= tx_files_limit;
+        tx_files_limit
+    } else {
+        0
+    };
+    let address_files_limit = if args.addressindex {
+        let address_files_limit = fd_remaining / 10;
+        fd_remaining -= address_files_limit;
+        address_files_limit
+    } else {
+        0
+    };
     // Make sure args forms a valid set of properties
     if let Err(err) = validate_args(args) {
         println!("{}", err);
@@ -262,6 +400,8 @@ pub fn create_core_with_runtime(runtime: &Runtime, args: &Args, fd_total_budget:
 
     let consensus_db_dir = db_dir.join(CONSENSUS_DB);
     let utxoindex_db_dir = db_dir.join(UTXOINDEX_DB);
+    let txindex_db_dir = db_dir.join(TXINDEX_DB);
+    let addressindex_db_dir = db_dir.join(ADDRESSINDEX_DB);
     let meta_db_dir = db_dir.join(META_DB);
 
     let mut is_db_reset_needed = args.reset_db;
@@ -281,6 +421,28 @@ do you confirm? (answer y/n or pass --yes to the Vecnod command line to confirm
         info!("Utxoindex Data directory {}", utxoindex_db_dir.display());
         fs::create_dir_all(utxoindex_db_dir.as_path()).unwrap();
     }
+    if args.txindex {
+        info!("Txindex Data directory {}", txindex_db_dir.display());
+        fs::create_dir_all(txindex_db_dir.as_path()).unwrap();
+    }
+    if args.addressindex {
+        info!("Addressindex Data directory {}", addressindex_db_dir.display());
+        fs::create_dir_all(addressindex_db_dir.as_path()).unwrap();
+    }
+
+    let mut rpc_authenticator = RpcAuthenticator::new();
+    for credential in args.rpc_credentials.iter() {
+        rpc_authenticator.register(credential.token.clone(), credential.permissions.iter().copied().collect());
+    }
+    if args.rpc_auth_cookie {
+        let cookie_path = app_dir.join(network.to_prefixed()).join(RPC_COOKIE_FILE);
+        let token = RpcAuthenticator::generate_token();
+        write_rpc_cookie(&cookie_path, &token).unwrap();
+        info!("RPC authentication cookie written to {}", cookie_path.display());
+        rpc_authenticator.register(token, RpcPermissions::all());
+    }
+    let rpc_authenticator = Arc::new(rpc_authenticator);
+    let rpc_tls_identity = rpc_tls_identity(args).unwrap();
 
     if !args.archival && args.retention_period_days.is_some() {
         let retention_period_days = args.retention_period_days.unwrap();
@@ -469,6 +631,14 @@ do you confirm? (answer y/n or pass --yes to the Vecnod command line to confirm
             fs::create_dir_all(utxoindex_db_dir.as_path()).unwrap();
         }
 
+        if args.txindex {
+            fs::create_dir_all(txindex_db_dir.as_path()).unwrap();
+        }
+
+        if args.addressindex {
+            fs::create_dir_all(addressindex_db_dir.as_path()).unwrap();
+        }
+
         // Reopen the DB
         meta_db = vecno_database::prelude::ConnBuilder::default()
             .with_db_path(meta_db_dir)
@@ -487,9 +657,16 @@ do you confirm? (answer y/n or pass --yes to the Vecnod command line to confirm
     // connect_peers means no DNS seeding and no outbound/inbound peers
     let outbound_target = if connect_peers.is_empty() { args.outbound_target } else { 0 };
     let inbound_limit = if connect_peers.is_empty() { args.inbound_limit } else { 0 };
-    let peers = if connect_peers.is_empty() && !args.disable_dns_seeding { config.peers } else { &[] };
+    // DNS seeding resolves the seeders locally, which would bypass the proxy, so it is disabled when one is used
+    let dns_seeding = connect_peers.is_empty() && !args.disable_dns_seeding;
+    if dns_seeding && config.proxy.is_some() {
+        info!("DNS seeding is disabled since outbound connections go through a proxy");
+    }
+    let peers = if dns_seeding && config.proxy.is_none() { config.peers } else { &[] };
 
     let grpc_server_addr = args.rpclisten.unwrap_or(ContextualNetAddress::loopback()).normalize(config.default_rpc_port());
+    let stratum_server_addr = args.stratumlisten.map(|address| address.normalize(DEFAULT_STRATUM_PORT));
+    let jsonrpc_server_addr = args.rpclisten_http.map(|address| address.normalize(network.network_type.default_http_rpc_port()));
 
     let core = Arc::new(Core::new());
 
@@ -507,6 +684,8 @@ do you confirm? (answer y/n or pass --yes to the Vecnod command line to confirm
     let tx_script_cache_counters = Arc::new(TxScriptCacheCounters::default());
     let p2p_tower_counters = Arc::new(TowerConnectionCounters::default());
     let grpc_tower_counters = Arc::new(TowerConnectionCounters::default());
+    let rpc_rate_limit_counters = Arc::new(RpcRateLimitCounters::default());
+    let rpc_rate_limiter = Arc::new(RpcRateLimiter::new(args.rpc_rate_limits.clone(), rpc_rate_limit_counters.clone()));
 
     // Use `num_cpus` background threads for the consensus database as recommended by rocksdb
     let mining_rules = Arc::new(MiningRules::default());
@@ -543,21 +722,40 @@ do you confirm? (answer y/n or pass --yes to the Vecnod command line to confirm
     let system_info = SystemInfo::default();
 
     let notify_service = Arc::new(NotifyService::new(notification_root.clone(), notification_recv, subscription_context.clone()));
-    let index_service: Option<Arc<IndexService>> = if args.utxoindex {
+    let index_service: Option<Arc<IndexService>> = if args.utxoindex || args.txindex || args.addressindex {
         // Use only a single thread for none-consensus databases
-        let utxoindex_db = vecno_database::prelude::ConnBuilder::default()
-            .with_db_path(utxoindex_db_dir)
-            .with_files_limit(utxo_files_limit)
-            .build()
-            .unwrap();
-        let utxoindex = UtxoIndexProxy::new(UtxoIndex::new(consensus_manager.clone(), utxoindex_db).unwrap());
-        let index_service = Arc::new(IndexService::new(&notify_service.notifier(), subscription_context.clone(), Some(utxoindex)));
+        let utxoindex = args.utxoindex.then(|| {
+            let utxoindex_db = vecno_database::prelude::ConnBuilder::default()
+                .with_db_path(utxoindex_db_dir)
+                .with_files_limit(utxo_files_limit)
+                .build()
+                .unwrap();
+            UtxoIndexProxy::new(UtxoIndex::new(consensus_manager.clone(), utxoindex_db).unwrap())
+        });
+        let txindex = args.txindex.then(|| {
+            let txindex_db = vecno_database::prelude::ConnBuilder::default()
+                .with_db_path(txindex_db_dir)
+                .with_files_limit(tx_files_limit)
+                .build()
+                .unwrap();
+            TxIndexProxy::new(TxIndex::new(consensus_manager.clone(), txindex_db).unwrap())
+        });
+        let addressindex = args.addressindex.then(|| {
+            let addressindex_db = vecno_database::prelude::ConnBuilder::default()
+                .with_db_path(addressindex_db_dir)
+                .with_files_limit(address_files_limit)
+                .build()
+                .unwrap();
+            AddressIndexProxy::new(AddressIndex::new(consensus_manager.clone(), addressindex_db).unwrap())
+        });
+        let index_service =
+            Arc::new(IndexService::new(&notify_service.notifier(), subscription_context.clone(), utxoindex, txindex, addressindex));
         Some(index_service)
     } else {
         None
     };
 
-    let (address_manager, port_mapping_extender_svc) = AddressManager::new(config.clone(), meta_db, tick_service.clone());
+    let (address_manager, port_mapping_extender_svc) = AddressManager::new(config.clone(), meta_db.clone(), tick_service.clone());
 
     let mining_manager = MiningManagerProxy::new(Arc::new(MiningManager::new_with_extended_config(
         config.target_time_per_block(),
@@ -565,6 +763,7 @@ do you confirm? (answer y/n or pass --yes to the Vecnod command line to confirm
         config.max_block_mass,
         config.ram_scale,
         config.block_template_cache_lifetime,
+        Arc::new(mempool_policy(args).unwrap()),
         mining_counters.clone(),
     )));
     let mining_monitor = Arc::new(MiningMonitor::new(
@@ -574,6 +773,9 @@ do you confirm? (answer y/n or pass --yes to the Vecnod command line to confirm
         tx_script_cache_counters.clone(),
         tick_service.clone(),
     ));
+    let mempool_persistence = args
+        .persist_mempool
+        .then(|| Arc::new(MempoolPersistence::new(mining_manager.clone(), consensus_manager.clone(), meta_db, tick_service.clone())));
 
     let hub = Hub::new();
     let mining_rule_engine = Arc::new(MiningRuleEngine::new(
@@ -584,6 +786,18 @@ do you confirm? (answer y/n or pass --yes to the Vecnod command line to confirm
         hub.clone(),
         mining_rules,
     ));
+    let p2p_secure_transport = (args.p2p_encryption || !args.p2p_allowed_peers.is_empty()).then(|| {
+        let identity_path = app_dir.join(network.to_prefixed()).join(P2P_IDENTITY_FILE);
+        let identity = match NodeIdentity::load_or_create(&identity_path) {
+            Ok(identity) => identity,
+            Err(err) => {
+                println!("{}", ConfigError::InvalidP2pIdentity(identity_path.display().to_string(), err.to_string()));
+                exit(1);
+            }
+        };
+        info!("P2P encrypted transport enabled, node identity key: {}", identity.public_key());
+        Arc::new(SecureTransport::new(identity, p2p_allowed_peers(args).unwrap()))
+    });
     let flow_context = Arc::new(FlowContext::new(
         consensus_manager.clone(),
         address_manager,
@@ -593,6 +807,7 @@ do you confirm? (answer y/n or pass --yes to the Vecnod command line to confirm
         notification_root,
         hub.clone(),
         mining_rule_engine.clone(),
+        p2p_secure_transport,
     ));
     let p2p_service = Arc::new(P2pService::new(
         flow_context.clone(),
@@ -606,14 +821,30 @@ do you confirm? (answer y/n or pass --yes to the Vecnod command line to confirm
         p2p_tower_counters.clone(),
     ));
 
+    let miner = args.mine.then(|| {
+        Arc::new(Miner::new(
+            consensus_manager.clone(),
+            mining_manager.clone(),
+            flow_context.clone(),
+            mining_rule_engine.clone(),
+            config.clone(),
+            tick_service.clone(),
+            &mining_address(args).unwrap(),
+            args.mining_threads,
+        ))
+    });
+
     let rpc_core_service = Arc::new(RpcCoreService::new(
         consensus_manager.clone(),
         notify_service.notifier(),
-        index_service.as_ref().map(|x| x.notifier()),
+        // UTXO related notifications are only relayed by the index service when the UTXO index is enabled
+        index_service.as_ref().filter(|x| x.utxoindex().is_some()).map(|x| x.notifier()),
         mining_manager,
         flow_context,
         subscription_context,
-        index_service.as_ref().map(|x| x.utxoindex().unwrap()),
+        index_service.as_ref().and_then(|x| x.utxoindex()),
+        index_service.as_ref().and_then(|x| x.txindex()),
+        index_service.as_ref().and_then(|x| x.addressindex()),
         config.clone(),
         core.clone(),
         processing_counters,
@@ -622,9 +853,38 @@ do you confirm? (answer y/n or pass --yes to the Vecnod command line to confirm
         perf_monitor.clone(),
         p2p_tower_counters.clone(),
         grpc_tower_counters.clone(),
+        rpc_rate_limit_counters,
         system_info,
         mining_rule_engine.clone(),
     ));
+    let stratum_service = stratum_server_addr.map(|stratum_server_addr| {
+        Arc::new(StratumService::new(
+            stratum_server_addr,
+            config.clone(),
+            rpc_core_service.clone(),
+            StratumOptions {
+                pay_address: stratum_pay_address(args).unwrap(),
+                difficulty: args.stratum_difficulty,
+                shares_per_minute: args.stratum_shares_per_minute,
+                max_connections: args.stratum_max_connections,
+            },
+        ))
+    });
+    let jsonrpc_service = jsonrpc_server_addr.map(|jsonrpc_server_addr| {
+        Arc::new(
+            JsonRpcService::new(
+                jsonrpc_server_addr,
+                rpc_core_service.clone(),
+                JsonRpcOptions {
+                    authenticator: rpc_authenticator.clone(),
+                    tls: rpc_tls_identity.clone(),
+                    rate_limiter: rpc_rate_limiter.clone(),
+                    max_clients: args.rpc_max_clients,
+                },
+            )
+            .unwrap(),
+        )
+    });
     let grpc_service_broadcasters: usize = 3; // TODO: add a command line argument or derive from other arg/config/host-related fields
     let grpc_service = if !args.disable_grpc {
         Some(Arc::new(GrpcService::new(
@@ -634,6 +894,9 @@ do you confirm? (answer y/n or pass --yes to the Vecnod command line to confirm
             args.rpc_max_clients,
             grpc_service_broadcasters,
             grpc_tower_counters,
+            rpc_authenticator.clone(),
+            rpc_tls_identity.clone(),
+            rpc_rate_limiter.clone(),
         )))
     } else {
         None
@@ -653,11 +916,23 @@ do you confirm? (answer y/n or pass --yes to the Vecnod command line to confirm
     if let Some(grpc_service) = grpc_service {
         async_runtime.register(grpc_service)
     }
+    if let Some(stratum_service) = stratum_service {
+        async_runtime.register(stratum_service)
+    }
+    if let Some(jsonrpc_service) = jsonrpc_service {
+        async_runtime.register(jsonrpc_service)
+    }
     async_runtime.register(p2p_service);
     async_runtime.register(consensus_monitor);
     async_runtime.register(mining_monitor);
+    if let Some(mempool_persistence) = mempool_persistence {
+        async_runtime.register(mempool_persistence)
+    };
     async_runtime.register(perf_monitor);
     async_runtime.register(mining_rule_engine);
+    if let Some(miner) = miner {
+        async_runtime.register(miner)
+    };
 
     let wrpc_service_tasks: usize = 2; // num_cpus::get() / 2;
                                        // Register wRPC servers based on command line arguments
@@ -668,7 +943,7 @@ do you confirm? (answer y/n or pass --yes to the Vecnod command line to confirm
     .into_iter()
     .filter_map(|(listen_address, encoding, wrpc_server_counters)| {
         listen_address.map(|listen_address| {
-            Arc::new(WrpcService::new(
+            let service = WrpcService::new(
                 wrpc_service_tasks,
                 Some(rpc_core_service.clone()),
                 &encoding,
@@ -676,9 +951,20 @@ do you confirm? (answer y/n or pass --yes to the Vecnod command line to confirm
                 WrpcServerOptions {
                     listen_address: listen_address.to_address(&network.network_type, &encoding).to_string(), // TODO: use a normalized ContextualNetAddress instead of a String
                     verbose: args.wrpc_verbose,
+                    authenticator: rpc_authenticator.clone(),
+                    tls: rpc_tls_identity.clone(),
+                    rate_limiter: rpc_rate_limiter.clone(),
+                    max_clients: args.rpc_max_clients,
                     ..WrpcServerOptions::default()
                 },
-            ))
+            );
+            match service {
+                Ok(service) => Arc::new(service),
+                Err(err) => {
+                    println!("{}", err);
+                    exit(1);
+                }
+            }
         })
     })
     .for_each(|server| async_runtime.register(server));
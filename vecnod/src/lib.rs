@@ -1,2 +1,6 @@
 pub mod args;
 pub mod daemon;
+pub mod miner;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,257 @@
+//! An in-process CPU miner, mainly meant for devnets, simnets and local testing.
+//!
+//! A single async task keeps a block template up to date through the mining manager and publishes it as a [`Job`]
+//! to a pool of worker threads, which search nonces with [`vecno_pow::State`]. Blocks found by the workers are
+//! submitted back through the flow context, exactly like blocks submitted via RPC.
+
+use std::{
+    iter::once,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+use async_channel::{unbounded, Receiver, Sender};
+use parking_lot::{Mutex, RwLock};
+use vecno_addresses::Address;
+use vecno_consensus_core::{
+    block::{Block, BlockTemplate},
+    coinbase::MinerData,
+    config::Config,
+};
+use vecno_consensusmanager::ConsensusManager;
+use vecno_core::{
+    debug, info,
+    task::{
+        service::{AsyncService, AsyncServiceFuture},
+        tick::{TickReason, TickService},
+    },
+    trace, vecnod_env::version, warn,
+};
+use vecno_mining::manager::MiningManagerProxy;
+use vecno_p2p_flows::flow_context::FlowContext;
+use vecno_p2p_mining::rule_engine::MiningRuleEngine;
+
+const SERVICE_NAME: &str = "cpu-miner";
+
+/// Interval at which the block template is refreshed, so that new transactions and tips get mined
+const TEMPLATE_REFRESH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Interval at which the hashrate is reported
+const HASHRATE_REPORT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Number of nonces hashed by a worker before it checks for a new job
+const NONCES_PER_ROUND: u64 = 1024;
+
+/// Duration after which a worker resumes mining a job it already found a block for. The job normally gets replaced
+/// by a new template before that, unless the found block was rejected.
+const EXHAUSTED_JOB_RETRY_INTERVAL: Duration = Duration::from_secs(2);
+
+/// A block template along with its PoW state, shared by all the workers
+struct Job {
+    id: u64,
+    template: BlockTemplate,
+    state: vecno_pow::State,
+}
+
+pub struct Miner {
+    consensus_manager: Arc<ConsensusManager>,
+    mining_manager: MiningManagerProxy,
+    flow_context: Arc<FlowContext>,
+    mining_rule_engine: Arc<MiningRuleEngine>,
+    config: Arc<Config>,
+    tick_service: Arc<TickService>,
+    miner_data: MinerData,
+    threads: usize,
+
+    /// The job currently mined by the workers, if any
+    job: Arc<RwLock<Option<Arc<Job>>>>,
+    hashes: Arc<AtomicU64>,
+    shutdown: Arc<AtomicBool>,
+    workers: Mutex<Vec<JoinHandle<()>>>,
+}
+
+impl Miner {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        consensus_manager: Arc<ConsensusManager>,
+        mining_manager: MiningManagerProxy,
+        flow_context: Arc<FlowContext>,
+        mining_rule_engine: Arc<MiningRuleEngine>,
+        config: Arc<Config>,
+        tick_service: Arc<TickService>,
+        pay_address: &Address,
+        threads: usize,
+    ) -> Self {
+        let script_public_key = vecno_txscript::pay_to_address_script(pay_address);
+        let extra_data = version().as_bytes().iter().chain(once(&(b'/'))).chain(SERVICE_NAME.as_bytes()).cloned().collect::<Vec<_>>();
+        Self {
+            consensus_manager,
+            mining_manager,
+            flow_context,
+            mining_rule_engine,
+            config,
+            tick_service,
+            miner_data: MinerData::new(script_public_key, extra_data),
+            threads,
+            job: Default::default(),
+            hashes: Default::default(),
+            shutdown: Default::default(),
+            workers: Default::default(),
+        }
+    }
+
+    fn spawn_workers(&self, found_sender: Sender<Block>) {
+        let mut workers = self.workers.lock();
+        for index in 0..self.threads {
+            let job = self.job.clone();
+            let hashes = self.hashes.clone();
+            let shutdown = self.shutdown.clone();
+            let found_sender = found_sender.clone();
+            let worker = std::thread::Builder::new()
+                .name(format!("{SERVICE_NAME}-{index}"))
+                .spawn(move || Self::worker(job, hashes, shutdown, found_sender))
+                .expect("failed spawning a miner thread");
+            workers.push(worker);
+        }
+    }
+
+    fn worker(job: Arc<RwLock<Option<Arc<Job>>>>, hashes: Arc<AtomicU64>, shutdown: Arc<AtomicBool>, found_sender: Sender<Block>) {
+        let mut current_job_id = None;
+        let mut nonce = 0u64;
+        let mut exhausted_job: Option<(u64, Instant)> = None;
+        while !shutdown.load(Ordering::Relaxed) {
+            let latest = job.read().clone();
+            let is_exhausted =
+                |job: &Job| exhausted_job.is_some_and(|(id, time)| id == job.id && time.elapsed() < EXHAUSTED_JOB_RETRY_INTERVAL);
+            let Some(latest) = latest.filter(|job| !is_exhausted(job)) else {
+                std::thread::sleep(Duration::from_millis(10));
+                continue;
+            };
+            if current_job_id != Some(latest.id) {
+                // Workers start at random offsets so they explore distinct nonce ranges
+                nonce = rand::random();
+                current_job_id = Some(latest.id);
+            }
+
+            let nonces = (0..NONCES_PER_ROUND).map(|i| nonce.wrapping_add(i)).collect::<Vec<_>>();
+            nonce = nonce.wrapping_add(NONCES_PER_ROUND);
+            let found = latest.state.check_pow_batch(&nonces);
+            hashes.fetch_add(NONCES_PER_ROUND, Ordering::Relaxed);
+
+            if let Some((found_nonce, _)) = found {
+                let mut block = latest.template.block.clone();
+                block.header.nonce = found_nonce;
+                block.header.finalize();
+                // The job is stale once a block is found, so wait for the next template
+                exhausted_job = Some((latest.id, Instant::now()));
+                if found_sender.send_blocking(block.to_immutable()).is_err() {
+                    break;
+                }
+            }
+        }
+        trace!("{} worker exiting", SERVICE_NAME);
+    }
+
+    /// Fetches a block template and publishes it as a new job if it differs from the current one
+    async fn update_job(&self, next_job_id: &mut u64) {
+        let session = self.consensus_manager.consensus().unguarded_session();
+        let sink_daa_score_timestamp = session.async_get_sink_daa_score_timestamp().await;
+        if !self.config.enable_unsynced_mining && !self.mining_rule_engine.should_mine(sink_daa_score_timestamp) {
+            if self.job.write().take().is_some() {
+                info!("The node is not synced, the CPU miner is pausing");
+            }
+            return;
+        }
+
+        let template = match self.mining_manager.clone().get_block_template(&session, self.miner_data.clone()).await {
+            Ok(template) => template,
+            Err(err) => {
+                warn!("The CPU miner failed getting a block template: {}", err);
+                return;
+            }
+        };
+        if self.job.read().as_ref().is_some_and(|job| job.template.block.header.hash == template.block.header.hash) {
+            return;
+        }
+        let state = vecno_pow::State::new(&template.block.header);
+        *self.job.write() = Some(Arc::new(Job { id: *next_job_id, template, state }));
+        *next_job_id += 1;
+    }
+
+    async fn submit_block(&self, block: Block) {
+        let hash = block.hash();
+        let session = self.consensus_manager.consensus().unguarded_session();
+        match self.flow_context.submit_rpc_block(&session, block).await {
+            Ok(_) => info!("Mined block {}", hash),
+            Err(err) => warn!("The block {} mined in-process was rejected: {}", hash, err),
+        }
+    }
+
+    async fn worker_manager(self: &Arc<Self>, found_receiver: Receiver<Block>) {
+        let mut next_job_id = 0;
+        let mut last_report = (Instant::now(), 0);
+        loop {
+            self.update_job(&mut next_job_id).await;
+
+            tokio::select! {
+                reason = self.tick_service.tick(TEMPLATE_REFRESH_INTERVAL) => {
+                    if let TickReason::Shutdown = reason {
+                        break;
+                    }
+                }
+                block = found_receiver.recv() => {
+                    match block {
+                        Ok(block) => self.submit_block(block).await,
+                        Err(_) => break,
+                    }
+                }
+            }
+
+            let elapsed = last_report.0.elapsed();
+            if elapsed >= HASHRATE_REPORT_INTERVAL {
+                let hashes = self.hashes.load(Ordering::Relaxed);
+                let hashrate = (hashes - last_report.1) as f64 / elapsed.as_secs_f64();
+                debug!("CPU miner hashrate: {:.2} kH/s over {} threads", hashrate / 1_000.0, self.threads);
+                last_report = (Instant::now(), hashes);
+            }
+        }
+    }
+}
+
+impl AsyncService for Miner {
+    fn ident(self: Arc<Self>) -> &'static str {
+        SERVICE_NAME
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        trace!("{} starting", SERVICE_NAME);
+        let (found_sender, found_receiver) = unbounded();
+        self.spawn_workers(found_sender);
+        info!("CPU miner started with {} threads", self.threads);
+        Box::pin(async move {
+            self.worker_manager(found_receiver).await;
+            Ok(())
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", SERVICE_NAME);
+        self.shutdown.store(true, Ordering::Relaxed);
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            self.shutdown.store(true, Ordering::Relaxed);
+            let workers = std::mem::take(&mut *self.workers.lock());
+            for worker in workers {
+                let _ = worker.join();
+            }
+            trace!("{} stopped", SERVICE_NAME);
+            Ok(())
+        })
+    }
+}
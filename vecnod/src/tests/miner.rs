@@ -0,0 +1,23 @@
+use super::{eventually, pay_address, TestNode};
+use crate::args::Args;
+use vecno_rpc_core::api::rpc::RpcApi;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_cpu_miner() {
+    let args = Args { mine: true, mining_address: Some(pay_address().to_string()), mining_threads: 2, ..Default::default() };
+    let node = TestNode::start(args);
+    let rpc = node.rpc();
+
+    // Mining several blocks in a row requires the workers to move on to a new template after each found block
+    eventually(|| async {
+        let response = rpc.get_block_count().await.unwrap();
+        if response.block_count >= 3 {
+            Ok(())
+        } else {
+            Err(format!("only {} blocks were mined", response.block_count))
+        }
+    })
+    .await;
+
+    node.shutdown();
+}
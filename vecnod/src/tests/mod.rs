@@ -0,0 +1,101 @@
+//!
+//! End-to-end tests running in-process simnet nodes.
+//!
+
+use crate::{
+    args::Args,
+    daemon::{create_core_with_runtime, Runtime},
+};
+use vecno_addresses::{Address, Prefix, Version};
+use vecno_consensus_core::block::Block;
+use vecno_core::{core::Core, signals::Shutdown};
+use vecno_rpc_core::api::rpc::RpcApi;
+use vecno_rpc_service::service::RpcCoreService;
+use std::{future::Future, net::TcpListener, sync::Arc, thread::JoinHandle, time::Duration};
+use tempfile::TempDir;
+
+mod miner;
+mod rpc;
+
+/// File descriptors budget of a test node
+const FD_TOTAL_BUDGET: i32 = 128;
+
+/// A simnet node running in-process on a temporary application directory
+pub(crate) struct TestNode {
+    core: Arc<Core>,
+    rpc_core_service: Option<Arc<RpcCoreService>>,
+    workers: Vec<JoinHandle<()>>,
+    _appdir: TempDir,
+}
+
+impl TestNode {
+    /// Starts a simnet node with the given args, listening to a free P2P port and without any RPC server
+    pub fn start(args: Args) -> Self {
+        let appdir = tempfile::tempdir().unwrap();
+        let p2p_port = free_port();
+        let args = Args {
+            simnet: true,
+            appdir: Some(appdir.path().to_str().unwrap().to_owned()),
+            no_log_files: true,
+            listen: Some(format!("127.0.0.1:{p2p_port}").parse().unwrap()),
+            disable_upnp: true,
+            disable_dns_seeding: true,
+            disable_grpc: true,
+            enable_unsynced_mining: true,
+            ..args
+        };
+        let (core, rpc_core_service) = create_core_with_runtime(&Runtime::default(), &args, FD_TOTAL_BUDGET);
+        let workers = core.start();
+        Self { core, rpc_core_service: Some(rpc_core_service), workers, _appdir: appdir }
+    }
+
+    pub fn rpc(&self) -> &Arc<RpcCoreService> {
+        self.rpc_core_service.as_ref().unwrap()
+    }
+
+    pub fn shutdown(mut self) {
+        // The RPC service must be released before the core is shut down
+        self.rpc_core_service.take();
+        self.core.shutdown();
+        self.core.join(std::mem::take(&mut self.workers));
+    }
+}
+
+/// Returns a local port free at the time of the call
+pub(crate) fn free_port() -> u16 {
+    TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port()
+}
+
+pub(crate) fn pay_address() -> Address {
+    Address::new(Prefix::Simnet, Version::PubKey, &[1u8; 32])
+}
+
+/// Mines a block through the RPC service and returns it. Proof of work is skipped by the simnet.
+pub(crate) async fn mine_block(rpc: &RpcCoreService, pay_address: &Address) -> Block {
+    let template = rpc.get_block_template(pay_address.clone(), vec![]).await.unwrap();
+    let block = Block::try_from(template.block.clone()).unwrap();
+    let response = rpc.submit_block(template.block, false).await.unwrap();
+    assert!(response.report.is_success(), "block {} was rejected: {:?}", block.hash(), response.report);
+    block
+}
+
+pub(crate) async fn mine_blocks(rpc: &RpcCoreService, pay_address: &Address, count: usize) -> Vec<Block> {
+    let mut blocks = Vec::with_capacity(count);
+    for _ in 0..count {
+        blocks.push(mine_block(rpc, pay_address).await);
+    }
+    blocks
+}
+
+/// Polls `f` until it succeeds, since the indexes are updated asynchronously, and panics after 10 seconds
+pub(crate) async fn eventually<T, E: std::fmt::Debug, F: Future<Output = Result<T, E>>>(mut f: impl FnMut() -> F) -> T {
+    let mut attempts = 100;
+    loop {
+        match f().await {
+            Ok(value) => return value,
+            Err(err) if attempts == 0 => panic!("condition not met in time: {err:?}"),
+            Err(_) => attempts -= 1,
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+}
@@ -0,0 +1,227 @@
+use super::{eventually, free_port, mine_block, mine_blocks, pay_address, TestNode};
+use crate::args::Args;
+use futures_util::StreamExt;
+use vecno_addresses::Prefix;
+use vecno_consensus_core::{config::params::SIMNET_PARAMS, tx::TransactionId};
+use vecno_hashes::Hash;
+use vecno_jsonrpc_server::http::MAX_BODY_SIZE;
+use vecno_notify::{
+    error::Error as NotifyError,
+    replay::Replayer,
+    scope::{ResumeToken, Scope, UtxosChangedScope, VirtualChainChangedScope},
+};
+use vecno_rpc_core::{
+    api::{
+        auth::{RpcCredential, RpcPermission},
+        rpc::RpcApi,
+    },
+    Notification, RpcAddressHistoryEntry, RpcError, RpcUtxoEntry,
+};
+use vecno_txscript::{
+    extract_script_pub_key_address,
+    standard::test_helpers::{create_transaction, op_true_script},
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_get_transaction() {
+    let node = TestNode::start(Args { txindex: true, ..Default::default() });
+    let rpc = node.rpc();
+    let blocks = mine_blocks(rpc, &pay_address(), 3).await;
+
+    // The coinbase transaction of a chain block is accepted by the next chain block
+    let coinbase_id = blocks[0].transactions[0].id();
+    let response = eventually(|| rpc.get_transaction(coinbase_id)).await;
+    assert_eq!(response.block_hash, blocks[0].hash());
+    assert_eq!(response.accepting_block_hash, blocks[1].hash());
+    assert_eq!(response.accepting_block_daa_score, blocks[1].header.daa_score);
+    assert_eq!(response.transaction.verbose_data.map(|x| x.transaction_id), Some(coinbase_id));
+
+    // The coinbase transaction of the sink is not accepted yet
+    let sink_coinbase_id = blocks[2].transactions[0].id();
+    assert!(matches!(rpc.get_transaction(sink_coinbase_id).await, Err(RpcError::TransactionNotFound(id)) if id == sink_coinbase_id));
+
+    let unknown_id = TransactionId::from_u64_word(1);
+    assert!(matches!(rpc.get_transaction(unknown_id).await, Err(RpcError::TransactionNotFound(_))));
+
+    node.shutdown();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_get_address_history() {
+    let node = TestNode::start(Args { addressindex: true, ..Default::default() });
+    let rpc = node.rpc();
+    let blocks = mine_blocks(rpc, &pay_address(), 6).await;
+
+    // Wait for the coinbase outputs accepted by the sink to be indexed
+    let sink = blocks[5].hash();
+    let history = eventually(|| async {
+        let history = rpc.get_address_history(pay_address(), None, 1000).await.unwrap();
+        match history.entries.last() {
+            Some(entry) if entry.accepting_block_hash == sink => Ok(history),
+            _ => Err("the sink is not indexed yet"),
+        }
+    })
+    .await;
+    assert!(history.entries.len() >= 2);
+    assert!(history.next_cursor.is_none());
+
+    // Paging through the history yields the same entries
+    let (mut paged, mut cursor) = (vec![], None);
+    loop {
+        let page = rpc.get_address_history(pay_address(), cursor, 2).await.unwrap();
+        assert!(page.entries.len() <= 2);
+        paged.extend(page.entries);
+        cursor = page.next_cursor;
+        if cursor.is_none() {
+            break;
+        }
+    }
+    let key = |entry: &RpcAddressHistoryEntry| (entry.transaction_id, entry.outpoint.index, entry.kind, entry.amount);
+    assert_eq!(paged.iter().map(key).collect::<Vec<_>>(), history.entries.iter().map(key).collect::<Vec<_>>());
+
+    assert!(matches!(
+        rpc.get_address_history(pay_address(), Some("00".to_string()), 2).await,
+        Err(RpcError::InvalidAddressHistoryCursor(_))
+    ));
+
+    node.shutdown();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_get_virtual_chain_chunk() {
+    let node = TestNode::start(Args::default());
+    let rpc = node.rpc();
+    let (script_public_key, _) = op_true_script();
+    let address = extract_script_pub_key_address(&script_public_key, Prefix::Simnet).unwrap();
+
+    // Mine until the coinbase output of blocks[1], paying the miner of blocks[0] and accepted by blocks[2], is mature
+    let maturity = SIMNET_PARAMS.coinbase_maturity().upper_bound() as usize;
+    let mut blocks = mine_blocks(rpc, &address, maturity + 5).await;
+    let coinbase = blocks[1].transactions[0].clone();
+    assert_eq!(coinbase.outputs[0].script_public_key, script_public_key);
+    let transaction = create_transaction(&coinbase, 100_000);
+    rpc.submit_transaction((&transaction).into(), false).await.unwrap();
+
+    // Mine until a chain block includes the transaction, then the one accepting it
+    for _ in 0..10 {
+        blocks.push(mine_block(rpc, &address).await);
+        if blocks.last().unwrap().transactions.iter().any(|tx| tx.id() == transaction.id()) {
+            break;
+        }
+    }
+    assert!(blocks.last().unwrap().transactions.iter().any(|tx| tx.id() == transaction.id()), "the transaction was not mined");
+    blocks.push(mine_block(rpc, &address).await);
+
+    // Paging from blocks[0] yields the rest of the chain, in full chunks but the last one
+    let (mut chunks, mut start_hash) = (vec![], blocks[0].hash());
+    loop {
+        let chunk = rpc.get_virtual_chain_chunk(start_hash, 10).await.unwrap();
+        assert!(chunk.removed_chain_block_hashes.is_empty());
+        assert!(!chunk.added_chain_blocks.is_empty() && chunk.added_chain_blocks.len() <= 10);
+        start_hash = chunk.added_chain_blocks.last().unwrap().hash;
+        let is_tip = chunk.is_tip;
+        chunks.push(chunk);
+        if is_tip {
+            break;
+        }
+    }
+    assert_eq!(chunks.len(), (blocks.len() - 1).div_ceil(10));
+    let added = chunks.into_iter().flat_map(|chunk| chunk.added_chain_blocks).collect::<Vec<_>>();
+    assert_eq!(added.iter().map(|x| x.hash).collect::<Vec<_>>(), blocks[1..].iter().map(|x| x.hash()).collect::<Vec<_>>());
+    assert!(added.iter().zip(blocks[1..].iter()).all(|(x, block)| x.daa_score == block.header.daa_score));
+
+    // Every chain block accepts the coinbase transaction of its selected parent, which spends no UTXO entry
+    for (chain_block, selected_parent) in added.iter().zip(blocks.iter()) {
+        let coinbase_id = selected_parent.transactions[0].id();
+        let accepted = chain_block.accepted_transactions.iter().find(|x| x.transaction_id == coinbase_id).unwrap();
+        assert!(accepted.input_utxo_entries.is_empty());
+    }
+
+    // The last chain block accepts the transaction along with the UTXO entry it spends
+    let accepted = added.last().unwrap().accepted_transactions.iter().find(|x| x.transaction_id == transaction.id()).unwrap();
+    let spent_entry = RpcUtxoEntry::new(coinbase.outputs[0].value, script_public_key, blocks[2].header.daa_score, true);
+    assert_eq!(accepted.input_utxo_entries, vec![spent_entry]);
+
+    // Streaming the chain yields the same chunks
+    let streamed = rpc.stream_virtual_chain_from_block(blocks[0].hash(), 10).collect::<Vec<_>>().await;
+    let streamed = streamed.into_iter().flat_map(|chunk| chunk.unwrap().added_chain_blocks).map(|x| x.hash).collect::<Vec<_>>();
+    assert_eq!(streamed, added.iter().map(|x| x.hash).collect::<Vec<_>>());
+
+    node.shutdown();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_jsonrpc_authentication() {
+    let address = format!("127.0.0.1:{}", free_port());
+    let credential = RpcCredential { token: "secret".to_owned(), permissions: vec![RpcPermission::Read] };
+    let node = TestNode::start(Args {
+        rpclisten_http: Some(address.parse().unwrap()),
+        rpc_credentials: vec![credential],
+        ..Default::default()
+    });
+
+    // A request announcing the largest body is refused out of its head, the connection being closed with the body unread
+    let mut stream = eventually(|| TcpStream::connect(&address)).await;
+    stream.write_all(format!("POST / HTTP/1.1\r\nContent-Length: {MAX_BODY_SIZE}\r\n\r\n").as_bytes()).await.unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 401"), "unexpected response {response}");
+
+    let body = r#"{"jsonrpc":"2.0","id":1,"method":"getBlockDagInfo","params":{}}"#;
+    let request = format!(
+        "POST / HTTP/1.1\r\nAuthorization: Bearer secret\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{body}",
+        body.len()
+    );
+    let mut stream = TcpStream::connect(&address).await.unwrap();
+    stream.write_all(request.as_bytes()).await.unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 200"), "unexpected response {response}");
+
+    node.shutdown();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_replay() {
+    let node = TestNode::start(Args::default());
+    let rpc = node.rpc();
+    let blocks = mine_blocks(rpc, &pay_address(), 5).await;
+    let hashes = blocks.iter().map(|block| block.hash()).collect::<Vec<_>>();
+
+    // The chain blocks added after the resume position are replayed at once, along with the transactions they accepted
+    let scope: Scope = VirtualChainChangedScope::new(true).into();
+    let replayed = rpc.replay(&scope, &ResumeToken::SinkHash(hashes[0])).await.unwrap();
+    let [Notification::VirtualChainChanged(notification)] = replayed.as_slice() else { panic!("unexpected replay {replayed:?}") };
+    assert!(notification.removed_chain_block_hashes.is_empty());
+    assert_eq!(*notification.added_chain_block_hashes, hashes[1..]);
+    let accepting_block_hashes = notification.accepted_transaction_ids.iter().map(|x| x.accepting_block_hash).collect::<Vec<_>>();
+    assert_eq!(accepting_block_hashes, hashes[1..]);
+
+    let replayed = rpc.replay(&scope, &ResumeToken::DaaScore(blocks[1].header.daa_score)).await.unwrap();
+    let [Notification::VirtualChainChanged(notification)] = replayed.as_slice() else { panic!("unexpected replay {replayed:?}") };
+    assert_eq!(*notification.added_chain_block_hashes, hashes[2..]);
+    assert!(rpc.replay(&scope, &ResumeToken::SinkHash(hashes[4])).await.unwrap().is_empty());
+
+    // The UTXO changes of the subscribed addresses are replayed by chain block, here the coinbase outputs they accepted
+    let scope: Scope = UtxosChangedScope::new(vec![pay_address()]).into();
+    let replayed = rpc.replay(&scope, &ResumeToken::SinkHash(hashes[0])).await.unwrap();
+    assert!(replayed.len() >= 2, "unexpected replay {replayed:?}");
+    for notification in replayed.iter() {
+        let Notification::UtxosChanged(notification) = notification else { panic!("unexpected notification {notification:?}") };
+        assert!(notification.removed.is_empty());
+        assert!(!notification.added.is_empty());
+        assert!(notification.added.iter().all(|x| x.address == Some(pay_address()) && x.utxo_entry.is_coinbase));
+    }
+
+    // Gaps are reported when the resume position is unknown or the scope cannot be replayed
+    let scope: Scope = VirtualChainChangedScope::new(false).into();
+    assert!(matches!(rpc.replay(&scope, &ResumeToken::SinkHash(Hash::from_u64_word(1))).await, Err(NotifyError::ResumeGap(_, _))));
+    let scope: Scope = UtxosChangedScope::new(vec![]).into();
+    assert!(matches!(rpc.replay(&scope, &ResumeToken::SinkHash(hashes[0])).await, Err(NotifyError::ResumeGap(_, _))));
+
+    node.shutdown();
+}
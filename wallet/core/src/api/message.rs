@@ -690,17 +690,18 @@ pub struct AccountsEstimateResponse {
 pub struct FeeRateEstimateBucket {
     feerate: f64,
     seconds: f64,
+    confidence: Option<f64>,
 }
 
 impl From<RpcFeerateBucket> for FeeRateEstimateBucket {
     fn from(bucket: RpcFeerateBucket) -> Self {
-        Self { feerate: bucket.feerate, seconds: bucket.estimated_seconds }
+        Self { feerate: bucket.feerate, seconds: bucket.estimated_seconds, confidence: bucket.confidence }
     }
 }
 
 impl From<&RpcFeerateBucket> for FeeRateEstimateBucket {
     fn from(bucket: &RpcFeerateBucket) -> Self {
-        Self { feerate: bucket.feerate, seconds: bucket.estimated_seconds }
+        Self { feerate: bucket.feerate, seconds: bucket.estimated_seconds, confidence: bucket.confidence }
     }
 }
 
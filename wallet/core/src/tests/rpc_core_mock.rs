@@ -387,6 +387,54 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
+    async fn get_transaction_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetTransactionRequest,
+    ) -> RpcResult<GetTransactionResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_address_history_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetAddressHistoryRequest,
+    ) -> RpcResult<GetAddressHistoryResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn submit_package_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: SubmitPackageRequest,
+    ) -> RpcResult<SubmitPackageResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_virtual_chain_chunk_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetVirtualChainChunkRequest,
+    ) -> RpcResult<GetVirtualChainChunkResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_fee_estimate_history_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetFeeEstimateHistoryRequest,
+    ) -> RpcResult<GetFeeEstimateHistoryResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_transaction_status_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetTransactionStatusRequest,
+    ) -> RpcResult<GetTransactionStatusResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
 